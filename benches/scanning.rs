@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks for the RPC-heavy scanning paths
+//!
+//! Everything runs against [`support::SimulatedChain`], so no network access is
+//! needed and results are comparable between runs:
+//!
+//! ```bash
+//! cargo bench --bench scanning
+//! cargo bench --bench scanning -- daily_window   # one group
+//! ```
+//!
+//! Criterion reports wall-clock regressions as usual. RPC call counts per
+//! iteration are printed at the end of the run, with the change since the
+//! previous run, and saved to `target/criterion/rpc-calls.json`.
+
+mod support;
+
+use std::sync::LazyLock;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockNumber};
+use alloy_rpc_types::Filter;
+use chrono::NaiveDate;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use semioscan::{
+    AdaptiveChunking, BlockWindowCalculator, EventScanner, GasCache, GasCostResult, MaxBlockRange,
+    SemioscanConfig, SemioscanConfigBuilder,
+};
+use tokio::runtime::Runtime;
+
+use support::{RpcCallReport, SimulatedChain, EMITTER};
+
+static REPORT: LazyLock<RpcCallReport> = LazyLock::new(RpcCallReport::default);
+
+/// 2024-01-01T00:00:00Z
+const GENESIS_TIMESTAMP: u64 = 1_704_067_200;
+
+fn runtime() -> Runtime {
+    Runtime::new().expect("tokio runtime")
+}
+
+/// Binary search cost of `get_daily_window` at different chain lengths
+fn daily_window(c: &mut Criterion) {
+    let runtime = runtime();
+    let date = NaiveDate::from_ymd_opt(2024, 6, 1).expect("valid date");
+    let mut group = c.benchmark_group("daily_window");
+
+    // Two years of history at Ethereum-, Base- and 1s-style block times
+    for block_time in [12, 2, 1] {
+        let head = 2 * 365 * 86_400 / block_time;
+        let chain = SimulatedChain::new(GENESIS_TIMESTAMP, block_time, head);
+        let calculator = BlockWindowCalculator::without_cache(chain.provider());
+        let id = BenchmarkId::new("block_time_secs", block_time);
+
+        let before = chain.calls();
+        runtime
+            .block_on(calculator.get_daily_window(NamedChain::Mainnet, date))
+            .expect("simulated window");
+        REPORT.record(format!("daily_window/{id}"), chain.calls() - before);
+
+        group.bench_function(id, |b| {
+            b.to_async(&runtime).iter(|| async {
+                calculator
+                    .get_daily_window(NamedChain::Mainnet, date)
+                    .await
+                    .expect("simulated window")
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Gap detection against caches holding thousands of disjoint ranges
+fn gap_calculation(c: &mut Criterion) {
+    let from = Address::repeat_byte(0xaa);
+    let to = Address::repeat_byte(0xbb);
+    let mut group = c.benchmark_group("gap_calculation");
+
+    for cached_ranges in [1_000u64, 5_000, 20_000] {
+        let mut cache = GasCache::default();
+        // Cache every other 100-block range so the request below has one gap per entry
+        for i in 0..cached_ranges {
+            let start = i * 200;
+            cache.insert(
+                from,
+                to,
+                start,
+                start + 99,
+                GasCostResult::new(NamedChain::Mainnet, from, to),
+            );
+        }
+        let end = cached_ranges * 200;
+
+        group.bench_with_input(
+            BenchmarkId::new("cached_ranges", cached_ranges),
+            &end,
+            |b, &end| b.iter(|| cache.calculate_gaps(NamedChain::Mainnet, from, to, 0, end)),
+        );
+    }
+
+    group.finish();
+}
+
+/// Sparse chain with a dense burst every 50,000 blocks
+fn bursty_density(block: BlockNumber) -> usize {
+    match block % 50_000 {
+        0..5_000 => 4,
+        _ if block % 250 == 0 => 1,
+        _ => 0,
+    }
+}
+
+/// Throughput of `EventScanner::scan` with fixed and adaptive chunk sizing
+fn chunking(c: &mut Criterion) {
+    const BLOCKS: BlockNumber = 200_000;
+
+    let runtime = runtime();
+    let chain = SimulatedChain::new(GENESIS_TIMESTAMP, 12, BLOCKS).with_log_density(bursty_density);
+    let filter = Filter::new().address(EMITTER);
+
+    let configs = [
+        ("fixed", SemioscanConfig::minimal()),
+        (
+            "adaptive",
+            SemioscanConfigBuilder::new()
+                .adaptive_chunking(AdaptiveChunking {
+                    max_block_range: MaxBlockRange::GENEROUS,
+                    ..AdaptiveChunking::default()
+                })
+                .build(),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("chunking");
+    group.throughput(Throughput::Elements(BLOCKS));
+    group.sample_size(10);
+
+    for (name, config) in configs {
+        let scanner = EventScanner::new(chain.provider(), config);
+
+        let before = chain.calls();
+        runtime
+            .block_on(scanner.scan(NamedChain::Mainnet, filter.clone(), 0, BLOCKS))
+            .expect("simulated scan");
+        REPORT.record(format!("chunking/{name}"), chain.calls() - before);
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async {
+                scanner
+                    .scan(NamedChain::Mainnet, filter.clone(), 0, BLOCKS)
+                    .await
+                    .expect("simulated scan")
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn rpc_call_report(_c: &mut Criterion) {
+    REPORT.finish();
+}
+
+criterion_group!(
+    benches,
+    daily_window,
+    gap_calculation,
+    chunking,
+    rpc_call_report
+);
+criterion_main!(benches);
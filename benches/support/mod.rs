@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Simulated JSON-RPC provider for semioscan benchmarks
+//!
+//! [`SimulatedChain`] answers `eth_blockNumber`, `eth_getBlockByNumber` and
+//! `eth_getLogs` from a deterministic model of a chain, so benchmarks run
+//! without network access and every run issues the same requests. It counts
+//! every request it serves, which is what the RPC-call report is built from.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use alloy_json_rpc as j;
+use alloy_primitives::{Address, BlockNumber, Bytes, LogData, B256, U64};
+use alloy_provider::{ProviderBuilder, RootProvider};
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types::{Block, Header, Log};
+use alloy_transport::{TransportError, TransportFut};
+use serde_json::Value;
+
+/// Address every simulated log is emitted from
+pub const EMITTER: Address = Address::repeat_byte(0x11);
+
+/// Deterministic chain model served over a mock transport
+#[derive(Clone)]
+pub struct SimulatedChain {
+    genesis_timestamp: u64,
+    block_time: u64,
+    head: BlockNumber,
+    result_limit: usize,
+    logs_per_block: fn(BlockNumber) -> usize,
+    calls: Arc<AtomicU64>,
+}
+
+impl SimulatedChain {
+    /// A chain producing a block every `block_time` seconds from `genesis_timestamp`
+    pub fn new(genesis_timestamp: u64, block_time: u64, head: BlockNumber) -> Self {
+        Self {
+            genesis_timestamp,
+            block_time,
+            head,
+            result_limit: 10_000,
+            logs_per_block: |_| 0,
+            calls: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Number of logs `eth_getLogs` returns for each block
+    pub fn with_log_density(mut self, logs_per_block: fn(BlockNumber) -> usize) -> Self {
+        self.logs_per_block = logs_per_block;
+        self
+    }
+
+    /// Provider backed by this chain
+    pub fn provider(&self) -> RootProvider {
+        ProviderBuilder::default().connect_client(RpcClient::new(self.clone(), true))
+    }
+
+    /// Requests served so far
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    fn respond(&self, request: &j::SerializedRequest) -> j::ResponsePayload {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+
+        let params: Value = request
+            .params()
+            .and_then(|params| serde_json::from_str(params.get()).ok())
+            .unwrap_or(Value::Null);
+
+        match request.method() {
+            "eth_blockNumber" => success(&U64::from(self.head)),
+            "eth_getBlockByNumber" => {
+                let number = parse_block(&params[0]).unwrap_or(self.head).min(self.head);
+                success(&self.block(number))
+            }
+            "eth_getLogs" => {
+                let from = parse_block(&params[0]["fromBlock"]).unwrap_or(0);
+                let to = parse_block(&params[0]["toBlock"])
+                    .unwrap_or(self.head)
+                    .min(self.head);
+                let count: usize = (from..=to).map(self.logs_per_block).sum();
+                if count > self.result_limit {
+                    return j::ResponsePayload::internal_error_message(
+                        format!("query returned more than {} results", self.result_limit).into(),
+                    );
+                }
+                success(&self.logs(from, to))
+            }
+            method => j::ResponsePayload::internal_error_message(
+                format!("method {method} is not simulated").into(),
+            ),
+        }
+    }
+
+    fn block(&self, number: BlockNumber) -> Block {
+        Block::empty(Header::new(alloy_consensus::Header {
+            number,
+            timestamp: self.genesis_timestamp + number * self.block_time,
+            ..Default::default()
+        }))
+    }
+
+    fn logs(&self, from: BlockNumber, to: BlockNumber) -> Vec<Log> {
+        let mut logs = Vec::new();
+        for block_number in from..=to {
+            for index in 0..(self.logs_per_block)(block_number) {
+                logs.push(Log {
+                    inner: alloy_primitives::Log {
+                        address: EMITTER,
+                        data: LogData::new_unchecked(vec![B256::ZERO], Bytes::new()),
+                    },
+                    block_number: Some(block_number),
+                    transaction_hash: Some(B256::with_last_byte(index as u8)),
+                    log_index: Some(index as u64),
+                    ..Default::default()
+                });
+            }
+        }
+        logs
+    }
+
+    fn handle(&self, request: j::RequestPacket) -> j::ResponsePacket {
+        let respond = |request: j::SerializedRequest| j::Response {
+            id: request.id().clone(),
+            payload: self.respond(&request),
+        };
+        match request {
+            j::RequestPacket::Single(request) => j::ResponsePacket::Single(respond(request)),
+            j::RequestPacket::Batch(requests) => {
+                j::ResponsePacket::Batch(requests.into_iter().map(respond).collect())
+            }
+        }
+    }
+}
+
+impl tower::Service<j::RequestPacket> for SimulatedChain {
+    type Response = j::ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: j::RequestPacket) -> Self::Future {
+        let response = self.handle(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+fn success<R: serde::Serialize>(response: &R) -> j::ResponsePayload {
+    let serialized = serde_json::to_string(response).expect("response should serialize");
+    j::ResponsePayload::Success(
+        serde_json::value::RawValue::from_string(serialized).expect("response should be JSON"),
+    )
+}
+
+fn parse_block(value: &Value) -> Option<BlockNumber> {
+    let hex = value.as_str()?.strip_prefix("0x")?;
+    BlockNumber::from_str_radix(hex, 16).ok()
+}
+
+/// RPC call counts per benchmark, compared against the previous run
+///
+/// Criterion only tracks wall-clock time, so call counts are collected here
+/// and written next to criterion's own results. Each report prints the change
+/// from the last saved run, which makes an extra probe or chunk visible even
+/// when it is too cheap against the simulated provider to move the timings.
+#[derive(Default)]
+pub struct RpcCallReport {
+    counts: Mutex<BTreeMap<String, u64>>,
+}
+
+impl RpcCallReport {
+    /// Record the number of calls one iteration of `benchmark` made
+    pub fn record(&self, benchmark: impl Into<String>, calls: u64) {
+        self.counts
+            .lock()
+            .expect("report lock")
+            .insert(benchmark.into(), calls);
+    }
+
+    /// Print the recorded counts with their change since the last run, then save them
+    pub fn finish(&self) {
+        let path = report_path();
+        let mut saved: BTreeMap<String, u64> = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        println!("\nRPC calls per iteration:");
+        for (benchmark, calls) in self.counts.lock().expect("report lock").iter() {
+            let change = match saved.insert(benchmark.clone(), *calls) {
+                Some(previous) if previous != *calls => {
+                    format!(" ({:+} since last run)", *calls as i64 - previous as i64)
+                }
+                Some(_) => " (unchanged)".to_string(),
+                None => String::new(),
+            };
+            println!("  {benchmark:<48} {calls:>6}{change}");
+        }
+
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_vec_pretty(&saved) {
+            let _ = std::fs::write(&path, json);
+        }
+    }
+}
+
+fn report_path() -> PathBuf {
+    std::env::var_os("CRITERION_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target/criterion"))
+        .join("rpc-calls.json")
+}
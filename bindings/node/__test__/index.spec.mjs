@@ -0,0 +1,50 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// Smoke tests for the built bindings. None of them reach the RPC endpoint:
+// constructors don't connect, and every call below fails on its arguments
+// before a request is sent.
+
+import assert from 'node:assert/strict'
+import { createRequire } from 'node:module'
+import { test } from 'node:test'
+
+const require = createRequire(import.meta.url)
+const { BlockWindowCalculator, CombinedCalculator } = require('../index.js')
+
+const RPC_URL = 'http://localhost:8545'
+const BASE = 8453
+const ADDRESS = '0x1111111111111111111111111111111111111111'
+
+test('exports the calculator classes', () => {
+  assert.equal(typeof BlockWindowCalculator, 'function')
+  assert.equal(typeof CombinedCalculator, 'function')
+})
+
+test('constructors reject unsupported chain IDs', () => {
+  assert.throws(() => new BlockWindowCalculator(RPC_URL, -1), /unsupported chain ID -1/)
+  assert.throws(() => new CombinedCalculator(RPC_URL, -1), /unsupported chain ID -1/)
+})
+
+test('constructors reject invalid endpoint URLs', () => {
+  assert.throws(() => new BlockWindowCalculator('not a url', BASE))
+  assert.throws(() => new CombinedCalculator('not a url', BASE))
+})
+
+test('getDailyWindow rejects malformed dates', async () => {
+  const windows = new BlockWindowCalculator(RPC_URL, BASE)
+  await assert.rejects(windows.getDailyWindow('2025-13-01'), /invalid date '2025-13-01'/)
+})
+
+test('calculateCombinedData rejects malformed addresses and blocks', async () => {
+  const combined = new CombinedCalculator(RPC_URL, BASE)
+  await assert.rejects(
+    combined.calculateCombinedData('0x1234', ADDRESS, ADDRESS, 1, 2),
+    /invalid address '0x1234'/,
+  )
+  await assert.rejects(
+    combined.calculateCombinedData(ADDRESS, ADDRESS, ADDRESS, -1, 2),
+    /invalid block number -1/,
+  )
+})
@@ -0,0 +1,164 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Node.js bindings for semioscan
+//!
+//! Exposes [`BlockWindowCalculator`](semioscan::BlockWindowCalculator) and
+//! [`CombinedCalculator`](semioscan::CombinedCalculator) as JavaScript classes
+//! whose async methods resolve to plain objects: the results' serde JSON
+//! form, with addresses, hashes and 256-bit amounts as strings.
+//!
+//! ```js
+//! const { BlockWindowCalculator, CombinedCalculator } = require('@semiotic-ai/semioscan')
+//!
+//! const windows = new BlockWindowCalculator('https://base.example.com', 8453)
+//! const window = await windows.getDailyWindow('2025-10-01')
+//!
+//! const combined = new CombinedCalculator('https://base.example.com', 8453)
+//! const data = await combined.calculateCombinedData(from, to, token, window.start_block, window.end_block)
+//! ```
+
+use alloy_chains::NamedChain;
+use alloy_network::Ethereum;
+use alloy_primitives::{Address, BlockNumber};
+use alloy_provider::RootProvider;
+use chrono::NaiveDate;
+use napi::bindgen_prelude::within_runtime_if_available;
+use napi::{Error, Result};
+use napi_derive::napi;
+use op_alloy_network::Optimism;
+use semioscan::provider::{
+    create_typed_http_provider, network_type_for_chain, NetworkType, ProviderConfig,
+};
+use semioscan::{MemoryCache, SemioscanConfig};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Maps UTC dates to block ranges, caching results in memory
+#[napi(js_name = "BlockWindowCalculator")]
+pub struct JsBlockWindowCalculator {
+    chain: NamedChain,
+    inner: semioscan::BlockWindowCalculator<RootProvider<Ethereum>>,
+}
+
+#[napi]
+impl JsBlockWindowCalculator {
+    /// Connect to `rpcUrl`, an endpoint for chain `chainId`
+    #[napi(constructor)]
+    pub fn new(rpc_url: String, chain_id: i64) -> Result<Self> {
+        let chain = chain_from_id(chain_id)?;
+        let provider = connect::<Ethereum>(rpc_url)?;
+        Ok(Self {
+            chain,
+            inner: semioscan::BlockWindowCalculator::new(provider, Box::new(MemoryCache::new())),
+        })
+    }
+
+    /// Block range produced on `date` (`YYYY-MM-DD`, UTC)
+    #[napi]
+    pub async fn get_daily_window(&self, date: String) -> Result<Value> {
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|e| Error::from_reason(format!("invalid date '{date}': {e}")))?;
+        let window = self
+            .inner
+            .get_daily_window(self.chain, date)
+            .await
+            .map_err(reason)?;
+        to_json(&window)
+    }
+}
+
+/// Network-typed combined calculator for one chain
+enum NetworkCombined {
+    Ethereum(semioscan::CombinedCalculator<Ethereum, RootProvider<Ethereum>>),
+    Optimism(semioscan::CombinedCalculator<Optimism, RootProvider<Optimism>>),
+}
+
+/// Transfer amounts and gas costs between two addresses
+///
+/// Picks the Ethereum or OP-stack receipt format from the chain, so L2 results
+/// include L1 data fees.
+#[napi(js_name = "CombinedCalculator")]
+pub struct JsCombinedCalculator {
+    chain: NamedChain,
+    inner: NetworkCombined,
+}
+
+#[napi]
+impl JsCombinedCalculator {
+    /// Connect to `rpcUrl`, an endpoint for chain `chainId`
+    #[napi(constructor)]
+    pub fn new(rpc_url: String, chain_id: i64) -> Result<Self> {
+        let chain = chain_from_id(chain_id)?;
+        let config = SemioscanConfig::default();
+        let inner = match network_type_for_chain(chain) {
+            NetworkType::Ethereum => NetworkCombined::Ethereum(
+                semioscan::CombinedCalculator::with_config(connect(rpc_url)?, config),
+            ),
+            NetworkType::Optimism => NetworkCombined::Optimism(
+                semioscan::CombinedCalculator::with_config(connect(rpc_url)?, config),
+            ),
+        };
+        Ok(Self { chain, inner })
+    }
+
+    /// Transfers of `token` from `from` to `to` in `startBlock..=endBlock`,
+    /// with the gas each transaction cost
+    #[napi]
+    pub async fn calculate_combined_data(
+        &self,
+        from: String,
+        to: String,
+        token: String,
+        start_block: i64,
+        end_block: i64,
+    ) -> Result<Value> {
+        let query = semioscan::CombinedQuery {
+            chain: self.chain,
+            from_address: parse_address(&from)?.into(),
+            to_address: parse_address(&to)?.into(),
+            token_address: parse_address(&token)?.into(),
+            from_block: block_number(start_block)?,
+            to_block: block_number(end_block)?,
+        };
+        let result = match &self.inner {
+            NetworkCombined::Ethereum(calculator) => calculator.calculate(&query).await,
+            NetworkCombined::Optimism(calculator) => calculator.calculate(&query).await,
+        }
+        .map_err(reason)?;
+        to_json(&result)
+    }
+}
+
+fn connect<N: alloy_network::Network>(rpc_url: String) -> Result<RootProvider<N>> {
+    // Transport layers may spawn onto the runtime they are built in
+    within_runtime_if_available(|| create_typed_http_provider::<N>(ProviderConfig::new(rpc_url)))
+        .map_err(reason)
+}
+
+fn chain_from_id(chain_id: i64) -> Result<NamedChain> {
+    u64::try_from(chain_id)
+        .ok()
+        .and_then(|id| NamedChain::try_from(id).ok())
+        .ok_or_else(|| Error::from_reason(format!("unsupported chain ID {chain_id}")))
+}
+
+fn block_number(block: i64) -> Result<BlockNumber> {
+    BlockNumber::try_from(block)
+        .map_err(|_| Error::from_reason(format!("invalid block number {block}")))
+}
+
+fn parse_address(address: &str) -> Result<Address> {
+    address
+        .parse()
+        .map_err(|e| Error::from_reason(format!("invalid address '{address}': {e}")))
+}
+
+fn reason(error: impl std::fmt::Display) -> Error {
+    Error::from_reason(error.to_string())
+}
+
+fn to_json(value: &impl Serialize) -> Result<Value> {
+    serde_json::to_value(value).map_err(reason)
+}
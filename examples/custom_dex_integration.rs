@@ -27,10 +27,23 @@
 /// 3. **Optional**: Implement `should_include_swap()` for custom filtering
 /// 4. **Use with PriceCalculator** to extract prices from blockchain
 ///
+/// # Configuration
+///
+/// Price sources are configured through a builder rather than a growing list
+/// of constructor arguments. The builder resolves the contract to watch from
+/// the chain (with an explicit override), and collects the filters applied in
+/// `should_include_swap()`: a sender allowlist and per-token minimum amounts.
+/// `build()` fails with a typed error when nothing is known for the chain, so
+/// misconfiguration surfaces before the first RPC call. Copy this shape for
+/// your own sources.
+///
 /// # Example: Uniswap V3 Integration
 ///
 /// This template shows how to integrate Uniswap V3 as an example.
 /// Follow the same pattern for any DEX (Curve, Balancer, etc.)
+use std::collections::{HashMap, HashSet};
+
+use alloy_chains::NamedChain;
 use alloy_primitives::{address, Address, B256, I256, U256};
 use alloy_rpc_types::Log;
 use alloy_sol_types::{sol, SolEvent};
@@ -75,44 +88,127 @@ pub struct UniswapV3PriceSource {
     token0: Address,
     /// Token1 in the pool (higher address)
     token1: Address,
-    /// Optional: Only include swaps from this address
-    allowed_sender: Option<Address>,
+    /// Only include swaps from these senders (empty = all senders)
+    allowed_senders: HashSet<Address>,
+    /// Smallest amount of a token a swap must move to be included
+    min_amounts: HashMap<Address, U256>,
 }
 
-impl UniswapV3PriceSource {
-    /// Create a new Uniswap V3 price source
+/// USDC/WETH 0.05% pools, used when only a chain is given
+///
+/// Returns `(pool, usdc, weth)`.
+fn default_pool(chain: NamedChain) -> Option<(Address, Address, Address)> {
+    match chain {
+        NamedChain::Mainnet => Some((
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640"),
+            address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+        )),
+        NamedChain::Arbitrum => Some((
+            address!("C6962004f452bE9203591991D15f6b388e09E8D0"),
+            address!("af88d065e77c8cC2239327C5EDb3A432268e5831"),
+            address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+        )),
+        NamedChain::Base => Some((
+            address!("d0b53D9277642d899DF5C87A3966A349A798F224"),
+            address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            address!("4200000000000000000000000000000000000006"),
+        )),
+        _ => None,
+    }
+}
+
+/// Why a [`UniswapV3PriceSourceBuilder`] could not build a price source
+#[derive(Debug, thiserror::Error)]
+pub enum PriceSourceConfigError {
+    /// Neither a chain with a known pool nor an explicit pool was given
+    #[error("no pool configured for {0}; set one with `pool()`")]
+    UnknownPool(String),
+}
+
+/// Builder for [`UniswapV3PriceSource`]
+#[derive(Debug, Default)]
+pub struct UniswapV3PriceSourceBuilder {
+    chain: Option<NamedChain>,
+    pool: Option<(Address, Address, Address)>,
+    allowed_senders: HashSet<Address>,
+    min_amounts: HashMap<Address, U256>,
+}
+
+impl UniswapV3PriceSourceBuilder {
+    /// Resolve the pool from the chain's default USDC/WETH pool
+    pub fn chain(mut self, chain: NamedChain) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Watch `pool`, which trades `token_a` against `token_b`, instead of the
+    /// chain's default
     ///
-    /// # Arguments
+    /// The tokens may be given in either order.
+    pub fn pool(mut self, pool: Address, token_a: Address, token_b: Address) -> Self {
+        self.pool = Some((pool, token_a, token_b));
+        self
+    }
+
+    /// Include swaps from `sender`
     ///
-    /// * `pool_address` - The Uniswap V3 pool contract address
-    /// * `token0` - The first token in the pool (lower address)
-    /// * `token1` - The second token in the pool (higher address)
+    /// Once any sender is allowed, swaps from other senders are skipped.
+    pub fn allow_sender(mut self, sender: Address) -> Self {
+        self.allowed_senders.insert(sender);
+        self
+    }
+
+    /// Skip swaps that move less than `amount` of `token`, in either direction
     ///
-    /// # Example
+    /// Dust swaps have the most extreme prices, so a floor on the stablecoin
+    /// side keeps them out of averages.
+    pub fn min_amount(mut self, token: Address, amount: U256) -> Self {
+        self.min_amounts.insert(token, amount);
+        self
+    }
+
+    /// Build the price source
     ///
-    /// ```rust,ignore
-    /// // USDC/WETH pool on Ethereum (0.05% fee tier)
-    /// let pool = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640".parse().unwrap();
-    /// let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
-    /// let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+    /// # Errors
     ///
-    /// let price_source = UniswapV3PriceSource::new(pool, usdc, weth);
-    /// ```
-    pub fn new(pool_address: Address, token0: Address, token1: Address) -> Self {
-        Self {
+    /// Returns [`PriceSourceConfigError::UnknownPool`] when no pool was given
+    /// and the chain has no default.
+    pub fn build(self) -> Result<UniswapV3PriceSource, PriceSourceConfigError> {
+        let (pool_address, token_a, token_b) = self
+            .pool
+            .or_else(|| self.chain.and_then(default_pool))
+            .ok_or_else(|| {
+                PriceSourceConfigError::UnknownPool(
+                    self.chain
+                        .map_or_else(|| "unspecified chain".to_string(), |c| c.to_string()),
+                )
+            })?;
+        Ok(UniswapV3PriceSource {
             pool_address,
-            token0,
-            token1,
-            allowed_sender: None,
-        }
+            token0: token_a.min(token_b),
+            token1: token_a.max(token_b),
+            allowed_senders: self.allowed_senders,
+            min_amounts: self.min_amounts,
+        })
     }
+}
 
-    /// Add a sender filter (optional)
+impl UniswapV3PriceSource {
+    /// Start configuring a Uniswap V3 price source
     ///
-    /// When set, only swaps initiated by this address will be included.
-    pub fn with_sender_filter(mut self, sender: Address) -> Self {
-        self.allowed_sender = Some(sender);
-        self
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Base's USDC/WETH pool, counting only swaps routed by `router`
+    /// let price_source = UniswapV3PriceSource::builder()
+    ///     .chain(NamedChain::Base)
+    ///     .allow_sender(router)
+    ///     .min_amount(usdc, U256::from(1_000_000)) // 1 USDC
+    ///     .build()?;
+    /// ```
+    pub fn builder() -> UniswapV3PriceSourceBuilder {
+        UniswapV3PriceSourceBuilder::default()
     }
 
     /// Convert signed amount to unsigned
@@ -198,14 +294,22 @@ impl PriceSource for UniswapV3PriceSource {
         }))
     }
 
-    /// Optional: Filter swaps by sender address
+    /// Optional: Filter swaps by sender and size
     ///
-    /// If allowed_sender is set, only include swaps from that address
+    /// Applies the sender allowlist and per-token minimum amounts from the
+    /// builder
     fn should_include_swap(&self, swap: &SwapData) -> bool {
-        match self.allowed_sender {
-            Some(allowed) => swap.sender == Some(allowed),
-            None => true, // Include all swaps if no filter is set
-        }
+        let sender_allowed = self.allowed_senders.is_empty()
+            || swap
+                .sender
+                .is_some_and(|sender| self.allowed_senders.contains(&sender));
+        let large_enough = [
+            (swap.token_in, swap.token_in_amount),
+            (swap.token_out, swap.token_out_amount),
+        ]
+        .iter()
+        .all(|(token, amount)| self.min_amounts.get(token).is_none_or(|min| amount >= min));
+        sender_allowed && large_enough
     }
 }
 
@@ -224,10 +328,11 @@ fn main() {
     println!("   ├─ Copy event signatures from your DEX contract");
     println!("   └─ Example: UniswapV3Swap event above\n");
 
-    println!("2. Create a Price Source Struct");
+    println!("2. Create a Price Source Struct and Builder");
     println!("   ├─ Store contract address(es)");
     println!("   ├─ Store token addresses (if needed)");
-    println!("   └─ Add optional filters (sender, etc.)\n");
+    println!("   ├─ Resolve defaults per chain in the builder");
+    println!("   └─ Add optional filters (sender allowlist, minimum amounts)\n");
 
     println!("3. Implement PriceSource Trait");
     println!("   ├─ router_address() → contract to monitor");
@@ -240,26 +345,36 @@ fn main() {
     println!("   ├─ Wrap in Box<dyn PriceSource>");
     println!("   └─ Pass to PriceCalculator::with_price_source()\n");
 
-    // Example instantiation
-    let pool = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640"); // USDC/WETH 0.05%
+    // Example instantiation: the pool comes from the chain, filters are opt-in
     let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
-    let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
-
-    let _price_source = UniswapV3PriceSource::new(pool, usdc, weth);
+    let price_source = UniswapV3PriceSource::builder()
+        .chain(NamedChain::Mainnet)
+        .min_amount(usdc, U256::from(1_000_000)) // 1 USDC
+        .build()
+        .expect("Mainnet has a default pool");
 
     println!("Example: Uniswap V3 USDC/WETH Pool");
-    println!("  Pool: {}", pool);
-    println!("  Token0 (USDC): {}", usdc);
-    println!("  Token1 (WETH): {}", weth);
+    println!("  Pool: {}", price_source.router_address());
+    println!("  Token0 (USDC): {}", price_source.token0);
+    println!("  Token1 (WETH): {}", price_source.token1);
     println!("\n  Price source created successfully!\n");
 
+    if let Err(e) = UniswapV3PriceSource::builder()
+        .chain(NamedChain::Sepolia)
+        .build()
+    {
+        println!("Without a default pool, build() fails early: {e}\n");
+    }
+
     println!("=== Real Usage Example ===\n");
     println!("```rust");
     println!("use semioscan::{{PriceCalculator, price::PriceSource}};");
     println!("use alloy_provider::ProviderBuilder;");
     println!();
     println!("// Create your custom price source");
-    println!("let price_source = UniswapV3PriceSource::new(pool, usdc, weth);");
+    println!("let price_source = UniswapV3PriceSource::builder()");
+    println!("    .chain(NamedChain::Mainnet)");
+    println!("    .build()?;");
     println!();
     println!("// Create provider");
     println!("let provider = ProviderBuilder::new()");
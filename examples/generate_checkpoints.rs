@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+/// Generates or refreshes a block checkpoint file for one chain
+///
+/// Writes one checkpoint per `INTERVAL_DAYS` days: the first block of that
+/// UTC day and its timestamp. An existing file at `OUTPUT` is extended from
+/// its last checkpoint, so rerunning the example only adds the days since.
+/// Load the result with `BlockWindowCalculator::load_checkpoints`.
+///
+/// Run with:
+/// ```bash
+/// CHAIN_ID=8453 \
+/// RPC_URL=https://mainnet.base.org \
+/// OUTPUT=checkpoints/base.json \
+/// INTERVAL_DAYS=7 \
+/// cargo run --package semioscan --example generate_checkpoints
+/// ```
+///
+/// `START_DATE` (YYYY-MM-DD) sets the first checkpoint of a new file. It
+/// defaults to the day after the chain's genesis, for chains listed in
+/// `constants::chain_genesis`.
+use alloy_chains::NamedChain;
+use alloy_provider::{Provider, ProviderBuilder};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use semioscan::constants::chain_genesis;
+use semioscan::{BlockCheckpoint, BlockCheckpoints, BlockWindowCalculator, UnixTimestamp};
+use std::env;
+use std::path::Path;
+use tracing::{info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::INFO)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)
+        .context("Failed to set tracing subscriber")?;
+
+    dotenvy::dotenv().ok();
+
+    let chain_id: u64 = env::var("CHAIN_ID")
+        .context("CHAIN_ID environment variable not set")?
+        .parse()
+        .context("CHAIN_ID must be a number")?;
+    let chain = NamedChain::try_from(chain_id).context("CHAIN_ID is not a known chain")?;
+    let rpc_url = env::var("RPC_URL").context("RPC_URL environment variable not set")?;
+    let output = env::var("OUTPUT").unwrap_or_else(|_| format!("checkpoints/{chain}.json"));
+    let interval_days: i64 = env::var("INTERVAL_DAYS")
+        .unwrap_or_else(|_| "7".to_string())
+        .parse()
+        .context("INTERVAL_DAYS must be a number")?;
+    if interval_days < 1 {
+        bail!("INTERVAL_DAYS must be at least 1");
+    }
+    let interval = TimeDelta::days(interval_days);
+
+    // Extend the existing file, or start a new one
+    let mut checkpoints = if Path::new(&output).exists() {
+        let checkpoints = BlockCheckpoints::load(&output)?;
+        if checkpoints.chain() != chain {
+            bail!("{output} holds checkpoints for {}", checkpoints.chain());
+        }
+        checkpoints
+    } else {
+        BlockCheckpoints::new(chain, Vec::new())?
+    };
+
+    let mut date = match checkpoints.last() {
+        Some(last) => date_of(last.timestamp)? + interval,
+        None => match env::var("START_DATE") {
+            Ok(start) => NaiveDate::parse_from_str(&start, "%Y-%m-%d")
+                .context("Failed to parse START_DATE (expected format: YYYY-MM-DD)")?,
+            Err(_) => {
+                let genesis = chain_genesis(chain)
+                    .context("START_DATE must be set for chains without a built-in genesis")?;
+                date_of(genesis.timestamp)? + TimeDelta::days(1)
+            }
+        },
+    };
+    let today = Utc::now().date_naive();
+
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse()?);
+    let calculator = BlockWindowCalculator::with_memory_cache(provider.clone())
+        .with_checkpoints(checkpoints.clone());
+
+    info!(chain = %chain, from = %date, interval_days, output, "Generating checkpoints");
+    let existing = checkpoints.len();
+    while date < today {
+        let window = calculator.get_daily_window(chain, date).await?;
+        let block = provider
+            .get_block_by_number(window.start_block.into())
+            .await?
+            .with_context(|| format!("Block {} not found", window.start_block))?;
+        checkpoints.push(BlockCheckpoint {
+            block: window.start_block,
+            timestamp: UnixTimestamp::from_u64(block.header.timestamp),
+        })?;
+        info!(date = %date, block = window.start_block, "Added checkpoint");
+        date += interval;
+    }
+
+    if let Some(parent) = Path::new(&output).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&output, checkpoints.to_json_string()?)?;
+
+    println!(
+        "Wrote {} checkpoints ({} new) for {chain} to {output}",
+        checkpoints.len(),
+        checkpoints.len() - existing
+    );
+    Ok(())
+}
+
+/// The UTC date of `ts`
+fn date_of(ts: UnixTimestamp) -> Result<NaiveDate> {
+    DateTime::from_timestamp(ts.0, 0)
+        .map(|dt| dt.date_naive())
+        .context("Checkpoint timestamp out of range")
+}
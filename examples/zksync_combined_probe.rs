@@ -13,7 +13,7 @@
 /// 3. repeated permissive raw `eth_getTransactionByHash` lookups into
 ///    `AnyRpcTransaction`
 /// 4. repeated `eth_getTransactionReceipt` lookups
-/// 5. the full `CombinedCalculator::calculate_combined_data_ethereum` path
+/// 5. the full `CombinedCalculator::calculate` path
 ///
 /// It is intended for incident reproduction and provider comparison when logs
 /// succeed but typed transaction enrichment does not.
@@ -47,7 +47,7 @@ use alloy_sol_types::SolEvent;
 use anyhow::{Context, Result};
 use semioscan::{
     create_typed_http_provider, fetch_logs_chunked, CombinedCalculator, CombinedDataResult,
-    ProviderConfig, SemioscanConfig, Transfer,
+    CombinedQuery, ProviderConfig, SemioscanConfig, Transfer,
 };
 use std::{
     borrow::Cow, env, error::Error as StdError, str::FromStr, time::Duration, time::Instant,
@@ -392,17 +392,17 @@ where
 {
     let calculator = CombinedCalculator::new(provider);
     let started = Instant::now();
+    let query = CombinedQuery::builder()
+        .chain(NamedChain::ZkSync)
+        .from(config.from_address)
+        .to(config.to_address)
+        .token(config.token_address)
+        .blocks(config.start_block..=config.end_block)
+        .build()?;
     let result = calculator
-        .calculate_combined_data_ethereum(
-            NamedChain::ZkSync,
-            config.from_address,
-            config.to_address,
-            config.token_address,
-            config.start_block,
-            config.end_block,
-        )
+        .calculate(&query)
         .await
-        .context("calculate_combined_data_ethereum failed")?;
+        .context("CombinedCalculator::calculate failed")?;
 
     println!(
         "  combined retrieval finished in {} ms",
@@ -0,0 +1,326 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Synchronous API for callers without an async runtime
+//!
+//! [`Client`] wraps a [`SemioscanClient`] together with a private
+//! current-thread Tokio runtime and blocks on each request, the way
+//! `reqwest::blocking` wraps the async `reqwest` client. Nothing about Tokio
+//! shows up in its API, so batch jobs and other synchronous code can use
+//! semioscan without setting up a runtime.
+//!
+//! # Panics
+//!
+//! Like `reqwest::blocking`, the blocking client must not be used from inside
+//! an async runtime: its methods panic when called from async code. Use
+//! [`SemioscanClient`] there instead.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::blocking::Client;
+//! use alloy_chains::NamedChain;
+//!
+//! let client = Client::builder()
+//!     .add_chain(NamedChain::Base, "https://base.example.com")
+//!     .build()?;
+//!
+//! let window = client.daily_window(NamedChain::Base, date)?;
+//! let report = client.daily_gas_report(NamedChain::Base, date, from, to, token)?;
+//! println!("{} transactions in {} blocks", report.gas.transaction_count, window.block_count());
+//! ```
+
+use std::future::Future;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::BlockNumber;
+use chrono::NaiveDate;
+use tokio::runtime::Runtime;
+
+use crate::blocks::cache::BlockWindowCache;
+use crate::blocks::window::DailyBlockWindow;
+use crate::client::{DailyGasReport, SemioscanClient, SemioscanClientBuilder};
+use crate::config::SemioscanConfig;
+use crate::errors::{RpcError, SemioscanError};
+use crate::price::{PriceSource, TokenPriceResult};
+use crate::provider::{ChainEndpoint, ProviderConfig};
+use crate::retrieval::{CombinedDataResult, UsdPriceTable};
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
+use crate::types::tokens::{TokenDecimals, TokenMetadata, TokenSet};
+
+/// Why a [`Client`] couldn't be built
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    /// The client's runtime failed to start
+    #[error("Failed to start blocking client runtime: {0}")]
+    Runtime(#[source] std::io::Error),
+
+    /// An endpoint couldn't be set up
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+
+    /// Token metadata couldn't be prefetched
+    #[error(transparent)]
+    Prefetch(#[from] SemioscanError),
+}
+
+/// Blocking counterpart of [`SemioscanClient`]
+pub struct Client {
+    inner: SemioscanClient,
+    runtime: Runtime,
+}
+
+impl Client {
+    /// Start configuring a client
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// The configuration every calculator was built with
+    pub fn config(&self) -> &SemioscanConfig {
+        self.inner.config()
+    }
+
+    /// Chains with a registered endpoint
+    pub fn chains(&self) -> Vec<NamedChain> {
+        self.inner.chains()
+    }
+
+    /// Block range produced on `date` (UTC)
+    ///
+    /// See [`SemioscanClient::daily_window`].
+    pub fn daily_window(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+    ) -> Result<DailyBlockWindow, SemioscanError> {
+        self.block_on(self.inner.daily_window(chain, date))
+    }
+
+    /// Gas spent on `token` transfers from `from` to `to` on `date` (UTC)
+    ///
+    /// See [`SemioscanClient::daily_gas_report`].
+    pub fn daily_gas_report(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+        from: impl Into<SenderAddress>,
+        to: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
+    ) -> Result<DailyGasReport, SemioscanError> {
+        self.block_on(self.inner.daily_gas_report(chain, date, from, to, token))
+    }
+
+    /// Transfer amounts and gas costs for `token` from `from` to `to` on `date` (UTC)
+    ///
+    /// See [`SemioscanClient::daily_combined_data`].
+    pub fn daily_combined_data(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+        from: impl Into<SenderAddress>,
+        to: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
+    ) -> Result<CombinedDataResult, SemioscanError> {
+        self.block_on(self.inner.daily_combined_data(chain, date, from, to, token))
+    }
+
+    /// Price of `token` against `usdc_address` from swaps in
+    /// `start_block..=end_block`
+    ///
+    /// Price sources are DEX-specific, so the caller supplies one. Each call
+    /// builds a fresh [`PriceCalculator`](crate::PriceCalculator), so price
+    /// results are not cached between calls.
+    pub fn price_between_blocks(
+        &self,
+        chain: NamedChain,
+        usdc_address: impl Into<TokenAddress>,
+        price_source: Box<dyn PriceSource>,
+        token: impl Into<TokenAddress>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<TokenPriceResult, SemioscanError> {
+        let mut calculator = self
+            .inner
+            .price_calculator(chain, usdc_address, price_source)?;
+        Ok(self.block_on(calculator.calculate_price_between_blocks(
+            token,
+            start_block,
+            end_block,
+        ))?)
+    }
+
+    /// Price of `token` against `usdc_address` from swaps on `date` (UTC)
+    ///
+    /// Resolves the day's block window, then calls
+    /// [`price_between_blocks`](Self::price_between_blocks).
+    pub fn daily_price(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+        usdc_address: impl Into<TokenAddress>,
+        price_source: Box<dyn PriceSource>,
+        token: impl Into<TokenAddress>,
+    ) -> Result<TokenPriceResult, SemioscanError> {
+        let window = self.daily_window(chain, date)?;
+        self.price_between_blocks(
+            chain,
+            usdc_address,
+            price_source,
+            token,
+            window.start_block,
+            window.end_block,
+        )
+    }
+
+    /// See [`SemioscanClient::token_metadata`]
+    pub fn token_metadata(
+        &self,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+    ) -> Option<&TokenMetadata> {
+        self.inner.token_metadata(chain, token)
+    }
+
+    /// See [`SemioscanClient::token_decimals`]
+    pub fn token_decimals(
+        &self,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+    ) -> Option<TokenDecimals> {
+        self.inner.token_decimals(chain, token)
+    }
+
+    /// See [`SemioscanClient::usd_price_table`]
+    pub fn usd_price_table(
+        &self,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+    ) -> Option<UsdPriceTable> {
+        self.inner.usd_price_table(chain, token)
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+/// Builder for [`Client`], mirroring [`SemioscanClientBuilder`]
+#[derive(Default)]
+pub struct ClientBuilder {
+    inner: SemioscanClientBuilder,
+}
+
+impl ClientBuilder {
+    /// Create a builder with the default configuration and no chains
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configuration shared by every calculator
+    pub fn with_config(mut self, config: SemioscanConfig) -> Self {
+        self.inner = self.inner.with_config(config);
+        self
+    }
+
+    /// Register an RPC endpoint for `chain`
+    pub fn add_chain(mut self, chain: NamedChain, url: impl Into<String>) -> Self {
+        self.inner = self.inner.add_chain(chain, url);
+        self
+    }
+
+    /// Register an endpoint from a [`ChainEndpoint`], keeping its rate limit
+    pub fn add_endpoint(mut self, endpoint: ChainEndpoint) -> Self {
+        self.inner = self.inner.add_endpoint(endpoint);
+        self
+    }
+
+    /// Register an endpoint with full provider settings (rate limits, fallback URLs)
+    pub fn add_chain_with_config(mut self, chain: NamedChain, config: ProviderConfig) -> Self {
+        self.inner = self.inner.add_chain_with_config(chain, config);
+        self
+    }
+
+    /// Cache for daily block windows, shared by every chain
+    pub fn with_window_cache(mut self, cache: impl BlockWindowCache + 'static) -> Self {
+        self.inner = self.inner.with_window_cache(cache);
+        self
+    }
+
+    /// Tokens on `chain` whose metadata [`build_and_prefetch`](Self::build_and_prefetch)
+    /// reads upfront
+    pub fn with_tokens(mut self, chain: NamedChain, tokens: TokenSet) -> Self {
+        self.inner = self.inner.with_tokens(chain, tokens);
+        self
+    }
+
+    /// Start the client's runtime, connect to every registered chain and
+    /// prefetch the metadata of the tokens registered with
+    /// [`with_tokens`](Self::with_tokens)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime can't be started, an endpoint URL is
+    /// invalid, or a token's metadata can't be read or has unexpected
+    /// decimals.
+    pub fn build_and_prefetch(self) -> Result<Client, BuildError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(BuildError::Runtime)?;
+        let inner = runtime.block_on(self.inner.build_and_prefetch())?;
+        Ok(Client { inner, runtime })
+    }
+
+    /// Start the client's runtime and connect to every registered chain
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime can't be started or an endpoint URL is
+    /// invalid. No requests are sent.
+    pub fn build(self) -> Result<Client, BuildError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(BuildError::Runtime)?;
+        // Transport layers may spawn onto the runtime they are built in
+        let inner = {
+            let _runtime = runtime.enter();
+            self.inner.build()?
+        };
+        Ok(Client { inner, runtime })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_without_an_outside_runtime() {
+        let client = Client::builder().build().unwrap();
+
+        let result = client.daily_window(
+            NamedChain::Base,
+            NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(SemioscanError::ChainNotConfigured {
+                chain: NamedChain::Base
+            })
+        ));
+    }
+
+    #[test]
+    fn builder_registers_chains() {
+        let client = Client::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .build()
+            .unwrap();
+
+        assert_eq!(client.chains(), vec![NamedChain::Mainnet]);
+    }
+}
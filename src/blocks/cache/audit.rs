@@ -0,0 +1,381 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Append-only audit log of block window cache mutations
+//!
+//! A [`DiskCache`](super::DiskCache) with an audit log appends one JSON line
+//! per change to its file: every insert (with the [`Provenance`] the writer
+//! recorded), every entry evicted or expired, and every clear. Each line
+//! carries when it happened, the writing process and an optional writer label,
+//! which answers "who or what wrote this cached window" after the fact.
+//!
+//! Any other [`BlockWindowCache`] can be wrapped in an [`AuditedCache`], which
+//! records inserts and clears. Evictions happen inside the wrapped backend, so
+//! only `DiskCache` records those. Setting
+//! [`SemioscanConfig::cache_audit`](crate::SemioscanConfig::cache_audit) wraps
+//! the window cache of a [`SemioscanClient`](crate::SemioscanClient) this way.
+//!
+//! Lines are only ever appended, and a change is logged after the cache
+//! applied it, so the log never records a change that didn't land. Rotating
+//! or archiving the log is left to the operator.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{types::TimestampMillis, BlockWindowCache, CacheKey, CacheStats, Provenance};
+use crate::blocks::window::DailyBlockWindow;
+use crate::errors::BlockWindowError;
+
+/// Kind of change an [`AuditRecord`] describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// An entry was written, replacing any previous entry for its key
+    Insert,
+    /// An entry was removed to stay within the size limit
+    Evict,
+    /// An entry past its TTL was removed by maintenance
+    Expire,
+    /// Every entry was removed
+    Clear,
+}
+
+/// One line of a cache audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the change was saved
+    #[serde(rename = "at")]
+    pub at: TimestampMillis,
+    /// What changed
+    #[serde(rename = "action")]
+    pub action: AuditAction,
+    /// Affected entry; `None` for [`AuditAction::Clear`]
+    #[serde(rename = "key", default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<CacheKey>,
+    /// How an inserted window was computed, when the writer recorded it
+    #[serde(
+        rename = "provenance",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub provenance: Option<Provenance>,
+    /// ID of the process that made the change
+    #[serde(rename = "pid")]
+    pub pid: u32,
+    /// Label of the writer, from [`CacheAuditLog::with_writer`]
+    #[serde(rename = "writer", default, skip_serializing_if = "Option::is_none")]
+    pub writer: Option<String>,
+}
+
+/// JSONL file that cache mutations are appended to
+///
+/// Cheap to clone; clones append to the same file.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{CacheAuditLog, DiskCache};
+///
+/// let cache = DiskCache::new("cache.json")
+///     .with_audit_log(CacheAuditLog::new("cache-audit.jsonl").with_writer("nightly-backfill"));
+///
+/// for record in CacheAuditLog::read("cache-audit.jsonl").await? {
+///     println!("{:?} {:?} by {:?}", record.action, record.key, record.writer);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheAuditLog {
+    path: PathBuf,
+    writer: Option<String>,
+    file: Arc<Mutex<Option<File>>>,
+}
+
+impl CacheAuditLog {
+    /// Log to `path`, which is created on the first change if missing
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            writer: None,
+            file: Arc::default(),
+        }
+    }
+
+    /// Label every record with `writer`, e.g. a job or host name
+    pub fn with_writer(mut self, writer: impl Into<String>) -> Self {
+        self.writer = Some(writer.into());
+        self
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A record of `action` on `key`, stamped now
+    pub(crate) fn record(
+        &self,
+        action: AuditAction,
+        key: Option<CacheKey>,
+        provenance: Option<Provenance>,
+    ) -> AuditRecord {
+        AuditRecord {
+            at: TimestampMillis::now(),
+            action,
+            key,
+            provenance,
+            pid: std::process::id(),
+            writer: self.writer.clone(),
+        }
+    }
+
+    /// Append `records`, one JSON object per line
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be opened or written.
+    pub(crate) async fn append(&self, records: &[AuditRecord]) -> Result<(), BlockWindowError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut lines = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut lines, record)
+                .map_err(BlockWindowError::serialization_error)?;
+            lines.push(b'\n');
+        }
+
+        let io_error = |path: &Path, e: std::io::Error| {
+            BlockWindowError::cache_io_error(
+                format!("Failed to append to audit log '{}'", path.display()),
+                e,
+            )
+        };
+        // File I/O blocks, so it runs off the async workers
+        let (path, file) = (self.path.clone(), Arc::clone(&self.file));
+        tokio::task::spawn_blocking(move || {
+            let mut file = file
+                .lock()
+                .map_err(|e| io_error(&path, std::io::Error::other(e.to_string())))?;
+            if file.is_none() {
+                *file = Some(
+                    OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&path)
+                        .map_err(|e| io_error(&path, e))?,
+                );
+            }
+            let file = file.as_mut().expect("opened above");
+            // One write per batch keeps a batch's lines together when several
+            // processes append to the same file
+            file.write_all(&lines)
+                .and_then(|()| file.flush())
+                .map_err(|e| io_error(&path, e))
+        })
+        .await
+        .map_err(|e| io_error(&self.path, std::io::Error::other(e)))?
+    }
+
+    /// Stamp and append one record per change
+    pub(crate) async fn append_changes(
+        &self,
+        changes: impl IntoIterator<Item = (AuditAction, Option<CacheKey>, Option<Provenance>)>,
+    ) -> Result<(), BlockWindowError> {
+        let records: Vec<_> = changes
+            .into_iter()
+            .map(|(action, key, provenance)| self.record(action, key, provenance))
+            .collect();
+        self.append(&records).await
+    }
+
+    /// Read every record from the log at `path`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be read or a line isn't a valid record.
+    pub async fn read(path: impl AsRef<Path>) -> Result<Vec<AuditRecord>, BlockWindowError> {
+        let path = path.as_ref();
+        let contents = tokio::fs::read_to_string(path).await.map_err(|e| {
+            BlockWindowError::cache_io_error(
+                format!("Failed to read audit log '{}'", path.display()),
+                e,
+            )
+        })?;
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(BlockWindowError::serialization_error))
+            .collect()
+    }
+}
+
+/// Any [`BlockWindowCache`] whose inserts and clears are recorded in a
+/// [`CacheAuditLog`]
+///
+/// Reads and statistics go straight to the wrapped cache. A failure to
+/// append is returned as the operation's error, although the wrapped cache
+/// has already changed. [`DiskCache`](super::DiskCache) should use
+/// [`with_audit_log`](super::DiskCache::with_audit_log) instead, which also
+/// records evictions and expirations.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{AuditedCache, CacheAuditLog, MemoryCache};
+///
+/// let cache = AuditedCache::new(MemoryCache::new(), CacheAuditLog::new("windows-audit.jsonl"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AuditedCache<C> {
+    inner: C,
+    log: CacheAuditLog,
+}
+
+impl<C: BlockWindowCache> AuditedCache<C> {
+    /// Record changes to `inner` in `log`
+    pub fn new(inner: C, log: CacheAuditLog) -> Self {
+        Self { inner, log }
+    }
+
+    /// The wrapped cache
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The log changes are recorded in
+    pub fn log(&self) -> &CacheAuditLog {
+        &self.log
+    }
+}
+
+#[async_trait]
+impl<C: BlockWindowCache> BlockWindowCache for AuditedCache<C> {
+    async fn get(&self, key: &CacheKey) -> Option<DailyBlockWindow> {
+        self.inner.get(key).await
+    }
+
+    async fn try_get(&self, key: &CacheKey) -> Result<Option<DailyBlockWindow>, BlockWindowError> {
+        self.inner.try_get(key).await
+    }
+
+    async fn insert(
+        &self,
+        key: CacheKey,
+        window: DailyBlockWindow,
+    ) -> Result<(), BlockWindowError> {
+        self.inner.insert(key.clone(), window).await?;
+        self.log
+            .append_changes([(AuditAction::Insert, Some(key), None)])
+            .await
+    }
+
+    async fn insert_with_provenance(
+        &self,
+        key: CacheKey,
+        window: DailyBlockWindow,
+        provenance: Provenance,
+    ) -> Result<(), BlockWindowError> {
+        self.inner
+            .insert_with_provenance(key.clone(), window, provenance.clone())
+            .await?;
+        self.log
+            .append_changes([(AuditAction::Insert, Some(key), Some(provenance))])
+            .await
+    }
+
+    async fn insert_many(
+        &self,
+        entries: Vec<(CacheKey, DailyBlockWindow)>,
+    ) -> Result<(), BlockWindowError> {
+        let keys: Vec<_> = entries.iter().map(|(key, _)| key.clone()).collect();
+        self.inner.insert_many(entries).await?;
+        self.log
+            .append_changes(
+                keys.into_iter()
+                    .map(|key| (AuditAction::Insert, Some(key), None)),
+            )
+            .await
+    }
+
+    async fn clear(&self) -> Result<(), BlockWindowError> {
+        self.inner.clear().await?;
+        self.log
+            .append_changes([(AuditAction::Clear, None, None)])
+            .await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        self.inner.stats().await
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::cache::MemoryCache;
+    use crate::blocks::UnixTimestamp;
+    use alloy_chains::NamedChain;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn key(day: u32) -> CacheKey {
+        CacheKey::new(
+            NamedChain::Mainnet,
+            NaiveDate::from_ymd_opt(2025, 10, day).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_audited_cache_records_inserts_and_clears() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.jsonl");
+        let cache = AuditedCache::new(
+            MemoryCache::new(),
+            CacheAuditLog::new(&path).with_writer("test"),
+        );
+        let window =
+            DailyBlockWindow::new(1_000, 2_000, UnixTimestamp(1_000), UnixTimestamp(2_000))
+                .unwrap();
+        let provenance = Provenance::current().with_probes_used(3);
+
+        cache
+            .insert_with_provenance(key(1), window.clone(), provenance.clone())
+            .await
+            .unwrap();
+        cache
+            .insert_many(vec![(key(2), window.clone()), (key(3), window.clone())])
+            .await
+            .unwrap();
+        assert_eq!(cache.get(&key(1)).await, Some(window));
+        cache.clear().await.unwrap();
+
+        let records = CacheAuditLog::read(&path).await.unwrap();
+        let actions: Vec<_> = records
+            .iter()
+            .map(|record| (record.action, record.key.clone()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (AuditAction::Insert, Some(key(1))),
+                (AuditAction::Insert, Some(key(2))),
+                (AuditAction::Insert, Some(key(3))),
+                (AuditAction::Clear, None),
+            ]
+        );
+        assert_eq!(records[0].provenance, Some(provenance));
+        assert!(records
+            .iter()
+            .all(|record| record.writer.as_deref() == Some("test")));
+    }
+}
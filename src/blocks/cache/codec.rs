@@ -0,0 +1,130 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Payload compression for the disk cache
+//!
+//! Compressed files are recognized by their magic bytes, so readers never need
+//! to know which codec wrote a file. Plain JSON always starts with `{` (or
+//! whitespace), which no supported codec produces.
+
+use std::borrow::Cow;
+use std::io;
+
+/// zstd frame magic number
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// gzip member header
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// zstd level used when writing; favors speed since the cache is rewritten often
+#[cfg(feature = "compression")]
+const ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to [`DiskCache`](super::DiskCache) files
+///
+/// Reading is automatic regardless of the configured codec, so a cache can
+/// switch codecs (or turn compression off) without a migration.
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codec {
+    /// Plain JSON
+    #[default]
+    None,
+    /// gzip (DEFLATE)
+    Gzip,
+    /// Zstandard, usually the smaller and faster option
+    Zstd,
+}
+
+/// Compress `json` with `codec`
+#[cfg(feature = "compression")]
+pub(super) fn encode(codec: Codec, json: Vec<u8>) -> io::Result<Vec<u8>> {
+    use std::io::Write;
+
+    match codec {
+        Codec::None => Ok(json),
+        Codec::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&json)?;
+            encoder.finish()
+        }
+        Codec::Zstd => zstd::encode_all(json.as_slice(), ZSTD_LEVEL),
+    }
+}
+
+/// Decompress a cache file, passing plain JSON through unchanged
+pub(super) fn decode(bytes: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    if bytes.starts_with(&ZSTD_MAGIC) {
+        return decode_zstd(bytes).map(Cow::Owned);
+    }
+    if bytes.starts_with(&GZIP_MAGIC) {
+        return decode_gzip(bytes).map(Cow::Owned);
+    }
+    Ok(Cow::Borrowed(bytes))
+}
+
+#[cfg(feature = "compression")]
+fn decode_zstd(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    zstd::decode_all(bytes)
+}
+
+#[cfg(feature = "compression")]
+fn decode_gzip(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut json = Vec::new();
+    flate2::read::GzDecoder::new(bytes).read_to_end(&mut json)?;
+    Ok(json)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decode_zstd(_bytes: &[u8]) -> io::Result<Vec<u8>> {
+    Err(unsupported("zstd"))
+}
+
+#[cfg(not(feature = "compression"))]
+fn decode_gzip(_bytes: &[u8]) -> io::Result<Vec<u8>> {
+    Err(unsupported("gzip"))
+}
+
+#[cfg(not(feature = "compression"))]
+fn unsupported(codec: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("cache file is {codec}-compressed; enable the `compression` feature to read it"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_json_passes_through() {
+        let json = br#"{"version":2,"entries":{}}"#;
+        assert!(matches!(decode(json).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_codecs_round_trip() {
+        let json = br#"{"version":2,"entries":{}}"#.repeat(100);
+
+        for codec in [Codec::None, Codec::Gzip, Codec::Zstd] {
+            let encoded = encode(codec, json.clone()).unwrap();
+            if codec != Codec::None {
+                assert!(encoded.len() < json.len(), "{codec:?} should compress");
+            }
+            assert_eq!(decode(&encoded).unwrap().as_ref(), json.as_slice());
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn test_compressed_files_need_the_feature() {
+        let error = decode(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -9,18 +9,36 @@ use async_trait::async_trait;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs::File;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 use tracing::{debug, info, warn};
 
-use super::{types::TimestampMillis, BlockWindowCache, CacheKey, CacheStats};
+use super::audit::{AuditAction, CacheAuditLog};
+#[cfg(feature = "compression")]
+use super::codec::Codec;
+use super::lock::{self, ExclusiveLock};
+use super::{codec, types::TimestampMillis, BlockWindowCache, CacheKey, CacheStats, Provenance};
 use crate::blocks::window::DailyBlockWindow;
 use crate::errors::BlockWindowError;
 
 /// Current cache format version
-const CACHE_VERSION: u32 = 1;
+///
+/// - Version 3 entries may carry [`Provenance`]
+/// - Version 2 files are written as compact JSON, optionally compressed
+const CACHE_VERSION: u32 = 3;
+
+/// Oldest cache format version that is still read (and upgraded on the next save)
+///
+/// Version 1 files hold pretty-printed, uncompressed JSON. Entries from
+/// versions 1 and 2 load without provenance.
+const MIN_SUPPORTED_VERSION: u32 = 1;
 
 /// Entry in the disk cache with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +48,9 @@ struct CacheEntry {
     /// When this entry was created (for TTL and eviction ordering)
     #[serde(default)]
     created_at: TimestampMillis,
+    /// How the window was computed, when the writer recorded it (version 3+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    provenance: Option<Provenance>,
 }
 
 impl CacheEntry {
@@ -37,6 +58,7 @@ impl CacheEntry {
         Self {
             window,
             created_at: TimestampMillis::now(),
+            provenance: None,
         }
     }
 
@@ -128,6 +150,13 @@ struct DiskCacheConfig {
     max_entries: Option<usize>,
     /// Time-to-live for cache entries
     ttl: Option<Duration>,
+    /// Number of rotated backups kept alongside the cache file
+    backups: usize,
+    /// Compression applied when writing the cache file
+    #[cfg(feature = "compression")]
+    codec: Codec,
+    /// Where changes to the cache file are recorded
+    audit: Option<CacheAuditLog>,
 }
 
 /// Internal state for disk cache
@@ -137,6 +166,45 @@ struct DiskCacheState {
     stats: CacheStats,
 }
 
+/// Work done by one [`DiskCache::run_maintenance`] pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaintenanceReport {
+    /// Entries removed because their TTL had passed
+    pub expired: usize,
+    /// Entries removed to bring the cache within `max_entries`
+    pub evicted: usize,
+    /// Entries left after the pass
+    pub entries: usize,
+    /// Whether the cache file was rewritten
+    pub compacted: bool,
+}
+
+/// Handle to a task started by [`DiskCache::spawn_maintenance`]
+///
+/// The task stops when the handle is dropped or [`stop`](Self::stop) is called.
+#[derive(Debug)]
+pub struct MaintenanceHandle {
+    task: JoinHandle<()>,
+}
+
+impl MaintenanceHandle {
+    /// Stop the maintenance task
+    pub fn stop(self) {
+        // Dropping the handle aborts the task
+    }
+
+    /// Whether the task has stopped
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Drop for MaintenanceHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 /// Disk-based cache with file locking, versioning, and TTL support
 ///
 /// This cache persists block windows to disk as JSON with:
@@ -144,6 +212,10 @@ struct DiskCacheState {
 /// - Cache format versioning for future migrations
 /// - Optional TTL (time-to-live) for automatic expiration
 /// - Optional size limits with oldest-first eviction
+/// - Optional background maintenance that prunes expired entries
+/// - Optional rotated backups used to recover from a corrupted file
+/// - Optional gzip or zstd compression (`compression` feature)
+/// - Optional append-only audit log of every change ([`CacheAuditLog`])
 /// - Path validation and helpful error messages
 ///
 /// # Examples
@@ -170,8 +242,12 @@ struct DiskCacheState {
 ///
 /// # File Locking
 ///
-/// Uses advisory file locking (`fs2` crate) to prevent corruption from
-/// concurrent access. Multiple processes can safely share the same cache file.
+/// Uses advisory file locking to prevent corruption from concurrent access,
+/// falling back to a sentinel file on platforms without file locks.
+/// Multiple processes can safely share the same cache file: every write holds
+/// an exclusive lock on `<path>.lock` while it re-reads the file, applies its
+/// change and writes the result, so concurrent inserts are merged rather than
+/// overwriting each other.
 ///
 /// # Performance
 ///
@@ -182,7 +258,7 @@ struct DiskCacheState {
 pub struct DiskCache {
     path: PathBuf,
     config: DiskCacheConfig,
-    state: Mutex<DiskCacheState>,
+    state: Arc<Mutex<DiskCacheState>>,
 }
 
 impl DiskCache {
@@ -205,7 +281,7 @@ impl DiskCache {
         Self {
             path: path.into(),
             config: DiskCacheConfig::default(),
-            state: Mutex::new(DiskCacheState::default()),
+            state: Arc::new(Mutex::new(DiskCacheState::default())),
         }
     }
 
@@ -226,6 +302,189 @@ impl DiskCache {
         self
     }
 
+    /// Compresses the cache file with `codec` when saving
+    ///
+    /// Reading detects the codec from the file contents, so existing plain
+    /// files stay readable and are compressed on the next save.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{Codec, DiskCache};
+    ///
+    /// let cache = DiskCache::new("cache.json.zst").with_compression(Codec::Zstd);
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn with_compression(mut self, codec: Codec) -> Self {
+        self.config.codec = codec;
+        self
+    }
+
+    /// Returns the provenance recorded for `key`, if the entry has any
+    ///
+    /// Entries written by older versions, or through
+    /// [`insert`](BlockWindowCache::insert) rather than
+    /// [`insert_with_provenance`](BlockWindowCache::insert_with_provenance),
+    /// have none. Expiry is not checked and statistics are not updated.
+    pub async fn provenance(&self, key: &CacheKey) -> Option<Provenance> {
+        let mut state = self.state.lock().await;
+        let mut data = self.load_or_recover(&mut state.stats).await.ok()?;
+        data.entries.remove(key)?.provenance
+    }
+
+    /// Keeps `count` rotated backups of the cache file
+    ///
+    /// Before each save the current file is copied to `<path>.bak.1`, shifting
+    /// older backups up to `<path>.bak.<count>`. If the cache file later fails
+    /// to parse, it is renamed to `<path>.corrupt-<unix millis>` and the most
+    /// recent backup that still parses is restored in its place. Without
+    /// backups a corrupted file is still quarantined, and the cache starts
+    /// empty. Each quarantined file is counted in [`CacheStats::corruptions`].
+    pub fn with_backups(mut self, count: usize) -> Self {
+        self.config.backups = count;
+        self
+    }
+
+    /// Appends a record of every change to `log`
+    ///
+    /// Inserts (with their [`Provenance`]), evictions, expirations removed by
+    /// [`run_maintenance`](Self::run_maintenance) and clears are recorded
+    /// after the cache file is saved. A failure to append is returned as the
+    /// operation's error, although the cache file has already changed.
+    pub fn with_audit_log(mut self, log: CacheAuditLog) -> Self {
+        self.config.audit = Some(log);
+        self
+    }
+
+    /// Records `changes` in the audit log, if there is one
+    async fn audit(
+        &self,
+        changes: impl IntoIterator<Item = (AuditAction, Option<CacheKey>, Option<Provenance>)>,
+    ) -> Result<(), BlockWindowError> {
+        match &self.config.audit {
+            Some(log) => log.append_changes(changes).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Prunes expired entries and enforces `max_entries` in one pass
+    ///
+    /// Expired entries are otherwise only dropped from the results of a lookup,
+    /// never from the file, so a long-running process keeps growing it. This
+    /// removes every expired entry, evicts the oldest entries over the size
+    /// limit, and rewrites the file if anything was removed. The removals are
+    /// added to the `expirations` and `evictions` counters in [`CacheStats`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache file cannot be read or rewritten.
+    pub async fn run_maintenance(&self) -> Result<MaintenanceReport, BlockWindowError> {
+        let mut state = self.state.lock().await;
+        let _update = self.lock_for_update().await?;
+        let mut data = self.load_or_recover(&mut state.stats).await?;
+
+        let ttl = self.config.ttl;
+        let mut expired_keys = Vec::new();
+        data.entries.retain(|key, entry| {
+            let expired = entry.is_expired(ttl);
+            if expired {
+                expired_keys.push(key.clone());
+            }
+            !expired
+        });
+        let expired = expired_keys.len();
+
+        let evicted_keys = match self.config.max_entries {
+            Some(max_entries) => Self::evict_oldest(&mut data, max_entries),
+            None => Vec::new(),
+        };
+        let evicted = evicted_keys.len();
+
+        let compacted = expired + evicted > 0;
+        if compacted {
+            self.save(&data).await?;
+        }
+        self.audit(
+            expired_keys
+                .into_iter()
+                .map(|key| (AuditAction::Expire, Some(key), None))
+                .chain(
+                    evicted_keys
+                        .into_iter()
+                        .map(|key| (AuditAction::Evict, Some(key), None)),
+                ),
+        )
+        .await?;
+
+        state.stats.expirations += expired as u64;
+        state.stats.evictions += evicted as u64;
+        state.stats.entries = data.entries.len();
+
+        let report = MaintenanceReport {
+            expired,
+            evicted,
+            entries: data.entries.len(),
+            compacted,
+        };
+        debug!(
+            path = %self.path.display(),
+            expired,
+            evicted,
+            entries = report.entries,
+            "Ran block window cache maintenance"
+        );
+
+        Ok(report)
+    }
+
+    /// Runs [`run_maintenance`](Self::run_maintenance) every `interval` in the background
+    ///
+    /// The task shares this cache's statistics, so its work shows up in
+    /// [`stats`](BlockWindowCache::stats) even after the cache has been boxed
+    /// into a [`BlockWindowCalculator`](crate::BlockWindowCalculator). The first
+    /// pass runs one `interval` after spawning. Failed passes are logged and
+    /// retried on the next tick.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero or if called outside a tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let cache = DiskCache::new("cache.json").with_ttl(Duration::from_secs(86400));
+    /// let maintenance = cache.spawn_maintenance(Duration::from_secs(3600));
+    /// let calculator = BlockWindowCalculator::new(provider, Box::new(cache));
+    /// // ... maintenance runs hourly until `maintenance` is dropped
+    /// ```
+    pub fn spawn_maintenance(&self, interval: Duration) -> MaintenanceHandle {
+        let cache = Self {
+            path: self.path.clone(),
+            config: self.config.clone(),
+            state: Arc::clone(&self.state),
+        };
+
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let task = tokio::spawn(async move {
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(e) = cache.run_maintenance().await {
+                    warn!(
+                        path = %cache.path.display(),
+                        error = %e,
+                        "Block window cache maintenance failed"
+                    );
+                }
+            }
+        });
+
+        MaintenanceHandle { task }
+    }
+
     /// Validates the cache path and creates parent directory if needed
     ///
     /// This method checks that:
@@ -281,52 +540,206 @@ impl DiskCache {
         Ok(self)
     }
 
+    /// Takes the exclusive write lock shared by every process using this cache
+    ///
+    /// The cache file itself is replaced by rename on each save, so a lock on
+    /// it would not outlive the write; `<path>.lock` is never replaced. The
+    /// lock is released when the returned guard is dropped. See
+    /// [`lock`](super::lock) for platforms without file locks.
+    async fn lock_for_update(&self) -> Result<ExclusiveLock, BlockWindowError> {
+        let lock_path = self.sibling("lock");
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                    BlockWindowError::cache_io_error(
+                        format!(
+                            "Failed to create cache directory '{}': {}",
+                            parent.display(),
+                            e
+                        ),
+                        e,
+                    )
+                })?;
+            }
+        }
+
+        lock::lock_exclusive(&lock_path).await.map_err(|e| {
+            BlockWindowError::cache_io_error(
+                format!(
+                    "Failed to acquire write lock on '{}': {}",
+                    lock_path.display(),
+                    e
+                ),
+                e,
+            )
+        })
+    }
+
     /// Loads cache data from disk with file locking
     async fn load(&self) -> Result<CacheData, BlockWindowError> {
-        if !self.path.exists() {
-            debug!(path = %self.path.display(), "Cache file does not exist, using empty cache");
+        Self::load_from(&self.path).await
+    }
+
+    /// Loads cache data, quarantining the file and restoring a backup if it is corrupt
+    async fn load_or_recover(&self, stats: &mut CacheStats) -> Result<CacheData, BlockWindowError> {
+        match self.load().await {
+            Err(BlockWindowError::SerializationError { source }) => {
+                stats.corruptions += 1;
+                Ok(self.recover(source).await)
+            }
+            result => result,
+        }
+    }
+
+    /// Moves a corrupt cache file aside and restores the newest readable backup
+    async fn recover(&self, error: serde_json::Error) -> CacheData {
+        let quarantine = self.sibling(&format!(
+            "corrupt-{}",
+            chrono::Utc::now().timestamp_millis()
+        ));
+        match tokio::fs::rename(&self.path, &quarantine).await {
+            Ok(()) => warn!(
+                path = %self.path.display(),
+                quarantined = %quarantine.display(),
+                %error,
+                "Cache file is corrupt, moved it aside"
+            ),
+            Err(e) => warn!(
+                path = %self.path.display(),
+                %error,
+                rename_error = %e,
+                "Cache file is corrupt and could not be moved aside"
+            ),
+        }
+
+        for generation in 1..=self.config.backups {
+            let backup = self.backup_path(generation);
+            if !backup.exists() {
+                continue;
+            }
+
+            match Self::load_from(&backup).await {
+                Ok(data) => {
+                    if let Err(e) = tokio::fs::copy(&backup, &self.path).await {
+                        warn!(
+                            backup = %backup.display(),
+                            error = %e,
+                            "Failed to copy cache backup into place"
+                        );
+                    }
+                    warn!(
+                        path = %self.path.display(),
+                        backup = %backup.display(),
+                        entries = data.entries.len(),
+                        "Restored block window cache from backup"
+                    );
+                    return data;
+                }
+                Err(e) => {
+                    warn!(backup = %backup.display(), error = %e, "Skipping unreadable cache backup");
+                }
+            }
+        }
+
+        CacheData::default()
+    }
+
+    /// Copies the current cache file to `.bak.1`, shifting older backups up
+    async fn rotate_backups(&self) {
+        if self.config.backups == 0 || !self.path.exists() {
+            return;
+        }
+
+        for generation in (1..self.config.backups).rev() {
+            let from = self.backup_path(generation);
+            if from.exists() {
+                if let Err(e) = tokio::fs::rename(&from, self.backup_path(generation + 1)).await {
+                    warn!(backup = %from.display(), error = %e, "Failed to rotate cache backup");
+                }
+            }
+        }
+
+        if let Err(e) = tokio::fs::copy(&self.path, self.backup_path(1)).await {
+            warn!(path = %self.path.display(), error = %e, "Failed to back up cache file");
+        }
+    }
+
+    fn backup_path(&self, generation: usize) -> PathBuf {
+        self.sibling(&format!("bak.{generation}"))
+    }
+
+    /// `<path>.<suffix>`, keeping the original extension
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut name = OsString::from(self.path.as_os_str());
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    /// Loads cache data from `path` with file locking
+    async fn load_from(path: &Path) -> Result<CacheData, BlockWindowError> {
+        if !path.exists() {
+            debug!(path = %path.display(), "Cache file does not exist, using empty cache");
             return Ok(CacheData::default());
         }
 
         // Open file and acquire shared lock for reading
-        let file = File::open(&self.path).map_err(|e| {
+        let file = File::open(path).map_err(|e| {
             BlockWindowError::cache_io_error(
                 format!(
                     "Failed to open cache file '{}': {}. Ensure the file is readable.",
-                    self.path.display(),
+                    path.display(),
                     e
                 ),
                 e,
             )
         })?;
 
-        // Acquire shared lock for reading (std lib, requires Rust 1.89+)
-        file.lock_shared().map_err(|e| {
+        // Acquire shared lock for reading, where the platform supports it
+        lock::lock_shared(&file).map_err(|e| {
             BlockWindowError::cache_io_error(
                 format!(
                     "Failed to acquire read lock on cache file '{}': {}",
-                    self.path.display(),
+                    path.display(),
                     e
                 ),
                 e,
             )
         })?;
 
+        let mut bytes = Vec::new();
+        (&file).read_to_end(&mut bytes).map_err(|e| {
+            BlockWindowError::cache_io_error(
+                format!("Failed to read cache file '{}': {}", path.display(), e),
+                e,
+            )
+        })?;
+
+        // Decompress if needed. A payload that fails to decompress is as
+        // damaged as one that fails to parse, so both count as corruption.
+        let json = codec::decode(&bytes).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::Unsupported {
+                BlockWindowError::cache_io_error(path.display().to_string(), e)
+            } else {
+                warn!(path = %path.display(), error = %e, "Failed to decompress cache file");
+                BlockWindowError::serialization_error(serde_json::Error::io(e))
+            }
+        })?;
+
         // Read and parse cache data
-        let data: CacheData = serde_json::from_reader(&file).map_err(|e| {
+        let mut data: CacheData = serde_json::from_slice(&json).map_err(|e| {
             warn!(
-                path = %self.path.display(),
+                path = %path.display(),
                 error = %e,
-                "Failed to parse cache file, using empty cache"
+                "Failed to parse cache file"
             );
-            // Don't fail on parse errors, just use empty cache
             BlockWindowError::serialization_error(e)
         })?;
 
         // Check version compatibility
-        if data.version != CACHE_VERSION {
+        if !(MIN_SUPPORTED_VERSION..=CACHE_VERSION).contains(&data.version) {
             warn!(
-                path = %self.path.display(),
+                path = %path.display(),
                 cached_version = data.version,
                 current_version = CACHE_VERSION,
                 "Cache version mismatch, ignoring cached data"
@@ -339,8 +752,18 @@ impl DiskCache {
         // Unlock by dropping the file
         drop(file);
 
+        if data.version < CACHE_VERSION {
+            debug!(
+                path = %path.display(),
+                cached_version = data.version,
+                current_version = CACHE_VERSION,
+                "Upgrading cache format on next save"
+            );
+            data.version = CACHE_VERSION;
+        }
+
         info!(
-            path = %self.path.display(),
+            path = %path.display(),
             entries = data.entries.len(),
             version = data.version,
             "Loaded block window cache"
@@ -352,8 +775,14 @@ impl DiskCache {
     /// Saves cache data to disk with file locking and atomic write
     async fn save(&self, data: &CacheData) -> Result<(), BlockWindowError> {
         // Serialize to JSON first (before acquiring lock)
-        let json =
-            serde_json::to_vec_pretty(data).map_err(BlockWindowError::serialization_error)?;
+        let json = serde_json::to_vec(data).map_err(BlockWindowError::serialization_error)?;
+        #[cfg(feature = "compression")]
+        let json = codec::encode(self.config.codec, json).map_err(|e| {
+            BlockWindowError::cache_io_error(
+                format!("Failed to compress cache for '{}'", self.path.display()),
+                e,
+            )
+        })?;
 
         // Create parent directory if it doesn't exist
         if let Some(parent) = self.path.parent() {
@@ -371,7 +800,8 @@ impl DiskCache {
             }
         }
 
-        // Write atomically using a temp file
+        // Write atomically using a temp file. Writers hold `lock_for_update`,
+        // so no two processes write the temp file at once.
         let temp_path = self.path.with_extension("tmp");
 
         tokio::fs::write(&temp_path, &json).await.map_err(|e| {
@@ -385,29 +815,7 @@ impl DiskCache {
             )
         })?;
 
-        // Open temp file and acquire exclusive lock
-        let file = File::open(&temp_path).map_err(|e| {
-            BlockWindowError::cache_io_error(
-                format!(
-                    "Failed to open temp cache file '{}': {}",
-                    temp_path.display(),
-                    e
-                ),
-                e,
-            )
-        })?;
-
-        // Acquire exclusive lock for writing (std lib, requires Rust 1.89+)
-        file.lock().map_err(|e| {
-            BlockWindowError::cache_io_error(
-                format!(
-                    "Failed to acquire write lock on cache file '{}': {}",
-                    temp_path.display(),
-                    e
-                ),
-                e,
-            )
-        })?;
+        self.rotate_backups().await;
 
         // Atomically rename temp file to final location
         tokio::fs::rename(&temp_path, &self.path)
@@ -424,9 +832,6 @@ impl DiskCache {
                 )
             })?;
 
-        // Unlock by dropping the file
-        drop(file);
-
         debug!(
             path = %self.path.display(),
             entries = data.entries.len(),
@@ -436,9 +841,67 @@ impl DiskCache {
         Ok(())
     }
 
-    /// Evicts the oldest entries to maintain size limit
-    fn evict_oldest(data: &mut CacheData, max_entries: usize) -> usize {
-        let mut evicted = 0;
+    /// Stores `entry`, evicting the oldest entries over `max_entries`
+    async fn insert_entry(&self, key: CacheKey, entry: CacheEntry) -> Result<(), BlockWindowError> {
+        self.insert_entries(vec![(key, entry)]).await
+    }
+
+    /// Stores `entries` with a single load and save, evicting the oldest
+    /// entries over `max_entries`
+    ///
+    /// The file is re-read under the write lock, so entries other processes
+    /// saved since this one last looked are kept.
+    async fn insert_entries(
+        &self,
+        entries: Vec<(CacheKey, CacheEntry)>,
+    ) -> Result<(), BlockWindowError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().await;
+        let _update = self.lock_for_update().await?;
+
+        // Load the latest cache data under the lock and merge into it
+        let mut data = self
+            .load_or_recover(&mut state.stats)
+            .await
+            .unwrap_or_default();
+
+        // Insert new entries
+        let mut changes = Vec::with_capacity(entries.len());
+        for (key, entry) in entries {
+            debug!(key = %key, "Inserting entry into disk cache");
+            changes.push((
+                AuditAction::Insert,
+                Some(key.clone()),
+                entry.provenance.clone(),
+            ));
+            data.entries.insert(key, entry);
+        }
+
+        // Evict oldest entries if needed
+        if let Some(max_entries) = self.config.max_entries {
+            let evicted = Self::evict_oldest(&mut data, max_entries);
+            state.stats.evictions += evicted.len() as u64;
+            changes.extend(
+                evicted
+                    .into_iter()
+                    .map(|key| (AuditAction::Evict, Some(key), None)),
+            );
+        }
+
+        state.stats.entries = data.entries.len();
+
+        // Save to disk
+        self.save(&data).await?;
+
+        self.audit(changes).await
+    }
+
+    /// Evicts the oldest entries to maintain size limit, returning their keys
+    fn evict_oldest(data: &mut CacheData, max_entries: usize) -> Vec<CacheKey> {
+        let mut evicted = Vec::new();
 
         while data.entries.len() > max_entries {
             // Find oldest entry by created_at timestamp, using cache key as stable tiebreaker
@@ -458,7 +921,7 @@ impl DiskCache {
             if let Some(key) = oldest_key {
                 debug!(key = %key, "Evicting oldest cache entry");
                 data.entries.remove(&key);
-                evicted += 1;
+                evicted.push(key);
             } else {
                 break;
             }
@@ -471,15 +934,21 @@ impl DiskCache {
 #[async_trait]
 impl BlockWindowCache for DiskCache {
     async fn get(&self, key: &CacheKey) -> Option<DailyBlockWindow> {
+        self.try_get(key).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load cache, treating as miss");
+            None
+        })
+    }
+
+    async fn try_get(&self, key: &CacheKey) -> Result<Option<DailyBlockWindow>, BlockWindowError> {
         let mut state = self.state.lock().await;
 
         // Load cache data
-        let data = match self.load().await {
+        let data = match self.load_or_recover(&mut state.stats).await {
             Ok(data) => data,
             Err(e) => {
-                warn!(error = %e, "Failed to load cache, treating as miss");
                 state.stats.misses += 1;
-                return None;
+                return Err(e);
             }
         };
 
@@ -489,16 +958,16 @@ impl BlockWindowCache for DiskCache {
                 debug!(key = %key, "Cache entry expired");
                 state.stats.expirations += 1;
                 state.stats.misses += 1;
-                return None;
+                return Ok(None);
             }
 
             state.stats.hits += 1;
             debug!(key = %key, "Cache hit (disk)");
-            Some(entry.window.clone())
+            Ok(Some(entry.window.clone()))
         } else {
             state.stats.misses += 1;
             debug!(key = %key, "Cache miss (disk)");
-            None
+            Ok(None)
         }
     }
 
@@ -507,33 +976,34 @@ impl BlockWindowCache for DiskCache {
         key: CacheKey,
         window: DailyBlockWindow,
     ) -> Result<(), BlockWindowError> {
-        let mut state = self.state.lock().await;
-
-        // Load existing cache data
-        let mut data = self.load().await.unwrap_or_default();
-
-        // Insert new entry
-        debug!(key = %key, "Inserting entry into disk cache");
-        data.entries.insert(key, CacheEntry::new(window));
-
-        // Evict oldest entries if needed
-        if let Some(max_entries) = self.config.max_entries {
-            let evicted = Self::evict_oldest(&mut data, max_entries);
-            if evicted > 0 {
-                state.stats.evictions += evicted as u64;
-            }
-        }
-
-        state.stats.entries = data.entries.len();
+        self.insert_entry(key, CacheEntry::new(window)).await
+    }
 
-        // Save to disk
-        self.save(&data).await?;
+    async fn insert_with_provenance(
+        &self,
+        key: CacheKey,
+        window: DailyBlockWindow,
+        provenance: Provenance,
+    ) -> Result<(), BlockWindowError> {
+        let mut entry = CacheEntry::new(window);
+        entry.provenance = Some(provenance);
+        self.insert_entry(key, entry).await
+    }
 
-        Ok(())
+    async fn insert_many(
+        &self,
+        entries: Vec<(CacheKey, DailyBlockWindow)>,
+    ) -> Result<(), BlockWindowError> {
+        let entries = entries
+            .into_iter()
+            .map(|(key, window)| (key, CacheEntry::new(window)))
+            .collect();
+        self.insert_entries(entries).await
     }
 
     async fn clear(&self) -> Result<(), BlockWindowError> {
         let mut state = self.state.lock().await;
+        let _update = self.lock_for_update().await?;
 
         debug!(path = %self.path.display(), "Clearing disk cache");
 
@@ -551,15 +1021,20 @@ impl BlockWindowCache for DiskCache {
             })?;
         }
 
+        // Backups would otherwise bring the cleared entries back after a corruption
+        for generation in 1..=self.config.backups {
+            let _ = tokio::fs::remove_file(self.backup_path(generation)).await;
+        }
+
         state.stats.entries = 0;
-        Ok(())
+        self.audit([(AuditAction::Clear, None, None)]).await
     }
 
     async fn stats(&self) -> CacheStats {
         let mut state = self.state.lock().await;
 
         // Update entry count from disk
-        if let Ok(data) = self.load().await {
+        if let Ok(data) = self.load_or_recover(&mut state.stats).await {
             state.stats.entries = data.entries.len();
         }
 
@@ -584,6 +1059,9 @@ mod tests {
             end_block,
             start_ts: crate::blocks::window::UnixTimestamp(1728518400),
             end_ts_exclusive: crate::blocks::window::UnixTimestamp(1728604800),
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         }
     }
 
@@ -757,4 +1235,406 @@ mod tests {
         // Parent directory should exist now
         assert!(cache_path.parent().unwrap().exists());
     }
+
+    #[tokio::test]
+    async fn test_audit_log_records_every_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let audit_path = temp_dir.path().join("audit.jsonl");
+        let cache = DiskCache::new(temp_dir.path().join("cache.json"))
+            .with_max_entries(1)
+            .with_audit_log(CacheAuditLog::new(&audit_path).with_writer("test"));
+
+        let provenance = Provenance::current().with_probes_used(7);
+        cache
+            .insert_with_provenance(
+                create_test_key(1),
+                create_test_window(1000, 2000),
+                provenance.clone(),
+            )
+            .await
+            .unwrap();
+        cache
+            .insert(create_test_key(2), create_test_window(2001, 3000))
+            .await
+            .unwrap();
+        cache.clear().await.unwrap();
+
+        let records = CacheAuditLog::read(&audit_path).await.unwrap();
+        let actions: Vec<_> = records
+            .iter()
+            .map(|record| (record.action, record.key.clone()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (AuditAction::Insert, Some(create_test_key(1))),
+                (AuditAction::Insert, Some(create_test_key(2))),
+                (AuditAction::Evict, Some(create_test_key(1))),
+                (AuditAction::Clear, None),
+            ]
+        );
+        assert_eq!(records[0].provenance, Some(provenance));
+        assert!(records
+            .iter()
+            .all(|record| record.writer.as_deref() == Some("test")
+                && record.pid == std::process::id()));
+    }
+
+    #[tokio::test]
+    async fn test_run_maintenance_prunes_expired_and_excess_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path)
+            .with_ttl(Duration::from_millis(50))
+            .validate()
+            .unwrap();
+
+        for day in 1..=3 {
+            cache
+                .insert(create_test_key(day), create_test_window(1000, 2000))
+                .await
+                .unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cache
+            .insert(create_test_key(4), create_test_window(1000, 2000))
+            .await
+            .unwrap();
+
+        let report = cache.run_maintenance().await.unwrap();
+        assert_eq!(report.expired, 3);
+        assert_eq!(report.entries, 1);
+        assert!(report.compacted);
+
+        let data = cache.load().await.unwrap();
+        assert_eq!(data.entries.len(), 1);
+        assert!(data.entries.contains_key(&create_test_key(4)));
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.expirations, 3);
+        assert_eq!(stats.entries, 1);
+
+        // Nothing left to prune, so the file is not rewritten
+        let report = cache.run_maintenance().await.unwrap();
+        assert_eq!(
+            report,
+            MaintenanceReport {
+                entries: 1,
+                ..MaintenanceReport::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawned_maintenance_runs_until_stopped() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path)
+            .with_ttl(Duration::from_millis(20))
+            .validate()
+            .unwrap();
+
+        cache
+            .insert(create_test_key(1), create_test_window(1000, 2000))
+            .await
+            .unwrap();
+
+        let maintenance = cache.spawn_maintenance(Duration::from_millis(30));
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        assert!(cache.load().await.unwrap().entries.is_empty());
+        assert_eq!(cache.stats().await.expirations, 1);
+
+        maintenance.stop();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_file_is_quarantined_without_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path).validate().unwrap();
+
+        std::fs::write(&cache_path, b"{\"version\": 1, \"entries\": {").unwrap();
+
+        assert!(cache.get(&create_test_key(1)).await.is_none());
+        assert!(!cache_path.exists());
+
+        let quarantined: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("cache.json.corrupt-")
+            })
+            .collect();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(cache.stats().await.corruptions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_file_restores_latest_good_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path)
+            .with_backups(2)
+            .validate()
+            .unwrap();
+
+        for day in 1..=3 {
+            cache
+                .insert(create_test_key(day), create_test_window(1000, 2000))
+                .await
+                .unwrap();
+        }
+
+        // Backups hold the file as it was before the last two saves
+        assert!(cache.sibling("bak.1").exists());
+        assert!(cache.sibling("bak.2").exists());
+        assert!(!cache.sibling("bak.3").exists());
+
+        // Truncate the live file as a crashed writer would
+        let contents = std::fs::read(&cache_path).unwrap();
+        std::fs::write(&cache_path, &contents[..contents.len() / 2]).unwrap();
+
+        // The newest backup predates the third insert
+        assert!(cache.get(&create_test_key(2)).await.is_some());
+        assert!(cache.get(&create_test_key(3)).await.is_none());
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.corruptions, 1);
+        assert_eq!(stats.entries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_v1_file_is_read_and_upgraded_on_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path).validate().unwrap();
+
+        let mut v1 = CacheData {
+            version: 1,
+            ..CacheData::default()
+        };
+        v1.entries.insert(
+            create_test_key(1),
+            CacheEntry::new(create_test_window(1000, 2000)),
+        );
+        std::fs::write(&cache_path, serde_json::to_vec_pretty(&v1).unwrap()).unwrap();
+
+        assert!(cache.get(&create_test_key(1)).await.is_some());
+
+        cache
+            .insert(create_test_key(2), create_test_window(3000, 4000))
+            .await
+            .unwrap();
+        let raw: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&cache_path).unwrap()).unwrap();
+        assert_eq!(raw["version"], CACHE_VERSION);
+        assert_eq!(raw["entries"].as_object().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_compressed_cache_round_trips_and_reads_plain_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+
+        // Written uncompressed first
+        let plain = DiskCache::new(&cache_path).validate().unwrap();
+        plain
+            .insert(create_test_key(1), create_test_window(1000, 2000))
+            .await
+            .unwrap();
+
+        for codec in [Codec::Gzip, Codec::Zstd] {
+            let cache = DiskCache::new(&cache_path)
+                .with_compression(codec)
+                .validate()
+                .unwrap();
+            assert!(cache.get(&create_test_key(1)).await.is_some());
+
+            cache
+                .insert(create_test_key(2), create_test_window(3000, 4000))
+                .await
+                .unwrap();
+            let bytes = std::fs::read(&cache_path).unwrap();
+            assert!(serde_json::from_slice::<serde_json::Value>(&bytes).is_err());
+
+            // Readers don't need to know the codec
+            assert!(plain.get(&create_test_key(2)).await.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provenance_is_persisted_per_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path).validate().unwrap();
+
+        let provenance = Provenance::current()
+            .with_rpc_endpoint("https://rpc.example.com")
+            .with_probes_used(48);
+        cache
+            .insert_with_provenance(
+                create_test_key(1),
+                create_test_window(1000, 2000),
+                provenance.clone(),
+            )
+            .await
+            .unwrap();
+        cache
+            .insert(create_test_key(2), create_test_window(3000, 4000))
+            .await
+            .unwrap();
+
+        // A fresh handle reads it back from disk
+        let reopened = DiskCache::new(&cache_path);
+        assert_eq!(
+            reopened.provenance(&create_test_key(1)).await,
+            Some(provenance)
+        );
+        assert_eq!(reopened.provenance(&create_test_key(2)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_v2_entries_load_without_provenance() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let key = create_test_key(1);
+
+        let v2 = serde_json::json!({
+            "version": 2,
+            "entries": {
+                key.to_string(): {
+                    "window": create_test_window(1000, 2000),
+                    "created_at": 1_728_518_400_000u64,
+                }
+            }
+        });
+        std::fs::write(&cache_path, serde_json::to_vec(&v2).unwrap()).unwrap();
+
+        let cache = DiskCache::new(&cache_path);
+        assert!(cache.get(&key).await.is_some());
+        assert_eq!(cache.provenance(&key).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_try_get_reports_unreadable_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path).validate().unwrap();
+
+        // A directory in place of the file can be opened but not read
+        std::fs::create_dir(&cache_path).unwrap();
+
+        let error = cache.try_get(&create_test_key(1)).await.unwrap_err();
+        assert!(matches!(error, BlockWindowError::CacheIoError { .. }));
+
+        // The lenient lookup treats the same failure as a miss
+        assert!(cache.get(&create_test_key(1)).await.is_none());
+        let stats = cache.stats().await;
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.corruptions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_insert_many_writes_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let cache = DiskCache::new(&cache_path)
+            .with_max_entries(4)
+            .with_backups(1)
+            .validate()
+            .unwrap();
+
+        let entries = (1..=5)
+            .map(|day| {
+                let window = create_test_window(day as u64 * 1000, day as u64 * 2000);
+                (create_test_key(day), window)
+            })
+            .collect();
+        cache.insert_many(entries).await.unwrap();
+
+        // A second save would have rotated the first file into a backup
+        assert!(!cache.backup_path(1).exists());
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 4);
+        assert_eq!(stats.evictions, 1);
+        assert!(cache.get(&create_test_key(1)).await.is_none());
+        assert!(cache.get(&create_test_key(5)).await.is_some());
+    }
+
+    const STRESS_PATH_VAR: &str = "SEMIOSCAN_DISK_CACHE_STRESS_PATH";
+    const STRESS_WORKER_VAR: &str = "SEMIOSCAN_DISK_CACHE_STRESS_WORKER";
+    const STRESS_WORKERS: u64 = 4;
+    const STRESS_INSERTS: u64 = 25;
+
+    fn stress_key(worker: u64, insert: u64) -> CacheKey {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()
+            + chrono::Days::new(worker * STRESS_INSERTS + insert);
+        CacheKey::new(NamedChain::Arbitrum, date)
+    }
+
+    /// Worker half of the multi-process test below; does nothing when run
+    /// directly
+    #[tokio::test]
+    async fn test_disk_cache_stress_worker() {
+        let (Ok(path), Ok(worker)) = (
+            std::env::var(STRESS_PATH_VAR),
+            std::env::var(STRESS_WORKER_VAR),
+        ) else {
+            return;
+        };
+        let worker: u64 = worker.parse().unwrap();
+        let cache = DiskCache::new(path);
+        for insert in 0..STRESS_INSERTS {
+            cache
+                .insert(
+                    stress_key(worker, insert),
+                    create_test_window(insert, insert + 1),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_processes_do_not_lose_inserts() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("cache.json");
+        let test_binary = std::env::current_exe().unwrap();
+
+        // Re-run this test binary as several processes inserting at once
+        let workers: Vec<_> = (0..STRESS_WORKERS)
+            .map(|worker| {
+                std::process::Command::new(&test_binary)
+                    .args([
+                        "--exact",
+                        "blocks::cache::disk::tests::test_disk_cache_stress_worker",
+                    ])
+                    .env(STRESS_PATH_VAR, &cache_path)
+                    .env(STRESS_WORKER_VAR, worker.to_string())
+                    .stdout(std::process::Stdio::null())
+                    .spawn()
+                    .unwrap()
+            })
+            .collect();
+        for mut worker in workers {
+            assert!(worker.wait().unwrap().success());
+        }
+
+        let cache = DiskCache::new(&cache_path);
+        assert_eq!(
+            cache.stats().await.entries as u64,
+            STRESS_WORKERS * STRESS_INSERTS
+        );
+        for worker in 0..STRESS_WORKERS {
+            for insert in 0..STRESS_INSERTS {
+                assert!(cache.get(&stress_key(worker, insert)).await.is_some());
+            }
+        }
+    }
 }
@@ -0,0 +1,333 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Portable file locking for [`DiskCache`](super::DiskCache)
+//!
+//! The standard library's file locks map to `flock` on Unix and `LockFileEx`
+//! on Windows, but fail on platforms and file systems without either: with
+//! [`ErrorKind::Unsupported`], or with `ENOLCK`/`EINVAL` on network and FUSE
+//! file systems. There, the exclusive write lock falls back to a sentinel file
+//! created with `create_new`, retried until the holder removes it, and shared
+//! read locks are skipped: saves replace the cache file by rename, so a reader
+//! always sees a complete file.
+//!
+//! Each sentinel holds a token unique to its holder. A holder only removes a
+//! sentinel still carrying its own token, and a stale sentinel is first
+//! renamed out of the way and checked, so two processes clearing the same
+//! stale sentinel can't delete each other's fresh one.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, warn};
+
+/// Pause between attempts to create the sentinel file
+const SENTINEL_RETRY_DELAY: Duration = Duration::from_millis(25);
+
+/// How long to wait for another process's sentinel before giving up
+const SENTINEL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Age after which a sentinel is assumed to belong to a crashed process
+const SENTINEL_STALE_AFTER: Duration = Duration::from_secs(120);
+
+/// `ENOLCK`: the file system has no lock support (e.g. NFS without `lockd`)
+#[cfg(target_os = "linux")]
+const ENOLCK: i32 = 37;
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+const ENOLCK: i32 = 77;
+
+/// An exclusive lock, released on drop
+#[derive(Debug)]
+pub(super) enum ExclusiveLock {
+    /// An OS lock held on the open lock file
+    Os(File),
+    /// A sentinel file this process created holding `token`, removed on drop
+    Sentinel { path: PathBuf, token: String },
+}
+
+impl Drop for ExclusiveLock {
+    fn drop(&mut self) {
+        if let ExclusiveLock::Sentinel { path, token } = self {
+            // A sentinel held past SENTINEL_STALE_AFTER may have been cleared
+            // and re-created by another process; leave that one alone
+            match std::fs::read_to_string(&*path) {
+                Ok(contents) if contents == *token => {
+                    if let Err(e) = std::fs::remove_file(&*path) {
+                        warn!(sentinel = %path.display(), error = %e, "Failed to remove cache lock sentinel");
+                    }
+                }
+                Ok(_) => {
+                    warn!(sentinel = %path.display(), "Cache lock sentinel was taken over as stale")
+                }
+                Err(e) => {
+                    warn!(sentinel = %path.display(), error = %e, "Failed to read cache lock sentinel")
+                }
+            }
+        }
+    }
+}
+
+/// Take the exclusive lock on `path`, waiting for other holders
+///
+/// Uses an OS lock where supported and a `<path>.held` sentinel otherwise.
+pub(super) async fn lock_exclusive(path: &Path) -> io::Result<ExclusiveLock> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+
+    // Another process may hold the lock for a whole load and save
+    let locked = tokio::task::spawn_blocking(move || file.lock().map(|()| file))
+        .await
+        .map_err(io::Error::other)?;
+    match locked {
+        Ok(file) => Ok(ExclusiveLock::Os(file)),
+        Err(e) if locks_unavailable(&e) => {
+            debug!(path = %path.display(), error = %e, "File locks unavailable, using a sentinel file");
+            acquire_sentinel(&sentinel_path(path), SENTINEL_RETRY_DELAY, SENTINEL_TIMEOUT).await
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Take a shared lock on `file`, or go without where file locks are
+/// unavailable
+pub(super) fn lock_shared(file: &File) -> io::Result<()> {
+    match file.lock_shared() {
+        Err(e) if locks_unavailable(&e) => Ok(()),
+        result => result,
+    }
+}
+
+/// Whether `error` means the platform or file system can't lock files at
+/// all, as opposed to the lock being held or the file being inaccessible
+fn locks_unavailable(error: &io::Error) -> bool {
+    is_enolck(error)
+        || matches!(
+            error.kind(),
+            ErrorKind::Unsupported | ErrorKind::InvalidInput
+        )
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+))]
+fn is_enolck(error: &io::Error) -> bool {
+    error.raw_os_error() == Some(ENOLCK)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+fn is_enolck(_error: &io::Error) -> bool {
+    false
+}
+
+/// A token no other sentinel holder, in this process or another, will use
+fn unique_token() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!(
+        "{pid}-{nanos}-{count}",
+        pid = std::process::id(),
+        count = COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+fn sentinel_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".held");
+    PathBuf::from(name)
+}
+
+/// Create `sentinel`, retrying every `retry_delay` while another process
+/// holds it
+async fn acquire_sentinel(
+    sentinel: &Path,
+    retry_delay: Duration,
+    timeout: Duration,
+) -> io::Result<ExclusiveLock> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(sentinel)
+        {
+            Ok(mut file) => {
+                let token = unique_token();
+                file.write_all(token.as_bytes())?;
+                return Ok(ExclusiveLock::Sentinel {
+                    path: sentinel.to_path_buf(),
+                    token,
+                });
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                if let Some(stale_token) = stale_token(sentinel) {
+                    warn!(sentinel = %sentinel.display(), "Removing stale cache lock sentinel");
+                    remove_stale(sentinel, &stale_token);
+                    continue;
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(io::Error::new(
+                        ErrorKind::TimedOut,
+                        format!(
+                            "timed out waiting for cache lock sentinel '{}'",
+                            sentinel.display()
+                        ),
+                    ));
+                }
+                tokio::time::sleep(retry_delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// The token of `sentinel` if it is old enough to belong to a crashed process
+fn stale_token(sentinel: &Path) -> Option<String> {
+    let stale = std::fs::metadata(sentinel)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > SENTINEL_STALE_AFTER);
+    stale
+        .then(|| std::fs::read_to_string(sentinel).ok())
+        .flatten()
+}
+
+/// Remove `sentinel` if it still holds `stale_token`
+///
+/// The sentinel is renamed to a private path first, which only one process
+/// can do. If what was renamed turns out to be a fresh sentinel created after
+/// the stale one was cleared, it is linked back into place.
+fn remove_stale(sentinel: &Path, stale_token: &str) {
+    let mut claimed = sentinel.as_os_str().to_owned();
+    claimed.push(format!(".stale-{}", unique_token()));
+    let claimed = PathBuf::from(claimed);
+
+    // Another process got there first
+    if std::fs::rename(sentinel, &claimed).is_err() {
+        return;
+    }
+    if std::fs::read_to_string(&claimed).is_ok_and(|contents| contents != stale_token) {
+        if let Err(e) = std::fs::hard_link(&claimed, sentinel) {
+            warn!(sentinel = %sentinel.display(), error = %e, "Failed to restore a live cache lock sentinel");
+        }
+    }
+    let _ = std::fs::remove_file(&claimed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_sentinel_waits_for_the_holder_and_is_removed_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+        let sentinel = temp_dir.path().join("cache.json.lock.held");
+        let delay = Duration::from_millis(5);
+
+        let held = acquire_sentinel(&sentinel, delay, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(sentinel.exists());
+
+        // A second holder times out while the first holds the sentinel
+        let err = acquire_sentinel(&sentinel, delay, Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+
+        drop(held);
+        assert!(!sentinel.exists());
+        let _again = acquire_sentinel(&sentinel, delay, Duration::from_secs(5))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_lock_is_released_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache.json.lock");
+
+        let lock = lock_exclusive(&path).await.unwrap();
+        drop(lock);
+        let _lock = lock_exclusive(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_sentinel_is_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        let sentinel = temp_dir.path().join("cache.json.lock.held");
+        let file = File::create(&sentinel).unwrap();
+        file.set_modified(SystemTime::now() - SENTINEL_STALE_AFTER * 2)
+            .unwrap();
+        drop(file);
+
+        let held = acquire_sentinel(&sentinel, Duration::from_millis(5), Duration::from_secs(5))
+            .await
+            .unwrap();
+        let ExclusiveLock::Sentinel { token, .. } = &held else {
+            panic!("expected a sentinel lock");
+        };
+        assert_eq!(&std::fs::read_to_string(&sentinel).unwrap(), token);
+        // Only the sentinel itself is left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sentinel_taken_over_by_another_holder_is_kept_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+        let sentinel = temp_dir.path().join("cache.json.lock.held");
+
+        let held = acquire_sentinel(&sentinel, Duration::from_millis(5), Duration::from_secs(5))
+            .await
+            .unwrap();
+        std::fs::write(&sentinel, "another holder").unwrap();
+        drop(held);
+
+        assert_eq!(
+            std::fs::read_to_string(&sentinel).unwrap(),
+            "another holder"
+        );
+    }
+
+    #[test]
+    fn test_remove_stale_restores_a_fresh_sentinel() {
+        let temp_dir = TempDir::new().unwrap();
+        let sentinel = temp_dir.path().join("cache.json.lock.held");
+        std::fs::write(&sentinel, "fresh").unwrap();
+
+        remove_stale(&sentinel, "stale");
+
+        assert_eq!(std::fs::read_to_string(&sentinel).unwrap(), "fresh");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_lock_errors_that_fall_back_to_a_sentinel() {
+        assert!(locks_unavailable(&io::Error::from(ErrorKind::Unsupported)));
+        assert!(locks_unavailable(&io::Error::from(ErrorKind::InvalidInput)));
+        #[cfg(target_os = "linux")]
+        assert!(locks_unavailable(&io::Error::from_raw_os_error(ENOLCK)));
+        assert!(!locks_unavailable(&io::Error::from(
+            ErrorKind::PermissionDenied
+        )));
+    }
+}
@@ -136,6 +136,8 @@ impl MemoryCache {
     /// Sets the time-to-live for cache entries
     ///
     /// Entries older than the TTL will be automatically expired when accessed.
+    /// Expired entries are removed on lookup and counted in both
+    /// [`CacheStats::expirations`] and [`CacheStats::misses`].
     pub fn with_ttl(mut self, ttl: Duration) -> Self {
         self.config.ttl = Some(ttl);
         self
@@ -182,8 +184,8 @@ impl BlockWindowCache for MemoryCache {
             if entry.is_expired(self.config.ttl) {
                 debug!(key = %key, "Cache entry expired");
                 state.entries.remove(key);
+                state.stats.entries = state.entries.len();
                 state.stats.expirations += 1;
-                state.stats.misses += 1;
                 (None, false)
             } else {
                 // Update access time with sequence number
@@ -200,12 +202,11 @@ impl BlockWindowCache for MemoryCache {
             state.next_seq = state.next_seq.next();
         }
 
-        // Update stats after releasing the entry borrow
+        // Update stats after releasing the entry borrow. An expired entry counts
+        // as both an expiration (above) and a miss, matching DiskCache.
         if result.is_some() {
             state.stats.hits += 1;
             debug!(key = %key, "Cache hit (memory)");
-        } else if state.entries.contains_key(key) {
-            // Entry existed but was expired (already counted in expirations)
         } else {
             state.stats.misses += 1;
             debug!(key = %key, "Cache miss (memory)");
@@ -267,6 +268,9 @@ mod tests {
             end_block,
             start_ts: crate::blocks::window::UnixTimestamp(1728518400),
             end_ts_exclusive: crate::blocks::window::UnixTimestamp(1728604800),
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         }
     }
 
@@ -357,6 +361,15 @@ mod tests {
         // Stats should show expiration
         let stats = cache.stats().await;
         assert_eq!(stats.expirations, 1);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 0);
+
+        // The expired entry was evicted, so later lookups are plain misses
+        assert!(cache.get(&key).await.is_none());
+        let stats = cache.stats().await;
+        assert_eq!(stats.expirations, 1);
+        assert_eq!(stats.misses, 2);
     }
 
     #[tokio::test]
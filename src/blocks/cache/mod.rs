@@ -6,9 +6,12 @@
 //!
 //! This module provides different caching strategies for storing block window data:
 //!
-//! - [`DiskCache`]: Persistent JSON-based cache with file locking (default)
+//! - [`DiskCache`]: Persistent JSON-based cache with file locking (default),
+//!   optionally recording every change in a [`CacheAuditLog`]
 //! - [`MemoryCache`]: In-memory cache with optional size limits
 //! - [`NoOpCache`]: Disables caching entirely (for testing or specific use cases)
+//! - [`AuditedCache`]: Records the inserts and clears of any other backend in a
+//!   [`CacheAuditLog`] (`disk-cache` feature)
 //!
 //! # Examples
 //!
@@ -39,16 +42,30 @@ use async_trait::async_trait;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::Arc;
 
 use crate::blocks::window::DailyBlockWindow;
 use crate::errors::BlockWindowError;
+use types::TimestampMillis;
 
+#[cfg(feature = "disk-cache")]
+mod audit;
+#[cfg(feature = "disk-cache")]
+mod codec;
+#[cfg(feature = "disk-cache")]
 mod disk;
+#[cfg(feature = "disk-cache")]
+mod lock;
 mod memory;
 mod noop;
 pub mod types;
 
-pub use disk::DiskCache;
+#[cfg(feature = "disk-cache")]
+pub use audit::{AuditAction, AuditRecord, AuditedCache, CacheAuditLog};
+#[cfg(feature = "compression")]
+pub use codec::Codec;
+#[cfg(feature = "disk-cache")]
+pub use disk::{DiskCache, MaintenanceHandle, MaintenanceReport};
 pub use memory::MemoryCache;
 pub use noop::NoOpCache;
 
@@ -85,6 +102,9 @@ pub struct CacheStats {
     pub expirations: u64,
     /// Current number of entries in the cache
     pub entries: usize,
+    /// Number of unreadable cache files that were quarantined
+    #[serde(default)]
+    pub corruptions: u64,
 }
 
 impl CacheStats {
@@ -103,17 +123,110 @@ impl fmt::Display for CacheStats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "hits={}, misses={}, evictions={}, expirations={}, entries={}, hit_rate={:.1}%",
+            "hits={}, misses={}, evictions={}, expirations={}, entries={}, corruptions={}, hit_rate={:.1}%",
             self.hits,
             self.misses,
             self.evictions,
             self.expirations,
             self.entries,
+            self.corruptions,
             self.hit_rate()
         )
     }
 }
 
+/// How a cached block window was computed
+///
+/// Recorded alongside entries by backends that persist it (currently
+/// [`DiskCache`]), to help trace a bad window back to the endpoint and release
+/// that produced it. Every field is optional because older entries, and
+/// callers that don't configure an endpoint, have nothing to record.
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::Provenance;
+///
+/// let provenance = Provenance::current()
+///     .with_rpc_endpoint("https://eth.example.com/v2/secret-key")
+///     .with_probes_used(52);
+///
+/// // Only a hash of the endpoint is stored, so API keys don't leak into the cache
+/// assert_eq!(provenance.rpc_endpoint_hash.as_ref().map(String::len), Some(16));
+/// assert_eq!(provenance.crate_version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Provenance {
+    /// First 8 bytes of the keccak256 hash of the RPC endpoint, hex-encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_endpoint_hash: Option<String>,
+    /// semioscan version that computed the window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crate_version: Option<String>,
+    /// When the window was computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed_at: Option<TimestampMillis>,
+    /// RPC calls made to compute the window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probes_used: Option<u64>,
+}
+
+impl Provenance {
+    /// Provenance for a window computed now by this version of semioscan
+    pub fn current() -> Self {
+        Self {
+            rpc_endpoint_hash: None,
+            crate_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            computed_at: Some(TimestampMillis::now()),
+            probes_used: None,
+        }
+    }
+
+    /// Records a hash of the RPC endpoint the window was computed against
+    pub fn with_rpc_endpoint(mut self, endpoint: impl AsRef<str>) -> Self {
+        self.rpc_endpoint_hash = Some(Self::hash_endpoint(endpoint.as_ref()));
+        self
+    }
+
+    /// Records how many RPC calls computing the window took
+    pub fn with_probes_used(mut self, probes: u64) -> Self {
+        self.probes_used = Some(probes);
+        self
+    }
+
+    /// Hash an endpoint the way [`rpc_endpoint_hash`](Self::rpc_endpoint_hash) stores it
+    pub fn hash_endpoint(endpoint: &str) -> String {
+        let hash = alloy_primitives::keccak256(endpoint.as_bytes());
+        alloy_primitives::hex::encode(&hash[..8])
+    }
+}
+
+/// How [`BlockWindowCalculator`](crate::BlockWindowCalculator) handles cache failures
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{BlockWindowCalculator, CachePolicy};
+///
+/// // Fail the call instead of silently recomputing when the cache is unreadable
+/// let calculator = BlockWindowCalculator::with_disk_cache(provider, "cache.json")?
+///     .with_cache_policy(CachePolicy::Strict);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Treat read failures as misses and ignore write failures (default)
+    ///
+    /// Failures are logged, and the window is computed over RPC as if it had
+    /// never been cached.
+    #[default]
+    BestEffort,
+    /// Return read and write failures as [`BlockWindowError`]s
+    ///
+    /// For deployments where a broken cache should be noticed rather than
+    /// quietly turning every lookup into a full binary search.
+    Strict,
+}
+
 /// Trait for block window cache backends
 ///
 /// Implementations provide different storage strategies for caching block windows.
@@ -128,6 +241,8 @@ impl fmt::Display for CacheStats {
 ///
 /// Cache operations should not fail the entire operation. If a cache read/write fails,
 /// implementations should log the error and continue (treating failures as cache misses).
+/// Backends whose reads can fail should also override [`try_get`](Self::try_get) so
+/// that [`CachePolicy::Strict`] can surface those failures.
 #[async_trait]
 pub trait BlockWindowCache: Send + Sync {
     /// Retrieves a cached block window for the given key
@@ -138,6 +253,15 @@ pub trait BlockWindowCache: Send + Sync {
     /// - A cache read error occurred (logged internally)
     async fn get(&self, key: &CacheKey) -> Option<DailyBlockWindow>;
 
+    /// Retrieves a cached block window, reporting read failures instead of
+    /// treating them as misses
+    ///
+    /// Used under [`CachePolicy::Strict`]. The default calls
+    /// [`get`](Self::get), which suits backends whose reads cannot fail.
+    async fn try_get(&self, key: &CacheKey) -> Result<Option<DailyBlockWindow>, BlockWindowError> {
+        Ok(self.get(key).await)
+    }
+
     /// Inserts a block window into the cache
     ///
     /// If the cache has size limits and is full, this may evict older entries.
@@ -150,6 +274,36 @@ pub trait BlockWindowCache: Send + Sync {
     async fn insert(&self, key: CacheKey, window: DailyBlockWindow)
         -> Result<(), BlockWindowError>;
 
+    /// Inserts a block window together with how it was computed
+    ///
+    /// Backends that persist [`Provenance`] override this. The default
+    /// discards it and calls [`insert`](Self::insert).
+    async fn insert_with_provenance(
+        &self,
+        key: CacheKey,
+        window: DailyBlockWindow,
+        provenance: Provenance,
+    ) -> Result<(), BlockWindowError> {
+        let _ = provenance;
+        self.insert(key, window).await
+    }
+
+    /// Inserts several block windows at once
+    ///
+    /// Meant for prefetching a range of dates. Backends with expensive writes
+    /// override this to persist the whole batch in one go (as [`DiskCache`]
+    /// does); the default inserts entries one at a time and stops at the
+    /// first failure.
+    async fn insert_many(
+        &self,
+        entries: Vec<(CacheKey, DailyBlockWindow)>,
+    ) -> Result<(), BlockWindowError> {
+        for (key, window) in entries {
+            self.insert(key, window).await?;
+        }
+        Ok(())
+    }
+
     /// Clears all entries from the cache
     ///
     /// Used for testing and cache management. Not all backends may support this.
@@ -165,3 +319,53 @@ pub trait BlockWindowCache: Send + Sync {
     /// Used for logging and debugging.
     fn name(&self) -> &'static str;
 }
+
+/// Lets one cache back several calculators, e.g. one per chain
+#[async_trait]
+impl<C: BlockWindowCache + ?Sized> BlockWindowCache for Arc<C> {
+    async fn get(&self, key: &CacheKey) -> Option<DailyBlockWindow> {
+        (**self).get(key).await
+    }
+
+    async fn try_get(&self, key: &CacheKey) -> Result<Option<DailyBlockWindow>, BlockWindowError> {
+        (**self).try_get(key).await
+    }
+
+    async fn insert(
+        &self,
+        key: CacheKey,
+        window: DailyBlockWindow,
+    ) -> Result<(), BlockWindowError> {
+        (**self).insert(key, window).await
+    }
+
+    async fn insert_with_provenance(
+        &self,
+        key: CacheKey,
+        window: DailyBlockWindow,
+        provenance: Provenance,
+    ) -> Result<(), BlockWindowError> {
+        (**self)
+            .insert_with_provenance(key, window, provenance)
+            .await
+    }
+
+    async fn insert_many(
+        &self,
+        entries: Vec<(CacheKey, DailyBlockWindow)>,
+    ) -> Result<(), BlockWindowError> {
+        (**self).insert_many(entries).await
+    }
+
+    async fn clear(&self) -> Result<(), BlockWindowError> {
+        (**self).clear().await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        (**self).stats().await
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+}
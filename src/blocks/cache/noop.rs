@@ -95,6 +95,9 @@ mod tests {
             end_block: 2000,
             start_ts: crate::blocks::window::UnixTimestamp(1728518400),
             end_ts_exclusive: crate::blocks::window::UnixTimestamp(1728604800),
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         };
 
         // Insert should succeed but do nothing
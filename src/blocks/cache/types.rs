@@ -10,7 +10,15 @@
 //! - [`AccessSequence`]: Monotonic sequence number for deterministic LRU ordering
 
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
+
+/// Milliseconds since the Unix epoch, read through chrono
+///
+/// `std::time::SystemTime::now` panics on `wasm32-unknown-unknown`; chrono's
+/// clock reads the JS `Date` there when the `wasm` feature is enabled.
+fn now_millis() -> u128 {
+    u128::try_from(chrono::Utc::now().timestamp_millis()).unwrap_or_default()
+}
 
 /// Unix timestamp in milliseconds for high-precision cache ordering
 ///
@@ -37,11 +45,7 @@ pub struct TimestampMillis(u128);
 impl TimestampMillis {
     /// Creates a new timestamp representing the current time
     pub fn now() -> Self {
-        let millis = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis();
-        Self(millis)
+        Self(now_millis())
     }
 
     /// Creates a timestamp from a raw millisecond value
@@ -57,12 +61,7 @@ impl TimestampMillis {
     /// Returns the duration between this timestamp and the current time.
     /// If this timestamp is in the future, returns zero duration.
     pub fn age_since_now(&self) -> Duration {
-        let now_millis = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis();
-
-        let age_millis = now_millis.saturating_sub(self.0);
+        let age_millis = now_millis().saturating_sub(self.0);
         Duration::from_millis(age_millis as u64)
     }
 
@@ -0,0 +1,446 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pre-seeded timestamp to block maps
+//!
+//! A [`BlockCheckpoints`] file lists known blocks of one chain with their
+//! timestamps, typically one per week. Given one through
+//! [`BlockWindowCalculator::with_checkpoints`](crate::BlockWindowCalculator::with_checkpoints),
+//! a window search starts between the two checkpoints around its target
+//! instead of probing from the head, so it takes a handful of requests
+//! however old the date.
+//!
+//! Without any provider, [`BlockCheckpoints::estimate_daily_window`]
+//! interpolates a day's blocks from the checkpoints alone.
+//!
+//! # File format
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "chain": "base",
+//!   "checkpoints": [
+//!     { "block": 0, "timestamp": 1686789347 },
+//!     { "block": 302400, "timestamp": 1687394147 }
+//!   ]
+//! }
+//! ```
+//!
+//! The `generate_checkpoints` example writes such a file from a provider.
+
+use std::path::Path;
+use std::time::Duration;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::BlockNumber;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::blocks::window::{day_bounds, DailyBlockWindow, UnixTimestamp};
+use crate::config::constants::ChainGenesis;
+use crate::errors::BlockWindowError;
+
+/// Format version written by [`BlockCheckpoints::to_json_string`]
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+/// A block and its timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCheckpoint {
+    /// Number of the block
+    #[serde(rename = "block")]
+    pub block: BlockNumber,
+    /// Timestamp of the block
+    #[serde(rename = "timestamp")]
+    pub timestamp: UnixTimestamp,
+}
+
+/// Checkpoints of one chain, ordered by block
+///
+/// # Examples
+///
+/// ```
+/// use alloy_chains::NamedChain;
+/// use semioscan::{BlockCheckpoint, BlockCheckpoints, UnixTimestamp};
+///
+/// let checkpoints = BlockCheckpoints::new(
+///     NamedChain::Base,
+///     vec![
+///         BlockCheckpoint { block: 0, timestamp: UnixTimestamp(1_000) },
+///         BlockCheckpoint { block: 100, timestamp: UnixTimestamp(1_200) },
+///     ],
+/// )
+/// .unwrap();
+/// assert_eq!(checkpoints.bounds(UnixTimestamp(1_100)), (Some(0), Some(100)));
+/// assert_eq!(checkpoints.bounds(UnixTimestamp(1_300)), (Some(100), None));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlockCheckpoints {
+    #[serde(rename = "version")]
+    version: u32,
+    #[serde(rename = "chain")]
+    chain: NamedChain,
+    #[serde(rename = "checkpoints")]
+    checkpoints: Vec<BlockCheckpoint>,
+}
+
+/// [`BlockCheckpoints`] as read, before validation
+#[derive(Deserialize)]
+struct RawCheckpoints {
+    version: u32,
+    chain: NamedChain,
+    checkpoints: Vec<BlockCheckpoint>,
+}
+
+impl BlockCheckpoints {
+    /// Checkpoints of `chain`, sorted by block
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockWindowError::InvalidCheckpoints`] if a later block has
+    /// an earlier timestamp, or one block has two timestamps.
+    pub fn new(
+        chain: NamedChain,
+        mut checkpoints: Vec<BlockCheckpoint>,
+    ) -> Result<Self, BlockWindowError> {
+        checkpoints.sort_by_key(|checkpoint| checkpoint.block);
+        checkpoints.dedup();
+        for pair in checkpoints.windows(2) {
+            let (earlier, later) = (pair[0], pair[1]);
+            if earlier.block == later.block {
+                return Err(BlockWindowError::invalid_checkpoints(
+                    chain,
+                    format!("block {} has two timestamps", later.block),
+                ));
+            }
+            if later.timestamp < earlier.timestamp {
+                return Err(BlockWindowError::invalid_checkpoints(
+                    chain,
+                    format!(
+                        "block {} is dated before block {}",
+                        later.block, earlier.block
+                    ),
+                ));
+            }
+        }
+        Ok(Self {
+            version: CHECKPOINT_FORMAT_VERSION,
+            chain,
+            checkpoints,
+        })
+    }
+
+    /// Load checkpoints from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockWindowError::CheckpointFileUnreadable`] if the file
+    /// can't be read, and fails as [`from_json_str`](Self::from_json_str)
+    /// does if it isn't a checkpoint file of a supported version or its
+    /// checkpoints are inconsistent.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BlockWindowError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            BlockWindowError::checkpoint_file_unreadable(path.display().to_string(), e)
+        })?;
+        Self::from_json_str(&json)
+    }
+
+    /// Parse checkpoints from a JSON document
+    ///
+    /// # Errors
+    ///
+    /// Fails if `json` isn't a checkpoint file of a supported version, or its
+    /// checkpoints are inconsistent.
+    pub fn from_json_str(json: &str) -> Result<Self, BlockWindowError> {
+        let raw: RawCheckpoints =
+            serde_json::from_str(json).map_err(BlockWindowError::serialization_error)?;
+        if raw.version != CHECKPOINT_FORMAT_VERSION {
+            return Err(BlockWindowError::invalid_checkpoints(
+                raw.chain,
+                format!("unsupported format version {}", raw.version),
+            ));
+        }
+        Self::new(raw.chain, raw.checkpoints)
+    }
+
+    /// Serialize the checkpoints to pretty-printed JSON
+    pub fn to_json_string(&self) -> Result<String, BlockWindowError> {
+        serde_json::to_string_pretty(self).map_err(BlockWindowError::serialization_error)
+    }
+
+    /// The chain the checkpoints belong to
+    pub fn chain(&self) -> NamedChain {
+        self.chain
+    }
+
+    /// The checkpoints, ordered by block
+    pub fn checkpoints(&self) -> &[BlockCheckpoint] {
+        &self.checkpoints
+    }
+
+    /// Number of checkpoints
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Whether there are no checkpoints
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    /// The latest checkpoint, if any
+    pub fn last(&self) -> Option<BlockCheckpoint> {
+        self.checkpoints.last().copied()
+    }
+
+    /// The blocks of the last checkpoint dated before `ts` and the first
+    /// dated after it
+    ///
+    /// The first block at, and the last block before, `ts` lie between the
+    /// two. Either is `None` when no checkpoint is on that side.
+    pub fn bounds(&self, ts: UnixTimestamp) -> (Option<BlockNumber>, Option<BlockNumber>) {
+        let before = self
+            .checkpoints
+            .partition_point(|checkpoint| checkpoint.timestamp < ts);
+        let after = self
+            .checkpoints
+            .partition_point(|checkpoint| checkpoint.timestamp <= ts);
+        (
+            before
+                .checked_sub(1)
+                .map(|index| self.checkpoints[index].block),
+            self.checkpoints
+                .get(after)
+                .map(|checkpoint| checkpoint.block),
+        )
+    }
+
+    /// Estimated position of `ts` on the chain, in blocks
+    ///
+    /// Interpolates linearly between the checkpoints around `ts`. Past the
+    /// last checkpoint (or before the first) it extrapolates at `block_time`,
+    /// falling back to the rate between the two outermost checkpoints.
+    /// Block `n` is produced at position `n`, so the first block at or after
+    /// `ts` is the ceiling of the result. Returns `None` without a
+    /// checkpoint, or without a rate to extrapolate at.
+    pub fn estimate_position(
+        &self,
+        ts: UnixTimestamp,
+        block_time: Option<Duration>,
+    ) -> Option<f64> {
+        let split = self
+            .checkpoints
+            .partition_point(|checkpoint| checkpoint.timestamp <= ts);
+        let before = split.checked_sub(1).map(|index| self.checkpoints[index]);
+        let after = self.checkpoints.get(split).copied();
+
+        let secs_per_block = |outer: &[BlockCheckpoint]| {
+            block_time
+                .map(|block_time| block_time.as_secs_f64())
+                .filter(|secs| *secs > 0.0)
+                .or_else(|| match outer {
+                    [a, b] => rate(*a, *b).map(|blocks_per_sec| 1.0 / blocks_per_sec),
+                    _ => None,
+                })
+        };
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                let blocks_per_sec = rate(before, after)?;
+                Some(before.block as f64 + (ts.0 - before.timestamp.0) as f64 * blocks_per_sec)
+            }
+            (Some(before), None) => {
+                let outer = self
+                    .checkpoints
+                    .len()
+                    .checked_sub(2)
+                    .map_or(&self.checkpoints[..0], |index| &self.checkpoints[index..]);
+                let secs = secs_per_block(outer)?;
+                Some(before.block as f64 + (ts.0 - before.timestamp.0) as f64 / secs)
+            }
+            (None, Some(after)) => {
+                let outer = &self.checkpoints[..self.checkpoints.len().min(2)];
+                let secs = secs_per_block(outer)?;
+                Some((after.block as f64 - (after.timestamp.0 - ts.0) as f64 / secs).max(0.0))
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Estimates the daily block window for `date` without any RPC call
+    ///
+    /// Interpolates between the checkpoints, plus `genesis` if given, around
+    /// each day boundary, and extrapolates past them at `block_time` (see
+    /// [`estimate_position`](Self::estimate_position)). The result has
+    /// [`estimated`](DailyBlockWindow::estimated) set and is approximate,
+    /// more so far from a checkpoint. It suits planning scan sizes, and tests
+    /// and demos without RPC access.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockWindowError::EstimateUnavailable`] with neither a
+    /// checkpoint nor `genesis`, or without a rate to extrapolate at, and
+    /// [`BlockWindowError::InvalidCheckpoints`] if `genesis` contradicts the
+    /// checkpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use alloy_chains::NamedChain;
+    /// use chrono::NaiveDate;
+    /// use semioscan::{BlockCheckpoint, BlockCheckpoints, UnixTimestamp};
+    ///
+    /// let checkpoints = BlockCheckpoints::new(
+    ///     NamedChain::Base,
+    ///     vec![BlockCheckpoint { block: 0, timestamp: UnixTimestamp(1_700_006_400) }],
+    /// )
+    /// .unwrap();
+    /// let date = NaiveDate::from_ymd_opt(2023, 11, 15).unwrap();
+    /// let window = checkpoints
+    ///     .estimate_daily_window(date, None, Some(Duration::from_secs(2)))
+    ///     .unwrap();
+    /// assert!(window.estimated);
+    /// assert_eq!((window.start_block, window.end_block), (0, 43_199));
+    /// ```
+    pub fn estimate_daily_window(
+        &self,
+        date: NaiveDate,
+        genesis: Option<ChainGenesis>,
+        block_time: Option<Duration>,
+    ) -> Result<DailyBlockWindow, BlockWindowError> {
+        let (start_ts, end_ts_exclusive) = day_bounds(date)?;
+
+        let mut anchors = self.checkpoints.clone();
+        if let Some(genesis) = genesis {
+            anchors.push(BlockCheckpoint {
+                block: genesis.first_block,
+                timestamp: genesis.timestamp,
+            });
+        }
+        let anchors = Self::new(self.chain, anchors)?;
+        let position = |ts| {
+            anchors
+                .estimate_position(ts, block_time)
+                .ok_or_else(|| BlockWindowError::estimate_unavailable(self.chain))
+        };
+        let start_block = position(start_ts)?.ceil() as BlockNumber;
+        let end_block = (position(end_ts_exclusive)?.ceil() as BlockNumber)
+            .saturating_sub(1)
+            .max(start_block);
+
+        let mut window = DailyBlockWindow::new(start_block, end_block, start_ts, end_ts_exclusive)?;
+        window.estimated = true;
+        Ok(window)
+    }
+
+    /// Add a checkpoint after the latest one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockWindowError::InvalidCheckpoints`] unless `checkpoint`
+    /// has a later block, and no earlier timestamp, than the latest one.
+    pub fn push(&mut self, checkpoint: BlockCheckpoint) -> Result<(), BlockWindowError> {
+        if let Some(last) = self.last() {
+            if checkpoint.block <= last.block || checkpoint.timestamp < last.timestamp {
+                return Err(BlockWindowError::invalid_checkpoints(
+                    self.chain,
+                    format!(
+                        "block {} doesn't follow the latest checkpoint, block {}",
+                        checkpoint.block, last.block
+                    ),
+                ));
+            }
+        }
+        self.checkpoints.push(checkpoint);
+        Ok(())
+    }
+}
+
+/// Blocks per second between two checkpoints, `None` if they share a
+/// timestamp
+fn rate(earlier: BlockCheckpoint, later: BlockCheckpoint) -> Option<f64> {
+    let secs = later.timestamp.0 - earlier.timestamp.0;
+    (secs > 0).then(|| later.block.saturating_sub(earlier.block) as f64 / secs as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::constants::chain_genesis;
+
+    fn checkpoint(block: BlockNumber, timestamp: i64) -> BlockCheckpoint {
+        BlockCheckpoint {
+            block,
+            timestamp: UnixTimestamp(timestamp),
+        }
+    }
+
+    #[test]
+    fn test_checkpoints_round_trip_and_reject_disorder() {
+        let checkpoints = BlockCheckpoints::new(
+            NamedChain::Base,
+            vec![checkpoint(200, 1_400), checkpoint(0, 1_000)],
+        )
+        .unwrap();
+        assert_eq!(checkpoints.checkpoints()[0].block, 0);
+        assert_eq!(checkpoints.bounds(UnixTimestamp(1_000)), (None, Some(200)));
+        assert_eq!(checkpoints.bounds(UnixTimestamp(999)), (None, Some(0)));
+
+        let json = checkpoints.to_json_string().unwrap();
+        assert_eq!(BlockCheckpoints::from_json_str(&json).unwrap(), checkpoints);
+
+        assert!(BlockCheckpoints::new(
+            NamedChain::Base,
+            vec![checkpoint(0, 1_000), checkpoint(200, 900)],
+        )
+        .is_err());
+        let mut extended = checkpoints.clone();
+        assert!(extended.push(checkpoint(100, 1_500)).is_err());
+        extended.push(checkpoint(400, 1_800)).unwrap();
+        assert_eq!(extended.last(), Some(checkpoint(400, 1_800)));
+
+        let future = json.replace("\"version\": 1", "\"version\": 2");
+        assert!(matches!(
+            BlockCheckpoints::from_json_str(&future),
+            Err(BlockWindowError::InvalidCheckpoints { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unreadable_checkpoint_file_names_its_path() {
+        let error = BlockCheckpoints::load("/nonexistent/checkpoints.json").unwrap_err();
+        assert!(matches!(
+            &error,
+            BlockWindowError::CheckpointFileUnreadable { path, .. }
+                if path == "/nonexistent/checkpoints.json"
+        ));
+    }
+
+    #[test]
+    fn test_estimate_daily_window_offline() {
+        let genesis = chain_genesis(NamedChain::Base).unwrap();
+        let checkpoints = BlockCheckpoints::new(
+            NamedChain::Base,
+            vec![checkpoint(1_000_000, genesis.timestamp.0 + 2_000_000)],
+        )
+        .unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
+        let window = checkpoints
+            .estimate_daily_window(date, Some(genesis), None)
+            .unwrap();
+        assert!(window.estimated);
+        assert_eq!(window.start_ts, UnixTimestamp(1_688_169_600));
+        // One block every two seconds since genesis
+        assert_eq!(window.start_block, 690_127);
+        assert_eq!(window.end_block, 733_326);
+
+        let empty = BlockCheckpoints::new(NamedChain::Sonic, Vec::new()).unwrap();
+        assert!(matches!(
+            empty.estimate_daily_window(date, None, Some(Duration::from_secs(1))),
+            Err(BlockWindowError::EstimateUnavailable { .. })
+        ));
+    }
+}
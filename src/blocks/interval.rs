@@ -0,0 +1,184 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Block and time intervals with their boundary semantics in the type
+//!
+//! A daily window's blocks include both ends, while its time span excludes
+//! its end, the next midnight. [`BlockInterval`] and [`TimeInterval`] each
+//! carry one of those conventions, so code reading a window's bounds through
+//! them can't mix the two up.
+
+use std::ops::{Range, RangeInclusive};
+
+use alloy_primitives::BlockNumber;
+use serde::{Deserialize, Serialize};
+
+use crate::blocks::window::UnixTimestamp;
+use crate::types::config::BlockCount;
+
+/// Blocks from `first` to `last`, both included
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::BlockInterval;
+///
+/// let blocks = BlockInterval::new(100, 199).unwrap();
+/// assert!(blocks.contains(199));
+/// assert_eq!(blocks.len().as_u64(), 100);
+/// assert_eq!(blocks.to_range(), 100..=199);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockInterval {
+    #[serde(rename = "first")]
+    first: BlockNumber,
+    #[serde(rename = "last")]
+    last: BlockNumber,
+}
+
+impl BlockInterval {
+    /// Blocks from `first` to `last` inclusive, or `None` if `last` is
+    /// before `first`
+    pub fn new(first: BlockNumber, last: BlockNumber) -> Option<Self> {
+        (first <= last).then_some(Self { first, last })
+    }
+
+    /// Blocks from `first` to `last`, widened to `first` alone if `last` is
+    /// before it
+    pub(crate) fn clamped(first: BlockNumber, last: BlockNumber) -> Self {
+        Self {
+            first,
+            last: last.max(first),
+        }
+    }
+
+    /// First block, included
+    pub const fn first(&self) -> BlockNumber {
+        self.first
+    }
+
+    /// Last block, included
+    pub const fn last(&self) -> BlockNumber {
+        self.last
+    }
+
+    /// Number of blocks, counting both ends
+    pub fn len(&self) -> BlockCount {
+        BlockCount::new(self.last.saturating_sub(self.first).saturating_add(1))
+    }
+
+    /// Always `false`: an interval holds at least its first block
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Whether `block` lies in the interval
+    pub fn contains(&self, block: BlockNumber) -> bool {
+        (self.first..=self.last).contains(&block)
+    }
+
+    /// The interval as an inclusive range
+    pub fn to_range(&self) -> RangeInclusive<BlockNumber> {
+        self.first..=self.last
+    }
+}
+
+impl From<BlockInterval> for RangeInclusive<BlockNumber> {
+    fn from(interval: BlockInterval) -> Self {
+        interval.to_range()
+    }
+}
+
+/// Seconds from `start`, included, up to `end`, excluded
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::{TimeInterval, UnixTimestamp};
+///
+/// let day = TimeInterval::new(UnixTimestamp(1_735_689_600), UnixTimestamp(1_735_776_000)).unwrap();
+/// assert!(day.contains(UnixTimestamp(1_735_689_600)));
+/// assert!(!day.contains(UnixTimestamp(1_735_776_000)));
+/// assert_eq!(day.last_second(), UnixTimestamp(1_735_775_999));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeInterval {
+    #[serde(rename = "start")]
+    start: UnixTimestamp,
+    #[serde(rename = "end_exclusive")]
+    end_exclusive: UnixTimestamp,
+}
+
+impl TimeInterval {
+    /// Seconds from `start` up to, but not including, `end_exclusive`, or
+    /// `None` if that span is empty
+    pub fn new(start: UnixTimestamp, end_exclusive: UnixTimestamp) -> Option<Self> {
+        (start < end_exclusive).then_some(Self {
+            start,
+            end_exclusive,
+        })
+    }
+
+    /// Seconds from `start` up to `end_exclusive`, widened to the single
+    /// second at `start` if that span is empty
+    pub(crate) fn clamped(start: UnixTimestamp, end_exclusive: UnixTimestamp) -> Self {
+        Self {
+            start,
+            end_exclusive: end_exclusive.max(UnixTimestamp(start.0.saturating_add(1))),
+        }
+    }
+
+    /// First second, included
+    pub const fn start(&self) -> UnixTimestamp {
+        self.start
+    }
+
+    /// First second after the interval, excluded
+    pub const fn end_exclusive(&self) -> UnixTimestamp {
+        self.end_exclusive
+    }
+
+    /// Last second, included
+    pub fn last_second(&self) -> UnixTimestamp {
+        self.end_exclusive.pred()
+    }
+
+    /// Length in seconds
+    pub fn duration_secs(&self) -> u64 {
+        self.end_exclusive.0.abs_diff(self.start.0)
+    }
+
+    /// Whether `ts` lies in the interval
+    pub fn contains(&self, ts: UnixTimestamp) -> bool {
+        (self.start..self.end_exclusive).contains(&ts)
+    }
+
+    /// The interval as a half-open range
+    pub fn to_range(&self) -> Range<UnixTimestamp> {
+        self.start..self.end_exclusive
+    }
+}
+
+impl From<TimeInterval> for Range<UnixTimestamp> {
+    fn from(interval: TimeInterval) -> Self {
+        interval.to_range()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_reject_empty_spans() {
+        assert_eq!(BlockInterval::new(5, 4), None);
+        assert_eq!(BlockInterval::new(5, 5).unwrap().len().as_u64(), 1);
+
+        let ts = UnixTimestamp(1_000);
+        assert_eq!(TimeInterval::new(ts, ts), None);
+        let second = TimeInterval::new(ts, UnixTimestamp(1_001)).unwrap();
+        assert_eq!(second.duration_secs(), 1);
+        assert_eq!(second.last_second(), ts);
+    }
+}
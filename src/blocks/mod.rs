@@ -10,8 +10,20 @@
 //! - Caching block window results with multiple backends
 
 pub mod cache;
+pub mod checkpoint;
+pub mod interval;
 pub mod window;
 
 // Re-export public API
-pub use cache::{BlockWindowCache, CacheKey, CacheStats, DiskCache, MemoryCache, NoOpCache};
+#[cfg(feature = "compression")]
+pub use cache::Codec;
+#[cfg(feature = "disk-cache")]
+pub use cache::{AuditAction, AuditRecord, AuditedCache, CacheAuditLog};
+pub use cache::{
+    BlockWindowCache, CacheKey, CachePolicy, CacheStats, MemoryCache, NoOpCache, Provenance,
+};
+#[cfg(feature = "disk-cache")]
+pub use cache::{DiskCache, MaintenanceHandle, MaintenanceReport};
+pub use checkpoint::{BlockCheckpoint, BlockCheckpoints, CHECKPOINT_FORMAT_VERSION};
+pub use interval::{BlockInterval, TimeInterval};
 pub use window::*;
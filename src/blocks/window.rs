@@ -34,20 +34,68 @@
 //!
 //! println!("Blocks for {}: [{}, {}]", date, window.start_block, window.end_block);
 //! ```
+//!
+//! # Search
+//!
+//! Each uncached window is found by interpolation followed by binary search.
+//! The calculator estimates the chain's block time from recent headers (see
+//! [`BlockWindowCalculator::estimate_block_time`]), guesses the boundary block
+//! from the head, and brackets the guess with strides that double until they
+//! straddle the boundary. The binary search then only covers the bracket.
 
 use alloy_chains::NamedChain;
-use alloy_primitives::BlockNumber;
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumberOrTag;
+use alloy_network::{AnyNetwork, BlockResponse, Ethereum, Network};
+use alloy_primitives::{BlockHash, BlockNumber};
 use alloy_provider::Provider;
+use alloy_transport::TransportErrorKind;
 use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
 use tracing::{debug, info};
 
-use crate::blocks::cache::{BlockWindowCache, CacheKey, DiskCache};
+#[cfg(feature = "disk-cache")]
+use crate::blocks::cache::DiskCache;
+use crate::blocks::cache::{BlockWindowCache, CacheKey, CachePolicy, Provenance};
+use crate::blocks::checkpoint::BlockCheckpoints;
+use crate::blocks::interval::{BlockInterval, TimeInterval};
+use crate::cache::header::{fetch_header, HeaderCache};
+use crate::config::constants::{chain_genesis, ChainGenesis};
+use crate::config::{within_deadline, OperationDeadline, SemioscanConfig, SpanConfig};
 use crate::errors::{BlockWindowError, RpcError};
+use crate::provider::{ChainHead, PooledProvider, ProviderPool};
 use crate::tracing::spans;
+use crate::types::cache::TimestampMillis;
 use crate::types::config::BlockCount;
 
+/// Consecutive blocks sampled by [`BlockWindowCalculator::estimate_block_time`]
+/// unless set with [`BlockWindowCalculator::with_block_time_sample`]
+const DEFAULT_BLOCK_TIME_SAMPLE: u64 = 32;
+
+/// How long a block time estimate is reused before it is sampled again
+const BLOCK_TIME_TTL: Duration = Duration::from_secs(600);
+
+/// Most headers requested at once while sampling the block time
+const BLOCK_TIME_CONCURRENCY: usize = 8;
+
+/// Oldest a tracked head's block may be before the head is fetched instead,
+/// unless set with [`BlockWindowCalculator::with_head_max_age`]
+const DEFAULT_HEAD_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// How long a probed earliest available block is trusted before it is
+/// probed again, since a pruning node keeps dropping old blocks
+const EARLIEST_AVAILABLE_TTL: Duration = Duration::from_secs(3_600);
+
+/// Initial distance, in time, between an interpolated guess and the bracket
+/// probes around it
+const BRACKET_SLACK: Duration = Duration::from_secs(600);
+
 /// Unix timestamp in seconds (always UTC)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct UnixTimestamp(pub i64);
@@ -85,19 +133,56 @@ impl std::fmt::Display for UnixTimestamp {
 /// - The first block produced on or after 00:00:00 UTC on the given date
 /// - The last block produced at or before 23:59:59 UTC on the given date
 /// - The exact UTC timestamps that define the day boundaries
+///
+/// This type is persisted by [`DiskCache`](crate::DiskCache), so its serialized
+/// field names are pinned and must not change between releases.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DailyBlockWindow {
     /// First block number in the window (inclusive)
+    #[serde(rename = "start_block")]
     pub start_block: BlockNumber,
 
     /// Last block number in the window (inclusive)
+    #[serde(rename = "end_block")]
     pub end_block: BlockNumber,
 
     /// UTC timestamp at start of day (00:00:00 UTC)
+    #[serde(rename = "start_ts")]
     pub start_ts: UnixTimestamp,
 
     /// UTC timestamp at start of next day (00:00:00 UTC next day) - exclusive boundary
+    #[serde(rename = "end_ts_exclusive")]
     pub end_ts_exclusive: UnixTimestamp,
+
+    /// Hash of `start_block` when the window was computed
+    ///
+    /// `None` for windows cached by older versions. See
+    /// [`BlockWindowCalculator::verify_anchors`].
+    #[serde(
+        default,
+        rename = "start_block_hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_block_hash: Option<BlockHash>,
+
+    /// Hash of `end_block` when the window was computed
+    #[serde(
+        default,
+        rename = "end_block_hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_block_hash: Option<BlockHash>,
+
+    /// Whether the blocks were estimated offline rather than searched for
+    ///
+    /// Set by [`BlockCheckpoints::estimate_daily_window`]. Omitted from
+    /// the serialized form when `false`.
+    #[serde(
+        default,
+        rename = "estimated",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub estimated: bool,
 }
 
 impl DailyBlockWindow {
@@ -122,9 +207,28 @@ impl DailyBlockWindow {
             end_block,
             start_ts,
             end_ts_exclusive,
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         })
     }
 
+    /// Anchors the window to the hashes of its boundary blocks
+    pub fn with_block_hashes(
+        mut self,
+        start_block_hash: Option<BlockHash>,
+        end_block_hash: Option<BlockHash>,
+    ) -> Self {
+        self.start_block_hash = start_block_hash;
+        self.end_block_hash = end_block_hash;
+        self
+    }
+
+    /// Whether either boundary block hash was recorded
+    pub fn is_anchored(&self) -> bool {
+        self.start_block_hash.is_some() || self.end_block_hash.is_some()
+    }
+
     /// Returns the number of blocks in this window (inclusive)
     pub fn block_count(&self) -> BlockCount {
         let count = self
@@ -133,6 +237,99 @@ impl DailyBlockWindow {
             .saturating_add(1);
         BlockCount::new(count)
     }
+
+    /// The window's blocks, `start_block` through `end_block` inclusive
+    pub fn blocks(&self) -> BlockInterval {
+        BlockInterval::clamped(self.start_block, self.end_block)
+    }
+
+    /// The window's day, from `start_ts` up to but excluding `end_ts_exclusive`
+    pub fn time(&self) -> TimeInterval {
+        TimeInterval::clamped(self.start_ts, self.end_ts_exclusive)
+    }
+}
+
+/// How a [`DailyBlockWindow`] was obtained
+///
+/// Returned by [`BlockWindowCalculator::get_daily_window_detailed`] for
+/// monitoring cache effectiveness and RPC cost per window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowProvenance {
+    /// The window was served from the cache without any RPC calls
+    pub cache_hit: bool,
+    /// RPC calls made to compute the window (0 on a cache hit)
+    pub probes: u32,
+    /// Age of the chain head when the window was computed, measured from the
+    /// head block's timestamp (zero on a cache hit)
+    ///
+    /// A large value means the provider is lagging behind the chain, so a
+    /// window for a recent date may be truncated.
+    pub head_age: Duration,
+}
+
+impl WindowProvenance {
+    fn cached() -> Self {
+        Self {
+            cache_hit: true,
+            probes: 0,
+            head_age: Duration::ZERO,
+        }
+    }
+}
+
+/// Block times measured over a run of consecutive recent blocks
+///
+/// Block timestamps have one-second resolution, so on chains producing
+/// several blocks per second most intervals are zero and the percentiles are
+/// coarse; [`average`](Self::average) stays accurate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockTimeStats {
+    /// Newest block of the sample
+    pub latest_block: BlockNumber,
+    /// Number of block intervals measured
+    pub intervals: u64,
+    /// Mean time between blocks
+    pub average: Duration,
+    /// Median time between consecutive blocks
+    pub p50: Duration,
+    /// 90th percentile time between consecutive blocks
+    pub p90: Duration,
+    /// Longest time between consecutive blocks
+    pub max: Duration,
+}
+
+impl BlockTimeStats {
+    /// Statistics of consecutive block `timestamps` (seconds, oldest first)
+    /// ending at `latest_block`, or `None` with fewer than two timestamps
+    fn from_timestamps(latest_block: BlockNumber, timestamps: &[u64]) -> Option<Self> {
+        let (first, last) = (timestamps.first()?, timestamps.last()?);
+        let mut gaps: Vec<u64> = timestamps
+            .windows(2)
+            .map(|pair| pair[1].saturating_sub(pair[0]))
+            .collect();
+        if gaps.is_empty() {
+            return None;
+        }
+        gaps.sort_unstable();
+        let intervals = gaps.len() as u64;
+        let percentile = |p: usize| Duration::from_secs(gaps[(gaps.len() - 1) * p / 100]);
+        Some(Self {
+            latest_block,
+            intervals,
+            average: Duration::from_secs_f64(last.saturating_sub(*first) as f64 / intervals as f64),
+            p50: percentile(50),
+            p90: percentile(90),
+            max: percentile(100),
+        })
+    }
+}
+
+/// Oldest block the provider serves, as declared or last probed
+#[derive(Debug, Clone, Copy)]
+struct EarliestAvailable {
+    block: BlockNumber,
+    /// When the block was probed; `None` if declared, which never expires
+    probed_at: Option<TimestampMillis>,
 }
 
 /// Calculates and caches daily block windows for blockchain queries
@@ -160,6 +357,21 @@ impl DailyBlockWindow {
 pub struct BlockWindowCalculator<P> {
     provider: P,
     cache: Box<dyn BlockWindowCache>,
+    operation_deadline: Option<Duration>,
+    config: Option<SemioscanConfig>,
+    rpc_endpoint: Option<String>,
+    cache_policy: CachePolicy,
+    header_cache: Option<HeaderCache>,
+    block_time_sample: u64,
+    block_times: Mutex<HashMap<NamedChain, (TimestampMillis, BlockTimeStats)>>,
+    head: Option<watch::Receiver<Option<ChainHead>>>,
+    head_max_age: Duration,
+    genesis: HashMap<NamedChain, ChainGenesis>,
+    earliest_available: Mutex<Option<EarliestAvailable>>,
+    probe_history: bool,
+    fallbacks: Vec<PooledProvider>,
+    checkpoints: HashMap<NamedChain, BlockCheckpoints>,
+    spans: SpanConfig,
 }
 
 impl<P: Provider> BlockWindowCalculator<P> {
@@ -192,7 +404,380 @@ impl<P: Provider> BlockWindowCalculator<P> {
     /// let calculator = BlockWindowCalculator::new(provider, Box::new(NoOpCache));
     /// ```
     pub fn new(provider: P, cache: Box<dyn BlockWindowCache>) -> Self {
-        Self { provider, cache }
+        Self {
+            provider,
+            cache,
+            operation_deadline: None,
+            config: None,
+            rpc_endpoint: None,
+            cache_policy: CachePolicy::default(),
+            header_cache: None,
+            block_time_sample: DEFAULT_BLOCK_TIME_SAMPLE,
+            block_times: Mutex::default(),
+            head: None,
+            head_max_age: DEFAULT_HEAD_MAX_AGE,
+            genesis: HashMap::new(),
+            earliest_available: Mutex::new(None),
+            probe_history: false,
+            fallbacks: Vec::new(),
+            checkpoints: HashMap::new(),
+            spans: SpanConfig::default(),
+        }
+    }
+
+    /// Bounds each uncached `get_daily_window` call to `deadline` in total
+    ///
+    /// A slow or degraded provider then fails with
+    /// [`BlockWindowError::DeadlineExceeded`] instead of stalling the binary
+    /// searches for hours. Use [`with_config`](Self::with_config) to share
+    /// the per-chain setting with the other calculators instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::BlockWindowCalculator;
+    /// use std::time::Duration;
+    ///
+    /// let calculator = BlockWindowCalculator::with_memory_cache(provider)
+    ///     .with_operation_deadline(Duration::from_secs(300));
+    /// ```
+    pub fn with_operation_deadline(mut self, deadline: Duration) -> Self {
+        self.operation_deadline = Some(deadline);
+        self
+    }
+
+    /// Reads per-chain operation deadlines and span settings from `config`
+    ///
+    /// Each `get_daily_window` call is bounded by
+    /// [`SemioscanConfig::get_operation_deadline`] for its chain, unless
+    /// [`with_operation_deadline`](Self::with_operation_deadline) set an
+    /// explicit deadline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{BlockWindowCalculator, SemioscanConfigBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let config = SemioscanConfigBuilder::new()
+    ///     .operation_deadline(Duration::from_secs(300))
+    ///     .build();
+    /// let calculator = BlockWindowCalculator::with_memory_cache(provider).with_config(config);
+    /// ```
+    pub fn with_config(mut self, config: SemioscanConfig) -> Self {
+        self.spans = config.spans.clone();
+        self.config = Some(config);
+        self
+    }
+
+    /// Deadline for one uncached window search on `chain`
+    fn operation_deadline_for(&self, chain: NamedChain) -> Option<Duration> {
+        self.operation_deadline.or_else(|| {
+            self.config
+                .as_ref()
+                .and_then(|config| config.get_operation_deadline(chain))
+        })
+    }
+
+    /// Records a hash of `endpoint` in the [`Provenance`] of computed windows
+    ///
+    /// Only the hash is written to the cache, so URLs containing API keys are
+    /// safe to pass.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::BlockWindowCalculator;
+    ///
+    /// let calculator = BlockWindowCalculator::with_disk_cache(provider, "cache.json")?
+    ///     .with_rpc_endpoint(&rpc_url);
+    /// ```
+    pub fn with_rpc_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.rpc_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets how cache read and write failures are handled
+    ///
+    /// Under the default [`CachePolicy::BestEffort`], an unreadable cache is
+    /// treated as a miss and a failed write is only logged. Under
+    /// [`CachePolicy::Strict`], both are returned as errors from
+    /// `get_daily_window` (typically [`BlockWindowError::CacheIoError`] or
+    /// [`BlockWindowError::SerializationError`]).
+    pub fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+
+    /// Reads block timestamps through `cache`, which may be shared with the
+    /// gas calculators of the same chain
+    ///
+    /// Binary searches for neighbouring dates, and repeated searches on the
+    /// same chain, then skip headers that were already fetched.
+    pub fn with_header_cache(mut self, cache: HeaderCache) -> Self {
+        self.header_cache = Some(cache);
+        self
+    }
+
+    /// Reads the chain head from `head`, as published by a
+    /// [`HeadTracker`](crate::HeadTracker) on this calculator's provider,
+    /// instead of fetching it for every uncached window
+    ///
+    /// Searches then start from a head at most one polling interval old, and
+    /// make one RPC call fewer. The head is fetched as before until the
+    /// tracker publishes its first one, once the tracker has stopped, and
+    /// while the tracked head's block is older than
+    /// [`with_head_max_age`](Self::with_head_max_age) allows, as when its
+    /// polls keep failing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{BlockWindowCalculator, HeadTracker};
+    ///
+    /// let heads = HeadTracker::new(provider.clone()).spawn();
+    /// let calculator = BlockWindowCalculator::with_memory_cache(provider)
+    ///     .with_head(heads.subscribe());
+    /// ```
+    pub fn with_head(mut self, head: watch::Receiver<Option<ChainHead>>) -> Self {
+        self.head = Some(head);
+        self
+    }
+
+    /// Fetch the head instead of reading the tracked one once the tracked
+    /// head's block is older than `max_age` (one minute by default)
+    ///
+    /// Set it to about the chain's block time plus the tracker's polling
+    /// interval, the oldest a head from a working tracker can be.
+    pub fn with_head_max_age(mut self, max_age: Duration) -> Self {
+        self.head_max_age = max_age;
+        self
+    }
+
+    /// The head last published to [`with_head`](Self::with_head), unless the
+    /// tracker has stopped or the head is too old to trust
+    fn tracked_head(&self) -> Option<ChainHead> {
+        let receiver = self.head.as_ref()?;
+        // The sender is dropped when the tracker stops, leaving its last head
+        if receiver.has_changed().is_err() {
+            return None;
+        }
+        let head = (*receiver.borrow())?;
+        let age = Utc::now().timestamp().saturating_sub(head.timestamp.0);
+        if Duration::from_secs(u64::try_from(age).unwrap_or_default()) > self.head_max_age {
+            debug!(
+                block_number = head.number,
+                age_secs = age,
+                "Tracked head is stale, fetching the head"
+            );
+            return None;
+        }
+        Some(head)
+    }
+
+    /// Starts searches on `chain` from `genesis` instead of the built-in
+    /// [`chain_genesis`] entry
+    ///
+    /// Searches on chains with neither never look below block 0, and probe
+    /// it like any other block. Set this for a chain whose block 0 has a
+    /// placeholder timestamp, or whose provider has pruned its early blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::constants::ChainGenesis;
+    /// use semioscan::{BlockWindowCalculator, UnixTimestamp};
+    ///
+    /// let calculator = BlockWindowCalculator::with_memory_cache(provider).with_genesis(
+    ///     NamedChain::Sonic,
+    ///     ChainGenesis {
+    ///         first_block: 1,
+    ///         timestamp: UnixTimestamp(1_733_011_200),
+    ///     },
+    /// );
+    /// ```
+    pub fn with_genesis(mut self, chain: NamedChain, genesis: ChainGenesis) -> Self {
+        self.genesis.insert(chain, genesis);
+        self
+    }
+
+    /// Where searches on `chain` start: the configured or built-in genesis,
+    /// if any
+    fn genesis(&self, chain: NamedChain) -> Option<ChainGenesis> {
+        self.genesis
+            .get(&chain)
+            .copied()
+            .or_else(|| chain_genesis(chain))
+    }
+
+    /// Declares `block` the oldest block the provider serves, as for a
+    /// non-archive node that prunes older history
+    ///
+    /// Searches then never probe below it, and a day that starts before it
+    /// fails with [`BlockWindowError::HistoryUnavailable`] instead of
+    /// misreading the provider's missing-block responses.
+    pub fn with_earliest_available_block(self, block: BlockNumber) -> Self {
+        *self.earliest_available.lock().unwrap() = Some(EarliestAvailable {
+            block,
+            probed_at: None,
+        });
+        self
+    }
+
+    /// Finds the oldest block the provider serves with
+    /// [`earliest_available_block`](Self::earliest_available_block) before
+    /// the first uncached window, unless it was set with
+    /// [`with_earliest_available_block`](Self::with_earliest_available_block)
+    ///
+    /// This costs about `log2(head)` requests once per calculator. Without
+    /// it, a pruned block is still reported as
+    /// [`BlockWindowError::HistoryUnavailable`] when a search reaches it, but
+    /// without the earliest available block.
+    pub fn with_history_probe(mut self) -> Self {
+        self.probe_history = true;
+        self
+    }
+
+    /// The oldest block the provider serves
+    ///
+    /// Returns the block set with
+    /// [`with_earliest_available_block`](Self::with_earliest_available_block)
+    /// or found by an earlier call within the last hour. Otherwise binary
+    /// searches for the first block the provider returns, treating missing
+    /// blocks and pruned-history errors as unavailable, and remembers the
+    /// result. A pruning node keeps dropping old blocks, so a probed block is
+    /// searched for again once it is an hour old.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the head can't be fetched, or a probe fails for another
+    /// reason.
+    pub async fn earliest_available_block(&self) -> Result<BlockNumber, BlockWindowError> {
+        if let Some(block) = self.known_earliest_available() {
+            return Ok(block);
+        }
+
+        let latest_block = match self.tracked_head() {
+            Some(head) => head.number,
+            None => self.fetch_latest_block_number().await?,
+        };
+        let (mut lo, mut hi) = (0, latest_block);
+        let mut earliest = latest_block;
+        while lo <= hi {
+            let mid = lo + (hi - lo) / 2;
+            // Fallbacks may keep a different history, so only the primary
+            // provider is probed
+            match self.fetch_block_timestamp_from_primary(mid).await {
+                Ok(_) => {
+                    earliest = mid;
+                    if mid == 0 {
+                        break;
+                    }
+                    hi = mid - 1;
+                }
+                Err(RpcError::BlockNotFound { .. }) => lo = mid + 1,
+                Err(e) if e.is_history_unavailable() => lo = mid + 1,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        debug!(earliest, latest_block, "Found earliest available block");
+        *self.earliest_available.lock().unwrap() = Some(EarliestAvailable {
+            block: earliest,
+            probed_at: Some(TimestampMillis::now()),
+        });
+        Ok(earliest)
+    }
+
+    /// The earliest available block, if set or probed within
+    /// [`EARLIEST_AVAILABLE_TTL`]
+    fn known_earliest_available(&self) -> Option<BlockNumber> {
+        self.earliest_available
+            .lock()
+            .unwrap()
+            .filter(|earliest| {
+                earliest
+                    .probed_at
+                    .is_none_or(|probed_at| probed_at.age_since_now() < EARLIEST_AVAILABLE_TTL)
+            })
+            .map(|earliest| earliest.block)
+    }
+
+    /// Retries a failed block or head fetch against `provider` before giving
+    /// up
+    ///
+    /// One flaky endpoint then no longer fails a whole binary search. Each
+    /// fallback is tried once per failed fetch, in the order added; the
+    /// primary provider's error is returned if they all fail too. A fallback
+    /// must serve the same chain as the primary provider.
+    /// [`earliest_available_block`](Self::earliest_available_block) probes
+    /// only the primary provider, since a fallback may keep more history.
+    pub fn with_fallback_provider(mut self, provider: PooledProvider) -> Self {
+        self.fallbacks.push(provider);
+        self
+    }
+
+    /// Adds `pool`'s fallback providers for `chain` (see
+    /// [`ProviderPool::add_fallback`]) as with
+    /// [`with_fallback_provider`](Self::with_fallback_provider)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{BlockWindowCalculator, ProviderPoolBuilder};
+    ///
+    /// let pool = ProviderPoolBuilder::new()
+    ///     .add_chain(NamedChain::Base, "https://mainnet.base.org")
+    ///     .add_fallback(NamedChain::Base, "https://base-rpc.publicnode.com")
+    ///     .build()?;
+    /// let calculator = BlockWindowCalculator::with_memory_cache(provider)
+    ///     .with_pool_fallbacks(&pool, NamedChain::Base);
+    /// ```
+    pub fn with_pool_fallbacks(mut self, pool: &ProviderPool, chain: NamedChain) -> Self {
+        self.fallbacks.extend(pool.fallbacks(chain));
+        self
+    }
+
+    /// Bounds searches on the checkpoints' chain by `checkpoints`
+    ///
+    /// A search for a date between two checkpoints only probes the blocks
+    /// between them, and one after the last checkpoint never looks below it.
+    /// Replaces any checkpoints given earlier for the same chain.
+    pub fn with_checkpoints(mut self, checkpoints: BlockCheckpoints) -> Self {
+        self.checkpoints.insert(checkpoints.chain(), checkpoints);
+        self
+    }
+
+    /// Loads a checkpoint file (see [`BlockCheckpoints`]) and uses it as
+    /// with [`with_checkpoints`](Self::with_checkpoints)
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be read or isn't a valid checkpoint file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let calculator = BlockWindowCalculator::with_memory_cache(provider)
+    ///     .load_checkpoints("checkpoints/base.json")?;
+    /// ```
+    pub fn load_checkpoints(self, path: impl AsRef<Path>) -> Result<Self, BlockWindowError> {
+        Ok(self.with_checkpoints(BlockCheckpoints::load(path)?))
+    }
+
+    /// Limit the tracing spans window searches create (default: all spans)
+    pub fn with_span_config(mut self, spans: SpanConfig) -> Self {
+        self.spans = spans;
+        self
+    }
+
+    /// Number of consecutive block intervals [`estimate_block_time`] samples
+    /// (default 32, at least 1)
+    ///
+    /// [`estimate_block_time`]: Self::estimate_block_time
+    pub fn with_block_time_sample(mut self, intervals: u64) -> Self {
+        self.block_time_sample = intervals.max(1);
+        self
     }
 
     /// Creates a calculator with a disk cache at the specified path
@@ -225,6 +810,7 @@ impl<P: Provider> BlockWindowCalculator<P> {
     ///     "/var/cache/block_windows.json"
     /// )?;
     /// ```
+    #[cfg(feature = "disk-cache")]
     pub fn with_disk_cache(
         provider: P,
         cache_path: impl AsRef<Path>,
@@ -289,22 +875,330 @@ impl<P: Provider> BlockWindowCalculator<P> {
         self.cache.stats().await
     }
 
-    /// Fetches the timestamp of a specific block
+    /// Fetches the timestamp and hash of a specific block
+    ///
+    /// Pruned-history errors, and missing blocks below the earliest
+    /// available block, become [`BlockWindowError::HistoryUnavailable`].
     async fn get_block_timestamp(
         &self,
         block_number: BlockNumber,
-    ) -> Result<UnixTimestamp, BlockWindowError> {
-        let span = spans::get_block_timestamp(block_number);
-        let _guard = span.enter();
+    ) -> Result<(UnixTimestamp, BlockHash), BlockWindowError> {
+        let span = spans::get_block_timestamp(&self.spans, block_number);
+        spans::in_span(span, async {
+            self.fetch_block_timestamp(block_number).await.map_err(|e| {
+                let earliest = self.known_earliest_available();
+                let pruned = match &e {
+                    RpcError::BlockNotFound { .. } => {
+                        earliest.is_some_and(|earliest| block_number < earliest)
+                    }
+                    e => e.is_history_unavailable(),
+                };
+                if pruned {
+                    BlockWindowError::history_unavailable(block_number, earliest)
+                } else {
+                    e.into()
+                }
+            })
+        })
+        .await
+    }
+
+    /// Fetches the timestamp and hash of a specific block, through the
+    /// header cache if set, retrying on each fallback provider if that fails
+    async fn fetch_block_timestamp(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<(UnixTimestamp, BlockHash), RpcError> {
+        let error = match self.fetch_block_timestamp_from_primary(block_number).await {
+            Ok(fetched) => return Ok(fetched),
+            Err(e) => e,
+        };
+
+        for (index, fallback) in self.fallbacks.iter().enumerate() {
+            match fetch_header::<AnyNetwork, _>(fallback.as_ref(), block_number).await {
+                Ok(header) => {
+                    debug!(
+                        block_number,
+                        fallback = index,
+                        error = %error,
+                        "Fetched block from fallback provider"
+                    );
+                    if let Some(headers) = &self.header_cache {
+                        headers.insert(header);
+                    }
+                    return Ok((UnixTimestamp::from_u64(header.timestamp), header.hash));
+                }
+                Err(e) => {
+                    debug!(block_number, fallback = index, error = %e, "Fallback provider failed");
+                }
+            }
+        }
+        Err(error)
+    }
+
+    /// Fetches the latest block number, retrying on each fallback provider if
+    /// that fails
+    async fn fetch_latest_block_number(&self) -> Result<BlockNumber, RpcError> {
+        let error = match self.provider.get_block_number().await {
+            Ok(number) => return Ok(number),
+            Err(e) => RpcError::get_block_number_failed(e),
+        };
+
+        for (index, fallback) in self.fallbacks.iter().enumerate() {
+            match fallback.get_block_number().await {
+                Ok(number) => {
+                    debug!(fallback = index, error = %error, "Fetched head from fallback provider");
+                    return Ok(number);
+                }
+                Err(e) => debug!(fallback = index, error = %e, "Fallback provider failed"),
+            }
+        }
+        Err(error)
+    }
+
+    /// Fetches the number and timestamp of the latest block, retrying on each
+    /// fallback provider if that fails
+    async fn fetch_latest_block(&self) -> Result<(BlockNumber, UnixTimestamp), RpcError> {
+        let error = match fetch_latest_block::<Ethereum, _>(&self.provider).await {
+            Ok(latest) => return Ok(latest),
+            Err(e) => e,
+        };
+
+        for (index, fallback) in self.fallbacks.iter().enumerate() {
+            match fetch_latest_block::<AnyNetwork, _>(fallback.as_ref()).await {
+                Ok(latest) => {
+                    debug!(fallback = index, error = %error, "Fetched head from fallback provider");
+                    return Ok(latest);
+                }
+                Err(e) => debug!(fallback = index, error = %e, "Fallback provider failed"),
+            }
+        }
+        Err(error)
+    }
+
+    /// Fetches the timestamp and hash of a specific block from the primary
+    /// provider, through the header cache if set
+    async fn fetch_block_timestamp_from_primary(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<(UnixTimestamp, BlockHash), RpcError> {
+        if let Some(headers) = &self.header_cache {
+            let header = headers
+                .get_or_fetch::<Ethereum, _>(&self.provider, block_number)
+                .await?;
+            return Ok((UnixTimestamp::from_u64(header.timestamp), header.hash));
+        }
 
         let block = self
             .provider
             .get_block_by_number(block_number.into())
             .await
             .map_err(|e| RpcError::get_block_failed(block_number, e))?
-            .ok_or_else(|| RpcError::BlockNotFound { block_number })?;
+            .ok_or(RpcError::BlockNotFound { block_number })?;
+
+        Ok((
+            UnixTimestamp::from_u64(block.header.timestamp),
+            block.header.hash,
+        ))
+    }
+
+    /// Block time statistics for `chain`, from the most recent blocks
+    ///
+    /// Fetches the headers of the latest
+    /// [`with_block_time_sample`](Self::with_block_time_sample) + 1 blocks
+    /// (through the header cache, if set), a few at a time, and measures the
+    /// intervals between them. The result is kept per chain for ten minutes. Daily window
+    /// searches use it to interpolate boundary blocks; it is also useful for
+    /// sizing scans, e.g. converting an hour into a block count.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the latest block number or a header can't be fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let stats = calculator.estimate_block_time(NamedChain::Base).await?;
+    /// let blocks_per_hour = 3600.0 / stats.average.as_secs_f64();
+    /// println!("p90 block time: {:?}", stats.p90);
+    /// ```
+    pub async fn estimate_block_time(
+        &self,
+        chain: NamedChain,
+    ) -> Result<BlockTimeStats, BlockWindowError> {
+        self.estimate_block_time_counted(chain, &mut 0).await
+    }
+
+    /// [`estimate_block_time`](Self::estimate_block_time), adding the blocks
+    /// it requests to `probes`
+    async fn estimate_block_time_counted(
+        &self,
+        chain: NamedChain,
+        probes: &mut u64,
+    ) -> Result<BlockTimeStats, BlockWindowError> {
+        if let Some((measured_at, stats)) = self.block_times.lock().unwrap().get(&chain) {
+            if measured_at.age_since_now() < BLOCK_TIME_TTL {
+                return Ok(*stats);
+            }
+        }
 
-        Ok(UnixTimestamp::from_u64(block.header.timestamp))
+        let latest_block = match self.tracked_head() {
+            Some(head) => head.number,
+            None => {
+                *probes += 1;
+                self.fetch_latest_block_number().await?
+            }
+        };
+        let first_block = latest_block
+            .saturating_sub(self.block_time_sample)
+            .max(self.genesis(chain).map_or(0, |genesis| genesis.first_block));
+        *probes += latest_block - first_block + 1;
+        let timestamps = stream::iter(first_block..=latest_block)
+            .map(|block| self.get_block_timestamp(block))
+            .buffered(BLOCK_TIME_CONCURRENCY)
+            .map_ok(|(ts, _)| ts.as_u64())
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let stats =
+            BlockTimeStats::from_timestamps(latest_block, &timestamps).ok_or_else(|| {
+                RpcError::get_block_number_failed(TransportErrorKind::custom_str(
+                    "chain has fewer than two blocks",
+                ))
+            })?;
+        debug!(
+            chain = %chain,
+            latest_block,
+            average_ms = stats.average.as_millis() as u64,
+            "Estimated block time"
+        );
+        self.block_times
+            .lock()
+            .unwrap()
+            .insert(chain, (TimestampMillis::now(), stats));
+        Ok(stats)
+    }
+
+    /// Narrow the search for `target_ts` to blocks around an interpolated guess
+    ///
+    /// The guess assumes blocks `block_time` apart back from the head. Probes
+    /// step away from it with doubling strides until `lo` is before
+    /// `target_ts` (or `first_block`) and `hi` is after it (or the head), so
+    /// a poor estimate costs a few extra probes rather than a wrong answer.
+    #[allow(clippy::too_many_arguments)]
+    async fn bracket(
+        &self,
+        target_ts: UnixTimestamp,
+        first_block: BlockNumber,
+        latest_block: BlockNumber,
+        head_ts: UnixTimestamp,
+        block_time: Duration,
+        deadline: Option<&OperationDeadline>,
+        probes: &mut u64,
+    ) -> Result<(BlockNumber, BlockNumber), BlockWindowError> {
+        let block_secs = block_time.as_secs_f64();
+        if block_secs <= 0.0 {
+            return Ok((first_block, latest_block));
+        }
+        let behind = (head_ts.0 - target_ts.0).max(0) as f64 / block_secs;
+        let guess = latest_block.saturating_sub(behind as u64).max(first_block);
+        let slack = ((BRACKET_SLACK.as_secs_f64() / block_secs) as u64).max(1);
+
+        let mut lo = guess.saturating_sub(slack).max(first_block);
+        let mut stride = slack;
+        while lo > first_block {
+            *probes += 1;
+            let (ts, _) = within_deadline(deadline, self.get_block_timestamp(lo))
+                .await
+                .ok_or_else(|| deadline_error(deadline))??;
+            if ts < target_ts {
+                break;
+            }
+            stride = stride.saturating_mul(2);
+            lo = lo.saturating_sub(stride).max(first_block);
+        }
+
+        let mut hi = guess.saturating_add(slack).min(latest_block);
+        stride = slack;
+        while hi < latest_block {
+            *probes += 1;
+            let (ts, _) = within_deadline(deadline, self.get_block_timestamp(hi))
+                .await
+                .ok_or_else(|| deadline_error(deadline))??;
+            if ts > target_ts {
+                break;
+            }
+            stride = stride.saturating_mul(2);
+            hi = hi.saturating_add(stride).min(latest_block);
+        }
+
+        debug!(target_ts = %target_ts, guess, lo, hi, "Bracketed block search");
+        Ok((lo, hi))
+    }
+
+    /// The blocks to binary search for `target_ts`, at or above `first_block`
+    ///
+    /// Checkpoints around the target bound it directly. Otherwise the search
+    /// is [`bracket`](Self::bracket)ed from the head when the block time is
+    /// known, starting no lower than the last checkpoint before the target,
+    /// and spans everything up to the head when it isn't.
+    ///
+    /// `block_time` is `None` until a search first needs it, and is then
+    /// estimated once for the searches that follow.
+    #[allow(clippy::too_many_arguments)]
+    async fn search_bounds(
+        &self,
+        chain: NamedChain,
+        target_ts: UnixTimestamp,
+        first_block: BlockNumber,
+        latest_block: BlockNumber,
+        head_ts: UnixTimestamp,
+        block_time: &mut Option<Option<Duration>>,
+        deadline: Option<&OperationDeadline>,
+        probes: &mut u64,
+    ) -> Result<(BlockNumber, BlockNumber), BlockWindowError> {
+        let (before, after) = self
+            .checkpoints
+            .get(&chain)
+            .map_or((None, None), |checkpoints| checkpoints.bounds(target_ts));
+        let lo = before.map_or(first_block, |before| before.max(first_block));
+        if let Some(after) = after {
+            return Ok((lo, after.clamp(lo, latest_block.max(lo))));
+        }
+        let block_time = match *block_time {
+            Some(block_time) => block_time,
+            None => {
+                let estimate = match within_deadline(
+                    deadline,
+                    self.estimate_block_time_counted(chain, probes),
+                )
+                .await
+                .ok_or_else(|| deadline_error(deadline))?
+                {
+                    Ok(stats) => Some(stats.average),
+                    Err(e) => {
+                        debug!(error = %e, "No block time estimate, searching from genesis");
+                        None
+                    }
+                };
+                *block_time.insert(estimate)
+            }
+        };
+        match block_time {
+            Some(block_time) => {
+                self.bracket(
+                    target_ts,
+                    lo,
+                    latest_block,
+                    head_ts,
+                    block_time,
+                    deadline,
+                    probes,
+                )
+                .await
+            }
+            None => Ok((lo, latest_block)),
+        }
     }
 
     /// Binary search to find the first block at or after the target timestamp
@@ -316,7 +1210,8 @@ impl<P: Provider> BlockWindowCalculator<P> {
     /// Uses binary search to efficiently locate the boundary block. The search maintains
     /// the invariant that `result` always points to a block with timestamp >= target_ts.
     ///
-    /// - **Search space**: [0, latest_block]
+    /// - **Search space**: [lo, hi], where blocks before `lo` have timestamp < target_ts
+    ///   and `hi` is the head or has timestamp >= target_ts
     /// - **Invariant**: All blocks < lo have timestamp < target_ts
     /// - **Invariant**: All blocks > hi have timestamp >= target_ts (or unknown)
     /// - **Result**: The smallest block number with timestamp >= target_ts
@@ -328,38 +1223,43 @@ impl<P: Provider> BlockWindowCalculator<P> {
     async fn find_first_block_at_or_after(
         &self,
         target_ts: UnixTimestamp,
-        latest_block: BlockNumber,
-    ) -> Result<BlockNumber, BlockWindowError> {
-        let span = spans::find_first_block_at_or_after(target_ts.as_u64(), latest_block);
-        let _guard = span.enter();
-
-        // Initialize search space: [0, latest_block]
-        let mut lo = 0u64;
-        let mut hi = latest_block;
-        // Default to latest_block if all blocks are >= target_ts
-        let mut result = latest_block;
-
-        while lo <= hi {
-            let mid = (lo + hi) / 2;
-            let ts = self.get_block_timestamp(mid).await?;
-
-            if ts >= target_ts {
-                // Mid block is a candidate - it's at or after target
-                // Keep looking left for earlier blocks that also qualify
-                result = mid;
-                if mid == 0 {
-                    // Can't go lower than block 0
-                    break;
+        (mut lo, mut hi): (BlockNumber, BlockNumber),
+        deadline: Option<&OperationDeadline>,
+        probes: &mut u64,
+    ) -> Result<(BlockNumber, Option<BlockHash>), BlockWindowError> {
+        let span = spans::find_first_block_at_or_after(&self.spans, target_ts.as_u64(), hi);
+        spans::in_span(span, async {
+            // Default to the top of the search space if no block in it is >= target_ts
+            let mut result = hi;
+            let mut result_hash = None;
+
+            while lo <= hi {
+                let mid = (lo + hi) / 2;
+                *probes += 1;
+                let (ts, hash) = within_deadline(deadline, self.get_block_timestamp(mid))
+                    .await
+                    .ok_or_else(|| deadline_error(deadline))??;
+
+                if ts >= target_ts {
+                    // Mid block is a candidate - it's at or after target
+                    // Keep looking left for earlier blocks that also qualify
+                    result = mid;
+                    result_hash = Some(hash);
+                    if mid == 0 {
+                        // Can't go lower than block 0
+                        break;
+                    }
+                    hi = mid - 1;
+                } else {
+                    // Mid block is too early - search right half
+                    lo = mid + 1;
                 }
-                hi = mid - 1;
-            } else {
-                // Mid block is too early - search right half
-                lo = mid + 1;
             }
-        }
 
-        debug!(target_ts = %target_ts, result, "Found first block at or after timestamp");
-        Ok(result)
+            debug!(target_ts = %target_ts, result, "Found first block at or after timestamp");
+            Ok::<_, BlockWindowError>((result, result_hash))
+        })
+        .await
     }
 
     /// Binary search to find the last block at or before the target timestamp
@@ -371,7 +1271,8 @@ impl<P: Provider> BlockWindowCalculator<P> {
     /// Uses binary search to efficiently locate the boundary block. The search maintains
     /// the invariant that `result` always points to a block with timestamp <= target_ts.
     ///
-    /// - **Search space**: [0, latest_block]
+    /// - **Search space**: [lo, hi], where `lo` is block 0 or has timestamp <= target_ts
+    ///   and blocks after `hi` have timestamp > target_ts
     /// - **Invariant**: All blocks < lo have timestamp <= target_ts (or unknown)
     /// - **Invariant**: All blocks > hi have timestamp > target_ts
     /// - **Result**: The largest block number with timestamp <= target_ts
@@ -383,38 +1284,97 @@ impl<P: Provider> BlockWindowCalculator<P> {
     async fn find_last_block_at_or_before(
         &self,
         target_ts: UnixTimestamp,
-        latest_block: BlockNumber,
-    ) -> Result<BlockNumber, BlockWindowError> {
-        let span = spans::find_last_block_at_or_before(target_ts.as_u64(), latest_block);
-        let _guard = span.enter();
-
-        // Initialize search space: [0, latest_block]
-        let mut lo = 0u64;
-        let mut hi = latest_block;
-        // Default to 0 if all blocks are > target_ts
-        let mut result = 0u64;
-
-        while lo <= hi {
-            let mid = (lo + hi) / 2;
-            let ts = self.get_block_timestamp(mid).await?;
-
-            if ts <= target_ts {
-                // Mid block is a candidate - it's at or before target
-                // Keep looking right for later blocks that also qualify
-                result = mid;
-                lo = mid + 1;
-            } else {
-                // Mid block is too late - search left half
-                if mid == 0 {
-                    // Can't go lower than block 0
-                    break;
+        (mut lo, mut hi): (BlockNumber, BlockNumber),
+        deadline: Option<&OperationDeadline>,
+        probes: &mut u64,
+    ) -> Result<(BlockNumber, Option<BlockHash>), BlockWindowError> {
+        let span = spans::find_last_block_at_or_before(&self.spans, target_ts.as_u64(), hi);
+        spans::in_span(span, async {
+            // Default to the bottom of the search space if no block in it is <= target_ts
+            let mut result = lo;
+            let mut result_hash = None;
+
+            while lo <= hi {
+                let mid = (lo + hi) / 2;
+                *probes += 1;
+                let (ts, hash) = within_deadline(deadline, self.get_block_timestamp(mid))
+                    .await
+                    .ok_or_else(|| deadline_error(deadline))??;
+
+                if ts <= target_ts {
+                    // Mid block is a candidate - it's at or before target
+                    // Keep looking right for later blocks that also qualify
+                    result = mid;
+                    result_hash = Some(hash);
+                    lo = mid + 1;
+                } else {
+                    // Mid block is too late - search left half
+                    if mid == 0 {
+                        // Can't go lower than block 0
+                        break;
+                    }
+                    hi = mid - 1;
                 }
-                hi = mid - 1;
             }
-        }
 
-        debug!(target_ts = %target_ts, result, "Found last block at or before timestamp");
-        Ok(result)
+            debug!(target_ts = %target_ts, result, "Found last block at or before timestamp");
+            Ok::<_, BlockWindowError>((result, result_hash))
+        })
+        .await
+    }
+
+    /// Estimates the daily block window for `chain` and `date` without any
+    /// RPC call
+    ///
+    /// Runs [`BlockCheckpoints::estimate_daily_window`] over the
+    /// [checkpoints](Self::with_checkpoints) and [genesis](Self::with_genesis)
+    /// of `chain`, at the block time measured by
+    /// [`estimate_block_time`](Self::estimate_block_time) if still cached,
+    /// otherwise alloy's average block time hint for the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlockWindowError::EstimateUnavailable`] when the chain has
+    /// neither a checkpoint nor a genesis entry, or no block time to
+    /// extrapolate with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let window = calculator.estimate_daily_window(NamedChain::Base, date)?;
+    /// assert!(window.estimated);
+    /// println!("~{} blocks", window.block_count());
+    /// ```
+    pub fn estimate_daily_window(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+    ) -> Result<DailyBlockWindow, BlockWindowError> {
+        let block_time = self
+            .block_times
+            .lock()
+            .unwrap()
+            .get(&chain)
+            .map(|(_, stats)| stats.average)
+            .or_else(|| chain.average_blocktime_hint());
+        let window = match self.checkpoints.get(&chain) {
+            Some(checkpoints) => {
+                checkpoints.estimate_daily_window(date, self.genesis(chain), block_time)
+            }
+            None => BlockCheckpoints::new(chain, Vec::new())?.estimate_daily_window(
+                date,
+                self.genesis(chain),
+                block_time,
+            ),
+        }?;
+        debug!(
+            chain = %chain,
+            date = %date,
+            start_block = window.start_block,
+            end_block = window.end_block,
+            "Estimated daily block window"
+        );
+        Ok(window)
     }
 
     /// Gets (or computes and caches) the daily block window for a specific chain and date
@@ -450,85 +1410,317 @@ impl<P: Provider> BlockWindowCalculator<P> {
         chain: NamedChain,
         date: NaiveDate,
     ) -> Result<DailyBlockWindow, BlockWindowError> {
-        let span = spans::get_daily_window(chain, date);
-        let _guard = span.enter();
+        self.get_daily_window_detailed(chain, date)
+            .await
+            .map(|(window, _)| window)
+    }
 
-        let key = CacheKey::new(chain, date);
+    /// Like [`get_daily_window`](Self::get_daily_window), but also reports how
+    /// the window was obtained
+    ///
+    /// Useful for SLO monitoring: cache hit rate, RPC calls per computed
+    /// window, and how far the provider's head lagged behind wall-clock time.
+    /// On a cache hit the provenance is `cache_hit: true` with zero probes and
+    /// zero head age, since no RPC calls are made.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let (window, provenance) = calculator
+    ///     .get_daily_window_detailed(NamedChain::Base, date)
+    ///     .await?;
+    ///
+    /// if !provenance.cache_hit {
+    ///     metrics.record_probes(provenance.probes);
+    ///     metrics.record_head_age(provenance.head_age);
+    /// }
+    /// ```
+    pub async fn get_daily_window_detailed(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+    ) -> Result<(DailyBlockWindow, WindowProvenance), BlockWindowError> {
+        let span = spans::get_daily_window(&self.spans, chain, date);
+        spans::in_span(span, async {
+            let key = CacheKey::new(chain, date);
+
+            // Check cache first
+            let cached = match self.cache_policy {
+                CachePolicy::BestEffort => self.cache.get(&key).await,
+                CachePolicy::Strict => self.cache.try_get(&key).await?,
+            };
+            if let Some(window) = cached {
+                info!(
+                    chain = %chain,
+                    date = %date,
+                    cache = %self.cache.name(),
+                    cached = true,
+                    "Retrieved daily block window from cache"
+                );
+                return Ok((window, WindowProvenance::cached()));
+            }
+
+            let (start_ts, end_ts_exclusive) = day_bounds(date)?;
+
+            let deadline = OperationDeadline::start(self.operation_deadline_for(chain));
+            let deadline = deadline.as_ref();
+
+            // Get the latest block, tracked or fetched; its timestamp also
+            // gives the head age
+            let mut probes = 0;
+            let (latest_block, head_ts) = match self.tracked_head() {
+                Some(head) => (head.number, head.timestamp),
+                None => {
+                    probes += 1;
+                    within_deadline(deadline, self.fetch_latest_block())
+                        .await
+                        .ok_or_else(|| deadline_error(deadline))??
+                }
+            };
+            let head_age = Duration::from_secs(
+                u64::try_from(Utc::now().timestamp())
+                    .unwrap_or_default()
+                    .saturating_sub(head_ts.as_u64()),
+            );
 
-        // Check cache first
-        if let Some(window) = self.cache.get(&key).await {
             info!(
                 chain = %chain,
                 date = %date,
-                cache = %self.cache.name(),
-                cached = true,
-                "Retrieved daily block window from cache"
+                start_ts = %start_ts,
+                end_ts_exclusive = %end_ts_exclusive,
+                latest_block,
+                head_age_secs = head_age.as_secs(),
+                "Computing daily block window"
             );
-            return Ok(window);
-        }
-
-        // Calculate UTC day boundaries
-        let start_dt = Utc
-            .with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
-            .single()
-            .ok_or_else(|| BlockWindowError::invalid_date_conversion(date))?;
-
-        let end_dt = start_dt
-            .checked_add_signed(chrono::TimeDelta::days(1))
-            .ok_or_else(|| BlockWindowError::date_arithmetic_overflow(date))?;
 
-        let start_ts = UnixTimestamp::from_datetime(start_dt);
-        let end_ts_exclusive = UnixTimestamp::from_datetime(end_dt);
+            // Interpolate from the head when the block time is known, then
+            // binary search within the bracket. The block time is only
+            // estimated once a search isn't bounded by checkpoints.
+            let mut block_time = None;
+            let end_ts = end_ts_exclusive.pred();
+            let genesis = self.genesis(chain);
+            let mut first_block = genesis.map_or(0, |genesis| genesis.first_block);
+
+            // On a pruned provider, blocks before the earliest available one
+            // can't be searched, so a day that may include them can't be
+            // computed
+            if self.probe_history {
+                within_deadline(deadline, self.earliest_available_block())
+                    .await
+                    .ok_or_else(|| deadline_error(deadline))??;
+            }
+            let pruned_below = self
+                .known_earliest_available()
+                .filter(|&earliest| earliest > first_block);
+            if let Some(earliest) = pruned_below {
+                probes += 1;
+                let (earliest_ts, _) =
+                    within_deadline(deadline, self.get_block_timestamp(earliest))
+                        .await
+                        .ok_or_else(|| deadline_error(deadline))??;
+                if earliest_ts >= start_ts {
+                    return Err(BlockWindowError::history_unavailable(
+                        earliest - 1,
+                        Some(earliest),
+                    ));
+                }
+                first_block = earliest;
+            }
 
-        // Get latest block number
-        let latest_block = self
-            .provider
-            .get_block_number()
+            // A day starting at or before genesis starts at the first block,
+            // which needs no search
+            let genesis_start = genesis.filter(|genesis| start_ts <= genesis.timestamp);
+            let (start_block, start_block_hash) = match genesis_start {
+                Some(genesis) => (genesis.first_block, None),
+                None => {
+                    let bounds = self
+                        .search_bounds(
+                            chain,
+                            start_ts,
+                            first_block,
+                            latest_block,
+                            head_ts,
+                            &mut block_time,
+                            deadline,
+                            &mut probes,
+                        )
+                        .await?;
+                    self.find_first_block_at_or_after(start_ts, bounds, deadline, &mut probes)
+                        .await?
+                }
+            };
+
+            // The end is bracketed and searched after the start was found, so
+            // a deadline hit in either step reports the start block
+            let (end_block, end_block_hash) = async {
+                let end_bounds = self
+                    .search_bounds(
+                        chain,
+                        end_ts,
+                        first_block,
+                        latest_block,
+                        head_ts,
+                        &mut block_time,
+                        deadline,
+                        &mut probes,
+                    )
+                    .await?;
+                self.find_last_block_at_or_before(end_ts, end_bounds, deadline, &mut probes)
+                    .await
+            }
             .await
-            .map_err(RpcError::get_block_number_failed)?;
+            .map_err(|e| with_start_block(e, start_block))?;
 
-        info!(
-            chain = %chain,
-            date = %date,
-            start_ts = %start_ts,
-            end_ts_exclusive = %end_ts_exclusive,
-            latest_block,
-            "Computing daily block window"
-        );
+            let window = DailyBlockWindow::new(start_block, end_block, start_ts, end_ts_exclusive)?
+                .with_block_hashes(start_block_hash, end_block_hash);
 
-        // Binary search for block boundaries
-        let start_block = self
-            .find_first_block_at_or_after(start_ts, latest_block)
-            .await?;
+            info!(
+                chain = %chain,
+                date = %date,
+                start_block = window.start_block,
+                end_block = window.end_block,
+                block_count = window.block_count().as_u64(),
+                probes,
+                cache = %self.cache.name(),
+                "Computed daily block window"
+            );
 
-        let end_block = self
-            .find_last_block_at_or_before(end_ts_exclusive.pred(), latest_block)
-            .await?;
+            let mut provenance = Provenance::current().with_probes_used(probes);
+            if let Some(endpoint) = &self.rpc_endpoint {
+                provenance = provenance.with_rpc_endpoint(endpoint);
+            }
 
-        let window = DailyBlockWindow::new(start_block, end_block, start_ts, end_ts_exclusive)?;
+            // Save to cache (errors are only fatal under a strict policy)
+            if let Err(e) = self
+                .cache
+                .insert_with_provenance(key, window.clone(), provenance)
+                .await
+            {
+                if self.cache_policy == CachePolicy::Strict {
+                    return Err(e);
+                }
+                debug!(error = %e, "Failed to cache block window (continuing anyway)");
+            }
 
-        info!(
-            chain = %chain,
-            date = %date,
-            start_block = window.start_block,
-            end_block = window.end_block,
-            block_count = window.block_count().as_u64(),
-            cache = %self.cache.name(),
-            "Computed daily block window"
-        );
+            let provenance = WindowProvenance {
+                cache_hit: false,
+                probes: u32::try_from(probes).unwrap_or(u32::MAX),
+                head_age,
+            };
+            Ok::<_, BlockWindowError>((window, provenance))
+        })
+        .await
+    }
 
-        // Save to cache (ignore errors - caching is best-effort)
-        if let Err(e) = self.cache.insert(key, window.clone()).await {
-            debug!(error = %e, "Failed to cache block window (continuing anyway)");
+    /// Checks whether a window's boundary blocks are still canonical
+    ///
+    /// Re-fetches `start_block` and `end_block` and compares their hashes with
+    /// the ones recorded when the window was computed. A mismatch means a reorg
+    /// replaced a boundary block, and the window should be recomputed (after
+    /// removing it from the cache).
+    ///
+    /// # Returns
+    ///
+    /// - `Some(true)`: every recorded hash still matches
+    /// - `Some(false)`: at least one boundary block has a different hash
+    /// - `None`: the window has no recorded hashes to check
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let window = calculator.get_daily_window(NamedChain::Base, date).await?;
+    /// if calculator.verify_anchors(&window).await? == Some(false) {
+    ///     // A boundary block was reorged out: evict the cached window and recompute
+    /// }
+    /// ```
+    pub async fn verify_anchors(
+        &self,
+        window: &DailyBlockWindow,
+    ) -> Result<Option<bool>, BlockWindowError> {
+        if !window.is_anchored() {
+            return Ok(None);
         }
 
-        Ok(window)
+        for (block, expected) in [
+            (window.start_block, window.start_block_hash),
+            (window.end_block, window.end_block_hash),
+        ] {
+            let Some(expected) = expected else {
+                continue;
+            };
+            let (_, hash) = self.get_block_timestamp(block).await?;
+            if hash != expected {
+                info!(
+                    block,
+                    %expected,
+                    actual = %hash,
+                    "Daily window boundary block is no longer canonical"
+                );
+                return Ok(Some(false));
+            }
+        }
+
+        Ok(Some(true))
+    }
+}
+
+/// Build the error returned when a window calculation runs out of time
+fn deadline_error(deadline: Option<&OperationDeadline>) -> BlockWindowError {
+    BlockWindowError::deadline_exceeded(deadline.map(OperationDeadline::budget).unwrap_or_default())
+}
+
+/// Record the window's already found `start_block` in a deadline error
+fn with_start_block(error: BlockWindowError, start_block: BlockNumber) -> BlockWindowError {
+    match error {
+        BlockWindowError::DeadlineExceeded { deadline, .. } => BlockWindowError::DeadlineExceeded {
+            deadline,
+            start_block: Some(start_block),
+        },
+        other => other,
     }
 }
 
+/// Number and timestamp of `provider`'s latest block
+async fn fetch_latest_block<N: Network, P: Provider<N>>(
+    provider: &P,
+) -> Result<(BlockNumber, UnixTimestamp), RpcError> {
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .map_err(RpcError::get_block_number_failed)?
+        .ok_or_else(|| {
+            RpcError::get_block_number_failed(TransportErrorKind::custom_str(
+                "provider returned no latest block",
+            ))
+        })?;
+    let header = block.header();
+    Ok((header.number(), UnixTimestamp::from_u64(header.timestamp())))
+}
+
+/// The UTC day `date` as its first second and the first second of the next
+/// day
+pub(crate) fn day_bounds(
+    date: NaiveDate,
+) -> Result<(UnixTimestamp, UnixTimestamp), BlockWindowError> {
+    let start_dt = Utc
+        .with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
+        .single()
+        .ok_or_else(|| BlockWindowError::invalid_date_conversion(date))?;
+
+    let end_dt = start_dt
+        .checked_add_signed(chrono::TimeDelta::days(1))
+        .ok_or_else(|| BlockWindowError::date_arithmetic_overflow(date))?;
+
+    Ok((
+        UnixTimestamp::from_datetime(start_dt),
+        UnixTimestamp::from_datetime(end_dt),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blocks::checkpoint::BlockCheckpoint;
 
     #[test]
     fn test_cache_key_display() {
@@ -569,6 +1761,9 @@ mod tests {
             end_block: 1000,
             start_ts: UnixTimestamp(1697328000),
             end_ts_exclusive: UnixTimestamp(1697414400),
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         };
         // Single block: [1000, 1000] contains 1 block
         assert_eq!(single.block_count().as_u64(), 1);
@@ -579,6 +1774,9 @@ mod tests {
             end_block: 100_040_000,
             start_ts: UnixTimestamp(1697328000),
             end_ts_exclusive: UnixTimestamp(1697414400),
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         };
         // Inclusive: [100M, 100M+40k] contains 40,001 blocks
         assert_eq!(large.block_count().as_u64(), 40_001);
@@ -589,6 +1787,9 @@ mod tests {
             end_block: 2000,
             start_ts: UnixTimestamp(1697328000),
             end_ts_exclusive: UnixTimestamp(1697414400),
+            start_block_hash: None,
+            end_block_hash: None,
+            estimated: false,
         };
         // Inclusive count: [1000, 2000] contains 1001 blocks
         assert_eq!(window.block_count().as_u64(), 1001);
@@ -672,4 +1873,541 @@ mod tests {
         let count = window.unwrap().block_count();
         assert_eq!(count.as_u64(), 101);
     }
+
+    #[test]
+    fn test_daily_block_window_serde_format_is_stable() {
+        let window = DailyBlockWindow::new(
+            1000,
+            2000,
+            UnixTimestamp(1728518400),
+            UnixTimestamp(1728604800),
+        )
+        .unwrap();
+
+        let json = serde_json::to_string(&window).unwrap();
+        assert_eq!(
+            json,
+            r#"{"start_block":1000,"end_block":2000,"start_ts":1728518400,"end_ts_exclusive":1728604800}"#
+        );
+
+        let decoded: DailyBlockWindow = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, window);
+    }
+
+    #[test]
+    fn test_daily_block_window_block_hashes_round_trip() {
+        let start_hash = BlockHash::repeat_byte(0x11);
+        let end_hash = BlockHash::repeat_byte(0x22);
+        let window = DailyBlockWindow::new(
+            1000,
+            2000,
+            UnixTimestamp(1728518400),
+            UnixTimestamp(1728604800),
+        )
+        .unwrap();
+        assert!(!window.is_anchored());
+
+        let anchored = window.with_block_hashes(Some(start_hash), Some(end_hash));
+        assert!(anchored.is_anchored());
+
+        let json = serde_json::to_value(&anchored).unwrap();
+        assert_eq!(json["start_block_hash"], serde_json::json!(start_hash));
+        assert_eq!(json["end_block_hash"], serde_json::json!(end_hash));
+
+        let decoded: DailyBlockWindow = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, anchored);
+    }
+
+    #[test]
+    fn test_block_time_stats_from_timestamps() {
+        // Intervals of 2, 2, 2 and 10 seconds
+        let stats = BlockTimeStats::from_timestamps(104, &[100, 102, 104, 106, 116]).unwrap();
+        assert_eq!(stats.latest_block, 104);
+        assert_eq!(stats.intervals, 4);
+        assert_eq!(stats.average, Duration::from_secs(4));
+        assert_eq!(stats.p50, Duration::from_secs(2));
+        assert_eq!(stats.p90, Duration::from_secs(2));
+        assert_eq!(stats.max, Duration::from_secs(10));
+
+        assert_eq!(BlockTimeStats::from_timestamps(0, &[100]), None);
+    }
+
+    #[test]
+    fn test_operation_deadline_read_from_config() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_transport::mock::Asserter;
+
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(Asserter::new());
+        let config = crate::SemioscanConfigBuilder::new()
+            .operation_deadline(Duration::from_secs(600))
+            .chain_operation_deadline(NamedChain::Base, Duration::from_secs(60))
+            .build();
+        let calculator = BlockWindowCalculator::with_memory_cache(provider).with_config(config);
+
+        assert_eq!(
+            calculator.operation_deadline_for(NamedChain::Mainnet),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(
+            calculator.operation_deadline_for(NamedChain::Base),
+            Some(Duration::from_secs(60))
+        );
+
+        // An explicit deadline wins over the config
+        let calculator = calculator.with_operation_deadline(Duration::from_secs(5));
+        assert_eq!(
+            calculator.operation_deadline_for(NamedChain::Base),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_earliest_available_block_on_pruned_provider() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let calculator = BlockWindowCalculator::with_memory_cache(provider);
+        let block = |number| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Head at 4; block 2 is missing, block 3's state is pruned
+        asserter.push_success(&"0x4");
+        asserter.push_success(&None::<Block>);
+        asserter.push_failure_msg("missing trie node 0xabc (path ) state is not available");
+        asserter.push_success(&Some(block(4)));
+        assert_eq!(calculator.earliest_available_block().await.unwrap(), 4);
+
+        // Remembered, and used to classify missing blocks below it
+        assert_eq!(calculator.earliest_available_block().await.unwrap(), 4);
+        asserter.push_success(&None::<Block>);
+        assert!(matches!(
+            calculator.get_block_timestamp(1).await,
+            Err(BlockWindowError::HistoryUnavailable {
+                needed_block: 1,
+                earliest_available: Some(4),
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_probed_earliest_available_block_expires() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let calculator = BlockWindowCalculator::with_memory_cache(provider);
+        let block = |number| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        *calculator.earliest_available.lock().unwrap() = Some(EarliestAvailable {
+            block: 1,
+            probed_at: Some(TimestampMillis::from_millis(0)),
+        });
+
+        // The stale probe is ignored and the node, which has since pruned
+        // block 1, is searched again
+        assert_eq!(calculator.known_earliest_available(), None);
+        asserter.push_success(&"0x2");
+        asserter.push_success(&None::<Block>);
+        asserter.push_success(&Some(block(2)));
+        assert_eq!(calculator.earliest_available_block().await.unwrap(), 2);
+        assert_eq!(calculator.known_earliest_available(), Some(2));
+
+        // A declared block never expires
+        let calculator = calculator.with_earliest_available_block(3);
+        assert_eq!(calculator.known_earliest_available(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_with_head_reads_the_tracked_head_until_it_is_stale() {
+        use alloy_primitives::B256;
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let now = Utc::now().timestamp();
+        let head = |number: BlockNumber, timestamp| ChainHead {
+            number,
+            hash: B256::repeat_byte(number as u8),
+            parent_hash: B256::ZERO,
+            timestamp: UnixTimestamp(timestamp),
+        };
+        let (sender, receiver) = watch::channel(Some(head(4, now)));
+        let calculator = BlockWindowCalculator::with_memory_cache(provider)
+            .with_head(receiver)
+            .with_head_max_age(Duration::from_secs(30));
+        let block = |number| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // The search starts from the tracked head at 4 without fetching it
+        asserter.push_success(&Some(block(2)));
+        asserter.push_success(&Some(block(0)));
+        assert_eq!(calculator.earliest_available_block().await.unwrap(), 0);
+
+        // A head older than the max age is ignored, a fresh one used again
+        sender.send_replace(Some(head(5, now - 3_600)));
+        assert_eq!(calculator.tracked_head(), None);
+        sender.send_replace(Some(head(6, now)));
+        assert_eq!(calculator.tracked_head(), Some(head(6, now)));
+
+        // A stopped tracker's last head is ignored, however recent
+        drop(sender);
+        assert_eq!(calculator.tracked_head(), None);
+    }
+
+    #[tokio::test]
+    async fn test_verify_anchors_detects_replaced_boundary_blocks() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let calculator = BlockWindowCalculator::with_memory_cache(provider);
+        let block = |number, hash| Block {
+            header: Header {
+                hash,
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let start_hash = BlockHash::repeat_byte(0x10);
+        let end_hash = BlockHash::repeat_byte(0x20);
+        let window =
+            DailyBlockWindow::new(10, 20, UnixTimestamp(1_000), UnixTimestamp(2_000)).unwrap();
+
+        // Windows without hashes can't be checked and make no requests
+        assert_eq!(calculator.verify_anchors(&window).await.unwrap(), None);
+
+        let window = window.with_block_hashes(Some(start_hash), Some(end_hash));
+        asserter.push_success(&Some(block(10, start_hash)));
+        asserter.push_success(&Some(block(20, end_hash)));
+        assert_eq!(
+            calculator.verify_anchors(&window).await.unwrap(),
+            Some(true)
+        );
+
+        // The end block was reorged out
+        asserter.push_success(&Some(block(10, start_hash)));
+        asserter.push_success(&Some(block(20, BlockHash::repeat_byte(0x21))));
+        assert_eq!(
+            calculator.verify_anchors(&window).await.unwrap(),
+            Some(false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_retries_on_fallback_provider() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+        use std::sync::Arc;
+
+        let primary = Asserter::new();
+        let fallback = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(primary.clone());
+        let calculator =
+            BlockWindowCalculator::with_memory_cache(provider).with_fallback_provider(Arc::new(
+                ProviderBuilder::default()
+                    .network::<AnyNetwork>()
+                    .connect_mocked_client(fallback.clone()),
+            ));
+        let block = Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number: 7,
+                    timestamp: 1_007,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        primary.push_failure_msg("upstream connect error");
+        fallback.push_success(&Some(block));
+        let (ts, _) = calculator.get_block_timestamp(7).await.unwrap();
+        assert_eq!(ts, UnixTimestamp(1_007));
+
+        // With every provider failing, the primary's error is returned
+        primary.push_failure_msg("upstream connect error");
+        fallback.push_failure_msg("rate limited");
+        let error = calculator.get_block_timestamp(7).await.unwrap_err();
+        assert!(error.to_string().contains("block 7"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_head_retries_on_fallback_but_history_probe_does_not() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+        use std::sync::Arc;
+
+        let primary = Asserter::new();
+        let fallback = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(primary.clone());
+        let calculator =
+            BlockWindowCalculator::with_memory_cache(provider).with_fallback_provider(Arc::new(
+                ProviderBuilder::default()
+                    .network::<AnyNetwork>()
+                    .connect_mocked_client(fallback.clone()),
+            ));
+        let block = |number| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        primary.push_failure_msg("upstream connect error");
+        fallback.push_success(&Some(block(9)));
+        assert_eq!(
+            calculator.fetch_latest_block().await.unwrap(),
+            (9, UnixTimestamp(1_009))
+        );
+
+        // The head comes from the fallback, but block 1, which only the
+        // fallback still serves, doesn't count as available
+        primary.push_failure_msg("upstream connect error");
+        fallback.push_success(&"0x2");
+        primary.push_success(&None::<Block>);
+        fallback.push_success(&Some(block(1)));
+        primary.push_success(&Some(block(2)));
+        assert_eq!(calculator.earliest_available_block().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_block_time_sample_counts_its_requests() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let calculator =
+            BlockWindowCalculator::with_memory_cache(provider).with_block_time_sample(3);
+        let block = |number: u64| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + 2 * number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // The head, then the sampled blocks in order
+        asserter.push_success(&"0x64");
+        for number in 97..=100 {
+            asserter.push_success(&Some(block(number)));
+        }
+        let mut probes = 0;
+        let stats = calculator
+            .estimate_block_time_counted(NamedChain::Base, &mut probes)
+            .await
+            .unwrap();
+        assert_eq!(stats.intervals, 3);
+        assert_eq!(stats.average, Duration::from_secs(2));
+        assert_eq!(probes, 5);
+
+        // A fresh estimate is reused without requests
+        let mut probes = 0;
+        calculator
+            .estimate_block_time_counted(NamedChain::Base, &mut probes)
+            .await
+            .unwrap();
+        assert_eq!(probes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bracket_widens_around_a_poor_guess() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let calculator = BlockWindowCalculator::with_memory_cache(provider);
+        // Blocks two seconds apart; the target is block 5,000
+        let block = |number: u64| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + 2 * number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let head_ts = UnixTimestamp(21_000);
+        let target_ts = UnixTimestamp(11_000);
+
+        // An accurate estimate brackets the guess within the slack
+        asserter.push_success(&Some(block(4_700)));
+        asserter.push_success(&Some(block(5_300)));
+        let mut probes = 0;
+        let bounds = calculator
+            .bracket(
+                target_ts,
+                0,
+                10_000,
+                head_ts,
+                Duration::from_secs(2),
+                None,
+                &mut probes,
+            )
+            .await
+            .unwrap();
+        assert_eq!(bounds, (4_700, 5_300));
+        assert_eq!(probes, 2);
+
+        // Overestimating the block time guesses block 7,500 with half the
+        // slack, and `lo` steps back with doubling strides until it is
+        // before the target
+        for number in [7_350, 7_050, 6_450, 5_250, 2_850, 7_650] {
+            asserter.push_success(&Some(block(number)));
+        }
+        let mut probes = 0;
+        let bounds = calculator
+            .bracket(
+                target_ts,
+                0,
+                10_000,
+                head_ts,
+                Duration::from_secs(4),
+                None,
+                &mut probes,
+            )
+            .await
+            .unwrap();
+        assert_eq!(bounds, (2_850, 7_650));
+        assert_eq!(probes, 6);
+    }
+
+    #[test]
+    fn test_deadline_after_start_block_reports_it() {
+        let deadline = Duration::from_secs(30);
+
+        assert!(matches!(
+            with_start_block(BlockWindowError::deadline_exceeded(deadline), 1_234),
+            BlockWindowError::DeadlineExceeded {
+                start_block: Some(1_234),
+                ..
+            }
+        ));
+        assert!(matches!(
+            with_start_block(
+                BlockWindowError::estimate_unavailable(NamedChain::Base),
+                1_234
+            ),
+            BlockWindowError::EstimateUnavailable { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoints_narrow_daily_window_probes() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_rpc_types::{Block, Header};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        // Blocks two seconds apart; 2023-11-15 spans blocks 3,200 to 46,399
+        let ts = |number: BlockNumber| 1_700_000_000 + 2 * number as i64;
+        let block = |number| Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: ts(number) as u64,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let checkpoint = |block| BlockCheckpoint {
+            block,
+            timestamp: UnixTimestamp(ts(block)),
+        };
+        let checkpoints = BlockCheckpoints::new(
+            NamedChain::Base,
+            [3_199, 3_201, 46_399, 46_400]
+                .into_iter()
+                .map(checkpoint)
+                .collect(),
+        )
+        .unwrap();
+        let calculator =
+            BlockWindowCalculator::with_memory_cache(provider).with_checkpoints(checkpoints);
+
+        // The head, then two probes between the checkpoints around each
+        // boundary instead of a search from the head. Neither search needs
+        // the block time, so none is sampled.
+        for number in [50_000, 3_200, 3_199, 46_399, 46_400] {
+            asserter.push_success(&Some(block(number)));
+        }
+        let date = NaiveDate::from_ymd_opt(2023, 11, 15).unwrap();
+        let (window, provenance) = calculator
+            .get_daily_window_detailed(NamedChain::Base, date)
+            .await
+            .unwrap();
+        assert_eq!((window.start_block, window.end_block), (3_200, 46_399));
+        assert_eq!(provenance.probes, 5);
+    }
 }
@@ -7,12 +7,18 @@
 //! This module provides a generic caching mechanism for any data that is keyed by
 //! block ranges. It supports automatic merging of overlapping ranges and gap detection
 //! to identify uncached regions.
+//!
+//! Entries reaching past the finalized block can be inserted as provisional,
+//! with a [`Checkpoint`] of their last block's hash. [`BlockRangeCache::settle`]
+//! later confirms them or drops them if that block was reorged out.
 
 use std::cmp::{max, min};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::hash::Hash;
 
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{BlockHash, BlockNumber};
+
+use crate::cache::finality::{Checkpoint, Reconciliation};
 
 /// Trait for values that can be merged when overlapping cache entries are combined
 pub trait Mergeable {
@@ -35,13 +41,29 @@ pub trait Mergeable {
 /// - **Range queries**: Retrieve cached data that fully contains a requested range
 /// - **Auto-merging**: Overlapping inserts are automatically merged
 /// - **Gap detection**: Calculate precisely which blocks are not yet cached
+/// - **Provisional entries**: Ranges touching unfinalized blocks are checked
+///   against the canonical chain once finality passes them
+///
+/// Ranges are indexed by start block per key, so containment and overlap
+/// queries take `O(log n)` plus the number of overlapping ranges, even with
+/// thousands of ranges cached for one key.
 #[derive(Debug, Clone, Default)]
 pub struct BlockRangeCache<K, V>
 where
     K: Clone + Eq + Hash,
     V: Mergeable + Clone,
 {
-    cache: HashMap<(K, BlockNumber, BlockNumber), V>,
+    /// Per-key ranges keyed by start block, mapping to `(end_block, value)`
+    ///
+    /// Inserts merge overlapping ranges, so the ranges stored for one key never
+    /// overlap and their end blocks are ordered like their start blocks. Lookups
+    /// rely on that to walk the tree instead of scanning every entry.
+    cache: HashMap<K, BTreeMap<BlockNumber, (BlockNumber, V)>>,
+    /// Checkpoints of provisional entries, keyed like `cache`
+    ///
+    /// Merging ranges unions their checkpoints, so an entry stays provisional
+    /// until every block it was computed against is final.
+    provisional: HashMap<K, BTreeMap<BlockNumber, Vec<Checkpoint>>>,
 }
 
 impl<K, V> BlockRangeCache<K, V>
@@ -66,38 +88,34 @@ where
     /// - `Some(result)`: Cached data that covers `[start_block, end_block]`
     /// - `None`: No cached entry fully contains this range
     pub fn get(&self, key: &K, start_block: BlockNumber, end_block: BlockNumber) -> Option<V> {
-        // First check for exact match
-        if let Some(result) = self.cache.get(&(key.clone(), start_block, end_block)) {
-            return Some(result.clone());
-        }
-
-        // Check for cached results that fully cover our requested range
-        for ((cached_key, cached_start, cached_end), result) in &self.cache {
-            if cached_key == key && *cached_start <= start_block && *cached_end >= end_block {
-                return Some(result.clone());
-            }
-        }
+        // Only the last range starting at or before `start_block` can contain the request
+        let (_, (cached_end, result)) = self.cache.get(key)?.range(..=start_block).next_back()?;
 
-        None
+        (*cached_end >= end_block).then(|| result.clone())
     }
 
     /// Find all cached results that overlap with the requested range
+    ///
+    /// Returns `(start_block, end_block, value)` tuples sorted by start block.
     fn find_overlapping(
         &self,
         key: &K,
         start_block: BlockNumber,
         end_block: BlockNumber,
-    ) -> Vec<((K, BlockNumber, BlockNumber), &V)> {
-        let mut overlapping = Vec::new();
-
-        for (cache_key @ (cached_key, cached_start, cached_end), result) in &self.cache {
-            if cached_key == key && !(*cached_end < start_block || *cached_start > end_block) {
-                overlapping.push((cache_key.clone(), result));
-            }
-        }
-
-        // Sort by start block to make merging easier
-        overlapping.sort_by_key(|((_, start, _), _)| *start);
+    ) -> Vec<(BlockNumber, BlockNumber, &V)> {
+        let Some(ranges) = self.cache.get(key) else {
+            return Vec::new();
+        };
+
+        // Walk back from the last range starting within the request; end blocks
+        // decrease along the way, so stop at the first range ending before it
+        let mut overlapping: Vec<_> = ranges
+            .range(..=end_block)
+            .rev()
+            .take_while(|(_, (cached_end, _))| *cached_end >= start_block)
+            .map(|(cached_start, (cached_end, result))| (*cached_start, *cached_end, result))
+            .collect();
+        overlapping.reverse();
 
         overlapping
     }
@@ -117,39 +135,122 @@ where
     /// * `end_block` - End of block range (inclusive)
     /// * `value` - Data for this range
     pub fn insert(&mut self, key: K, start_block: BlockNumber, end_block: BlockNumber, value: V) {
-        // Find overlapping results
-        let overlapping = self.find_overlapping(&key, start_block, end_block);
+        self.insert_with_checkpoints(key, start_block, end_block, value, Vec::new());
+    }
 
-        if overlapping.is_empty() {
-            // No overlap, simple insert
-            self.cache.insert((key, start_block, end_block), value);
-            return;
-        }
+    /// Insert a result computed against unfinalized blocks
+    ///
+    /// Behaves like [`insert`](Self::insert), but the merged entry stays
+    /// provisional until [`settle`](Self::settle) confirms `checkpoint`.
+    pub fn insert_provisional(
+        &mut self,
+        key: K,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        value: V,
+        checkpoint: Checkpoint,
+    ) {
+        self.insert_with_checkpoints(key, start_block, end_block, value, vec![checkpoint]);
+    }
+
+    fn insert_with_checkpoints(
+        &mut self,
+        key: K,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        value: V,
+        mut checkpoints: Vec<Checkpoint>,
+    ) {
+        // Find overlapping ranges
+        let overlapping: Vec<(BlockNumber, BlockNumber)> = self
+            .find_overlapping(&key, start_block, end_block)
+            .into_iter()
+            .map(|(cached_start, cached_end, _)| (cached_start, cached_end))
+            .collect();
+
+        let ranges = self.cache.entry(key.clone()).or_default();
+        let mut provisional = self.provisional.remove(&key).unwrap_or_default();
 
-        // There's overlap - we need to merge results
+        // Merge and remove all overlapping results
         let mut merged_value = value;
         let mut min_start = start_block;
         let mut max_end = end_block;
 
-        // Collect keys to remove after merging
-        let keys_to_remove: Vec<(K, BlockNumber, BlockNumber)> =
-            overlapping.iter().map(|(k, _)| k.clone()).collect();
-
-        // Merge all overlapping results
-        for ((_, cached_start, cached_end), cached_value) in overlapping {
+        for (cached_start, cached_end) in overlapping {
             min_start = min(min_start, cached_start);
             max_end = max(max_end, cached_end);
 
-            merged_value.merge(cached_value);
+            if let Some((_, cached_value)) = ranges.remove(&cached_start) {
+                merged_value.merge(&cached_value);
+            }
+            checkpoints.extend(provisional.remove(&cached_start).unwrap_or_default());
         }
 
-        // Remove old entries
-        for cache_key in keys_to_remove {
-            self.cache.remove(&cache_key);
+        // Insert the merged result
+        ranges.insert(min_start, (max_end, merged_value));
+        if !checkpoints.is_empty() {
+            checkpoints.sort_by_key(|checkpoint| checkpoint.block_number);
+            checkpoints.dedup();
+            provisional.insert(min_start, checkpoints);
+        }
+        if !provisional.is_empty() {
+            self.provisional.insert(key, provisional);
         }
+    }
 
-        // Insert the merged result
-        self.cache.insert((key, min_start, max_end), merged_value);
+    /// Number of provisional entries
+    pub fn provisional_len(&self) -> usize {
+        self.provisional.values().map(BTreeMap::len).sum()
+    }
+
+    /// Checkpoint blocks at or below `finalized_block`
+    ///
+    /// Their canonical hashes are what [`settle`](Self::settle) needs.
+    pub fn pending_checkpoints(&self, finalized_block: BlockNumber) -> BTreeSet<BlockNumber> {
+        self.provisional
+            .values()
+            .flat_map(BTreeMap::values)
+            .flatten()
+            .map(|checkpoint| checkpoint.block_number)
+            .filter(|block_number| *block_number <= finalized_block)
+            .collect()
+    }
+
+    /// Check provisional entries against the canonical hashes of final blocks
+    ///
+    /// An entry with a checkpoint whose hash differs from `canonical` is
+    /// removed, so its range counts as a gap again. Checkpoints that match
+    /// are cleared, and an entry left without any becomes final. Checkpoints
+    /// for blocks missing from `canonical` are kept for a later call.
+    pub fn settle(&mut self, canonical: &HashMap<BlockNumber, BlockHash>) -> Reconciliation {
+        let mut reconciliation = Reconciliation::default();
+
+        for (key, entries) in &mut self.provisional {
+            entries.retain(|start, checkpoints| {
+                let reorged = checkpoints.iter().any(|checkpoint| {
+                    canonical
+                        .get(&checkpoint.block_number)
+                        .is_some_and(|hash| *hash != checkpoint.hash)
+                });
+                if reorged {
+                    if let Some(ranges) = self.cache.get_mut(key) {
+                        ranges.remove(start);
+                    }
+                    reconciliation.invalidated += 1;
+                    return false;
+                }
+
+                checkpoints.retain(|checkpoint| !canonical.contains_key(&checkpoint.block_number));
+                if checkpoints.is_empty() {
+                    reconciliation.confirmed += 1;
+                }
+                !checkpoints.is_empty()
+            });
+        }
+        self.provisional.retain(|_, entries| !entries.is_empty());
+        self.cache.retain(|_, ranges| !ranges.is_empty());
+
+        reconciliation
     }
 
     /// Calculate uncached block ranges (gaps) and return merged cached data
@@ -200,24 +301,15 @@ where
 
         // Merge the overlapping results
         let mut merged_result = create_empty();
-        for (_, result) in &overlapping {
+        for (_, _, result) in &overlapping {
             merged_result.merge(result);
         }
 
-        // Identify gaps by tracking covered ranges
-        let mut covered_ranges: Vec<(BlockNumber, BlockNumber)> = overlapping
-            .iter()
-            .map(|((_, block_start, block_end), _)| (*block_start, *block_end))
-            .collect();
-
-        // Sort by start block
-        covered_ranges.sort_by_key(|(start, _)| *start);
-
         // Find gaps
         let mut gaps = vec![];
         let mut current = start_block;
 
-        for (range_start, range_end) in covered_ranges {
+        for (range_start, range_end, _) in overlapping {
             if current < range_start {
                 // Found a gap
                 gaps.push((current, range_start - 1));
@@ -234,14 +326,49 @@ where
         (Some(merged_result), gaps)
     }
 
+    /// Remove every entry overlapping `[start_block, end_block]`
+    ///
+    /// Inserting merges overlapping entries by adding their values, so a
+    /// value recomputed for blocks that are already cached has to replace
+    /// them: remove the overlapping entries first, then insert it. Removed
+    /// entries reaching outside the range are dropped whole.
+    pub fn remove_overlapping(
+        &mut self,
+        key: &K,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) {
+        let overlapping: Vec<BlockNumber> = self
+            .find_overlapping(key, start_block, end_block)
+            .into_iter()
+            .map(|(cached_start, _, _)| cached_start)
+            .collect();
+        if let Some(ranges) = self.cache.get_mut(key) {
+            for cached_start in &overlapping {
+                ranges.remove(cached_start);
+            }
+            if ranges.is_empty() {
+                self.cache.remove(key);
+            }
+        }
+        if let Some(provisional) = self.provisional.get_mut(key) {
+            for cached_start in &overlapping {
+                provisional.remove(cached_start);
+            }
+            if provisional.is_empty() {
+                self.provisional.remove(key);
+            }
+        }
+    }
+
     /// Get the total number of cached entries
     pub fn len(&self) -> usize {
-        self.cache.len()
+        self.cache.values().map(BTreeMap::len).sum()
     }
 
     /// Check if the cache contains no entries
     pub fn is_empty(&self) -> bool {
-        self.cache.is_empty()
+        self.cache.values().all(BTreeMap::is_empty)
     }
 
     /// Clear all entries matching a predicate on the key
@@ -249,8 +376,14 @@ where
     where
         F: FnMut(&K, BlockNumber, BlockNumber) -> bool,
     {
-        self.cache
-            .retain(|(key, start, end), _| predicate(key, *start, *end));
+        for (key, ranges) in &mut self.cache {
+            ranges.retain(|start, (end, _)| predicate(key, *start, *end));
+            if let Some(entries) = self.provisional.get_mut(key) {
+                entries.retain(|start, _| ranges.contains_key(start));
+            }
+        }
+        self.cache.retain(|_, ranges| !ranges.is_empty());
+        self.provisional.retain(|_, entries| !entries.is_empty());
     }
 }
 
@@ -400,6 +533,21 @@ mod tests {
         assert_eq!(merged.total, 1300); // 500 + 800
     }
 
+    #[test]
+    fn test_remove_overlapping_lets_a_recomputed_range_replace_cached_ones() {
+        let mut cache: BlockRangeCache<&str, TestValue> = BlockRangeCache::default();
+        cache.insert("key", 100, 200, TestValue::new(1, 10));
+        cache.insert("key", 201, 300, TestValue::new(0, 0));
+        cache.insert("key", 400, 500, TestValue::new(2, 20));
+
+        cache.remove_overlapping(&"key", 150, 250);
+        cache.insert("key", 100, 300, TestValue::new(1, 10));
+
+        assert_eq!(cache.get(&"key", 100, 300), Some(TestValue::new(1, 10)));
+        assert_eq!(cache.get(&"key", 400, 500), Some(TestValue::new(2, 20)));
+        assert_eq!(cache.len(), 2);
+    }
+
     #[test]
     fn test_len_and_is_empty() {
         let mut cache: BlockRangeCache<String, TestValue> = BlockRangeCache::default();
@@ -427,4 +575,117 @@ mod tests {
         assert!(cache.get(&key1, 100, 200).is_some());
         assert!(cache.get(&key2, 300, 400).is_none());
     }
+
+    #[test]
+    fn test_insert_bridging_several_ranges_merges_all() {
+        let mut cache = BlockRangeCache::default();
+        let key = "test".to_string();
+
+        cache.insert(key.clone(), 100, 150, TestValue::new(1, 10));
+        cache.insert(key.clone(), 200, 250, TestValue::new(1, 20));
+        cache.insert(key.clone(), 300, 350, TestValue::new(1, 30));
+        cache.insert(key.clone(), 500, 550, TestValue::new(1, 50));
+
+        // Overlaps the first three ranges but not the last
+        cache.insert(key.clone(), 140, 310, TestValue::new(1, 5));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key, 100, 350), Some(TestValue::new(4, 65)));
+        assert_eq!(cache.get(&key, 500, 550), Some(TestValue::new(1, 50)));
+    }
+
+    #[test]
+    fn test_lookups_with_many_ranges() {
+        let mut cache = BlockRangeCache::default();
+        let key = "test".to_string();
+
+        // Cache [0, 99], [200, 299], ... leaving a 100-block gap after each
+        for i in 0..1_000u64 {
+            cache.insert(key.clone(), i * 200, i * 200 + 99, TestValue::new(1, i));
+        }
+
+        assert_eq!(cache.len(), 1_000);
+        assert_eq!(
+            cache.get(&key, 50_020, 50_080),
+            Some(TestValue::new(1, 250))
+        );
+        assert!(cache.get(&key, 50_090, 50_110).is_none());
+
+        let (result, gaps) = cache.calculate_gaps(&key, 1_050, 1_650, TestValue::default);
+        assert_eq!(result, Some(TestValue::new(4, 5 + 6 + 7 + 8)));
+        assert_eq!(gaps, vec![(1_100, 1_199), (1_300, 1_399), (1_500, 1_599)]);
+    }
+
+    #[test]
+    fn test_keys_do_not_share_ranges() {
+        let mut cache = BlockRangeCache::default();
+
+        cache.insert("a".to_string(), 100, 200, TestValue::new(1, 1));
+        cache.insert("b".to_string(), 150, 250, TestValue::new(2, 2));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.get(&"a".to_string(), 100, 200),
+            Some(TestValue::new(1, 1))
+        );
+        assert!(cache.get(&"a".to_string(), 100, 250).is_none());
+    }
+
+    fn checkpoint(block_number: BlockNumber, byte: u8) -> Checkpoint {
+        Checkpoint {
+            block_number,
+            hash: BlockHash::repeat_byte(byte),
+        }
+    }
+
+    #[test]
+    fn test_settle_confirms_matching_checkpoints() {
+        let mut cache = BlockRangeCache::default();
+        cache.insert_provisional("a", 100, 200, TestValue::new(1, 10), checkpoint(200, 1));
+
+        // Not final yet: nothing to check
+        assert!(cache.pending_checkpoints(150).is_empty());
+        assert_eq!(cache.pending_checkpoints(200), BTreeSet::from([200]));
+
+        let reconciliation = cache.settle(&HashMap::from([(200, BlockHash::repeat_byte(1))]));
+
+        assert_eq!(reconciliation.confirmed, 1);
+        assert_eq!(reconciliation.invalidated, 0);
+        assert_eq!(cache.provisional_len(), 0);
+        assert!(cache.get(&"a", 100, 200).is_some());
+    }
+
+    #[test]
+    fn test_settle_drops_reorged_entries() {
+        let mut cache = BlockRangeCache::default();
+        cache.insert("a", 0, 99, TestValue::new(1, 10));
+        // Merges with the final range above, which goes with it on a reorg
+        cache.insert_provisional("a", 100, 200, TestValue::new(1, 10), checkpoint(200, 1));
+
+        let reconciliation = cache.settle(&HashMap::from([(200, BlockHash::repeat_byte(2))]));
+
+        assert_eq!(reconciliation.invalidated, 1);
+        assert!(cache.is_empty());
+        let (cached, gaps) = cache.calculate_gaps(&"a", 0, 200, TestValue::default);
+        assert!(cached.is_none());
+        assert_eq!(gaps, vec![(0, 200)]);
+    }
+
+    #[test]
+    fn test_merged_entries_stay_provisional_until_every_checkpoint_settles() {
+        let mut cache = BlockRangeCache::default();
+        cache.insert_provisional("a", 100, 200, TestValue::new(1, 10), checkpoint(200, 1));
+        cache.insert_provisional("a", 150, 300, TestValue::new(1, 10), checkpoint(300, 3));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.provisional_len(), 1);
+
+        let reconciliation = cache.settle(&HashMap::from([(200, BlockHash::repeat_byte(1))]));
+        assert_eq!(reconciliation, Reconciliation::default());
+        assert_eq!(cache.provisional_len(), 1);
+
+        let reconciliation = cache.settle(&HashMap::from([(300, BlockHash::repeat_byte(3))]));
+        assert_eq!(reconciliation.confirmed, 1);
+        assert_eq!(cache.provisional_len(), 0);
+        assert!(cache.get(&"a", 100, 300).is_some());
+    }
 }
@@ -0,0 +1,248 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contract deployment block discovery
+//!
+//! Scans that start at block 0 because a token's deployment block is unknown
+//! spend most of their requests on history where the contract didn't exist.
+//! [`find_deployment_block`] binary searches `eth_getCode` for the first block
+//! with code at an address, and a [`DeploymentCache`] shared between
+//! calculators (it is cheap to clone, and clones share entries) remembers the
+//! answer per `(chain, address)` so each contract is searched once.
+//!
+//! The search needs historical state, so it must run against an archive node.
+//! Contracts that self-destructed and were redeployed at the same address
+//! report their latest deployment.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{DeploymentCache, GasCostCalculator, PriceCalculator};
+//!
+//! let deployments = DeploymentCache::new();
+//! let gas = GasCostCalculator::new(provider.clone()).with_deployment_cache(deployments.clone());
+//! let prices = PriceCalculator::new(provider, chain, usdc, source)
+//!     .with_deployment_cache(deployments);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use alloy_chains::NamedChain;
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber};
+use alloy_provider::Provider;
+use tracing::debug;
+
+use crate::errors::RpcError;
+
+/// Shared cache of contract deployment blocks keyed by `(chain, address)`
+///
+/// Only found deployments are cached; an address without code is searched
+/// again next time, since it may have been deployed since.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentCache {
+    blocks: Arc<Mutex<HashMap<(NamedChain, Address), BlockNumber>>>,
+}
+
+impl DeploymentCache {
+    /// An empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached deployment block of `address` on `chain`, if present
+    pub fn get(&self, chain: NamedChain, address: Address) -> Option<BlockNumber> {
+        self.blocks.lock().unwrap().get(&(chain, address)).copied()
+    }
+
+    /// Record that `address` on `chain` was deployed in `block`
+    pub fn insert(&self, chain: NamedChain, address: Address, block: BlockNumber) {
+        self.blocks.lock().unwrap().insert((chain, address), block);
+    }
+
+    /// The deployment block of `address`, searched with `provider` on a miss
+    ///
+    /// Returns `None` when `address` has no code at the latest block, as for
+    /// externally owned accounts.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the latest block number or any `eth_getCode` lookup fails.
+    pub async fn find_deployment_block<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+        chain: NamedChain,
+        address: Address,
+    ) -> Result<Option<BlockNumber>, RpcError> {
+        if let Some(block) = self.get(chain, address) {
+            return Ok(Some(block));
+        }
+        let block = find_deployment_block(provider, address).await?;
+        if let Some(block) = block {
+            self.insert(chain, address, block);
+        }
+        Ok(block)
+    }
+
+    /// Raise `start_block` to the latest deployment block among `addresses`
+    ///
+    /// Addresses without code don't constrain the range. Returns `None` when
+    /// the range ends before a contract existed, so there is nothing to scan.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a deployment block search fails.
+    pub(crate) async fn clamp_start<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+        chain: NamedChain,
+        addresses: &[Address],
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<Option<BlockNumber>, RpcError> {
+        let mut start = start_block;
+        for &address in addresses {
+            if let Some(deployed) = self.find_deployment_block(provider, chain, address).await? {
+                start = start.max(deployed);
+            }
+        }
+        if start > start_block {
+            debug!(
+                ?chain,
+                start_block,
+                clamped_start = start,
+                "Clamped scan start to contract deployment"
+            );
+        }
+        Ok((start <= end_block).then_some(start))
+    }
+}
+
+/// The first block in which `address` has code, uncached
+///
+/// Binary searches `eth_getCode` between genesis and the latest block, which
+/// takes about `log2(latest)` requests. Returns `None` when `address` has no
+/// code at the latest block.
+///
+/// # Errors
+///
+/// Fails if the latest block number or any `eth_getCode` lookup fails.
+pub async fn find_deployment_block<N: Network, P: Provider<N>>(
+    provider: &P,
+    address: Address,
+) -> Result<Option<BlockNumber>, RpcError> {
+    let latest = provider
+        .get_block_number()
+        .await
+        .map_err(RpcError::get_block_number_failed)?;
+    if !has_code(provider, address, latest).await? {
+        return Ok(None);
+    }
+
+    // Invariant: `address` has code at `high`
+    let (mut low, mut high) = (0, latest);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if has_code(provider, address, mid).await? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(Some(low))
+}
+
+async fn has_code<N: Network, P: Provider<N>>(
+    provider: &P,
+    address: Address,
+    block: BlockNumber,
+) -> Result<bool, RpcError> {
+    let code = provider
+        .get_code_at(address)
+        .block_id(BlockId::number(block))
+        .await
+        .map_err(|e| RpcError::request_failed(format!("eth_getCode at block {block}"), e))?;
+    Ok(!code.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes, U64};
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_transport::mock::Asserter;
+
+    const TOKEN: Address = address!("00000000000000000000000000000000000000aa");
+
+    fn code() -> Bytes {
+        Bytes::from_static(&[0x60, 0x80])
+    }
+
+    #[tokio::test]
+    async fn test_binary_search_finds_first_block_with_code() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        // Latest block 8, deployed in block 5: probes 8, 4, 6 and 5
+        asserter.push_success(&U64::from(8));
+        asserter.push_success(&code());
+        asserter.push_success(&Bytes::new());
+        asserter.push_success(&code());
+        asserter.push_success(&code());
+
+        let cache = DeploymentCache::new();
+        let block = cache
+            .find_deployment_block(&provider, NamedChain::Mainnet, TOKEN)
+            .await
+            .unwrap();
+        assert_eq!(block, Some(5));
+
+        // The second lookup is served from the cache without any requests
+        let cached = cache
+            .find_deployment_block(&provider, NamedChain::Mainnet, TOKEN)
+            .await
+            .unwrap();
+        assert_eq!(cached, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_accounts_without_code_are_not_cached() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&U64::from(8));
+        asserter.push_success(&Bytes::new());
+
+        let cache = DeploymentCache::new();
+        let block = cache
+            .find_deployment_block(&provider, NamedChain::Mainnet, TOKEN)
+            .await
+            .unwrap();
+        assert_eq!(block, None);
+        assert_eq!(cache.get(NamedChain::Mainnet, TOKEN), None);
+    }
+
+    #[tokio::test]
+    async fn test_clamp_skips_ranges_before_deployment() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let cache = DeploymentCache::new();
+        cache.insert(NamedChain::Mainnet, TOKEN, 100);
+
+        for ((start, end), expected) in [
+            ((0, 200), Some(100)),
+            ((150, 200), Some(150)),
+            ((0, 99), None),
+        ] {
+            let clamped = cache
+                .clamp_start(&provider, NamedChain::Mainnet, &[TOKEN], start, end)
+                .await
+                .unwrap();
+            assert_eq!(clamped, expected);
+        }
+    }
+}
@@ -0,0 +1,380 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Finality tracking for range caches
+//!
+//! Results cached for block ranges near the chain tip can be undone by a
+//! reorg. A [`FinalityTracker`] follows the chain's finalized block (from the
+//! `finalized` or `safe` block tag, or a fixed confirmation depth behind the
+//! tip where the node supports neither). Calculators given one cache ranges
+//! reaching past that block as provisional, remembering the hash of their last
+//! block. Once finality passes that block, the entry is kept if the block
+//! hash is unchanged and dropped otherwise, so the next calculation scans the
+//! range again.
+//!
+//! The tracker is cheap to clone; clones share the last finality seen. Given
+//! a [`HeadTracker`](crate::HeadTracker)'s heads, it reads the tip from them
+//! rather than fetching it itself.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{FinalityTracker, GasCostCalculator, PriceCalculator};
+//!
+//! let finality = FinalityTracker::new().with_confirmation_depth(12);
+//! let gas = GasCostCalculator::new(provider.clone()).with_finality_tracker(finality.clone());
+//! let prices = PriceCalculator::new(provider, chain, usdc, source)
+//!     .with_finality_tracker(finality);
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use alloy_consensus::BlockHeader;
+use alloy_network::{BlockResponse, HeaderResponse, Network};
+use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+use tokio::sync::watch;
+use tracing::{debug, warn};
+
+use crate::cache::header::fetch_header;
+use crate::errors::RpcError;
+use crate::provider::ChainHead;
+
+/// Block tag a [`FinalityTracker`] treats as final
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FinalityTag {
+    /// The `finalized` tag: blocks the consensus layer will not revert
+    #[default]
+    Finalized,
+    /// The `safe` tag: closer to the tip, reverted only under an unusual attack
+    Safe,
+}
+
+impl FinalityTag {
+    fn block_tag(self) -> BlockNumberOrTag {
+        match self {
+            FinalityTag::Finalized => BlockNumberOrTag::Finalized,
+            FinalityTag::Safe => BlockNumberOrTag::Safe,
+        }
+    }
+}
+
+/// Where a [`Finality`] reading came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalitySource {
+    /// The node answered the block tag
+    Tag(FinalityTag),
+    /// The node had no such tag, so the finalized block was taken to be a
+    /// fixed depth behind the latest block
+    Depth(u64),
+}
+
+/// The finalized block at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Finality {
+    pub finalized_block: BlockNumber,
+    pub source: FinalitySource,
+}
+
+/// The last block of a provisional cache entry and its hash when cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Checkpoint {
+    pub block_number: BlockNumber,
+    pub hash: BlockHash,
+}
+
+/// Provisional cache entries settled by one reconciliation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Entries whose checkpoints all matched and are now final
+    pub confirmed: usize,
+    /// Entries dropped because a checkpoint's block was reorged out
+    pub invalidated: usize,
+}
+
+/// Shared view of the chain's finalized block
+#[derive(Debug, Clone)]
+pub struct FinalityTracker {
+    last: Arc<Mutex<Option<Finality>>>,
+    tag: FinalityTag,
+    confirmation_depth: u64,
+    head: Option<watch::Receiver<Option<ChainHead>>>,
+}
+
+impl Default for FinalityTracker {
+    fn default() -> Self {
+        Self {
+            last: Arc::default(),
+            tag: FinalityTag::default(),
+            confirmation_depth: Self::DEFAULT_CONFIRMATION_DEPTH,
+            head: None,
+        }
+    }
+}
+
+impl FinalityTracker {
+    /// Depth behind the tip treated as final when the node has no finality tags
+    ///
+    /// Two epochs of Ethereum slots, about the time the beacon chain takes to
+    /// finalize.
+    pub const DEFAULT_CONFIRMATION_DEPTH: u64 = 64;
+
+    /// Track the `finalized` tag, falling back to
+    /// [`DEFAULT_CONFIRMATION_DEPTH`](Self::DEFAULT_CONFIRMATION_DEPTH)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat `tag` as final instead of `finalized`
+    pub fn with_tag(mut self, tag: FinalityTag) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Treat blocks `depth` behind the latest as final when the node has no
+    /// finality tags
+    pub fn with_confirmation_depth(mut self, depth: u64) -> Self {
+        self.confirmation_depth = depth;
+        self
+    }
+
+    /// Take the latest block for the confirmation-depth fallback from
+    /// `head`, as published by a [`HeadTracker`](crate::HeadTracker), instead
+    /// of asking the provider
+    ///
+    /// The provider is still asked while no head has been published.
+    pub fn with_head(mut self, head: watch::Receiver<Option<ChainHead>>) -> Self {
+        self.head = Some(head);
+        self
+    }
+
+    /// The finalized block from the last [`refresh`](Self::refresh), if any
+    pub fn last(&self) -> Option<Finality> {
+        *self.last.lock().unwrap()
+    }
+
+    /// Whether `block_number` was final as of the last refresh
+    ///
+    /// Always `false` before the first refresh.
+    pub fn is_final(&self, block_number: BlockNumber) -> bool {
+        self.last()
+            .is_some_and(|finality| block_number <= finality.finalized_block)
+    }
+
+    /// Ask `provider` for the finalized block and remember it
+    ///
+    /// The finalized block never moves backwards: a reading below the last
+    /// one, as a lagging load-balanced node may give, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tag lookup fails and the latest block number can't be
+    /// fetched either.
+    pub async fn refresh<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+    ) -> Result<Finality, RpcError> {
+        let tagged = match provider.get_block_by_number(self.tag.block_tag()).await {
+            Ok(block) => block.map(|block| block.header().number()),
+            Err(e) => {
+                debug!(tag = ?self.tag, error = %e, "Finality tag unavailable, using confirmation depth");
+                None
+            }
+        };
+        let reading = match tagged {
+            Some(finalized_block) => Finality {
+                finalized_block,
+                source: FinalitySource::Tag(self.tag),
+            },
+            None => {
+                let tracked = self
+                    .head
+                    .as_ref()
+                    .and_then(|head| *head.borrow())
+                    .map(|head| head.number);
+                let latest = match tracked {
+                    Some(latest) => latest,
+                    None => provider
+                        .get_block_number()
+                        .await
+                        .map_err(RpcError::get_block_number_failed)?,
+                };
+                Finality {
+                    finalized_block: latest.saturating_sub(self.confirmation_depth),
+                    source: FinalitySource::Depth(self.confirmation_depth),
+                }
+            }
+        };
+
+        let mut last = self.last.lock().unwrap();
+        match *last {
+            Some(previous) if previous.finalized_block > reading.finalized_block => Ok(previous),
+            _ => {
+                *last = Some(reading);
+                Ok(reading)
+            }
+        }
+    }
+
+    /// The checkpoint to cache a range ending at `end_block` under, or `None`
+    /// if the range is already final
+    ///
+    /// # Errors
+    ///
+    /// Fails if the block at `end_block` can't be fetched.
+    pub async fn checkpoint<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+        end_block: BlockNumber,
+    ) -> Result<Option<Checkpoint>, RpcError> {
+        if self.is_final(end_block) {
+            return Ok(None);
+        }
+        let header = fetch_header(provider, end_block).await?;
+        Ok(Some(Checkpoint {
+            block_number: end_block,
+            hash: header.hash,
+        }))
+    }
+
+    /// Canonical hashes of `blocks`, which are expected to be final by now
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the blocks can't be fetched.
+    pub async fn canonical_hashes<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+        blocks: BTreeSet<BlockNumber>,
+    ) -> Result<HashMap<BlockNumber, BlockHash>, RpcError> {
+        let mut hashes = HashMap::with_capacity(blocks.len());
+        for block_number in blocks {
+            let header = fetch_header(provider, block_number).await?;
+            hashes.insert(block_number, header.hash);
+        }
+        Ok(hashes)
+    }
+}
+
+/// Log the outcome of settling a cache's provisional entries
+pub(crate) fn log_reconciliation(cache: &str, reconciliation: Reconciliation) {
+    if reconciliation.invalidated > 0 {
+        warn!(
+            cache,
+            invalidated = reconciliation.invalidated,
+            confirmed = reconciliation.confirmed,
+            "Dropped cached ranges whose blocks were reorged out before finality"
+        );
+    } else if reconciliation.confirmed > 0 {
+        debug!(
+            cache,
+            confirmed = reconciliation.confirmed,
+            "Provisional cached ranges are now final"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_rpc_types::{Block, Header};
+    use alloy_transport::mock::Asserter;
+
+    fn block(number: BlockNumber) -> Block {
+        Block {
+            header: Header {
+                inner: alloy_consensus::Header {
+                    number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_reads_the_finalized_tag() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&Some(block(1_000)));
+
+        let tracker = FinalityTracker::new();
+        let finality = tracker.refresh(&provider).await.unwrap();
+
+        assert_eq!(finality.finalized_block, 1_000);
+        assert_eq!(finality.source, FinalitySource::Tag(FinalityTag::Finalized));
+        assert!(tracker.is_final(1_000));
+        assert!(!tracker.is_final(1_001));
+    }
+
+    #[tokio::test]
+    async fn refresh_falls_back_to_confirmation_depth() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_failure_msg("unknown block tag");
+        asserter.push_success(&alloy_primitives::U64::from(500));
+
+        let tracker = FinalityTracker::new().with_confirmation_depth(12);
+        let finality = tracker.refresh(&provider).await.unwrap();
+
+        assert_eq!(finality.finalized_block, 488);
+        assert_eq!(finality.source, FinalitySource::Depth(12));
+    }
+
+    #[tokio::test]
+    async fn confirmation_depth_reads_the_tracked_head() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_failure_msg("unknown block tag");
+
+        let (_sender, head) = watch::channel(Some(ChainHead {
+            number: 500,
+            hash: BlockHash::ZERO,
+            parent_hash: BlockHash::ZERO,
+            timestamp: crate::UnixTimestamp(0),
+        }));
+        let tracker = FinalityTracker::new()
+            .with_confirmation_depth(12)
+            .with_head(head);
+        let finality = tracker.refresh(&provider).await.unwrap();
+
+        assert_eq!(finality.finalized_block, 488);
+    }
+
+    #[tokio::test]
+    async fn finalized_block_never_moves_backwards() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&Some(block(1_000)));
+        asserter.push_success(&Some(block(990)));
+
+        let tracker = FinalityTracker::new();
+        tracker.refresh(&provider).await.unwrap();
+        let finality = tracker.refresh(&provider).await.unwrap();
+
+        assert_eq!(finality.finalized_block, 1_000);
+    }
+
+    #[tokio::test]
+    async fn final_ranges_need_no_checkpoint() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&Some(block(1_000)));
+
+        let tracker = FinalityTracker::new();
+        tracker.refresh(&provider).await.unwrap();
+
+        // Would need a block fetch, which the empty mock queue would fail
+        assert_eq!(tracker.checkpoint(&provider, 900).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,258 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Process-wide cache of block header fields
+//!
+//! Block window binary searches, base fee lookups and gas percentile sampling
+//! all read block headers, often the same ones. A [`HeaderCache`] shared
+//! between them (it is cheap to clone, and clones share entries) keeps the
+//! fields semioscan uses from each header so every block is fetched once.
+//!
+//! Entries are keyed by block number alone, so a cache belongs to one chain:
+//! share it between calculators on the same chain, and give every chain its
+//! own. A cache shared across chains would answer one chain's lookups with
+//! another chain's headers.
+//!
+//! Entries never go stale: only numbered blocks are cached, and callers only
+//! look up blocks they already know to exist. Under reorg-sensitive use, keep
+//! lookups behind the finalized block. Once `capacity` is reached the least
+//! recently used header is evicted.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{BlockWindowCalculator, GasCostCalculator, HeaderCache};
+//!
+//! let headers = HeaderCache::with_capacity(50_000);
+//! let windows = BlockWindowCalculator::with_memory_cache(provider.clone())
+//!     .with_header_cache(headers.clone());
+//! let gas = GasCostCalculator::new(provider).with_header_cache(headers.clone());
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use alloy_consensus::BlockHeader;
+use alloy_network::{BlockResponse, HeaderResponse, Network};
+use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+
+use crate::errors::RpcError;
+use crate::types::gas::GasPrice;
+
+/// The header fields semioscan uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedHeader {
+    pub number: BlockNumber,
+    pub hash: BlockHash,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// `None` before London
+    pub base_fee_per_gas: Option<GasPrice>,
+}
+
+/// Hit and miss counts of a [`HeaderCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeaderCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub evictions: u64,
+}
+
+#[derive(Debug, Default)]
+struct HeaderCacheState {
+    entries: HashMap<BlockNumber, (CachedHeader, u64)>,
+    /// Access sequence → block, oldest first
+    recency: BTreeMap<u64, BlockNumber>,
+    next_sequence: u64,
+    stats: HeaderCacheStats,
+}
+
+impl HeaderCacheState {
+    fn touch(&mut self, block_number: BlockNumber) -> Option<CachedHeader> {
+        let sequence = self.next_sequence;
+        let (header, last_used) = self.entries.get_mut(&block_number)?;
+        self.recency.remove(last_used);
+        *last_used = sequence;
+        self.recency.insert(sequence, block_number);
+        self.next_sequence += 1;
+        Some(*header)
+    }
+}
+
+/// Bounded, shared cache of [`CachedHeader`]s keyed by block number
+///
+/// Holds the headers of a single chain; see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct HeaderCache {
+    state: Arc<Mutex<HeaderCacheState>>,
+    capacity: usize,
+}
+
+impl Default for HeaderCache {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl HeaderCache {
+    /// Headers kept by [`HeaderCache::new`]
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// A cache holding up to [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) headers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache holding up to `capacity` headers (at least one)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: Arc::default(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// The cached header of `block_number`, if present
+    pub fn get(&self, block_number: BlockNumber) -> Option<CachedHeader> {
+        let mut state = self.state.lock().unwrap();
+        let header = state.touch(block_number);
+        match header {
+            Some(_) => state.stats.hits += 1,
+            None => state.stats.misses += 1,
+        }
+        header
+    }
+
+    /// Cache `header`, evicting the least recently used entry if full
+    pub fn insert(&self, header: CachedHeader) {
+        let mut state = self.state.lock().unwrap();
+        if state.touch(header.number).is_some() {
+            if let Some(entry) = state.entries.get_mut(&header.number) {
+                entry.0 = header;
+            }
+            return;
+        }
+        if state.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = state.recency.pop_first() {
+                state.entries.remove(&oldest);
+                state.stats.evictions += 1;
+            }
+        }
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        state.entries.insert(header.number, (header, sequence));
+        state.recency.insert(sequence, header.number);
+    }
+
+    /// The header of `block_number`, fetched from `provider` on a miss
+    ///
+    /// Concurrent first lookups of the same block may each fetch it.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the block can't be fetched or doesn't exist.
+    pub async fn get_or_fetch<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+        block_number: BlockNumber,
+    ) -> Result<CachedHeader, RpcError> {
+        if let Some(header) = self.get(block_number) {
+            return Ok(header);
+        }
+        let header = fetch_header(provider, block_number).await?;
+        self.insert(header);
+        Ok(header)
+    }
+
+    /// Drop the headers of `first_invalid_block` and later, after a reorg
+    pub fn clear_from_block(&self, first_invalid_block: BlockNumber) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        state
+            .entries
+            .retain(|number, _| *number < first_invalid_block);
+        state
+            .recency
+            .retain(|_, number| *number < first_invalid_block);
+    }
+
+    /// Hit, miss and eviction counts so far
+    pub fn stats(&self) -> HeaderCacheStats {
+        let state = self.state.lock().unwrap();
+        HeaderCacheStats {
+            entries: state.entries.len(),
+            ..state.stats
+        }
+    }
+}
+
+/// Fetch the header fields of `block_number` from `provider`, uncached
+pub(crate) async fn fetch_header<N: Network, P: Provider<N>>(
+    provider: &P,
+    block_number: BlockNumber,
+) -> Result<CachedHeader, RpcError> {
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Number(block_number))
+        .await
+        .map_err(|e| RpcError::get_block_failed(block_number, e))?
+        .ok_or(RpcError::BlockNotFound { block_number })?;
+    let header = block.header();
+    Ok(CachedHeader {
+        number: block_number,
+        hash: header.hash(),
+        timestamp: header.timestamp(),
+        base_fee_per_gas: header.base_fee_per_gas().map(GasPrice::new),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(number: BlockNumber) -> CachedHeader {
+        CachedHeader {
+            number,
+            hash: BlockHash::repeat_byte(number as u8),
+            timestamp: 1_700_000_000 + number * 12,
+            base_fee_per_gas: Some(GasPrice::new(number)),
+        }
+    }
+
+    #[test]
+    fn least_recently_used_header_is_evicted() {
+        let cache = HeaderCache::with_capacity(2);
+        cache.insert(header(1));
+        cache.insert(header(2));
+        // Touch 1 so 2 becomes the oldest
+        assert_eq!(cache.get(1), Some(header(1)));
+        cache.insert(header(3));
+
+        assert_eq!(cache.get(2), None);
+        assert_eq!(cache.get(1), Some(header(1)));
+        assert_eq!(cache.get(3), Some(header(3)));
+        assert_eq!(
+            cache.stats(),
+            HeaderCacheStats {
+                hits: 3,
+                misses: 1,
+                entries: 2,
+                evictions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn clones_share_entries_and_reinserting_does_not_evict() {
+        let cache = HeaderCache::with_capacity(2);
+        let shared = cache.clone();
+        cache.insert(header(1));
+        cache.insert(header(2));
+        shared.insert(header(2));
+
+        assert_eq!(shared.get(1), Some(header(1)));
+        assert_eq!(shared.stats().evictions, 0);
+    }
+}
@@ -8,7 +8,19 @@
 //! - Gas calculation caching
 //! - Price calculation caching
 //! - Other block-range-based data
+//!
+//! It also holds [`header::HeaderCache`], the block header cache shared by the
+//! block window and gas calculators, and [`finality::FinalityTracker`], which
+//! lets the gas and price caches hold ranges near the chain tip provisionally.
+//! [`deployment::DeploymentCache`] remembers contract deployment blocks so the
+//! calculators can skip history before a contract existed.
+//! [`transaction::TransactionCache`] keeps fetched transactions and receipts
+//! so gas and combined runs over the same transactions fetch each once.
 
 pub mod block_range;
+pub mod deployment;
+pub mod finality;
+pub mod header;
+pub mod transaction;
 
 // Note: block_range types are internal and not re-exported
@@ -0,0 +1,314 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Process-wide cache of transactions and receipts
+//!
+//! Gas and combined runs over the same addresses look up the same
+//! transactions. A [`TransactionCache`] shared between the calculators (it is
+//! cheap to clone, and clones share entries) keeps each transaction and
+//! receipt fetched, keyed by hash, so a transaction seen by both runs is
+//! fetched once.
+//!
+//! Mined transactions don't change, but a reorg can move them to another
+//! block or drop them: register the cache with a
+//! [`ReorgDetector`](crate::ReorgDetector) as a [`ReorgHook`](crate::ReorgHook),
+//! which calls [`TransactionCache::clear_from_block`], or keep lookups behind
+//! the finalized block. Once `capacity`
+//! transactions are cached the least recently used one is evicted, with its
+//! receipt.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{CombinedCalculator, GasCostCalculator, TransactionCache};
+//!
+//! let transactions = TransactionCache::with_capacity(50_000);
+//! let gas = GasCostCalculator::new(provider.clone())
+//!     .with_transaction_cache(transactions.clone());
+//! let combined = CombinedCalculator::new(provider)
+//!     .with_transaction_cache(transactions.clone());
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use alloy_network::{Network, ReceiptResponse, TransactionResponse};
+use alloy_primitives::{BlockNumber, TxHash};
+
+/// Hit and miss counts of a [`TransactionCache`]
+///
+/// Transaction and receipt lookups are counted separately, so a transaction
+/// whose receipt is cached but whose body isn't counts one hit and one miss.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionCacheStats {
+    /// Transaction or receipt lookups answered from the cache
+    pub hits: u64,
+    /// Transaction or receipt lookups not in the cache
+    pub misses: u64,
+    /// Transactions currently cached, with or without their receipts
+    pub entries: usize,
+    /// Transactions dropped, with their receipts, to stay within capacity
+    pub evictions: u64,
+}
+
+struct TransactionEntry<N: Network> {
+    transaction: Option<N::TransactionResponse>,
+    receipt: Option<N::ReceiptResponse>,
+    last_used: u64,
+}
+
+impl<N: Network> TransactionEntry<N> {
+    /// The block the transaction was mined in, as far as is known
+    fn block_number(&self) -> Option<BlockNumber> {
+        self.receipt
+            .as_ref()
+            .and_then(ReceiptResponse::block_number)
+            .or_else(|| {
+                self.transaction
+                    .as_ref()
+                    .and_then(TransactionResponse::block_number)
+            })
+    }
+}
+
+struct TransactionCacheState<N: Network> {
+    entries: HashMap<TxHash, TransactionEntry<N>>,
+    /// Access sequence → transaction, oldest first
+    recency: BTreeMap<u64, TxHash>,
+    next_sequence: u64,
+    stats: TransactionCacheStats,
+}
+
+impl<N: Network> Default for TransactionCacheState<N> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_sequence: 0,
+            stats: TransactionCacheStats::default(),
+        }
+    }
+}
+
+impl<N: Network> TransactionCacheState<N> {
+    fn touch(&mut self, tx_hash: TxHash) -> Option<&mut TransactionEntry<N>> {
+        let sequence = self.next_sequence;
+        let entry = self.entries.get_mut(&tx_hash)?;
+        self.recency.remove(&entry.last_used);
+        entry.last_used = sequence;
+        self.recency.insert(sequence, tx_hash);
+        self.next_sequence += 1;
+        Some(entry)
+    }
+
+    /// The entry of `tx_hash`, created empty (evicting if full) if missing
+    fn entry(&mut self, tx_hash: TxHash, capacity: usize) -> &mut TransactionEntry<N> {
+        if self.touch(tx_hash).is_none() {
+            if self.entries.len() >= capacity {
+                if let Some((_, oldest)) = self.recency.pop_first() {
+                    self.entries.remove(&oldest);
+                    self.stats.evictions += 1;
+                }
+            }
+            let sequence = self.next_sequence;
+            self.next_sequence += 1;
+            self.entries.insert(
+                tx_hash,
+                TransactionEntry {
+                    transaction: None,
+                    receipt: None,
+                    last_used: sequence,
+                },
+            );
+            self.recency.insert(sequence, tx_hash);
+        }
+        self.entries
+            .get_mut(&tx_hash)
+            .expect("entry was just touched or inserted")
+    }
+
+    fn record(&mut self, hit: bool) {
+        if hit {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+    }
+}
+
+/// Bounded, shared cache of transactions and receipts keyed by hash
+pub struct TransactionCache<N: Network> {
+    state: Arc<Mutex<TransactionCacheState<N>>>,
+    capacity: usize,
+}
+
+impl<N: Network> Clone for TransactionCache<N> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl<N: Network> fmt::Debug for TransactionCache<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionCache")
+            .field("capacity", &self.capacity)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl<N: Network> Default for TransactionCache<N> {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<N: Network> TransactionCache<N> {
+    /// Transactions kept by [`TransactionCache::new`]
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// A cache holding up to [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) transactions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache holding up to `capacity` transactions (at least one)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: Arc::default(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// The cached transaction `tx_hash`, if present
+    pub fn transaction(&self, tx_hash: TxHash) -> Option<N::TransactionResponse> {
+        let mut state = self.state.lock().unwrap();
+        let transaction = state
+            .touch(tx_hash)
+            .and_then(|entry| entry.transaction.clone());
+        state.record(transaction.is_some());
+        transaction
+    }
+
+    /// The cached receipt of `tx_hash`, if present
+    pub fn receipt(&self, tx_hash: TxHash) -> Option<N::ReceiptResponse> {
+        let mut state = self.state.lock().unwrap();
+        let receipt = state.touch(tx_hash).and_then(|entry| entry.receipt.clone());
+        state.record(receipt.is_some());
+        receipt
+    }
+
+    /// Cache `transaction`, evicting the least recently used entry if full
+    pub fn insert_transaction(&self, tx_hash: TxHash, transaction: N::TransactionResponse) {
+        let mut state = self.state.lock().unwrap();
+        state.entry(tx_hash, self.capacity).transaction = Some(transaction);
+    }
+
+    /// Cache the receipt of `tx_hash`, evicting the least recently used
+    /// entry if full
+    pub fn insert_receipt(&self, tx_hash: TxHash, receipt: N::ReceiptResponse) {
+        let mut state = self.state.lock().unwrap();
+        state.entry(tx_hash, self.capacity).receipt = Some(receipt);
+    }
+
+    /// Drop transactions mined in `first_invalid_block` or later, after a
+    /// reorg
+    ///
+    /// Transactions without a known block are dropped too.
+    pub fn clear_from_block(&self, first_invalid_block: BlockNumber) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        state.entries.retain(|_, entry| {
+            entry
+                .block_number()
+                .is_some_and(|block| block < first_invalid_block)
+        });
+        let entries = &state.entries;
+        state
+            .recency
+            .retain(|_, tx_hash| entries.contains_key(tx_hash));
+    }
+
+    /// Hit, miss and eviction counts so far
+    pub fn stats(&self) -> TransactionCacheStats {
+        let state = self.state.lock().unwrap();
+        TransactionCacheStats {
+            entries: state.entries.len(),
+            ..state.stats
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_network::Ethereum;
+    use alloy_primitives::B256;
+    use alloy_rpc_types::TransactionReceipt;
+
+    fn receipt(block_number: BlockNumber) -> TransactionReceipt {
+        let json = serde_json::json!({
+            "transactionHash": B256::repeat_byte(block_number as u8),
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0xbb),
+            "blockNumber": format!("{block_number:#x}"),
+            "from": "0x0000000000000000000000000000000000000001",
+            "to": "0x0000000000000000000000000000000000000002",
+            "cumulativeGasUsed": "0x5208",
+            "gasUsed": "0x5208",
+            "contractAddress": null,
+            "logs": [],
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "type": "0x2",
+            "effectiveGasPrice": "0x3b9aca00",
+            "status": "0x1"
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn hash(n: u8) -> TxHash {
+        TxHash::repeat_byte(n)
+    }
+
+    #[test]
+    fn test_least_recently_used_transaction_is_evicted() {
+        let cache = TransactionCache::<Ethereum>::with_capacity(2);
+        cache.insert_receipt(hash(1), receipt(1));
+        cache.insert_receipt(hash(2), receipt(2));
+        // Touch 1 so 2 becomes the oldest
+        assert_eq!(cache.receipt(hash(1)), Some(receipt(1)));
+        cache.insert_receipt(hash(3), receipt(3));
+
+        assert_eq!(cache.receipt(hash(2)), None);
+        assert_eq!(cache.transaction(hash(1)), None);
+        assert_eq!(cache.receipt(hash(3)), Some(receipt(3)));
+        assert_eq!(
+            cache.stats(),
+            TransactionCacheStats {
+                hits: 2,
+                misses: 2,
+                entries: 2,
+                evictions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_clones_share_entries_and_reorgs_drop_later_blocks() {
+        let cache = TransactionCache::<Ethereum>::new();
+        let shared = cache.clone();
+        cache.insert_receipt(hash(1), receipt(10));
+        cache.insert_receipt(hash(2), receipt(20));
+        assert_eq!(shared.receipt(hash(2)), Some(receipt(20)));
+
+        shared.clear_from_block(15);
+        assert_eq!(cache.receipt(hash(1)), Some(receipt(10)));
+        assert_eq!(cache.receipt(hash(2)), None);
+        assert_eq!(cache.stats().entries, 1);
+    }
+}
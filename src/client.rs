@@ -0,0 +1,954 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! High-level client wiring every calculator from one configuration
+//!
+//! Using the calculators directly means building a provider of the right
+//! network type per chain, sharing caches between them, and mapping dates to
+//! block ranges before every query. [`SemioscanClient`] does that plumbing
+//! once: register an RPC endpoint per chain, and ask date-oriented questions.
+//!
+//! Each chain gets a single RPC client (so its rate limit covers every
+//! calculator), typed as Ethereum or Optimism according to
+//! [`network_type_for_chain`]. The block window cache is shared across chains,
+//! since its entries are keyed by chain and date. Gas results are keyed by
+//! address pair only, so each chain gets its own gas cache. Each endpoint is
+//! probed for optional RPC methods (see
+//! [`ProviderCapabilities`](crate::ProviderCapabilities)) on its first scan,
+//! and its gas and combined calculators use what the probe found.
+//!
+//! Tokens registered with [`SemioscanClientBuilder::with_tokens`] have their
+//! decimals and symbols read once by
+//! [`build_and_prefetch`](SemioscanClientBuilder::build_and_prefetch), so
+//! formatting their amounts later needs no RPC call, and a token whose
+//! decimals contradict the chain's canonical token list fails the build
+//! instead of skewing every amount.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{DiskCache, SemioscanClient, SemioscanConfig};
+//! use alloy_chains::NamedChain;
+//!
+//! let client = SemioscanClient::builder()
+//!     .with_config(SemioscanConfig::with_common_defaults())
+//!     .add_chain(NamedChain::Mainnet, "https://eth.example.com")
+//!     .add_chain(NamedChain::Base, "https://base.example.com")
+//!     .with_window_cache(DiskCache::new("windows.json").validate()?)
+//!     .build()?;
+//!
+//! let report = client
+//!     .daily_gas_report(NamedChain::Base, date, from, to, token)
+//!     .await?;
+//! println!(
+//!     "{}: {} transactions over {} blocks",
+//!     report.date,
+//!     report.gas.transaction_count,
+//!     report.window.block_count(),
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use alloy_chains::NamedChain;
+use alloy_erc20::LazyToken;
+use alloy_network::Ethereum;
+use alloy_primitives::Address;
+use alloy_provider::{CallItem, Provider, RootProvider};
+use alloy_rpc_client::RpcClient;
+use alloy_sol_types::{sol, SolCall};
+use chrono::NaiveDate;
+use futures::future::try_join_all;
+use op_alloy_network::Optimism;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+#[cfg(feature = "disk-cache")]
+use crate::blocks::cache::AuditedCache;
+use crate::blocks::cache::{BlockWindowCache, MemoryCache};
+use crate::blocks::window::{BlockWindowCalculator, DailyBlockWindow};
+use crate::config::constants::canonical_tokens;
+use crate::config::SemioscanConfig;
+use crate::errors::{RpcError, SemioscanError};
+use crate::gas::cache::GasCache;
+use crate::gas::calculator::{GasCostCalculator, GasCostResult};
+use crate::price::{PriceCalculator, PriceSource};
+use crate::provider::{
+    http_client, network_type_for_chain, CapabilityCache, ChainEndpoint, NetworkType,
+    ProviderConfig,
+};
+use crate::retrieval::{CombinedCalculator, CombinedDataResult, CombinedQuery, UsdPriceTable};
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
+use crate::types::tokens::{TokenDecimals, TokenMetadata, TokenSet};
+
+/// Gas spent on transfers between two addresses over one UTC day
+#[derive(Debug, Clone)]
+pub struct DailyGasReport {
+    /// Chain the report covers
+    pub chain: NamedChain,
+    /// UTC date the report covers
+    pub date: NaiveDate,
+    /// Blocks produced on `date`
+    pub window: DailyBlockWindow,
+    /// Gas costs of the matching transfers within `window`
+    pub gas: GasCostResult,
+}
+
+/// Network-typed calculators for one chain
+enum NetworkCalculators {
+    Ethereum {
+        gas: GasCostCalculator<Ethereum, RootProvider<Ethereum>>,
+        combined: CombinedCalculator<Ethereum, RootProvider<Ethereum>>,
+    },
+    Optimism {
+        gas: GasCostCalculator<Optimism, RootProvider<Optimism>>,
+        combined: CombinedCalculator<Optimism, RootProvider<Optimism>>,
+    },
+}
+
+/// Everything the client needs to answer queries for one chain
+struct ChainClient {
+    /// Ethereum-typed view of the chain's RPC client, for calculators that
+    /// only read blocks, logs and contract state
+    provider: RootProvider<Ethereum>,
+    windows: BlockWindowCalculator<RootProvider<Ethereum>>,
+    calculators: NetworkCalculators,
+}
+
+/// One entry point for block windows, gas, combined data and prices
+///
+/// Built with [`SemioscanClient::builder`], which registers one RPC endpoint
+/// per chain and wires the calculators for it.
+pub struct SemioscanClient {
+    chains: HashMap<NamedChain, ChainClient>,
+    config: SemioscanConfig,
+    token_metadata: HashMap<(NamedChain, Address), TokenMetadata>,
+}
+
+impl SemioscanClient {
+    /// Start configuring a client
+    pub fn builder() -> SemioscanClientBuilder {
+        SemioscanClientBuilder::new()
+    }
+
+    /// The configuration every calculator was built with
+    pub fn config(&self) -> &SemioscanConfig {
+        &self.config
+    }
+
+    /// Chains with a registered endpoint
+    pub fn chains(&self) -> Vec<NamedChain> {
+        self.chains.keys().copied().collect()
+    }
+
+    /// Prefetched metadata of `token` on `chain`, if it was prefetched
+    pub fn token_metadata(
+        &self,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+    ) -> Option<&TokenMetadata> {
+        self.token_metadata.get(&(chain, token.into().as_address()))
+    }
+
+    /// Prefetched decimals of `token` on `chain`, if it was prefetched
+    pub fn token_decimals(
+        &self,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+    ) -> Option<TokenDecimals> {
+        self.token_metadata(chain, token)
+            .map(|metadata| metadata.decimals)
+    }
+
+    /// Read and remember the decimals and symbol of every token in `tokens`
+    ///
+    /// The decimals of all tokens are read in one Multicall3 request and
+    /// their symbols in another; on a chain without Multicall3 each token is
+    /// read separately. Tokens already prefetched are skipped. A token without
+    /// a string `symbol()` is kept without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SemioscanError::ChainNotConfigured`] for an unknown chain,
+    /// [`SemioscanError::TokenMetadataUnavailable`] if a token doesn't answer
+    /// `decimals()`, and [`SemioscanError::UnexpectedTokenDecimals`] if its
+    /// decimals differ from the chain's
+    /// [`canonical_tokens`](crate::config::constants::canonical_tokens). No
+    /// metadata is kept from a failed prefetch.
+    pub async fn prefetch_token_metadata(
+        &mut self,
+        chain: NamedChain,
+        tokens: &TokenSet,
+    ) -> Result<(), SemioscanError> {
+        let provider = &self.chain(chain)?.provider;
+        let pending: Vec<Address> = tokens
+            .iter()
+            .filter(|token| !self.token_metadata.contains_key(&(chain, **token)))
+            .copied()
+            .collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        info!(chain = %chain, count = pending.len(), "Prefetching token metadata");
+        let fetched = fetch_token_metadata(provider, chain, &pending).await?;
+        self.token_metadata
+            .extend(pending.into_iter().map(|token| (chain, token)).zip(fetched));
+        Ok(())
+    }
+
+    /// Block range produced on `date` (UTC)
+    pub async fn daily_window(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+    ) -> Result<DailyBlockWindow, SemioscanError> {
+        Ok(self
+            .chain(chain)?
+            .windows
+            .get_daily_window(chain, date)
+            .await?)
+    }
+
+    /// Gas spent on `token` transfers from `from` to `to` on `date` (UTC)
+    ///
+    /// On OP-stack chains the result includes L1 data fees.
+    pub async fn daily_gas_report(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+        from: impl Into<SenderAddress>,
+        to: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
+    ) -> Result<DailyGasReport, SemioscanError> {
+        let client = self.chain(chain)?;
+        let window = client.windows.get_daily_window(chain, date).await?;
+        let (from, to, token) = (from.into(), to.into(), token.into());
+
+        let gas = match &client.calculators {
+            NetworkCalculators::Ethereum { gas, .. } => {
+                gas.calculate_gas_cost_for_transfers_between_blocks(
+                    chain,
+                    from,
+                    to,
+                    token,
+                    window.start_block,
+                    window.end_block,
+                )
+                .await?
+            }
+            NetworkCalculators::Optimism { gas, .. } => {
+                gas.calculate_gas_cost_for_transfers_between_blocks(
+                    chain,
+                    from,
+                    to,
+                    token,
+                    window.start_block,
+                    window.end_block,
+                )
+                .await?
+            }
+        };
+
+        Ok(DailyGasReport {
+            chain,
+            date,
+            window,
+            gas,
+        })
+    }
+
+    /// Transfer amounts and gas costs for `token` from `from` to `to` on `date` (UTC)
+    pub async fn daily_combined_data(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+        from: impl Into<SenderAddress>,
+        to: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
+    ) -> Result<CombinedDataResult, SemioscanError> {
+        let client = self.chain(chain)?;
+        let window = client.windows.get_daily_window(chain, date).await?;
+
+        let query = CombinedQuery {
+            chain,
+            from_address: from.into(),
+            to_address: to.into(),
+            token_address: token.into(),
+            from_block: window.start_block,
+            to_block: window.end_block,
+        };
+        let result = match &client.calculators {
+            NetworkCalculators::Ethereum { combined, .. } => combined.calculate(&query).await?,
+            NetworkCalculators::Optimism { combined, .. } => combined.calculate(&query).await?,
+        };
+        Ok(result)
+    }
+
+    /// A [`PriceCalculator`] for `chain` using the client's provider and configuration
+    ///
+    /// Price sources are DEX-specific, so the caller supplies one. Decimals
+    /// prefetched for `chain` are handed to the calculator, so it reads only
+    /// those of other tokens.
+    pub fn price_calculator(
+        &self,
+        chain: NamedChain,
+        usdc_address: impl Into<TokenAddress>,
+        price_source: Box<dyn PriceSource>,
+    ) -> Result<PriceCalculator<RootProvider<Ethereum>>, SemioscanError> {
+        let client = self.chain(chain)?;
+        let decimals = self
+            .token_metadata
+            .iter()
+            .filter(|((token_chain, _), _)| *token_chain == chain)
+            .map(|((_, token), metadata)| (*token, metadata.decimals));
+        Ok(PriceCalculator::with_config(
+            client.provider.clone(),
+            chain,
+            usdc_address,
+            price_source,
+            self.config.clone(),
+        )
+        .with_token_decimals(decimals))
+    }
+
+    /// An empty [`UsdPriceTable`] for valuing `token` transfers on `chain`,
+    /// using its prefetched decimals
+    ///
+    /// Returns `None` if `token` wasn't prefetched for `chain`.
+    pub fn usd_price_table(
+        &self,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+    ) -> Option<UsdPriceTable> {
+        self.token_decimals(chain, token).map(UsdPriceTable::new)
+    }
+
+    /// Ethereum-typed provider for `chain`, for reads that don't depend on
+    /// the network type
+    pub(crate) fn provider(
+        &self,
+        chain: NamedChain,
+    ) -> Result<&RootProvider<Ethereum>, SemioscanError> {
+        Ok(&self.chain(chain)?.provider)
+    }
+
+    fn chain(&self, chain: NamedChain) -> Result<&ChainClient, SemioscanError> {
+        self.chains
+            .get(&chain)
+            .ok_or(SemioscanError::ChainNotConfigured { chain })
+    }
+}
+
+sol! {
+    function decimals() external view returns (uint8);
+    function symbol() external view returns (string);
+}
+
+/// Read the decimals and symbols of `tokens` in two Multicall3 requests,
+/// checking the decimals against the chain's canonical token list
+///
+/// Falls back to reading each token separately if `chain` has no Multicall3
+/// or the endpoint rejects the aggregated call.
+async fn fetch_token_metadata(
+    provider: &RootProvider<Ethereum>,
+    chain: NamedChain,
+    tokens: &[Address],
+) -> Result<Vec<TokenMetadata>, SemioscanError> {
+    let decimals = match provider
+        .multicall()
+        .dynamic::<decimalsCall>()
+        .extend_calls(tokens.iter().map(|&token| {
+            CallItem::new(token, decimalsCall {}.abi_encode().into()).with_failure_allowed()
+        }))
+        .aggregate3()
+        .await
+    {
+        Ok(decimals) => decimals,
+        Err(e) => {
+            warn!(chain = %chain, error = %e, "Multicall3 unavailable, reading token metadata per token");
+            return try_join_all(
+                tokens
+                    .iter()
+                    .map(|&token| fetch_single_token_metadata(provider, chain, token)),
+            )
+            .await;
+        }
+    };
+
+    let mut fetched = Vec::with_capacity(tokens.len());
+    for (&token, decimals) in tokens.iter().zip(decimals) {
+        let decimals =
+            decimals.map_err(|e| SemioscanError::token_metadata_unavailable(chain, token, e))?;
+        fetched.push(checked_metadata(chain, token, decimals)?);
+    }
+
+    let symbols = provider
+        .multicall()
+        .dynamic::<symbolCall>()
+        .extend_calls(tokens.iter().map(|&token| {
+            CallItem::new(token, symbolCall {}.abi_encode().into()).with_failure_allowed()
+        }))
+        .aggregate3()
+        .await;
+    match symbols {
+        Ok(symbols) => {
+            for ((metadata, &token), symbol) in fetched.iter_mut().zip(tokens).zip(symbols) {
+                match symbol {
+                    Ok(symbol) => metadata.symbol = Some(symbol),
+                    Err(e) => {
+                        debug!(chain = %chain, token = %token, error = %e, "Token has no string symbol")
+                    }
+                }
+            }
+        }
+        Err(e) => debug!(chain = %chain, error = %e, "Token symbols unavailable"),
+    }
+    Ok(fetched)
+}
+
+/// Read `token`'s decimals and symbol with separate calls
+async fn fetch_single_token_metadata(
+    provider: &RootProvider<Ethereum>,
+    chain: NamedChain,
+    token: Address,
+) -> Result<TokenMetadata, SemioscanError> {
+    let contract = LazyToken::new(token, provider.clone());
+    let decimals = *contract
+        .decimals()
+        .await
+        .map_err(|e| SemioscanError::token_metadata_unavailable(chain, token, e))?;
+    let metadata = checked_metadata(chain, token, decimals)?;
+    Ok(match contract.symbol().await {
+        Ok(symbol) => metadata.with_symbol(symbol.clone()),
+        Err(e) => {
+            debug!(chain = %chain, token = %token, error = %e, "Token has no string symbol");
+            metadata
+        }
+    })
+}
+
+/// Metadata of `token` with `decimals`, unless they contradict the chain's
+/// canonical token list
+fn checked_metadata(
+    chain: NamedChain,
+    token: Address,
+    decimals: u8,
+) -> Result<TokenMetadata, SemioscanError> {
+    if let Some(expected) = canonical_tokens(chain).decimals(chain, token) {
+        if expected != decimals {
+            return Err(SemioscanError::UnexpectedTokenDecimals {
+                chain,
+                token,
+                expected,
+                actual: decimals,
+            });
+        }
+    }
+    Ok(TokenMetadata::new(TokenDecimals::new(decimals)))
+}
+
+/// Builder for [`SemioscanClient`]
+pub struct SemioscanClientBuilder {
+    config: SemioscanConfig,
+    endpoints: Vec<(NamedChain, ProviderConfig)>,
+    window_cache: Option<Arc<dyn BlockWindowCache>>,
+    tokens: HashMap<NamedChain, TokenSet>,
+}
+
+impl Default for SemioscanClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SemioscanClientBuilder {
+    /// Create a builder with the default configuration and no chains
+    pub fn new() -> Self {
+        Self {
+            config: SemioscanConfig::default(),
+            endpoints: Vec::new(),
+            window_cache: None,
+            tokens: HashMap::new(),
+        }
+    }
+
+    /// Configuration shared by every calculator
+    pub fn with_config(mut self, config: SemioscanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Register an RPC endpoint for `chain`
+    pub fn add_chain(self, chain: NamedChain, url: impl Into<String>) -> Self {
+        self.add_chain_with_config(chain, ProviderConfig::new(url))
+    }
+
+    /// Register an endpoint from a [`ChainEndpoint`], keeping its rate limit
+    pub fn add_endpoint(self, endpoint: ChainEndpoint) -> Self {
+        let config = ProviderConfig::new(endpoint.url).with_rate_limit_opt(endpoint.rate_limit);
+        self.add_chain_with_config(endpoint.chain, config)
+    }
+
+    /// Register an endpoint with full provider settings (rate limits, fallback URLs)
+    ///
+    /// Registering a chain twice replaces the earlier endpoint.
+    pub fn add_chain_with_config(mut self, chain: NamedChain, config: ProviderConfig) -> Self {
+        self.endpoints.retain(|(existing, _)| *existing != chain);
+        self.endpoints.push((chain, config));
+        self
+    }
+
+    /// Cache for daily block windows, shared by every chain
+    ///
+    /// Defaults to a [`MemoryCache`].
+    pub fn with_window_cache(mut self, cache: impl BlockWindowCache + 'static) -> Self {
+        self.window_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Tokens on `chain` whose metadata
+    /// [`build_and_prefetch`](Self::build_and_prefetch) reads upfront
+    ///
+    /// Calling this again for the same chain adds to its tokens.
+    pub fn with_tokens(mut self, chain: NamedChain, tokens: TokenSet) -> Self {
+        self.tokens.entry(chain).or_default().extend(tokens);
+        self
+    }
+
+    /// Connect to every registered chain, then prefetch the metadata of the
+    /// tokens registered with [`with_tokens`](Self::with_tokens)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an endpoint URL is invalid, or as
+    /// [`SemioscanClient::prefetch_token_metadata`] does for the first chain
+    /// whose tokens can't all be read.
+    pub async fn build_and_prefetch(mut self) -> Result<SemioscanClient, SemioscanError> {
+        let tokens = std::mem::take(&mut self.tokens);
+        let mut client = self.build()?;
+        for (chain, tokens) in &tokens {
+            client.prefetch_token_metadata(*chain, tokens).await?;
+        }
+        Ok(client)
+    }
+
+    /// Connect to every registered chain
+    ///
+    /// Tokens registered with [`with_tokens`](Self::with_tokens) are not
+    /// prefetched; use [`build_and_prefetch`](Self::build_and_prefetch) for
+    /// that.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an endpoint URL is invalid. No requests are sent;
+    /// endpoint capabilities are probed on the first scan.
+    pub fn build(self) -> Result<SemioscanClient, RpcError> {
+        self.build_with(http_client)
+    }
+
+    /// Connect every chain through a mocked transport answered by `asserter`
+    #[cfg(test)]
+    pub(crate) fn build_mocked(self, asserter: alloy_transport::mock::Asserter) -> SemioscanClient {
+        self.build_with(|_| Ok(RpcClient::mocked(asserter.clone())))
+            .expect("mocked clients don't fail to connect")
+    }
+
+    fn build_with(
+        self,
+        connect: impl Fn(&ProviderConfig) -> Result<RpcClient, RpcError>,
+    ) -> Result<SemioscanClient, RpcError> {
+        let window_cache = self
+            .window_cache
+            .unwrap_or_else(|| Arc::new(MemoryCache::new()));
+        #[cfg(feature = "disk-cache")]
+        let window_cache: Arc<dyn BlockWindowCache> = match &self.config.cache_audit {
+            Some(log) => Arc::new(AuditedCache::new(window_cache, log.clone())),
+            None => window_cache,
+        };
+
+        // Each endpoint is probed for optional methods on its first scan
+        let capabilities = CapabilityCache::new();
+        let mut chains = HashMap::with_capacity(self.endpoints.len());
+        for (chain, provider_config) in self.endpoints {
+            let rpc = connect(&provider_config)?;
+            let provider = RootProvider::<Ethereum>::new(rpc.clone());
+            // The same address pair on two chains must not share cached results
+            let gas_cache = Arc::new(Mutex::new(GasCache::default()));
+
+            let windows =
+                BlockWindowCalculator::new(provider.clone(), Box::new(window_cache.clone()))
+                    .with_rpc_endpoint(&provider_config.url)
+                    .with_config(self.config.clone());
+
+            let calculators = match network_type_for_chain(chain) {
+                NetworkType::Ethereum => NetworkCalculators::Ethereum {
+                    gas: GasCostCalculator::with_cache_and_config(
+                        provider.clone(),
+                        gas_cache,
+                        self.config.clone(),
+                    )
+                    .with_capability_probe(capabilities.clone(), &provider_config.url),
+                    combined: CombinedCalculator::with_config(
+                        provider.clone(),
+                        self.config.clone(),
+                    )
+                    .with_capability_probe(capabilities.clone(), &provider_config.url),
+                },
+                NetworkType::Optimism => {
+                    let provider = RootProvider::<Optimism>::new(rpc);
+                    NetworkCalculators::Optimism {
+                        gas: GasCostCalculator::with_cache_and_config(
+                            provider.clone(),
+                            gas_cache,
+                            self.config.clone(),
+                        )
+                        .with_capability_probe(capabilities.clone(), &provider_config.url),
+                        combined: CombinedCalculator::with_config(provider, self.config.clone())
+                            .with_capability_probe(capabilities.clone(), &provider_config.url),
+                    }
+                }
+            };
+
+            chains.insert(
+                chain,
+                ChainClient {
+                    provider,
+                    windows,
+                    calculators,
+                },
+            );
+        }
+
+        Ok(SemioscanClient {
+            chains,
+            config: self.config,
+            token_metadata: HashMap::new(),
+        })
+    }
+}
+
+/// One [`SemioscanClient`] operation, for use through [`CalculatorService`]
+///
+/// Requests are cheap to clone, so middleware such as retries can replay them.
+/// Prices are not included because price sources are supplied per call; use
+/// [`SemioscanClient::price_calculator`] for those.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalculatorRequest {
+    /// See [`SemioscanClient::daily_window`]
+    DailyWindow { chain: NamedChain, date: NaiveDate },
+    /// See [`SemioscanClient::daily_gas_report`]
+    DailyGasReport {
+        chain: NamedChain,
+        date: NaiveDate,
+        from: Address,
+        to: Address,
+        token: Address,
+    },
+    /// See [`SemioscanClient::daily_combined_data`]
+    DailyCombinedData {
+        chain: NamedChain,
+        date: NaiveDate,
+        from: Address,
+        to: Address,
+        token: Address,
+    },
+}
+
+impl CalculatorRequest {
+    /// Chain the request is for
+    pub fn chain(&self) -> NamedChain {
+        match self {
+            CalculatorRequest::DailyWindow { chain, .. }
+            | CalculatorRequest::DailyGasReport { chain, .. }
+            | CalculatorRequest::DailyCombinedData { chain, .. } => *chain,
+        }
+    }
+
+    /// Operation name, for metrics and logs
+    pub fn operation(&self) -> &'static str {
+        match self {
+            CalculatorRequest::DailyWindow { .. } => "daily_window",
+            CalculatorRequest::DailyGasReport { .. } => "daily_gas_report",
+            CalculatorRequest::DailyCombinedData { .. } => "daily_combined_data",
+        }
+    }
+}
+
+/// Result of a [`CalculatorRequest`], in the variant matching the request
+#[derive(Debug, Clone)]
+pub enum CalculatorResponse {
+    DailyWindow(DailyBlockWindow),
+    DailyGasReport(DailyGasReport),
+    DailyCombinedData(CombinedDataResult),
+}
+
+/// [`tower::Service`] over a shared [`SemioscanClient`]
+///
+/// Lets standard tower middleware (retries, timeouts, concurrency limits,
+/// metrics, auth) wrap semioscan operations uniformly. The service is always
+/// ready; rate limiting belongs in the provider's transport layers or in
+/// middleware around this service. Clones share the client.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{CalculatorRequest, CalculatorResponse, SemioscanClient};
+/// use tower::{Service, ServiceBuilder};
+///
+/// let client = SemioscanClient::builder().add_chain(NamedChain::Base, url).build()?;
+/// let mut service = ServiceBuilder::new()
+///     .timeout(Duration::from_secs(300))
+///     .service(client.into_service());
+///
+/// let response = service
+///     .call(CalculatorRequest::DailyWindow { chain: NamedChain::Base, date })
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct CalculatorService {
+    client: Arc<SemioscanClient>,
+}
+
+impl CalculatorService {
+    /// Serve requests with `client`
+    pub fn new(client: Arc<SemioscanClient>) -> Self {
+        Self { client }
+    }
+
+    /// The client requests are served with
+    pub fn client(&self) -> &SemioscanClient {
+        &self.client
+    }
+}
+
+impl SemioscanClient {
+    /// Wrap the client in a [`CalculatorService`] for use with tower middleware
+    pub fn into_service(self) -> CalculatorService {
+        CalculatorService::new(Arc::new(self))
+    }
+}
+
+impl tower::Service<CalculatorRequest> for CalculatorService {
+    type Response = CalculatorResponse;
+    type Error = SemioscanError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: CalculatorRequest) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move {
+            match request {
+                CalculatorRequest::DailyWindow { chain, date } => client
+                    .daily_window(chain, date)
+                    .await
+                    .map(CalculatorResponse::DailyWindow),
+                CalculatorRequest::DailyGasReport {
+                    chain,
+                    date,
+                    from,
+                    to,
+                    token,
+                } => client
+                    .daily_gas_report(chain, date, from, to, token)
+                    .await
+                    .map(CalculatorResponse::DailyGasReport),
+                CalculatorRequest::DailyCombinedData {
+                    chain,
+                    date,
+                    from,
+                    to,
+                    token,
+                } => client
+                    .daily_combined_data(chain, date, from, to, token)
+                    .await
+                    .map(CalculatorResponse::DailyCombinedData),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_registers_each_chain_once() {
+        let client = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .add_endpoint(ChainEndpoint::base("http://localhost:8546").with_rate_limit(5))
+            .add_chain(NamedChain::Mainnet, "http://localhost:8547")
+            .build()
+            .unwrap();
+
+        let mut chains = client.chains();
+        chains.sort_by_key(|chain| *chain as u64);
+        assert_eq!(chains, vec![NamedChain::Mainnet, NamedChain::Base]);
+        assert!(matches!(
+            client.chains[&NamedChain::Base].calculators,
+            NetworkCalculators::Optimism { .. }
+        ));
+    }
+
+    #[test]
+    fn test_each_chain_has_its_own_gas_cache() {
+        let client = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .add_chain(NamedChain::Arbitrum, "http://localhost:8546")
+            .build()
+            .unwrap();
+
+        let gas_cache = |chain| match &client.chains[&chain].calculators {
+            NetworkCalculators::Ethereum { gas, .. } => gas.gas_cache.clone(),
+            NetworkCalculators::Optimism { gas, .. } => gas.gas_cache.clone(),
+        };
+        assert!(!Arc::ptr_eq(
+            &gas_cache(NamedChain::Mainnet),
+            &gas_cache(NamedChain::Arbitrum)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_url_fails_build() {
+        let result = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "not a url")
+            .build();
+        assert!(matches!(result, Err(RpcError::ProviderUrlInvalid(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_chain_is_an_error() {
+        let client = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .build()
+            .unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let error = client
+            .daily_window(NamedChain::Arbitrum, date)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            SemioscanError::ChainNotConfigured {
+                chain: NamedChain::Arbitrum
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_rejects_unexpected_canonical_decimals() {
+        use alloy_primitives::U256;
+        use alloy_provider::ProviderBuilder;
+        use alloy_sol_types::SolValue;
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let usdc = canonical_tokens(NamedChain::Mainnet).usdc.unwrap();
+        asserter.push_success(&aggregate3_returns([U256::from(18).abi_encode()]));
+
+        let error = fetch_token_metadata(&provider, NamedChain::Mainnet, &[usdc])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            SemioscanError::UnexpectedTokenDecimals {
+                expected: 6,
+                actual: 18,
+                ..
+            }
+        ));
+    }
+
+    /// Encoded `aggregate3` result of calls that all returned `returns`
+    fn aggregate3_returns(returns: impl IntoIterator<Item = Vec<u8>>) -> alloy_primitives::Bytes {
+        use alloy_provider::bindings::IMulticall3;
+
+        let results: Vec<_> = returns
+            .into_iter()
+            .map(|data| IMulticall3::Result {
+                success: true,
+                returnData: data.into(),
+            })
+            .collect();
+        IMulticall3::aggregate3Call::abi_encode_returns(&results).into()
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_batches_metadata_into_price_tables() {
+        use alloy_primitives::U256;
+        use alloy_sol_types::SolValue;
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let mut client = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .build_mocked(asserter.clone());
+        let usdc = canonical_tokens(NamedChain::Mainnet).usdc.unwrap();
+        let other = Address::repeat_byte(0x11);
+        // One request for both tokens' decimals, one for both symbols, in
+        // address order
+        asserter.push_success(&aggregate3_returns([
+            U256::from(8).abi_encode(),
+            U256::from(6).abi_encode(),
+        ]));
+        asserter.push_success(&aggregate3_returns([
+            "TKN".to_string().abi_encode(),
+            "USDC".to_string().abi_encode(),
+        ]));
+
+        let tokens: TokenSet = [usdc, other].into_iter().collect();
+        client
+            .prefetch_token_metadata(NamedChain::Mainnet, &tokens)
+            .await
+            .unwrap();
+        assert_eq!(
+            client.token_metadata(NamedChain::Mainnet, usdc),
+            Some(&TokenMetadata::new(TokenDecimals::USDC).with_symbol("USDC"))
+        );
+        assert_eq!(
+            client.token_metadata(NamedChain::Mainnet, other),
+            Some(&TokenMetadata::new(TokenDecimals::WBTC).with_symbol("TKN"))
+        );
+        assert!(client.usd_price_table(NamedChain::Mainnet, other).is_some());
+        assert!(client.usd_price_table(NamedChain::Base, other).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_service_dispatches_requests_to_the_client() {
+        use tower::Service;
+
+        let mut service = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .build()
+            .unwrap()
+            .into_service();
+        std::future::poll_fn(|cx| service.poll_ready(cx))
+            .await
+            .unwrap();
+
+        let request = CalculatorRequest::DailyWindow {
+            chain: NamedChain::Arbitrum,
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        };
+        assert_eq!(request.operation(), "daily_window");
+        assert_eq!(request.chain(), NamedChain::Arbitrum);
+
+        let error = service.call(request).await.unwrap_err();
+        assert!(matches!(
+            error,
+            SemioscanError::ChainNotConfigured {
+                chain: NamedChain::Arbitrum
+            }
+        ));
+    }
+}
@@ -0,0 +1,462 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Automatic `max_block_range` calibration
+//!
+//! The default 500-block chunk is a guess: some provider/chain pairs accept far
+//! larger `eth_getLogs` ranges, others reject much smaller ones. A
+//! [`BlockRangeCalibrator`] probes a chain with growing ranges ending at the
+//! chain head and keeps the largest range that answered without error and
+//! within a latency threshold.
+//!
+//! Results can be applied to a [`SemioscanConfig`] directly with
+//! [`SemioscanConfig::apply_calibration`], or recorded in a
+//! [`CalibrationStore`] and persisted as JSON so later runs skip probing.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::config::calibration::{BlockRangeCalibrator, CalibrationStore};
+//! use semioscan::SemioscanConfig;
+//! use alloy_chains::NamedChain;
+//!
+//! let mut store = CalibrationStore::load_or_default("calibration.json")?;
+//! if store.get(NamedChain::Base).is_none() {
+//!     let result = BlockRangeCalibrator::new(&provider).calibrate(NamedChain::Base).await?;
+//!     store.record(&result);
+//!     store.save("calibration.json")?;
+//! }
+//!
+//! let mut config = SemioscanConfig::default();
+//! store.apply_to(&mut config);
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::BlockNumber;
+use alloy_provider::Provider;
+use alloy_rpc_types::Filter;
+use alloy_sol_types::SolEvent;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tracing::{debug, info};
+
+use crate::config::SemioscanConfig;
+use crate::errors::RpcError;
+use crate::events::definitions::Transfer;
+use crate::types::config::MaxBlockRange;
+
+/// Tuning for a calibration run
+#[derive(Debug, Clone)]
+pub struct CalibrationOptions {
+    /// First range probed, in blocks
+    pub start_range: u64,
+    /// Largest range probed, in blocks
+    pub max_range: u64,
+    /// Factor the range grows by after each successful probe (at least 2)
+    pub growth_factor: u64,
+    /// Probes slower than this count as failures
+    pub latency_threshold: Duration,
+    /// Filter used for probes (block range is set per probe)
+    ///
+    /// Defaults to every ERC-20 Transfer, which is busy enough on most chains
+    /// to trigger result-count limits as well as block-range limits.
+    pub probe_filter: Filter,
+}
+
+impl Default for CalibrationOptions {
+    fn default() -> Self {
+        Self {
+            start_range: 100,
+            max_range: 10_000,
+            growth_factor: 2,
+            latency_threshold: Duration::from_secs(5),
+            probe_filter: Filter::new().event_signature(Transfer::SIGNATURE_HASH),
+        }
+    }
+}
+
+impl CalibrationOptions {
+    /// Set the first range probed
+    pub fn with_start_range(mut self, blocks: u64) -> Self {
+        self.start_range = blocks.max(1);
+        self
+    }
+
+    /// Set the largest range probed
+    pub fn with_max_range(mut self, blocks: u64) -> Self {
+        self.max_range = blocks.max(1);
+        self
+    }
+
+    /// Set how fast the probed range grows
+    pub fn with_growth_factor(mut self, factor: u64) -> Self {
+        self.growth_factor = factor.max(2);
+        self
+    }
+
+    /// Set the latency above which a probe counts as failed
+    pub fn with_latency_threshold(mut self, threshold: Duration) -> Self {
+        self.latency_threshold = threshold;
+        self
+    }
+
+    /// Set the filter used for probes
+    pub fn with_probe_filter(mut self, filter: Filter) -> Self {
+        self.probe_filter = filter;
+        self
+    }
+}
+
+/// How a single probe ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// The query succeeded within the latency threshold
+    Accepted {
+        /// Number of logs returned
+        logs: usize,
+    },
+    /// The query succeeded but took longer than the latency threshold
+    TooSlow,
+    /// The provider rejected the query
+    Rejected {
+        /// Error reported by the provider
+        error: String,
+    },
+}
+
+impl ProbeOutcome {
+    /// Whether the probed range is usable
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted { .. })
+    }
+}
+
+/// One probe of a calibration run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationProbe {
+    /// Blocks covered by the probe
+    pub range: u64,
+    /// Wall-clock time of the query
+    pub latency: Duration,
+    /// How the probe ended
+    pub outcome: ProbeOutcome,
+}
+
+/// Outcome of calibrating one chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationResult {
+    /// Chain that was probed
+    pub chain: NamedChain,
+    /// Largest accepted range, or `None` if even a single block was rejected
+    pub max_block_range: Option<MaxBlockRange>,
+    /// Every probe, in the order it ran
+    pub probes: Vec<CalibrationProbe>,
+}
+
+/// Decides which range to probe next
+#[derive(Debug)]
+struct RangeSearch {
+    max_range: u64,
+    growth_factor: u64,
+    best: Option<u64>,
+    next: Option<u64>,
+}
+
+impl RangeSearch {
+    fn new(options: &CalibrationOptions) -> Self {
+        let max_range = options.max_range.max(1);
+        Self {
+            max_range,
+            growth_factor: options.growth_factor.max(2),
+            best: None,
+            next: Some(options.start_range.clamp(1, max_range)),
+        }
+    }
+
+    /// Grow after a success; after a failure stop if anything worked, else shrink
+    fn record(&mut self, range: u64, accepted: bool) {
+        self.next = if accepted {
+            self.best = Some(range);
+            (range < self.max_range)
+                .then(|| range.saturating_mul(self.growth_factor).min(self.max_range))
+        } else if self.best.is_none() && range > 1 {
+            Some(range / 2)
+        } else {
+            None
+        };
+    }
+}
+
+/// Probes a provider to find the largest workable `eth_getLogs` range
+pub struct BlockRangeCalibrator<P> {
+    provider: P,
+    options: CalibrationOptions,
+}
+
+impl<P: Provider> BlockRangeCalibrator<P> {
+    /// Create a calibrator with default [`CalibrationOptions`]
+    pub fn new(provider: P) -> Self {
+        Self::with_options(provider, CalibrationOptions::default())
+    }
+
+    /// Create a calibrator with custom options
+    pub fn with_options(provider: P, options: CalibrationOptions) -> Self {
+        Self { provider, options }
+    }
+
+    /// Probe `chain` with ranges ending at the current head
+    ///
+    /// Ranges grow by [`CalibrationOptions::growth_factor`] until a probe is
+    /// rejected, exceeds the latency threshold, or reaches
+    /// [`CalibrationOptions::max_range`]. If the first probe already fails the
+    /// range is halved until one succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain head cannot be fetched. Probe failures are
+    /// part of the result, not errors.
+    pub async fn calibrate(&self, chain: NamedChain) -> Result<CalibrationResult, RpcError> {
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(RpcError::get_block_number_failed)?;
+
+        let mut search = RangeSearch::new(&self.options);
+        let mut probes = Vec::new();
+
+        while let Some(range) = search.next {
+            let probe = self.probe(head, range).await;
+            debug!(
+                chain = %chain,
+                range,
+                latency_ms = probe.latency.as_millis(),
+                outcome = ?probe.outcome,
+                "Calibration probe finished"
+            );
+            search.record(range, probe.outcome.is_accepted());
+            probes.push(probe);
+        }
+
+        let max_block_range = search.best.map(MaxBlockRange::new);
+        info!(
+            chain = %chain,
+            max_block_range = ?search.best,
+            probes = probes.len(),
+            "Calibrated max block range"
+        );
+
+        Ok(CalibrationResult {
+            chain,
+            max_block_range,
+            probes,
+        })
+    }
+
+    async fn probe(&self, head: BlockNumber, range: u64) -> CalibrationProbe {
+        let from_block = head.saturating_sub(range - 1);
+        let filter = self
+            .options
+            .probe_filter
+            .clone()
+            .from_block(from_block)
+            .to_block(head);
+
+        let started = Instant::now();
+        let result = self.provider.get_logs(&filter).await;
+        let latency = started.elapsed();
+
+        let outcome = match result {
+            Ok(_) if latency > self.options.latency_threshold => ProbeOutcome::TooSlow,
+            Ok(logs) => ProbeOutcome::Accepted { logs: logs.len() },
+            Err(e) => ProbeOutcome::Rejected {
+                error: e.to_string(),
+            },
+        };
+
+        CalibrationProbe {
+            range,
+            latency,
+            outcome,
+        }
+    }
+}
+
+impl SemioscanConfig {
+    /// Use a calibrated range for subsequent scans on the result's chain
+    ///
+    /// Other overrides for the chain are kept. Results without an accepted
+    /// range leave the configuration unchanged.
+    pub fn apply_calibration(&mut self, result: &CalibrationResult) {
+        if let Some(range) = result.max_block_range {
+            self.chain_overrides
+                .entry(result.chain)
+                .or_default()
+                .max_block_range = Some(range);
+        }
+    }
+}
+
+/// Persistable record of calibrated ranges per chain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CalibrationStore {
+    ranges: BTreeMap<NamedChain, MaxBlockRange>,
+}
+
+impl CalibrationStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a store from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Load a store from a JSON file, or start empty if the file does not exist
+    pub fn load_or_default(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        match Self::load(path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            other => other,
+        }
+    }
+
+    /// Write the store to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Record a calibration result, replacing any earlier range for its chain
+    pub fn record(&mut self, result: &CalibrationResult) {
+        if let Some(range) = result.max_block_range {
+            self.ranges.insert(result.chain, range);
+        }
+    }
+
+    /// Calibrated range for `chain`, if any
+    pub fn get(&self, chain: NamedChain) -> Option<MaxBlockRange> {
+        self.ranges.get(&chain).copied()
+    }
+
+    /// Apply every recorded range to `config` as a chain override
+    pub fn apply_to(&self, config: &mut SemioscanConfig) {
+        for (&chain, &range) in &self.ranges {
+            config
+                .chain_overrides
+                .entry(chain)
+                .or_default()
+                .max_block_range = Some(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_search(options: &CalibrationOptions, limit: u64) -> (Option<u64>, Vec<u64>) {
+        let mut search = RangeSearch::new(options);
+        let mut probed = Vec::new();
+        while let Some(range) = search.next {
+            probed.push(range);
+            search.record(range, range <= limit);
+        }
+        (search.best, probed)
+    }
+
+    #[test]
+    fn search_grows_until_rejection() {
+        let (best, probed) = run_search(&CalibrationOptions::default(), 1_000);
+        assert_eq!(best, Some(800));
+        assert_eq!(probed, vec![100, 200, 400, 800, 1_600]);
+    }
+
+    #[test]
+    fn search_stops_at_max_range() {
+        let options = CalibrationOptions::default().with_max_range(300);
+        let (best, probed) = run_search(&options, u64::MAX);
+        assert_eq!(best, Some(300));
+        assert_eq!(probed, vec![100, 200, 300]);
+    }
+
+    #[test]
+    fn search_shrinks_when_start_range_fails() {
+        let (best, probed) = run_search(&CalibrationOptions::default(), 30);
+        assert_eq!(best, Some(25));
+        assert_eq!(probed, vec![100, 50, 25]);
+    }
+
+    #[test]
+    fn search_gives_up_below_one_block() {
+        let options = CalibrationOptions::default().with_start_range(4);
+        let (best, probed) = run_search(&options, 0);
+        assert_eq!(best, None);
+        assert_eq!(probed, vec![4, 2, 1]);
+    }
+
+    fn result(chain: NamedChain, range: Option<u64>) -> CalibrationResult {
+        CalibrationResult {
+            chain,
+            max_block_range: range.map(MaxBlockRange::new),
+            probes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn apply_calibration_keeps_other_overrides() {
+        let mut config = SemioscanConfig::default();
+        config.apply_calibration(&result(NamedChain::Base, Some(2_000)));
+
+        assert_eq!(
+            config.get_max_block_range(NamedChain::Base),
+            MaxBlockRange::new(2_000)
+        );
+        // Base's default rate limit override survives
+        assert_eq!(
+            config.get_rate_limit_delay(NamedChain::Base),
+            Some(Duration::from_millis(250))
+        );
+
+        config.apply_calibration(&result(NamedChain::Base, None));
+        assert_eq!(
+            config.get_max_block_range(NamedChain::Base),
+            MaxBlockRange::new(2_000)
+        );
+    }
+
+    #[test]
+    fn store_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("calibration.json");
+
+        assert_eq!(
+            CalibrationStore::load_or_default(&path).unwrap(),
+            CalibrationStore::new()
+        );
+
+        let mut store = CalibrationStore::new();
+        store.record(&result(NamedChain::Arbitrum, Some(5_000)));
+        store.save(&path).unwrap();
+
+        let loaded = CalibrationStore::load(&path).unwrap();
+        assert_eq!(
+            loaded.get(NamedChain::Arbitrum),
+            Some(MaxBlockRange::new(5_000))
+        );
+
+        let mut config = SemioscanConfig::default();
+        loaded.apply_to(&mut config);
+        assert_eq!(
+            config.get_max_block_range(NamedChain::Arbitrum),
+            MaxBlockRange::new(5_000)
+        );
+    }
+}
@@ -6,8 +6,16 @@
 //!
 //! This module centralizes magic constants and well-known blockchain addresses
 //! used throughout the semioscan crate, improving discoverability and maintainability.
+//!
+//! [`canonical_tokens`] maps each supported chain to its canonical USDC, USDT,
+//! DAI and wrapped native token, so callers don't hard-code addresses.
+//! [`chain_genesis`] gives the first block of each supported chain that
+//! providers reliably serve, so block searches needn't probe block 0.
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{address, Address, BlockNumber};
 
-use alloy_primitives::{address, Address};
+use crate::blocks::window::UnixTimestamp;
 
 /// Well-known stablecoin addresses
 pub mod stablecoins {
@@ -30,6 +38,221 @@ pub mod stablecoins {
     ///
     /// Contract: 0xdAC17F958D2ee523a2206206994597C13D831ec7
     pub const ETH_USDT: Address = address!("dac17f958d2ee523a2206206994597c13d831ec7");
+
+    /// DAI on Ethereum Mainnet
+    ///
+    /// Contract: 0x6B175474E89094C44Da98b954EedeAC495271d0F
+    pub const ETH_DAI: Address = address!("6b175474e89094c44da98b954eedeac495271d0f");
+
+    /// Native USDC on Arbitrum One
+    ///
+    /// Contract: 0xaf88d065e77c8cC2239327C5EDb3A432268e5831
+    pub const ARBITRUM_USDC: Address = address!("af88d065e77c8cc2239327c5edb3a432268e5831");
+
+    /// USDT on Arbitrum One
+    ///
+    /// Contract: 0xFd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9
+    pub const ARBITRUM_USDT: Address = address!("fd086bc7cd5c481dcc9c85ebe478a1c0b69fcbb9");
+
+    /// Native USDC on Base
+    ///
+    /// Contract: 0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913
+    pub const BASE_USDC: Address = address!("833589fcd6edb6e08f4c7c32d4f71b54bda02913");
+
+    /// USDT on BSC (BNB Smart Chain)
+    ///
+    /// Contract: 0x55d398326f99059fF775485246999027B3197955
+    pub const BSC_USDT: Address = address!("55d398326f99059ff775485246999027b3197955");
+
+    /// Native USDC on Optimism
+    ///
+    /// Contract: 0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85
+    pub const OPTIMISM_USDC: Address = address!("0b2c639c533813f4aa9d7837caf62653d097ff85");
+
+    /// Native USDC on Polygon PoS
+    ///
+    /// Contract: 0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359
+    pub const POLYGON_USDC: Address = address!("3c499c542cef5e3811e1192ce70d8cc03d5c3359");
+
+    /// Every stablecoin listed above, the default contents of
+    /// [`StablecoinRegistry`](crate::price::StablecoinRegistry)
+    pub const ALL: &[Address] = &[
+        BSC_BINANCE_PEG_USDC,
+        ETH_USDC,
+        ETH_USDT,
+        ETH_DAI,
+        ARBITRUM_USDC,
+        ARBITRUM_USDT,
+        BASE_USDC,
+        BSC_USDT,
+        OPTIMISM_USDC,
+        POLYGON_USDC,
+    ];
+}
+
+/// Canonical token addresses on one chain
+///
+/// "Canonical" means the token most venues quote against: native USDC where
+/// Circle issues it, otherwise the dominant bridged version. `None` means the
+/// chain has no such token or it isn't listed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalTokens {
+    /// USDC (6 decimals, except Binance-Peg USDC on BSC with 18)
+    pub usdc: Option<Address>,
+    /// USDT (6 decimals, except on BSC with 18)
+    pub usdt: Option<Address>,
+    /// DAI (18 decimals)
+    pub dai: Option<Address>,
+    /// Wrapped native token such as WETH (18 decimals)
+    pub wrapped_native: Option<Address>,
+}
+
+impl CanonicalTokens {
+    const NONE: Self = Self {
+        usdc: None,
+        usdt: None,
+        dai: None,
+        wrapped_native: None,
+    };
+
+    /// Decimals of `token` if it is one of these canonical tokens on `chain`
+    pub fn decimals(&self, chain: NamedChain, token: Address) -> Option<u8> {
+        let bsc = chain == NamedChain::BinanceSmartChain;
+        if self.usdc == Some(token) || self.usdt == Some(token) {
+            Some(if bsc { 18 } else { 6 })
+        } else if self.dai == Some(token) || self.wrapped_native == Some(token) {
+            Some(18)
+        } else {
+            None
+        }
+    }
+}
+
+/// Wrapped native token shared by OP Stack chains
+const OP_STACK_WETH: Address = address!("4200000000000000000000000000000000000006");
+
+/// DAI bridged to Arbitrum and Optimism
+const L2_DAI: Address = address!("da10009cbd5d07dd0cecc66161fc93d7c9000da1");
+
+/// Canonical token addresses on `chain`
+///
+/// # Examples
+///
+/// ```
+/// use alloy_chains::NamedChain;
+/// use semioscan::constants::{canonical_tokens, stablecoins};
+///
+/// let base = canonical_tokens(NamedChain::Base);
+/// assert_eq!(base.usdc, Some(stablecoins::BASE_USDC));
+/// ```
+pub fn canonical_tokens(chain: NamedChain) -> CanonicalTokens {
+    match chain {
+        NamedChain::Mainnet => CanonicalTokens {
+            usdc: Some(stablecoins::ETH_USDC),
+            usdt: Some(stablecoins::ETH_USDT),
+            dai: Some(stablecoins::ETH_DAI),
+            wrapped_native: Some(address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")),
+        },
+        NamedChain::Arbitrum => CanonicalTokens {
+            usdc: Some(stablecoins::ARBITRUM_USDC),
+            usdt: Some(stablecoins::ARBITRUM_USDT),
+            dai: Some(L2_DAI),
+            wrapped_native: Some(address!("82af49447d8a07e3bd95bd0d56f35241523fbab1")),
+        },
+        NamedChain::Optimism => CanonicalTokens {
+            usdc: Some(stablecoins::OPTIMISM_USDC),
+            usdt: Some(address!("94b008aa00579c1307b0ef2c499ad98a8ce58e58")),
+            dai: Some(L2_DAI),
+            wrapped_native: Some(OP_STACK_WETH),
+        },
+        NamedChain::Base => CanonicalTokens {
+            usdc: Some(stablecoins::BASE_USDC),
+            usdt: None,
+            dai: Some(address!("50c5725949a6f0c72e6c4a641f24049a917db0cb")),
+            wrapped_native: Some(OP_STACK_WETH),
+        },
+        NamedChain::Polygon => CanonicalTokens {
+            usdc: Some(stablecoins::POLYGON_USDC),
+            usdt: Some(address!("c2132d05d31c914a87c6611c10748aeb04b58e8f")),
+            dai: Some(address!("8f3cf7ad23cd3cadbd9735aff958023239c6a063")),
+            wrapped_native: Some(address!("0d500b1d8e8ef31e21c99d1db9a6444d3adf1270")),
+        },
+        NamedChain::BinanceSmartChain => CanonicalTokens {
+            usdc: Some(stablecoins::BSC_BINANCE_PEG_USDC),
+            usdt: Some(stablecoins::BSC_USDT),
+            dai: Some(address!("1af3f329e8be154074d8769d1ffa4ee058b1dbc3")),
+            wrapped_native: Some(address!("bb4cdb9cbd36b01bd1cbaebf2de08d9173bc095c")),
+        },
+        NamedChain::Avalanche => CanonicalTokens {
+            usdc: Some(address!("b97ef9ef8734c71904d8002f8b6bc66dd9c48a6e")),
+            usdt: Some(address!("9702230a8ea53601f5cd2dc00fdbc13d4df4a8c7")),
+            dai: Some(address!("d586e7f844cea2f87f50152665bcbc2c279d8d70")),
+            wrapped_native: Some(address!("b31f66aa3c1e785363f0875a1b74e27b85fd66c7")),
+        },
+        _ => CanonicalTokens::NONE,
+    }
+}
+
+/// Canonical USDC on `chain`
+pub fn canonical_usdc(chain: NamedChain) -> Option<Address> {
+    canonical_tokens(chain).usdc
+}
+
+/// Canonical USDT on `chain`
+pub fn canonical_usdt(chain: NamedChain) -> Option<Address> {
+    canonical_tokens(chain).usdt
+}
+
+/// Canonical DAI on `chain`
+pub fn canonical_dai(chain: NamedChain) -> Option<Address> {
+    canonical_tokens(chain).dai
+}
+
+/// Canonical wrapped native token (WETH, WBNB, WPOL, ...) on `chain`
+pub fn canonical_wrapped_native(chain: NamedChain) -> Option<Address> {
+    canonical_tokens(chain).wrapped_native
+}
+
+/// The first block of a chain that block searches should consider
+///
+/// This is usually block 0. It is later where block 0 has a placeholder
+/// timestamp (Ethereum mainnet's genesis is dated 1970) or where common
+/// providers can't serve the early blocks (Arbitrum One's pre-Nitro history).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainGenesis {
+    /// First block searches start from
+    pub first_block: BlockNumber,
+    /// Timestamp of `first_block`
+    pub timestamp: UnixTimestamp,
+}
+
+/// The first searchable block of `chain` and its timestamp, or `None` if the
+/// chain isn't listed and must be probed over RPC
+///
+/// # Examples
+///
+/// ```
+/// use alloy_chains::NamedChain;
+/// use semioscan::constants::chain_genesis;
+///
+/// // Block 0 of Ethereum mainnet has timestamp 0
+/// assert_eq!(chain_genesis(NamedChain::Mainnet).unwrap().first_block, 1);
+/// ```
+pub fn chain_genesis(chain: NamedChain) -> Option<ChainGenesis> {
+    let (first_block, timestamp) = match chain {
+        NamedChain::Mainnet => (1, 1_438_269_988),
+        NamedChain::Sepolia => (0, 1_633_267_481),
+        // Nitro nodes don't serve blocks from before the Nitro migration
+        NamedChain::Arbitrum => (22_207_817, 1_661_956_342),
+        NamedChain::Base => (0, 1_686_789_347),
+        NamedChain::Polygon => (0, 1_590_824_836),
+        NamedChain::BinanceSmartChain => (0, 1_587_390_414),
+        _ => return None,
+    };
+    Some(ChainGenesis {
+        first_block,
+        timestamp: UnixTimestamp(timestamp),
+    })
 }
 
 #[cfg(test)]
@@ -60,4 +283,58 @@ mod tests {
             address!("dac17f958d2ee523a2206206994597c13d831ec7")
         );
     }
+
+    #[test]
+    fn test_canonical_tokens_by_chain() {
+        assert_eq!(
+            canonical_usdc(NamedChain::BinanceSmartChain),
+            Some(stablecoins::BSC_BINANCE_PEG_USDC)
+        );
+        assert_eq!(
+            canonical_wrapped_native(NamedChain::Base),
+            Some(OP_STACK_WETH)
+        );
+        assert_eq!(canonical_dai(NamedChain::Arbitrum), Some(L2_DAI));
+        assert_eq!(canonical_usdt(NamedChain::Base), None);
+        assert_eq!(canonical_tokens(NamedChain::Sepolia), CanonicalTokens::NONE);
+    }
+
+    #[test]
+    fn test_chain_genesis() {
+        let mainnet = chain_genesis(NamedChain::Mainnet).unwrap();
+        assert_eq!(mainnet.first_block, 1);
+        assert!(mainnet.timestamp > UnixTimestamp(0));
+        assert_eq!(
+            chain_genesis(NamedChain::Arbitrum).unwrap().first_block,
+            22_207_817
+        );
+        assert_eq!(chain_genesis(NamedChain::Base).unwrap().first_block, 0);
+        assert_eq!(chain_genesis(NamedChain::Sonic), None);
+    }
+
+    #[test]
+    fn test_canonical_decimals() {
+        let bsc = canonical_tokens(NamedChain::BinanceSmartChain);
+        assert_eq!(
+            bsc.decimals(NamedChain::BinanceSmartChain, stablecoins::BSC_USDT),
+            Some(18)
+        );
+
+        let mainnet = canonical_tokens(NamedChain::Mainnet);
+        assert_eq!(
+            mainnet.decimals(NamedChain::Mainnet, stablecoins::ETH_USDC),
+            Some(6)
+        );
+        assert_eq!(
+            mainnet.decimals(NamedChain::Mainnet, stablecoins::ETH_DAI),
+            Some(18)
+        );
+        assert_eq!(mainnet.decimals(NamedChain::Mainnet, Address::ZERO), None);
+    }
+
+    #[test]
+    fn test_all_stablecoins_are_distinct() {
+        let unique: std::collections::HashSet<_> = stablecoins::ALL.iter().collect();
+        assert_eq!(unique.len(), stablecoins::ALL.len());
+    }
 }
@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Running clock for [`SemioscanConfig::operation_deadline`](super::SemioscanConfig::operation_deadline)
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::{timeout_at, Instant};
+
+/// Tracks how much of an operation's time budget is left
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OperationDeadline {
+    budget: Duration,
+    expires_at: Instant,
+}
+
+impl OperationDeadline {
+    /// Start the clock, or return `None` when no deadline is configured
+    pub(crate) fn start(budget: Option<Duration>) -> Option<Self> {
+        budget.map(|budget| Self {
+            budget,
+            expires_at: Instant::now() + budget,
+        })
+    }
+
+    /// The configured time budget
+    pub(crate) fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Whether the budget is used up
+    pub(crate) fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    /// Run `fut`, returning `None` if the deadline passes first
+    pub(crate) async fn run<F: Future>(&self, fut: F) -> Option<F::Output> {
+        timeout_at(self.expires_at, fut).await.ok()
+    }
+}
+
+/// Run `fut` under an optional deadline, returning `None` if it passes first
+pub(crate) async fn within_deadline<F: Future>(
+    deadline: Option<&OperationDeadline>,
+    fut: F,
+) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => deadline.run(fut).await,
+        None => Some(fut.await),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_budget_means_no_deadline() {
+        assert!(OperationDeadline::start(None).is_none());
+    }
+
+    #[tokio::test]
+    async fn expires_after_budget() {
+        let deadline = OperationDeadline::start(Some(Duration::from_millis(50))).unwrap();
+        assert!(!deadline.is_expired());
+        assert_eq!(within_deadline(Some(&deadline), async { 7 }).await, Some(7));
+
+        let slow = tokio::time::sleep(Duration::from_secs(5));
+        assert_eq!(within_deadline(Some(&deadline), slow).await, None);
+        assert!(deadline.is_expired());
+    }
+
+    #[tokio::test]
+    async fn without_deadline_runs_to_completion() {
+        let slow = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            1
+        };
+        assert_eq!(within_deadline(None, slow).await, Some(1));
+    }
+}
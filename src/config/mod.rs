@@ -37,15 +37,36 @@
 //! // For premium RPC providers with higher rate limits
 //! let config = SemioscanConfig::minimal();
 //! ```
+//!
+//! # Calibrated block ranges
+//!
+//! Instead of guessing `max_block_range`, [`calibration::BlockRangeCalibrator`]
+//! can probe a provider for the largest range it handles and record the result
+//! as a per-chain override.
+//!
+//! # Span verbosity
+//!
+//! [`SpanConfig`] controls which tracing spans calculations create, per
+//! subsystem, and samples the per-item spans that dominate long backfills.
 
 use std::collections::HashMap;
 use std::time::Duration;
 
 use alloy_chains::NamedChain;
 
+#[cfg(feature = "disk-cache")]
+use crate::blocks::CacheAuditLog;
+use crate::transport::{EndpointBudget, Pacer};
 use crate::types::config::MaxBlockRange;
+use crate::types::warnings::{push_warning, ScanWarning};
 
+pub mod calibration;
 pub mod constants;
+mod deadline;
+mod spans;
+
+pub(crate) use deadline::{within_deadline, OperationDeadline};
+pub use spans::{SpanConfig, SpanSubsystem, SpanVerbosity};
 
 /// Configuration for semioscan operations
 ///
@@ -69,6 +90,35 @@ pub struct SemioscanConfig {
     /// Default: 1 (one bounded retry pass per failed decoded transfer)
     pub serial_lookup_fallback_attempts: usize,
 
+    /// Overall time budget for a single calculation (e.g. one daily window or
+    /// one combined data run), as opposed to `rpc_timeout` which bounds each request
+    /// Default: None (no deadline)
+    pub operation_deadline: Option<Duration>,
+
+    /// Shared rate budget for the endpoint this configuration's provider uses
+    /// Default: None (each scanning loop sleeps `rate_limit_delay` between its own requests)
+    pub rate_budget: Option<EndpointBudget>,
+
+    /// Size log-scanning chunks from observed log density instead of using a
+    /// fixed `max_block_range`
+    /// Default: None (fixed-size chunks)
+    pub adaptive_chunking: Option<AdaptiveChunking>,
+
+    /// Which tracing spans calculations create
+    /// Default: every span
+    pub spans: SpanConfig,
+
+    /// What calculations do with logs and transactions they can't process
+    /// Default: [`ScanPolicy::Lenient`] (skip them and report a warning)
+    pub scan_policy: ScanPolicy,
+
+    /// Log every change to the block window cache of a
+    /// [`SemioscanClient`](crate::SemioscanClient), by wrapping it in an
+    /// [`AuditedCache`](crate::AuditedCache)
+    /// Default: None (no audit log)
+    #[cfg(feature = "disk-cache")]
+    pub cache_audit: Option<CacheAuditLog>,
+
     /// Chain-specific overrides
     pub chain_overrides: HashMap<NamedChain, ChainConfig>,
 }
@@ -89,6 +139,101 @@ pub struct ChainConfig {
 
     /// Override serial tx/receipt enrichment retries for this chain
     pub serial_lookup_fallback_attempts: Option<usize>,
+
+    /// Override the overall operation deadline for this chain
+    pub operation_deadline: Option<Duration>,
+}
+
+/// Tuning for adaptive chunk sizing
+///
+/// Scans start with `max_block_range`-sized chunks. A chunk that returns more
+/// than `dense_log_threshold` logs, or fails, halves the chunk size (down to
+/// `min_block_range`); a chunk with fewer than `sparse_log_threshold` logs
+/// doubles it again (up to `max_block_range`).
+///
+/// # Example
+///
+/// ```rust
+/// use semioscan::{AdaptiveChunking, MaxBlockRange, SemioscanConfigBuilder};
+///
+/// let config = SemioscanConfigBuilder::new()
+///     .adaptive_chunking(AdaptiveChunking {
+///         max_block_range: MaxBlockRange::GENEROUS,
+///         ..AdaptiveChunking::default()
+///     })
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveChunking {
+    /// Smallest chunk the scheduler shrinks to
+    /// Default: 10 blocks
+    pub min_block_range: MaxBlockRange,
+
+    /// Starting and largest chunk size, capped by a per-chain
+    /// [`ChainConfig::max_block_range`] when one is set
+    /// Default: 10,000 blocks
+    pub max_block_range: MaxBlockRange,
+
+    /// Shrink when a chunk returns more logs than this
+    /// Default: 5,000
+    pub dense_log_threshold: usize,
+
+    /// Grow when a chunk returns fewer logs than this
+    /// Default: 500
+    pub sparse_log_threshold: usize,
+}
+
+impl Default for AdaptiveChunking {
+    fn default() -> Self {
+        Self {
+            min_block_range: MaxBlockRange::new(10),
+            max_block_range: MaxBlockRange::GENEROUS,
+            dense_log_threshold: 5_000,
+            sparse_log_threshold: 500,
+        }
+    }
+}
+
+/// What a calculation does with data it has to leave out
+///
+/// Gas, price and combined calculations can meet a log that doesn't decode, a
+/// transaction or receipt the provider can't return, or a chunk of blocks whose
+/// logs can't be fetched. Each is described by a [`ScanWarning`].
+///
+/// # Example
+///
+/// ```rust
+/// use semioscan::{ScanPolicy, SemioscanConfigBuilder};
+///
+/// // Audit runs fail instead of reporting incomplete totals
+/// let config = SemioscanConfigBuilder::new()
+///     .scan_policy(ScanPolicy::Strict)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScanPolicy {
+    /// Skip the data, record the warning on the result and continue
+    #[default]
+    Lenient,
+    /// Stop and return an error carrying the warning
+    Strict,
+}
+
+impl ScanPolicy {
+    /// Record `warning` in `warnings`, or return it under [`Strict`](Self::Strict)
+    pub(crate) fn record(
+        self,
+        warnings: &mut Vec<ScanWarning>,
+        warning: ScanWarning,
+    ) -> Result<(), ScanWarning> {
+        match self {
+            ScanPolicy::Lenient => {
+                push_warning(warnings, warning);
+                Ok(())
+            }
+            ScanPolicy::Strict => Err(warning),
+        }
+    }
 }
 
 impl Default for SemioscanConfig {
@@ -118,6 +263,13 @@ impl SemioscanConfig {
             rate_limit_delay: None,
             rpc_timeout: Duration::from_secs(30), // 30 second default timeout
             serial_lookup_fallback_attempts: 1,
+            operation_deadline: None,
+            rate_budget: None,
+            adaptive_chunking: None,
+            spans: SpanConfig::default(),
+            scan_policy: ScanPolicy::default(),
+            #[cfg(feature = "disk-cache")]
+            cache_audit: None,
             chain_overrides: HashMap::new(),
         };
 
@@ -129,6 +281,7 @@ impl SemioscanConfig {
                 rate_limit_delay: Some(Duration::from_millis(250)),
                 rpc_timeout: None, // Use default timeout
                 serial_lookup_fallback_attempts: None,
+                operation_deadline: None,
             },
         );
 
@@ -140,6 +293,7 @@ impl SemioscanConfig {
                 rate_limit_delay: Some(Duration::from_millis(250)),
                 rpc_timeout: None, // Use default timeout
                 serial_lookup_fallback_attempts: None,
+                operation_deadline: None,
             },
         );
 
@@ -164,6 +318,13 @@ impl SemioscanConfig {
             rate_limit_delay: None,
             rpc_timeout: Duration::from_secs(30), // Still include timeout for safety
             serial_lookup_fallback_attempts: 1,
+            operation_deadline: None,
+            rate_budget: None,
+            adaptive_chunking: None,
+            spans: SpanConfig::default(),
+            scan_policy: ScanPolicy::default(),
+            #[cfg(feature = "disk-cache")]
+            cache_audit: None,
             chain_overrides: HashMap::new(),
         }
     }
@@ -186,6 +347,7 @@ impl SemioscanConfig {
     ///         rate_limit_delay: None,
     ///         rpc_timeout: None,
     ///         serial_lookup_fallback_attempts: None,
+    ///         operation_deadline: None,
     ///     },
     ///     );
     ///
@@ -265,6 +427,47 @@ impl SemioscanConfig {
             .unwrap_or(self.serial_lookup_fallback_attempts)
     }
 
+    /// Get effective operation deadline for a specific chain
+    ///
+    /// Returns chain-specific override if set, otherwise returns global default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use semioscan::SemioscanConfigBuilder;
+    /// use alloy_chains::NamedChain;
+    /// use std::time::Duration;
+    ///
+    /// let config = SemioscanConfigBuilder::new()
+    ///     .operation_deadline(Duration::from_secs(600))
+    ///     .chain_operation_deadline(NamedChain::Mainnet, Duration::from_secs(1800))
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     config.get_operation_deadline(NamedChain::Mainnet),
+    ///     Some(Duration::from_secs(1800))
+    /// );
+    /// assert_eq!(
+    ///     config.get_operation_deadline(NamedChain::Base),
+    ///     Some(Duration::from_secs(600))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn get_operation_deadline(&self, chain: NamedChain) -> Option<Duration> {
+        self.chain_overrides
+            .get(&chain)
+            .and_then(|c| c.operation_deadline)
+            .or(self.operation_deadline)
+    }
+
+    /// Pacer for one scanning loop on `chain`
+    ///
+    /// Draws from [`rate_budget`](Self::rate_budget) when set, otherwise sleeps
+    /// the chain's rate limit delay between the loop's requests.
+    pub(crate) fn pacer(&self, chain: NamedChain) -> Pacer<'_> {
+        Pacer::new(self.rate_budget.as_ref(), self.get_rate_limit_delay(chain))
+    }
+
     /// Set chain-specific override
     ///
     /// # Example
@@ -282,6 +485,7 @@ impl SemioscanConfig {
     ///         rate_limit_delay: Some(Duration::from_millis(500)),
     ///         rpc_timeout: None,
     ///         serial_lookup_fallback_attempts: None,
+    ///         operation_deadline: None,
     ///     },
     /// );
     /// ```
@@ -407,6 +611,69 @@ impl SemioscanConfigBuilder {
         self
     }
 
+    /// Enable adaptive chunk sizing for every log-scanning loop
+    pub fn adaptive_chunking(mut self, adaptive: AdaptiveChunking) -> Self {
+        self.config.adaptive_chunking = Some(adaptive);
+        self
+    }
+
+    /// Set what calculations do with logs and transactions they can't process
+    pub fn scan_policy(mut self, policy: ScanPolicy) -> Self {
+        self.config.scan_policy = policy;
+        self
+    }
+
+    /// Record every change to the client's block window cache in `log`
+    ///
+    /// Leave this unset when the cache is a [`DiskCache`](crate::DiskCache)
+    /// with its own [`with_audit_log`](crate::DiskCache::with_audit_log), or
+    /// inserts are logged twice.
+    #[cfg(feature = "disk-cache")]
+    pub fn cache_audit_log(mut self, log: CacheAuditLog) -> Self {
+        self.config.cache_audit = Some(log);
+        self
+    }
+
+    /// Set the span verbosity of every subsystem without an override
+    pub fn span_verbosity(mut self, verbosity: SpanVerbosity) -> Self {
+        self.config.spans.verbosity = verbosity;
+        self
+    }
+
+    /// Set the span verbosity of one subsystem
+    pub fn subsystem_span_verbosity(
+        mut self,
+        subsystem: SpanSubsystem,
+        verbosity: SpanVerbosity,
+    ) -> Self {
+        self.config.spans.overrides.insert(subsystem, verbosity);
+        self
+    }
+
+    /// Keep one item span in `interval` (1 keeps all; 0 is treated as 1)
+    pub fn item_span_sampling(mut self, interval: u64) -> Self {
+        self.config.spans.item_sample_interval =
+            std::num::NonZeroU64::new(interval).unwrap_or(std::num::NonZeroU64::MIN);
+        self
+    }
+
+    /// Share a rate budget with other calculators using the same endpoint
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use semioscan::{RateBudget, SemioscanConfigBuilder};
+    ///
+    /// let budget = RateBudget::new();
+    /// let config = SemioscanConfigBuilder::with_defaults()
+    ///     .rate_budget(budget.endpoint("base-mainnet"))
+    ///     .build();
+    /// ```
+    pub fn rate_budget(mut self, budget: EndpointBudget) -> Self {
+        self.config.rate_budget = Some(budget);
+        self
+    }
+
     /// Set global serial tx/receipt enrichment fallback attempts.
     ///
     /// `0` disables the serial fallback pass after batch lookup failures.
@@ -415,6 +682,15 @@ impl SemioscanConfigBuilder {
         self
     }
 
+    /// Set global operation deadline
+    ///
+    /// Calculations that run past the deadline fail with a deadline error that
+    /// carries the progress made so far.
+    pub fn operation_deadline(mut self, deadline: Duration) -> Self {
+        self.config.operation_deadline = Some(deadline);
+        self
+    }
+
     /// Add chain-specific configuration
     ///
     /// # Example
@@ -432,6 +708,7 @@ impl SemioscanConfigBuilder {
     ///             rate_limit_delay: Some(Duration::from_millis(500)),
     ///             rpc_timeout: None,
     ///             serial_lookup_fallback_attempts: None,
+    ///             operation_deadline: None,
     ///         },
     ///     )
     ///     .build();
@@ -500,6 +777,11 @@ impl SemioscanConfigBuilder {
         })
     }
 
+    /// Convenience: set operation deadline for a specific chain
+    pub fn chain_operation_deadline(self, chain: NamedChain, deadline: Duration) -> Self {
+        self.modify_chain(chain, |c| c.operation_deadline = Some(deadline))
+    }
+
     fn modify_chain<F: FnOnce(&mut ChainConfig)>(mut self, chain: NamedChain, f: F) -> Self {
         f(self.config.chain_overrides.entry(chain).or_default());
         self
@@ -606,6 +888,7 @@ mod tests {
                 rate_limit_delay: Some(Duration::from_millis(100)),
                 rpc_timeout: None, // Use default timeout
                 serial_lookup_fallback_attempts: None,
+                operation_deadline: None,
             },
         );
 
@@ -746,4 +1029,24 @@ mod tests {
             Some(Duration::from_millis(500))
         );
     }
+
+    #[test]
+    fn test_operation_deadline_defaults_and_overrides() {
+        let config = SemioscanConfig::default();
+        assert_eq!(config.get_operation_deadline(NamedChain::Mainnet), None);
+
+        let config = SemioscanConfigBuilder::with_defaults()
+            .chain_operation_deadline(NamedChain::Base, Duration::from_secs(120))
+            .build();
+        assert_eq!(
+            config.get_operation_deadline(NamedChain::Base),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(config.get_operation_deadline(NamedChain::Arbitrum), None);
+        // Existing Base override survives
+        assert_eq!(
+            config.get_rate_limit_delay(NamedChain::Base),
+            Some(Duration::from_millis(250))
+        );
+    }
 }
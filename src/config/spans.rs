@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Span verbosity and sampling
+//!
+//! Every calculation opens a span per operation, per chunk of blocks, and per
+//! log, transaction or block it touches. At trace level a month-long backfill
+//! produces millions of item spans. [`SpanConfig`] limits how deep each
+//! subsystem goes and keeps only a sample of the item spans.
+
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
+/// How detailed the spans of a subsystem are
+///
+/// Levels are cumulative: `Chunk` also keeps operation spans.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpanVerbosity {
+    /// No spans
+    Off,
+    /// One span per public operation, such as a daily window or a gas
+    /// calculation
+    Operation,
+    /// Also a span per chunk of blocks scanned or per search
+    Chunk,
+    /// Also a span per log, transaction or block header processed
+    #[default]
+    Item,
+}
+
+/// Groups of spans that can be configured separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpanSubsystem {
+    /// Daily block window searches
+    BlockWindow,
+    /// Gas cost calculations
+    Gas,
+    /// Combined transfer and gas retrieval
+    Retrieval,
+}
+
+/// Which spans semioscan creates
+///
+/// The default keeps every span, as earlier releases did.
+///
+/// # Example
+///
+/// ```rust
+/// use semioscan::{SemioscanConfigBuilder, SpanSubsystem, SpanVerbosity};
+///
+/// // Keep chunk spans everywhere, item spans for 1 in 100 gas transactions
+/// let config = SemioscanConfigBuilder::new()
+///     .span_verbosity(SpanVerbosity::Chunk)
+///     .subsystem_span_verbosity(SpanSubsystem::Gas, SpanVerbosity::Item)
+///     .item_span_sampling(100)
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanConfig {
+    /// Verbosity of subsystems without an override
+    pub verbosity: SpanVerbosity,
+    /// Per-subsystem verbosity
+    pub overrides: HashMap<SpanSubsystem, SpanVerbosity>,
+    /// Keep one item span in this many
+    ///
+    /// Sampling is keyed by the item (transaction hash or block number), so
+    /// the same items are kept across runs.
+    pub item_sample_interval: NonZeroU64,
+}
+
+impl Default for SpanConfig {
+    fn default() -> Self {
+        Self {
+            verbosity: SpanVerbosity::default(),
+            overrides: HashMap::new(),
+            item_sample_interval: NonZeroU64::MIN,
+        }
+    }
+}
+
+impl SpanConfig {
+    /// Verbosity of `subsystem`
+    pub fn verbosity_for(&self, subsystem: SpanSubsystem) -> SpanVerbosity {
+        self.overrides
+            .get(&subsystem)
+            .copied()
+            .unwrap_or(self.verbosity)
+    }
+
+    /// Whether `subsystem` creates spans at `level`
+    pub(crate) fn enabled(&self, subsystem: SpanSubsystem, level: SpanVerbosity) -> bool {
+        level != SpanVerbosity::Off && self.verbosity_for(subsystem) >= level
+    }
+
+    /// Whether `subsystem` creates the item span of the item identified by `key`
+    pub(crate) fn item_enabled(&self, subsystem: SpanSubsystem, key: u64) -> bool {
+        self.enabled(subsystem, SpanVerbosity::Item) && key % self.item_sample_interval.get() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_and_sampling_limit_spans() {
+        let config = SpanConfig {
+            verbosity: SpanVerbosity::Chunk,
+            overrides: HashMap::from([(SpanSubsystem::Gas, SpanVerbosity::Off)]),
+            item_sample_interval: NonZeroU64::new(10).unwrap(),
+        };
+
+        assert!(config.enabled(SpanSubsystem::Retrieval, SpanVerbosity::Chunk));
+        assert!(!config.item_enabled(SpanSubsystem::Retrieval, 20));
+        assert!(!config.enabled(SpanSubsystem::Gas, SpanVerbosity::Operation));
+
+        let config = SpanConfig {
+            verbosity: SpanVerbosity::Item,
+            ..config
+        };
+        assert!(config.item_enabled(SpanSubsystem::BlockWindow, 20));
+        assert!(!config.item_enabled(SpanSubsystem::BlockWindow, 21));
+    }
+}
@@ -0,0 +1,765 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Nightly incremental runs over a fixed set of targets
+//!
+//! The usual job built around this crate computes, every day, yesterday's gas,
+//! transfer and price figures for a list of (chain, token, sender, recipient)
+//! targets and stores them somewhere. [`DailyRunner`] is that job: it takes a
+//! [`SemioscanClient`], the [`DailyTarget`]s and any number of
+//! [`ResultSink`]s, and for each target:
+//!
+//! 1. maps the date to its block window,
+//! 2. waits until the chain's finalized block has passed the end of the
+//!    window, so figures are never computed over blocks a reorg could undo,
+//! 3. runs the gas, combined and (where a price source is configured) price
+//!    calculations over the window,
+//! 4. hands the resulting [`DailyReport`] to every sink.
+//!
+//! When a sink fails, the target is retried on the next run, but only sinks
+//! that haven't accepted its report yet are given it again. A report can
+//! still reach a sink twice if the process restarts in between, so sinks
+//! should write idempotently, e.g. by upserting on target and date.
+//!
+//! Once every target has been tried, each sink also gets a [`RunSummary`]
+//! of the run.
+//!
+//! [`DailyRunner::run_for_date`] does this once; [`DailyRunner::run_daily`]
+//! repeats it for each new day until cancelled, retrying targets that weren't
+//! final yet or failed.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{DailyRunner, DailyTarget, SemioscanClient};
+//!
+//! let client = Arc::new(
+//!     SemioscanClient::builder()
+//!         .add_chain(NamedChain::Base, base_url)
+//!         .build()?,
+//! );
+//! let runner = DailyRunner::new(client)
+//!     .with_target(DailyTarget::new(NamedChain::Base, treasury, router, usdc))
+//!     .with_price_source(NamedChain::Base, usdc, Box::new(UniswapV3Source::new(router)))?
+//!     .with_sink(PostgresSink(pool));
+//!
+//! let shutdown = CancellationToken::new();
+//! runner.run_daily(shutdown.clone()).await;
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy_chains::NamedChain;
+use alloy_network::Ethereum;
+use alloy_primitives::{Address, BlockNumber};
+use alloy_provider::RootProvider;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::blocks::window::DailyBlockWindow;
+use crate::cache::finality::FinalityTracker;
+use crate::client::SemioscanClient;
+use crate::errors::SemioscanError;
+use crate::gas::calculator::GasCostResult;
+use crate::price::{PriceCalculator, PriceSource, TokenPriceResult};
+use crate::retrieval::CombinedDataResult;
+use crate::sink::ResultSink;
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
+
+/// Transfers of one token between two addresses on one chain, computed daily
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DailyTarget {
+    /// Chain the transfers happen on
+    #[serde(rename = "chain")]
+    pub chain: NamedChain,
+    /// Token transferred
+    #[serde(rename = "token_address")]
+    pub token_address: TokenAddress,
+    /// Sender of the transfers
+    #[serde(rename = "from_address")]
+    pub from_address: SenderAddress,
+    /// Recipient of the transfers
+    #[serde(rename = "to_address")]
+    pub to_address: RecipientAddress,
+}
+
+impl DailyTarget {
+    /// Transfers of `token_address` from `from_address` to `to_address` on
+    /// `chain`, in the argument order of
+    /// [`SemioscanClient::daily_gas_report`]
+    pub fn new(
+        chain: NamedChain,
+        from_address: impl Into<SenderAddress>,
+        to_address: impl Into<RecipientAddress>,
+        token_address: impl Into<TokenAddress>,
+    ) -> Self {
+        Self {
+            chain,
+            token_address: token_address.into(),
+            from_address: from_address.into(),
+            to_address: to_address.into(),
+        }
+    }
+}
+
+/// Everything computed for one target over one UTC day
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyReport {
+    /// Target the figures are for
+    #[serde(rename = "target")]
+    pub target: DailyTarget,
+    /// UTC day the figures cover
+    #[serde(rename = "date")]
+    pub date: NaiveDate,
+    /// Blocks produced on `date`
+    #[serde(rename = "window")]
+    pub window: DailyBlockWindow,
+    /// Gas costs of the matching transfers within `window`
+    #[serde(rename = "gas")]
+    pub gas: GasCostResult,
+    /// Transfer amounts and per-transaction gas within `window`
+    #[serde(rename = "combined")]
+    pub combined: CombinedDataResult,
+    /// Price of the token over `window`, if a price source is configured for
+    /// the chain
+    #[serde(rename = "price", skip_serializing_if = "Option::is_none")]
+    pub price: Option<TokenPriceResult>,
+}
+
+/// What happened to one target during a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetOutcome {
+    /// The results were computed and every sink accepted them
+    Completed,
+    /// The day's last block isn't final yet, so nothing was computed
+    AwaitingFinality {
+        /// Last block of the day's window
+        end_block: BlockNumber,
+        /// Finalized block at the time of the run
+        finalized_block: BlockNumber,
+    },
+    /// A lookup, calculation or sink failed
+    Failed {
+        /// The error, rendered
+        error: String,
+    },
+}
+
+/// Outcome of every target for one date
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyRunReport {
+    /// UTC day the targets were run for
+    pub date: NaiveDate,
+    /// Targets in the order they were added, with their outcome
+    pub outcomes: Vec<(DailyTarget, TargetOutcome)>,
+}
+
+impl DailyRunReport {
+    /// Whether every target completed
+    pub fn is_complete(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|(_, outcome)| *outcome == TargetOutcome::Completed)
+    }
+
+    /// Targets that didn't complete, to run again later
+    pub fn incomplete(&self) -> impl Iterator<Item = &DailyTarget> {
+        self.outcomes
+            .iter()
+            .filter(|(_, outcome)| *outcome != TargetOutcome::Completed)
+            .map(|(target, _)| target)
+    }
+}
+
+/// A target that failed during a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedTarget {
+    /// The target that failed
+    #[serde(rename = "target")]
+    pub target: DailyTarget,
+    /// The error, rendered
+    #[serde(rename = "error")]
+    pub error: String,
+}
+
+/// Totals of one run, handed to every [`ResultSink`] once the run is over
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// UTC day the targets were run for
+    #[serde(rename = "date")]
+    pub date: NaiveDate,
+    /// When the run started
+    #[serde(rename = "started_at")]
+    pub started_at: DateTime<Utc>,
+    /// When the last target had been tried
+    #[serde(rename = "finished_at")]
+    pub finished_at: DateTime<Utc>,
+    /// Targets whose results every sink accepted
+    #[serde(rename = "completed")]
+    pub completed: usize,
+    /// Targets skipped because the day wasn't final yet
+    #[serde(rename = "awaiting_finality")]
+    pub awaiting_finality: usize,
+    /// Targets that failed, with their errors
+    #[serde(rename = "failed", skip_serializing_if = "Vec::is_empty", default)]
+    pub failed: Vec<FailedTarget>,
+}
+
+impl RunSummary {
+    fn new(report: &DailyRunReport, started_at: DateTime<Utc>) -> Self {
+        let mut summary = Self {
+            date: report.date,
+            started_at,
+            finished_at: Utc::now(),
+            completed: 0,
+            awaiting_finality: 0,
+            failed: Vec::new(),
+        };
+        for (target, outcome) in &report.outcomes {
+            match outcome {
+                TargetOutcome::Completed => summary.completed += 1,
+                TargetOutcome::AwaitingFinality { .. } => summary.awaiting_finality += 1,
+                TargetOutcome::Failed { error } => summary.failed.push(FailedTarget {
+                    target: *target,
+                    error: error.clone(),
+                }),
+            }
+        }
+        summary
+    }
+}
+
+/// Computes daily figures for a set of targets and hands them to sinks
+///
+/// See the [module documentation](self) for what a run does.
+pub struct DailyRunner {
+    client: Arc<SemioscanClient>,
+    targets: Vec<DailyTarget>,
+    sinks: Vec<Arc<dyn ResultSink>>,
+    prices: HashMap<NamedChain, Mutex<PriceCalculator<RootProvider<Ethereum>>>>,
+    /// Trackers by chain, created on a chain's first run unless given
+    finality: Mutex<HashMap<NamedChain, FinalityTracker>>,
+    /// Indices of the sinks that accepted each target's report for a date,
+    /// so retries skip them; dropped once every sink has it
+    delivered: Mutex<HashMap<(DailyTarget, NaiveDate), HashSet<usize>>>,
+    poll_interval: Duration,
+}
+
+impl DailyRunner {
+    /// Create a runner with no targets or sinks
+    pub fn new(client: Arc<SemioscanClient>) -> Self {
+        Self {
+            client,
+            targets: Vec::new(),
+            sinks: Vec::new(),
+            prices: HashMap::new(),
+            finality: Mutex::default(),
+            delivered: Mutex::default(),
+            poll_interval: Duration::from_secs(300),
+        }
+    }
+
+    /// Compute figures for `target` on every run
+    ///
+    /// Adding the same target twice has no effect.
+    pub fn with_target(mut self, target: DailyTarget) -> Self {
+        if !self.targets.contains(&target) {
+            self.targets.push(target);
+        }
+        self
+    }
+
+    /// Hand every [`DailyReport`] to `sink`, after any sinks added earlier
+    pub fn with_sink(mut self, sink: impl ResultSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Price tokens on `chain` against `usdc_address` with `price_source`
+    ///
+    /// Without a price source, [`DailyReport::price`] is `None` for the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SemioscanError::ChainNotConfigured`] if the client has no
+    /// endpoint for `chain`.
+    pub fn with_price_source(
+        mut self,
+        chain: NamedChain,
+        usdc_address: Address,
+        price_source: Box<dyn PriceSource>,
+    ) -> Result<Self, SemioscanError> {
+        let calculator = self
+            .client
+            .price_calculator(chain, usdc_address, price_source)?;
+        self.prices.insert(chain, Mutex::new(calculator));
+        Ok(self)
+    }
+
+    /// Decide when `chain`'s blocks are final with `tracker`
+    ///
+    /// Defaults to a [`FinalityTracker::new`] per chain.
+    pub fn with_finality_tracker(mut self, chain: NamedChain, tracker: FinalityTracker) -> Self {
+        self.finality.get_mut().insert(chain, tracker);
+        self
+    }
+
+    /// How long [`run_daily`](Self::run_daily) waits before retrying targets
+    /// that weren't final yet or failed
+    ///
+    /// Defaults to five minutes.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Run every target for yesterday (UTC)
+    pub async fn run_yesterday(&self) -> DailyRunReport {
+        self.run_for_date(yesterday()).await
+    }
+
+    /// Run every target for `date`
+    ///
+    /// Targets are run one at a time, so a chain's rate limit is shared with
+    /// nothing else the runner does. A failing target doesn't stop the others.
+    pub async fn run_for_date(&self, date: NaiveDate) -> DailyRunReport {
+        let targets = self.targets.clone();
+        self.run_targets(date, &targets).await
+    }
+
+    /// Run yesterday's targets, then each following day's, until `shutdown`
+    /// is cancelled
+    ///
+    /// Targets that weren't final yet or failed are retried every
+    /// [poll interval](Self::with_poll_interval) until they complete or the
+    /// next day starts, at which point they are given up on with a warning.
+    pub async fn run_daily(&self, shutdown: CancellationToken) {
+        let mut date = yesterday();
+        let mut pending = self.targets.clone();
+        loop {
+            if !pending.is_empty() {
+                let report = self.run_targets(date, &pending).await;
+                pending = report.incomplete().copied().collect();
+            }
+
+            let until_next_day = duration_until_next_day();
+            let wait = if pending.is_empty() {
+                until_next_day
+            } else {
+                self.poll_interval.min(until_next_day)
+            };
+            if tokio::time::timeout(wait, shutdown.cancelled())
+                .await
+                .is_ok()
+            {
+                info!("Daily runner stopped");
+                return;
+            }
+
+            let next = yesterday();
+            if next != date {
+                if !pending.is_empty() {
+                    warn!(
+                        %date,
+                        incomplete = pending.len(),
+                        "Giving up on targets that didn't complete before the next day"
+                    );
+                }
+                self.delivered
+                    .lock()
+                    .await
+                    .retain(|(_, day), _| *day != date);
+                date = next;
+                pending = self.targets.clone();
+            }
+        }
+    }
+
+    async fn run_targets(&self, date: NaiveDate, targets: &[DailyTarget]) -> DailyRunReport {
+        let started_at = Utc::now();
+        let mut finalized: HashMap<NamedChain, Result<BlockNumber, String>> = HashMap::new();
+        let mut outcomes = Vec::with_capacity(targets.len());
+
+        for target in targets {
+            if !finalized.contains_key(&target.chain) {
+                let reading = self.finalized_block(target.chain).await;
+                finalized.insert(target.chain, reading.map_err(|e| e.to_string()));
+            }
+            let outcome = match &finalized[&target.chain] {
+                Ok(finalized_block) => match self.run_target(*target, date, *finalized_block).await
+                {
+                    Ok(outcome) => outcome,
+                    Err(e) => TargetOutcome::Failed {
+                        error: e.to_string(),
+                    },
+                },
+                Err(error) => TargetOutcome::Failed {
+                    error: error.clone(),
+                },
+            };
+
+            match &outcome {
+                TargetOutcome::Completed => {}
+                TargetOutcome::AwaitingFinality {
+                    end_block,
+                    finalized_block,
+                } => info!(
+                    chain = %target.chain,
+                    %date,
+                    end_block,
+                    finalized_block,
+                    "Day not final yet"
+                ),
+                TargetOutcome::Failed { error } => warn!(
+                    chain = %target.chain,
+                    %date,
+                    token = %target.token_address,
+                    %error,
+                    "Daily run failed for target"
+                ),
+            }
+            outcomes.push((*target, outcome));
+        }
+
+        let report = DailyRunReport { date, outcomes };
+        info!(
+            %date,
+            targets = targets.len(),
+            complete = report.is_complete(),
+            "Finished daily run"
+        );
+
+        // A summary that can't be delivered doesn't undo the results
+        let summary = RunSummary::new(&report, started_at);
+        for sink in &self.sinks {
+            if let Err(e) = sink.write_summary(&summary).await {
+                warn!(%date, error = %e, "Sink rejected run summary");
+            }
+        }
+        report
+    }
+
+    async fn finalized_block(&self, chain: NamedChain) -> Result<BlockNumber, SemioscanError> {
+        let provider = self.client.provider(chain)?;
+        // Keep the tracker, which never reports the finalized block going back
+        let tracker = self.finality.lock().await.entry(chain).or_default().clone();
+        let finality = tracker.refresh(provider).await?;
+        Ok(finality.finalized_block)
+    }
+
+    async fn run_target(
+        &self,
+        target: DailyTarget,
+        date: NaiveDate,
+        finalized_block: BlockNumber,
+    ) -> Result<TargetOutcome, SemioscanError> {
+        let window = self.client.daily_window(target.chain, date).await?;
+        if window.end_block > finalized_block {
+            return Ok(TargetOutcome::AwaitingFinality {
+                end_block: window.end_block,
+                finalized_block,
+            });
+        }
+
+        let gas = self
+            .client
+            .daily_gas_report(
+                target.chain,
+                date,
+                target.from_address,
+                target.to_address,
+                target.token_address,
+            )
+            .await?
+            .gas;
+        let combined = self
+            .client
+            .daily_combined_data(
+                target.chain,
+                date,
+                target.from_address,
+                target.to_address,
+                target.token_address,
+            )
+            .await?;
+        let price = match self.prices.get(&target.chain) {
+            Some(calculator) => Some(
+                calculator
+                    .lock()
+                    .await
+                    .calculate_price_between_blocks(
+                        target.token_address,
+                        window.start_block,
+                        window.end_block,
+                    )
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let report = DailyReport {
+            target,
+            date,
+            window,
+            gas,
+            combined,
+            price,
+        };
+        let key = (target, date);
+        for (index, sink) in self.sinks.iter().enumerate() {
+            let delivered = self
+                .delivered
+                .lock()
+                .await
+                .get(&key)
+                .is_some_and(|sinks| sinks.contains(&index));
+            if delivered {
+                continue;
+            }
+            sink.write_daily(&report).await?;
+            self.delivered
+                .lock()
+                .await
+                .entry(key)
+                .or_default()
+                .insert(index);
+        }
+        self.delivered.lock().await.remove(&key);
+        Ok(TargetOutcome::Completed)
+    }
+}
+
+/// The UTC date before today
+fn yesterday() -> NaiveDate {
+    let today = Utc::now().date_naive();
+    today.checked_sub_days(Days::new(1)).unwrap_or(today)
+}
+
+/// Time left until the next UTC midnight
+fn duration_until_next_day() -> Duration {
+    let now = Utc::now();
+    now.date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|tomorrow| tomorrow.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| (midnight.and_utc() - now).to_std().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use alloy_primitives::U64;
+    use alloy_rpc_types::Log;
+    use alloy_transport::mock::Asserter;
+    use async_trait::async_trait;
+
+    use crate::blocks::cache::{BlockWindowCache, CacheKey, MemoryCache};
+    use crate::blocks::window::UnixTimestamp;
+    use crate::errors::SinkError;
+
+    struct CountingSink(Arc<AtomicUsize>, Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl ResultSink for CountingSink {
+        async fn write_daily(&self, _report: &DailyReport) -> Result<(), SinkError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn write_summary(&self, summary: &RunSummary) -> Result<(), SinkError> {
+            assert_eq!(summary.failed.len(), 1);
+            assert_eq!(summary.completed, 0);
+            self.1.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_chain_fails_its_targets_and_is_summarized() {
+        let client = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .build()
+            .unwrap();
+        let writes = Arc::new(AtomicUsize::new(0));
+        let summaries = Arc::new(AtomicUsize::new(0));
+        let target = DailyTarget::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x01),
+        );
+        let runner = DailyRunner::new(Arc::new(client))
+            .with_target(target)
+            .with_target(target)
+            .with_sink(CountingSink(writes.clone(), summaries.clone()));
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let report = runner.run_for_date(date).await;
+
+        assert_eq!(report.outcomes.len(), 1);
+        assert!(matches!(
+            &report.outcomes[0].1,
+            TargetOutcome::Failed { error } if error.contains("No RPC endpoint")
+        ));
+        assert!(!report.is_complete());
+        assert_eq!(report.incomplete().collect::<Vec<_>>(), vec![&target]);
+        assert_eq!(writes.load(Ordering::SeqCst), 0);
+        assert_eq!(summaries.load(Ordering::SeqCst), 1);
+    }
+
+    /// Counts the reports it accepts, rejecting the first `failures` of them
+    #[derive(Default)]
+    struct RecordingSink {
+        failures: AtomicUsize,
+        writes: AtomicUsize,
+        summaries: std::sync::Mutex<Vec<RunSummary>>,
+    }
+
+    #[async_trait]
+    impl ResultSink for Arc<RecordingSink> {
+        async fn write_daily(&self, _report: &DailyReport) -> Result<(), SinkError> {
+            let failing = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err(SinkError::Other("storage unavailable".into()));
+            }
+            self.writes.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn write_summary(&self, summary: &RunSummary) -> Result<(), SinkError> {
+            self.summaries.lock().unwrap().push(summary.clone());
+            Ok(())
+        }
+    }
+
+    /// A Mainnet runner whose window for `date` is blocks 100 to 110,
+    /// answering RPC requests from `asserter`
+    async fn mocked_runner(asserter: &Asserter, date: NaiveDate) -> DailyRunner {
+        let windows = MemoryCache::new();
+        let window = DailyBlockWindow::new(
+            100,
+            110,
+            UnixTimestamp(1_735_689_600),
+            UnixTimestamp(1_735_776_000),
+        )
+        .unwrap();
+        windows
+            .insert(CacheKey::new(NamedChain::Mainnet, date), window)
+            .await
+            .unwrap();
+        let client = SemioscanClient::builder()
+            .add_chain(NamedChain::Mainnet, "http://localhost:8545")
+            .with_window_cache(windows)
+            .build_mocked(asserter.clone());
+        DailyRunner::new(Arc::new(client)).with_finality_tracker(
+            NamedChain::Mainnet,
+            FinalityTracker::new().with_confirmation_depth(0),
+        )
+    }
+
+    /// Queue a finality reading of `latest` from a node without finality tags
+    fn push_finalized_block(asserter: &Asserter, latest: u64) {
+        asserter.push_failure_msg("finalized tag unsupported");
+        asserter.push_success(&U64::from(latest));
+    }
+
+    fn target() -> DailyTarget {
+        DailyTarget::new(
+            NamedChain::Mainnet,
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x01),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_day_past_the_finalized_block_awaits_finality_without_writing() {
+        let asserter = Asserter::new();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let sink = Arc::new(RecordingSink::default());
+        let runner = mocked_runner(&asserter, date)
+            .await
+            .with_target(target())
+            .with_sink(sink.clone());
+
+        push_finalized_block(&asserter, 105);
+        let report = runner.run_for_date(date).await;
+
+        assert_eq!(
+            report.outcomes,
+            vec![(
+                target(),
+                TargetOutcome::AwaitingFinality {
+                    end_block: 110,
+                    finalized_block: 105,
+                }
+            )]
+        );
+        assert_eq!(sink.writes.load(Ordering::SeqCst), 0);
+        let summaries = sink.summaries.lock().unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].awaiting_finality, 1);
+        assert_eq!(summaries[0].completed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_completed_target_reaches_each_sink_once_across_retries() {
+        let asserter = Asserter::new();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let accepting = Arc::new(RecordingSink::default());
+        let flaky = Arc::new(RecordingSink {
+            failures: AtomicUsize::new(1),
+            ..RecordingSink::default()
+        });
+        let runner = mocked_runner(&asserter, date)
+            .await
+            .with_target(target())
+            .with_sink(accepting.clone())
+            .with_sink(flaky.clone());
+
+        // Finality, the gas scan's logs and capability probes, then the
+        // combined scan's logs
+        push_finalized_block(&asserter, 200);
+        asserter.push_success(&Vec::<Log>::new());
+        for _ in 0..6 {
+            asserter.push_failure_msg("method not supported");
+        }
+        asserter.push_success(&Vec::<Log>::new());
+        let report = runner.run_for_date(date).await;
+        assert!(matches!(
+            &report.outcomes[0].1,
+            TargetOutcome::Failed { error } if error.contains("storage unavailable")
+        ));
+        assert_eq!(accepting.writes.load(Ordering::SeqCst), 1);
+        assert_eq!(flaky.writes.load(Ordering::SeqCst), 0);
+
+        // The retry reads gas from the cache and rescans the combined data,
+        // but only hands the report to the sink that rejected it
+        push_finalized_block(&asserter, 200);
+        asserter.push_success(&Vec::<Log>::new());
+        let report = runner.run_for_date(date).await;
+        assert!(report.is_complete());
+        assert_eq!(accepting.writes.load(Ordering::SeqCst), 1);
+        assert_eq!(flaky.writes.load(Ordering::SeqCst), 1);
+        assert_eq!(flaky.summaries.lock().unwrap()[1].completed, 1);
+    }
+
+    #[test]
+    fn test_next_day_is_at_most_a_day_away() {
+        let wait = duration_until_next_day();
+        assert!(wait > Duration::ZERO);
+        assert!(wait <= Duration::from_secs(24 * 60 * 60));
+        assert!(yesterday() < Utc::now().date_naive());
+    }
+}
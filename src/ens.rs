@@ -0,0 +1,313 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! ENS name resolution for address labels
+//!
+//! Mainnet reports read better with `vitalik.eth` than with
+//! `0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045`. [`EnsResolver`] looks names up
+//! in the ENS registry in both directions and caches every answer, including
+//! "no name", so repeated lookups across a report cost nothing.
+//!
+//! Reverse records are set by the address owner and can claim any name, so
+//! [`EnsResolver::lookup_address`] only returns a name whose forward resolution
+//! points back at the same address.
+//!
+//! Names are hashed as given, lowercased. Full UTS-46 normalization is not
+//! applied, which is fine for the ASCII names reports mostly deal with.
+//!
+//! Enabled with the `ens` feature.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{AddressBook, EnsResolver};
+//!
+//! let resolver = EnsResolver::new(mainnet_provider);
+//! let mut book = AddressBook::load("labels.json")?;
+//!
+//! // `result` is a `CombinedDataResult`
+//! let added = resolver
+//!     .label_addresses(&mut book, [result.from_address, result.to_address])
+//!     .await?;
+//! println!("{added} addresses labelled from ENS");
+//!
+//! // "vitalik.eth (0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045)"
+//! println!("{}", book.display(result.from_address));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use alloy_primitives::{address, hex, keccak256, Address, Bytes, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{TransactionInput, TransactionRequest};
+use alloy_sol_types::{sol, SolCall};
+use tracing::debug;
+
+use crate::errors::RpcError;
+use crate::labels::{AddressBook, AddressLabel};
+
+/// ENS registry, deployed at the same address on mainnet and testnets
+pub const ENS_REGISTRY: Address = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+/// Tag given to labels added by [`EnsResolver::label_addresses`]
+pub const ENS_TAG: &str = "ens";
+
+sol! {
+    function resolver(bytes32 node) external view returns (address);
+    function addr(bytes32 node) external view returns (address);
+    function name(bytes32 node) external view returns (string);
+}
+
+/// ENS namehash of `name`, as defined in EIP-137
+///
+/// The empty name hashes to the zero node.
+pub fn namehash(name: &str) -> B256 {
+    let name = name.to_lowercase();
+    let mut node = B256::ZERO;
+    if name.is_empty() {
+        return node;
+    }
+    for label in name.rsplit('.') {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(node.as_slice());
+        buf[32..].copy_from_slice(keccak256(label.as_bytes()).as_slice());
+        node = keccak256(buf);
+    }
+    node
+}
+
+/// Node holding the reverse record of `address`
+fn reverse_node(address: Address) -> B256 {
+    namehash(&format!("{}.addr.reverse", hex::encode(address)))
+}
+
+/// Cached forward and reverse ENS lookups against one provider
+///
+/// The provider must be connected to the chain the registry lives on,
+/// normally Ethereum mainnet.
+#[derive(Debug)]
+pub struct EnsResolver<P> {
+    provider: P,
+    registry: Address,
+    names: Mutex<HashMap<Address, Option<String>>>,
+    addresses: Mutex<HashMap<String, Option<Address>>>,
+}
+
+impl<P: Provider> EnsResolver<P> {
+    /// A resolver using the canonical [`ENS_REGISTRY`]
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            registry: ENS_REGISTRY,
+            names: Mutex::new(HashMap::new()),
+            addresses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Use a different registry, e.g. on a fork or test deployment
+    pub fn with_registry(mut self, registry: Address) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Address `name` resolves to, or `None` if it has no resolver or address
+    ///
+    /// # Errors
+    ///
+    /// Fails if an `eth_call` fails. Failures are not cached.
+    pub async fn resolve_name(&self, name: &str) -> Result<Option<Address>, RpcError> {
+        let name = name.to_lowercase();
+        if let Some(cached) = self.addresses.lock().unwrap().get(&name) {
+            return Ok(*cached);
+        }
+
+        let node = namehash(&name);
+        let resolved = match self.resolver(node).await? {
+            Some(resolver) => {
+                let data = self
+                    .call(resolver, addrCall { node }.abi_encode(), &name)
+                    .await?;
+                addrCall::abi_decode_returns(&data)
+                    .ok()
+                    .filter(|address| !address.is_zero())
+            }
+            None => None,
+        };
+        debug!(%name, ?resolved, "Resolved ENS name");
+
+        self.addresses.lock().unwrap().insert(name, resolved);
+        Ok(resolved)
+    }
+
+    /// Primary ENS name of `address`, or `None` if it has none
+    ///
+    /// Only names that resolve back to `address` are returned.
+    ///
+    /// # Errors
+    ///
+    /// Fails if an `eth_call` fails. Failures are not cached.
+    pub async fn lookup_address(&self, address: Address) -> Result<Option<String>, RpcError> {
+        if let Some(cached) = self.names.lock().unwrap().get(&address) {
+            return Ok(cached.clone());
+        }
+
+        let node = reverse_node(address);
+        let claimed = match self.resolver(node).await? {
+            Some(resolver) => {
+                let data = self
+                    .call(resolver, nameCall { node }.abi_encode(), &address)
+                    .await?;
+                nameCall::abi_decode_returns(&data)
+                    .ok()
+                    .filter(|name| !name.is_empty())
+            }
+            None => None,
+        };
+
+        let name = match claimed {
+            Some(name) if self.resolve_name(&name).await? == Some(address) => Some(name),
+            Some(name) => {
+                debug!(%address, %name, "Ignoring ENS reverse record that doesn't resolve back");
+                None
+            }
+            None => None,
+        };
+
+        self.names.lock().unwrap().insert(address, name.clone());
+        Ok(name)
+    }
+
+    /// Label every address in `addresses` that `book` doesn't already know
+    /// with its ENS name, tagged [`ENS_TAG`]
+    ///
+    /// Existing labels always win. Returns the number of labels added.
+    ///
+    /// # Errors
+    ///
+    /// Fails on the first `eth_call` that fails; labels added before it are
+    /// kept.
+    pub async fn label_addresses(
+        &self,
+        book: &mut AddressBook,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<usize, RpcError> {
+        let mut added = 0;
+        for address in addresses {
+            if book.get(&address).is_some() {
+                continue;
+            }
+            if let Some(name) = self.lookup_address(address).await? {
+                book.insert(address, AddressLabel::new(name).with_tag(ENS_TAG));
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Resolver contract registered for `node`
+    async fn resolver(&self, node: B256) -> Result<Option<Address>, RpcError> {
+        let data = self
+            .call(self.registry, resolverCall { node }.abi_encode(), &node)
+            .await?;
+        Ok(resolverCall::abi_decode_returns(&data)
+            .ok()
+            .filter(|resolver| !resolver.is_zero()))
+    }
+
+    async fn call(
+        &self,
+        to: Address,
+        input: Vec<u8>,
+        subject: &(dyn std::fmt::Display + Sync),
+    ) -> Result<Bytes, RpcError> {
+        let request = TransactionRequest::default()
+            .to(to)
+            .input(TransactionInput::new(input.into()));
+        self.provider
+            .call(request)
+            .await
+            .map_err(|e| RpcError::request_failed(format!("ENS eth_call for {subject}"), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_network::Ethereum;
+    use alloy_primitives::b256;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_sol_types::SolValue;
+    use alloy_transport::mock::Asserter;
+
+    #[test]
+    fn namehash_matches_eip_137_vectors() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+        assert_eq!(namehash("Foo.ETH"), namehash("foo.eth"));
+    }
+
+    #[tokio::test]
+    async fn reverse_lookups_are_verified_and_cached() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let resolver = EnsResolver::new(provider);
+
+        let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let public_resolver = address!("231b0Ee14048e9dCcD1d247744d114a4EB5E8E63");
+        // Reverse: resolver, name
+        asserter.push_success(&Bytes::from(public_resolver.abi_encode()));
+        asserter.push_success(&Bytes::from("vitalik.eth".to_string().abi_encode()));
+        // Forward: resolver, addr
+        asserter.push_success(&Bytes::from(public_resolver.abi_encode()));
+        asserter.push_success(&Bytes::from(owner.abi_encode()));
+
+        let mut book = AddressBook::new().with_label(Address::ZERO, "Null");
+        let added = resolver
+            .label_addresses(&mut book, [owner, Address::ZERO])
+            .await
+            .unwrap();
+        assert_eq!(added, 1);
+        assert_eq!(book.label(&owner), Some("vitalik.eth"));
+        assert!(book.get(&owner).unwrap().has_tag(ENS_TAG));
+        assert_eq!(book.label(&Address::ZERO), Some("Null"));
+
+        // Served from the cache: the mock queue is empty
+        assert_eq!(
+            resolver.lookup_address(owner).await.unwrap().as_deref(),
+            Some("vitalik.eth")
+        );
+        assert_eq!(
+            resolver.resolve_name("Vitalik.eth").await.unwrap(),
+            Some(owner)
+        );
+    }
+
+    #[tokio::test]
+    async fn reverse_records_that_do_not_resolve_back_are_ignored() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let resolver = EnsResolver::new(provider);
+
+        let impostor = address!("1111111111111111111111111111111111111111");
+        let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let public_resolver = address!("231b0Ee14048e9dCcD1d247744d114a4EB5E8E63");
+
+        asserter.push_success(&Bytes::from(public_resolver.abi_encode()));
+        asserter.push_success(&Bytes::from("vitalik.eth".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(public_resolver.abi_encode()));
+        asserter.push_success(&Bytes::from(owner.abi_encode()));
+
+        assert_eq!(resolver.lookup_address(impostor).await.unwrap(), None);
+    }
+}
@@ -7,6 +7,9 @@
 //! This module provides error types for operations in the `blocks` module,
 //! particularly for calculating daily block windows.
 
+use std::time::Duration;
+
+use alloy_chains::NamedChain;
 use alloy_primitives::BlockNumber;
 use chrono::NaiveDate;
 
@@ -125,6 +128,59 @@ pub enum BlockWindowError {
     /// block window calculations (e.g., fetching block numbers, block details).
     #[error("RPC error: {0}")]
     Rpc(#[from] RpcError),
+
+    /// The calculation ran past its configured operation deadline.
+    ///
+    /// `start_block` is set when the start of the window had already been
+    /// found, so a retry only needs to locate the end.
+    #[error("Operation deadline of {deadline:?} exceeded (start block found: {start_block:?})")]
+    DeadlineExceeded {
+        /// The configured time budget
+        deadline: Duration,
+        /// First block of the window, if it was found before the deadline
+        start_block: Option<BlockNumber>,
+    },
+
+    /// The provider has pruned a block the calculation needs.
+    ///
+    /// Non-archive nodes only keep recent history. Point the calculator at an
+    /// archive node to compute windows before `earliest_available`.
+    #[error("Block {needed_block} is not available from the provider (earliest available: {earliest_available:?})")]
+    HistoryUnavailable {
+        /// The block that couldn't be fetched
+        needed_block: BlockNumber,
+        /// The oldest block the provider serves, if known
+        earliest_available: Option<BlockNumber>,
+    },
+
+    /// There is nothing to estimate a window offline from.
+    ///
+    /// The chain needs a checkpoint or genesis entry, and a block time, for
+    /// `BlockCheckpoints::estimate_daily_window`.
+    #[error("Cannot estimate block windows for {chain} without checkpoints and a block time")]
+    EstimateUnavailable {
+        /// The chain without estimation data
+        chain: NamedChain,
+    },
+
+    /// A checkpoint file is malformed or contradicts itself.
+    #[error("Invalid block checkpoints for {chain}: {reason}")]
+    InvalidCheckpoints {
+        /// The chain the checkpoints were for
+        chain: NamedChain,
+        /// What is wrong with them
+        reason: String,
+    },
+
+    /// A checkpoint file couldn't be read.
+    #[error("Failed to read block checkpoints from {path}: {source}")]
+    CheckpointFileUnreadable {
+        /// Path of the checkpoint file
+        path: String,
+        /// The underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 impl BlockWindowError {
@@ -163,4 +219,44 @@ impl BlockWindowError {
     pub fn serialization_error(source: serde_json::Error) -> Self {
         BlockWindowError::SerializationError { source }
     }
+
+    /// Create a `HistoryUnavailable` error for a pruned block.
+    pub fn history_unavailable(
+        needed_block: BlockNumber,
+        earliest_available: Option<BlockNumber>,
+    ) -> Self {
+        BlockWindowError::HistoryUnavailable {
+            needed_block,
+            earliest_available,
+        }
+    }
+
+    /// Create an `EstimateUnavailable` error for `chain`.
+    pub fn estimate_unavailable(chain: NamedChain) -> Self {
+        BlockWindowError::EstimateUnavailable { chain }
+    }
+
+    /// Create an `InvalidCheckpoints` error for `chain`'s checkpoints.
+    pub fn invalid_checkpoints(chain: NamedChain, reason: impl Into<String>) -> Self {
+        BlockWindowError::InvalidCheckpoints {
+            chain,
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a `CheckpointFileUnreadable` error from a path and I/O error.
+    pub fn checkpoint_file_unreadable(path: impl Into<String>, source: std::io::Error) -> Self {
+        BlockWindowError::CheckpointFileUnreadable {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Create a `DeadlineExceeded` error with no partial progress.
+    pub fn deadline_exceeded(deadline: Duration) -> Self {
+        BlockWindowError::DeadlineExceeded {
+            deadline,
+            start_block: None,
+        }
+    }
 }
@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Error types for currency conversion.
+//!
+//! This module provides error types for [`FxRate`](crate::FxRate) sources
+//! and the conversions built on them.
+
+use chrono::NaiveDate;
+
+use crate::Currency;
+
+/// Errors that can occur while looking up an exchange rate or converting a
+/// value.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{Currency, FxError};
+///
+/// match usd_value.convert(Currency::EUR, date, &rates).await {
+///     Ok(eur) => println!("{eur}"),
+///     Err(FxError::RateUnavailable { currency, date }) => {
+///         eprintln!("No {currency} rate for {date}");
+///     }
+///     Err(e) => eprintln!("Conversion failed: {e}"),
+/// }
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum FxError {
+    /// A currency code wasn't three ASCII letters.
+    #[error("Invalid currency code: {code:?}")]
+    InvalidCurrency {
+        /// The rejected code
+        code: String,
+    },
+
+    /// The source has no rate for the currency on the date.
+    #[error("No USD/{currency} rate for {date}")]
+    RateUnavailable {
+        /// Currency that was requested
+        currency: Currency,
+        /// Day that was requested
+        date: NaiveDate,
+    },
+
+    /// The source returned a rate that isn't a positive, finite number.
+    #[error("Invalid USD/{currency} rate: {rate}")]
+    InvalidRate {
+        /// Currency that was requested
+        currency: Currency,
+        /// The rejected rate
+        rate: f64,
+    },
+
+    /// An exchange rate request failed or was answered with an error status.
+    #[cfg(feature = "fx-http")]
+    #[error("Exchange rate request failed: {0}")]
+    Http(#[from] reqwest::Error),
+}
@@ -8,6 +8,8 @@
 //! particularly for calculating gas costs for token transfers and approvals.
 
 use super::RpcError;
+use crate::provider::NetworkType;
+use crate::types::warnings::ScanWarning;
 
 /// Errors that can occur during gas cost calculations.
 ///
@@ -81,6 +83,35 @@ pub enum GasCalculationError {
     /// gas calculations (e.g., fetching logs, transactions, receipts).
     #[error("RPC error: {0}")]
     Rpc(#[from] RpcError),
+
+    /// The provider is connected to a chain of a different network type than
+    /// the calculator was built for.
+    ///
+    /// For example, an Optimism-typed calculator pointed at an Ethereum RPC
+    /// would silently report no L1 data fees.
+    #[error(
+        "Provider is on chain {chain_id} ({} network) but the calculator expects {}",
+        actual.name(),
+        expected.name()
+    )]
+    NetworkMismatch {
+        /// Chain ID reported by the provider
+        chain_id: u64,
+        /// Network type the calculator was built for
+        expected: NetworkType,
+        /// Network type of the provider's chain
+        actual: NetworkType,
+    },
+
+    /// Data had to be skipped and the configured
+    /// [`ScanPolicy`](crate::ScanPolicy) is `Strict`.
+    ///
+    /// `warning` identifies the log, transaction or block range.
+    #[error("Strict scan policy rejected skipped data: {warning}")]
+    SkippedData {
+        /// What would have been skipped
+        warning: Box<ScanWarning>,
+    },
 }
 
 impl GasCalculationError {
@@ -115,6 +146,15 @@ impl GasCalculationError {
         }
     }
 
+    /// Create a `NetworkMismatch` error.
+    pub fn network_mismatch(chain_id: u64, expected: NetworkType, actual: NetworkType) -> Self {
+        GasCalculationError::NetworkMismatch {
+            chain_id,
+            expected,
+            actual,
+        }
+    }
+
     /// Helper to create a `MissingData` error for a missing transaction hash.
     pub fn missing_transaction_hash() -> Self {
         Self::missing_data("transaction_hash")
@@ -134,4 +174,11 @@ impl GasCalculationError {
     pub fn missing_receipt(tx_hash: &str) -> Self {
         Self::missing_data(format!("receipt for transaction {}", tx_hash))
     }
+
+    /// Create a `SkippedData` error for `warning`.
+    pub fn skipped_data(warning: ScanWarning) -> Self {
+        Self::SkippedData {
+            warning: Box::new(warning),
+        }
+    }
 }
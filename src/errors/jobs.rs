@@ -0,0 +1,68 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Error types for backfill jobs.
+//!
+//! This module provides error types for the `retrieval::jobs` module, which
+//! runs a manifest of backfill tasks and records their progress on disk.
+//! Failures of the tasks themselves are reported in the job's report, not as
+//! errors.
+
+use std::path::PathBuf;
+
+/// Errors that can occur while reading or writing a job's state file.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{JobError, JobRunner};
+///
+/// match runner.run(&manifest, run_task).await {
+///     Ok(report) => println!("{} tasks completed", report.completed),
+///     Err(JobError::Io { path, source }) => {
+///         eprintln!("Could not access {}: {source}", path.display());
+///     }
+///     Err(e) => eprintln!("Job state error: {e}"),
+/// }
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    /// The state file could not be read or written.
+    #[error("Failed to access job state {path}: {source}")]
+    Io {
+        /// Path of the state file
+        path: PathBuf,
+        /// Underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The state file does not hold valid job state.
+    #[error("Invalid job state {path}: {source}")]
+    Json {
+        /// Path of the state file
+        path: PathBuf,
+        /// Underlying parse error
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl JobError {
+    /// Create an `Io` error for the state file at `path`.
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Create a `Json` error for the state file at `path`.
+    pub fn json(path: impl Into<PathBuf>, source: serde_json::Error) -> Self {
+        Self::Json {
+            path: path.into(),
+            source,
+        }
+    }
+}
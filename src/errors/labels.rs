@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Error types for address labelling.
+//!
+//! This module provides error types for loading an
+//! [`AddressBook`](crate::AddressBook) from disk or from a string.
+
+use std::path::PathBuf;
+
+/// Errors that can occur while loading an address book.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{AddressBook, AddressBookError};
+///
+/// match AddressBook::load("labels.json") {
+///     Ok(book) => println!("Loaded {} labels", book.len()),
+///     Err(AddressBookError::Io { path, source }) => {
+///         eprintln!("Could not read {}: {source}", path.display());
+///     }
+///     Err(e) => eprintln!("Invalid address book: {e}"),
+/// }
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum AddressBookError {
+    /// The address book file could not be read.
+    #[error("Failed to read address book {path}: {source}")]
+    Io {
+        /// Path that was being read
+        path: PathBuf,
+        /// Underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The file extension does not identify a supported format.
+    #[error("Unsupported address book format for {path} (expected .json or .toml)")]
+    UnsupportedFormat {
+        /// Path with the unrecognized extension
+        path: PathBuf,
+    },
+
+    /// The JSON document could not be parsed.
+    #[error("Invalid JSON address book: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The TOML document could not be parsed.
+    #[cfg(feature = "toml")]
+    #[error("Invalid TOML address book: {0}")]
+    Toml(#[from] toml::de::Error),
+}
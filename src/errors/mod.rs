@@ -20,6 +20,9 @@
 //! - [`PriceCalculationError`] - Errors from price calculations (wraps [`crate::price::PriceSourceError`])
 //! - [`EventProcessingError`] - Errors from event scanning and processing
 //! - [`RetrievalError`] - Errors from combined data retrieval operations
+//! - [`SinkError`] - Errors from persisting results to external storage
+//! - [`AddressBookError`] - Errors from loading address labels
+//! - [`FxError`] - Errors from exchange rate lookups and currency conversion
 //!
 //! Additionally, [`RpcError`] provides shared error variants for blockchain RPC operations.
 //!
@@ -68,17 +71,25 @@
 
 mod blocks;
 mod events;
+mod fx;
 mod gas;
+mod jobs;
+mod labels;
 mod price;
 mod retrieval;
 mod rpc;
+mod sink;
 
 pub use blocks::BlockWindowError;
 pub use events::EventProcessingError;
+pub use fx::FxError;
 pub use gas::GasCalculationError;
+pub use jobs::JobError;
+pub use labels::AddressBookError;
 pub use price::PriceCalculationError;
 pub use retrieval::RetrievalError;
 pub use rpc::RpcError;
+pub use sink::SinkError;
 
 /// Unified error type for all semioscan operations.
 ///
@@ -130,4 +141,80 @@ pub enum SemioscanError {
     /// Error from combined data retrieval operations.
     #[error("Data retrieval error: {0}")]
     Retrieval(#[from] RetrievalError),
+
+    /// Error from writing results to a sink.
+    #[error("Sink error: {0}")]
+    Sink(#[from] SinkError),
+
+    /// Error from reading or writing a backfill job's state.
+    #[error("Job error: {0}")]
+    Job(#[from] JobError),
+
+    /// RPC error outside any one calculation, such as a finality lookup.
+    #[error("RPC error: {0}")]
+    Rpc(#[from] RpcError),
+
+    /// Error from loading an address book.
+    #[error("Address book error: {0}")]
+    AddressBook(#[from] AddressBookError),
+
+    /// Error from an exchange rate lookup or currency conversion.
+    #[error("Currency conversion error: {0}")]
+    Fx(#[from] FxError),
+
+    /// No RPC endpoint is registered for the chain.
+    ///
+    /// Returned by [`SemioscanClient`](crate::SemioscanClient) methods called
+    /// with a chain that wasn't added to its builder.
+    #[error("No RPC endpoint configured for chain {chain}")]
+    ChainNotConfigured {
+        /// The chain that was requested
+        chain: alloy_chains::NamedChain,
+    },
+
+    /// A token's metadata couldn't be read while prefetching it.
+    ///
+    /// Returned by
+    /// [`SemioscanClientBuilder::build_and_prefetch`](crate::SemioscanClientBuilder::build_and_prefetch)
+    /// when a configured token doesn't answer `decimals()`.
+    #[error("Failed to fetch metadata for token {token} on {chain}")]
+    TokenMetadataUnavailable {
+        /// Chain the token was configured for
+        chain: alloy_chains::NamedChain,
+        /// Address of the token
+        token: alloy_primitives::Address,
+        /// The underlying error from the contract call
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// A token reported different decimals than its chain's canonical token
+    /// list expects, as Binance-Peg stablecoins on BSC (18, not 6) would if
+    /// the list were wrong.
+    #[error("Token {token} on {chain} reports {actual} decimals, expected {expected}")]
+    UnexpectedTokenDecimals {
+        /// Chain the token was configured for
+        chain: alloy_chains::NamedChain,
+        /// Address of the token
+        token: alloy_primitives::Address,
+        /// Decimals listed in [`canonical_tokens`](crate::config::constants::canonical_tokens)
+        expected: u8,
+        /// Decimals reported by the contract
+        actual: u8,
+    },
+}
+
+impl SemioscanError {
+    /// Create a `TokenMetadataUnavailable` error for a token on `chain`.
+    pub fn token_metadata_unavailable(
+        chain: alloy_chains::NamedChain,
+        token: alloy_primitives::Address,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        SemioscanError::TokenMetadataUnavailable {
+            chain,
+            token,
+            source: Box::new(source),
+        }
+    }
 }
@@ -9,7 +9,9 @@
 
 use alloy_primitives::Address;
 
-use super::RpcError;
+use super::{BlockWindowError, RpcError};
+use crate::types::warnings::ScanWarning;
+use crate::{TransactionCount, UsdValue};
 
 /// Errors that can occur during price calculations.
 ///
@@ -78,6 +80,36 @@ pub enum PriceCalculationError {
     /// price calculations (e.g., fetching swap events, token metadata).
     #[error("RPC error: {0}")]
     Rpc(#[from] RpcError),
+
+    /// The block window for a date-based price calculation couldn't be
+    /// resolved.
+    #[error("Block window error: {0}")]
+    BlockWindow(#[from] BlockWindowError),
+
+    /// Too little trading backs the price to trust it.
+    ///
+    /// Returned instead of a result when the calculator's
+    /// [`LiquidityThresholds`](crate::LiquidityThresholds) reject results
+    /// below them.
+    #[error("Insufficient liquidity for {token}: {swaps} swaps totalling {volume}")]
+    InsufficientLiquidity {
+        /// Address of the token
+        token: Address,
+        /// Total USDC volume of the counted swaps
+        volume: UsdValue,
+        /// Number of counted swaps
+        swaps: TransactionCount,
+    },
+
+    /// Data had to be skipped and the configured
+    /// [`ScanPolicy`](crate::ScanPolicy) is `Strict`.
+    ///
+    /// `warning` identifies the log, transaction or block range.
+    #[error("Strict scan policy rejected skipped data: {warning}")]
+    SkippedData {
+        /// What would have been skipped
+        warning: Box<ScanWarning>,
+    },
 }
 
 impl PriceCalculationError {
@@ -119,4 +151,11 @@ impl PriceCalculationError {
             details: details.into(),
         }
     }
+
+    /// Create a `SkippedData` error for `warning`.
+    pub fn skipped_data(warning: ScanWarning) -> Self {
+        Self::SkippedData {
+            warning: Box::new(warning),
+        }
+    }
 }
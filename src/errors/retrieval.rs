@@ -8,7 +8,13 @@
 //! particularly for retrieving combined blockchain data (transactions, receipts,
 //! events, gas costs) for analysis.
 
-use super::RpcError;
+use std::time::Duration;
+
+use alloy_primitives::BlockNumber;
+
+use super::{BlockWindowError, EventProcessingError, RpcError};
+use crate::retrieval::CombinedDataResult;
+use crate::types::warnings::ScanWarning;
 
 /// Errors that can occur during data retrieval operations.
 ///
@@ -19,15 +25,21 @@ use super::RpcError;
 /// # Examples
 ///
 /// ```rust,ignore
-/// use semioscan::{CombinedCalculator, RetrievalError};
+/// use semioscan::{CombinedCalculator, CombinedQuery, RetrievalError};
 /// use alloy_chains::NamedChain;
 ///
 /// async fn example() -> Result<(), RetrievalError> {
 ///     let calculator = CombinedCalculator::new(provider);
 ///
-///     match calculator.calculate_combined_data_ethereum(
-///         NamedChain::Arbitrum, from_address, to_address, token_address, start_block, end_block
-///     ).await {
+///     let query = CombinedQuery::builder()
+///         .chain(NamedChain::Arbitrum)
+///         .from(from_address)
+///         .to(to_address)
+///         .token(token_address)
+///         .blocks(start_block..=end_block)
+///         .build()?;
+///
+///     match calculator.calculate(&query).await {
 ///         Ok(result) => println!("Retrieved data: {:?}", result),
 ///         Err(RetrievalError::MissingBlockchainData { field }) => {
 ///             eprintln!("Missing required field: {}", field);
@@ -80,9 +92,54 @@ pub enum RetrievalError {
     /// data retrieval (e.g., fetching transactions, receipts, logs).
     #[error("RPC error: {0}")]
     Rpc(#[from] RpcError),
+
+    /// The block window for a date-based retrieval couldn't be computed.
+    #[error("Block window error: {0}")]
+    BlockWindow(#[from] BlockWindowError),
+
+    /// A log scan used by the retrieval failed.
+    #[error("Event processing error: {0}")]
+    Events(#[from] EventProcessingError),
+
+    /// The calculation ran past its configured operation deadline.
+    ///
+    /// `partial` holds everything gathered for blocks before `next_block`, so
+    /// callers can keep it and resume from `next_block`.
+    #[error("Operation deadline of {deadline:?} exceeded before block {next_block}")]
+    DeadlineExceeded {
+        /// The configured time budget
+        deadline: Duration,
+        /// First block that was not fully processed
+        next_block: BlockNumber,
+        /// Result for the blocks processed before the deadline
+        partial: Box<CombinedDataResult>,
+    },
+
+    /// Data had to be skipped and the configured
+    /// [`ScanPolicy`](crate::ScanPolicy) is `Strict`.
+    ///
+    /// `warning` identifies the log, transaction or block range.
+    #[error("Strict scan policy rejected skipped data: {warning}")]
+    SkippedData {
+        /// What would have been skipped
+        warning: Box<ScanWarning>,
+    },
+
+    /// A [`CombinedQuery`](crate::CombinedQuery) was built without one of
+    /// its fields.
+    #[error("Combined query is missing its {field}")]
+    IncompleteQuery {
+        /// Name of the builder method that wasn't called
+        field: &'static str,
+    },
 }
 
 impl RetrievalError {
+    /// Create an `IncompleteQuery` error for a builder field that wasn't set.
+    pub fn incomplete_query(field: &'static str) -> Self {
+        Self::IncompleteQuery { field }
+    }
+
     /// Create a `MissingBlockchainData` error for a specific field.
     pub fn missing_blockchain_data(field: impl Into<String>) -> Self {
         RetrievalError::MissingBlockchainData {
@@ -104,6 +161,13 @@ impl RetrievalError {
         }
     }
 
+    /// Create a `SkippedData` error for `warning`.
+    pub fn skipped_data(warning: ScanWarning) -> Self {
+        Self::SkippedData {
+            warning: Box::new(warning),
+        }
+    }
+
     /// Helper to create a missing transaction hash error.
     pub fn missing_transaction_hash() -> Self {
         Self::missing_blockchain_data("transaction_hash")
@@ -217,7 +217,39 @@ pub enum RpcError {
     ProviderConnectionFailed(String),
 }
 
+/// Fragments of the errors nodes return for blocks or state they have pruned
+///
+/// "header not found" is left out: nodes also return it for blocks beyond
+/// the chain tip.
+const PRUNED_HISTORY_MESSAGES: &[&str] = &[
+    "missing trie node",
+    "pruned history unavailable",
+    "state is pruned",
+    "historical state",
+    "distance to target block exceeds",
+];
+
 impl RpcError {
+    /// Whether the provider failed because it no longer has the requested
+    /// block or its state
+    ///
+    /// Non-archive nodes answer such requests with errors like "missing trie
+    /// node" or "pruned history unavailable". A plain
+    /// [`RpcError::BlockNotFound`] or "header not found" error isn't counted,
+    /// since it also means a block beyond the chain tip.
+    pub fn is_history_unavailable(&self) -> bool {
+        let source = match self {
+            RpcError::GetLogsFailed { source, .. }
+            | RpcError::RequestFailed { source, .. }
+            | RpcError::GetBlockFailed { source, .. } => source,
+            _ => return false,
+        };
+        let message = source.to_string().to_ascii_lowercase();
+        PRUNED_HISTORY_MESSAGES
+            .iter()
+            .any(|fragment| message.contains(fragment))
+    }
+
     /// Helper to create a `GetLogsFailed` error from a transport error.
     ///
     /// # Examples
@@ -0,0 +1,68 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Error types for result sinks.
+//!
+//! This module provides error types for operations in the `sink` module,
+//! which persist semioscan results to external storage.
+
+use super::RetrievalError;
+
+/// Errors that can occur while writing results to a sink.
+///
+/// Backend-specific variants are only present when the corresponding
+/// feature is enabled.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::sink::postgres::insert_combined_result;
+/// use semioscan::SinkError;
+///
+/// match insert_combined_result(&pool, &result).await {
+///     Ok(rows) => println!("Upserted {rows} rows"),
+///     Err(SinkError::Conversion(e)) => eprintln!("Value could not be stored: {e}"),
+///     Err(e) => eprintln!("Sink failure: {e}"),
+/// }
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum SinkError {
+    /// A value could not be converted into the sink's storage representation.
+    #[error("Conversion failed: {0}")]
+    Conversion(#[from] RetrievalError),
+
+    /// Error returned by the Postgres driver.
+    #[cfg(feature = "postgres")]
+    #[error("Postgres error: {0}")]
+    Postgres(#[from] sqlx::Error),
+
+    /// A record could not be encoded as JSON.
+    #[error("JSON encoding failed: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A record could not be written out.
+    #[error("Write failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A webhook request failed or was answered with an error status.
+    #[cfg(feature = "webhook")]
+    #[error("Webhook request failed: {0}")]
+    Webhook(#[from] reqwest::Error),
+
+    /// Error returned by a sink implemented outside this crate.
+    #[error("Sink failed: {0}")]
+    Other(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// Error returned by the ClickHouse client.
+    #[cfg(feature = "clickhouse")]
+    #[error("ClickHouse error: {0}")]
+    ClickHouse(#[from] clickhouse::error::Error),
+}
+
+impl SinkError {
+    /// Create an `Other` error from any error type.
+    pub fn other(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Other(error.into())
+    }
+}
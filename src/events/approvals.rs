@@ -0,0 +1,358 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Outstanding ERC-20 allowance tracking from Approval events
+//!
+//! ERC-20 `Approval` events carry the *new* allowance rather than a delta, so
+//! replaying them in chain order yields the latest approved value for every
+//! `(owner, spender, token)` triple. [`AllowanceTracker`] holds that state and
+//! [`ApprovalAnalyzer`] builds it by scanning a block range.
+//!
+//! Allowances consumed by `transferFrom` do not always emit an `Approval`, so
+//! the replayed value is the last *approved* amount, which is an upper bound on
+//! what the spender can still move.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{ApprovalAnalyzer, SemioscanConfig};
+//! use alloy_chains::NamedChain;
+//!
+//! let analyzer = ApprovalAnalyzer::new(provider, SemioscanConfig::default());
+//! let tracker = analyzer
+//!     .replay_approvals(NamedChain::Mainnet, Some(owner), &[], start_block, end_block)
+//!     .await?;
+//!
+//! for (key, state) in tracker.unlimited() {
+//!     println!("{} has unlimited {} allowance for {}", key.owner, key.token, key.spender);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockNumber, TxHash, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{Filter, Log};
+use alloy_sol_types::SolEvent;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::SemioscanConfig;
+use crate::errors::EventProcessingError;
+use crate::events::definitions::Approval;
+use crate::events::scanner::EventScanner;
+
+/// Identifies a single allowance: `owner` lets `spender` move `token`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AllowanceKey {
+    /// Token holder granting the allowance
+    pub owner: Address,
+    /// Address allowed to spend the owner's tokens
+    pub spender: Address,
+    /// ERC-20 token contract
+    pub token: Address,
+}
+
+/// Latest approved value for an [`AllowanceKey`] and where it was set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowanceState {
+    /// Most recently approved amount (raw, not normalized for decimals)
+    pub value: U256,
+    /// Block of the Approval event that set this value
+    pub block_number: BlockNumber,
+    /// Position of that event within its block
+    pub log_index: u64,
+    /// Transaction that emitted the event, when reported by the provider
+    pub tx_hash: Option<TxHash>,
+}
+
+impl AllowanceState {
+    /// Whether this is an "infinite" approval (`U256::MAX`)
+    pub fn is_unlimited(&self) -> bool {
+        self.value == U256::MAX
+    }
+
+    /// Whether the allowance has been revoked (set to zero)
+    pub fn is_revoked(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    fn position(&self) -> (BlockNumber, u64) {
+        (self.block_number, self.log_index)
+    }
+}
+
+/// Allowance state reconstructed from replayed Approval events
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllowanceTracker {
+    allowances: HashMap<AllowanceKey, AllowanceState>,
+}
+
+impl AllowanceTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an approval, keeping it only if it is newer than the stored one
+    ///
+    /// Ordering is by `(block_number, log_index)`, so events may be applied in
+    /// any order.
+    pub fn apply(&mut self, key: AllowanceKey, state: AllowanceState) {
+        match self.allowances.get(&key) {
+            Some(existing) if existing.position() > state.position() => {}
+            _ => {
+                self.allowances.insert(key, state);
+            }
+        }
+    }
+
+    /// Decode an Approval log and record it
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventProcessingError::DecodeFailed`] if the log is not a valid
+    /// Approval event or lacks a block number.
+    pub fn apply_log(&mut self, log: &Log) -> Result<(), EventProcessingError> {
+        let event = Approval::decode_log(&log.inner)
+            .map_err(|e| EventProcessingError::decode_failed(format!("Approval: {e}")))?;
+        let block_number = log.block_number.ok_or_else(|| {
+            EventProcessingError::decode_failed("Approval log is missing its block number")
+        })?;
+
+        self.apply(
+            AllowanceKey {
+                owner: event.owner,
+                spender: event.spender,
+                token: log.address(),
+            },
+            AllowanceState {
+                value: event.value,
+                block_number,
+                log_index: log.log_index.unwrap_or_default(),
+                tx_hash: log.transaction_hash,
+            },
+        );
+        Ok(())
+    }
+
+    /// Latest state for an allowance, if any Approval was seen for it
+    pub fn get(&self, owner: Address, spender: Address, token: Address) -> Option<&AllowanceState> {
+        self.allowances.get(&AllowanceKey {
+            owner,
+            spender,
+            token,
+        })
+    }
+
+    /// All tracked allowances, including revoked ones
+    pub fn iter(&self) -> impl Iterator<Item = (&AllowanceKey, &AllowanceState)> {
+        self.allowances.iter()
+    }
+
+    /// Allowances with a non-zero latest value
+    pub fn outstanding(&self) -> impl Iterator<Item = (&AllowanceKey, &AllowanceState)> {
+        self.iter().filter(|(_, state)| !state.is_revoked())
+    }
+
+    /// Allowances whose latest value is `U256::MAX`
+    pub fn unlimited(&self) -> impl Iterator<Item = (&AllowanceKey, &AllowanceState)> {
+        self.iter().filter(|(_, state)| state.is_unlimited())
+    }
+
+    /// Allowances whose latest value is zero
+    ///
+    /// This includes zero-value approvals that never followed a grant.
+    pub fn revoked(&self) -> impl Iterator<Item = (&AllowanceKey, &AllowanceState)> {
+        self.iter().filter(|(_, state)| state.is_revoked())
+    }
+
+    /// All allowances granted by `owner`
+    pub fn for_owner(
+        &self,
+        owner: Address,
+    ) -> impl Iterator<Item = (&AllowanceKey, &AllowanceState)> {
+        self.iter().filter(move |(key, _)| key.owner == owner)
+    }
+
+    /// Number of tracked allowances
+    pub fn len(&self) -> usize {
+        self.allowances.len()
+    }
+
+    /// Check if no allowances are tracked
+    pub fn is_empty(&self) -> bool {
+        self.allowances.is_empty()
+    }
+}
+
+/// Replays Approval events over a block range into an [`AllowanceTracker`]
+pub struct ApprovalAnalyzer<P> {
+    provider: P,
+    config: SemioscanConfig,
+}
+
+impl<P: Provider> ApprovalAnalyzer<P> {
+    /// Creates a new `ApprovalAnalyzer` with the given provider and configuration
+    pub fn new(provider: P, config: SemioscanConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Replay Approval events in `[from_block, to_block]`
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - Chain to scan (determines chunking and rate limits)
+    /// * `owner` - Only replay approvals granted by this owner, if set
+    /// * `tokens` - Only replay approvals on these token contracts; empty means all tokens
+    /// * `from_block` - Starting block number (inclusive)
+    /// * `to_block` - Ending block number (inclusive)
+    ///
+    /// Logs that fail to decode are logged and skipped.
+    pub async fn replay_approvals(
+        &self,
+        chain: NamedChain,
+        owner: Option<Address>,
+        tokens: &[Address],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<AllowanceTracker, EventProcessingError> {
+        let mut tracker = AllowanceTracker::new();
+        self.replay_into(&mut tracker, chain, owner, tokens, from_block, to_block)
+            .await?;
+        Ok(tracker)
+    }
+
+    /// Replay Approval events on top of an existing tracker
+    ///
+    /// Useful for incrementally extending a tracker built from an earlier range.
+    pub async fn replay_into(
+        &self,
+        tracker: &mut AllowanceTracker,
+        chain: NamedChain,
+        owner: Option<Address>,
+        tokens: &[Address],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<(), EventProcessingError> {
+        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let filter = approval_filter(owner, tokens);
+        let logs = scanner.scan(chain, filter, from_block, to_block).await?;
+
+        for log in &logs {
+            if let Err(e) = tracker.apply_log(log) {
+                warn!(error = %e, tx_hash = ?log.transaction_hash, "Skipping Approval log");
+            }
+        }
+
+        info!(
+            chain = %chain,
+            logs = logs.len(),
+            allowances = tracker.len(),
+            unlimited = tracker.unlimited().count(),
+            "Finished replaying approvals"
+        );
+
+        Ok(())
+    }
+}
+
+fn approval_filter(owner: Option<Address>, tokens: &[Address]) -> Filter {
+    let mut filter = Filter::new().event_signature(Approval::SIGNATURE_HASH);
+    if !tokens.is_empty() {
+        filter = filter.address(tokens.to_vec());
+    }
+    if let Some(owner) = owner {
+        filter = filter.topic1(owner.into_word());
+    }
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const OWNER: Address = address!("1111111111111111111111111111111111111111");
+    const SPENDER: Address = address!("2222222222222222222222222222222222222222");
+    const TOKEN: Address = address!("3333333333333333333333333333333333333333");
+
+    fn approval_log(value: U256, block_number: BlockNumber, log_index: u64) -> Log {
+        let event = Approval {
+            owner: OWNER,
+            spender: SPENDER,
+            value,
+        };
+        Log {
+            inner: alloy_primitives::Log {
+                address: TOKEN,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block_number),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn latest_approval_wins_regardless_of_apply_order() {
+        let mut tracker = AllowanceTracker::new();
+        tracker
+            .apply_log(&approval_log(U256::from(50u64), 20, 0))
+            .unwrap();
+        tracker
+            .apply_log(&approval_log(U256::from(10u64), 10, 5))
+            .unwrap();
+
+        let state = tracker.get(OWNER, SPENDER, TOKEN).unwrap();
+        assert_eq!(state.value, U256::from(50u64));
+        assert_eq!(state.block_number, 20);
+    }
+
+    #[test]
+    fn log_index_orders_approvals_within_a_block() {
+        let mut tracker = AllowanceTracker::new();
+        tracker.apply_log(&approval_log(U256::MAX, 10, 2)).unwrap();
+        tracker.apply_log(&approval_log(U256::ZERO, 10, 1)).unwrap();
+
+        assert_eq!(tracker.unlimited().count(), 1);
+        assert_eq!(tracker.revoked().count(), 0);
+    }
+
+    #[test]
+    fn unlimited_and_revoked_are_flagged() {
+        let mut tracker = AllowanceTracker::new();
+        tracker.apply_log(&approval_log(U256::MAX, 1, 0)).unwrap();
+        assert_eq!(tracker.unlimited().count(), 1);
+        assert_eq!(tracker.outstanding().count(), 1);
+
+        tracker.apply_log(&approval_log(U256::ZERO, 2, 0)).unwrap();
+        assert_eq!(tracker.unlimited().count(), 0);
+        assert_eq!(tracker.outstanding().count(), 0);
+        assert_eq!(tracker.revoked().count(), 1);
+        assert_eq!(tracker.for_owner(OWNER).count(), 1);
+    }
+
+    #[test]
+    fn logs_without_block_numbers_are_rejected() {
+        let mut log = approval_log(U256::from(1u64), 1, 0);
+        log.block_number = None;
+
+        let mut tracker = AllowanceTracker::new();
+        assert!(tracker.apply_log(&log).is_err());
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn approval_filter_scopes_owner_and_tokens() {
+        let filter = approval_filter(Some(OWNER), &[TOKEN]);
+        assert!(filter.address.matches(&TOKEN));
+        assert!(filter.topics[1].matches(&OWNER.into_word()));
+
+        let unscoped = approval_filter(None, &[]);
+        assert!(unscoped.address.is_empty());
+        assert!(unscoped.topics[1].is_empty());
+    }
+}
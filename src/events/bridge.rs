@@ -0,0 +1,888 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! L1 ↔ L2 bridge flow reconciliation
+//!
+//! Bridged transfers produce one event on the chain where they are initiated
+//! and another on the chain where they are finalized. This module decodes both
+//! legs into [`BridgeLeg`]s keyed by a cross-chain message identifier, and
+//! [`BridgeFlowCalculator`] pairs them into [`BridgeFlow`]s.
+//!
+//! # Supported protocols
+//!
+//! **OP Stack** ([`BridgeProtocol::OpStack`]): standard bridge events
+//! (`ERC20DepositInitiated`, `ETHDepositInitiated`, `DepositFinalized`,
+//! `WithdrawalInitiated`, `ERC20WithdrawalFinalized`, `ETHWithdrawalFinalized`)
+//! are keyed by the cross-domain message hash. On the initiating side the hash
+//! is recomputed from the messenger's `SentMessage`/`SentMessageExtension1`
+//! events in the same transaction; on the finalizing side it is read from
+//! `RelayedMessage`. Only version 1 (Bedrock) messages are hashed.
+//!
+//! **Arbitrum** ([`BridgeProtocol::Arbitrum`]): withdrawals are keyed by the
+//! outbox position, pairing `L2ToL1Tx` (ArbSys) with `OutBoxTransactionExecuted`
+//! (Outbox). Retryable deposits are keyed by the L1 inbox message number from
+//! `InboxMessageDelivered`. The L2 side of a retryable (`TicketCreated`) does
+//! not carry that number in its logs, so it must be built with
+//! [`arbitrum_retryable_leg`] using the `requestId` of the L2 submission
+//! transaction.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{BridgeFlowCalculator, BridgeLegScanner, BridgeProtocol, SemioscanConfig};
+//! use alloy_chains::NamedChain;
+//!
+//! let l1_legs = BridgeLegScanner::new(l1_provider, SemioscanConfig::default())
+//!     .scan(NamedChain::Mainnet, BridgeProtocol::OpStack, &l1_contracts, l1_start, l1_end)
+//!     .await?;
+//! let l2_legs = BridgeLegScanner::new(l2_provider, SemioscanConfig::default())
+//!     .scan(NamedChain::Base, BridgeProtocol::OpStack, &l2_contracts, l2_start, l2_end)
+//!     .await?;
+//!
+//! let mut calculator = BridgeFlowCalculator::new();
+//! calculator.add_legs(l1_legs);
+//! calculator.add_legs(l2_legs);
+//!
+//! let flows = calculator.flows();
+//! println!("{} completed, {} pending", flows.completed.len(), flows.pending.len());
+//! ```
+
+use std::collections::BTreeMap;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{keccak256, Address, BlockNumber, TxHash, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{Filter, Log};
+use alloy_sol_types::{sol, SolCall, SolEvent};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::SemioscanConfig;
+use crate::errors::EventProcessingError;
+use crate::events::scanner::EventScanner;
+
+sol! {
+    /// OP Stack L1StandardBridge: ERC-20 deposit initiated on L1
+    #[derive(Debug)]
+    event ERC20DepositInitiated(
+        address indexed l1Token,
+        address indexed l2Token,
+        address indexed from,
+        address to,
+        uint256 amount,
+        bytes extraData
+    );
+
+    /// OP Stack L1StandardBridge: ETH deposit initiated on L1
+    #[derive(Debug)]
+    event ETHDepositInitiated(address indexed from, address indexed to, uint256 amount, bytes extraData);
+
+    /// OP Stack L2StandardBridge: deposit (ETH or ERC-20) finalized on L2
+    #[derive(Debug)]
+    event DepositFinalized(
+        address indexed l1Token,
+        address indexed l2Token,
+        address indexed from,
+        address to,
+        uint256 amount,
+        bytes extraData
+    );
+
+    /// OP Stack L2StandardBridge: withdrawal (ETH or ERC-20) initiated on L2
+    #[derive(Debug)]
+    event WithdrawalInitiated(
+        address indexed l1Token,
+        address indexed l2Token,
+        address indexed from,
+        address to,
+        uint256 amount,
+        bytes extraData
+    );
+
+    /// OP Stack L1StandardBridge: ERC-20 withdrawal finalized on L1
+    #[derive(Debug)]
+    event ERC20WithdrawalFinalized(
+        address indexed l1Token,
+        address indexed l2Token,
+        address indexed from,
+        address to,
+        uint256 amount,
+        bytes extraData
+    );
+
+    /// OP Stack L1StandardBridge: ETH withdrawal finalized on L1
+    #[derive(Debug)]
+    event ETHWithdrawalFinalized(address indexed from, address indexed to, uint256 amount, bytes extraData);
+
+    /// OP Stack CrossDomainMessenger: message sent to the other chain
+    #[derive(Debug)]
+    event SentMessage(address indexed target, address sender, bytes message, uint256 messageNonce, uint256 gasLimit);
+
+    /// OP Stack CrossDomainMessenger: value attached to the preceding `SentMessage`
+    #[derive(Debug)]
+    event SentMessageExtension1(address indexed sender, uint256 value);
+
+    /// OP Stack CrossDomainMessenger: message from the other chain relayed
+    #[derive(Debug)]
+    event RelayedMessage(bytes32 indexed msgHash);
+
+    /// OP Stack CrossDomainMessenger entry point, hashed to identify messages
+    function relayMessage(
+        uint256 _nonce,
+        address _sender,
+        address _target,
+        uint256 _value,
+        uint256 _minGasLimit,
+        bytes _message
+    );
+
+    /// Arbitrum Inbox: message (including retryable tickets) delivered to L2
+    #[derive(Debug)]
+    event InboxMessageDelivered(uint256 indexed messageNum, bytes data);
+
+    /// Arbitrum ArbRetryableTx precompile: retryable ticket created on L2
+    #[derive(Debug)]
+    event TicketCreated(bytes32 indexed ticketId);
+
+    /// Arbitrum ArbSys precompile: L2 → L1 message sent
+    #[derive(Debug)]
+    event L2ToL1Tx(
+        address caller,
+        address indexed destination,
+        uint256 indexed hash,
+        uint256 indexed position,
+        uint256 arbBlockNum,
+        uint256 ethBlockNum,
+        uint256 timestamp,
+        uint256 callvalue,
+        bytes data
+    );
+
+    /// Arbitrum Outbox: L2 → L1 message executed on L1
+    #[derive(Debug)]
+    event OutBoxTransactionExecuted(
+        address indexed to,
+        address indexed l2Sender,
+        uint256 indexed zero,
+        uint256 transactionIndex
+    );
+}
+
+/// Bridge protocol a leg was decoded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BridgeProtocol {
+    /// OP Stack standard bridge (Optimism, Base, and other OP chains)
+    OpStack,
+    /// Arbitrum Nitro (retryable tickets and the outbox)
+    Arbitrum,
+}
+
+/// Direction of a bridged transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BridgeDirection {
+    /// L1 → L2
+    Deposit,
+    /// L2 → L1
+    Withdrawal,
+}
+
+/// Which end of a bridged transfer a leg represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BridgeLegKind {
+    /// The transfer was started on this chain
+    Initiated,
+    /// The transfer was completed on this chain
+    Finalized,
+}
+
+/// One side of a bridged transfer, decoded from a single chain
+///
+/// Fields the protocol does not expose for this leg are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeLeg {
+    /// Protocol the leg was decoded from
+    pub protocol: BridgeProtocol,
+    /// L1 → L2 or L2 → L1
+    pub direction: BridgeDirection,
+    /// Whether this leg starts or completes the transfer
+    pub kind: BridgeLegKind,
+    /// Cross-chain identifier shared by both legs of the same transfer
+    pub message_id: B256,
+    /// Token on L1 (`Address::ZERO` for ETH on the OP Stack)
+    pub l1_token: Option<Address>,
+    /// Token on L2
+    pub l2_token: Option<Address>,
+    /// Sender of the bridged funds
+    pub from: Option<Address>,
+    /// Recipient of the bridged funds
+    pub to: Option<Address>,
+    /// Raw amount bridged
+    pub amount: Option<U256>,
+    /// Block containing the leg's event
+    pub block_number: Option<BlockNumber>,
+    /// Transaction containing the leg's event
+    pub tx_hash: Option<TxHash>,
+}
+
+impl BridgeLeg {
+    fn from_log(
+        protocol: BridgeProtocol,
+        direction: BridgeDirection,
+        kind: BridgeLegKind,
+        log: &Log,
+    ) -> Self {
+        Self {
+            protocol,
+            direction,
+            kind,
+            message_id: B256::ZERO,
+            l1_token: None,
+            l2_token: None,
+            from: None,
+            to: None,
+            amount: None,
+            block_number: log.block_number,
+            tx_hash: log.transaction_hash,
+        }
+    }
+
+    fn with_transfer(
+        mut self,
+        l1_token: Option<Address>,
+        l2_token: Option<Address>,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Self {
+        self.l1_token = l1_token;
+        self.l2_token = l2_token;
+        self.from = Some(from);
+        self.to = Some(to);
+        self.amount = Some(amount);
+        self
+    }
+
+    fn key(&self) -> (BridgeProtocol, BridgeDirection, B256) {
+        (self.protocol, self.direction, self.message_id)
+    }
+}
+
+/// A bridged transfer with both legs observed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeFlow {
+    /// Leg on the chain where the transfer started
+    pub initiated: BridgeLeg,
+    /// Leg on the chain where the transfer completed
+    pub finalized: BridgeLeg,
+}
+
+impl BridgeFlow {
+    /// Cross-chain identifier shared by both legs
+    pub fn message_id(&self) -> B256 {
+        self.initiated.message_id
+    }
+
+    /// L1 → L2 or L2 → L1
+    pub fn direction(&self) -> BridgeDirection {
+        self.initiated.direction
+    }
+}
+
+/// Result of pairing bridge legs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeFlows {
+    /// Transfers with both legs observed
+    pub completed: Vec<BridgeFlow>,
+    /// Initiated legs with no matching finalization (in flight, or outside the scanned range)
+    pub pending: Vec<BridgeLeg>,
+    /// Finalized legs whose initiation was not observed
+    pub unmatched_finalizations: Vec<BridgeLeg>,
+}
+
+/// Pairs L1 and L2 bridge legs by cross-chain message identifier
+///
+/// Legs are keyed by `(protocol, direction, message_id)`, so deposits and
+/// withdrawals never pair with each other even when their identifiers collide.
+/// If the same leg is added twice the later one wins.
+#[derive(Debug, Clone, Default)]
+pub struct BridgeFlowCalculator {
+    initiated: BTreeMap<(BridgeProtocol, BridgeDirection, B256), BridgeLeg>,
+    finalized: BTreeMap<(BridgeProtocol, BridgeDirection, B256), BridgeLeg>,
+}
+
+impl BridgeFlowCalculator {
+    /// Create an empty calculator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single leg
+    pub fn add_leg(&mut self, leg: BridgeLeg) {
+        let legs = match leg.kind {
+            BridgeLegKind::Initiated => &mut self.initiated,
+            BridgeLegKind::Finalized => &mut self.finalized,
+        };
+        legs.insert(leg.key(), leg);
+    }
+
+    /// Add legs from either chain
+    pub fn add_legs(&mut self, legs: impl IntoIterator<Item = BridgeLeg>) {
+        for leg in legs {
+            self.add_leg(leg);
+        }
+    }
+
+    /// Pair the legs added so far
+    pub fn flows(&self) -> BridgeFlows {
+        let mut flows = BridgeFlows::default();
+
+        for (key, initiated) in &self.initiated {
+            match self.finalized.get(key) {
+                Some(finalized) => flows.completed.push(BridgeFlow {
+                    initiated: initiated.clone(),
+                    finalized: finalized.clone(),
+                }),
+                None => flows.pending.push(initiated.clone()),
+            }
+        }
+
+        flows.unmatched_finalizations = self
+            .finalized
+            .iter()
+            .filter(|(key, _)| !self.initiated.contains_key(key))
+            .map(|(_, leg)| leg.clone())
+            .collect();
+
+        flows
+    }
+}
+
+/// Scans a chain for bridge events and decodes them into [`BridgeLeg`]s
+pub struct BridgeLegScanner<P> {
+    provider: P,
+    config: SemioscanConfig,
+}
+
+impl<P: Provider> BridgeLegScanner<P> {
+    /// Creates a new `BridgeLegScanner` with the given provider and configuration
+    pub fn new(provider: P, config: SemioscanConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Scan `[from_block, to_block]` for `protocol`'s bridge events
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - Chain to scan (determines chunking and rate limits)
+    /// * `protocol` - Which bridge's events to decode
+    /// * `contracts` - Bridge, messenger, inbox or outbox contracts to scan;
+    ///   empty means any emitter. For the OP Stack include both the standard
+    ///   bridge and the cross-domain messenger.
+    /// * `from_block` - Starting block number (inclusive)
+    /// * `to_block` - Ending block number (inclusive)
+    pub async fn scan(
+        &self,
+        chain: NamedChain,
+        protocol: BridgeProtocol,
+        contracts: &[Address],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<BridgeLeg>, EventProcessingError> {
+        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let mut filter = Filter::new().event_signature(bridge_event_topics(protocol));
+        if !contracts.is_empty() {
+            filter = filter.address(contracts.to_vec());
+        }
+
+        let logs = scanner.scan(chain, filter, from_block, to_block).await?;
+        let legs = match protocol {
+            BridgeProtocol::OpStack => extract_op_stack_legs(&logs),
+            BridgeProtocol::Arbitrum => extract_arbitrum_legs(&logs),
+        };
+
+        info!(
+            chain = %chain,
+            ?protocol,
+            logs = logs.len(),
+            legs = legs.len(),
+            "Finished scanning bridge events"
+        );
+
+        Ok(legs)
+    }
+}
+
+/// Event signatures needed to decode `protocol`'s bridge legs
+pub fn bridge_event_topics(protocol: BridgeProtocol) -> Vec<B256> {
+    match protocol {
+        BridgeProtocol::OpStack => vec![
+            ERC20DepositInitiated::SIGNATURE_HASH,
+            ETHDepositInitiated::SIGNATURE_HASH,
+            DepositFinalized::SIGNATURE_HASH,
+            WithdrawalInitiated::SIGNATURE_HASH,
+            ERC20WithdrawalFinalized::SIGNATURE_HASH,
+            ETHWithdrawalFinalized::SIGNATURE_HASH,
+            SentMessage::SIGNATURE_HASH,
+            SentMessageExtension1::SIGNATURE_HASH,
+            RelayedMessage::SIGNATURE_HASH,
+        ],
+        BridgeProtocol::Arbitrum => vec![
+            InboxMessageDelivered::SIGNATURE_HASH,
+            L2ToL1Tx::SIGNATURE_HASH,
+            OutBoxTransactionExecuted::SIGNATURE_HASH,
+        ],
+    }
+}
+
+/// Decode OP Stack bridge legs from logs in block/log-index order
+///
+/// Logs are grouped by transaction. Within a transaction, the n-th standard
+/// bridge event is assigned the n-th message hash: on the initiating side only
+/// messages sent by the emitting bridge are considered. Bridge events without a
+/// message hash in the same transaction are dropped.
+pub fn extract_op_stack_legs(logs: &[Log]) -> Vec<BridgeLeg> {
+    logs.chunk_by(|a, b| a.transaction_hash == b.transaction_hash)
+        .flat_map(op_stack_legs_for_tx)
+        .collect()
+}
+
+fn op_stack_legs_for_tx(logs: &[Log]) -> Vec<BridgeLeg> {
+    use BridgeDirection::{Deposit, Withdrawal};
+    use BridgeLegKind::{Finalized, Initiated};
+
+    let mut legs: Vec<(BridgeLeg, Address)> = Vec::new();
+    let mut sent: Vec<(Address, B256)> = Vec::new();
+    let mut relayed: Vec<B256> = Vec::new();
+    let mut pending_message: Option<SentMessage> = None;
+
+    for log in logs {
+        let Some(&topic0) = log.topic0() else {
+            continue;
+        };
+        let leg =
+            |direction, kind| BridgeLeg::from_log(BridgeProtocol::OpStack, direction, kind, log);
+        let decoded = if topic0 == SentMessage::SIGNATURE_HASH {
+            SentMessage::decode_log(&log.inner).map(|event| {
+                pending_message = Some(event.data);
+            })
+        } else if topic0 == SentMessageExtension1::SIGNATURE_HASH {
+            SentMessageExtension1::decode_log(&log.inner).map(|event| {
+                if let Some(message) = pending_message.take() {
+                    if let Some(hash) = op_message_hash(&message, event.value) {
+                        sent.push((message.sender, hash));
+                    }
+                }
+            })
+        } else if topic0 == RelayedMessage::SIGNATURE_HASH {
+            RelayedMessage::decode_log(&log.inner).map(|event| relayed.push(event.msgHash))
+        } else if topic0 == ERC20DepositInitiated::SIGNATURE_HASH {
+            ERC20DepositInitiated::decode_log(&log.inner).map(|e| {
+                let leg = leg(Deposit, Initiated).with_transfer(
+                    Some(e.l1Token),
+                    Some(e.l2Token),
+                    e.from,
+                    e.to,
+                    e.amount,
+                );
+                legs.push((leg, log.address()));
+            })
+        } else if topic0 == ETHDepositInitiated::SIGNATURE_HASH {
+            ETHDepositInitiated::decode_log(&log.inner).map(|e| {
+                let leg = leg(Deposit, Initiated).with_transfer(
+                    Some(Address::ZERO),
+                    None,
+                    e.from,
+                    e.to,
+                    e.amount,
+                );
+                legs.push((leg, log.address()));
+            })
+        } else if topic0 == DepositFinalized::SIGNATURE_HASH {
+            DepositFinalized::decode_log(&log.inner).map(|e| {
+                let leg = leg(Deposit, Finalized).with_transfer(
+                    Some(e.l1Token),
+                    Some(e.l2Token),
+                    e.from,
+                    e.to,
+                    e.amount,
+                );
+                legs.push((leg, log.address()));
+            })
+        } else if topic0 == WithdrawalInitiated::SIGNATURE_HASH {
+            WithdrawalInitiated::decode_log(&log.inner).map(|e| {
+                let leg = leg(Withdrawal, Initiated).with_transfer(
+                    Some(e.l1Token),
+                    Some(e.l2Token),
+                    e.from,
+                    e.to,
+                    e.amount,
+                );
+                legs.push((leg, log.address()));
+            })
+        } else if topic0 == ERC20WithdrawalFinalized::SIGNATURE_HASH {
+            ERC20WithdrawalFinalized::decode_log(&log.inner).map(|e| {
+                let leg = leg(Withdrawal, Finalized).with_transfer(
+                    Some(e.l1Token),
+                    Some(e.l2Token),
+                    e.from,
+                    e.to,
+                    e.amount,
+                );
+                legs.push((leg, log.address()));
+            })
+        } else if topic0 == ETHWithdrawalFinalized::SIGNATURE_HASH {
+            ETHWithdrawalFinalized::decode_log(&log.inner).map(|e| {
+                let leg = leg(Withdrawal, Finalized).with_transfer(
+                    Some(Address::ZERO),
+                    None,
+                    e.from,
+                    e.to,
+                    e.amount,
+                );
+                legs.push((leg, log.address()));
+            })
+        } else {
+            Ok(())
+        };
+
+        if let Err(e) = decoded {
+            warn!(error = %e, tx_hash = ?log.transaction_hash, "Skipping OP Stack bridge log");
+        }
+    }
+
+    let mut relayed = relayed.into_iter();
+    let mut result = Vec::with_capacity(legs.len());
+    for (mut leg, bridge) in legs {
+        let message_id = match leg.kind {
+            BridgeLegKind::Initiated => sent
+                .iter()
+                .position(|(sender, _)| *sender == bridge)
+                .map(|index| sent.remove(index).1),
+            BridgeLegKind::Finalized => relayed.next(),
+        };
+        match message_id {
+            Some(message_id) => {
+                leg.message_id = message_id;
+                result.push(leg);
+            }
+            None => {
+                warn!(tx_hash = ?leg.tx_hash, "No cross-domain message found for bridge event");
+            }
+        }
+    }
+    result
+}
+
+/// Hash an OP Stack cross-domain message as `Hashing.hashCrossDomainMessageV1` does
+///
+/// Returns `None` for messages with a version other than 1.
+fn op_message_hash(message: &SentMessage, value: U256) -> Option<B256> {
+    let version = message.messageNonce >> 240;
+    if version != U256::from(1) {
+        return None;
+    }
+    let call = relayMessageCall {
+        _nonce: message.messageNonce,
+        _sender: message.sender,
+        _target: message.target,
+        _value: value,
+        _minGasLimit: message.gasLimit,
+        _message: message.message.clone(),
+    };
+    Some(keccak256(call.abi_encode()))
+}
+
+/// Decode Arbitrum bridge legs from logs
+///
+/// Handles `InboxMessageDelivered` (deposit initiated on L1), `L2ToL1Tx`
+/// (withdrawal initiated on L2) and `OutBoxTransactionExecuted` (withdrawal
+/// finalized on L1). Use [`arbitrum_retryable_leg`] for the L2 side of deposits.
+pub fn extract_arbitrum_legs(logs: &[Log]) -> Vec<BridgeLeg> {
+    use BridgeDirection::{Deposit, Withdrawal};
+    use BridgeLegKind::{Finalized, Initiated};
+
+    let mut legs = Vec::new();
+    for log in logs {
+        let Some(&topic0) = log.topic0() else {
+            continue;
+        };
+        let leg =
+            |direction, kind| BridgeLeg::from_log(BridgeProtocol::Arbitrum, direction, kind, log);
+        let decoded = if topic0 == InboxMessageDelivered::SIGNATURE_HASH {
+            InboxMessageDelivered::decode_log(&log.inner).map(|e| {
+                legs.push(BridgeLeg {
+                    message_id: B256::from(e.messageNum),
+                    ..leg(Deposit, Initiated)
+                });
+            })
+        } else if topic0 == L2ToL1Tx::SIGNATURE_HASH {
+            L2ToL1Tx::decode_log(&log.inner).map(|e| {
+                legs.push(BridgeLeg {
+                    message_id: B256::from(e.position),
+                    from: Some(e.caller),
+                    to: Some(e.destination),
+                    amount: Some(e.callvalue),
+                    ..leg(Withdrawal, Initiated)
+                });
+            })
+        } else if topic0 == OutBoxTransactionExecuted::SIGNATURE_HASH {
+            OutBoxTransactionExecuted::decode_log(&log.inner).map(|e| {
+                legs.push(BridgeLeg {
+                    message_id: B256::from(e.transactionIndex),
+                    from: Some(e.l2Sender),
+                    to: Some(e.to),
+                    ..leg(Withdrawal, Finalized)
+                });
+            })
+        } else {
+            Ok(())
+        };
+
+        if let Err(e) = decoded {
+            warn!(error = %e, tx_hash = ?log.transaction_hash, "Skipping Arbitrum bridge log");
+        }
+    }
+    legs
+}
+
+/// Build the L2 leg of an Arbitrum retryable deposit from its `TicketCreated` log
+///
+/// `request_id` is the `requestId` of the L2 submit-retryable transaction, which
+/// equals the L1 inbox message number. Returns `Ok(None)` if the log is not a
+/// `TicketCreated` event.
+///
+/// # Errors
+///
+/// Returns [`EventProcessingError::DecodeFailed`] if the log has the
+/// `TicketCreated` signature but cannot be decoded.
+pub fn arbitrum_retryable_leg(
+    log: &Log,
+    request_id: U256,
+) -> Result<Option<BridgeLeg>, EventProcessingError> {
+    if log.topic0() != Some(&TicketCreated::SIGNATURE_HASH) {
+        return Ok(None);
+    }
+    TicketCreated::decode_log(&log.inner)
+        .map_err(|e| EventProcessingError::decode_failed(format!("TicketCreated: {e}")))?;
+
+    Ok(Some(BridgeLeg {
+        message_id: B256::from(request_id),
+        ..BridgeLeg::from_log(
+            BridgeProtocol::Arbitrum,
+            BridgeDirection::Deposit,
+            BridgeLegKind::Finalized,
+            log,
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, Bytes};
+
+    const L1_BRIDGE: Address = address!("3154Cf16ccdb4C6d922629664174b904d80F2C35");
+    const L2_BRIDGE: Address = address!("4200000000000000000000000000000000000010");
+    const L1_TOKEN: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const L2_TOKEN: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+    const USER: Address = address!("1111111111111111111111111111111111111111");
+
+    fn log_of(
+        address: Address,
+        event: &impl SolEvent,
+        tx_hash: B256,
+        block_number: BlockNumber,
+    ) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block_number),
+            transaction_hash: Some(tx_hash),
+            ..Default::default()
+        }
+    }
+
+    fn sent_message() -> SentMessage {
+        SentMessage {
+            target: L2_BRIDGE,
+            sender: L1_BRIDGE,
+            message: Bytes::from_static(b"finalizeBridgeERC20"),
+            messageNonce: (U256::from(1) << 240) | U256::from(7),
+            gasLimit: U256::from(200_000),
+        }
+    }
+
+    #[test]
+    fn op_stack_deposit_legs_pair_by_message_hash() {
+        let l1_tx = b256!("00000000000000000000000000000000000000000000000000000000000000a1");
+        let l2_tx = b256!("00000000000000000000000000000000000000000000000000000000000000b2");
+        let message = sent_message();
+        let hash = op_message_hash(&message, U256::ZERO).unwrap();
+
+        let l1_logs = vec![
+            log_of(
+                L1_BRIDGE,
+                &ERC20DepositInitiated {
+                    l1Token: L1_TOKEN,
+                    l2Token: L2_TOKEN,
+                    from: USER,
+                    to: USER,
+                    amount: U256::from(1_000_000),
+                    extraData: Bytes::new(),
+                },
+                l1_tx,
+                100,
+            ),
+            log_of(L1_BRIDGE, &message, l1_tx, 100),
+            log_of(
+                L1_BRIDGE,
+                &SentMessageExtension1 {
+                    sender: L1_BRIDGE,
+                    value: U256::ZERO,
+                },
+                l1_tx,
+                100,
+            ),
+        ];
+        let l2_logs = vec![
+            log_of(
+                L2_BRIDGE,
+                &DepositFinalized {
+                    l1Token: L1_TOKEN,
+                    l2Token: L2_TOKEN,
+                    from: USER,
+                    to: USER,
+                    amount: U256::from(1_000_000),
+                    extraData: Bytes::new(),
+                },
+                l2_tx,
+                5_000,
+            ),
+            log_of(L2_BRIDGE, &RelayedMessage { msgHash: hash }, l2_tx, 5_000),
+        ];
+
+        let mut calculator = BridgeFlowCalculator::new();
+        calculator.add_legs(extract_op_stack_legs(&l1_logs));
+        calculator.add_legs(extract_op_stack_legs(&l2_logs));
+        let flows = calculator.flows();
+
+        assert_eq!(flows.completed.len(), 1);
+        assert!(flows.pending.is_empty());
+        assert!(flows.unmatched_finalizations.is_empty());
+
+        let flow = &flows.completed[0];
+        assert_eq!(flow.message_id(), hash);
+        assert_eq!(flow.direction(), BridgeDirection::Deposit);
+        assert_eq!(flow.initiated.tx_hash, Some(l1_tx));
+        assert_eq!(flow.finalized.tx_hash, Some(l2_tx));
+        assert_eq!(flow.finalized.amount, Some(U256::from(1_000_000)));
+    }
+
+    #[test]
+    fn legacy_op_messages_are_not_hashed() {
+        let mut message = sent_message();
+        message.messageNonce = U256::from(7);
+        assert_eq!(op_message_hash(&message, U256::ZERO), None);
+    }
+
+    #[test]
+    fn arbitrum_withdrawals_pair_by_outbox_position() {
+        let position = U256::from(42);
+        let l2_logs = vec![log_of(
+            address!("0000000000000000000000000000000000000064"),
+            &L2ToL1Tx {
+                caller: USER,
+                destination: USER,
+                hash: U256::from(1),
+                position,
+                arbBlockNum: U256::from(10),
+                ethBlockNum: U256::from(9),
+                timestamp: U256::from(1_700_000_000),
+                callvalue: U256::from(5),
+                data: Bytes::new(),
+            },
+            B256::repeat_byte(1),
+            10,
+        )];
+        let l1_logs = vec![log_of(
+            address!("0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840"),
+            &OutBoxTransactionExecuted {
+                to: USER,
+                l2Sender: USER,
+                zero: U256::ZERO,
+                transactionIndex: position,
+            },
+            B256::repeat_byte(2),
+            20,
+        )];
+
+        let mut calculator = BridgeFlowCalculator::new();
+        calculator.add_legs(extract_arbitrum_legs(&l2_logs));
+        calculator.add_legs(extract_arbitrum_legs(&l1_logs));
+        let flows = calculator.flows();
+
+        assert_eq!(flows.completed.len(), 1);
+        assert_eq!(flows.completed[0].direction(), BridgeDirection::Withdrawal);
+        assert_eq!(flows.completed[0].initiated.amount, Some(U256::from(5)));
+    }
+
+    #[test]
+    fn arbitrum_retryables_pair_by_request_id() {
+        let message_num = U256::from(9_001);
+        let l1_logs = vec![log_of(
+            address!("4Dbd4fc535Ac27206064B68FfCf827b0A60BAB3f"),
+            &InboxMessageDelivered {
+                messageNum: message_num,
+                data: Bytes::new(),
+            },
+            B256::repeat_byte(3),
+            30,
+        )];
+        let ticket = log_of(
+            address!("000000000000000000000000000000000000006E"),
+            &TicketCreated {
+                ticketId: B256::repeat_byte(4),
+            },
+            B256::repeat_byte(4),
+            40,
+        );
+
+        let mut calculator = BridgeFlowCalculator::new();
+        calculator.add_legs(extract_arbitrum_legs(&l1_logs));
+        calculator.add_leg(
+            arbitrum_retryable_leg(&ticket, message_num)
+                .unwrap()
+                .unwrap(),
+        );
+
+        let flows = calculator.flows();
+        assert_eq!(flows.completed.len(), 1);
+        assert_eq!(flows.completed[0].direction(), BridgeDirection::Deposit);
+    }
+
+    #[test]
+    fn deposits_and_withdrawals_with_equal_ids_do_not_pair() {
+        let log = Log::default();
+        let mut calculator = BridgeFlowCalculator::new();
+        calculator.add_leg(BridgeLeg::from_log(
+            BridgeProtocol::Arbitrum,
+            BridgeDirection::Deposit,
+            BridgeLegKind::Initiated,
+            &log,
+        ));
+        calculator.add_leg(BridgeLeg::from_log(
+            BridgeProtocol::Arbitrum,
+            BridgeDirection::Withdrawal,
+            BridgeLegKind::Finalized,
+            &log,
+        ));
+
+        let flows = calculator.flows();
+        assert!(flows.completed.is_empty());
+        assert_eq!(flows.pending.len(), 1);
+        assert_eq!(flows.unmatched_finalizations.len(), 1);
+    }
+}
@@ -7,6 +7,12 @@
 //! Provides a standalone function for fetching logs in chunks without
 //! requiring `SemioscanConfig` or chain-specific configuration.
 //!
+//! It also hosts the range bisection used by every log-scanning loop, whatever
+//! [`LogSource`] it reads from: when a source rejects a chunk because it would
+//! return too many results (e.g. "query returned more than 10000 results"), the
+//! chunk is split in half and each half retried, down to single blocks, instead
+//! of failing the scan.
+//!
 //! # Example
 //!
 //! ```rust,no_run
@@ -32,11 +38,18 @@
 //! # }
 //! ```
 
+use std::future::Future;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::BlockNumber;
 use alloy_provider::Provider;
 use alloy_rpc_types::{Filter, Log};
-use tracing::debug;
+use alloy_transport::TransportError;
+use tracing::{debug, warn};
 
+use crate::config::{AdaptiveChunking, SemioscanConfig};
 use crate::errors::EventProcessingError;
+use crate::events::source::{LogSource, RpcLogSource};
 use crate::MaxBlockRange;
 
 /// Fetch logs in chunks to handle large block ranges
@@ -61,6 +74,9 @@ use crate::MaxBlockRange;
 /// - The filter doesn't have both `from_block` and `to_block` set
 /// - Any chunk fetch fails (fails fast, no partial results)
 ///
+/// Chunks rejected for returning too many results are bisected and retried
+/// rather than treated as failures.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -110,6 +126,7 @@ pub async fn fetch_logs_chunked<P: Provider>(
         .ok_or_else(|| EventProcessingError::invalid_input("Filter must have to_block set"))?;
 
     let max_block_range = MaxBlockRange::new(chunk_size);
+    let source = RpcLogSource::new(provider);
 
     debug!(
         start_block = start_block,
@@ -130,11 +147,13 @@ pub async fn fetch_logs_chunked<P: Provider>(
             "Fetching logs for chunk"
         );
 
-        let logs = provider.get_logs(&chunk_filter).await.map_err(|e| {
-            EventProcessingError::rpc_failed(format!(
-                "Failed to fetch logs for blocks {chunk_start}-{chunk_end}: {e}"
-            ))
-        })?;
+        let logs = get_logs_bisecting(&source, &chunk_filter, chunk_start, chunk_end)
+            .await
+            .map_err(|e| {
+                EventProcessingError::rpc_failed(format!(
+                    "Failed to fetch logs for blocks {chunk_start}-{chunk_end}: {e}"
+                ))
+            })?;
 
         debug!(logs_count = logs.len(), "Fetched logs for chunk");
         all_logs.extend(logs);
@@ -145,10 +164,197 @@ pub async fn fetch_logs_chunked<P: Provider>(
     Ok(all_logs)
 }
 
+/// Error message fragments providers use when a log query is too large
+///
+/// Matched case-insensitively against the full error text, which covers both
+/// JSON-RPC error responses and HTTP error bodies.
+const RESULT_LIMIT_PATTERNS: &[&str] = &[
+    "query returned more than",
+    "log response size exceeded",
+    "response size exceeded",
+    "too many results",
+    "exceeds the limit",
+    "block range is too wide",
+    "block range too large",
+    "range too large",
+    "exceed maximum block range",
+];
+
+/// Check whether an `eth_getLogs` error means the range should be split
+pub(crate) fn is_result_limit_error(error: &TransportError) -> bool {
+    // -32005 is the "limit exceeded" code used by Infura and others
+    if error
+        .as_error_resp()
+        .is_some_and(|payload| payload.code == -32005)
+    {
+        return true;
+    }
+    let message = error.to_string().to_lowercase();
+    RESULT_LIMIT_PATTERNS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Fetch logs for `[from_block, to_block]`, bisecting the range on result-limit errors
+///
+/// The block range of `filter` is replaced by the given bounds. Sub-ranges are
+/// fetched in block order, so logs come back in the same order a single
+/// successful query would return them. Errors other than result limits, and
+/// result-limit errors on a single block, are returned unchanged.
+pub(crate) async fn get_logs_bisecting<S: LogSource + ?Sized>(
+    source: &S,
+    filter: &Filter,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<Log>, TransportError> {
+    bisect_range(from_block, to_block, |from, to| {
+        let filter = filter.clone().from_block(from).to_block(to);
+        async move { source.get_logs(&filter).await }
+    })
+    .await
+}
+
+/// Core of [`get_logs_bisecting`], generic over the fetch for testability
+async fn bisect_range<F, Fut>(
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    mut fetch: F,
+) -> Result<Vec<Log>, TransportError>
+where
+    F: FnMut(BlockNumber, BlockNumber) -> Fut,
+    Fut: Future<Output = Result<Vec<Log>, TransportError>>,
+{
+    let mut logs = Vec::new();
+    // Ranges still to fetch, with the earliest on top of the stack
+    let mut pending = vec![(from_block, to_block)];
+
+    while let Some((from, to)) = pending.pop() {
+        match fetch(from, to).await {
+            Ok(chunk) => logs.extend(chunk),
+            Err(e) if from < to && is_result_limit_error(&e) => {
+                let mid = from + (to - from) / 2;
+                warn!(
+                    from_block = from,
+                    to_block = to,
+                    error = %e,
+                    "Log query exceeded provider limits, splitting range"
+                );
+                pending.push((mid + 1, to));
+                pending.push((from, mid));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(logs)
+}
+
+/// Walks a block range in chunks for the log-scanning loops
+///
+/// With [`SemioscanConfig::adaptive_chunking`] unset, every chunk is the
+/// chain's `max_block_range` and failures are never retried, matching the
+/// fixed-size loops this replaced. With it set, chunk size follows the observed
+/// log density as described on [`AdaptiveChunking`], but never exceeds a
+/// per-chain `max_block_range` override.
+#[derive(Debug)]
+pub(crate) struct ChunkScheduler {
+    next_block: Option<BlockNumber>,
+    end_block: BlockNumber,
+    size: u64,
+    max_size: u64,
+    adaptive: Option<AdaptiveChunking>,
+}
+
+impl ChunkScheduler {
+    pub(crate) fn new(
+        config: &SemioscanConfig,
+        chain: NamedChain,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Self {
+        let adaptive = config.adaptive_chunking;
+        let max_size = match adaptive {
+            // A per-chain limit (set by hand or by calibration) caps the adaptive range
+            Some(adaptive) => config
+                .chain_overrides
+                .get(&chain)
+                .and_then(|chain_config| chain_config.max_block_range)
+                .map_or(adaptive.max_block_range, |chain_max| {
+                    chain_max.min(adaptive.max_block_range)
+                })
+                .as_u64(),
+            None => config.get_max_block_range(chain).as_u64(),
+        }
+        .max(1);
+        Self {
+            next_block: (start_block <= end_block).then_some(start_block),
+            end_block,
+            size: max_size,
+            max_size,
+            adaptive,
+        }
+    }
+
+    /// The chunk to fetch next, or `None` once the range is covered
+    pub(crate) fn next_chunk(&self) -> Option<(BlockNumber, BlockNumber)> {
+        let from = self.next_block?;
+        let to = from.saturating_add(self.size - 1).min(self.end_block);
+        Some((from, to))
+    }
+
+    /// Record that the current chunk returned `log_count` logs and move past it
+    pub(crate) fn chunk_succeeded(&mut self, log_count: usize) {
+        if let Some(adaptive) = self.adaptive {
+            if log_count > adaptive.dense_log_threshold {
+                self.shrink(adaptive);
+            } else if log_count < adaptive.sparse_log_threshold {
+                self.size = self.size.saturating_mul(2).min(self.max_size);
+            }
+        }
+        self.skip_chunk();
+    }
+
+    /// Record that the current chunk failed
+    ///
+    /// Returns `true` if the chunk size was reduced and the (now smaller) chunk
+    /// should be retried. Returns `false` when there is nothing left to shrink;
+    /// the caller then either gives up or calls [`skip_chunk`](Self::skip_chunk).
+    pub(crate) fn chunk_failed(&mut self) -> bool {
+        match self.adaptive {
+            Some(adaptive) => self.shrink(adaptive),
+            None => false,
+        }
+    }
+
+    /// Move past the current chunk without adjusting the chunk size
+    pub(crate) fn skip_chunk(&mut self) {
+        if let Some((_, to)) = self.next_chunk() {
+            self.next_block = (to < self.end_block).then(|| to + 1);
+        }
+    }
+
+    fn shrink(&mut self, adaptive: AdaptiveChunking) -> bool {
+        let min = adaptive.min_block_range.as_u64().clamp(1, self.max_size);
+        let shrunk = (self.size / 2).max(min);
+        let changed = shrunk < self.size;
+        if changed {
+            debug!(
+                from = self.size,
+                to = shrunk,
+                "Shrinking log query chunk size"
+            );
+        }
+        self.size = shrunk;
+        changed
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ChainConfig;
     use alloy_provider::ProviderBuilder;
+    use alloy_transport::TransportErrorKind;
 
     /// Create a provider for validation tests. The provider won't be called
     /// because validation fails before any RPC requests are made.
@@ -217,4 +423,170 @@ mod tests {
         );
         assert!(err.to_string().contains("to_block"));
     }
+
+    fn log_at(block_number: BlockNumber) -> Log {
+        Log {
+            block_number: Some(block_number),
+            ..Default::default()
+        }
+    }
+
+    fn too_many_results() -> TransportError {
+        TransportErrorKind::custom_str("query returned more than 10000 results")
+    }
+
+    #[test]
+    fn test_result_limit_errors_are_recognized() {
+        assert!(is_result_limit_error(&too_many_results()));
+        assert!(is_result_limit_error(&TransportErrorKind::custom_str(
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"
+        )));
+        assert!(!is_result_limit_error(&TransportErrorKind::custom_str(
+            "connection reset by peer"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_bisection_splits_until_ranges_fit() {
+        let mut calls = Vec::new();
+        let logs = bisect_range(0, 7, |from, to| {
+            calls.push((from, to));
+            async move {
+                // Provider only accepts ranges of at most 2 blocks
+                if to - from + 1 > 2 {
+                    Err(too_many_results())
+                } else {
+                    Ok((from..=to).map(log_at).collect())
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        let blocks: Vec<_> = logs.iter().map(|log| log.block_number.unwrap()).collect();
+        assert_eq!(blocks, (0..=7).collect::<Vec<_>>());
+        assert_eq!(calls[0], (0, 7));
+        assert_eq!(calls.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_bisection_surfaces_other_errors() {
+        let result = bisect_range(0, 7, |_, _| async {
+            Err(TransportErrorKind::custom_str("connection reset by peer"))
+        })
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bisection_gives_up_on_single_blocks() {
+        let mut calls = 0;
+        let result = bisect_range(5, 5, |_, _| {
+            calls += 1;
+            async { Err(too_many_results()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    fn adaptive_config() -> SemioscanConfig {
+        SemioscanConfig {
+            adaptive_chunking: Some(AdaptiveChunking {
+                min_block_range: MaxBlockRange::new(10),
+                max_block_range: MaxBlockRange::new(1_000),
+                dense_log_threshold: 100,
+                sparse_log_threshold: 10,
+            }),
+            ..SemioscanConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_fixed_scheduler_walks_range_without_retries() {
+        let config = SemioscanConfig::default();
+        let size = config.get_max_block_range(NamedChain::Mainnet).as_u64();
+        let mut chunks = ChunkScheduler::new(&config, NamedChain::Mainnet, 0, size + 5);
+
+        assert_eq!(chunks.next_chunk(), Some((0, size - 1)));
+        assert!(!chunks.chunk_failed());
+        chunks.chunk_succeeded(1_000_000);
+        assert_eq!(chunks.next_chunk(), Some((size, size + 5)));
+        chunks.skip_chunk();
+        assert_eq!(chunks.next_chunk(), None);
+    }
+
+    #[test]
+    fn test_adaptive_scheduler_shrinks_on_dense_chunks_and_failures() {
+        let mut chunks = ChunkScheduler::new(&adaptive_config(), NamedChain::Mainnet, 0, 10_000);
+
+        assert_eq!(chunks.next_chunk(), Some((0, 999)));
+        chunks.chunk_succeeded(500);
+        assert_eq!(chunks.next_chunk(), Some((1_000, 1_499)));
+
+        assert!(chunks.chunk_failed());
+        assert_eq!(chunks.next_chunk(), Some((1_000, 1_249)));
+
+        for _ in 0..10 {
+            chunks.chunk_failed();
+        }
+        assert_eq!(chunks.next_chunk(), Some((1_000, 1_009)));
+        assert!(!chunks.chunk_failed());
+    }
+
+    #[test]
+    fn test_adaptive_scheduler_grows_back_on_sparse_chunks() {
+        let mut chunks = ChunkScheduler::new(&adaptive_config(), NamedChain::Mainnet, 0, 10_000);
+        chunks.chunk_failed();
+        chunks.chunk_failed();
+        assert_eq!(chunks.next_chunk(), Some((0, 249)));
+
+        chunks.chunk_succeeded(50);
+        assert_eq!(chunks.next_chunk(), Some((250, 499)));
+        chunks.chunk_succeeded(0);
+        assert_eq!(chunks.next_chunk(), Some((500, 999)));
+        chunks.chunk_succeeded(0);
+        chunks.chunk_succeeded(0);
+        assert_eq!(chunks.next_chunk(), Some((2_000, 2_999)));
+    }
+
+    #[test]
+    fn test_adaptive_scheduler_respects_chain_max_block_range() {
+        let mut config = adaptive_config();
+        config.set_chain_override(
+            NamedChain::Base,
+            ChainConfig {
+                max_block_range: Some(MaxBlockRange::new(200)),
+                ..ChainConfig::default()
+            },
+        );
+
+        let mut chunks = ChunkScheduler::new(&config, NamedChain::Base, 0, 10_000);
+        assert_eq!(chunks.next_chunk(), Some((0, 199)));
+
+        // Sparse chunks grow back only up to the chain's limit
+        chunks.chunk_failed();
+        assert_eq!(chunks.next_chunk(), Some((0, 99)));
+        chunks.chunk_succeeded(0);
+        chunks.chunk_succeeded(0);
+        assert_eq!(chunks.next_chunk(), Some((300, 499)));
+
+        // Chains without an override keep the adaptive maximum
+        let chunks = ChunkScheduler::new(&config, NamedChain::Mainnet, 0, 10_000);
+        assert_eq!(chunks.next_chunk(), Some((0, 999)));
+    }
+
+    #[test]
+    fn test_scheduler_handles_empty_and_terminal_ranges() {
+        let config = adaptive_config();
+        assert_eq!(
+            ChunkScheduler::new(&config, NamedChain::Mainnet, 10, 5).next_chunk(),
+            None
+        );
+
+        let mut chunks = ChunkScheduler::new(&config, NamedChain::Mainnet, u64::MAX - 1, u64::MAX);
+        assert_eq!(chunks.next_chunk(), Some((u64::MAX - 1, u64::MAX)));
+        chunks.chunk_succeeded(0);
+        assert_eq!(chunks.next_chunk(), None);
+    }
 }
@@ -8,10 +8,15 @@
 //! token events: Transfer and Approval. These events are universal across all
 //! ERC-20 tokens and follow the ERC-20 specification.
 //!
+//! It also defines the WETH9 `Deposit` and `Withdrawal` events, which
+//! wrapped-native tokens emit instead of mint/burn Transfers.
+//!
 //! # Event Signatures
 //!
 //! - **Transfer**: `Transfer(address,address,uint256)`
 //! - **Approval**: `Approval(address,address,uint256)`
+//! - **Deposit**: `Deposit(address,uint256)`
+//! - **Withdrawal**: `Withdrawal(address,uint256)`
 //!
 //! # Example: Decoding Transfer events
 //!
@@ -142,3 +147,43 @@ impl Debug for Approval {
         )
     }
 }
+
+sol! {
+    /// WETH9 Deposit event
+    ///
+    /// Emitted when native ETH is wrapped. WETH9 and most wrapped-native tokens
+    /// do not emit a `Transfer` from the zero address alongside it, so this is
+    /// the only on-chain record of the mint.
+    ///
+    /// # Fields
+    ///
+    /// - `dst`: Address credited with the wrapped tokens (indexed)
+    /// - `wad`: Amount wrapped (raw, 18 decimals for WETH)
+    event Deposit(address indexed dst, uint256 wad);
+}
+
+impl Debug for Deposit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Deposit(dst: {}, wad: {})", self.dst, self.wad)
+    }
+}
+
+sol! {
+    /// WETH9 Withdrawal event
+    ///
+    /// Emitted when wrapped tokens are unwrapped back to native ETH. Like
+    /// [`Deposit`], it replaces the `Transfer` to the zero address that a
+    /// regular burn would emit.
+    ///
+    /// # Fields
+    ///
+    /// - `src`: Address whose wrapped tokens were burned (indexed)
+    /// - `wad`: Amount unwrapped (raw, 18 decimals for WETH)
+    event Withdrawal(address indexed src, uint256 wad);
+}
+
+impl Debug for Withdrawal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Withdrawal(src: {}, wad: {})", self.src, self.wad)
+    }
+}
@@ -2,16 +2,20 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-//! Token discovery by scanning Transfer events
+//! Token and counterparty discovery by scanning Transfer events
 //!
 //! This module provides utilities for discovering which tokens have been transferred
-//! to a specific address (typically a router contract) by scanning blockchain Transfer events.
+//! to a specific address (typically a router contract) by scanning blockchain Transfer events,
+//! and which addresses a sender has transferred a token to.
 //!
 //! # Use Cases
 //!
 //! - **Token inventory**: Discover which tokens a contract has received
 //! - **Balance checking**: Identify tokens that may have non-zero balances
 //! - **Historical analysis**: Track token flows over time
+//! - **Counterparty discovery**: Find every recipient of a sender's transfers
+//!   before attributing gas and amounts to each, with
+//!   [`discover_recipients()`] and `CombinedCalculator::calculate_for_counterparties`
 //!
 //! # Example: Discover tokens transferred to a router
 //!
@@ -80,6 +84,8 @@
 //! let logs = scanner.scan(NamedChain::Arbitrum, filter, 1000, 2000).await?;
 //! ```
 
+use std::collections::BTreeSet;
+
 use alloy_chains::NamedChain;
 use alloy_primitives::{Address, BlockNumber};
 use alloy_provider::Provider;
@@ -87,10 +93,13 @@ use alloy_sol_types::SolEvent;
 use tracing::{info, warn};
 
 use crate::config::SemioscanConfig;
-use crate::errors::EventProcessingError;
+use crate::errors::{EventProcessingError, RpcError};
+use crate::events::chunked::{get_logs_bisecting, ChunkScheduler};
 use crate::events::definitions::Transfer;
 use crate::events::filter::TransferFilterBuilder;
 use crate::events::scanner::EventScanner;
+use crate::events::source::{LogSource, RpcLogSource};
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
 use crate::types::tokens::TokenSet;
 
 /// Extract tokens transferred to a router contract using default configuration
@@ -260,3 +269,156 @@ pub async fn extract_transferred_to_tokens_with_config<T: Provider>(
 
     Ok(transferred_to_tokens)
 }
+
+/// Discover the addresses `sender` transferred `token` to, using default configuration
+///
+/// Scans `token`'s Transfer events from `sender` over the block range and
+/// returns each distinct recipient once, in address order. Feed the result
+/// to a calculator to attribute gas and amounts per counterparty, or use
+/// `CombinedCalculator::calculate_for_counterparties`, which does both.
+///
+/// # Errors
+///
+/// Fails if a chunk of logs can't be fetched even at the smallest chunk
+/// size: a skipped chunk could hide a counterparty.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use semioscan::discover_recipients;
+///
+/// let recipients = discover_recipients(
+///     &provider,
+///     NamedChain::Base,
+///     usdc,
+///     hot_wallet,
+///     1_000_000,
+///     1_010_000,
+/// )
+/// .await?;
+/// println!("{} counterparties", recipients.len());
+/// ```
+pub async fn discover_recipients<T: Provider>(
+    provider: &T,
+    chain: NamedChain,
+    token: impl Into<TokenAddress>,
+    sender: impl Into<SenderAddress>,
+    start_block: BlockNumber,
+    end_block: BlockNumber,
+) -> Result<BTreeSet<RecipientAddress>, EventProcessingError> {
+    discover_recipients_with_config(
+        provider,
+        chain,
+        token,
+        sender,
+        start_block,
+        end_block,
+        &SemioscanConfig::default(),
+    )
+    .await
+}
+
+/// Discover the addresses `sender` transferred `token` to, with custom configuration
+///
+/// Like [`discover_recipients`], with chunking and rate limiting taken from
+/// `config`.
+pub async fn discover_recipients_with_config<T: Provider>(
+    provider: &T,
+    chain: NamedChain,
+    token: impl Into<TokenAddress>,
+    sender: impl Into<SenderAddress>,
+    start_block: BlockNumber,
+    end_block: BlockNumber,
+    config: &SemioscanConfig,
+) -> Result<BTreeSet<RecipientAddress>, EventProcessingError> {
+    discover_recipients_from_source(
+        &RpcLogSource::new(provider),
+        config,
+        chain,
+        token.into(),
+        sender.into(),
+        start_block,
+        end_block,
+    )
+    .await
+}
+
+/// Discover the recipients of `sender`'s `token` transfers from the logs of `source`
+pub(crate) async fn discover_recipients_from_source(
+    source: &dyn LogSource,
+    config: &SemioscanConfig,
+    chain: NamedChain,
+    token: TokenAddress,
+    sender: SenderAddress,
+    start_block: BlockNumber,
+    end_block: BlockNumber,
+) -> Result<BTreeSet<RecipientAddress>, EventProcessingError> {
+    let (token, sender) = (token.as_address(), sender.as_address());
+    info!(
+        chain = %chain,
+        token = %token,
+        sender = %sender,
+        start_block,
+        end_block,
+        "Discovering transfer recipients"
+    );
+
+    let filter = TransferFilterBuilder::new()
+        .with_token(token)
+        .with_sender(sender)
+        .build();
+    let mut pacer = config.pacer(chain);
+    let mut chunks = ChunkScheduler::new(config, chain, start_block, end_block);
+
+    let mut recipients = BTreeSet::new();
+    let mut total_transfer_events = 0;
+    while let Some((current_block, to_block)) = chunks.next_chunk() {
+        pacer.wait().await;
+
+        let logs = match get_logs_bisecting(source, &filter, current_block, to_block).await {
+            Ok(logs) => logs,
+            Err(e) if chunks.chunk_failed() => {
+                warn!(
+                    ?e,
+                    current_block,
+                    to_block,
+                    "Error fetching logs in range, retrying with a smaller chunk"
+                );
+                continue;
+            }
+            Err(e) => {
+                return Err(RpcError::get_logs_failed(
+                    format!(
+                        "Transfer events from {sender} from block {current_block} to {to_block}"
+                    ),
+                    e,
+                )
+                .into());
+            }
+        };
+        chunks.chunk_succeeded(logs.len());
+        total_transfer_events += logs.len();
+
+        for log in logs {
+            match Transfer::decode_log(&log.inner) {
+                Ok(event) if event.from == sender => {
+                    recipients.insert(RecipientAddress::from(event.to));
+                }
+                Err(e) => {
+                    warn!(error = ?e, "Failed to decode Transfer log");
+                }
+                _ => {}
+            }
+        }
+    }
+
+    info!(
+        chain = %chain,
+        sender = %sender,
+        unique_recipients = recipients.len(),
+        total_transfer_events,
+        "Recipient discovery completed"
+    );
+
+    Ok(recipients)
+}
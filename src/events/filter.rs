@@ -45,6 +45,9 @@
 
 use alloy_primitives::{keccak256, Address, BlockNumber, U256};
 use alloy_rpc_types::Filter;
+use alloy_sol_types::SolEvent;
+
+use crate::events::definitions::Approval;
 
 /// Builder for ERC-20 Transfer event filters with semantic methods
 ///
@@ -91,13 +94,13 @@ use alloy_rpc_types::Filter;
 ///     .in_block_range(start_block, end_block)
 ///     .build();
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct TransferFilterBuilder {
     from_block: Option<BlockNumber>,
     to_block: Option<BlockNumber>,
-    token_address: Option<Address>,
-    from_address: Option<Address>,
-    to_address: Option<Address>,
+    pub(crate) token_address: Option<Address>,
+    pub(crate) from_address: Option<Address>,
+    pub(crate) to_address: Option<Address>,
 }
 
 impl TransferFilterBuilder {
@@ -226,6 +229,75 @@ impl TransferFilterBuilder {
     }
 }
 
+/// Builder for ERC-20 Approval event filters with semantic methods
+///
+/// The Approval counterpart of [`TransferFilterBuilder`]:
+/// ```solidity
+/// event Approval(address indexed owner, address indexed spender, uint256 value);
+/// ```
+///
+/// - topic1: `owner` address (holder granting the allowance)
+/// - topic2: `spender` address (address allowed to spend)
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::events::filter::ApprovalFilterBuilder;
+///
+/// // Find every allowance an owner granted a router
+/// let filter = ApprovalFilterBuilder::new()
+///     .with_token(usdc)
+///     .with_owner(owner)
+///     .with_spender(router)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ApprovalFilterBuilder {
+    pub(crate) token_address: Option<Address>,
+    pub(crate) owner: Option<Address>,
+    pub(crate) spender: Option<Address>,
+}
+
+impl ApprovalFilterBuilder {
+    /// Create a new Approval event filter builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filter for approvals of a specific token
+    pub fn with_token(mut self, token: Address) -> Self {
+        self.token_address = Some(token);
+        self
+    }
+
+    /// Filter for approvals granted by `owner` (topic1 in ERC-20 Approval)
+    pub fn with_owner(mut self, owner: Address) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Filter for approvals granted to `spender` (topic2 in ERC-20 Approval)
+    pub fn with_spender(mut self, spender: Address) -> Self {
+        self.spender = Some(spender);
+        self
+    }
+
+    /// Build the final Alloy Filter, without a block range
+    pub fn build(self) -> Filter {
+        let mut filter = Filter::new().event_signature(Approval::SIGNATURE_HASH);
+        if let Some(token) = self.token_address {
+            filter = filter.address(token);
+        }
+        if let Some(owner) = self.owner {
+            filter = filter.topic1(owner);
+        }
+        if let Some(spender) = self.spender {
+            filter = filter.topic2(spender);
+        }
+        filter
+    }
+}
+
 /// Convenience function to create a Transfer filter for token discovery
 ///
 /// Creates a filter that finds all Transfer events where the recipient is the specified address.
@@ -361,6 +433,25 @@ mod tests {
         assert_eq!(filter.get_to_block(), None);
     }
 
+    #[test]
+    fn test_approval_builder_sets_owner_and_spender_topics() {
+        let token = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let owner = address!("1111111111111111111111111111111111111111");
+        let spender = address!("2222222222222222222222222222222222222222");
+
+        let filter = ApprovalFilterBuilder::new()
+            .with_token(token)
+            .with_owner(owner)
+            .with_spender(spender)
+            .build();
+
+        let topic = |index: usize| filter.topics[index].iter().copied().collect::<Vec<_>>();
+        assert_eq!(topic(0), vec![Approval::SIGNATURE_HASH]);
+        assert_eq!(topic(1), vec![owner.into_word()]);
+        assert_eq!(topic(2), vec![spender.into_word()]);
+        assert_eq!(filter.get_from_block(), None);
+    }
+
     #[test]
     fn test_default_builder() {
         let filter = TransferFilterBuilder::new().build();
@@ -0,0 +1,321 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Directed transfer graphs for flow-of-funds analysis
+//!
+//! A [`TransferGraph`] aggregates ERC-20 Transfer events into edges keyed by
+//! `(from, to, token)`, where each edge carries the total raw amount moved and
+//! the number of transfers. Nodes are the addresses that appear on either side
+//! of an edge.
+//!
+//! Graphs can be exported to Graphviz DOT with [`TransferGraph::to_dot`] or to
+//! JSON with [`TransferGraph::to_json`]. [`TransferGraphBuilder`] scans a block
+//! range and builds a graph in one call.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{SemioscanConfig, TransferGraphBuilder};
+//! use alloy_chains::NamedChain;
+//!
+//! let builder = TransferGraphBuilder::new(provider, SemioscanConfig::default());
+//! let graph = builder
+//!     .build(NamedChain::Base, &[usdc], start_block, end_block)
+//!     .await?;
+//!
+//! std::fs::write("flows.dot", graph.to_dot())?;
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockNumber, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{Filter, Log};
+use alloy_sol_types::SolEvent;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::SemioscanConfig;
+use crate::errors::EventProcessingError;
+use crate::events::definitions::Transfer;
+use crate::events::scanner::EventScanner;
+
+/// Aggregated transfers from one address to another for a single token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferEdge {
+    /// Sender of the transfers
+    pub from: Address,
+    /// Recipient of the transfers
+    pub to: Address,
+    /// ERC-20 token contract
+    pub token: Address,
+    /// Total raw amount transferred (saturates at `U256::MAX`)
+    pub amount: U256,
+    /// Number of Transfer events aggregated into this edge
+    pub transfer_count: u64,
+}
+
+/// Serializable snapshot of a [`TransferGraph`], as produced by [`TransferGraph::to_json`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferGraphExport {
+    /// All addresses appearing in the graph, sorted
+    pub nodes: Vec<Address>,
+    /// All edges, sorted by `(from, to, token)`
+    pub edges: Vec<TransferEdge>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct EdgeTotals {
+    amount: U256,
+    transfer_count: u64,
+}
+
+/// Directed graph of token transfers between addresses
+///
+/// Edges are kept in a sorted map so iteration and exports are deterministic.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferGraph {
+    edges: BTreeMap<(Address, Address, Address), EdgeTotals>,
+}
+
+impl TransferGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single transfer of `amount` of `token` from `from` to `to`
+    pub fn add_transfer(&mut self, from: Address, to: Address, token: Address, amount: U256) {
+        let totals = self.edges.entry((from, to, token)).or_default();
+        totals.amount = totals.amount.saturating_add(amount);
+        totals.transfer_count += 1;
+    }
+
+    /// Decode a Transfer log and add it to the graph
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventProcessingError::DecodeFailed`] if the log is not a valid
+    /// Transfer event.
+    pub fn apply_log(&mut self, log: &Log) -> Result<(), EventProcessingError> {
+        let event = Transfer::decode_log(&log.inner)
+            .map_err(|e| EventProcessingError::decode_failed(format!("Transfer: {e}")))?;
+        self.add_transfer(event.from, event.to, log.address(), event.value);
+        Ok(())
+    }
+
+    /// All edges, sorted by `(from, to, token)`
+    pub fn edges(&self) -> impl Iterator<Item = TransferEdge> + '_ {
+        self.edges
+            .iter()
+            .map(|(&(from, to, token), totals)| TransferEdge {
+                from,
+                to,
+                token,
+                amount: totals.amount,
+                transfer_count: totals.transfer_count,
+            })
+    }
+
+    /// All addresses that sent or received a transfer, sorted
+    pub fn nodes(&self) -> BTreeSet<Address> {
+        self.edges
+            .keys()
+            .flat_map(|&(from, to, _)| [from, to])
+            .collect()
+    }
+
+    /// Edges leaving `address`
+    pub fn outgoing(&self, address: Address) -> impl Iterator<Item = TransferEdge> + '_ {
+        self.edges().filter(move |edge| edge.from == address)
+    }
+
+    /// Edges arriving at `address`
+    pub fn incoming(&self, address: Address) -> impl Iterator<Item = TransferEdge> + '_ {
+        self.edges().filter(move |edge| edge.to == address)
+    }
+
+    /// Number of distinct `(from, to, token)` edges
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Check if the graph has no edges
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Snapshot the graph as sorted node and edge lists
+    pub fn export(&self) -> TransferGraphExport {
+        TransferGraphExport {
+            nodes: self.nodes().into_iter().collect(),
+            edges: self.edges().collect(),
+        }
+    }
+
+    /// Serialize the graph to JSON (see [`TransferGraphExport`] for the layout)
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.export())
+    }
+
+    /// Render the graph in Graphviz DOT format
+    ///
+    /// Each edge is labelled with its token, raw amount and transfer count.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph transfers {\n");
+        for node in self.nodes() {
+            let _ = writeln!(dot, "    \"{node}\";");
+        }
+        for edge in self.edges() {
+            let TransferEdge {
+                from,
+                to,
+                token,
+                amount,
+                transfer_count,
+            } = edge;
+            let _ = writeln!(
+                dot,
+                "    \"{from}\" -> \"{to}\" [label=\"{token}\\n{amount} ({transfer_count} transfers)\"];"
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Scans Transfer events over a block range and builds a [`TransferGraph`]
+pub struct TransferGraphBuilder<P> {
+    provider: P,
+    config: SemioscanConfig,
+}
+
+impl<P: Provider> TransferGraphBuilder<P> {
+    /// Creates a new `TransferGraphBuilder` with the given provider and configuration
+    pub fn new(provider: P, config: SemioscanConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Build a graph from Transfer events in `[from_block, to_block]`
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - Chain to scan (determines chunking and rate limits)
+    /// * `tokens` - Only include transfers of these token contracts; empty means all tokens
+    /// * `from_block` - Starting block number (inclusive)
+    /// * `to_block` - Ending block number (inclusive)
+    ///
+    /// Logs that fail to decode are logged and skipped.
+    pub async fn build(
+        &self,
+        chain: NamedChain,
+        tokens: &[Address],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<TransferGraph, EventProcessingError> {
+        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let logs = scanner
+            .scan(chain, transfer_graph_filter(tokens), from_block, to_block)
+            .await?;
+
+        let mut graph = TransferGraph::new();
+        for log in &logs {
+            if let Err(e) = graph.apply_log(log) {
+                warn!(error = %e, tx_hash = ?log.transaction_hash, "Skipping Transfer log");
+            }
+        }
+
+        info!(
+            chain = %chain,
+            logs = logs.len(),
+            edges = graph.edge_count(),
+            "Built transfer graph"
+        );
+
+        Ok(graph)
+    }
+}
+
+fn transfer_graph_filter(tokens: &[Address]) -> Filter {
+    let filter = Filter::new().event_signature(Transfer::SIGNATURE_HASH);
+    if tokens.is_empty() {
+        filter
+    } else {
+        filter.address(tokens.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const ALICE: Address = address!("1111111111111111111111111111111111111111");
+    const BOB: Address = address!("2222222222222222222222222222222222222222");
+    const TOKEN: Address = address!("3333333333333333333333333333333333333333");
+
+    fn transfer_log(from: Address, to: Address, value: u64) -> Log {
+        let event = Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        };
+        Log {
+            inner: alloy_primitives::Log {
+                address: TOKEN,
+                data: event.encode_log_data(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transfers_aggregate_per_edge() {
+        let mut graph = TransferGraph::new();
+        graph.apply_log(&transfer_log(ALICE, BOB, 10)).unwrap();
+        graph.apply_log(&transfer_log(ALICE, BOB, 5)).unwrap();
+        graph.apply_log(&transfer_log(BOB, ALICE, 1)).unwrap();
+
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.nodes().len(), 2);
+
+        let edge = graph.outgoing(ALICE).next().unwrap();
+        assert_eq!(edge.amount, U256::from(15u64));
+        assert_eq!(edge.transfer_count, 2);
+        assert_eq!(graph.incoming(ALICE).count(), 1);
+    }
+
+    #[test]
+    fn amounts_saturate_instead_of_overflowing() {
+        let mut graph = TransferGraph::new();
+        graph.add_transfer(ALICE, BOB, TOKEN, U256::MAX);
+        graph.add_transfer(ALICE, BOB, TOKEN, U256::from(1u64));
+
+        assert_eq!(graph.edges().next().unwrap().amount, U256::MAX);
+    }
+
+    #[test]
+    fn json_export_round_trips() {
+        let mut graph = TransferGraph::new();
+        graph.add_transfer(ALICE, BOB, TOKEN, U256::from(42u64));
+
+        let json = graph.to_json().unwrap();
+        let export: TransferGraphExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(export, graph.export());
+        assert_eq!(export.nodes, vec![ALICE, BOB]);
+    }
+
+    #[test]
+    fn dot_export_lists_nodes_and_labelled_edges() {
+        let mut graph = TransferGraph::new();
+        graph.add_transfer(ALICE, BOB, TOKEN, U256::from(42u64));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph transfers {"));
+        assert!(dot.contains(&format!("\"{ALICE}\" -> \"{BOB}\"")));
+        assert!(dot.contains("42 (1 transfers)"));
+    }
+}
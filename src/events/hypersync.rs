@@ -0,0 +1,410 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! HyperSync-backed [`LogSource`]
+//!
+//! `eth_getLogs` over months of Transfer, Approval or swap events means
+//! thousands of chunked requests. Envio's HyperSync indexer serves the same
+//! logs in a handful of paginated queries. [`HyperSyncLogSource`] translates
+//! each [`Filter`] (addresses plus up to four topic positions) into a HyperSync
+//! query, so every scanner that accepts `with_log_source` can use it unchanged.
+//!
+//! # Fallback
+//!
+//! HyperSync only indexes some chains and trails the chain head by a few
+//! blocks. With [`with_fallback`](HyperSyncLogSource::with_fallback) set,
+//! blocks past HyperSync's archive height and queries that fail outright are
+//! served by the fallback source, normally an [`RpcLogSource`] on the same
+//! chain. [`HyperSyncLogSource::or_fallback`] picks HyperSync for supported
+//! chains and the fallback for everything else.
+//!
+//! Enabled with the `hypersync` feature.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use semioscan::{EventScanner, HyperSyncLogSource, RpcLogSource, SemioscanConfig};
+//!
+//! let rpc = Arc::new(RpcLogSource::new(provider.clone()));
+//! let logs = HyperSyncLogSource::or_fallback(chain, Some(api_token), rpc);
+//! let scanner = EventScanner::new(provider, SemioscanConfig::default()).with_log_source(logs);
+//! ```
+//!
+//! [`RpcLogSource`]: crate::events::source::RpcLogSource
+
+use std::sync::Arc;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockHash, Bytes, TxHash, B256};
+use alloy_rpc_types::{Filter, Log};
+use alloy_transport::{TransportError, TransportErrorKind};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::events::source::LogSource;
+
+/// Chains HyperSync indexes, served at `https://{chain_id}.hypersync.xyz`
+const SUPPORTED_CHAINS: &[NamedChain] = &[
+    NamedChain::Mainnet,
+    NamedChain::Sepolia,
+    NamedChain::Optimism,
+    NamedChain::Base,
+    NamedChain::Arbitrum,
+    NamedChain::Polygon,
+    NamedChain::BinanceSmartChain,
+    NamedChain::Avalanche,
+    NamedChain::Gnosis,
+    NamedChain::Linea,
+    NamedChain::Scroll,
+    NamedChain::Blast,
+];
+
+/// Log fields requested from HyperSync, all of which map onto [`Log`]
+const LOG_FIELDS: &[&str] = &[
+    "block_number",
+    "block_hash",
+    "transaction_hash",
+    "transaction_index",
+    "log_index",
+    "address",
+    "data",
+    "topic0",
+    "topic1",
+    "topic2",
+    "topic3",
+];
+
+/// [`LogSource`] backed by Envio's HyperSync query API
+#[derive(Clone)]
+pub struct HyperSyncLogSource {
+    client: reqwest::Client,
+    url: String,
+    api_token: Option<String>,
+    fallback: Option<Arc<dyn LogSource>>,
+}
+
+impl std::fmt::Debug for HyperSyncLogSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HyperSyncLogSource")
+            .field("url", &self.url)
+            .field("api_token", &self.api_token.as_ref().map(|_| "<redacted>"))
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+impl HyperSyncLogSource {
+    /// A source querying the HyperSync endpoint at `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into().trim_end_matches('/').to_string(),
+            api_token: None,
+            fallback: None,
+        }
+    }
+
+    /// The public HyperSync endpoint for `chain`, or `None` if it isn't indexed
+    pub fn for_chain(chain: NamedChain) -> Option<Self> {
+        Self::supports(chain).then(|| Self::new(format!("https://{}.hypersync.xyz", chain as u64)))
+    }
+
+    /// Whether HyperSync indexes `chain`
+    pub fn supports(chain: NamedChain) -> bool {
+        SUPPORTED_CHAINS.contains(&chain)
+    }
+
+    /// HyperSync for `chain` falling back to `fallback`, or just `fallback`
+    /// when `chain` isn't indexed
+    pub fn or_fallback(
+        chain: NamedChain,
+        api_token: Option<String>,
+        fallback: Arc<dyn LogSource>,
+    ) -> Arc<dyn LogSource> {
+        match Self::for_chain(chain) {
+            Some(mut source) => {
+                source.api_token = api_token;
+                Arc::new(source.with_fallback(fallback))
+            }
+            None => {
+                debug!(
+                    ?chain,
+                    "HyperSync does not index chain, using fallback log source"
+                );
+                fallback
+            }
+        }
+    }
+
+    /// Send `token` as a bearer token with every query
+    pub fn with_api_token(mut self, token: impl Into<String>) -> Self {
+        self.api_token = Some(token.into());
+        self
+    }
+
+    /// Serve blocks HyperSync hasn't indexed yet, and failed queries, from `source`
+    pub fn with_fallback(mut self, source: Arc<dyn LogSource>) -> Self {
+        self.fallback = Some(source);
+        self
+    }
+
+    async fn query(&self, query: &Query) -> Result<QueryResponse, TransportError> {
+        let mut request = self.client.post(format!("{}/query", self.url)).json(query);
+        if let Some(token) = &self.api_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(TransportErrorKind::custom)?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(TransportErrorKind::custom_str(&format!(
+                "HyperSync query failed with {status}: {body}"
+            )));
+        }
+        response.json().await.map_err(TransportErrorKind::custom)
+    }
+
+    /// Logs for `[from_block, to_block]` from HyperSync, plus the first block
+    /// it couldn't serve (past its archive height), if any
+    async fn get_indexed_logs(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<(Vec<Log>, Option<u64>), TransportError> {
+        let mut query = Query::from_filter(filter, from_block, to_block);
+        let mut logs = Vec::new();
+
+        loop {
+            let response = self.query(&query).await?;
+            logs.extend(
+                response
+                    .data
+                    .into_iter()
+                    .flat_map(|batch| batch.logs)
+                    .map(HyperSyncLog::into_log),
+            );
+
+            let next_block = response.next_block;
+            if next_block > to_block {
+                return Ok((logs, None));
+            }
+            // No progress means HyperSync has nothing past its archive height
+            if next_block <= query.from_block {
+                return Ok((logs, Some(next_block)));
+            }
+            query.from_block = next_block;
+        }
+    }
+}
+
+#[async_trait]
+impl LogSource for HyperSyncLogSource {
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+        let (Some(from_block), Some(to_block)) = (filter.get_from_block(), filter.get_to_block())
+        else {
+            return Err(TransportErrorKind::custom_str(
+                "HyperSync queries need a numeric block range",
+            ));
+        };
+
+        let (mut logs, unserved_from) = match self
+            .get_indexed_logs(filter, from_block, to_block)
+            .await
+        {
+            Ok(result) => result,
+            Err(error) => match &self.fallback {
+                Some(fallback) => {
+                    warn!(%error, from_block, to_block, "HyperSync query failed, using fallback");
+                    return fallback.get_logs(filter).await;
+                }
+                None => return Err(error),
+            },
+        };
+
+        if let Some(unserved_from) = unserved_from {
+            let fallback = self.fallback.as_ref().ok_or_else(|| {
+                TransportErrorKind::custom_str(&format!(
+                    "HyperSync has not indexed blocks {unserved_from}-{to_block} yet"
+                ))
+            })?;
+            debug!(
+                unserved_from,
+                to_block, "Fetching blocks past HyperSync archive height from fallback"
+            );
+            let tail = filter.clone().from_block(unserved_from).to_block(to_block);
+            logs.extend(fallback.get_logs(&tail).await?);
+        }
+
+        Ok(logs)
+    }
+}
+
+/// HyperSync query body
+#[derive(Debug, Serialize)]
+struct Query {
+    from_block: u64,
+    /// Exclusive
+    to_block: u64,
+    logs: Vec<LogSelection>,
+    field_selection: FieldSelection,
+}
+
+#[derive(Debug, Serialize)]
+struct LogSelection {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    address: Vec<Address>,
+    topics: Vec<Vec<B256>>,
+}
+
+#[derive(Debug, Serialize)]
+struct FieldSelection {
+    log: &'static [&'static str],
+}
+
+impl Query {
+    fn from_filter(filter: &Filter, from_block: u64, to_block: u64) -> Self {
+        let mut topics: Vec<Vec<B256>> = filter
+            .topics
+            .iter()
+            .map(|topic| topic.iter().copied().collect())
+            .collect();
+        // Trailing wildcards are implied
+        while topics.last().is_some_and(Vec::is_empty) {
+            topics.pop();
+        }
+        Self {
+            from_block,
+            to_block: to_block + 1,
+            logs: vec![LogSelection {
+                address: filter.address.iter().copied().collect(),
+                topics,
+            }],
+            field_selection: FieldSelection { log: LOG_FIELDS },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    data: Vec<ResponseBatch>,
+    next_block: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseBatch {
+    #[serde(default)]
+    logs: Vec<HyperSyncLog>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HyperSyncLog {
+    block_number: Option<u64>,
+    block_hash: Option<BlockHash>,
+    transaction_hash: Option<TxHash>,
+    transaction_index: Option<u64>,
+    log_index: Option<u64>,
+    address: Address,
+    #[serde(default)]
+    data: Bytes,
+    topic0: Option<B256>,
+    topic1: Option<B256>,
+    topic2: Option<B256>,
+    topic3: Option<B256>,
+}
+
+impl HyperSyncLog {
+    fn into_log(self) -> Log {
+        let topics = [self.topic0, self.topic1, self.topic2, self.topic3]
+            .into_iter()
+            .map_while(|topic| topic)
+            .collect();
+        Log {
+            inner: alloy_primitives::Log::new_unchecked(self.address, topics, self.data),
+            block_hash: self.block_hash,
+            block_number: self.block_number,
+            transaction_hash: self.transaction_hash,
+            transaction_index: self.transaction_index,
+            log_index: self.log_index,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::definitions::Transfer;
+    use alloy_primitives::address;
+    use alloy_sol_types::SolEvent;
+
+    const USDC: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+
+    #[test]
+    fn query_mirrors_filter_with_exclusive_end() {
+        let recipient = B256::left_padding_from(
+            address!("1111111111111111111111111111111111111111").as_slice(),
+        );
+        let filter = Filter::new()
+            .address(USDC)
+            .event_signature(Transfer::SIGNATURE_HASH)
+            .topic2(recipient);
+
+        let query = serde_json::to_value(Query::from_filter(&filter, 100, 199)).unwrap();
+        assert_eq!(query["from_block"], 100);
+        assert_eq!(query["to_block"], 200);
+        let address: Address =
+            serde_json::from_value(query["logs"][0]["address"][0].clone()).unwrap();
+        assert_eq!(address, USDC);
+        let topics = query["logs"][0]["topics"].as_array().unwrap();
+        assert_eq!(topics.len(), 3);
+        assert_eq!(topics[1], serde_json::json!([]));
+    }
+
+    #[test]
+    fn response_logs_convert_to_rpc_logs() {
+        let response: QueryResponse = serde_json::from_value(serde_json::json!({
+            "data": [{
+                "logs": [{
+                    "block_number": 100,
+                    "log_index": 3,
+                    "transaction_index": 1,
+                    "transaction_hash": B256::repeat_byte(0xaa),
+                    "block_hash": B256::repeat_byte(0xbb),
+                    "address": USDC,
+                    "data": "0x01",
+                    "topic0": Transfer::SIGNATURE_HASH,
+                    "topic1": B256::ZERO,
+                    "topic2": null,
+                    "topic3": null
+                }]
+            }],
+            "next_block": 200,
+            "archive_height": 250
+        }))
+        .unwrap();
+        assert_eq!(response.next_block, 200);
+
+        let log = response
+            .data
+            .into_iter()
+            .flat_map(|batch| batch.logs)
+            .map(HyperSyncLog::into_log)
+            .next()
+            .unwrap();
+        assert_eq!(log.address(), USDC);
+        assert_eq!(log.topics(), &[Transfer::SIGNATURE_HASH, B256::ZERO]);
+        assert_eq!(log.block_number, Some(100));
+        assert_eq!(log.log_index, Some(3));
+    }
+
+    #[test]
+    fn unsupported_chains_have_no_endpoint() {
+        assert!(HyperSyncLogSource::for_chain(NamedChain::Base).is_some());
+        assert!(HyperSyncLogSource::for_chain(NamedChain::Sonic).is_none());
+    }
+}
@@ -0,0 +1,383 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache of `eth_getLogs` pages
+//!
+//! Reruns of a report with a different aggregation scan the same chunks and
+//! fetch the same log pages again. A [`CachedLogSource`] in front of another
+//! [`LogSource`] keeps each page under a hash of its filter (block range,
+//! addresses and topics), and serves repeated queries from memory.
+//!
+//! Pages past the finalized block can still change in a reorg, so with a
+//! [`FinalityTracker`] set only pages ending at or before its last finalized
+//! block are kept; the rest are always fetched. Without one every page is
+//! kept, which suits scans of settled history. The cache is bounded by entry
+//! count and by the approximate size of the logs it holds, evicting the least
+//! recently used pages first. It is cheap to clone, and clones share entries.
+//! Registered with a [`ReorgDetector`](crate::ReorgDetector) as a
+//! [`ReorgHook`](crate::ReorgHook), it drops the pages a reorg invalidates.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use semioscan::{CachedLogSource, EventScanner, FinalityTracker, RpcLogSource};
+//!
+//! let logs = CachedLogSource::new(Arc::new(RpcLogSource::new(provider.clone())))
+//!     .with_max_bytes(256 * 1024 * 1024)
+//!     .with_finality_tracker(finality.clone());
+//! let scanner = EventScanner::new(provider, config).with_log_source(Arc::new(logs.clone()));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use alloy_primitives::{keccak256, Address, BlockNumber, B256};
+use alloy_rpc_types::{Filter, Log};
+use alloy_transport::TransportError;
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::cache::finality::FinalityTracker;
+use crate::events::source::LogSource;
+
+/// Hit, miss and eviction counts of a [`CachedLogSource`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogCacheStats {
+    /// Queries served from a cached page
+    pub hits: u64,
+    /// Queries passed to the inner source
+    pub misses: u64,
+    /// Pages fetched but not kept because they reach past the finalized block
+    pub unfinalized: u64,
+    /// Pages dropped to stay within the entry or size bound
+    pub evictions: u64,
+    /// Pages currently cached
+    pub entries: usize,
+    /// Approximate size of the cached logs
+    pub bytes: usize,
+}
+
+#[derive(Debug)]
+struct Page {
+    logs: Arc<Vec<Log>>,
+    to_block: BlockNumber,
+    bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct LogCacheState {
+    pages: HashMap<B256, Page>,
+    /// Access sequence → filter hash, oldest first
+    recency: BTreeMap<u64, B256>,
+    next_sequence: u64,
+    bytes: usize,
+    stats: LogCacheStats,
+}
+
+impl LogCacheState {
+    fn touch(&mut self, key: B256) -> Option<Arc<Vec<Log>>> {
+        let sequence = self.next_sequence;
+        let page = self.pages.get_mut(&key)?;
+        self.recency.remove(&page.last_used);
+        page.last_used = sequence;
+        self.recency.insert(sequence, key);
+        self.next_sequence += 1;
+        Some(Arc::clone(&page.logs))
+    }
+
+    fn remove(&mut self, key: B256) {
+        if let Some(page) = self.pages.remove(&key) {
+            self.recency.remove(&page.last_used);
+            self.bytes -= page.bytes;
+        }
+    }
+}
+
+/// [`LogSource`] that caches the pages of another source
+#[derive(Clone)]
+pub struct CachedLogSource {
+    inner: Arc<dyn LogSource>,
+    state: Arc<Mutex<LogCacheState>>,
+    max_entries: usize,
+    max_bytes: usize,
+    finality: Option<FinalityTracker>,
+}
+
+impl std::fmt::Debug for CachedLogSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedLogSource")
+            .field("max_entries", &self.max_entries)
+            .field("max_bytes", &self.max_bytes)
+            .field("finality", &self.finality.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CachedLogSource {
+    /// Pages kept unless set with [`with_max_entries`](Self::with_max_entries)
+    pub const DEFAULT_MAX_ENTRIES: usize = 4_096;
+
+    /// Approximate bytes of logs kept unless set with
+    /// [`with_max_bytes`](Self::with_max_bytes)
+    pub const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+    /// Cache the pages `inner` returns
+    pub fn new(inner: Arc<dyn LogSource>) -> Self {
+        Self {
+            inner,
+            state: Arc::default(),
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            finality: None,
+        }
+    }
+
+    /// Keep at most `max_entries` pages (at least one)
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Keep at most about `max_bytes` of logs
+    ///
+    /// A single page larger than this is returned but not kept.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Only keep pages ending at or before `finality`'s last finalized block
+    ///
+    /// The tracker isn't refreshed here; share one that the calculators
+    /// refresh, or refresh it yourself.
+    pub fn with_finality_tracker(mut self, finality: FinalityTracker) -> Self {
+        self.finality = Some(finality);
+        self
+    }
+
+    /// Hit, miss and eviction counts so far
+    pub fn stats(&self) -> LogCacheStats {
+        let state = self.state.lock().unwrap();
+        LogCacheStats {
+            entries: state.pages.len(),
+            bytes: state.bytes,
+            ..state.stats
+        }
+    }
+
+    /// Drop every page reaching `first_invalid_block` or later, after a reorg
+    pub fn clear_from_block(&self, first_invalid_block: BlockNumber) {
+        let mut state = self.state.lock().unwrap();
+        let stale: Vec<B256> = state
+            .pages
+            .iter()
+            .filter(|(_, page)| page.to_block >= first_invalid_block)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in stale {
+            state.remove(key);
+        }
+    }
+
+    /// Drop every page
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.pages.clear();
+        state.recency.clear();
+        state.bytes = 0;
+    }
+
+    fn insert(&self, key: B256, to_block: BlockNumber, logs: Arc<Vec<Log>>) {
+        let bytes = logs.iter().map(log_size).sum::<usize>();
+        if bytes > self.max_bytes {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.remove(key);
+        while state.pages.len() >= self.max_entries || state.bytes + bytes > self.max_bytes {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            if let Some(page) = state.pages.remove(&oldest) {
+                state.bytes -= page.bytes;
+                state.stats.evictions += 1;
+            }
+        }
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        state.bytes += bytes;
+        state.recency.insert(sequence, key);
+        state.pages.insert(
+            key,
+            Page {
+                logs,
+                to_block,
+                bytes,
+                last_used: sequence,
+            },
+        );
+    }
+}
+
+#[async_trait]
+impl LogSource for CachedLogSource {
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+        // Filters by block hash, or by tag, can't be keyed by a stable range
+        let (Some(from_block), Some(to_block)) = (filter.get_from_block(), filter.get_to_block())
+        else {
+            return self.inner.get_logs(filter).await;
+        };
+
+        let key = filter_hash(filter, from_block, to_block);
+        let cached = {
+            let mut state = self.state.lock().unwrap();
+            let cached = state.touch(key);
+            match cached {
+                Some(_) => state.stats.hits += 1,
+                None => state.stats.misses += 1,
+            }
+            cached
+        };
+        if let Some(logs) = cached {
+            debug!(
+                from_block,
+                to_block,
+                logs = logs.len(),
+                "Log page cache hit"
+            );
+            return Ok(logs.as_ref().clone());
+        }
+
+        let logs = self.inner.get_logs(filter).await?;
+        let settled = self
+            .finality
+            .as_ref()
+            .is_none_or(|finality| finality.is_final(to_block));
+        if settled {
+            self.insert(key, to_block, Arc::new(logs.clone()));
+        } else {
+            self.state.lock().unwrap().stats.unfinalized += 1;
+        }
+        Ok(logs)
+    }
+}
+
+/// Hash of the parts of `filter` that select logs, independent of the order
+/// its addresses and topics were added in
+fn filter_hash(filter: &Filter, from_block: BlockNumber, to_block: BlockNumber) -> B256 {
+    let mut buf = Vec::with_capacity(256);
+    buf.extend_from_slice(&from_block.to_be_bytes());
+    buf.extend_from_slice(&to_block.to_be_bytes());
+
+    let mut addresses: Vec<Address> = filter.address.iter().copied().collect();
+    addresses.sort_unstable();
+    buf.extend_from_slice(&(addresses.len() as u64).to_be_bytes());
+    for address in &addresses {
+        buf.extend_from_slice(address.as_slice());
+    }
+
+    for topic in &filter.topics {
+        let mut values: Vec<B256> = topic.iter().copied().collect();
+        values.sort_unstable();
+        buf.extend_from_slice(&(values.len() as u64).to_be_bytes());
+        for value in &values {
+            buf.extend_from_slice(value.as_slice());
+        }
+    }
+    keccak256(&buf)
+}
+
+/// Approximate heap and inline size of `log`
+fn log_size(log: &Log) -> usize {
+    std::mem::size_of::<Log>() + log.data().data.len() + log.topics().len() * 32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, LogData};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns one log per block of the range, counting calls
+    #[derive(Default)]
+    struct CountingSource {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LogSource for CountingSource {
+        async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let from = filter.get_from_block().unwrap();
+            let to = filter.get_to_block().unwrap();
+            Ok((from..=to)
+                .map(|block| Log {
+                    inner: alloy_primitives::Log {
+                        address: Address::ZERO,
+                        data: LogData::new_unchecked(Vec::new(), Bytes::from(vec![0; 64])),
+                    },
+                    block_number: Some(block),
+                    ..Default::default()
+                })
+                .collect())
+        }
+    }
+
+    fn filter(from: BlockNumber, to: BlockNumber) -> Filter {
+        Filter::new()
+            .from_block(from)
+            .to_block(to)
+            .address(vec![Address::repeat_byte(2), Address::repeat_byte(1)])
+    }
+
+    #[tokio::test]
+    async fn test_repeated_pages_are_served_from_cache() {
+        let inner = Arc::new(CountingSource::default());
+        let cache = CachedLogSource::new(inner.clone()).with_max_entries(2);
+
+        let first = cache.get_logs(&filter(1, 10)).await.unwrap();
+        assert_eq!(first.len(), 10);
+        // Same page with its addresses in another order
+        let reordered = Filter::new()
+            .from_block(1)
+            .to_block(10)
+            .address(vec![Address::repeat_byte(1), Address::repeat_byte(2)]);
+        assert_eq!(cache.get_logs(&reordered).await.unwrap(), first);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        // A third page evicts the least recently used one
+        cache.get_logs(&filter(11, 20)).await.unwrap();
+        cache.get_logs(&filter(21, 30)).await.unwrap();
+        cache.get_logs(&filter(1, 10)).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 2);
+
+        cache.clear_from_block(25);
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unfinalized_and_oversized_pages_are_not_kept() {
+        let inner = Arc::new(CountingSource::default());
+        // A tracker that was never refreshed treats nothing as final
+        let cache =
+            CachedLogSource::new(inner.clone()).with_finality_tracker(FinalityTracker::new());
+        cache.get_logs(&filter(1, 10)).await.unwrap();
+        cache.get_logs(&filter(1, 10)).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.stats().unfinalized, 2);
+
+        let tiny = CachedLogSource::new(inner.clone()).with_max_bytes(100);
+        tiny.get_logs(&filter(1, 10)).await.unwrap();
+        assert_eq!(tiny.stats().entries, 0);
+        assert_eq!(tiny.stats().bytes, 0);
+    }
+}
@@ -6,33 +6,82 @@
 //!
 //! This module handles:
 //! - Transfer and Approval event definitions
-//! - Transfer amount extraction and accumulation
-//! - Token discovery via event scanning
+//! - Transfer amount extraction and accumulation, with fee-on-transfer detection
+//! - Token and counterparty discovery via event scanning
 //! - Semantic filter builders for type-safe event filtering
+//! - Event queries naming Transfer and Approval indexed parameters
 //! - Generic event scanning with chunking and rate limiting
+//! - Pluggable log backends behind the [`LogSource`] trait, including HyperSync
+//!   (requires `hypersync` feature), and a bounded, finality-aware cache of
+//!   log pages in front of any of them
+//! - L1 ↔ L2 bridge leg decoding and flow pairing (OP Stack, Arbitrum)
+//! - Directed transfer graphs with DOT/JSON export
+//! - Outstanding allowance tracking from replayed Approval events
+//! - Safe multisig executions with their attributed transfers and gas
+//! - A registry of custom event decoders for contracts semioscan doesn't know about
+//! - Bounded decode → enrich → sink pipelines over log streams, with backpressure
 //! - Real-time event streaming via WebSocket subscriptions (requires `ws` feature)
 
-mod chunked;
+pub mod approvals;
+pub mod bridge;
+pub(crate) mod chunked;
 pub mod definitions;
 pub mod discovery;
 pub mod filter;
+pub mod graph;
+#[cfg(feature = "hypersync")]
+pub mod hypersync;
+pub mod log_cache;
+pub mod pipeline;
+pub mod query;
 #[cfg(feature = "ws")]
 pub mod realtime;
+pub mod registry;
+pub mod safe;
 pub mod scanner;
+pub mod source;
+pub mod transfer_tax;
 pub mod transfers;
 
 // Re-export public types
+pub use approvals::{AllowanceKey, AllowanceState, AllowanceTracker, ApprovalAnalyzer};
+pub use bridge::{
+    arbitrum_retryable_leg, bridge_event_topics, extract_arbitrum_legs, extract_op_stack_legs,
+    BridgeDirection, BridgeFlow, BridgeFlowCalculator, BridgeFlows, BridgeLeg, BridgeLegKind,
+    BridgeLegScanner, BridgeProtocol,
+};
 pub use chunked::fetch_logs_chunked;
-pub use definitions::{Approval, Transfer};
-pub use discovery::{extract_transferred_to_tokens, extract_transferred_to_tokens_with_config};
-pub use transfers::{AmountCalculator, AmountResult};
+pub use definitions::{Approval, Deposit as WethDeposit, Transfer, Withdrawal as WethWithdrawal};
+pub use discovery::{
+    discover_recipients, discover_recipients_with_config, extract_transferred_to_tokens,
+    extract_transferred_to_tokens_with_config,
+};
+pub use graph::{TransferEdge, TransferGraph, TransferGraphBuilder, TransferGraphExport};
+#[cfg(feature = "hypersync")]
+pub use hypersync::HyperSyncLogSource;
+pub use log_cache::{CachedLogSource, LogCacheStats};
+pub use pipeline::{
+    Enricher, EventSink, Passthrough, Pipeline, PipelineHandle, PipelineInput, PipelineStats,
+};
+pub use query::EventQuery;
+pub use registry::{DecodedEvent, EventDecoderFn, EventRegistry};
+pub use safe::{
+    extract_safe_executions, ExecutionFailure, ExecutionSuccess, SafeExecution,
+    SafeExecutionScanner, SafeTransfer,
+};
+pub use source::{LogSource, RpcLogSource};
+pub use transfer_tax::{TransferTax, TransferTaxMode};
+pub use transfers::{wrapped_native_as_transfer, AmountCalculator, AmountResult};
 
 // Public API exports for external consumers (not used internally, which is expected for a library)
 // These are tested in filter::tests::integration module
 #[allow(unused_imports)]
-pub use filter::{transfer_filter_from_to, transfer_filter_to_recipient, TransferFilterBuilder};
+pub use filter::{
+    transfer_filter_from_to, transfer_filter_to_recipient, ApprovalFilterBuilder,
+    TransferFilterBuilder,
+};
 #[cfg(feature = "ws")]
 #[allow(unused_imports)]
 pub use realtime::RealtimeEventScanner;
 #[allow(unused_imports)]
-pub use scanner::EventScanner;
+pub use scanner::{DecodedScan, EventScanner};
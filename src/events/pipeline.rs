@@ -0,0 +1,508 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bounded, staged processing of log streams
+//!
+//! A [`Pipeline`] runs four stages as separate tasks: the source stream of
+//! logs, decoding through an [`EventRegistry`], an [`Enricher`], and an
+//! [`EventSink`]. Stages are joined by bounded channels, so when the sink
+//! falls behind the channels fill up and each stage waits for room before
+//! taking more input. A slow sink then slows the source down instead of
+//! letting queued events grow without limit.
+//!
+//! Logs no decoder is registered for are dropped, as are logs that fail to
+//! decode or enrich; all three are counted in [`PipelineStats`]. A sink
+//! error stops the pipeline and is returned by [`PipelineHandle::join`].
+//!
+//! Backpressure can't slow down a live subscription. When the pipeline
+//! falls far enough behind one, the subscription's buffer overflows and its
+//! oldest logs are lost. A source that knows it skipped logs reports them
+//! with [`PipelineInput::Lagged`], and they are counted in
+//! [`PipelineStats::lagged`]; `RealtimeEventScanner::spawn_pipeline` (`ws`
+//! feature) does. A plain `subscription.into_stream()` skips them silently,
+//! so don't feed one to a pipeline directly.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{EventRegistry, Pipeline};
+//!
+//! let (sink, mut events) = tokio::sync::mpsc::channel(64);
+//! let pipeline = Pipeline::new(EventRegistry::new().with_erc20_events()).with_capacity(256);
+//! let handle = scanner.spawn_pipeline(filter, pipeline, sink).await?;
+//!
+//! while let Some(event) = events.recv().await {
+//!     println!("{} in block {:?}", event.name, event.block_number);
+//! }
+//! println!("{:?}", handle.stats());
+//! ```
+
+use std::pin::pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use alloy_rpc_types::Log;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::errors::{EventProcessingError, SinkError};
+use crate::events::registry::{DecodedEvent, EventRegistry};
+
+/// Turns decoded events into the records a pipeline's sink receives
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{DecodedEvent, Enricher, EventProcessingError};
+///
+/// struct WithTimestamp(HeaderCache);
+///
+/// #[async_trait::async_trait]
+/// impl Enricher for WithTimestamp {
+///     type Output = (DecodedEvent, Option<u64>);
+///
+///     async fn enrich(&self, event: DecodedEvent) -> Result<Self::Output, EventProcessingError> {
+///         let timestamp = event
+///             .block_number
+///             .and_then(|block| self.0.get(block))
+///             .map(|header| header.timestamp);
+///         Ok((event, timestamp))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait Enricher: Send + Sync + 'static {
+    /// Record passed on to the sink
+    type Output: Send + 'static;
+
+    /// Enrich one event, or fail to drop it
+    async fn enrich(&self, event: DecodedEvent) -> Result<Self::Output, EventProcessingError>;
+}
+
+/// Enricher passing decoded events through unchanged
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Passthrough;
+
+#[async_trait]
+impl Enricher for Passthrough {
+    type Output = DecodedEvent;
+
+    async fn enrich(&self, event: DecodedEvent) -> Result<DecodedEvent, EventProcessingError> {
+        Ok(event)
+    }
+}
+
+/// Receives the records at the end of a pipeline, one at a time
+///
+/// The pipeline doesn't hand over the next record until `write` returns, so
+/// a slow sink applies backpressure to every earlier stage.
+#[async_trait]
+pub trait EventSink<T: Send + 'static>: Send + Sync + 'static {
+    /// Store one record
+    async fn write(&self, record: T) -> Result<(), SinkError>;
+}
+
+#[async_trait]
+impl<T: Send + 'static> EventSink<T> for mpsc::Sender<T> {
+    async fn write(&self, record: T) -> Result<(), SinkError> {
+        self.send(record)
+            .await
+            .map_err(|_| SinkError::other("pipeline output receiver dropped"))
+    }
+}
+
+/// One item of a pipeline's source
+///
+/// Streams of plain [`Log`]s convert into this, so any log stream can feed a
+/// pipeline.
+#[derive(Debug, Clone)]
+pub enum PipelineInput {
+    /// A log to decode
+    Log(Log),
+    /// The source skipped this many logs, as a live subscription does when
+    /// the pipeline falls behind it
+    Lagged(u64),
+}
+
+impl From<Log> for PipelineInput {
+    fn from(log: Log) -> Self {
+        PipelineInput::Log(log)
+    }
+}
+
+/// Counts of what a pipeline did with its input so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineStats {
+    /// Logs taken from the source
+    pub received: u64,
+    /// Logs decoded by a registered decoder
+    pub decoded: u64,
+    /// Logs dropped because no decoder is registered for their `topic0`
+    pub unregistered: u64,
+    /// Logs dropped because their decoder failed
+    pub decode_failures: u64,
+    /// Events enriched
+    pub enriched: u64,
+    /// Events dropped because enrichment failed
+    pub enrich_failures: u64,
+    /// Records accepted by the sink
+    pub written: u64,
+    /// Times a stage found the next stage's channel full and had to wait
+    pub backpressure_waits: u64,
+    /// Logs the source reported skipping with [`PipelineInput::Lagged`],
+    /// which never reached the pipeline
+    pub lagged: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    received: AtomicU64,
+    decoded: AtomicU64,
+    unregistered: AtomicU64,
+    decode_failures: AtomicU64,
+    enriched: AtomicU64,
+    enrich_failures: AtomicU64,
+    written: AtomicU64,
+    backpressure_waits: AtomicU64,
+    lagged: AtomicU64,
+}
+
+impl Counters {
+    fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> PipelineStats {
+        PipelineStats {
+            received: self.received.load(Ordering::Relaxed),
+            decoded: self.decoded.load(Ordering::Relaxed),
+            unregistered: self.unregistered.load(Ordering::Relaxed),
+            decode_failures: self.decode_failures.load(Ordering::Relaxed),
+            enriched: self.enriched.load(Ordering::Relaxed),
+            enrich_failures: self.enrich_failures.load(Ordering::Relaxed),
+            written: self.written.load(Ordering::Relaxed),
+            backpressure_waits: self.backpressure_waits.load(Ordering::Relaxed),
+            lagged: self.lagged.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Send `item` downstream, waiting for room if the channel is full
+    ///
+    /// Returns `false` once the downstream stage has stopped.
+    async fn forward<T>(&self, sender: &mpsc::Sender<T>, item: T) -> bool {
+        match sender.try_send(item) {
+            Ok(()) => true,
+            Err(TrySendError::Full(item)) => {
+                Self::increment(&self.backpressure_waits);
+                sender.send(item).await.is_ok()
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
+/// Decodes, enriches and stores a stream of logs in bounded stages
+pub struct Pipeline<E = Passthrough> {
+    registry: EventRegistry,
+    enricher: E,
+    capacity: usize,
+}
+
+impl Pipeline {
+    /// Items buffered between two stages unless set with
+    /// [`with_capacity`](Self::with_capacity)
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Decode logs with `registry`, passing decoded events to the sink as is
+    pub fn new(registry: EventRegistry) -> Self {
+        Self {
+            registry,
+            enricher: Passthrough,
+            capacity: Self::DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl<E: Enricher> Pipeline<E> {
+    /// Buffer at most `capacity` items between two stages (at least one)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Enrich decoded events with `enricher` before they reach the sink
+    pub fn with_enricher<F: Enricher>(self, enricher: F) -> Pipeline<F> {
+        Pipeline {
+            registry: self.registry,
+            enricher,
+            capacity: self.capacity,
+        }
+    }
+
+    /// Run the pipeline from `source` to `sink` in the background
+    ///
+    /// `source` yields [`Log`]s, or [`PipelineInput`]s to also report logs
+    /// it skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a tokio runtime.
+    pub fn spawn<S, K>(self, source: S, sink: K) -> PipelineHandle
+    where
+        S: Stream + Send + 'static,
+        S::Item: Into<PipelineInput>,
+        K: EventSink<E::Output>,
+    {
+        let counters = Arc::new(Counters::default());
+        let (log_tx, mut log_rx) = mpsc::channel::<Log>(self.capacity);
+        let (event_tx, mut event_rx) = mpsc::channel::<DecodedEvent>(self.capacity);
+        let (record_tx, mut record_rx) = mpsc::channel::<E::Output>(self.capacity);
+
+        let source_task = tokio::spawn({
+            let counters = Arc::clone(&counters);
+            async move {
+                let mut source = pin!(source);
+                while let Some(item) = source.next().await {
+                    let item: PipelineInput = item.into();
+                    let log = match item {
+                        PipelineInput::Log(log) => log,
+                        PipelineInput::Lagged(skipped) => {
+                            counters.lagged.fetch_add(skipped, Ordering::Relaxed);
+                            warn!(skipped, "Pipeline fell behind its source, logs were lost");
+                            continue;
+                        }
+                    };
+                    Counters::increment(&counters.received);
+                    if !counters.forward(&log_tx, log).await {
+                        break;
+                    }
+                }
+                debug!("Pipeline source finished");
+            }
+        });
+
+        let registry = self.registry;
+        let decode_task = tokio::spawn({
+            let counters = Arc::clone(&counters);
+            async move {
+                while let Some(log) = log_rx.recv().await {
+                    match registry.decode(&log) {
+                        None => Counters::increment(&counters.unregistered),
+                        Some(Err(e)) => {
+                            Counters::increment(&counters.decode_failures);
+                            debug!(error = %e, "Dropping log that failed to decode");
+                        }
+                        Some(Ok(event)) => {
+                            Counters::increment(&counters.decoded);
+                            if !counters.forward(&event_tx, event).await {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let enricher = self.enricher;
+        let enrich_task = tokio::spawn({
+            let counters = Arc::clone(&counters);
+            async move {
+                while let Some(event) = event_rx.recv().await {
+                    match enricher.enrich(event).await {
+                        Ok(record) => {
+                            Counters::increment(&counters.enriched);
+                            if !counters.forward(&record_tx, record).await {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            Counters::increment(&counters.enrich_failures);
+                            debug!(error = %e, "Dropping event that failed to enrich");
+                        }
+                    }
+                }
+            }
+        });
+
+        let sink_task = tokio::spawn({
+            let counters = Arc::clone(&counters);
+            async move {
+                while let Some(record) = record_rx.recv().await {
+                    if let Err(e) = sink.write(record).await {
+                        warn!(error = %e, "Pipeline sink failed, stopping");
+                        return Err(e);
+                    }
+                    Counters::increment(&counters.written);
+                }
+                Ok(())
+            }
+        });
+
+        PipelineHandle {
+            counters,
+            stages: vec![source_task, decode_task, enrich_task],
+            sink: Some(sink_task),
+        }
+    }
+}
+
+/// Handle to a pipeline started by [`Pipeline::spawn`]
+///
+/// Every stage stops when the handle is dropped or [`stop`](Self::stop) is
+/// called.
+#[derive(Debug)]
+pub struct PipelineHandle {
+    counters: Arc<Counters>,
+    stages: Vec<JoinHandle<()>>,
+    sink: Option<JoinHandle<Result<(), SinkError>>>,
+}
+
+impl PipelineHandle {
+    /// Counts of what the pipeline did so far
+    pub fn stats(&self) -> PipelineStats {
+        self.counters.snapshot()
+    }
+
+    /// Wait until the source ends and every record it produced was written
+    ///
+    /// Never returns for an endless source, such as a live subscription,
+    /// unless the sink fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the sink's error if a write failed, which stops the pipeline.
+    pub async fn join(mut self) -> Result<PipelineStats, SinkError> {
+        if let Some(sink) = self.sink.take() {
+            sink.await.map_err(SinkError::other)??;
+        }
+        for stage in std::mem::take(&mut self.stages) {
+            // Upstream stages have finished once the sink has seen them close
+            let _ = stage.await;
+        }
+        Ok(self.stats())
+    }
+
+    /// Stop every stage
+    pub fn stop(self) {
+        // Dropping the handle aborts the tasks
+    }
+
+    /// Whether every stage has stopped
+    pub fn is_finished(&self) -> bool {
+        self.stages.iter().all(JoinHandle::is_finished)
+            && self.sink.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+}
+
+impl Drop for PipelineHandle {
+    fn drop(&mut self) {
+        for stage in &self.stages {
+            stage.abort();
+        }
+        if let Some(sink) = &self.sink {
+            sink.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_primitives::{Address, LogData, B256, U256};
+    use alloy_sol_types::SolEvent;
+
+    use crate::events::definitions::Transfer;
+
+    fn transfer_log(value: u64) -> Log {
+        let event = Transfer {
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            value: U256::from(value),
+        };
+        Log {
+            inner: alloy_primitives::Log {
+                address: Address::repeat_byte(3),
+                data: event.encode_log_data(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn unknown_log() -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: Address::repeat_byte(3),
+                data: LogData::new_unchecked(vec![B256::repeat_byte(9)], Default::default()),
+            },
+            ..Default::default()
+        }
+    }
+
+    struct Failing;
+
+    #[async_trait]
+    impl EventSink<DecodedEvent> for Failing {
+        async fn write(&self, _record: DecodedEvent) -> Result<(), SinkError> {
+            Err(SinkError::other("disk full"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decodes_registered_logs_in_order() {
+        let logs = vec![transfer_log(1), unknown_log(), transfer_log(2)];
+        let (sink, mut output) = mpsc::channel(1);
+        let handle = Pipeline::new(EventRegistry::new().with_erc20_events())
+            .with_capacity(1)
+            .spawn(futures::stream::iter(logs), sink);
+
+        let mut values = Vec::new();
+        while let Some(event) = output.recv().await {
+            values.push(event.field("value").cloned());
+        }
+        let stats = handle.join().await.unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                Some(DynSolValue::Uint(U256::from(1u64), 256)),
+                Some(DynSolValue::Uint(U256::from(2u64), 256)),
+            ]
+        );
+        assert_eq!(stats.received, 3);
+        assert_eq!(stats.decoded, 2);
+        assert_eq!(stats.unregistered, 1);
+        assert_eq!(stats.written, 2);
+    }
+
+    #[tokio::test]
+    async fn test_lagged_source_items_are_counted() {
+        let input = vec![
+            PipelineInput::Log(transfer_log(1)),
+            PipelineInput::Lagged(5),
+            PipelineInput::Log(transfer_log(2)),
+        ];
+        let (sink, mut output) = mpsc::channel(4);
+        let handle = Pipeline::new(EventRegistry::new().with_erc20_events())
+            .spawn(futures::stream::iter(input), sink);
+
+        while output.recv().await.is_some() {}
+        let stats = handle.join().await.unwrap();
+
+        assert_eq!(stats.received, 2);
+        assert_eq!(stats.lagged, 5);
+        assert_eq!(stats.written, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sink_failure_stops_the_pipeline() {
+        let handle = Pipeline::new(EventRegistry::new().with_erc20_events())
+            .spawn(futures::stream::iter(vec![transfer_log(1)]), Failing);
+
+        assert!(matches!(handle.join().await, Err(SinkError::Other(_))));
+    }
+}
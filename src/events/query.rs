@@ -0,0 +1,180 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Event filters with their indexed parameters named
+//!
+//! ERC-20 `Transfer` and `Approval` events both index two addresses, but
+//! `topic1` is the sender of a transfer and the owner of an approval.
+//! [`EventQuery`] holds either event's filter builder, which names them
+//! ([`TransferFilterBuilder::with_sender`] and
+//! [`with_recipient`](TransferFilterBuilder::with_recipient),
+//! [`ApprovalFilterBuilder::with_owner`] and
+//! [`with_spender`](ApprovalFilterBuilder::with_spender)), so APIs can take
+//! either kind of event and still compile it to the right [`Filter`].
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::EventQuery;
+//!
+//! // Transfers of USDC into a router, from anyone
+//! let logs = scanner
+//!     .scan_query(chain, EventQuery::transfers().with_token(usdc).with_recipient(router), start, end)
+//!     .await?;
+//!
+//! // Gas spent by an owner approving a spender
+//! let gas = calculator
+//!     .calculate_gas_cost_for_query(
+//!         chain,
+//!         EventQuery::approvals().with_token(usdc).with_owner(owner).with_spender(spender),
+//!         start,
+//!         end,
+//!     )
+//!     .await?;
+//! ```
+
+use alloy_primitives::Address;
+use alloy_rpc_types::Filter;
+
+use crate::events::filter::{ApprovalFilterBuilder, TransferFilterBuilder};
+use crate::gas::EventType;
+
+/// Events of one kind, filtered by token and named indexed parameters
+///
+/// Start from [`EventQuery::transfers`] or [`EventQuery::approvals`]; the
+/// returned builder converts into an `EventQuery`, so APIs taking
+/// `impl Into<EventQuery>` accept it directly. Unset parameters match any
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventQuery {
+    /// ERC-20 `Transfer` events
+    Transfer(TransferFilterBuilder),
+    /// ERC-20 `Approval` events
+    Approval(ApprovalFilterBuilder),
+}
+
+impl EventQuery {
+    /// A query matching every `Transfer` event
+    pub fn transfers() -> TransferFilterBuilder {
+        TransferFilterBuilder::new()
+    }
+
+    /// A query matching every `Approval` event
+    pub fn approvals() -> ApprovalFilterBuilder {
+        ApprovalFilterBuilder::new()
+    }
+
+    /// Kind of event matched
+    pub fn event_type(&self) -> EventType {
+        match self {
+            EventQuery::Transfer(_) => EventType::Transfer,
+            EventQuery::Approval(_) => EventType::Approval,
+        }
+    }
+
+    /// Token contract the events must come from, if any
+    pub fn token(&self) -> Option<Address> {
+        match self {
+            EventQuery::Transfer(builder) => builder.token_address,
+            EventQuery::Approval(builder) => builder.token_address,
+        }
+    }
+
+    /// The first and second indexed addresses (`topic1` and `topic2`)
+    pub(crate) fn indexed(&self) -> (Option<Address>, Option<Address>) {
+        match self {
+            EventQuery::Transfer(builder) => (builder.from_address, builder.to_address),
+            EventQuery::Approval(builder) => (builder.owner, builder.spender),
+        }
+    }
+
+    /// A query for `event_type` with both indexed addresses, in topic order
+    pub(crate) fn from_topics(
+        event_type: EventType,
+        token: Address,
+        topic1: Address,
+        topic2: Address,
+    ) -> Self {
+        match event_type {
+            EventType::Transfer => Self::transfers()
+                .with_token(token)
+                .with_sender(topic1)
+                .with_recipient(topic2)
+                .into(),
+            EventType::Approval => Self::approvals()
+                .with_token(token)
+                .with_owner(topic1)
+                .with_spender(topic2)
+                .into(),
+        }
+    }
+
+    /// Compile to a log filter, without a block range
+    pub fn to_filter(&self) -> Filter {
+        match self {
+            EventQuery::Transfer(builder) => builder.build(),
+            EventQuery::Approval(builder) => builder.build(),
+        }
+    }
+}
+
+impl From<TransferFilterBuilder> for EventQuery {
+    fn from(builder: TransferFilterBuilder) -> Self {
+        EventQuery::Transfer(builder)
+    }
+}
+
+impl From<ApprovalFilterBuilder> for EventQuery {
+    fn from(builder: ApprovalFilterBuilder) -> Self {
+        EventQuery::Approval(builder)
+    }
+}
+
+impl From<EventQuery> for Filter {
+    fn from(query: EventQuery) -> Self {
+        query.to_filter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_sol_types::SolEvent;
+
+    use crate::events::definitions::{Approval, Transfer};
+
+    fn topic(filter: &Filter, index: usize) -> Vec<B256> {
+        filter.topics[index].iter().copied().collect()
+    }
+
+    #[test]
+    fn test_named_parameters_compile_to_their_topics() {
+        let (owner, spender) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let approvals = EventQuery::from(EventQuery::approvals().with_spender(spender)).to_filter();
+        assert_eq!(topic(&approvals, 0), vec![Approval::SIGNATURE_HASH]);
+        assert!(approvals.topics[1].is_empty());
+        assert_eq!(topic(&approvals, 2), vec![spender.into_word()]);
+
+        let transfers = EventQuery::from(EventQuery::transfers().with_sender(owner)).to_filter();
+        assert_eq!(topic(&transfers, 0), vec![Transfer::SIGNATURE_HASH]);
+        assert_eq!(topic(&transfers, 1), vec![owner.into_word()]);
+        assert!(transfers.topics[2].is_empty());
+
+        let token = Address::repeat_byte(3);
+        let query = EventQuery::from_topics(EventType::Approval, token, owner, spender);
+        assert_eq!(
+            query,
+            EventQuery::Approval(
+                ApprovalFilterBuilder::new()
+                    .with_token(token)
+                    .with_owner(owner)
+                    .with_spender(spender)
+            )
+        );
+        assert_eq!(query.token(), Some(token));
+        assert_eq!(query.indexed(), (Some(owner), Some(spender)));
+    }
+}
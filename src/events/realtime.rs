@@ -13,6 +13,10 @@
 //! - **`EventScanner`**: Historical data, batch processing, guaranteed delivery
 //! - **`RealtimeEventScanner`**: Live monitoring, real-time alerts, low latency
 //!
+//! To process a live subscription without unbounded buffering, hand it to a
+//! [`Pipeline`] with
+//! [`spawn_pipeline`](RealtimeEventScanner::spawn_pipeline).
+//!
 //! # Provider Setup
 //!
 //! The `RealtimeEventScanner` requires a WebSocket-connected provider:
@@ -69,9 +73,11 @@ use alloy_provider::Provider;
 use alloy_rpc_types::{BlockNumberOrTag, Filter, Header, Log};
 use futures::stream::{Stream, StreamExt};
 use std::pin::Pin;
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, info};
 
 use crate::errors::{EventProcessingError, RpcError};
+use crate::events::pipeline::{Enricher, EventSink, Pipeline, PipelineHandle, PipelineInput};
 
 /// Real-time event scanner using WebSocket subscriptions.
 ///
@@ -325,6 +331,70 @@ where
         Ok(Box::pin(combined))
     }
 
+    /// Subscribe to logs matching a filter and feed them through `pipeline`.
+    ///
+    /// Logs are decoded, enriched and written to `sink` in background tasks
+    /// joined by bounded channels, so a slow sink holds back the subscription
+    /// instead of buffering without limit. The subscription stays open until
+    /// the returned handle is dropped or the sink fails.
+    ///
+    /// The node keeps pushing logs regardless, so a pipeline that stays
+    /// behind long enough overflows the subscription's buffer and loses its
+    /// oldest logs. They are counted in [`PipelineStats::lagged`](crate::PipelineStats::lagged)
+    /// and logged as a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subscription can't be established.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{EventRegistry, Pipeline};
+    ///
+    /// let scanner = RealtimeEventScanner::new(ws_provider);
+    /// let pipeline = Pipeline::new(EventRegistry::new().with_erc20_events());
+    /// let handle = scanner.spawn_pipeline(filter, pipeline, my_sink).await?;
+    ///
+    /// // Later
+    /// println!("{:?}", handle.stats());
+    /// ```
+    pub async fn spawn_pipeline<E, K>(
+        &self,
+        filter: Filter,
+        pipeline: Pipeline<E>,
+        sink: K,
+    ) -> Result<PipelineHandle, EventProcessingError>
+    where
+        E: Enricher,
+        K: EventSink<E::Output>,
+    {
+        info!(
+            address = ?filter.address,
+            topics = ?filter.topics,
+            "Subscribing to logs for a pipeline"
+        );
+
+        let subscription = self
+            .provider
+            .subscribe_logs(&filter)
+            .await
+            .map_err(|e| EventProcessingError::Rpc(RpcError::subscription_failed("logs", e)))?;
+
+        // Unlike `into_stream`, report the logs the subscription drops when
+        // the pipeline falls behind, so they're counted in its stats
+        let source = futures::stream::unfold(subscription, |mut subscription| async move {
+            match subscription.recv().await {
+                Ok(log) => Some((PipelineInput::Log(log), subscription)),
+                Err(RecvError::Lagged(skipped)) => {
+                    Some((PipelineInput::Lagged(skipped), subscription))
+                }
+                Err(RecvError::Closed) => None,
+            }
+        });
+        Ok(pipeline.spawn(source, sink))
+    }
+
     /// Get a reference to the underlying provider.
     ///
     /// Useful for making additional RPC calls while maintaining the scanner.
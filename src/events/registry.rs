@@ -0,0 +1,315 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Event signature registry for decoding arbitrary contract events
+//!
+//! The built-in scanners only understand the handful of events semioscan ships
+//! definitions for. An [`EventRegistry`] lets callers register their own
+//! `(topic0, decoder)` pairs so that logs from proprietary contracts are
+//! decoded into a generic [`DecodedEvent`] instead of being dropped.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use alloy_dyn_abi::DynSolValue;
+//! use alloy_sol_types::sol;
+//! use semioscan::{EventRegistry, EventScanner};
+//!
+//! sol! {
+//!     event BridgeLocked(address indexed sender, uint256 amount, bytes32 messageId);
+//! }
+//!
+//! let registry = EventRegistry::new()
+//!     .with_erc20_events()
+//!     .with_sol_event::<BridgeLocked, _>(|event| {
+//!         vec![
+//!             ("sender".into(), DynSolValue::Address(event.sender)),
+//!             ("amount".into(), DynSolValue::Uint(event.amount, 256)),
+//!             ("messageId".into(), DynSolValue::FixedBytes(event.messageId, 32)),
+//!         ]
+//!     });
+//!
+//! let scanner = EventScanner::new(provider, config);
+//! let scan = scanner
+//!     .scan_decoded(chain, filter, start_block, end_block, &registry)
+//!     .await?;
+//! for event in scan.events {
+//!     println!("{} at block {:?}: {:?}", event.name, event.block_number, event.fields);
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::{Address, BlockNumber, TxHash, B256};
+use alloy_rpc_types::Log;
+use alloy_sol_types::SolEvent;
+
+use crate::errors::EventProcessingError;
+use crate::events::definitions::{Approval, Transfer};
+
+/// A log decoded by a registered decoder
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    /// Human-readable event name (the registered name, usually the Solidity signature)
+    pub name: String,
+    /// Event signature hash (`topic0`)
+    pub topic0: B256,
+    /// Contract that emitted the event
+    pub address: Address,
+    /// Block containing the log, when the provider reported it
+    pub block_number: Option<BlockNumber>,
+    /// Transaction that emitted the log, when the provider reported it
+    pub transaction_hash: Option<TxHash>,
+    /// Position of the log within its block, when the provider reported it
+    pub log_index: Option<u64>,
+    /// Decoded parameters in declaration order
+    pub fields: Vec<(String, DynSolValue)>,
+}
+
+impl DecodedEvent {
+    /// Create a decoded event carrying the metadata of `log`
+    pub fn from_log(
+        name: impl Into<String>,
+        log: &Log,
+        fields: Vec<(String, DynSolValue)>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            topic0: log.topic0().copied().unwrap_or_default(),
+            address: log.address(),
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash,
+            log_index: log.log_index,
+            fields,
+        }
+    }
+
+    /// Look up a decoded parameter by name
+    pub fn field(&self, name: &str) -> Option<&DynSolValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Decoder invoked for logs whose `topic0` matches its registration
+pub type EventDecoderFn =
+    Arc<dyn Fn(&Log) -> Result<DecodedEvent, EventProcessingError> + Send + Sync>;
+
+#[derive(Clone)]
+struct RegisteredEvent {
+    name: String,
+    decoder: EventDecoderFn,
+}
+
+/// Registry mapping event signature hashes to decoders
+///
+/// Registering a second decoder for the same `topic0` replaces the first.
+#[derive(Clone, Default)]
+pub struct EventRegistry {
+    events: HashMap<B256, RegisteredEvent>,
+}
+
+impl fmt::Debug for EventRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.events.values().map(|e| e.name.as_str()).collect();
+        names.sort_unstable();
+        f.debug_struct("EventRegistry")
+            .field("events", &names)
+            .finish()
+    }
+}
+
+impl EventRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the ERC-20 `Transfer` and `Approval` events
+    pub fn with_erc20_events(self) -> Self {
+        self.with_sol_event::<Transfer, _>(|event| {
+            vec![
+                ("from".to_string(), DynSolValue::Address(event.from)),
+                ("to".to_string(), DynSolValue::Address(event.to)),
+                ("value".to_string(), DynSolValue::Uint(event.value, 256)),
+            ]
+        })
+        .with_sol_event::<Approval, _>(|event| {
+            vec![
+                ("owner".to_string(), DynSolValue::Address(event.owner)),
+                ("spender".to_string(), DynSolValue::Address(event.spender)),
+                ("value".to_string(), DynSolValue::Uint(event.value, 256)),
+            ]
+        })
+    }
+
+    /// Register a decoder for `topic0` (builder form of [`register`](Self::register))
+    pub fn with_decoder<F>(mut self, topic0: B256, name: impl Into<String>, decoder: F) -> Self
+    where
+        F: Fn(&Log) -> Result<DecodedEvent, EventProcessingError> + Send + Sync + 'static,
+    {
+        self.register(topic0, name, decoder);
+        self
+    }
+
+    /// Register a `sol!`-generated event (builder form of [`register_sol_event`](Self::register_sol_event))
+    pub fn with_sol_event<E, F>(mut self, to_fields: F) -> Self
+    where
+        E: SolEvent + 'static,
+        F: Fn(E) -> Vec<(String, DynSolValue)> + Send + Sync + 'static,
+    {
+        self.register_sol_event::<E, F>(to_fields);
+        self
+    }
+
+    /// Register a decoder for `topic0`
+    ///
+    /// The decoder receives every scanned log whose first topic equals `topic0`.
+    pub fn register<F>(&mut self, topic0: B256, name: impl Into<String>, decoder: F)
+    where
+        F: Fn(&Log) -> Result<DecodedEvent, EventProcessingError> + Send + Sync + 'static,
+    {
+        self.events.insert(
+            topic0,
+            RegisteredEvent {
+                name: name.into(),
+                decoder: Arc::new(decoder),
+            },
+        );
+    }
+
+    /// Register a `sol!`-generated event, keyed by its `SIGNATURE_HASH`
+    ///
+    /// Decoding and metadata handling are done by the registry; `to_fields`
+    /// only maps the typed event onto named values.
+    pub fn register_sol_event<E, F>(&mut self, to_fields: F)
+    where
+        E: SolEvent + 'static,
+        F: Fn(E) -> Vec<(String, DynSolValue)> + Send + Sync + 'static,
+    {
+        self.register(E::SIGNATURE_HASH, E::SIGNATURE, move |log: &Log| {
+            let event = E::decode_log_data(log.data()).map_err(|e| {
+                EventProcessingError::decode_failed(format!(
+                    "{signature}: {e}",
+                    signature = E::SIGNATURE
+                ))
+            })?;
+            Ok(DecodedEvent::from_log(E::SIGNATURE, log, to_fields(event)))
+        });
+    }
+
+    /// Check whether a decoder is registered for `topic0`
+    pub fn contains(&self, topic0: &B256) -> bool {
+        self.events.contains_key(topic0)
+    }
+
+    /// Name registered for `topic0`, if any
+    pub fn name(&self, topic0: &B256) -> Option<&str> {
+        self.events.get(topic0).map(|e| e.name.as_str())
+    }
+
+    /// All registered signature hashes, suitable for `Filter::event_signature`
+    pub fn topics(&self) -> Vec<B256> {
+        self.events.keys().copied().collect()
+    }
+
+    /// Number of registered events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check if no events are registered
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Decode a log with its registered decoder
+    ///
+    /// Returns `None` if the log has no topics or its `topic0` is not registered.
+    pub fn decode(&self, log: &Log) -> Option<Result<DecodedEvent, EventProcessingError>> {
+        let topic0 = log.topic0()?;
+        let registered = self.events.get(topic0)?;
+        Some((registered.decoder)(log))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes, LogData, U256};
+
+    fn transfer_log(value: u64) -> Log {
+        let from = address!("1111111111111111111111111111111111111111");
+        let to = address!("2222222222222222222222222222222222222222");
+        let event = Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        };
+        Log {
+            inner: alloy_primitives::Log {
+                address: address!("3333333333333333333333333333333333333333"),
+                data: event.encode_log_data(),
+            },
+            block_number: Some(7),
+            transaction_hash: Some(TxHash::repeat_byte(0xaa)),
+            log_index: Some(3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unregistered_topics_are_not_decoded() {
+        let registry = EventRegistry::new();
+        assert!(registry.decode(&transfer_log(1)).is_none());
+    }
+
+    #[test]
+    fn erc20_events_decode_into_named_fields() {
+        let registry = EventRegistry::new().with_erc20_events();
+        assert_eq!(registry.len(), 2);
+        assert!(registry.contains(&Transfer::SIGNATURE_HASH));
+
+        let event = registry.decode(&transfer_log(42)).unwrap().unwrap();
+        assert_eq!(event.name, Transfer::SIGNATURE);
+        assert_eq!(event.topic0, Transfer::SIGNATURE_HASH);
+        assert_eq!(event.block_number, Some(7));
+        assert_eq!(event.log_index, Some(3));
+        assert_eq!(
+            event.field("value"),
+            Some(&DynSolValue::Uint(U256::from(42u64), 256))
+        );
+    }
+
+    #[test]
+    fn custom_decoders_override_by_topic() {
+        let registry = EventRegistry::new().with_erc20_events().with_decoder(
+            Transfer::SIGNATURE_HASH,
+            "CustomTransfer",
+            |log: &Log| Ok(DecodedEvent::from_log("CustomTransfer", log, Vec::new())),
+        );
+
+        assert_eq!(registry.len(), 2);
+        let event = registry.decode(&transfer_log(1)).unwrap().unwrap();
+        assert_eq!(event.name, "CustomTransfer");
+    }
+
+    #[test]
+    fn malformed_registered_logs_surface_decode_errors() {
+        let registry = EventRegistry::new().with_erc20_events();
+        let mut log = transfer_log(1);
+        log.inner.data =
+            LogData::new_unchecked(vec![Transfer::SIGNATURE_HASH], Bytes::from_static(&[0x01]));
+
+        assert!(matches!(
+            registry.decode(&log),
+            Some(Err(EventProcessingError::DecodeFailed { .. }))
+        ));
+    }
+}
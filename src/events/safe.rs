@@ -0,0 +1,399 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Safe (formerly Gnosis Safe) multisig execution tracking
+//!
+//! A Safe emits `ExecutionSuccess` or `ExecutionFailure` after running each
+//! approved transaction through `execTransaction`. Token movements made by that
+//! transaction show up as ordinary `Transfer` logs emitted just before it, in
+//! the same on-chain transaction, which is usually sent by an owner or relayer
+//! rather than the Safe itself.
+//!
+//! [`SafeExecutionScanner`] finds a Safe's executions in a block range and
+//! attributes the token transfers and gas of the enclosing transaction to each
+//! one, giving a treasury view of "what did the multisig do" instead of "what
+//! did the relayer send".
+//!
+//! # Attribution
+//!
+//! Within one transaction, a transfer belongs to the first execution event
+//! after it, provided the transfer moves tokens into or out of that Safe.
+//! Transfers before an earlier execution event belong to that earlier one, so
+//! batched executions (several `execTransaction` calls in one transaction) each
+//! get their own transfers. Executions in the same transaction all report the
+//! transaction's gas; L1 data fees on rollups are not included.
+//!
+//! Both event layouts are decoded: Safe v1.3.0 (`txHash` in data) and v1.4.0+
+//! (`txHash` indexed).
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{SafeExecutionScanner, SemioscanConfig};
+//! use alloy_chains::NamedChain;
+//!
+//! let executions = SafeExecutionScanner::new(provider, SemioscanConfig::default())
+//!     .scan(NamedChain::Mainnet, &[treasury_safe], start_block, end_block)
+//!     .await?;
+//! for execution in &executions {
+//!     println!(
+//!         "{} moved {} tokens for {} wei of gas",
+//!         execution.safe_tx_hash,
+//!         execution.transfers.len(),
+//!         execution.gas_cost()
+//!     );
+//! }
+//! ```
+
+use std::collections::HashSet;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{Filter, Log};
+use alloy_sol_types::{sol, SolEvent, SolValue};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::config::SemioscanConfig;
+use crate::errors::{EventProcessingError, RpcError};
+use crate::events::definitions::Transfer;
+use crate::events::scanner::EventScanner;
+use crate::types::gas::{GasAmount, GasPrice};
+
+sol! {
+    /// Safe: a multisig transaction was executed and did not revert
+    ///
+    /// Declared with the v1.3.0 layout; v1.4.0+ indexes `txHash`, which keeps
+    /// the same signature.
+    ///
+    /// # Fields
+    ///
+    /// - `txHash`: Safe transaction hash the owners signed
+    /// - `payment`: Gas refund paid to the relayer, in the refund token
+    #[derive(Debug)]
+    event ExecutionSuccess(bytes32 txHash, uint256 payment);
+
+    /// Safe: a multisig transaction was executed and reverted
+    ///
+    /// The nonce is still consumed and the relayer still paid.
+    #[derive(Debug)]
+    event ExecutionFailure(bytes32 txHash, uint256 payment);
+}
+
+/// A token transfer attributed to a [`SafeExecution`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafeTransfer {
+    /// Token contract that emitted the transfer
+    pub token: Address,
+    pub from: Address,
+    pub to: Address,
+    /// Raw amount, not adjusted for decimals
+    pub value: U256,
+    /// Position of the transfer log in its block
+    pub log_index: Option<u64>,
+}
+
+/// One Safe transaction executed on-chain, with what it moved and cost
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafeExecution {
+    /// Safe that executed the transaction
+    pub safe: Address,
+    /// Safe transaction hash signed by the owners (not the on-chain hash)
+    pub safe_tx_hash: B256,
+    /// Whether the Safe transaction succeeded
+    pub success: bool,
+    /// Gas refund paid to the relayer, in the refund token
+    pub payment: U256,
+    /// Block containing the execution
+    pub block_number: Option<BlockNumber>,
+    /// On-chain transaction containing the execution
+    pub tx_hash: Option<TxHash>,
+    /// Position of the execution event in its block
+    pub log_index: Option<u64>,
+    /// Token transfers into or out of the Safe made by this execution
+    pub transfers: Vec<SafeTransfer>,
+    /// Gas used by the enclosing transaction
+    pub gas_used: GasAmount,
+    /// Effective gas price of the enclosing transaction
+    pub effective_gas_price: GasPrice,
+}
+
+impl SafeExecution {
+    /// Execution gas cost of the enclosing transaction in wei
+    pub fn gas_cost(&self) -> U256 {
+        self.gas_used.cost(self.effective_gas_price)
+    }
+
+    /// Transfers that moved tokens out of the Safe
+    pub fn outflows(&self) -> impl Iterator<Item = &SafeTransfer> {
+        self.transfers
+            .iter()
+            .filter(move |transfer| transfer.from == self.safe)
+    }
+
+    /// Transfers that moved tokens into the Safe
+    pub fn inflows(&self) -> impl Iterator<Item = &SafeTransfer> {
+        self.transfers
+            .iter()
+            .filter(move |transfer| transfer.to == self.safe)
+    }
+}
+
+/// Decode an `ExecutionSuccess`/`ExecutionFailure` log in either layout into
+/// `(safe_tx_hash, payment, success)`
+fn decode_execution(log: &Log) -> Option<(B256, U256, bool)> {
+    let success = match *log.topic0()? {
+        topic if topic == ExecutionSuccess::SIGNATURE_HASH => true,
+        topic if topic == ExecutionFailure::SIGNATURE_HASH => false,
+        _ => return None,
+    };
+    let data = log.data().data.as_ref();
+    match log.topics() {
+        [_] => <(B256, U256)>::abi_decode(data)
+            .ok()
+            .map(|(safe_tx_hash, payment)| (safe_tx_hash, payment, success)),
+        [_, safe_tx_hash] => U256::abi_decode(data)
+            .ok()
+            .map(|payment| (*safe_tx_hash, payment, success)),
+        _ => None,
+    }
+}
+
+/// Attribute the transfers in one transaction's `logs` to the Safe executions
+/// among them
+///
+/// `logs` should be every log of the transaction, e.g. from its receipt, in
+/// order. Only executions by `safes` are returned, or by any emitter if `safes`
+/// is empty. `gas_used` and `effective_gas_price` are those of the
+/// transaction.
+pub fn extract_safe_executions(
+    logs: &[Log],
+    safes: &[Address],
+    gas_used: GasAmount,
+    effective_gas_price: GasPrice,
+) -> Vec<SafeExecution> {
+    let mut executions = Vec::new();
+    let mut pending_transfers: Vec<SafeTransfer> = Vec::new();
+
+    for log in logs {
+        if log.topic0() == Some(&Transfer::SIGNATURE_HASH) {
+            if let Ok(event) = Transfer::decode_log(&log.inner) {
+                pending_transfers.push(SafeTransfer {
+                    token: log.address(),
+                    from: event.from,
+                    to: event.to,
+                    value: event.value,
+                    log_index: log.log_index,
+                });
+            }
+            continue;
+        }
+
+        let Some((safe_tx_hash, payment, success)) = decode_execution(log) else {
+            continue;
+        };
+        let safe = log.address();
+        // Transfers before this event belong to it or to no execution at all
+        let transfers = std::mem::take(&mut pending_transfers);
+        if !safes.is_empty() && !safes.contains(&safe) {
+            continue;
+        }
+        executions.push(SafeExecution {
+            safe,
+            safe_tx_hash,
+            success,
+            payment,
+            block_number: log.block_number,
+            tx_hash: log.transaction_hash,
+            log_index: log.log_index,
+            transfers: transfers
+                .into_iter()
+                .filter(|transfer| transfer.from == safe || transfer.to == safe)
+                .collect(),
+            gas_used,
+            effective_gas_price,
+        });
+    }
+
+    executions
+}
+
+/// Scans a chain for Safe executions and attributes transfers and gas to them
+pub struct SafeExecutionScanner<P> {
+    provider: P,
+    config: SemioscanConfig,
+}
+
+impl<P: Provider> SafeExecutionScanner<P> {
+    /// Creates a new `SafeExecutionScanner` with the given provider and configuration
+    pub fn new(provider: P, config: SemioscanConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Executions by `safes` in `[from_block, to_block]`
+    ///
+    /// Execution events are found with `eth_getLogs`; each transaction
+    /// containing one is then read with `eth_getTransactionReceipt` to pick up
+    /// its transfers and gas. An empty `safes` matches every Safe, which is
+    /// only practical for short ranges.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the log scan fails, or a receipt can't be fetched.
+    pub async fn scan(
+        &self,
+        chain: NamedChain,
+        safes: &[Address],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<SafeExecution>, EventProcessingError> {
+        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let mut filter = Filter::new().event_signature(vec![
+            ExecutionSuccess::SIGNATURE_HASH,
+            ExecutionFailure::SIGNATURE_HASH,
+        ]);
+        if !safes.is_empty() {
+            filter = filter.address(safes.to_vec());
+        }
+        let logs = scanner.scan(chain, filter, from_block, to_block).await?;
+
+        let mut seen = HashSet::new();
+        let tx_hashes: Vec<TxHash> = logs
+            .iter()
+            .filter_map(|log| log.transaction_hash)
+            .filter(|tx_hash| seen.insert(*tx_hash))
+            .collect();
+
+        let mut executions = Vec::new();
+        for tx_hash in &tx_hashes {
+            let receipt = self
+                .provider
+                .get_transaction_receipt(*tx_hash)
+                .await
+                .map_err(|e| {
+                    RpcError::request_failed(format!("get_transaction_receipt({tx_hash})"), e)
+                })?
+                .ok_or(RpcError::ReceiptNotFound { tx_hash: *tx_hash })?;
+
+            executions.extend(extract_safe_executions(
+                receipt.inner.logs(),
+                safes,
+                GasAmount::new(receipt.gas_used),
+                GasPrice::from(U256::from(receipt.effective_gas_price)),
+            ));
+        }
+
+        info!(
+            chain = %chain,
+            safes = safes.len(),
+            transactions = tx_hashes.len(),
+            executions = executions.len(),
+            "Finished scanning Safe executions"
+        );
+
+        Ok(executions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, Bytes, LogData};
+
+    const SAFE: Address = address!("5aFE3855358E112B5647B952709E6165e1c1eEEe");
+    const OTHER_SAFE: Address = address!("849D52316331967b6fF1198e5E32A0eB168D039d");
+    const TOKEN: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const RECIPIENT: Address = address!("1111111111111111111111111111111111111111");
+    const TX_HASH: TxHash =
+        b256!("00000000000000000000000000000000000000000000000000000000000000aa");
+
+    fn log_of(address: Address, data: LogData, log_index: u64) -> Log {
+        Log {
+            inner: alloy_primitives::Log { address, data },
+            block_number: Some(100),
+            transaction_hash: Some(TX_HASH),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    fn transfer(from: Address, to: Address, value: u64, log_index: u64) -> Log {
+        let event = Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        };
+        log_of(TOKEN, event.encode_log_data(), log_index)
+    }
+
+    fn execution_v130(safe: Address, safe_tx_hash: B256, log_index: u64) -> Log {
+        let event = ExecutionSuccess {
+            txHash: safe_tx_hash,
+            payment: U256::ZERO,
+        };
+        log_of(safe, event.encode_log_data(), log_index)
+    }
+
+    fn failure_v141(safe: Address, safe_tx_hash: B256, payment: u64, log_index: u64) -> Log {
+        let data = LogData::new_unchecked(
+            vec![ExecutionFailure::SIGNATURE_HASH, safe_tx_hash],
+            Bytes::from(U256::from(payment).abi_encode()),
+        );
+        log_of(safe, data, log_index)
+    }
+
+    #[test]
+    fn batched_executions_each_get_their_own_transfers() {
+        let first = B256::repeat_byte(1);
+        let second = B256::repeat_byte(2);
+        let logs = vec![
+            transfer(SAFE, RECIPIENT, 500, 0),
+            // Not involving the Safe, so not attributed
+            transfer(RECIPIENT, OTHER_SAFE, 7, 1),
+            execution_v130(SAFE, first, 2),
+            transfer(RECIPIENT, SAFE, 300, 3),
+            failure_v141(SAFE, second, 42, 4),
+        ];
+
+        let executions =
+            extract_safe_executions(&logs, &[SAFE], GasAmount::new(100_000), GasPrice::new(10));
+        assert_eq!(executions.len(), 2);
+
+        let (success, failure) = (&executions[0], &executions[1]);
+        assert_eq!(success.safe_tx_hash, first);
+        assert!(success.success);
+        assert_eq!(success.transfers.len(), 1);
+        assert_eq!(success.outflows().count(), 1);
+        assert_eq!(success.transfers[0].value, U256::from(500));
+        assert_eq!(success.gas_cost(), U256::from(1_000_000));
+
+        assert_eq!(failure.safe_tx_hash, second);
+        assert!(!failure.success);
+        assert_eq!(failure.payment, U256::from(42));
+        assert_eq!(failure.inflows().count(), 1);
+        assert_eq!(failure.log_index, Some(4));
+    }
+
+    #[test]
+    fn executions_by_other_safes_are_skipped_without_stealing_transfers() {
+        let logs = vec![
+            transfer(OTHER_SAFE, RECIPIENT, 1, 0),
+            execution_v130(OTHER_SAFE, B256::repeat_byte(1), 1),
+            transfer(SAFE, RECIPIENT, 2, 2),
+            execution_v130(SAFE, B256::repeat_byte(2), 3),
+        ];
+
+        let executions =
+            extract_safe_executions(&logs, &[SAFE], GasAmount::new(1), GasPrice::new(1));
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].safe, SAFE);
+        assert_eq!(executions[0].transfers.len(), 1);
+        assert_eq!(executions[0].transfers[0].value, U256::from(2));
+
+        let all = extract_safe_executions(&logs, &[], GasAmount::new(1), GasPrice::new(1));
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].transfers[0].value, U256::from(1));
+    }
+}
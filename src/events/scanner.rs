@@ -38,15 +38,33 @@
 //! ).await?;
 //! ```
 
+use std::sync::Arc;
+
 use alloy_chains::NamedChain;
 use alloy_primitives::BlockNumber;
 use alloy_provider::Provider;
 use alloy_rpc_types::{Filter, Log};
-use tokio::time::sleep;
-use tracing::{debug, error};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, warn};
 
 use crate::config::SemioscanConfig;
 use crate::errors::EventProcessingError;
+use crate::events::chunked::{get_logs_bisecting, ChunkScheduler};
+use crate::events::query::EventQuery;
+use crate::events::registry::{DecodedEvent, EventRegistry};
+use crate::events::source::{LogSource, RpcLogSource};
+use crate::types::warnings::{push_warning, ScanWarning};
+
+/// Logs from a scan, split by whether an [`EventRegistry`] could decode them
+#[derive(Debug, Clone, Default)]
+pub struct DecodedScan {
+    /// Logs decoded by a registered decoder, in scan order
+    pub events: Vec<DecodedEvent>,
+    /// Logs whose `topic0` has no registered decoder
+    pub unrecognized: Vec<Log>,
+    /// Logs with a registered `topic0` whose decoder returned an error
+    pub decode_failures: Vec<Log>,
+}
 
 /// Generic event scanner with chunking and rate limiting
 ///
@@ -79,6 +97,8 @@ use crate::errors::EventProcessingError;
 pub struct EventScanner<P> {
     provider: P,
     config: SemioscanConfig,
+    cancellation: Option<CancellationToken>,
+    log_source: Option<Arc<dyn LogSource>>,
 }
 
 impl<P: Provider> EventScanner<P> {
@@ -101,13 +121,43 @@ impl<P: Provider> EventScanner<P> {
     /// let scanner = EventScanner::new(provider, config);
     /// ```
     pub fn new(provider: P, config: SemioscanConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            cancellation: None,
+            log_source: None,
+        }
+    }
+
+    /// Stop scanning between chunks once `token` is cancelled
+    ///
+    /// A cancelled scan returns the logs fetched so far. Callers that need to
+    /// tell a partial scan from a complete one should check
+    /// [`is_cancelled`](Self::is_cancelled) afterwards.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Fetch logs from `source` instead of `eth_getLogs` on the provider
+    pub fn with_log_source(mut self, source: Arc<dyn LogSource>) -> Self {
+        self.log_source = Some(source);
+        self
+    }
+
+    /// Whether the cancellation token passed to
+    /// [`with_cancellation`](Self::with_cancellation) has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
     }
 
     /// Scan for events over a block range with automatic chunking and rate limiting
     ///
     /// This method handles:
     /// - Splitting large block ranges into chain-specific chunks
+    /// - Bisecting chunks the provider rejects for returning too many results
     /// - Applying rate limiting between RPC calls
     /// - Error handling and logging for failed chunks
     /// - Aggregating results into a single vector
@@ -154,6 +204,38 @@ impl<P: Provider> EventScanner<P> {
         start_block: BlockNumber,
         end_block: BlockNumber,
     ) -> Result<Vec<Log>, EventProcessingError> {
+        self.scan_with_warnings(chain, filter_template, start_block, end_block)
+            .await
+            .map(|(logs, _)| logs)
+    }
+
+    /// Scans for the events matching `query` between `start_block` and
+    /// `end_block`
+    ///
+    /// Like [`scan`](Self::scan), with the filter compiled from an
+    /// [`EventQuery`].
+    pub async fn scan_query(
+        &self,
+        chain: NamedChain,
+        query: impl Into<EventQuery>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<Vec<Log>, EventProcessingError> {
+        self.scan(chain, query.into().to_filter(), start_block, end_block)
+            .await
+    }
+
+    /// Like [`scan`](Self::scan), also returning the block ranges whose
+    /// chunks failed as [`ScanWarning::Gap`]s
+    ///
+    /// Consecutive failed chunks are reported as one gap.
+    pub async fn scan_with_warnings(
+        &self,
+        chain: NamedChain,
+        filter_template: Filter,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<(Vec<Log>, Vec<ScanWarning>), EventProcessingError> {
         debug!(
             chain = %chain,
             start_block = start_block,
@@ -161,17 +243,26 @@ impl<P: Provider> EventScanner<P> {
             "Starting event scan"
         );
 
-        let max_block_range = self.config.get_max_block_range(chain);
-        let rate_limit = self.config.get_rate_limit_delay(chain);
+        let mut pacer = self.config.pacer(chain);
+        let mut chunks = ChunkScheduler::new(&self.config, chain, start_block, end_block);
+        let rpc_logs = RpcLogSource::new(&self.provider);
+        let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
 
         let mut all_logs = Vec::new();
-        let mut current_block = start_block;
-
-        while current_block <= end_block {
-            let to_block = current_block
-                .saturating_add(max_block_range.as_u64())
-                .saturating_sub(1)
-                .min(end_block);
+        let mut warnings = Vec::new();
+        while let Some((current_block, to_block)) = chunks.next_chunk() {
+            // Wait for a rate limit permit before the next chunk
+            pacer.wait().await;
+
+            if self.is_cancelled() {
+                warn!(
+                    chain = %chain,
+                    current_block = current_block,
+                    end_block = end_block,
+                    "Event scan cancelled"
+                );
+                break;
+            }
 
             // Clone the filter template and add block range
             let filter = filter_template
@@ -186,7 +277,7 @@ impl<P: Provider> EventScanner<P> {
                 "Fetching logs for chunk"
             );
 
-            match self.provider.get_logs(&filter).await {
+            match get_logs_bisecting(log_source, &filter, current_block, to_block).await {
                 Ok(logs) => {
                     debug!(
                         logs_count = logs.len(),
@@ -194,8 +285,17 @@ impl<P: Provider> EventScanner<P> {
                         to_block = to_block,
                         "Fetched logs for block range"
                     );
+                    chunks.chunk_succeeded(logs.len());
                     all_logs.extend(logs);
                 }
+                Err(e) if chunks.chunk_failed() => {
+                    warn!(
+                        ?e,
+                        %current_block,
+                        %to_block,
+                        "Error fetching logs in range, retrying with a smaller chunk"
+                    );
+                }
                 Err(e) => {
                     error!(
                         ?e,
@@ -204,20 +304,8 @@ impl<P: Provider> EventScanner<P> {
                         "Error fetching logs in range"
                     );
                     // Continue with next chunk rather than failing completely
-                }
-            }
-
-            current_block = to_block + 1;
-
-            // Apply rate limiting if configured for this chain
-            if let Some(delay) = rate_limit {
-                if current_block <= end_block {
-                    debug!(
-                        chain = %chain,
-                        delay_ms = delay.as_millis(),
-                        "Applying rate limit delay"
-                    );
-                    sleep(delay).await;
+                    push_warning(&mut warnings, ScanWarning::gap(current_block, to_block, &e));
+                    chunks.skip_chunk();
                 }
             }
         }
@@ -228,7 +316,40 @@ impl<P: Provider> EventScanner<P> {
             "Finished event scan"
         );
 
-        Ok(all_logs)
+        Ok((all_logs, warnings))
+    }
+
+    /// Scan for events and decode them with an [`EventRegistry`]
+    ///
+    /// Behaves like [`scan`](Self::scan), but every log is passed through
+    /// `registry`. Logs with no registered decoder are returned in
+    /// [`DecodedScan::unrecognized`] rather than dropped, and logs whose decoder
+    /// fails are logged and returned in [`DecodedScan::decode_failures`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use alloy_rpc_types::Filter;
+    ///
+    /// let filter = Filter::new()
+    ///     .address(bridge_address)
+    ///     .event_signature(registry.topics());
+    /// let scan = scanner
+    ///     .scan_decoded(chain, filter, start_block, end_block, &registry)
+    ///     .await?;
+    /// ```
+    pub async fn scan_decoded(
+        &self,
+        chain: NamedChain,
+        filter_template: Filter,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        registry: &EventRegistry,
+    ) -> Result<DecodedScan, EventProcessingError> {
+        let logs = self
+            .scan(chain, filter_template, start_block, end_block)
+            .await?;
+        Ok(decode_logs(registry, logs))
     }
 
     /// Scan for events and process them with a custom handler
@@ -286,23 +407,31 @@ impl<P: Provider> EventScanner<P> {
             "Starting event scan with handler"
         );
 
-        let max_block_range = self.config.get_max_block_range(chain);
-        let rate_limit = self.config.get_rate_limit_delay(chain);
-
-        let mut current_block = start_block;
-
-        while current_block <= end_block {
-            let to_block = current_block
-                .saturating_add(max_block_range.as_u64())
-                .saturating_sub(1)
-                .min(end_block);
+        let mut pacer = self.config.pacer(chain);
+        let mut chunks = ChunkScheduler::new(&self.config, chain, start_block, end_block);
+        let rpc_logs = RpcLogSource::new(&self.provider);
+        let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
+
+        while let Some((current_block, to_block)) = chunks.next_chunk() {
+            // Wait for a rate limit permit before the next chunk
+            pacer.wait().await;
+
+            if self.is_cancelled() {
+                warn!(
+                    chain = %chain,
+                    current_block = current_block,
+                    end_block = end_block,
+                    "Event scan cancelled"
+                );
+                break;
+            }
 
             let filter = filter_template
                 .clone()
                 .from_block(current_block)
                 .to_block(to_block);
 
-            match self.provider.get_logs(&filter).await {
+            match get_logs_bisecting(log_source, &filter, current_block, to_block).await {
                 Ok(logs) => {
                     debug!(
                         logs_count = logs.len(),
@@ -310,10 +439,19 @@ impl<P: Provider> EventScanner<P> {
                         to_block = to_block,
                         "Processing logs for block range"
                     );
+                    chunks.chunk_succeeded(logs.len());
 
                     // Call the handler with this chunk of logs
                     handler(logs).await?;
                 }
+                Err(e) if chunks.chunk_failed() => {
+                    warn!(
+                        ?e,
+                        %current_block,
+                        %to_block,
+                        "Error fetching logs in range, retrying with a smaller chunk"
+                    );
+                }
                 Err(e) => {
                     error!(
                         ?e,
@@ -322,15 +460,7 @@ impl<P: Provider> EventScanner<P> {
                         "Error fetching logs in range"
                     );
                     // Continue with next chunk
-                }
-            }
-
-            current_block = to_block + 1;
-
-            // Apply rate limiting if configured
-            if let Some(delay) = rate_limit {
-                if current_block <= end_block {
-                    sleep(delay).await;
+                    chunks.skip_chunk();
                 }
             }
         }
@@ -341,6 +471,27 @@ impl<P: Provider> EventScanner<P> {
     }
 }
 
+/// Split logs into decoded events, unrecognized logs, and decode failures
+pub(crate) fn decode_logs(registry: &EventRegistry, logs: Vec<Log>) -> DecodedScan {
+    let mut scan = DecodedScan::default();
+    for log in logs {
+        match registry.decode(&log) {
+            Some(Ok(event)) => scan.events.push(event),
+            Some(Err(e)) => {
+                warn!(
+                    error = %e,
+                    tx_hash = ?log.transaction_hash,
+                    log_index = ?log.log_index,
+                    "Registered decoder failed for log"
+                );
+                scan.decode_failures.push(log);
+            }
+            None => scan.unrecognized.push(log),
+        }
+    }
+    scan
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,4 +537,79 @@ mod tests {
         assert_eq!(config.get_rate_limit_delay(NamedChain::Sonic), None);
         assert_eq!(config.get_rate_limit_delay(NamedChain::Arbitrum), None);
     }
+
+    #[test]
+    fn test_decode_logs_partitions_by_registry() {
+        use crate::events::definitions::Transfer;
+        use alloy_primitives::{Address, Bytes, LogData, B256, U256};
+        use alloy_sol_types::SolEvent;
+
+        let transfer = Transfer {
+            from: Address::repeat_byte(0x11),
+            to: Address::repeat_byte(0x22),
+            value: U256::from(5u64),
+        };
+        let decodable = Log {
+            inner: alloy_primitives::Log {
+                address: Address::repeat_byte(0x33),
+                data: transfer.encode_log_data(),
+            },
+            ..Default::default()
+        };
+        let malformed = Log {
+            inner: alloy_primitives::Log {
+                address: Address::repeat_byte(0x33),
+                data: LogData::new_unchecked(vec![Transfer::SIGNATURE_HASH], Bytes::new()),
+            },
+            ..Default::default()
+        };
+        let unknown = Log {
+            inner: alloy_primitives::Log {
+                address: Address::repeat_byte(0x44),
+                data: LogData::new_unchecked(vec![B256::repeat_byte(0x99)], Bytes::new()),
+            },
+            ..Default::default()
+        };
+
+        let registry = EventRegistry::new().with_erc20_events();
+        let scan = decode_logs(&registry, vec![decodable, malformed, unknown]);
+
+        assert_eq!(scan.events.len(), 1);
+        assert_eq!(scan.decode_failures.len(), 1);
+        assert_eq!(scan.unrecognized.len(), 1);
+        assert_eq!(scan.unrecognized[0].address(), Address::repeat_byte(0x44));
+    }
+
+    #[tokio::test]
+    async fn test_failed_chunks_are_reported_as_one_gap() {
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_failure_msg("upstream timeout");
+        asserter.push_failure_msg("upstream timeout");
+        asserter.push_success(&Vec::<Log>::new());
+
+        let config = SemioscanConfigBuilder::new()
+            .chain_max_blocks(NamedChain::Mainnet, 10)
+            .build();
+        let scanner = EventScanner::new(provider, config);
+
+        let (logs, warnings) = scanner
+            .scan_with_warnings(NamedChain::Mainnet, Filter::new(), 0, 29)
+            .await
+            .unwrap();
+
+        assert!(logs.is_empty());
+        assert!(matches!(
+            warnings.as_slice(),
+            [ScanWarning::Gap {
+                from_block: 0,
+                to_block: 19,
+                ..
+            }]
+        ));
+    }
 }
@@ -0,0 +1,150 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pluggable backends for historical logs
+//!
+//! Every scan loop in semioscan (events, gas, price and combined) fetches logs
+//! through a [`LogSource`]. By default that is [`RpcLogSource`], which calls
+//! `eth_getLogs` on the calculator's provider. Indexing services that serve
+//! logs faster than an RPC node can be plugged in by implementing the trait
+//! and passing it to the calculator's `with_log_source`; the chunking,
+//! bisection, rate limiting and decoding around the fetch stay the same.
+//!
+//! Only log fetching moves to the source. Receipts, blocks and contract calls
+//! still go to the provider.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use semioscan::{EventScanner, LogSource, SemioscanConfig};
+//!
+//! struct ArchiveLogs { /* client for an indexing service */ }
+//!
+//! #[async_trait::async_trait]
+//! impl LogSource for ArchiveLogs {
+//!     async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+//!         // Query the archive, mapping its errors with TransportErrorKind::custom
+//!     }
+//! }
+//!
+//! let scanner = EventScanner::new(provider, SemioscanConfig::default())
+//!     .with_log_source(Arc::new(ArchiveLogs { /* ... */ }));
+//! ```
+
+use std::marker::PhantomData;
+
+use alloy_network::{Ethereum, Network};
+use alloy_provider::Provider;
+use alloy_rpc_types::{Filter, Log};
+use alloy_transport::TransportError;
+use async_trait::async_trait;
+
+/// A backend that returns the logs matching a filter
+///
+/// Implementations must return logs in block and log-index order, like
+/// `eth_getLogs` does. Errors are reported as [`TransportError`] so scan loops
+/// treat every backend alike: a "too many results" error (JSON-RPC code
+/// `-32005`, or a message such as "query returned more than 10000 results")
+/// makes the caller split the block range and retry, anything else fails the
+/// chunk. Backends without a natural transport error can wrap theirs with
+/// `TransportErrorKind::custom`.
+#[async_trait]
+pub trait LogSource: Send + Sync {
+    /// Logs matching `filter`, whose block range is always set
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError>;
+}
+
+/// [`LogSource`] backed by `eth_getLogs` on an Alloy provider
+#[derive(Debug, Clone)]
+pub struct RpcLogSource<P, N = Ethereum> {
+    provider: P,
+    _network: PhantomData<fn() -> N>,
+}
+
+impl<P, N> RpcLogSource<P, N> {
+    /// Fetch logs from `provider`
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            _network: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<N: Network, P: Provider<N>> LogSource for RpcLogSource<P, N> {
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+        self.provider.get_logs(filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SemioscanConfig;
+    use crate::events::scanner::EventScanner;
+    use alloy_chains::NamedChain;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_transport::mock::Asserter;
+    use alloy_transport::TransportErrorKind;
+    use std::sync::{Arc, Mutex};
+
+    /// Serves logs from memory, rejecting ranges wider than `max_blocks` the
+    /// way RPC providers reject queries with too many results
+    struct InMemoryLogs {
+        logs: Vec<Log>,
+        max_blocks: u64,
+        queries: Mutex<Vec<(u64, u64)>>,
+    }
+
+    #[async_trait]
+    impl LogSource for InMemoryLogs {
+        async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+            let from = filter.get_from_block().unwrap();
+            let to = filter.get_to_block().unwrap();
+            self.queries.lock().unwrap().push((from, to));
+            if to - from + 1 > self.max_blocks {
+                return Err(TransportErrorKind::custom_str(
+                    "query returned more than 10000 results",
+                ));
+            }
+            Ok(self
+                .logs
+                .iter()
+                .filter(|log| (from..=to).contains(&log.block_number.unwrap()))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn scanner_reads_from_custom_source_and_bisects_its_limit_errors() {
+        let source = Arc::new(InMemoryLogs {
+            logs: (100..110)
+                .map(|block| Log {
+                    block_number: Some(block),
+                    ..Default::default()
+                })
+                .collect(),
+            max_blocks: 4,
+            queries: Mutex::new(Vec::new()),
+        });
+
+        // Any request reaching the provider would fail on the empty mock queue
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(Asserter::new());
+        let scanner =
+            EventScanner::new(provider, SemioscanConfig::default()).with_log_source(source.clone());
+
+        let logs = scanner
+            .scan(NamedChain::Arbitrum, Filter::new(), 100, 109)
+            .await
+            .unwrap();
+
+        let blocks: Vec<_> = logs.iter().filter_map(|log| log.block_number).collect();
+        assert_eq!(blocks, (100..110).collect::<Vec<_>>());
+        assert!(source.queries.lock().unwrap().len() > 1);
+    }
+}
@@ -0,0 +1,322 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fee-on-transfer and rebasing token detection
+//!
+//! `Transfer` events report the amount the sender sent. Fee-on-transfer tokens
+//! deliver less than that to the recipient, and rebasing tokens change
+//! balances without emitting events, so summing events overstates what the
+//! recipient actually received.
+//!
+//! Detection samples a few blocks containing matching transfers. For each, it
+//! compares the recipient's `balanceOf` change across the block with the net
+//! amount the block's `Transfer` events moved in and out of the recipient.
+//! The ratio of the two, over all usable samples, is the token's
+//! [`TransferTax`].
+//!
+//! The sampled blocks' logs come from the calculator's
+//! [`LogSource`], like every other log fetch; balances are read from the
+//! provider.
+
+use alloy_eips::BlockId;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{Address, BlockNumber, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::{sol, SolCall, SolEvent};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::errors::{EventProcessingError, RpcError};
+use crate::events::definitions::Transfer;
+use crate::events::filter::TransferFilterBuilder;
+use crate::events::source::LogSource;
+use crate::types::tokens::TokenAmount;
+
+sol! {
+    function balanceOf(address owner) external view returns (uint256);
+}
+
+/// Basis points in one whole
+const BPS: u64 = 10_000;
+
+/// How [`AmountCalculator`](crate::AmountCalculator) and
+/// [`CombinedCalculator`](crate::CombinedCalculator) treat tokens whose
+/// transfers deliver a different amount than their events report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferTaxMode {
+    /// Sum event amounts as reported
+    #[default]
+    Ignore,
+    /// Sample balances and report the [`TransferTax`] on the result, leaving
+    /// the amount as emitted
+    Detect,
+    /// Like [`Detect`](Self::Detect), and scale the amount to what the
+    /// recipient received
+    Adjust,
+}
+
+/// Share of emitted transfer amounts a recipient actually received
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferTax {
+    /// Received amount per 10,000 emitted
+    ///
+    /// Below 10,000 for fee-on-transfer tokens; rebasing tokens can land on
+    /// either side.
+    #[serde(rename = "received_bps")]
+    pub received_bps: u64,
+    /// Number of blocks the estimate is based on
+    #[serde(rename = "samples")]
+    pub samples: usize,
+}
+
+impl TransferTax {
+    /// Whether the recipient received exactly what the events reported
+    pub fn is_exact(&self) -> bool {
+        self.received_bps == BPS
+    }
+
+    /// Estimate the amount received from an `emitted` amount
+    pub fn apply(&self, emitted: TokenAmount) -> TokenAmount {
+        let received = emitted
+            .as_u256()
+            .saturating_mul(U256::from(self.received_bps))
+            / U256::from(BPS);
+        TokenAmount::from(received)
+    }
+
+    /// Estimate from total received and total net emitted amounts, or `None`
+    /// if nothing was emitted
+    fn from_totals(received: U256, emitted: U256, samples: usize) -> Option<Self> {
+        if emitted.is_zero() {
+            return None;
+        }
+        let received_bps = received.saturating_mul(U256::from(BPS)) / emitted;
+        Some(Self {
+            received_bps: received_bps.saturating_to(),
+            samples,
+        })
+    }
+}
+
+/// Up to `count` block numbers spread evenly over `blocks`, which must be sorted
+pub(crate) fn sample_blocks(blocks: &[BlockNumber], count: usize) -> Vec<BlockNumber> {
+    let mut distinct = blocks.to_vec();
+    distinct.dedup();
+    if distinct.len() <= count {
+        return distinct;
+    }
+    (0..count)
+        .map(|i| distinct[i * (distinct.len() - 1) / count.saturating_sub(1).max(1)])
+        .collect()
+}
+
+/// Estimate the transfer tax of `token` for `recipient` from balance changes
+/// over `blocks`
+///
+/// Each block's `Transfer` events are read from `logs` and the recipient's
+/// balances from `provider`. Blocks where the recipient's events net out to zero or negative are
+/// skipped, as are blocks where its balance fell. Returns `None` when no block
+/// was usable.
+///
+/// # Errors
+///
+/// Fails if fetching a block's logs or a historical balance fails.
+pub(crate) async fn estimate_transfer_tax<N: Network, P: Provider<N>>(
+    provider: &P,
+    logs: &dyn LogSource,
+    token: Address,
+    recipient: Address,
+    blocks: &[BlockNumber],
+) -> Result<Option<TransferTax>, EventProcessingError> {
+    let (mut received, mut emitted, mut samples) = (U256::ZERO, U256::ZERO, 0);
+
+    for &block in blocks {
+        let Some(previous) = block.checked_sub(1) else {
+            continue;
+        };
+        let incoming = transfer_total(
+            logs,
+            TransferFilterBuilder::new()
+                .with_token(token)
+                .with_recipient(recipient),
+            block,
+        )
+        .await?;
+        let outgoing = transfer_total(
+            logs,
+            TransferFilterBuilder::new()
+                .with_token(token)
+                .with_sender(recipient),
+            block,
+        )
+        .await?;
+        let Some(net_emitted) = incoming.checked_sub(outgoing).filter(|net| !net.is_zero()) else {
+            continue;
+        };
+
+        let before = balance_of(provider, token, recipient, previous).await?;
+        let after = balance_of(provider, token, recipient, block).await?;
+        let Some(net_received) = after.checked_sub(before) else {
+            continue;
+        };
+
+        debug!(
+            ?token,
+            block,
+            %net_emitted,
+            %net_received,
+            "Sampled transfer tax"
+        );
+        received += net_received;
+        emitted += net_emitted;
+        samples += 1;
+    }
+
+    Ok(TransferTax::from_totals(received, emitted, samples))
+}
+
+/// Sum of the `Transfer` amounts matching `filter` in `block`
+async fn transfer_total(
+    logs: &dyn LogSource,
+    filter: TransferFilterBuilder,
+    block: BlockNumber,
+) -> Result<U256, EventProcessingError> {
+    let filter = filter.build().from_block(block).to_block(block);
+    let logs = logs
+        .get_logs(&filter)
+        .await
+        .map_err(|e| RpcError::request_failed(format!("eth_getLogs for block {block}"), e))?;
+    Ok(logs
+        .iter()
+        .filter_map(|log| Transfer::decode_log(&log.inner).ok())
+        .fold(U256::ZERO, |total, event| total.saturating_add(event.value)))
+}
+
+/// `token.balanceOf(owner)` as of the end of `block`
+async fn balance_of<N: Network, P: Provider<N>>(
+    provider: &P,
+    token: Address,
+    owner: Address,
+    block: BlockNumber,
+) -> Result<U256, EventProcessingError> {
+    let request = N::TransactionRequest::default()
+        .with_to(token)
+        .with_input(balanceOfCall { owner }.abi_encode());
+    let data = provider
+        .call(request)
+        .block(BlockId::number(block))
+        .await
+        .map_err(|e| RpcError::request_failed(format!("balanceOf at block {block}"), e))?;
+    balanceOfCall::abi_decode_returns(&data)
+        .map_err(|e| EventProcessingError::decode_failed(format!("balanceOf: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use alloy_network::Ethereum;
+    use alloy_primitives::{address, Bytes};
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_rpc_types::Filter;
+    use alloy_transport::TransportError;
+    use async_trait::async_trait;
+
+    use crate::events::source::RpcLogSource;
+    use alloy_rpc_types::Log;
+    use alloy_sol_types::SolValue;
+    use alloy_transport::mock::Asserter;
+
+    const TOKEN: Address = address!("00000000000000000000000000000000000000aa");
+    const RECIPIENT: Address = address!("00000000000000000000000000000000000000bb");
+
+    fn transfer_log(from: Address, to: Address, value: u64) -> Log {
+        let event = Transfer {
+            from,
+            to,
+            value: U256::from(value),
+        };
+        Log {
+            inner: alloy_primitives::Log {
+                address: TOKEN,
+                data: event.encode_log_data(),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Answers each `get_logs` with the next queued batch
+    struct QueuedLogs(Mutex<VecDeque<Vec<Log>>>);
+
+    #[async_trait]
+    impl LogSource for QueuedLogs {
+        async fn get_logs(&self, _filter: &Filter) -> Result<Vec<Log>, TransportError> {
+            Ok(self.0.lock().unwrap().pop_front().unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn test_tax_scales_emitted_amounts() {
+        let tax = TransferTax::from_totals(U256::from(95), U256::from(100), 1).unwrap();
+        assert_eq!(tax.received_bps, 9_500);
+        assert!(!tax.is_exact());
+        assert_eq!(
+            tax.apply(TokenAmount::from(U256::from(1_000))),
+            TokenAmount::from(U256::from(950))
+        );
+        assert_eq!(TransferTax::from_totals(U256::ZERO, U256::ZERO, 0), None);
+    }
+
+    #[test]
+    fn test_samples_are_spread_over_distinct_blocks() {
+        assert_eq!(sample_blocks(&[5, 5, 7], 3), vec![5, 7]);
+        assert_eq!(sample_blocks(&[1, 2, 3, 4, 5, 6, 7], 3), vec![1, 4, 7]);
+    }
+
+    #[tokio::test]
+    async fn test_balance_diff_reveals_fee_on_transfer() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        // 100 emitted into the recipient, nothing out, balance grew by 98
+        asserter.push_success(&vec![transfer_log(Address::ZERO, RECIPIENT, 100)]);
+        asserter.push_success(&Vec::<Log>::new());
+        asserter.push_success(&Bytes::from(U256::from(1_000).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(1_098).abi_encode()));
+
+        let logs = RpcLogSource::<_, Ethereum>::new(&provider);
+        let tax = estimate_transfer_tax(&provider, &logs, TOKEN, RECIPIENT, &[10])
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(tax.received_bps, 9_800);
+        assert_eq!(tax.samples, 1);
+    }
+
+    #[tokio::test]
+    async fn test_sampled_logs_come_from_the_log_source() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        // Only the balances are read from the provider
+        asserter.push_success(&Bytes::from(U256::from(1_000).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(1_090).abi_encode()));
+        let logs = QueuedLogs(Mutex::new(VecDeque::from([
+            vec![transfer_log(Address::ZERO, RECIPIENT, 100)],
+            Vec::new(),
+        ])));
+
+        let tax = estimate_transfer_tax(&provider, &logs, TOKEN, RECIPIENT, &[10])
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Had the logs been requested from the provider, it would have been
+        // handed the balances instead and failed
+        assert_eq!(tax.received_bps, 9_000);
+    }
+}
@@ -31,18 +31,42 @@
 //!
 //! println!("Total transferred: {} (raw amount)", result.amount);
 //! ```
+//!
+//! # Wrapped native tokens
+//!
+//! WETH-like contracts emit `Deposit`/`Withdrawal` instead of a `Transfer` from
+//! or to the zero address when wrapping and unwrapping. Register such tokens
+//! with [`AmountCalculator::with_wrapped_native_tokens`] to count those events
+//! as mints and burns.
+//!
+//! # Fee-on-transfer tokens
+//!
+//! Tokens that take a fee on transfer, or rebase, emit `Transfer` events for
+//! more than the recipient ends up holding. Enable
+//! [`AmountCalculator::with_transfer_tax`] to sample the recipient's balance
+//! and report, or correct for, the difference.
+
+use std::collections::HashSet;
+use std::sync::Arc;
 
 use alloy_chains::NamedChain;
+use alloy_network::Ethereum;
 use alloy_primitives::{Address, BlockNumber};
 use alloy_provider::Provider;
+use alloy_rpc_types::{Filter, Log};
 use alloy_sol_types::SolEvent;
+use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use crate::config::SemioscanConfig;
 use crate::errors::EventProcessingError;
-use crate::events::definitions::Transfer;
+use crate::events::definitions::{Deposit, Transfer, Withdrawal};
 use crate::events::filter::TransferFilterBuilder;
 use crate::events::scanner::EventScanner;
+use crate::events::source::{LogSource, RpcLogSource};
+use crate::events::transfer_tax::{
+    estimate_transfer_tax, sample_blocks, TransferTax, TransferTaxMode,
+};
 use crate::types::tokens::TokenAmount;
 
 /// Result of transfer amount calculation
@@ -64,15 +88,30 @@ use crate::types::tokens::TokenAmount;
 /// let normalized = result.amount.normalize(TokenDecimals::USDC);
 /// // normalized.as_f64() gives the human-readable value (e.g., 1.0 for 1 USDC)
 /// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AmountResult {
     /// Chain ID where the transfers occurred
+    #[serde(rename = "chain")]
     pub chain: NamedChain,
     /// Address that received the tokens
+    #[serde(rename = "to")]
     pub to: Address,
     /// Token contract address
+    #[serde(rename = "token")]
     pub token: Address,
     /// Total amount transferred (raw, not normalized for decimals)
+    #[serde(rename = "amount")]
     pub amount: TokenAmount,
+    /// Measured share of emitted amounts the recipient received, when
+    /// transfer tax detection is enabled and a sample was usable
+    ///
+    /// With [`TransferTaxMode::Adjust`], `amount` has already been scaled by it.
+    #[serde(
+        rename = "transfer_tax",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transfer_tax: Option<TransferTax>,
 }
 
 /// Calculator for ERC-20 token transfer amounts
@@ -99,6 +138,10 @@ pub struct AmountResult {
 pub struct AmountCalculator<P> {
     provider: P,
     config: SemioscanConfig,
+    wrapped_native_tokens: HashSet<Address>,
+    transfer_tax: TransferTaxMode,
+    transfer_tax_samples: usize,
+    log_source: Option<Arc<dyn LogSource>>,
 }
 
 impl<P: Provider> AmountCalculator<P> {
@@ -125,7 +168,89 @@ impl<P: Provider> AmountCalculator<P> {
     /// let premium_calculator = AmountCalculator::new(root_provider, SemioscanConfig::minimal());
     /// ```
     pub fn new(provider: P, config: SemioscanConfig) -> Self {
-        Self { provider, config }
+        Self {
+            provider,
+            config,
+            wrapped_native_tokens: HashSet::new(),
+            transfer_tax: TransferTaxMode::default(),
+            transfer_tax_samples: 3,
+            log_source: None,
+        }
+    }
+
+    /// Fetch logs from `source` instead of `eth_getLogs` on the provider
+    ///
+    /// Covers the transfer scan and the blocks sampled for transfer tax
+    /// detection; the sampled balances still come from the provider.
+    pub fn with_log_source(mut self, source: Arc<dyn LogSource>) -> Self {
+        self.log_source = Some(source);
+        self
+    }
+
+    /// Treat `Deposit`/`Withdrawal` events on these tokens as mints and burns
+    ///
+    /// For a registered token, a transfer query from [`Address::ZERO`] also
+    /// counts `Deposit` events crediting the recipient, and a query to
+    /// [`Address::ZERO`] also counts `Withdrawal` events debiting the sender.
+    /// Queries between two non-zero addresses are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{AmountCalculator, SemioscanConfig};
+    /// use alloy_primitives::address;
+    ///
+    /// let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    /// let calculator = AmountCalculator::new(provider, SemioscanConfig::default())
+    ///     .with_wrapped_native_tokens([weth]);
+    ///
+    /// // Total WETH minted to `user` by wrapping ETH
+    /// let wrapped = calculator
+    ///     .calculate_transfer_amount_between_blocks(chain, Address::ZERO, user, weth, start, end)
+    ///     .await?;
+    /// ```
+    pub fn with_wrapped_native_tokens(mut self, tokens: impl IntoIterator<Item = Address>) -> Self {
+        self.wrapped_native_tokens.extend(tokens);
+        self
+    }
+
+    /// Check whether recipients receive what `Transfer` events report
+    ///
+    /// With [`TransferTaxMode::Detect`] or [`TransferTaxMode::Adjust`], each
+    /// calculation samples a few blocks containing matched transfers and
+    /// compares the recipient's `balanceOf` change across each block with the
+    /// net amount its events moved. The ratio is reported as
+    /// [`AmountResult::transfer_tax`]; `Adjust` also scales the amount by it.
+    ///
+    /// Sampling costs two `eth_getLogs` and two historical `eth_call`s per
+    /// block, and needs an archive node for older ranges. Rebasing tokens show
+    /// up the same way as fee-on-transfer tokens, since their balances drift
+    /// without events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{AmountCalculator, SemioscanConfig, TransferTaxMode};
+    ///
+    /// let calculator = AmountCalculator::new(provider, SemioscanConfig::default())
+    ///     .with_transfer_tax(TransferTaxMode::Adjust);
+    ///
+    /// let result = calculator
+    ///     .calculate_transfer_amount_between_blocks(chain, from, to, token, start, end)
+    ///     .await?;
+    /// if let Some(tax) = result.transfer_tax.filter(|tax| !tax.is_exact()) {
+    ///     println!("recipient kept {} bps of each transfer", tax.received_bps);
+    /// }
+    /// ```
+    pub fn with_transfer_tax(mut self, mode: TransferTaxMode) -> Self {
+        self.transfer_tax = mode;
+        self
+    }
+
+    /// Number of blocks sampled for transfer tax detection (default 3)
+    pub fn with_transfer_tax_samples(mut self, samples: usize) -> Self {
+        self.transfer_tax_samples = samples;
+        self
     }
 
     /// Calculate total ERC-20 token transfers from one address to another
@@ -197,10 +322,15 @@ impl<P: Provider> AmountCalculator<P> {
             to,
             token,
             amount: TokenAmount::ZERO,
+            transfer_tax: None,
         };
+        let mut blocks = Vec::new();
 
         // Create a scanner with the provider and config
-        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let mut scanner = EventScanner::new(&self.provider, self.config.clone());
+        if let Some(source) = &self.log_source {
+            scanner = scanner.with_log_source(source.clone());
+        }
 
         // Build a filter for transfers between specific addresses
         // The filter builder handles the topic1/topic2 encoding internally
@@ -215,6 +345,7 @@ impl<P: Provider> AmountCalculator<P> {
 
         // Process the logs to calculate total amount
         for log in logs {
+            blocks.extend(log.block_number);
             match Transfer::decode_log(&log.into()) {
                 Ok(event) => {
                     info!(
@@ -233,6 +364,46 @@ impl<P: Provider> AmountCalculator<P> {
             }
         }
 
+        if self.wrapped_native_tokens.contains(&token) {
+            if let Some(filter) = wrapped_native_filter(token, from, to) {
+                let logs = scanner.scan(chain, filter, from_block, to_block).await?;
+                for log in logs {
+                    match wrapped_native_as_transfer(&log) {
+                        Ok(Some(event)) => {
+                            result.amount = result.amount + TokenAmount::from(event.value);
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            warn!(error = ?e, "Failed to decode wrapped native token log");
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.transfer_tax != TransferTaxMode::Ignore && !blocks.is_empty() {
+            blocks.sort_unstable();
+            let samples = sample_blocks(&blocks, self.transfer_tax_samples);
+            let rpc_logs = RpcLogSource::<_, Ethereum>::new(&self.provider);
+            let logs: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
+            result.transfer_tax =
+                estimate_transfer_tax(&self.provider, logs, token, to, &samples).await?;
+            if let Some(tax) = result.transfer_tax {
+                if !tax.is_exact() {
+                    warn!(
+                        chain = ?chain,
+                        to = ?to,
+                        token = ?token,
+                        received_bps = tax.received_bps,
+                        "Recipient received a different amount than transfer events report"
+                    );
+                }
+                if self.transfer_tax == TransferTaxMode::Adjust {
+                    result.amount = tax.apply(result.amount);
+                }
+            }
+        }
+
         info!(
             chain = ?chain,
             to = ?to,
@@ -245,6 +416,60 @@ impl<P: Provider> AmountCalculator<P> {
     }
 }
 
+/// Convert a WETH-style `Deposit` or `Withdrawal` log into the equivalent mint or burn
+///
+/// `Deposit(dst, wad)` becomes `Transfer(0x0, dst, wad)` and
+/// `Withdrawal(src, wad)` becomes `Transfer(src, 0x0, wad)`. Returns `Ok(None)`
+/// for any other event.
+///
+/// # Errors
+///
+/// Returns [`EventProcessingError::DecodeFailed`] if the log has a
+/// `Deposit`/`Withdrawal` signature but cannot be decoded.
+pub fn wrapped_native_as_transfer(log: &Log) -> Result<Option<Transfer>, EventProcessingError> {
+    let Some(&topic0) = log.topic0() else {
+        return Ok(None);
+    };
+
+    if topic0 == Deposit::SIGNATURE_HASH {
+        let event = Deposit::decode_log(&log.inner)
+            .map_err(|e| EventProcessingError::decode_failed(format!("Deposit: {e}")))?;
+        Ok(Some(Transfer {
+            from: Address::ZERO,
+            to: event.dst,
+            value: event.wad,
+        }))
+    } else if topic0 == Withdrawal::SIGNATURE_HASH {
+        let event = Withdrawal::decode_log(&log.inner)
+            .map_err(|e| EventProcessingError::decode_failed(format!("Withdrawal: {e}")))?;
+        Ok(Some(Transfer {
+            from: event.src,
+            to: Address::ZERO,
+            value: event.wad,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Filter for the wrap/unwrap events equivalent to a `from -> to` mint or burn query
+fn wrapped_native_filter(token: Address, from: Address, to: Address) -> Option<Filter> {
+    let filter = Filter::new().address(token);
+    match (from.is_zero(), to.is_zero()) {
+        (true, false) => Some(
+            filter
+                .event_signature(Deposit::SIGNATURE_HASH)
+                .topic1(to.into_word()),
+        ),
+        (false, true) => Some(
+            filter
+                .event_signature(Withdrawal::SIGNATURE_HASH)
+                .topic1(from.into_word()),
+        ),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,6 +488,7 @@ mod tests {
             to,
             token,
             amount: TokenAmount::ZERO,
+            transfer_tax: None,
         };
 
         assert_eq!(result.chain, chain);
@@ -334,6 +560,7 @@ mod tests {
             to,
             token,
             amount: TokenAmount::ZERO,
+            transfer_tax: None,
         };
 
         // Add amounts using the Add trait (uses saturating_add internally)
@@ -356,6 +583,7 @@ mod tests {
             to,
             token,
             amount: TokenAmount::from(U256::MAX - U256::from(100u64)),
+            transfer_tax: None,
         };
 
         // Add amount that would overflow - should saturate at U256::MAX
@@ -375,6 +603,7 @@ mod tests {
             to,
             token,
             amount: TokenAmount::ZERO,
+            transfer_tax: None,
         };
 
         // Test with 18-decimal token (like WETH): 1 ETH = 1e18 wei
@@ -387,4 +616,93 @@ mod tests {
             TokenAmount::from(2_000_000_000_000_000_000u64)
         ); // 2 ETH
     }
+
+    #[test]
+    fn test_amount_result_serde_round_trip() {
+        let result = AmountResult {
+            chain: NamedChain::Arbitrum,
+            to: address!("1111111111111111111111111111111111111111"),
+            token: address!("2222222222222222222222222222222222222222"),
+            amount: TokenAmount::from(1_000_000u64),
+            transfer_tax: None,
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        let keys: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, vec!["amount", "chain", "to", "token"]);
+
+        let decoded: AmountResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    fn weth_log(event: &impl SolEvent) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                data: event.encode_log_data(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_weth_deposit_is_treated_as_mint() {
+        use alloy_primitives::U256;
+
+        let user = address!("1111111111111111111111111111111111111111");
+        let log = weth_log(&Deposit {
+            dst: user,
+            wad: U256::from(5u64),
+        });
+
+        let transfer = wrapped_native_as_transfer(&log).unwrap().unwrap();
+        assert_eq!(transfer.from, Address::ZERO);
+        assert_eq!(transfer.to, user);
+        assert_eq!(transfer.value, U256::from(5u64));
+    }
+
+    #[test]
+    fn test_weth_withdrawal_is_treated_as_burn() {
+        use alloy_primitives::U256;
+
+        let user = address!("1111111111111111111111111111111111111111");
+        let log = weth_log(&Withdrawal {
+            src: user,
+            wad: U256::from(7u64),
+        });
+
+        let transfer = wrapped_native_as_transfer(&log).unwrap().unwrap();
+        assert_eq!(transfer.from, user);
+        assert_eq!(transfer.to, Address::ZERO);
+        assert_eq!(transfer.value, U256::from(7u64));
+    }
+
+    #[test]
+    fn test_regular_transfer_is_not_a_wrap() {
+        use alloy_primitives::U256;
+
+        let log = weth_log(&Transfer {
+            from: address!("1111111111111111111111111111111111111111"),
+            to: address!("2222222222222222222222222222222222222222"),
+            value: U256::from(1u64),
+        });
+
+        assert!(wrapped_native_as_transfer(&log).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_wrapped_native_filter_only_applies_to_mints_and_burns() {
+        let token = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let user = address!("1111111111111111111111111111111111111111");
+        let other = address!("2222222222222222222222222222222222222222");
+
+        let mint = wrapped_native_filter(token, Address::ZERO, user).unwrap();
+        assert!(mint.topics[0].matches(&Deposit::SIGNATURE_HASH));
+        assert!(mint.topics[1].matches(&user.into_word()));
+
+        let burn = wrapped_native_filter(token, user, Address::ZERO).unwrap();
+        assert!(burn.topics[0].matches(&Withdrawal::SIGNATURE_HASH));
+
+        assert!(wrapped_native_filter(token, user, other).is_none());
+    }
 }
@@ -0,0 +1,507 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Firehose block ingestion
+//!
+//! Backfills over JSON-RPC pay for every `eth_getLogs` chunk and every receipt.
+//! A Firehose endpoint streams whole flat blocks (header, transactions,
+//! receipts and logs) over gRPC instead. [`FirehoseClient`] reads a block range
+//! from the `sf.firehose.v2.Stream/Blocks` service and exposes it two ways:
+//!
+//! - as a [`LogSource`], so scanners and calculators configured with
+//!   `with_log_source` fetch logs from Firehose, matching them against each
+//!   [`Filter`] locally;
+//! - as [`FlatBlock`]s via [`FirehoseClient::blocks`], carrying the receipt data
+//!   (gas used, effective gas price, status) needed to compute gas without
+//!   `eth_getTransactionReceipt`.
+//!
+//! Only final (irreversible) blocks are requested, so the stream never has to
+//! be rolled back. Only the subset of the `sf.ethereum.type.v2` schema semioscan
+//! needs is decoded; unknown fields are skipped.
+//!
+//! Enabled with the `firehose` feature.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use semioscan::firehose::FirehoseClient;
+//! use semioscan::{EventScanner, SemioscanConfig};
+//!
+//! let firehose = FirehoseClient::connect("https://mainnet.eth.streamingfast.io:443")
+//!     .await?
+//!     .with_api_token(token);
+//!
+//! let scanner = EventScanner::new(provider, SemioscanConfig::default())
+//!     .with_log_source(Arc::new(firehose.clone()));
+//!
+//! for block in firehose.blocks(20_000_000, 20_000_099).await? {
+//!     println!("{}: {} transactions", block.number, block.transactions.len());
+//! }
+//! ```
+
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, TxHash, B256};
+use alloy_rpc_types::{Filter, Log};
+use alloy_transport::{TransportError, TransportErrorKind};
+use async_trait::async_trait;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tracing::debug;
+
+use crate::events::source::LogSource;
+use crate::types::gas::{GasAmount, GasPrice};
+
+const BLOCKS_PATH: &str = "/sf.firehose.v2.Stream/Blocks";
+const ETHEREUM_BLOCK_TYPE: &str = "type.googleapis.com/sf.ethereum.type.v2.Block";
+
+/// A block as streamed from Firehose, reduced to what semioscan uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatBlock {
+    pub number: BlockNumber,
+    pub hash: BlockHash,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    /// `None` before London
+    pub base_fee_per_gas: Option<GasPrice>,
+    /// Transactions in block order
+    pub transactions: Vec<FlatTransaction>,
+}
+
+/// A transaction with its receipt data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatTransaction {
+    pub hash: TxHash,
+    pub index: u64,
+    pub from: Address,
+    /// `None` for contract creations
+    pub to: Option<Address>,
+    pub gas_used: GasAmount,
+    /// Price per gas actually paid, as reported by Firehose
+    pub effective_gas_price: GasPrice,
+    /// Whether the transaction succeeded
+    pub success: bool,
+    /// Logs, with block and transaction fields filled in. Empty for failed
+    /// transactions.
+    pub logs: Vec<Log>,
+}
+
+impl FlatBlock {
+    /// Every log in the block, in order
+    pub fn logs(&self) -> impl Iterator<Item = &Log> {
+        self.transactions.iter().flat_map(|tx| tx.logs.iter())
+    }
+
+    fn from_proto(block: pb::Block) -> Self {
+        let header = block.header.unwrap_or_default();
+        let hash = B256::try_from(block.hash.as_slice()).unwrap_or_default();
+        let timestamp = header.timestamp.map_or(0, |ts| ts.seconds.max(0) as u64);
+        let transactions = block
+            .transaction_traces
+            .into_iter()
+            .map(|trace| FlatTransaction::from_proto(trace, block.number, hash, timestamp))
+            .collect();
+        Self {
+            number: block.number,
+            hash,
+            timestamp,
+            base_fee_per_gas: header
+                .base_fee_per_gas
+                .map(|base_fee| GasPrice::from(base_fee.to_u256())),
+            transactions,
+        }
+    }
+}
+
+impl FlatTransaction {
+    fn from_proto(
+        trace: pb::TransactionTrace,
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        block_timestamp: u64,
+    ) -> Self {
+        let hash = B256::try_from(trace.hash.as_slice()).unwrap_or_default();
+        let success = trace.status == pb::STATUS_SUCCEEDED;
+        let logs = match (success, trace.receipt) {
+            (true, Some(receipt)) => receipt
+                .logs
+                .into_iter()
+                .map(|log| Log {
+                    inner: alloy_primitives::Log::new_unchecked(
+                        Address::try_from(log.address.as_slice()).unwrap_or_default(),
+                        log.topics
+                            .iter()
+                            .map(|topic| B256::try_from(topic.as_slice()).unwrap_or_default())
+                            .collect(),
+                        Bytes::from(log.data),
+                    ),
+                    block_hash: Some(block_hash),
+                    block_number: Some(block_number),
+                    block_timestamp: Some(block_timestamp),
+                    transaction_hash: Some(hash),
+                    transaction_index: Some(u64::from(trace.index)),
+                    log_index: Some(u64::from(log.block_index)),
+                    removed: false,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            hash,
+            index: u64::from(trace.index),
+            from: Address::try_from(trace.from.as_slice()).unwrap_or_default(),
+            to: Address::try_from(trace.to.as_slice()).ok(),
+            gas_used: GasAmount::new(trace.gas_used),
+            effective_gas_price: GasPrice::from(
+                trace
+                    .gas_price
+                    .map(|price| price.to_u256())
+                    .unwrap_or_default(),
+            ),
+            success,
+            logs,
+        }
+    }
+}
+
+/// Client for a Firehose `sf.firehose.v2.Stream` endpoint serving Ethereum blocks
+#[derive(Clone)]
+pub struct FirehoseClient {
+    channel: Channel,
+    api_token: Option<String>,
+}
+
+impl std::fmt::Debug for FirehoseClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FirehoseClient")
+            .field("api_token", &self.api_token.as_ref().map(|_| "<redacted>"))
+            .finish_non_exhaustive()
+    }
+}
+
+impl FirehoseClient {
+    /// Connect to `endpoint`, e.g. `https://mainnet.eth.streamingfast.io:443`
+    ///
+    /// `https` endpoints use TLS with the bundled web PKI roots.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the endpoint is malformed or can't be reached.
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self, TransportError> {
+        let endpoint = endpoint.into();
+        let mut builder =
+            Endpoint::from_shared(endpoint.clone()).map_err(TransportErrorKind::custom)?;
+        if endpoint.starts_with("https://") {
+            builder = builder
+                .tls_config(ClientTlsConfig::new().with_webpki_roots())
+                .map_err(TransportErrorKind::custom)?;
+        }
+        let channel = builder
+            .connect()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        Ok(Self {
+            channel,
+            api_token: None,
+        })
+    }
+
+    /// Send `token` as a bearer token with every request
+    pub fn with_api_token(mut self, token: impl Into<String>) -> Self {
+        self.api_token = Some(token.into());
+        self
+    }
+
+    /// Final blocks `[from_block, to_block]`, in order
+    ///
+    /// # Errors
+    ///
+    /// Fails if the stream can't be opened or breaks, or if it carries
+    /// something other than Ethereum blocks.
+    pub async fn blocks(
+        &self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<FlatBlock>, TransportError> {
+        let mut request = tonic::Request::new(pb::Request {
+            start_block_num: i64::try_from(from_block).map_err(TransportErrorKind::custom)?,
+            stop_block_num: to_block,
+            final_blocks_only: true,
+            ..Default::default()
+        });
+        if let Some(token) = &self.api_token {
+            let value = format!("Bearer {token}")
+                .parse()
+                .map_err(TransportErrorKind::custom)?;
+            request.metadata_mut().insert("authorization", value);
+        }
+
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready().await.map_err(TransportErrorKind::custom)?;
+        let mut stream = grpc
+            .server_streaming(
+                request,
+                PathAndQuery::from_static(BLOCKS_PATH),
+                tonic::codec::ProstCodec::<pb::Request, pb::Response>::default(),
+            )
+            .await
+            .map_err(TransportErrorKind::custom)?
+            .into_inner();
+
+        let mut blocks = Vec::new();
+        while let Some(response) = stream.message().await.map_err(TransportErrorKind::custom)? {
+            let Some(any) = response.block else {
+                continue;
+            };
+            if any.type_url != ETHEREUM_BLOCK_TYPE {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "Firehose endpoint streams {}, not Ethereum blocks",
+                    any.type_url
+                )));
+            }
+            let block = <pb::Block as prost::Message>::decode(any.value.as_slice())
+                .map_err(TransportErrorKind::custom)?;
+            blocks.push(FlatBlock::from_proto(block));
+        }
+
+        debug!(
+            from_block,
+            to_block,
+            blocks = blocks.len(),
+            "Streamed Firehose blocks"
+        );
+        Ok(blocks)
+    }
+}
+
+#[async_trait]
+impl LogSource for FirehoseClient {
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+        let (Some(from_block), Some(to_block)) = (filter.get_from_block(), filter.get_to_block())
+        else {
+            return Err(TransportErrorKind::custom_str(
+                "Firehose queries need a numeric block range",
+            ));
+        };
+        let blocks = self.blocks(from_block, to_block).await?;
+        Ok(matching_logs(&blocks, filter))
+    }
+}
+
+/// Logs in `blocks` matching `filter`'s addresses and topics
+fn matching_logs(blocks: &[FlatBlock], filter: &Filter) -> Vec<Log> {
+    blocks
+        .iter()
+        .flat_map(FlatBlock::logs)
+        .filter(|log| filter.matches(&log.inner))
+        .cloned()
+        .collect()
+}
+
+/// Hand-written subset of the `sf.firehose.v2` and `sf.ethereum.type.v2`
+/// protobuf schemas
+mod pb {
+    use alloy_primitives::U256;
+
+    pub(super) const STATUS_SUCCEEDED: i32 = 1;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct Request {
+        #[prost(int64, tag = "1")]
+        pub start_block_num: i64,
+        #[prost(string, tag = "2")]
+        pub cursor: String,
+        #[prost(uint64, tag = "3")]
+        pub stop_block_num: u64,
+        #[prost(bool, tag = "4")]
+        pub final_blocks_only: bool,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct Response {
+        #[prost(message, optional, tag = "1")]
+        pub block: Option<Any>,
+        #[prost(string, tag = "10")]
+        pub cursor: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct Any {
+        #[prost(string, tag = "1")]
+        pub type_url: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub value: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct Block {
+        #[prost(bytes = "vec", tag = "2")]
+        pub hash: Vec<u8>,
+        #[prost(uint64, tag = "3")]
+        pub number: u64,
+        #[prost(message, optional, tag = "5")]
+        pub header: Option<BlockHeader>,
+        #[prost(message, repeated, tag = "10")]
+        pub transaction_traces: Vec<TransactionTrace>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct BlockHeader {
+        #[prost(uint64, tag = "9")]
+        pub number: u64,
+        #[prost(message, optional, tag = "12")]
+        pub timestamp: Option<Timestamp>,
+        #[prost(message, optional, tag = "18")]
+        pub base_fee_per_gas: Option<BigInt>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct Timestamp {
+        #[prost(int64, tag = "1")]
+        pub seconds: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct BigInt {
+        #[prost(bytes = "vec", tag = "1")]
+        pub bytes: Vec<u8>,
+    }
+
+    impl BigInt {
+        /// Big-endian bytes as a `U256`, saturating if wider
+        pub(super) fn to_u256(&self) -> U256 {
+            U256::try_from_be_slice(&self.bytes).unwrap_or(U256::MAX)
+        }
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct TransactionTrace {
+        #[prost(bytes = "vec", tag = "1")]
+        pub to: Vec<u8>,
+        #[prost(message, optional, tag = "3")]
+        pub gas_price: Option<BigInt>,
+        #[prost(uint64, tag = "10")]
+        pub gas_used: u64,
+        #[prost(uint32, tag = "20")]
+        pub index: u32,
+        #[prost(bytes = "vec", tag = "21")]
+        pub hash: Vec<u8>,
+        #[prost(bytes = "vec", tag = "22")]
+        pub from: Vec<u8>,
+        #[prost(int32, tag = "30")]
+        pub status: i32,
+        #[prost(message, optional, tag = "31")]
+        pub receipt: Option<TransactionReceipt>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct TransactionReceipt {
+        #[prost(message, repeated, tag = "4")]
+        pub logs: Vec<Log>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct Log {
+        #[prost(bytes = "vec", tag = "1")]
+        pub address: Vec<u8>,
+        #[prost(bytes = "vec", repeated, tag = "2")]
+        pub topics: Vec<Vec<u8>>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub data: Vec<u8>,
+        #[prost(uint32, tag = "6")]
+        pub block_index: u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::definitions::Transfer;
+    use alloy_primitives::address;
+    use alloy_sol_types::SolEvent;
+    use prost::Message;
+
+    const USDC: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const DAI: Address = address!("6B175474E89094C44Da98b954EedeAC495271d0F");
+
+    fn trace(
+        index: u32,
+        status: i32,
+        log_address: Address,
+        block_index: u32,
+    ) -> pb::TransactionTrace {
+        pb::TransactionTrace {
+            to: log_address.to_vec(),
+            gas_price: Some(pb::BigInt {
+                bytes: vec![0x3b, 0x9a, 0xca, 0x00],
+            }),
+            gas_used: 50_000,
+            index,
+            hash: B256::repeat_byte(index as u8 + 1).to_vec(),
+            from: address!("1111111111111111111111111111111111111111").to_vec(),
+            status,
+            receipt: Some(pb::TransactionReceipt {
+                logs: vec![pb::Log {
+                    address: log_address.to_vec(),
+                    topics: vec![Transfer::SIGNATURE_HASH.to_vec(), B256::ZERO.to_vec()],
+                    data: vec![1],
+                    block_index,
+                }],
+            }),
+        }
+    }
+
+    fn encoded_block() -> Vec<u8> {
+        pb::Block {
+            hash: B256::repeat_byte(0xbb).to_vec(),
+            number: 100,
+            header: Some(pb::BlockHeader {
+                number: 100,
+                timestamp: Some(pb::Timestamp {
+                    seconds: 1_700_000_000,
+                }),
+                base_fee_per_gas: Some(pb::BigInt { bytes: vec![0x64] }),
+            }),
+            transaction_traces: vec![
+                trace(0, pb::STATUS_SUCCEEDED, USDC, 0),
+                trace(1, pb::STATUS_SUCCEEDED, DAI, 1),
+                // Reverted: its receipt logs never happened
+                trace(2, 3, USDC, 2),
+            ],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn flat_blocks_carry_receipt_data_and_only_successful_logs() {
+        let block = FlatBlock::from_proto(pb::Block::decode(encoded_block().as_slice()).unwrap());
+
+        assert_eq!(block.number, 100);
+        assert_eq!(block.timestamp, 1_700_000_000);
+        assert_eq!(block.base_fee_per_gas, Some(GasPrice::new(100)));
+        assert_eq!(block.transactions.len(), 3);
+
+        let first = &block.transactions[0];
+        assert!(first.success);
+        assert_eq!(first.gas_used, GasAmount::new(50_000));
+        assert_eq!(first.effective_gas_price, GasPrice::from_gwei(1));
+        assert_eq!(first.logs[0].block_number, Some(100));
+        assert_eq!(first.logs[0].transaction_hash, Some(first.hash));
+
+        assert!(!block.transactions[2].success);
+        assert_eq!(block.logs().count(), 2);
+    }
+
+    #[test]
+    fn logs_are_matched_against_filter_locally() {
+        let block = FlatBlock::from_proto(pb::Block::decode(encoded_block().as_slice()).unwrap());
+        let filter = Filter::new()
+            .address(USDC)
+            .event_signature(Transfer::SIGNATURE_HASH);
+
+        let logs = matching_logs(&[block], &filter);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address(), USDC);
+        assert_eq!(logs[0].log_index, Some(0));
+    }
+}
@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! EIP-1559 base fee lookups
+//!
+//! The base fee is part of each block header. Multiplied by a transaction's gas
+//! used, it gives the portion of the execution cost that was burned (or, on OP
+//! Stack chains, paid to the base fee vault); the rest of the effective gas
+//! price is the priority fee paid to the block producer. See
+//! [`GasAmount::fee_split`](crate::GasAmount::fee_split).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use semioscan::gas::base_fee::get_base_fee_at_block;
+//!
+//! let base_fee = get_base_fee_at_block(&provider, 19_000_000).await?;
+//! println!("Base fee: {base_fee}");
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+
+use alloy_network::Network;
+use alloy_primitives::{BlockNumber, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+use futures::future::join_all;
+use tracing::warn;
+
+use crate::cache::header::{fetch_header, HeaderCache};
+use crate::errors::RpcError;
+use crate::provider::ProviderCapabilities;
+use crate::types::gas::GasPrice;
+use crate::types::warnings::{push_warning, ScanWarning};
+
+/// Get the base fee per gas of a specific block.
+///
+/// Blocks from before the London hard fork have no base fee and return zero,
+/// so their whole execution cost counts as priority fee.
+pub async fn get_base_fee_at_block<N, P>(
+    provider: &P,
+    block_number: BlockNumber,
+) -> Result<GasPrice, RpcError>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let header = fetch_header(provider, block_number).await?;
+    Ok(header.base_fee_per_gas.unwrap_or_default())
+}
+
+/// Base fees looked up by [`get_base_fees`]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct BaseFees {
+    /// Base fee of every block that could be looked up
+    pub(crate) fees: HashMap<BlockNumber, GasPrice>,
+    /// Blocks whose base fee couldn't be fetched, with the reason
+    pub(crate) missing: Vec<(BlockNumber, String)>,
+}
+
+impl BaseFees {
+    /// Base fee of `block_number`, recording a
+    /// [`ScanWarning::MissingBaseFee`] in `warnings` if it couldn't be fetched
+    pub(crate) fn for_block(
+        &self,
+        block_number: BlockNumber,
+        warnings: &mut Vec<ScanWarning>,
+    ) -> Option<GasPrice> {
+        let base_fee = self.fees.get(&block_number).copied();
+        if base_fee.is_none() {
+            if let Some((_, reason)) = self
+                .missing
+                .iter()
+                .find(|(block, _)| *block == block_number)
+            {
+                push_warning(
+                    warnings,
+                    ScanWarning::missing_base_fee(block_number, reason),
+                );
+            }
+        }
+        base_fee
+    }
+}
+
+/// Base fees for a set of blocks
+///
+/// Blocks already in `headers` are answered from it. When `capabilities` show
+/// `eth_feeHistory` can span the blocks, they come from that single request,
+/// and any block the response doesn't cover falls back to its header. Otherwise
+/// each block's header is fetched once, concurrently, and added to `headers`.
+/// Blocks whose base fee can't be fetched are returned in
+/// [`BaseFees::missing`], so their transactions are reported without a fee
+/// split rather than failing the calculation.
+pub(crate) async fn get_base_fees<N, P>(
+    provider: &P,
+    blocks: impl IntoIterator<Item = BlockNumber>,
+    capabilities: Option<&ProviderCapabilities>,
+    headers: Option<&HeaderCache>,
+) -> BaseFees
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let mut base_fees = BaseFees::default();
+    let mut blocks: BTreeSet<BlockNumber> = blocks
+        .into_iter()
+        .filter(|block_number| {
+            let cached = headers.and_then(|headers| headers.get(*block_number));
+            if let Some(header) = cached {
+                base_fees
+                    .fees
+                    .insert(*block_number, header.base_fee_per_gas.unwrap_or_default());
+            }
+            cached.is_none()
+        })
+        .collect();
+    let (Some(&first), Some(&last)) = (blocks.first(), blocks.last()) else {
+        return base_fees;
+    };
+
+    let span = last - first + 1;
+    if capabilities.is_some_and(|capabilities| capabilities.fee_history_covers(span)) {
+        match provider
+            .get_fee_history(span, BlockNumberOrTag::Number(last), &[])
+            .await
+        {
+            Ok(history) => blocks.retain(|&block_number| {
+                let base_fee = block_number
+                    .checked_sub(history.oldest_block)
+                    .and_then(|offset| usize::try_from(offset).ok())
+                    .and_then(|offset| history.base_fee_per_gas.get(offset));
+                match base_fee {
+                    Some(base_fee) => {
+                        base_fees
+                            .fees
+                            .insert(block_number, GasPrice::from(U256::from(*base_fee)));
+                        false
+                    }
+                    None => true,
+                }
+            }),
+            Err(error) => warn!(
+                first,
+                last,
+                %error,
+                "eth_feeHistory failed, fetching base fees from block headers"
+            ),
+        }
+    }
+
+    let lookups = blocks.into_iter().map(|block_number| async move {
+        let header = match headers {
+            Some(headers) => headers.get_or_fetch(provider, block_number).await,
+            None => fetch_header(provider, block_number).await,
+        };
+        (
+            block_number,
+            header.map(|header| header.base_fee_per_gas.unwrap_or_default()),
+        )
+    });
+
+    for (block_number, base_fee) in join_all(lookups).await {
+        match base_fee {
+            Ok(base_fee) => {
+                base_fees.fees.insert(block_number, base_fee);
+            }
+            Err(error) => {
+                warn!(
+                    block_number,
+                    %error,
+                    "Failed to fetch block base fee, skipping fee split for its transactions"
+                );
+                base_fees.missing.push((block_number, error.to_string()));
+            }
+        }
+    }
+    base_fees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_network::Ethereum;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_transport::mock::Asserter;
+
+    use crate::cache::header::CachedHeader;
+
+    #[tokio::test]
+    async fn test_base_fees_come_from_one_fee_history_request_when_supported() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&serde_json::json!({
+            "oldestBlock": "0x64",
+            "baseFeePerGas": ["0x1", "0x2", "0x3", "0x4"],
+            "gasUsedRatio": [0.5, 0.5, 0.5],
+        }));
+
+        let capabilities = ProviderCapabilities {
+            fee_history_max_blocks: Some(1024),
+            ..ProviderCapabilities::default()
+        };
+        let base_fees = get_base_fees(&provider, [102, 100, 102], Some(&capabilities), None).await;
+
+        assert_eq!(
+            base_fees.fees,
+            HashMap::from([(100, GasPrice::new(1)), (102, GasPrice::new(3))])
+        );
+        assert!(base_fees.missing.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cached_headers_are_not_fetched_again() {
+        // Any request would fail on the empty mock queue
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(Asserter::new());
+        let headers = HeaderCache::new();
+        headers.insert(CachedHeader {
+            number: 100,
+            hash: Default::default(),
+            timestamp: 1_700_000_000,
+            base_fee_per_gas: Some(GasPrice::new(7)),
+        });
+
+        let base_fees = get_base_fees(&provider, [100], None, Some(&headers)).await;
+
+        assert_eq!(base_fees.fees, HashMap::from([(100, GasPrice::new(7))]));
+        assert_eq!(headers.stats().hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unfetchable_blocks_are_returned_as_missing() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_failure_msg("header not available");
+
+        let base_fees = get_base_fees(&provider, [100], None, None).await;
+
+        assert!(base_fees.fees.is_empty());
+        assert_eq!(base_fees.missing.len(), 1);
+        assert_eq!(base_fees.missing[0].0, 100);
+    }
+
+    #[tokio::test]
+    async fn test_blocks_outside_the_fee_history_fall_back_to_headers() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        // The provider answers with fewer blocks than requested, so block 100
+        // is not covered and its header is fetched instead
+        asserter.push_success(&serde_json::json!({
+            "oldestBlock": "0x65",
+            "baseFeePerGas": ["0x2", "0x3"],
+            "gasUsedRatio": [0.5],
+        }));
+        asserter.push_failure_msg("header not available");
+
+        let capabilities = ProviderCapabilities {
+            fee_history_max_blocks: Some(1024),
+            ..ProviderCapabilities::default()
+        };
+        let base_fees = get_base_fees(&provider, [100, 101], Some(&capabilities), None).await;
+
+        assert_eq!(base_fees.fees, HashMap::from([(101, GasPrice::new(2))]));
+        assert_eq!(base_fees.missing.len(), 1);
+        assert_eq!(base_fees.missing[0].0, 100);
+    }
+}
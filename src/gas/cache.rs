@@ -61,10 +61,13 @@
 //! assert_eq!(gaps[2], (401, 500));
 //! ```
 
+use std::collections::{BTreeSet, HashMap};
+
 use alloy_chains::NamedChain;
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockHash, BlockNumber};
 
 use crate::cache::block_range::{BlockRangeCache, Mergeable};
+use crate::cache::finality::{Checkpoint, Reconciliation};
 use crate::gas::calculator::GasCostResult;
 
 // Implement Mergeable for GasCostResult
@@ -72,6 +75,7 @@ impl Mergeable for GasCostResult {
     fn merge(&mut self, other: &Self) {
         self.total_gas_cost = self.total_gas_cost + other.total_gas_cost;
         self.transaction_count += other.transaction_count;
+        self.transactions.extend(other.transactions.iter().cloned());
     }
 }
 
@@ -213,6 +217,56 @@ impl GasCache {
             .insert((from, to), start_block, end_block, result);
     }
 
+    /// Remove every entry for the address pair overlapping
+    /// `[start_block, end_block]`
+    ///
+    /// [`insert`](Self::insert) adds overlapping results together, so a
+    /// result recomputed for cached blocks must be inserted after removing
+    /// the entries it replaces, or those blocks are counted twice.
+    pub fn remove_range(
+        &mut self,
+        from: Address,
+        to: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) {
+        self.inner
+            .remove_overlapping(&(from, to), start_block, end_block);
+    }
+
+    /// Insert a result computed against unfinalized blocks
+    ///
+    /// Like [`insert`](Self::insert), but the entry is dropped by a later
+    /// [`settle`](Self::settle) if `checkpoint`'s block is reorged out.
+    pub fn insert_provisional(
+        &mut self,
+        from: Address,
+        to: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        result: GasCostResult,
+        checkpoint: Checkpoint,
+    ) {
+        self.inner
+            .insert_provisional((from, to), start_block, end_block, result, checkpoint);
+    }
+
+    /// Checkpoint blocks of provisional entries that are now final
+    pub fn pending_checkpoints(&self, finalized_block: BlockNumber) -> BTreeSet<BlockNumber> {
+        self.inner.pending_checkpoints(finalized_block)
+    }
+
+    /// Confirm or drop provisional entries given the canonical hashes of
+    /// final blocks
+    pub fn settle(&mut self, canonical: &HashMap<BlockNumber, BlockHash>) -> Reconciliation {
+        self.inner.settle(canonical)
+    }
+
+    /// Number of entries still waiting on finality
+    pub fn provisional_len(&self) -> usize {
+        self.inner.provisional_len()
+    }
+
     /// Calculate uncached block ranges (gaps) and return merged cached data
     ///
     /// This is the key method for incremental scanning. It analyzes which portions of
@@ -337,6 +391,34 @@ impl GasCache {
         self.inner.retain(|_, _, end_block| end_block >= min_block);
     }
 
+    /// Clear all cached entries that reach `first_invalid_block` or later
+    ///
+    /// Used after a reorg orphans blocks from `first_invalid_block` on: any
+    /// entry covering one of them was computed from logs no longer canonical.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use semioscan::{GasCache, GasCostResult};
+    /// use alloy_chains::NamedChain;
+    /// use alloy_primitives::Address;
+    ///
+    /// let mut cache = GasCache::default();
+    /// let from = Address::ZERO;
+    /// let to = Address::ZERO;
+    ///
+    /// cache.insert(from, to, 100, 200, GasCostResult::new(NamedChain::Mainnet, from, to));
+    /// cache.insert(from, to, 500, 600, GasCostResult::new(NamedChain::Mainnet, from, to));
+    ///
+    /// // Blocks from 550 on were reorged out
+    /// cache.clear_from_block(550);
+    /// assert_eq!(cache.len(), 1); // Only [100, 200] remains
+    /// ```
+    pub fn clear_from_block(&mut self, first_invalid_block: BlockNumber) {
+        self.inner
+            .retain(|_, _, end_block| end_block < first_invalid_block);
+    }
+
     /// Get the total number of cached entries
     ///
     /// # Example
@@ -21,7 +21,7 @@
 //!     .get_gas_cost(chain_id, from_addr, to_addr, start_block, end_block)
 //!     .await?;
 //!
-//! println!("Total gas cost: {} wei", result.total_gas_cost);
+//! println!("Total gas cost: {} wei", result.total_gas_cost.as_u256());
 //! println!("Transactions: {}", result.transaction_count);
 //! ```
 
@@ -29,24 +29,39 @@ use std::sync::Arc;
 
 use alloy_chains::NamedChain;
 use alloy_network::Network;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, U256};
 use alloy_provider::Provider;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
+use crate::cache::deployment::DeploymentCache;
+use crate::cache::finality::FinalityTracker;
+use crate::cache::header::HeaderCache;
+use crate::cache::transaction::TransactionCache;
 use crate::config::SemioscanConfig;
+use crate::errors::{GasCalculationError, RpcError};
+use crate::events::source::LogSource;
 use crate::gas::cache::GasCache;
-use crate::retrieval::DecimalPrecision;
+use crate::gas::percentiles::PercentileCache;
+use crate::provider::capabilities::CapabilitySource;
+use crate::provider::{network_type_for_chain, CapabilityCache, NetworkType, ProviderCapabilities};
 use crate::types::config::TransactionCount;
-use crate::types::fees::L1DataFee;
+use crate::types::display::DisplayOptions;
+use crate::types::fees::{L1DataFee, Percentage};
 use crate::types::gas::{BlobCount, BlobGasPrice, GasAmount, GasBreakdown, GasPrice};
+use crate::types::warnings::{extend_warnings, ScanWarning};
 use crate::types::wei::WeiAmount;
 
 /// Gas data for a single transaction
 ///
 /// This enum represents gas costs for either L1 or L2 transactions. L2 transactions
 /// include additional L1 data fees that are automatically included in calculations.
-#[derive(Debug, Clone)]
+///
+/// Serialized with a `network` tag of `"l1"` or `"l2"` next to the variant's fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "network", rename_all = "snake_case")]
 pub enum GasForTx {
     /// L1 (Ethereum) transaction gas data
     L1(L1Gas),
@@ -54,6 +69,21 @@ pub enum GasForTx {
     L2(L2Gas),
 }
 
+impl GasForTx {
+    /// Attach the base fee of the transaction's block
+    ///
+    /// With a base fee, the breakdown splits the execution cost into the
+    /// base fee burned and the priority fee paid.
+    #[must_use]
+    pub fn with_base_fee(mut self, base_fee_per_gas: Option<GasPrice>) -> Self {
+        match &mut self {
+            Self::L1(gas) => gas.base_fee_per_gas = base_fee_per_gas,
+            Self::L2(gas) => gas.base_fee_per_gas = base_fee_per_gas,
+        }
+        self
+    }
+}
+
 impl From<(U256, U256)> for GasForTx {
     fn from((gas_used, effective_gas_price): (U256, U256)) -> Self {
         Self::L1(L1Gas::from((gas_used, effective_gas_price)))
@@ -66,26 +96,48 @@ impl From<(U256, U256, U256)> for GasForTx {
     }
 }
 
+/// Base fee and priority fee portions of an execution cost, zero when the base fee is unknown
+fn fee_split(
+    gas_used: GasAmount,
+    effective_gas_price: GasPrice,
+    base_fee_per_gas: Option<GasPrice>,
+) -> (U256, U256) {
+    base_fee_per_gas.map_or((U256::ZERO, U256::ZERO), |base_fee| {
+        gas_used.fee_split(effective_gas_price, base_fee)
+    })
+}
+
 /// Gas data for L1 (Ethereum) transactions
 ///
 /// L1 transactions have a gas cost calculation that may include blob gas (EIP-4844):
 /// `total_cost = (gas_used * effective_gas_price) + blob_gas_cost`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct L1Gas {
     /// Amount of gas consumed by the transaction
+    #[serde(rename = "gas_used")]
     pub gas_used: GasAmount,
     /// Effective gas price paid per unit of gas (in wei)
+    #[serde(rename = "effective_gas_price")]
     pub effective_gas_price: GasPrice,
     /// Number of blobs in this transaction (0 for non-EIP-4844)
+    #[serde(rename = "blob_count")]
     pub blob_count: BlobCount,
     /// Blob gas price (0 for non-EIP-4844)
+    #[serde(rename = "blob_gas_price")]
     pub blob_gas_price: BlobGasPrice,
+    /// Base fee per gas of the transaction's block, if it was looked up
+    #[serde(
+        default,
+        rename = "base_fee_per_gas",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base_fee_per_gas: Option<GasPrice>,
 }
 
 impl L1Gas {
     /// Calculate execution gas cost (gas_used * effective_gas_price)
     pub fn execution_cost(&self) -> U256 {
-        self.gas_used * self.effective_gas_price
+        (self.gas_used * self.effective_gas_price).as_u256()
     }
 
     /// Calculate blob gas cost (blob_gas_used * blob_gas_price)
@@ -100,11 +152,18 @@ impl L1Gas {
 
     /// Convert to GasBreakdown for detailed analysis
     pub fn to_breakdown(&self) -> GasBreakdown {
+        let (base_fee_burned, priority_fee_paid) = fee_split(
+            self.gas_used,
+            self.effective_gas_price,
+            self.base_fee_per_gas,
+        );
         GasBreakdown::builder()
             .execution_gas_cost(self.execution_cost())
             .blob_gas_cost(self.blob_cost())
             .blob_count(self.blob_count)
             .blob_gas_price(self.blob_gas_price)
+            .base_fee_burned(base_fee_burned)
+            .priority_fee_paid(priority_fee_paid)
             .build()
     }
 }
@@ -116,6 +175,7 @@ impl From<(U256, U256)> for L1Gas {
             effective_gas_price: GasPrice::from(effective_gas_price),
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_per_gas: None,
         }
     }
 }
@@ -134,6 +194,7 @@ impl From<(U256, U256, BlobCount, BlobGasPrice)> for L1Gas {
             effective_gas_price: GasPrice::from(effective_gas_price),
             blob_count,
             blob_gas_price,
+            base_fee_per_gas: None,
         }
     }
 }
@@ -144,24 +205,36 @@ impl From<(U256, U256, BlobCount, BlobGasPrice)> for L1Gas {
 /// `total_cost = (gas_used * effective_gas_price) + l1_data_fee + blob_gas_cost`
 ///
 /// The L1 data fee covers the cost of posting transaction data to the L1 chain.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct L2Gas {
     /// Amount of L2 gas consumed by the transaction
+    #[serde(rename = "gas_used")]
     pub gas_used: GasAmount,
     /// Effective L2 gas price paid per unit of gas (in wei)
+    #[serde(rename = "effective_gas_price")]
     pub effective_gas_price: GasPrice,
     /// L1 data fee for posting transaction to L1 chain
+    #[serde(rename = "l1_data_fee")]
     pub l1_data_fee: L1DataFee,
     /// Number of blobs in this transaction (0 for non-EIP-4844)
+    #[serde(rename = "blob_count")]
     pub blob_count: BlobCount,
     /// Blob gas price (0 for non-EIP-4844)
+    #[serde(rename = "blob_gas_price")]
     pub blob_gas_price: BlobGasPrice,
+    /// Base fee per gas of the transaction's block, if it was looked up
+    #[serde(
+        default,
+        rename = "base_fee_per_gas",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base_fee_per_gas: Option<GasPrice>,
 }
 
 impl L2Gas {
     /// Calculate execution gas cost (gas_used * effective_gas_price)
     pub fn execution_cost(&self) -> U256 {
-        self.gas_used * self.effective_gas_price
+        (self.gas_used * self.effective_gas_price).as_u256()
     }
 
     /// Calculate blob gas cost (blob_gas_used * blob_gas_price)
@@ -178,12 +251,19 @@ impl L2Gas {
 
     /// Convert to GasBreakdown for detailed analysis
     pub fn to_breakdown(&self) -> GasBreakdown {
+        let (base_fee_burned, priority_fee_paid) = fee_split(
+            self.gas_used,
+            self.effective_gas_price,
+            self.base_fee_per_gas,
+        );
         GasBreakdown::builder()
             .execution_gas_cost(self.execution_cost())
             .blob_gas_cost(self.blob_cost())
             .l1_data_fee(self.l1_data_fee.as_u256())
             .blob_count(self.blob_count)
             .blob_gas_price(self.blob_gas_price)
+            .base_fee_burned(base_fee_burned)
+            .priority_fee_paid(priority_fee_paid)
             .build()
     }
 }
@@ -196,6 +276,7 @@ impl From<(U256, U256, U256)> for L2Gas {
             l1_data_fee: L1DataFee::new(l1_data_fee),
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_per_gas: None,
         }
     }
 }
@@ -216,10 +297,33 @@ impl From<(U256, U256, U256, BlobCount, BlobGasPrice)> for L2Gas {
             l1_data_fee: L1DataFee::new(l1_data_fee),
             blob_count,
             blob_gas_price,
+            base_fee_per_gas: None,
         }
     }
 }
 
+/// Gas paid by one transaction, with where it was found
+///
+/// Kept on [`GasCostResult::transactions`] by calculators built with
+/// [`GasCostCalculator::with_transaction_detail`], so an audit can store the
+/// transaction-level evidence behind the totals.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionGas {
+    /// Hash of the transaction
+    #[serde(rename = "tx_hash")]
+    pub tx_hash: TxHash,
+    /// Block of the transaction, when the provider returned it with the log
+    #[serde(
+        default,
+        rename = "block_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub block_number: Option<BlockNumber>,
+    /// Gas paid
+    #[serde(rename = "gas")]
+    pub gas: GasForTx,
+}
+
 /// Result of gas cost calculation over a block range
 ///
 /// Contains the total gas costs paid for all transactions from one address to another,
@@ -238,20 +342,58 @@ impl From<(U256, U256, U256, BlobCount, BlobGasPrice)> for L2Gas {
 ///
 /// For transactions with blobs, the `breakdown` field separates blob gas costs from
 /// execution gas costs, allowing detailed analysis of EIP-4844 transaction costs.
-#[derive(Default, Debug, Clone, Serialize)]
+///
+/// # Serialization
+///
+/// The serialized field names are stable across releases. Wei values serialize as
+/// hex strings (the `U256` serde format) and `chain` uses the `NamedChain` string form.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GasCostResult {
     /// Chain where the transactions occurred
+    #[serde(rename = "chain")]
     pub chain: NamedChain,
     /// Address that sent the transactions
+    #[serde(rename = "from")]
     pub from: Address,
     /// Address that received the transactions
+    #[serde(rename = "to")]
     pub to: Address,
     /// Total gas cost in wei (includes L1 data fees for L2 chains and blob gas)
+    #[serde(rename = "total_gas_cost")]
     pub total_gas_cost: WeiAmount,
     /// Number of transactions processed
+    #[serde(rename = "transaction_count")]
     pub transaction_count: TransactionCount,
     /// Detailed breakdown of gas costs (execution vs blob vs L1 data)
+    #[serde(rename = "breakdown")]
     pub breakdown: GasBreakdown,
+    /// Whether the calculation was cancelled before covering the whole range
+    ///
+    /// Cancelled results hold totals for the chunks processed so far and are
+    /// never cached. Omitted from the serialized form when `false`.
+    #[serde(
+        default,
+        rename = "cancelled",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub cancelled: bool,
+    /// Logs and transactions left out of the totals
+    ///
+    /// Omitted from the serialized form when empty.
+    #[serde(default, rename = "warnings", skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ScanWarning>,
+    /// Per-transaction gas behind the totals, in the order it was found
+    ///
+    /// Only filled by calculators built with
+    /// [`GasCostCalculator::with_transaction_detail`]. A transaction appears
+    /// once per matching log, as it does in the totals. Omitted from the
+    /// serialized form when empty.
+    #[serde(
+        default,
+        rename = "transactions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub transactions: Vec<TransactionGas>,
 }
 
 impl GasCostResult {
@@ -263,6 +405,9 @@ impl GasCostResult {
             total_gas_cost: WeiAmount::ZERO,
             transaction_count: TransactionCount::ZERO,
             breakdown: GasBreakdown::new(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transactions: Vec::new(),
         }
     }
 
@@ -297,11 +442,39 @@ impl GasCostResult {
         }
     }
 
+    /// Add a transaction and keep its detail in [`transactions`](Self::transactions)
+    pub fn add_transaction_detail(
+        &mut self,
+        tx_hash: TxHash,
+        block_number: Option<BlockNumber>,
+        gas: GasForTx,
+    ) {
+        self.add_transaction(gas.clone());
+        self.transactions.push(TransactionGas {
+            tx_hash,
+            block_number,
+            gas,
+        });
+    }
+
+    /// Whether [`transactions`](Self::transactions) holds every counted
+    /// transaction
+    ///
+    /// `false` for results computed without
+    /// [`GasCostCalculator::with_transaction_detail`] that counted any
+    /// transactions.
+    pub fn has_transaction_detail(&self) -> bool {
+        self.transactions.len() == self.transaction_count.as_usize()
+    }
+
     /// Merge another gas cost result into this one
     pub fn merge(&mut self, other: &Self) {
         self.total_gas_cost = self.total_gas_cost + other.total_gas_cost;
         self.transaction_count += other.transaction_count;
         self.breakdown.merge(&other.breakdown);
+        self.cancelled |= other.cancelled;
+        extend_warnings(&mut self.warnings, &other.warnings);
+        self.transactions.extend(other.transactions.iter().cloned());
     }
 
     /// Check if any transactions in this result used blob gas (EIP-4844)
@@ -331,24 +504,105 @@ impl GasCostResult {
 
     /// Get the total gas cost formatted as a string
     pub fn formatted_gas_cost(&self) -> String {
-        self.format_gas_cost()
+        self.total_gas_cost.to_eth_string()
     }
 
-    fn format_gas_cost(&self) -> String {
-        let gas_cost = self.total_gas_cost.as_u256();
+    /// Format the total gas cost in whole units of the native currency under
+    /// `options`
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        self.total_gas_cost.to_display(options)
+    }
+
+    /// Split the total gas cost across counterparties by percentage
+    ///
+    /// Percentages are applied at basis-point precision and must sum to exactly
+    /// 100%. Each counterparty first receives the floor of its exact share; the
+    /// leftover wei (at most one per counterparty) are then handed out one at a
+    /// time in order of largest fractional remainder, with ties going to the
+    /// earlier entry. The returned shares are in input order and always sum to
+    /// exactly [`total_gas_cost`](Self::total_gas_cost).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GasCalculationError::CalculationFailed`] if `shares` is empty or
+    /// the percentages do not sum to 10,000 basis points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_chains::NamedChain;
+    /// use alloy_primitives::Address;
+    /// use semioscan::{GasCostResult, Percentage, WeiAmount};
+    ///
+    /// let mut result = GasCostResult::new(NamedChain::Mainnet, Address::ZERO, Address::ZERO);
+    /// result.total_gas_cost = WeiAmount::from(100u64);
+    ///
+    /// let a = Address::repeat_byte(0xaa);
+    /// let b = Address::repeat_byte(0xbb);
+    /// let c = Address::repeat_byte(0xcc);
+    /// let third = Percentage::from_basis_points(3333);
+    /// let shares = result
+    ///     .apportion(&[(a, third), (b, third), (c, Percentage::from_basis_points(3334))])
+    ///     .unwrap();
+    ///
+    /// let total: WeiAmount = shares.iter().map(|(_, share)| *share).sum();
+    /// assert_eq!(total, result.total_gas_cost);
+    /// ```
+    pub fn apportion(
+        &self,
+        shares: &[(Address, Percentage)],
+    ) -> Result<Vec<(Address, WeiAmount)>, GasCalculationError> {
+        const BASIS_POINTS: u64 = 10_000;
+
+        if shares.is_empty() {
+            return Err(GasCalculationError::calculation_failed(
+                "cannot apportion gas cost across zero counterparties",
+            ));
+        }
 
-        let decimals = DecimalPrecision::NativeToken.decimals();
+        let total_bps: u64 = shares.iter().map(|(_, pct)| pct.as_basis_points()).sum();
+        if total_bps != BASIS_POINTS {
+            return Err(GasCalculationError::calculation_failed(format!(
+                "apportionment percentages must sum to {BASIS_POINTS} basis points, got {total_bps}"
+            )));
+        }
 
-        let divisor = U256::from(10).pow(U256::from(decimals));
+        // Split the total as `quotient * 10_000 + remainder` so that scaling by
+        // basis points can never overflow, even for totals near U256::MAX.
+        let total = self.total_gas_cost.as_u256();
+        let divisor = U256::from(BASIS_POINTS);
+        let quotient = total / divisor;
+        let remainder = total % divisor;
+
+        let mut allocated = Vec::with_capacity(shares.len());
+        let mut fractional = Vec::with_capacity(shares.len());
+        for (index, (address, pct)) in shares.iter().enumerate() {
+            let bps = U256::from(pct.as_basis_points());
+            let scaled_remainder = remainder * bps;
+            let share = quotient * bps + scaled_remainder / divisor;
+            allocated.push((*address, share));
+            fractional.push((scaled_remainder % divisor, index));
+        }
 
-        let whole = gas_cost / divisor;
-        let fractional = gas_cost % divisor;
+        let distributed = allocated
+            .iter()
+            .fold(U256::ZERO, |acc, (_, share)| acc + *share);
+        let mut leftover = total - distributed;
 
-        // Convert fractional part to string with leading zeros
-        let fractional_str = format!("{:0width$}", fractional, width = decimals as usize);
+        // Largest remainder first; stable sort keeps input order for ties
+        fractional.sort_by(|a, b| b.0.cmp(&a.0));
+        for (_, index) in fractional {
+            if leftover.is_zero() {
+                break;
+            }
+            allocated[index].1 += U256::from(1u64);
+            leftover -= U256::from(1u64);
+        }
 
-        // Format with proper decimal places, ensuring we don't have trailing zeros
-        format!("{}.{}", whole, fractional_str.trim_end_matches('0'))
+        Ok(allocated
+            .into_iter()
+            .map(|(address, share)| (address, WeiAmount::new(share)))
+            .collect())
     }
 }
 
@@ -356,6 +610,15 @@ pub struct GasCostCalculator<N: Network, P: Provider<N>> {
     pub(crate) provider: P,
     pub(crate) gas_cache: Arc<Mutex<GasCache>>,
     pub(crate) config: SemioscanConfig,
+    pub(crate) cancellation: Option<CancellationToken>,
+    pub(crate) percentile_cache: PercentileCache,
+    pub(crate) capabilities: CapabilitySource,
+    pub(crate) log_source: Option<Arc<dyn LogSource>>,
+    pub(crate) header_cache: Option<HeaderCache>,
+    pub(crate) transaction_cache: Option<TransactionCache<N>>,
+    pub(crate) finality: Option<FinalityTracker>,
+    pub(crate) deployments: Option<DeploymentCache>,
+    pub(crate) transaction_detail: bool,
     pub(crate) _phantom: std::marker::PhantomData<N>,
 }
 
@@ -371,6 +634,15 @@ impl<N: Network, P: Provider<N>> GasCostCalculator<N, P> {
             provider,
             gas_cache: Arc::new(Mutex::new(GasCache::default())),
             config,
+            cancellation: None,
+            percentile_cache: PercentileCache::default(),
+            capabilities: CapabilitySource::Unknown,
+            log_source: None,
+            header_cache: None,
+            transaction_cache: None,
+            finality: None,
+            deployments: None,
+            transaction_detail: false,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -385,6 +657,15 @@ impl<N: Network, P: Provider<N>> GasCostCalculator<N, P> {
             provider,
             gas_cache,
             config,
+            cancellation: None,
+            percentile_cache: PercentileCache::default(),
+            capabilities: CapabilitySource::Unknown,
+            log_source: None,
+            header_cache: None,
+            transaction_cache: None,
+            finality: None,
+            deployments: None,
+            transaction_detail: false,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -393,6 +674,167 @@ impl<N: Network, P: Provider<N>> GasCostCalculator<N, P> {
     pub fn with_cache(provider: P, gas_cache: Arc<Mutex<GasCache>>) -> Self {
         Self::with_cache_and_config(provider, gas_cache, SemioscanConfig::default())
     }
+
+    /// Stop calculations between chunks once `token` is cancelled
+    ///
+    /// A cancelled calculation returns the totals gathered so far with
+    /// [`GasCostResult::cancelled`] set, instead of an error.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Use methods the provider is known to support
+    ///
+    /// With `eth_feeHistory` available, base fees for a chunk of logs are read
+    /// in one request instead of one block header each, and
+    /// [`daily_gas_percentiles`](Self::daily_gas_percentiles) sizes its samples
+    /// to the provider's limit. See [`ProviderCapabilities::probe`].
+    pub fn with_capabilities(mut self, capabilities: ProviderCapabilities) -> Self {
+        self.capabilities = CapabilitySource::Known(capabilities);
+        self
+    }
+
+    /// Probe the provider for optional methods on the first calculation
+    ///
+    /// The result is kept in `cache` under `endpoint`, so calculators sharing
+    /// the cache probe each endpoint once. Capabilities given with
+    /// [`with_capabilities`](Self::with_capabilities) are replaced.
+    pub fn with_capability_probe(
+        mut self,
+        cache: CapabilityCache,
+        endpoint: impl Into<String>,
+    ) -> Self {
+        self.capabilities = CapabilitySource::Probe {
+            cache,
+            endpoint: endpoint.into(),
+        };
+        self
+    }
+
+    /// Fetch logs from `source` instead of `eth_getLogs` on the provider
+    ///
+    /// Receipts, transactions and blocks still come from the provider.
+    pub fn with_log_source(mut self, source: Arc<dyn LogSource>) -> Self {
+        self.log_source = Some(source);
+        self
+    }
+
+    /// Read and record block headers in `cache`, which may be shared with
+    /// other calculators on the same chain
+    ///
+    /// Base fee lookups and block-header percentile sampling check it before
+    /// fetching a header.
+    pub fn with_header_cache(mut self, cache: HeaderCache) -> Self {
+        self.header_cache = Some(cache);
+        self
+    }
+
+    /// Read and record transactions and receipts in `cache`, which may be
+    /// shared with other calculators
+    ///
+    /// Each matching log's transaction and receipt are looked up in it
+    /// before fetching them.
+    pub fn with_transaction_cache(mut self, cache: TransactionCache<N>) -> Self {
+        self.transaction_cache = Some(cache);
+        self
+    }
+
+    /// Cache ranges reaching past the finalized block provisionally
+    ///
+    /// Each calculation first refreshes `tracker` and drops cached ranges whose
+    /// last block was reorged out before it became final.
+    pub fn with_finality_tracker(mut self, tracker: FinalityTracker) -> Self {
+        self.finality = Some(tracker);
+        self
+    }
+
+    /// Start scans no earlier than the token's deployment block
+    ///
+    /// The deployment block is found once per token with a binary search
+    /// over `eth_getCode` and kept in `cache`, which may be shared with other
+    /// calculators. Ranges ending before the deployment return an empty
+    /// result without scanning. Requires an archive node.
+    pub fn with_deployment_cache(mut self, cache: DeploymentCache) -> Self {
+        self.deployments = Some(cache);
+        self
+    }
+
+    /// Keep each transaction's gas in [`GasCostResult::transactions`]
+    ///
+    /// Cached results without that detail are rescanned rather than reused.
+    pub fn with_transaction_detail(mut self) -> Self {
+        self.transaction_detail = true;
+        self
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Compare the provider's `eth_chainId` against `expected`
+    pub(crate) async fn check_network(
+        &self,
+        expected: NetworkType,
+        check: NetworkCheck,
+    ) -> Result<Option<NamedChain>, GasCalculationError> {
+        let chain_id = self
+            .provider
+            .get_chain_id()
+            .await
+            .map_err(|e| RpcError::request_failed("eth_chainId", e))?;
+        check_network_type(chain_id, expected, check)
+    }
+}
+
+/// What [`GasCostCalculator::verify_network_with`] does on a network mismatch
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{GasCostCalculator, NetworkCheck};
+///
+/// // Log a mismatch and keep going instead of failing startup
+/// let calculator = GasCostCalculator::new(provider);
+/// calculator.verify_network_with(NetworkCheck::Warn).await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NetworkCheck {
+    /// Return [`GasCalculationError::NetworkMismatch`] (default)
+    #[default]
+    Strict,
+    /// Log the mismatch as a warning and return the provider's chain
+    Warn,
+}
+
+/// Map `chain_id` to a network type and compare it with `expected`
+///
+/// Unrecognized chain IDs can't be classified, so they pass with a warning.
+fn check_network_type(
+    chain_id: u64,
+    expected: NetworkType,
+    check: NetworkCheck,
+) -> Result<Option<NamedChain>, GasCalculationError> {
+    let Ok(chain) = NamedChain::try_from(chain_id) else {
+        warn!(
+            chain_id,
+            expected = expected.name(),
+            "Unrecognized chain ID, skipping network type check"
+        );
+        return Ok(None);
+    };
+
+    let actual = network_type_for_chain(chain);
+    if actual != expected {
+        let error = GasCalculationError::network_mismatch(chain_id, expected, actual);
+        match check {
+            NetworkCheck::Strict => return Err(error),
+            NetworkCheck::Warn => warn!(%error, "Gas calculator may report wrong fees"),
+        }
+    }
+    Ok(Some(chain))
 }
 
 #[cfg(test)]
@@ -412,6 +854,7 @@ mod tests {
             effective_gas_price: GasPrice::from_gwei(50),
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_per_gas: None,
         }));
 
         assert_eq!(result.transaction_count, TransactionCount::new(1));
@@ -426,6 +869,7 @@ mod tests {
             effective_gas_price: GasPrice::from_gwei(60),
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_per_gas: None,
         }));
 
         assert_eq!(result.transaction_count, TransactionCount::new(2));
@@ -457,6 +901,7 @@ mod tests {
             effective_gas_price: GasPrice::from_gwei(50),
             blob_count: BlobCount::new(2),
             blob_gas_price: BlobGasPrice::from_gwei(1),
+            base_fee_per_gas: None,
         }));
 
         assert_eq!(result.transaction_count, TransactionCount::new(1));
@@ -490,6 +935,7 @@ mod tests {
             l1_data_fee: L1DataFee::new(U256::from(5_000_000_000_000_000u64)), // 0.005 ETH
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_per_gas: None,
         }));
 
         assert_eq!(result.transaction_count, TransactionCount::new(1));
@@ -525,6 +971,9 @@ mod tests {
             breakdown: GasBreakdown::builder()
                 .execution_gas_cost(U256::from(1_000_000_000_000_000u64))
                 .build(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transactions: Vec::new(),
         };
 
         let result2 = GasCostResult {
@@ -536,6 +985,9 @@ mod tests {
             breakdown: GasBreakdown::builder()
                 .execution_gas_cost(U256::from(500_000_000_000_000u64))
                 .build(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transactions: Vec::new(),
         };
 
         result1.merge(&result2);
@@ -567,6 +1019,9 @@ mod tests {
             breakdown: GasBreakdown::builder()
                 .execution_gas_cost(U256::from(1_000_000u64))
                 .build(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transactions: Vec::new(),
         };
 
         let empty = GasCostResult::new(NamedChain::Mainnet, from, to);
@@ -593,6 +1048,7 @@ mod tests {
             effective_gas_price: GasPrice::new(1000000),
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_per_gas: None,
         }));
 
         // Should saturate at U256::MAX, not wrap around
@@ -612,6 +1068,9 @@ mod tests {
             total_gas_cost: WeiAmount::from(U256::MAX - U256::from(100u64)),
             transaction_count: TransactionCount::new(5),
             breakdown: GasBreakdown::new(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transactions: Vec::new(),
         };
 
         let result2 = GasCostResult {
@@ -621,6 +1080,9 @@ mod tests {
             total_gas_cost: WeiAmount::from(500u64),
             transaction_count: TransactionCount::new(3),
             breakdown: GasBreakdown::new(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transactions: Vec::new(),
         };
 
         result1.merge(&result2);
@@ -671,6 +1133,111 @@ mod tests {
         );
     }
 
+    fn apportion_total(shares: &[(Address, WeiAmount)]) -> WeiAmount {
+        shares.iter().map(|(_, share)| *share).sum()
+    }
+
+    #[test]
+    fn test_apportion_even_split() {
+        let a = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let b = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, a, b);
+        result.total_gas_cost = WeiAmount::from(1_000_000u64);
+
+        let half = Percentage::from_basis_points(5000);
+        let shares = result.apportion(&[(a, half), (b, half)]).unwrap();
+
+        assert_eq!(
+            shares,
+            vec![
+                (a, WeiAmount::from(500_000u64)),
+                (b, WeiAmount::from(500_000u64))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apportion_distributes_remainder_exactly() {
+        let a = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let b = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        let c = address!("cccccccccccccccccccccccccccccccccccccccc");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, a, b);
+        result.total_gas_cost = WeiAmount::from(10u64);
+
+        let shares = result
+            .apportion(&[
+                (a, Percentage::from_basis_points(3333)),
+                (b, Percentage::from_basis_points(3333)),
+                (c, Percentage::from_basis_points(3334)),
+            ])
+            .unwrap();
+
+        // Exact shares are 3.333, 3.333 and 3.334 wei; c has the largest
+        // fractional part and receives the leftover wei.
+        assert_eq!(
+            shares,
+            vec![
+                (a, WeiAmount::from(3u64)),
+                (b, WeiAmount::from(3u64)),
+                (c, WeiAmount::from(4u64))
+            ]
+        );
+        assert_eq!(apportion_total(&shares), result.total_gas_cost);
+    }
+
+    #[test]
+    fn test_apportion_ties_favor_earlier_entries() {
+        let a = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let b = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, a, b);
+        result.total_gas_cost = WeiAmount::from(1u64);
+
+        let half = Percentage::from_basis_points(5000);
+        let shares = result.apportion(&[(a, half), (b, half)]).unwrap();
+
+        assert_eq!(
+            shares,
+            vec![(a, WeiAmount::from(1u64)), (b, WeiAmount::ZERO)]
+        );
+    }
+
+    #[test]
+    fn test_apportion_does_not_overflow_near_max() {
+        let a = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let b = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, a, b);
+        result.total_gas_cost = WeiAmount::new(U256::MAX);
+
+        let shares = result
+            .apportion(&[
+                (a, Percentage::from_basis_points(2500)),
+                (b, Percentage::from_basis_points(7500)),
+            ])
+            .unwrap();
+
+        assert_eq!(apportion_total(&shares), WeiAmount::new(U256::MAX));
+    }
+
+    #[test]
+    fn test_apportion_rejects_invalid_percentages() {
+        let a = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let b = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, a, b);
+        result.total_gas_cost = WeiAmount::from(100u64);
+
+        assert!(matches!(
+            result.apportion(&[]),
+            Err(GasCalculationError::CalculationFailed { .. })
+        ));
+        assert!(matches!(
+            result.apportion(&[
+                (a, Percentage::from_basis_points(5000)),
+                (b, Percentage::from_basis_points(4000)),
+            ]),
+            Err(GasCalculationError::CalculationFailed { .. })
+        ));
+    }
+
     #[test]
     fn test_formatted_gas_cost() {
         let from = address!("1111111111111111111111111111111111111111");
@@ -681,6 +1248,174 @@ mod tests {
 
         let formatted = result.formatted_gas_cost();
         // Should format as "1.5" (trailing zeros removed)
-        assert!(formatted.starts_with("1.5"));
+        assert_eq!(formatted, "1.5");
+
+        result.total_gas_cost = WeiAmount::ZERO;
+        assert_eq!(result.formatted_gas_cost(), "0");
+
+        result.total_gas_cost = WeiAmount::from(2_000_000_000_000_000_000u64);
+        assert_eq!(result.formatted_gas_cost(), "2");
+    }
+
+    #[test]
+    fn test_gas_cost_to_display() {
+        let from = address!("1111111111111111111111111111111111111111");
+        let to = address!("2222222222222222222222222222222222222222");
+
+        let mut result = GasCostResult::new(NamedChain::Mainnet, from, to);
+        result.total_gas_cost = WeiAmount::from(1_500_000_000_000_000_000u64);
+        assert_eq!(result.to_display(&DisplayOptions::fixed(3)), "1.500");
+        assert_eq!(result.to_display(&DisplayOptions::default()), "1.5");
+    }
+
+    #[test]
+    fn test_gas_cost_result_serde_round_trip() {
+        let from = address!("1111111111111111111111111111111111111111");
+        let to = address!("2222222222222222222222222222222222222222");
+        let mut result = GasCostResult::new(NamedChain::Base, from, to);
+        result.add_transaction(GasForTx::from((
+            U256::from(21000u64),
+            U256::from(1_000_000_000u64),
+            U256::from(5_000u64),
+        )));
+
+        let json = serde_json::to_value(&result).unwrap();
+        let keys: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(
+            keys,
+            vec![
+                "breakdown",
+                "chain",
+                "from",
+                "to",
+                "total_gas_cost",
+                "transaction_count"
+            ]
+        );
+
+        let decoded: GasCostResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_transaction_detail_round_trips() {
+        let from = address!("1111111111111111111111111111111111111111");
+        let to = address!("2222222222222222222222222222222222222222");
+        let mut result = GasCostResult::new(NamedChain::Base, from, to);
+        let gas = GasForTx::from((
+            U256::from(21000u64),
+            U256::from(1_000_000_000u64),
+            U256::from(5_000u64),
+        ))
+        .with_base_fee(Some(GasPrice::from_gwei(1)));
+        result.add_transaction_detail(TxHash::repeat_byte(0x01), Some(42), gas.clone());
+        assert!(result.has_transaction_detail());
+        assert_eq!(
+            result.total_gas_cost,
+            WeiAmount::from(21_000_000_005_000u64)
+        );
+
+        let json = serde_json::to_value(&result).unwrap();
+        let detail = &json["transactions"][0];
+        assert_eq!(detail["block_number"], 42);
+        assert_eq!(detail["gas"]["network"], "l2");
+
+        let decoded: GasCostResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+        assert_eq!(decoded.transactions[0].gas, gas);
+
+        // Totals counted without detail leave it incomplete
+        result.add_transaction(gas);
+        assert!(!result.has_transaction_detail());
+    }
+
+    #[test]
+    fn test_gas_cost_result_merge_propagates_cancellation() {
+        let from = address!("1111111111111111111111111111111111111111");
+        let to = address!("2222222222222222222222222222222222222222");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, from, to);
+        let mut partial = GasCostResult::new(NamedChain::Mainnet, from, to);
+        partial.cancelled = true;
+
+        result.merge(&partial);
+        assert!(result.cancelled);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["cancelled"], true);
+        let decoded: GasCostResult = serde_json::from_value(json).unwrap();
+        assert!(decoded.cancelled);
+    }
+
+    #[test]
+    fn test_check_network_type() {
+        assert_eq!(
+            check_network_type(8453, NetworkType::Optimism, NetworkCheck::Strict).unwrap(),
+            Some(NamedChain::Base)
+        );
+        assert_eq!(
+            check_network_type(1, NetworkType::Ethereum, NetworkCheck::Strict).unwrap(),
+            Some(NamedChain::Mainnet)
+        );
+
+        // An Optimism calculator on an Ethereum RPC
+        let error = check_network_type(1, NetworkType::Optimism, NetworkCheck::Strict).unwrap_err();
+        assert!(matches!(
+            error,
+            GasCalculationError::NetworkMismatch {
+                chain_id: 1,
+                expected: NetworkType::Optimism,
+                actual: NetworkType::Ethereum,
+            }
+        ));
+
+        // Unknown chains can't be classified
+        assert_eq!(
+            check_network_type(999_999_999, NetworkType::Ethereum, NetworkCheck::Strict).unwrap(),
+            None
+        );
+
+        // Warn mode logs the mismatch and reports the provider's chain
+        assert_eq!(
+            check_network_type(1, NetworkType::Optimism, NetworkCheck::Warn).unwrap(),
+            Some(NamedChain::Mainnet)
+        );
+    }
+
+    #[test]
+    fn test_base_fee_split_rolls_up_into_breakdown() {
+        let from = address!("1111111111111111111111111111111111111111");
+        let to = address!("2222222222222222222222222222222222222222");
+        let mut result = GasCostResult::new(NamedChain::Mainnet, from, to);
+
+        // 21000 gas at 12 gwei over a 10 gwei base fee
+        result.add_transaction(
+            GasForTx::from((U256::from(21_000u64), U256::from(12_000_000_000u64)))
+                .with_base_fee(Some(GasPrice::from_gwei(10))),
+        );
+        // 50000 gas at 3 gwei, base fee not looked up
+        result.add_transaction(GasForTx::from((
+            U256::from(50_000u64),
+            U256::from(3_000_000_000u64),
+        )));
+
+        assert_eq!(
+            result.breakdown.base_fee_burned,
+            U256::from(210_000_000_000_000u64)
+        );
+        assert_eq!(
+            result.breakdown.priority_fee_paid,
+            U256::from(42_000_000_000_000u64)
+        );
+        assert_eq!(
+            result.breakdown.execution_gas_cost,
+            U256::from(252_000_000_000_000u64 + 150_000_000_000_000u64)
+        );
+
+        let mut merged = result.clone();
+        merged.merge(&result);
+        assert_eq!(
+            merged.breakdown.base_fee_burned,
+            U256::from(420_000_000_000_000u64)
+        );
     }
 }
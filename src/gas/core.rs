@@ -9,15 +9,24 @@ use alloy_provider::{network::eip2718::Typed2718, Provider};
 use alloy_rpc_types::{Filter, Log, TransactionTrait};
 use alloy_sol_types::SolEvent;
 use op_alloy_network::Optimism;
-use tokio::time::sleep;
+use std::collections::{BTreeMap, HashMap};
 
+use crate::cache::finality::log_reconciliation;
 use crate::errors::{GasCalculationError, RpcError};
+use crate::events::chunked::{get_logs_bisecting, ChunkScheduler};
 use crate::events::definitions::{Approval, Transfer};
+use crate::events::query::EventQuery;
+use crate::events::source::{LogSource, RpcLogSource};
 use crate::gas::adapter::{EthereumReceiptAdapter, OptimismReceiptAdapter, ReceiptAdapter};
-use crate::gas::calculator::{GasCostCalculator, GasCostResult, GasForTx};
+use crate::gas::base_fee::get_base_fees;
+use crate::gas::calculator::{GasCostCalculator, GasCostResult, GasForTx, NetworkCheck};
 use crate::gas::transaction;
+use crate::provider::NetworkType;
 use crate::tracing::spans;
-use tracing::{error, info, trace, Instrument};
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
+use crate::types::gas::GasPrice;
+use crate::types::warnings::ScanWarning;
+use tracing::{error, info, trace, warn, Instrument};
 
 /// Type of ERC-20 event for gas calculation
 ///
@@ -128,15 +137,114 @@ mod gas_calc_core {
         topic1: Address,
         topic2: Address,
     ) -> Filter {
-        let event_topic = event_type.signature_hash();
+        EventQuery::from_topics(event_type, token, topic1, topic2)
+            .to_filter()
+            .from_block(current_block)
+            .to_block(to_block)
+    }
 
+    /// Log filters matching exactly `pairs` of indexed addresses
+    ///
+    /// A filter ORs the addresses given for each topic, so it matches every
+    /// combination of them. Each group keeps one side to a single address,
+    /// so the combinations are exactly the pairs of the group. Groups are
+    /// taken greedily around the address shared by the most remaining pairs,
+    /// on either side: one hot wallet against many counterparties takes one
+    /// filter per direction.
+    pub(super) fn pair_filter_topics(
+        pairs: &[(Address, Address)],
+    ) -> Vec<(Vec<Address>, Vec<Address>)> {
+        let mut remaining = pairs.to_vec();
+        remaining.sort();
+        remaining.dedup();
+
+        let mut groups = Vec::new();
+        while !remaining.is_empty() {
+            let mut by_topic1: BTreeMap<Address, usize> = BTreeMap::new();
+            let mut by_topic2: BTreeMap<Address, usize> = BTreeMap::new();
+            for &(topic1, topic2) in &remaining {
+                *by_topic1.entry(topic1).or_default() += 1;
+                *by_topic2.entry(topic2).or_default() += 1;
+            }
+            // `max_by_key` keeps the last maximum, so iterate in reverse to
+            // break ties on the lowest address
+            let (&topic1, &topic1_count) = by_topic1
+                .iter()
+                .rev()
+                .max_by_key(|(_, &count)| count)
+                .expect("remaining pairs");
+            let (&topic2, &topic2_count) = by_topic2
+                .iter()
+                .rev()
+                .max_by_key(|(_, &count)| count)
+                .expect("remaining pairs");
+
+            if topic2_count >= topic1_count {
+                let (group, rest): (Vec<_>, Vec<_>) =
+                    remaining.into_iter().partition(|pair| pair.1 == topic2);
+                remaining = rest;
+                groups.push((group.into_iter().map(|pair| pair.0).collect(), vec![topic2]));
+            } else {
+                let (group, rest): (Vec<_>, Vec<_>) =
+                    remaining.into_iter().partition(|pair| pair.0 == topic1);
+                remaining = rest;
+                groups.push((vec![topic1], group.into_iter().map(|pair| pair.1).collect()));
+            }
+        }
+        groups
+    }
+
+    /// A filter for `event_type` logs of `token` with any of `topic1s` and
+    /// any of `topic2s` as indexed addresses
+    pub(super) fn create_pairs_filter(
+        event_type: EventType,
+        current_block: BlockNumber,
+        to_block: BlockNumber,
+        token: Address,
+        topic1s: &[Address],
+        topic2s: &[Address],
+    ) -> Filter {
+        let words = |addresses: &[Address]| {
+            addresses
+                .iter()
+                .map(|address| address.into_word())
+                .collect::<Vec<B256>>()
+        };
         Filter::new()
+            .event_signature(event_type.signature_hash())
+            .address(token)
+            .topic1(words(topic1s))
+            .topic2(words(topic2s))
             .from_block(current_block)
             .to_block(to_block)
-            .address(token)
-            .event_signature(vec![event_topic])
-            .topic1(topic1)
-            .topic2(topic2)
+    }
+
+    /// The two indexed addresses of an event log
+    pub(super) fn indexed_pair(log: &Log) -> Option<(Address, Address)> {
+        let topics = log.topics();
+        Some((
+            Address::from_word(*topics.get(1)?),
+            Address::from_word(*topics.get(2)?),
+        ))
+    }
+
+    /// The event type, token and indexed addresses of a fully specified `query`
+    pub(super) fn query_parts(
+        query: &EventQuery,
+    ) -> Result<(EventType, Address, Address, Address), GasCalculationError> {
+        let (topic1, topic2) = query.indexed();
+        let (first, second) = match query.event_type() {
+            EventType::Transfer => ("from", "to"),
+            EventType::Approval => ("owner", "spender"),
+        };
+        Ok((
+            query.event_type(),
+            query
+                .token()
+                .ok_or_else(|| GasCalculationError::missing_data("token"))?,
+            topic1.ok_or_else(|| GasCalculationError::missing_data(first))?,
+            topic2.ok_or_else(|| GasCalculationError::missing_data(second))?,
+        ))
     }
 }
 
@@ -149,31 +257,57 @@ where
     async fn process_event_log<A: ReceiptAdapter<N>>(
         &self,
         log: &Log,
+        base_fee_per_gas: Option<GasPrice>,
         adapter: &A,
     ) -> Result<Option<GasForTx>, GasCalculationError> {
         let tx_hash = log
             .transaction_hash
             .ok_or_else(GasCalculationError::missing_transaction_hash)?;
 
-        let span = spans::process_event_log(tx_hash);
+        let span = spans::process_event_log(&self.config.spans, tx_hash);
+        let cache = self.transaction_cache.as_ref();
         let (transaction, receipt) = async {
-            let transaction = self
-                .provider
-                .get_transaction_by_hash(tx_hash)
-                .await
-                .map_err(|e| {
-                    RpcError::request_failed(format!("get_transaction_by_hash({tx_hash})"), e)
-                })?
-                .ok_or_else(|| RpcError::TransactionNotFound { tx_hash })?;
-
-            let receipt = self
-                .provider
-                .get_transaction_receipt(tx_hash)
-                .await
-                .map_err(|e| {
-                    RpcError::request_failed(format!("get_transaction_receipt({tx_hash})"), e)
-                })?
-                .ok_or_else(|| RpcError::ReceiptNotFound { tx_hash })?;
+            let transaction = match cache.and_then(|cache| cache.transaction(tx_hash)) {
+                Some(transaction) => transaction,
+                None => {
+                    let transaction = self
+                        .provider
+                        .get_transaction_by_hash(tx_hash)
+                        .await
+                        .map_err(|e| {
+                            RpcError::request_failed(
+                                format!("get_transaction_by_hash({tx_hash})"),
+                                e,
+                            )
+                        })?
+                        .ok_or_else(|| RpcError::TransactionNotFound { tx_hash })?;
+                    if let Some(cache) = cache {
+                        cache.insert_transaction(tx_hash, transaction.clone());
+                    }
+                    transaction
+                }
+            };
+
+            let receipt = match cache.and_then(|cache| cache.receipt(tx_hash)) {
+                Some(receipt) => receipt,
+                None => {
+                    let receipt = self
+                        .provider
+                        .get_transaction_receipt(tx_hash)
+                        .await
+                        .map_err(|e| {
+                            RpcError::request_failed(
+                                format!("get_transaction_receipt({tx_hash})"),
+                                e,
+                            )
+                        })?
+                        .ok_or_else(|| RpcError::ReceiptNotFound { tx_hash })?;
+                    if let Some(cache) = cache {
+                        cache.insert_receipt(tx_hash, receipt.clone());
+                    }
+                    receipt
+                }
+            };
 
             Ok::<_, GasCalculationError>((transaction, receipt))
         }
@@ -220,6 +354,8 @@ where
             }
         };
 
+        let gas_for_tx = gas_for_tx.with_base_fee(base_fee_per_gas);
+
         info!(?gas_for_tx, "Gas for transaction");
 
         Ok(Some(gas_for_tx))
@@ -242,6 +378,7 @@ where
         adapter: &A,
     ) -> Result<GasCostResult, GasCalculationError> {
         let span = spans::process_logs_in_range(
+            &self.config.spans,
             event_type,
             chain,
             topic1_addr,
@@ -252,10 +389,12 @@ where
         );
         async {
             let mut result = GasCostResult::new(chain, topic1_addr, topic2_addr);
-            let mut current_block = from_block;
 
             let max_block_range = self.config.get_max_block_range(chain);
-            let rate_limit = self.config.get_rate_limit_delay(chain);
+            let mut pacer = self.config.pacer(chain);
+            let mut chunks = ChunkScheduler::new(&self.config, chain, from_block, to_block);
+            let rpc_logs = RpcLogSource::<_, N>::new(&self.provider);
+            let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
 
             info!(
                 event_type = event_type.name(),
@@ -267,9 +406,21 @@ where
             let mut total_logs = 0;
             let mut chunk_count = 0;
 
-            while current_block <= to_block {
-                let chunk_end =
-                    std::cmp::min(current_block + max_block_range.as_u64() - 1, to_block);
+            while let Some((current_block, chunk_end)) = chunks.next_chunk() {
+                // Wait for a rate limit permit before the next chunk
+                pacer.wait().await;
+
+                if self.is_cancelled() {
+                    warn!(
+                        event_type = event_type.name(),
+                        current_block,
+                        to_block,
+                        "Gas cost calculation cancelled, returning partial result"
+                    );
+                    result.cancelled = true;
+                    break;
+                }
+
                 chunk_count += 1;
 
                 let filter = gas_calc_core::create_event_filter(
@@ -281,15 +432,31 @@ where
                     topic2_addr,
                 );
 
-                let logs = self.provider.get_logs(&filter).await.map_err(|e| {
-                    RpcError::get_logs_failed(
-                        format!(
-                            "{event_name} events from block {current_block} to {chunk_end}",
-                            event_name = event_type.name()
-                        ),
-                        e,
-                    )
-                })?;
+                let logs =
+                    match get_logs_bisecting(log_source, &filter, current_block, chunk_end).await {
+                        Ok(logs) => logs,
+                        Err(e) if chunks.chunk_failed() => {
+                            warn!(
+                                event_type = event_type.name(),
+                                ?e,
+                                current_block,
+                                to_block = chunk_end,
+                                "Error fetching logs, retrying with a smaller chunk"
+                            );
+                            continue;
+                        }
+                        Err(e) => {
+                            return Err(RpcError::get_logs_failed(
+                                format!(
+                                    "{event_name} events from block {current_block} to {chunk_end}",
+                                    event_name = event_type.name()
+                                ),
+                                e,
+                            )
+                            .into());
+                        }
+                    };
+                chunks.chunk_succeeded(logs.len());
                 total_logs += logs.len();
 
                 trace!(
@@ -301,19 +468,29 @@ where
                     "Fetched logs for gas cost calculation"
                 );
 
-                for log in &logs {
-                    // Decode and process the log
-                    event_type.decode_and_log(log, current_block)?;
-                    self.handle_log(log, &mut result, adapter).await?;
-                }
-
-                current_block = chunk_end + 1;
+                let capabilities = self.capabilities.resolve(&self.provider).await;
+                let base_fees = get_base_fees(
+                    &self.provider,
+                    logs.iter().filter_map(|log| log.block_number),
+                    capabilities.as_ref(),
+                    self.header_cache.as_ref(),
+                )
+                .await;
 
-                // Apply rate limiting if configured for this chain
-                if let Some(delay) = rate_limit {
-                    if current_block <= to_block {
-                        sleep(delay).await;
+                for log in &logs {
+                    // Decode and process the log, leaving out logs that don't decode
+                    if let Err(e) = event_type.decode_and_log(log, current_block) {
+                        self.config
+                            .scan_policy
+                            .record(&mut result.warnings, ScanWarning::skipped_log(log, &e))
+                            .map_err(GasCalculationError::skipped_data)?;
+                        continue;
                     }
+                    let base_fee_per_gas = log.block_number.and_then(|block_number| {
+                        base_fees.for_block(block_number, &mut result.warnings)
+                    });
+                    self.handle_log(log, base_fee_per_gas, &mut result, adapter)
+                        .await?;
                 }
             }
 
@@ -336,16 +513,40 @@ where
     async fn handle_log<A: ReceiptAdapter<N>>(
         &self,
         log: &Log,
+        base_fee_per_gas: Option<GasPrice>,
         result: &mut GasCostResult,
         adapter: &A,
     ) -> Result<(), GasCalculationError> {
-        match self.process_event_log(log, adapter).await {
-            Ok(Some(gas)) => {
-                result.add_transaction(gas);
-            }
+        match self.process_event_log(log, base_fee_per_gas, adapter).await {
+            Ok(Some(gas)) => match log.transaction_hash {
+                Some(tx_hash) if self.transaction_detail => {
+                    result.add_transaction_detail(tx_hash, log.block_number, gas);
+                }
+                _ => result.add_transaction(gas),
+            },
             Ok(None) => {
                 info!("No transfer event found");
             }
+            Err(e @ GasCalculationError::MissingData { .. }) => {
+                error!(error = ?e, "Skipping log without a transaction hash");
+                self.config
+                    .scan_policy
+                    .record(&mut result.warnings, ScanWarning::skipped_log(log, &e))
+                    .map_err(GasCalculationError::skipped_data)?;
+            }
+            Err(GasCalculationError::Rpc(
+                e @ (RpcError::TransactionNotFound { tx_hash }
+                | RpcError::ReceiptNotFound { tx_hash }),
+            )) => {
+                error!(error = ?e, "Skipping transaction the provider couldn't find");
+                self.config
+                    .scan_policy
+                    .record(
+                        &mut result.warnings,
+                        ScanWarning::failed_receipt(tx_hash, log.block_number, &e),
+                    )
+                    .map_err(GasCalculationError::skipped_data)?;
+            }
             Err(e) => {
                 error!(error = ?e, "Error processing transfer event for gas");
                 return Err(e);
@@ -354,6 +555,81 @@ where
         Ok(())
     }
 
+    /// Refresh finality and settle the provisional cache entries it now covers
+    async fn settle_provisional(&self) -> Result<(), RpcError> {
+        let Some(finality) = &self.finality else {
+            return Ok(());
+        };
+        let finalized_block = finality.refresh(&self.provider).await?.finalized_block;
+        let pending = self
+            .gas_cache
+            .lock()
+            .await
+            .pending_checkpoints(finalized_block);
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let canonical = finality.canonical_hashes(&self.provider, pending).await?;
+        let reconciliation = self.gas_cache.lock().await.settle(&canonical);
+        log_reconciliation("gas", reconciliation);
+        Ok(())
+    }
+
+    /// Cache `result`, provisionally if `end_block` isn't final yet
+    async fn cache_result(
+        &self,
+        from: Address,
+        to: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        result: GasCostResult,
+    ) -> Result<(), RpcError> {
+        self.store_result(from, to, start_block, end_block, result, false)
+            .await
+    }
+
+    /// Cache `result` in place of the cached entries overlapping its range
+    ///
+    /// Used for rescans of cached blocks: a plain insert would add the new
+    /// totals to the cached ones and count those blocks twice.
+    async fn replace_cached_result(
+        &self,
+        from: Address,
+        to: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        result: GasCostResult,
+    ) -> Result<(), RpcError> {
+        self.store_result(from, to, start_block, end_block, result, true)
+            .await
+    }
+
+    async fn store_result(
+        &self,
+        from: Address,
+        to: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        result: GasCostResult,
+        replace: bool,
+    ) -> Result<(), RpcError> {
+        let checkpoint = match &self.finality {
+            Some(finality) => finality.checkpoint(&self.provider, end_block).await?,
+            None => None,
+        };
+        let mut cache = self.gas_cache.lock().await;
+        if replace {
+            cache.remove_range(from, to, start_block, end_block);
+        }
+        match checkpoint {
+            Some(checkpoint) => {
+                cache.insert_provisional(from, to, start_block, end_block, result, checkpoint)
+            }
+            None => cache.insert(from, to, start_block, end_block, result),
+        }
+        Ok(())
+    }
+
     /// Calculate gas costs between blocks using the provided adapter (unified method)
     ///
     /// This method replaces `calculate_gas_cost_for_transfers_with_adapter` and
@@ -373,6 +649,7 @@ where
         adapter: &A,
     ) -> Result<GasCostResult, GasCalculationError> {
         let span = spans::calculate_gas_cost_with_adapter(
+            &self.config.spans,
             event_type,
             chain,
             topic1_addr,
@@ -392,12 +669,38 @@ where
                 "Starting gas cost calculation"
             );
 
+            self.settle_provisional().await?;
+
+            let start_block = match &self.deployments {
+                Some(deployments) => {
+                    let clamped = deployments
+                        .clamp_start(&self.provider, chain, &[token], start_block, end_block)
+                        .await?;
+                    let Some(start_block) = clamped else {
+                        return Ok(GasCostResult::new(chain, topic1_addr, topic2_addr));
+                    };
+                    start_block
+                }
+                None => start_block,
+            };
+
             // Check cache and calculate gaps that need to be filled
             let (cached_result, gaps) = {
                 let cache = self.gas_cache.lock().await;
                 cache.calculate_gaps(chain, topic1_addr, topic2_addr, start_block, end_block)
             };
 
+            // Cached totals without per-transaction detail can't supply it, so
+            // the whole range is rescanned and replaces the cached entries
+            let rescan = cached_result
+                .as_ref()
+                .is_some_and(|cached| self.transaction_detail && !cached.has_transaction_detail());
+            let (cached_result, gaps) = if rescan {
+                (None, vec![(start_block, end_block)])
+            } else {
+                (cached_result, gaps)
+            };
+
             // If there are no gaps, we can return the cached result
             if let Some(result) = cached_result.clone() {
                 if gaps.is_empty() {
@@ -452,16 +755,31 @@ where
                     )
                     .await?;
 
+                // A cancelled gap only covers part of its range, so it must not be cached
+                if gap_result.cancelled {
+                    gas_data.merge(&gap_result);
+                    break;
+                }
+
                 // Cache the gap result
-                {
-                    let mut cache = self.gas_cache.lock().await;
-                    cache.insert(
+                if rescan {
+                    self.replace_cached_result(
                         topic1_addr,
                         topic2_addr,
                         *gap_start,
                         *gap_end,
                         gap_result.clone(),
-                    );
+                    )
+                    .await?;
+                } else {
+                    self.cache_result(
+                        topic1_addr,
+                        topic2_addr,
+                        *gap_start,
+                        *gap_end,
+                        gap_result.clone(),
+                    )
+                    .await?;
                 }
 
                 // Merge the gap result with our main result
@@ -478,18 +796,8 @@ where
                 );
             }
 
-            // Cache the complete result
-            {
-                let mut cache = self.gas_cache.lock().await;
-                cache.insert(
-                    topic1_addr,
-                    topic2_addr,
-                    start_block,
-                    end_block,
-                    gas_data.clone(),
-                );
-            }
-
+            // The cached entries and the gaps just cached already cover the
+            // whole range; inserting `gas_data` again would count it twice
             info!(
                 event_type = event_type.name(),
                 ?chain,
@@ -497,6 +805,7 @@ where
                 topic2 = %topic2_addr,
                 total_gas_cost = %gas_data.total_gas_cost,
                 transaction_count = gas_data.transaction_count.as_usize(),
+                cancelled = gas_data.cancelled,
                 "Finished gas cost calculation"
             );
 
@@ -505,24 +814,383 @@ where
         .instrument(span)
         .await
     }
+
+    /// Process logs in a block range for several pairs of indexed addresses at once
+    ///
+    /// Each chunk is fetched with one filter per group of
+    /// [`pair_filter_topics`](gas_calc_core::pair_filter_topics), and its logs
+    /// are credited to the pair they name.
+    #[allow(clippy::too_many_arguments)]
+    async fn process_pairs_in_range<A: ReceiptAdapter<N>>(
+        &self,
+        event_type: EventType,
+        chain: NamedChain,
+        token: Address,
+        pairs: &[(Address, Address)],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        adapter: &A,
+    ) -> Result<HashMap<(Address, Address), GasCostResult>, GasCalculationError> {
+        let mut results: HashMap<(Address, Address), GasCostResult> = pairs
+            .iter()
+            .map(|&(topic1, topic2)| ((topic1, topic2), GasCostResult::new(chain, topic1, topic2)))
+            .collect();
+        let groups = gas_calc_core::pair_filter_topics(pairs);
+
+        let mut pacer = self.config.pacer(chain);
+        let mut chunks = ChunkScheduler::new(&self.config, chain, from_block, to_block);
+        let rpc_logs = RpcLogSource::<_, N>::new(&self.provider);
+        let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
+
+        info!(
+            event_type = event_type.name(),
+            pair_count = pairs.len(),
+            filter_count = groups.len(),
+            total_blocks = to_block.saturating_sub(from_block) + 1,
+            "Starting log processing for address pairs"
+        );
+
+        'chunks: while let Some((current_block, chunk_end)) = chunks.next_chunk() {
+            if self.is_cancelled() {
+                warn!(
+                    event_type = event_type.name(),
+                    current_block,
+                    to_block,
+                    "Gas cost calculation cancelled, returning partial results"
+                );
+                for result in results.values_mut() {
+                    result.cancelled = true;
+                }
+                break;
+            }
+
+            // Fetch every group before crediting any, so a failed chunk can be retried whole
+            let mut logs = Vec::new();
+            for (topic1s, topic2s) in &groups {
+                pacer.wait().await;
+                let filter = gas_calc_core::create_pairs_filter(
+                    event_type,
+                    current_block,
+                    chunk_end,
+                    token,
+                    topic1s,
+                    topic2s,
+                );
+                match get_logs_bisecting(log_source, &filter, current_block, chunk_end).await {
+                    Ok(group_logs) => logs.extend(group_logs),
+                    Err(e) if chunks.chunk_failed() => {
+                        warn!(
+                            event_type = event_type.name(),
+                            ?e,
+                            current_block,
+                            to_block = chunk_end,
+                            "Error fetching logs, retrying with a smaller chunk"
+                        );
+                        continue 'chunks;
+                    }
+                    Err(e) => {
+                        return Err(RpcError::get_logs_failed(
+                            format!(
+                                "{event_name} events for {pair_count} address pairs from block {current_block} to {chunk_end}",
+                                event_name = event_type.name(),
+                                pair_count = pairs.len()
+                            ),
+                            e,
+                        )
+                        .into());
+                    }
+                }
+            }
+            chunks.chunk_succeeded(logs.len());
+
+            let capabilities = self.capabilities.resolve(&self.provider).await;
+            let base_fees = get_base_fees(
+                &self.provider,
+                logs.iter().filter_map(|log| log.block_number),
+                capabilities.as_ref(),
+                self.header_cache.as_ref(),
+            )
+            .await;
+
+            for log in &logs {
+                let Some(result) =
+                    gas_calc_core::indexed_pair(log).and_then(|pair| results.get_mut(&pair))
+                else {
+                    trace!(?log.transaction_hash, "Skipping log outside the requested pairs");
+                    continue;
+                };
+                if let Err(e) = event_type.decode_and_log(log, current_block) {
+                    self.config
+                        .scan_policy
+                        .record(&mut result.warnings, ScanWarning::skipped_log(log, &e))
+                        .map_err(GasCalculationError::skipped_data)?;
+                    continue;
+                }
+                let base_fee_per_gas = log.block_number.and_then(|block_number| {
+                    base_fees.for_block(block_number, &mut result.warnings)
+                });
+                self.handle_log(log, base_fee_per_gas, result, adapter)
+                    .await?;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Calculate gas costs for several pairs of indexed addresses in one pass
+    ///
+    /// Pairs with a complete cached result are served from the cache. The
+    /// rest, including partially cached ones, share one scan of the whole
+    /// range, and each pair's result replaces its cached entries in that
+    /// range. Returns one result per distinct pair, in the order first given.
+    #[allow(clippy::too_many_arguments)]
+    async fn calculate_gas_cost_for_pairs_with_adapter<A: ReceiptAdapter<N>>(
+        &self,
+        event_type: EventType,
+        chain: NamedChain,
+        token: Address,
+        pairs: &[(Address, Address)],
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        adapter: &A,
+    ) -> Result<Vec<GasCostResult>, GasCalculationError> {
+        let mut distinct = Vec::with_capacity(pairs.len());
+        for &pair in pairs {
+            if !distinct.contains(&pair) {
+                distinct.push(pair);
+            }
+        }
+        if distinct.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.settle_provisional().await?;
+
+        let start_block = match &self.deployments {
+            Some(deployments) => {
+                let clamped = deployments
+                    .clamp_start(&self.provider, chain, &[token], start_block, end_block)
+                    .await?;
+                let Some(start_block) = clamped else {
+                    return Ok(distinct
+                        .iter()
+                        .map(|&(topic1, topic2)| GasCostResult::new(chain, topic1, topic2))
+                        .collect());
+                };
+                start_block
+            }
+            None => start_block,
+        };
+
+        let mut cached = HashMap::new();
+        {
+            let cache = self.gas_cache.lock().await;
+            for &(topic1, topic2) in &distinct {
+                let (result, gaps) =
+                    cache.calculate_gaps(chain, topic1, topic2, start_block, end_block);
+                match result {
+                    Some(result)
+                        if gaps.is_empty()
+                            && (!self.transaction_detail || result.has_transaction_detail()) =>
+                    {
+                        cached.insert((topic1, topic2), result);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let uncached: Vec<_> = distinct
+            .iter()
+            .copied()
+            .filter(|pair| !cached.contains_key(pair))
+            .collect();
+
+        info!(
+            event_type = event_type.name(),
+            ?chain,
+            start_block,
+            end_block,
+            pair_count = distinct.len(),
+            cached_pairs = cached.len(),
+            "Starting gas cost calculation for address pairs"
+        );
+
+        let mut scanned = if uncached.is_empty() {
+            HashMap::new()
+        } else {
+            self.process_pairs_in_range(
+                event_type,
+                chain,
+                token,
+                &uncached,
+                start_block,
+                end_block,
+                adapter,
+            )
+            .await?
+        };
+
+        let mut results = Vec::with_capacity(distinct.len());
+        for pair in distinct {
+            let result = match cached.remove(&pair) {
+                Some(result) => result,
+                None => {
+                    let result = scanned
+                        .remove(&pair)
+                        .unwrap_or_else(|| GasCostResult::new(chain, pair.0, pair.1));
+                    // A cancelled scan only covers part of the range, so it must not be cached.
+                    // The scan covers the whole range, so it replaces any partial entries
+                    // rather than adding to them.
+                    if !result.cancelled {
+                        self.replace_cached_result(
+                            pair.0,
+                            pair.1,
+                            start_block,
+                            end_block,
+                            result.clone(),
+                        )
+                        .await?;
+                    }
+                    result
+                }
+            };
+            results.push(result);
+        }
+        Ok(results)
+    }
 }
 
 // Network-specific implementations using the adapters
 impl<P: Provider<Ethereum>> GasCostCalculator<Ethereum, P> {
+    /// Check that the provider is connected to a Ethereum network
+    ///
+    /// Calls `eth_chainId` and maps the chain with
+    /// [`network_type_for_chain`](crate::network_type_for_chain). Call this once
+    /// after construction to catch a calculator wired to the wrong kind of RPC;
+    /// use [`verify_network_with`](Self::verify_network_with) to warn instead
+    /// of failing.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(chain))`: the provider's chain is an Ethereum network
+    /// - `Ok(None)`: the chain ID isn't a known [`NamedChain`], so it can't be checked
+    /// - `Err(GasCalculationError::NetworkMismatch { .. })`: the chain belongs to
+    ///   the other network type
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let calculator = GasCostCalculator::new(provider);
+    /// calculator.verify_network().await?;
+    /// ```
+    pub async fn verify_network(&self) -> Result<Option<NamedChain>, GasCalculationError> {
+        self.verify_network_with(NetworkCheck::Strict).await
+    }
+
+    /// Check the provider's network like [`verify_network`](Self::verify_network),
+    /// handling a mismatch as `check` says
+    ///
+    /// Under [`NetworkCheck::Warn`] a mismatch is logged and the provider's
+    /// chain is returned as `Ok(Some(chain))`.
+    pub async fn verify_network_with(
+        &self,
+        check: NetworkCheck,
+    ) -> Result<Option<NamedChain>, GasCalculationError> {
+        self.check_network(NetworkType::Ethereum, check).await
+    }
+
+    /// Calculate gas costs for any supported [`EventType`] on Ethereum-like chains (Ethereum, Arbitrum, Polygon)
+    ///
+    /// `topic1` and `topic2` are the event's two indexed addresses: `from` and
+    /// `to` for [`EventType::Transfer`], `owner` and `spender` for
+    /// [`EventType::Approval`], typed as the sender and recipient. Use this when the event type is chosen at
+    /// runtime instead of branching between the per-event methods.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{EventType, GasCostCalculator};
+    ///
+    /// let result = calculator
+    ///     .calculate_gas_cost_for_event(
+    ///         EventType::Approval,
+    ///         chain,
+    ///         owner,
+    ///         spender,
+    ///         token,
+    ///         start_block,
+    ///         end_block,
+    ///     )
+    ///     .await?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_gas_cost_for_event(
+        &self,
+        event_type: EventType,
+        chain: NamedChain,
+        topic1: impl Into<SenderAddress>,
+        topic2: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<GasCostResult, GasCalculationError> {
+        self.calculate_gas_cost_with_adapter(
+            event_type,
+            chain,
+            topic1.into().as_address(),
+            topic2.into().as_address(),
+            token.into().as_address(),
+            start_block,
+            end_block,
+            &EthereumReceiptAdapter,
+        )
+        .await
+    }
+
+    /// Calculate gas costs for the events matching `query`
+    ///
+    /// Like [`calculate_gas_cost_for_event`](Self::calculate_gas_cost_for_event),
+    /// with the indexed addresses named rather than given as `topic1` and
+    /// `topic2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GasCalculationError::MissingData`] if `query` leaves its
+    /// token or either indexed address unset, since gas results are cached by
+    /// both addresses.
+    pub async fn calculate_gas_cost_for_query(
+        &self,
+        chain: NamedChain,
+        query: impl Into<EventQuery>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<GasCostResult, GasCalculationError> {
+        let (event_type, token, topic1, topic2) = gas_calc_core::query_parts(&query.into())?;
+        self.calculate_gas_cost_for_event(
+            event_type,
+            chain,
+            topic1,
+            topic2,
+            token,
+            start_block,
+            end_block,
+        )
+        .await
+    }
+
     /// Calculate gas costs for Transfer events between two addresses
     ///
     /// This is a convenience method for Ethereum-like chains (Ethereum, Arbitrum, Polygon).
     pub async fn calculate_gas_cost_for_transfers_between_blocks(
         &self,
         chain: NamedChain,
-        from: Address,
-        to: Address,
-        token: Address,
+        from: impl Into<SenderAddress>,
+        to: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
         start_block: BlockNumber,
         end_block: BlockNumber,
     ) -> Result<GasCostResult, GasCalculationError> {
-        let adapter = EthereumReceiptAdapter;
-        self.calculate_gas_cost_with_adapter(
+        self.calculate_gas_cost_for_event(
             EventType::Transfer,
             chain,
             from,
@@ -530,13 +1198,179 @@ impl<P: Provider<Ethereum>> GasCostCalculator<Ethereum, P> {
             token,
             start_block,
             end_block,
-            &adapter,
+        )
+        .await
+    }
+
+    /// Calculate gas costs for many `(topic1, topic2)` address pairs in one pass
+    ///
+    /// Instead of one scan per pair, pairs sharing an address are fetched
+    /// with one log filter listing their counterparties, so reconciling one
+    /// hot wallet against many counterparties costs about as many requests
+    /// as a single pair. Works on Ethereum-like chains (Ethereum, Arbitrum, Polygon).
+    ///
+    /// Returns one result per distinct pair, in the order first given. Each
+    /// is cached as if calculated on its own, and pairs already fully cached
+    /// aren't scanned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::EventType;
+    ///
+    /// let pairs = counterparties.iter().map(|&counterparty| (counterparty, hot_wallet));
+    /// let results = calculator
+    ///     .calculate_gas_cost_for_pairs(
+    ///         EventType::Transfer,
+    ///         chain,
+    ///         token,
+    ///         pairs,
+    ///         start_block,
+    ///         end_block,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn calculate_gas_cost_for_pairs(
+        &self,
+        event_type: EventType,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+        pairs: impl IntoIterator<Item = (Address, Address)>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<Vec<GasCostResult>, GasCalculationError> {
+        let pairs: Vec<_> = pairs.into_iter().collect();
+        self.calculate_gas_cost_for_pairs_with_adapter(
+            event_type,
+            chain,
+            token.into().as_address(),
+            &pairs,
+            start_block,
+            end_block,
+            &EthereumReceiptAdapter,
         )
         .await
     }
 }
 
 impl<P: Provider<Optimism>> GasCostCalculator<Optimism, P> {
+    /// Check that the provider is connected to an Optimism network
+    ///
+    /// Calls `eth_chainId` and maps the chain with
+    /// [`network_type_for_chain`](crate::network_type_for_chain). Call this once
+    /// after construction to catch a calculator wired to the wrong kind of RPC;
+    /// use [`verify_network_with`](Self::verify_network_with) to warn instead
+    /// of failing.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(chain))`: the provider's chain is an Optimism network
+    /// - `Ok(None)`: the chain ID isn't a known [`NamedChain`], so it can't be checked
+    /// - `Err(GasCalculationError::NetworkMismatch { .. })`: the chain belongs to
+    ///   the other network type
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let calculator = GasCostCalculator::new(provider);
+    /// calculator.verify_network().await?;
+    /// ```
+    pub async fn verify_network(&self) -> Result<Option<NamedChain>, GasCalculationError> {
+        self.verify_network_with(NetworkCheck::Strict).await
+    }
+
+    /// Check the provider's network like [`verify_network`](Self::verify_network),
+    /// handling a mismatch as `check` says
+    ///
+    /// Under [`NetworkCheck::Warn`] a mismatch is logged and the provider's
+    /// chain is returned as `Ok(Some(chain))`.
+    pub async fn verify_network_with(
+        &self,
+        check: NetworkCheck,
+    ) -> Result<Option<NamedChain>, GasCalculationError> {
+        self.check_network(NetworkType::Optimism, check).await
+    }
+
+    /// Calculate gas costs for any supported [`EventType`] on Optimism Stack chains (Base, Optimism, Mode, Fraxtal, Sonic)
+    ///
+    /// `topic1` and `topic2` are the event's two indexed addresses: `from` and
+    /// `to` for [`EventType::Transfer`], `owner` and `spender` for
+    /// [`EventType::Approval`], typed as the sender and recipient. Use this when the event type is chosen at
+    /// runtime instead of branching between the per-event methods.
+    /// L1 data fees are included automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{EventType, GasCostCalculator};
+    ///
+    /// let result = calculator
+    ///     .calculate_gas_cost_for_event(
+    ///         EventType::Approval,
+    ///         chain,
+    ///         owner,
+    ///         spender,
+    ///         token,
+    ///         start_block,
+    ///         end_block,
+    ///     )
+    ///     .await?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_gas_cost_for_event(
+        &self,
+        event_type: EventType,
+        chain: NamedChain,
+        topic1: impl Into<SenderAddress>,
+        topic2: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<GasCostResult, GasCalculationError> {
+        self.calculate_gas_cost_with_adapter(
+            event_type,
+            chain,
+            topic1.into().as_address(),
+            topic2.into().as_address(),
+            token.into().as_address(),
+            start_block,
+            end_block,
+            &OptimismReceiptAdapter,
+        )
+        .await
+    }
+
+    /// Calculate gas costs for the events matching `query`
+    ///
+    /// Like [`calculate_gas_cost_for_event`](Self::calculate_gas_cost_for_event),
+    /// with the indexed addresses named rather than given as `topic1` and
+    /// `topic2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GasCalculationError::MissingData`] if `query` leaves its
+    /// token or either indexed address unset, since gas results are cached by
+    /// both addresses.
+    pub async fn calculate_gas_cost_for_query(
+        &self,
+        chain: NamedChain,
+        query: impl Into<EventQuery>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<GasCostResult, GasCalculationError> {
+        let (event_type, token, topic1, topic2) = gas_calc_core::query_parts(&query.into())?;
+        self.calculate_gas_cost_for_event(
+            event_type,
+            chain,
+            topic1,
+            topic2,
+            token,
+            start_block,
+            end_block,
+        )
+        .await
+    }
+
     /// Calculate gas costs for Transfer events between two addresses
     ///
     /// This is a convenience method for Optimism Stack chains (Base, Optimism, Mode, Fraxtal, Sonic).
@@ -544,14 +1378,13 @@ impl<P: Provider<Optimism>> GasCostCalculator<Optimism, P> {
     pub async fn calculate_gas_cost_for_transfers_between_blocks(
         &self,
         chain: NamedChain,
-        from: Address,
-        to: Address,
-        token: Address,
+        from: impl Into<SenderAddress>,
+        to: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
         start_block: BlockNumber,
         end_block: BlockNumber,
     ) -> Result<GasCostResult, GasCalculationError> {
-        let adapter = OptimismReceiptAdapter;
-        self.calculate_gas_cost_with_adapter(
+        self.calculate_gas_cost_for_event(
             EventType::Transfer,
             chain,
             from,
@@ -559,7 +1392,56 @@ impl<P: Provider<Optimism>> GasCostCalculator<Optimism, P> {
             token,
             start_block,
             end_block,
-            &adapter,
+        )
+        .await
+    }
+
+    /// Calculate gas costs for many `(topic1, topic2)` address pairs in one pass
+    ///
+    /// Instead of one scan per pair, pairs sharing an address are fetched
+    /// with one log filter listing their counterparties, so reconciling one
+    /// hot wallet against many counterparties costs about as many requests
+    /// as a single pair. L1 data fees are included automatically.
+    ///
+    /// Returns one result per distinct pair, in the order first given. Each
+    /// is cached as if calculated on its own, and pairs already fully cached
+    /// aren't scanned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::EventType;
+    ///
+    /// let pairs = counterparties.iter().map(|&counterparty| (counterparty, hot_wallet));
+    /// let results = calculator
+    ///     .calculate_gas_cost_for_pairs(
+    ///         EventType::Transfer,
+    ///         chain,
+    ///         token,
+    ///         pairs,
+    ///         start_block,
+    ///         end_block,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn calculate_gas_cost_for_pairs(
+        &self,
+        event_type: EventType,
+        chain: NamedChain,
+        token: impl Into<TokenAddress>,
+        pairs: impl IntoIterator<Item = (Address, Address)>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<Vec<GasCostResult>, GasCalculationError> {
+        let pairs: Vec<_> = pairs.into_iter().collect();
+        self.calculate_gas_cost_for_pairs_with_adapter(
+            event_type,
+            chain,
+            token.into().as_address(),
+            &pairs,
+            start_block,
+            end_block,
+            &OptimismReceiptAdapter,
         )
         .await
     }
@@ -573,14 +1455,13 @@ impl<P: Provider<Ethereum>> GasCostCalculator<Ethereum, P> {
     pub async fn calculate_gas_cost_for_approvals_between_blocks(
         &self,
         chain: NamedChain,
-        owner: Address,
-        spender: Address,
-        token: Address,
+        owner: impl Into<SenderAddress>,
+        spender: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
         start_block: BlockNumber,
         end_block: BlockNumber,
     ) -> Result<GasCostResult, GasCalculationError> {
-        let adapter = EthereumReceiptAdapter;
-        self.calculate_gas_cost_with_adapter(
+        self.calculate_gas_cost_for_event(
             EventType::Approval,
             chain,
             owner,
@@ -588,7 +1469,6 @@ impl<P: Provider<Ethereum>> GasCostCalculator<Ethereum, P> {
             token,
             start_block,
             end_block,
-            &adapter,
         )
         .await
     }
@@ -602,14 +1482,13 @@ impl<P: Provider<Optimism>> GasCostCalculator<Optimism, P> {
     pub async fn calculate_gas_cost_for_approvals_between_blocks(
         &self,
         chain: NamedChain,
-        owner: Address,
-        spender: Address,
-        token: Address,
+        owner: impl Into<SenderAddress>,
+        spender: impl Into<RecipientAddress>,
+        token: impl Into<TokenAddress>,
         start_block: BlockNumber,
         end_block: BlockNumber,
     ) -> Result<GasCostResult, GasCalculationError> {
-        let adapter = OptimismReceiptAdapter;
-        self.calculate_gas_cost_with_adapter(
+        self.calculate_gas_cost_for_event(
             EventType::Approval,
             chain,
             owner,
@@ -617,7 +1496,6 @@ impl<P: Provider<Optimism>> GasCostCalculator<Optimism, P> {
             token,
             start_block,
             end_block,
-            &adapter,
         )
         .await
     }
@@ -626,9 +1504,228 @@ impl<P: Provider<Optimism>> GasCostCalculator<Optimism, P> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::transaction::TransactionCache;
+    use crate::gas::cache::GasCache;
     use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
-    use alloy_primitives::B256;
+    use alloy_primitives::{LogData, B256};
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_sol_types::SolValue;
+    use alloy_transport::mock::Asserter;
+    use alloy_transport::TransportError;
+    use async_trait::async_trait;
     use serde_json::json;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    /// Serves the logs within each filter's block range that match its
+    /// address and topics
+    struct FixedLogs(Vec<Log>);
+
+    #[async_trait]
+    impl LogSource for FixedLogs {
+        async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+            let from = filter.get_from_block().unwrap();
+            let to = filter.get_to_block().unwrap();
+            Ok(self
+                .0
+                .iter()
+                .filter(|log| (from..=to).contains(&log.block_number.unwrap()))
+                .filter(|log| filter.matches(&log.inner))
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// A calculator sharing `gas_cache` whose `transfers` of `token`, each
+    /// `(from, to, block)` in its own transaction, are served from memory;
+    /// any request reaching the provider fails
+    fn calculator_with_transfers(
+        gas_cache: Arc<Mutex<GasCache>>,
+        token: Address,
+        transfers: &[(Address, Address, BlockNumber)],
+    ) -> GasCostCalculator<Ethereum, RootProvider<Ethereum>> {
+        let transactions = TransactionCache::new();
+        let mut logs = Vec::new();
+        for (index, &(from, to, block)) in transfers.iter().enumerate() {
+            let tx_hash = B256::repeat_byte(0x77 + index as u8);
+            logs.push(Log {
+                inner: alloy_primitives::Log {
+                    address: token,
+                    data: LogData::new(
+                        vec![Transfer::SIGNATURE_HASH, from.into_word(), to.into_word()],
+                        U256::from(1_000u64).abi_encode().into(),
+                    )
+                    .expect("valid log data"),
+                },
+                block_number: Some(block),
+                transaction_hash: Some(tx_hash),
+                ..Default::default()
+            });
+            let transaction = serde_json::from_value(json!({
+                "hash": tx_hash,
+                "nonce": "0x1",
+                "blockHash": B256::repeat_byte(0x22),
+                "blockNumber": format!("{block:#x}"),
+                "transactionIndex": "0x0",
+                "from": from,
+                "to": token,
+                "value": "0x0",
+                "gasPrice": "0x3b9aca00",
+                "gas": "0x5208",
+                "maxFeePerGas": "0xba43b7400",
+                "maxPriorityFeePerGas": "0x5f5e100",
+                "input": "0x",
+                "r": B256::repeat_byte(0x33),
+                "s": B256::repeat_byte(0x44),
+                "v": "0x0",
+                "yParity": "0x0",
+                "chainId": "0x1",
+                "accessList": [],
+                "type": "0x2"
+            }))
+            .expect("valid transaction response");
+            let receipt = serde_json::from_value(json!({
+                "transactionHash": tx_hash,
+                "blockHash": B256::repeat_byte(0x22),
+                "blockNumber": format!("{block:#x}"),
+                "transactionIndex": "0x0",
+                "from": from,
+                "to": token,
+                "cumulativeGasUsed": "0x5208",
+                "gasUsed": "0x5208",
+                "effectiveGasPrice": "0x3b9aca00",
+                "logs": [],
+                "logsBloom": format!("0x{}", "0".repeat(512)),
+                "status": "0x1",
+                "type": "0x2"
+            }))
+            .expect("valid receipt response");
+            transactions.insert_transaction(tx_hash, transaction);
+            transactions.insert_receipt(tx_hash, receipt);
+        }
+
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(Asserter::new());
+        GasCostCalculator::with_cache(provider, gas_cache)
+            .with_log_source(Arc::new(FixedLogs(logs)))
+            .with_transaction_cache(transactions)
+    }
+
+    /// A calculator sharing `gas_cache` whose one transfer, at block 150, is
+    /// served from memory; any request reaching the provider fails
+    fn calculator_with_one_transfer(
+        gas_cache: Arc<Mutex<GasCache>>,
+        token: Address,
+        from: Address,
+        to: Address,
+    ) -> GasCostCalculator<Ethereum, RootProvider<Ethereum>> {
+        calculator_with_transfers(gas_cache, token, &[(from, to, 150)])
+    }
+
+    #[tokio::test]
+    async fn test_pairs_share_a_scan_and_partial_cache_hits_are_not_double_counted() {
+        let token = Address::from([0x33; 20]);
+        let hot_wallet = Address::from([0xaa; 20]);
+        let (sender, recipient, stranger) = (
+            Address::from([0x11; 20]),
+            Address::from([0x22; 20]),
+            Address::from([0x44; 20]),
+        );
+        let gas_cache = Arc::new(Mutex::new(GasCache::default()));
+        let calculator = calculator_with_transfers(
+            gas_cache.clone(),
+            token,
+            &[
+                (sender, hot_wallet, 150),
+                (hot_wallet, recipient, 160),
+                (stranger, hot_wallet, 170),
+            ],
+        );
+        let chain = NamedChain::Mainnet;
+        let pairs = [(sender, hot_wallet), (hot_wallet, recipient)];
+
+        // Cache the inbound pair over part of the range only
+        let partial = calculator
+            .calculate_gas_cost_for_transfers_between_blocks(
+                chain, sender, hot_wallet, token, 100, 155,
+            )
+            .await
+            .unwrap();
+        assert_eq!(partial.transaction_count.as_usize(), 1);
+
+        // Both pairs are scanned together, each counted once, and the
+        // stranger's transfer into the hot wallet isn't credited to anyone
+        for _ in 0..2 {
+            let results = calculator
+                .calculate_gas_cost_for_pairs(EventType::Transfer, chain, token, pairs, 100, 200)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 2);
+            for result in &results {
+                assert_eq!(result.transaction_count.as_usize(), 1);
+                assert_eq!(result.total_gas_cost, partial.total_gas_cost);
+            }
+        }
+        assert_eq!(
+            gas_cache
+                .lock()
+                .await
+                .get(sender, hot_wallet, 100, 200)
+                .map(|cached| cached.transaction_count.as_usize()),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_detail_rescans_replace_cached_totals() {
+        let (token, from, to) = (
+            Address::from([0x33; 20]),
+            Address::from([0x11; 20]),
+            Address::from([0x22; 20]),
+        );
+        let gas_cache = Arc::new(Mutex::new(GasCache::default()));
+        let totals_only = calculator_with_one_transfer(gas_cache.clone(), token, from, to);
+        let detailed = calculator_with_one_transfer(gas_cache.clone(), token, from, to)
+            .with_transaction_detail();
+        let chain = NamedChain::Mainnet;
+
+        let first = totals_only
+            .calculate_gas_cost_for_transfers_between_blocks(chain, from, to, token, 100, 200)
+            .await
+            .unwrap();
+        assert_eq!(first.transaction_count.as_usize(), 1);
+        assert!(!first.has_transaction_detail());
+
+        // The first detailed run rescans the cached range, the second reads it back
+        for _ in 0..2 {
+            let result = detailed
+                .calculate_gas_cost_for_transfers_between_blocks(chain, from, to, token, 100, 200)
+                .await
+                .unwrap();
+            assert_eq!(result.transaction_count.as_usize(), 1);
+            assert_eq!(result.total_gas_cost, first.total_gas_cost);
+            assert!(result.has_transaction_detail());
+        }
+
+        // A partial cache hit only adds the gap
+        for _ in 0..2 {
+            let result = detailed
+                .calculate_gas_cost_for_transfers_between_blocks(chain, from, to, token, 100, 300)
+                .await
+                .unwrap();
+            assert_eq!(result.transaction_count.as_usize(), 1);
+            assert_eq!(result.total_gas_cost, first.total_gas_cost);
+            assert!(result.has_transaction_detail());
+        }
+        assert_eq!(
+            gas_cache
+                .lock()
+                .await
+                .get(from, to, 100, 200)
+                .map(|cached| cached.transaction_count.as_usize()),
+            Some(1)
+        );
+    }
 
     #[test]
     fn test_blob_gas_per_blob_constant() {
@@ -671,6 +1768,74 @@ mod tests {
         let _ = filter; // Use the filter to avoid unused warning
     }
 
+    #[test]
+    fn test_pair_filter_topics_group_by_the_shared_address() {
+        let hot_wallet = Address::from([0xaa; 20]);
+        let counterparties: Vec<Address> = (1..=3).map(|n| Address::from([n; 20])).collect();
+
+        // Many senders into one wallet take one filter
+        let inbound: Vec<_> = counterparties.iter().map(|&c| (c, hot_wallet)).collect();
+        assert_eq!(
+            gas_calc_core::pair_filter_topics(&inbound),
+            vec![(counterparties.clone(), vec![hot_wallet])]
+        );
+
+        // ... as do many recipients from it
+        let outbound: Vec<_> = counterparties.iter().map(|&c| (hot_wallet, c)).collect();
+        assert_eq!(
+            gas_calc_core::pair_filter_topics(&outbound),
+            vec![(vec![hot_wallet], counterparties.clone())]
+        );
+
+        // Both directions take one filter each, and never match a pair that wasn't asked for
+        let both: Vec<_> = inbound.iter().chain(&outbound).copied().collect();
+        let groups = gas_calc_core::pair_filter_topics(&both);
+        assert_eq!(
+            groups,
+            vec![
+                (counterparties.clone(), vec![hot_wallet]),
+                (vec![hot_wallet], counterparties.clone())
+            ]
+        );
+        for (topic1s, topic2s) in &groups {
+            for topic1 in topic1s {
+                for topic2 in topic2s {
+                    assert!(both.contains(&(*topic1, *topic2)));
+                }
+            }
+        }
+
+        let filter = gas_calc_core::create_pairs_filter(
+            EventType::Transfer,
+            100,
+            200,
+            Address::ZERO,
+            &counterparties,
+            &[hot_wallet],
+        );
+        assert_eq!(filter.topics[1].len(), 3);
+        assert!(filter.topics[2].contains(&hot_wallet.into_word()));
+    }
+
+    #[test]
+    fn test_query_parts_require_named_addresses() {
+        let (token, owner, spender) = (
+            Address::from([0x33; 20]),
+            Address::from([0x11; 20]),
+            Address::from([0x22; 20]),
+        );
+        let query = EventQuery::approvals().with_token(token).with_owner(owner);
+
+        let err = gas_calc_core::query_parts(&query.into()).unwrap_err();
+        assert!(matches!(
+            err,
+            GasCalculationError::MissingData { ref field } if field == "spender"
+        ));
+
+        let parts = gas_calc_core::query_parts(&query.with_spender(spender).into()).unwrap();
+        assert_eq!(parts, (EventType::Approval, token, owner, spender));
+    }
+
     #[test]
     fn test_event_type_signature_hash() {
         assert_eq!(
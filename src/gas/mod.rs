@@ -20,6 +20,12 @@
 //! - [`blob::estimate_blob_cost`] - Estimate cost for N blobs
 //! - [`blob::calculate_blob_gas`] - Pure calculation of blob gas units
 //!
+//! ## EIP-1559 Fee Split
+//!
+//! [`base_fee::get_base_fee_at_block`] fetches a block's base fee. Gas results
+//! split execution costs into the base fee burned and the priority fee paid
+//! (see [`GasBreakdown`](crate::GasBreakdown)).
+//!
 //! ## Key Features
 //!
 //! - Automatic L1 data fee calculation for L2 chains
@@ -34,12 +40,15 @@
 //! - `adapter` - Network-specific logic
 
 pub mod adapter;
+pub mod base_fee;
 pub mod blob;
 pub mod cache;
 pub mod calculator;
 pub mod core;
+pub mod percentiles;
 pub(crate) mod transaction;
 
 // Re-export public API
 pub use calculator::*;
 pub use core::EventType;
+pub use percentiles::{DailyGasPercentiles, GasPercentileSource, GasPercentiles};
@@ -0,0 +1,315 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas price percentiles over a UTC day
+//!
+//! [`GasCostCalculator::daily_gas_percentiles`] summarizes what gas cost on a
+//! given day: the 10th, 50th and 90th percentile base fee and effective gas
+//! price across the day's [`DailyBlockWindow`].
+//!
+//! Prices are sampled with `eth_feeHistory`, which reports each block's base
+//! fee and the 10th/50th/90th percentile priority fee paid in it. Long days
+//! are sampled in evenly spaced ranges rather than block by block. Providers
+//! without `eth_feeHistory` fall back to sampling block headers, which only
+//! yields base fees.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{BlockWindowCalculator, GasCostCalculator};
+//!
+//! let percentiles = gas_calculator
+//!     .daily_gas_percentiles(chain, date, &window_calculator)
+//!     .await?;
+//! println!("median base fee: {}", percentiles.base_fee.p50);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use alloy_chains::NamedChain;
+use alloy_network::Network;
+use alloy_primitives::BlockNumber;
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::blocks::window::{BlockWindowCalculator, DailyBlockWindow};
+use crate::errors::{GasCalculationError, RpcError, SemioscanError};
+use crate::gas::base_fee::get_base_fees;
+use crate::gas::calculator::GasCostCalculator;
+use crate::types::gas::GasPrice;
+
+/// Most blocks `eth_feeHistory` reports per request on common providers
+const FEE_HISTORY_BLOCKS: u64 = 1024;
+
+/// Most block ranges sampled per day, roughly one per hour
+const MAX_SAMPLE_RANGES: u64 = 24;
+
+/// Priority fee percentiles requested from `eth_feeHistory`
+const REWARD_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+/// Percentiles computed per day, keyed by chain and date
+pub(crate) type PercentileCache = Arc<Mutex<HashMap<(NamedChain, NaiveDate), DailyGasPercentiles>>>;
+
+/// 10th, 50th and 90th percentile of a gas price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasPercentiles {
+    #[serde(rename = "p10")]
+    pub p10: GasPrice,
+    #[serde(rename = "p50")]
+    pub p50: GasPrice,
+    #[serde(rename = "p90")]
+    pub p90: GasPrice,
+}
+
+impl GasPercentiles {
+    /// Nearest-rank percentiles of `samples`, or `None` if there are none
+    fn from_samples(mut samples: Vec<u128>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = |percentile: usize| {
+            let index = (samples.len() * percentile).div_ceil(100).saturating_sub(1);
+            GasPrice::from(alloy_primitives::U256::from(samples[index]))
+        };
+        Some(Self {
+            p10: rank(10),
+            p50: rank(50),
+            p90: rank(90),
+        })
+    }
+}
+
+/// How [`DailyGasPercentiles`] were sampled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasPercentileSource {
+    /// `eth_feeHistory`, with base fees and priority fees
+    FeeHistory,
+    /// Block headers, with base fees only
+    BlockHeaders,
+}
+
+/// Gas price percentiles over one UTC day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyGasPercentiles {
+    #[serde(rename = "chain")]
+    pub chain: NamedChain,
+    #[serde(rename = "date")]
+    pub date: NaiveDate,
+    /// Block window the samples were drawn from
+    #[serde(rename = "window")]
+    pub window: DailyBlockWindow,
+    /// Base fee per gas across the sampled blocks
+    #[serde(rename = "base_fee")]
+    pub base_fee: GasPercentiles,
+    /// Effective gas price (base fee plus priority fee) across the sampled
+    /// blocks, pooling each block's 10th, 50th and 90th percentile
+    /// transaction. `None` when sampled from block headers.
+    #[serde(rename = "effective_gas_price")]
+    pub effective_gas_price: Option<GasPercentiles>,
+    /// Number of blocks sampled
+    #[serde(rename = "sampled_blocks")]
+    pub sampled_blocks: usize,
+    #[serde(rename = "source")]
+    pub source: GasPercentileSource,
+}
+
+/// Block ranges to sample from a window, as `(first_block, block_count)`
+///
+/// Ranges hold at most `range_blocks` blocks. Windows that fit in
+/// [`MAX_SAMPLE_RANGES`] ranges are covered completely; longer ones get that
+/// many evenly spaced ranges.
+fn sample_ranges(
+    start_block: BlockNumber,
+    end_block: BlockNumber,
+    range_blocks: u64,
+) -> Vec<(BlockNumber, u64)> {
+    let span = end_block.saturating_sub(start_block) + 1;
+    let range_count = span.div_ceil(range_blocks).min(MAX_SAMPLE_RANGES);
+    let stride = (span / range_count).max(range_blocks);
+    (0..range_count)
+        .map(|range| {
+            let first = start_block + range * stride;
+            let count = range_blocks.min(end_block - first + 1);
+            (first, count)
+        })
+        .collect()
+}
+
+impl<N: Network, P: Provider<N>> GasCostCalculator<N, P> {
+    /// Base fee and effective gas price percentiles for `date` on `chain`
+    ///
+    /// The day's block window comes from `windows`. Results are cached per
+    /// `(chain, date)` for the lifetime of this calculator.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the block window can't be found, or if neither fee history nor
+    /// block headers yield any samples.
+    pub async fn daily_gas_percentiles<W: Provider>(
+        &self,
+        chain: NamedChain,
+        date: NaiveDate,
+        windows: &BlockWindowCalculator<W>,
+    ) -> Result<DailyGasPercentiles, SemioscanError> {
+        if let Some(cached) = self.percentile_cache.lock().await.get(&(chain, date)) {
+            return Ok(cached.clone());
+        }
+
+        let window = windows.get_daily_window(chain, date).await?;
+
+        // Without probed capabilities, assume the common 1024-block limit and
+        // find out the hard way
+        let fee_history_blocks = match self.capabilities.resolve(&self.provider).await {
+            Some(capabilities) => capabilities
+                .fee_history_max_blocks
+                .map(|max_blocks| max_blocks.min(FEE_HISTORY_BLOCKS)),
+            None => Some(FEE_HISTORY_BLOCKS),
+        };
+        let ranges = sample_ranges(
+            window.start_block,
+            window.end_block,
+            fee_history_blocks.unwrap_or(FEE_HISTORY_BLOCKS),
+        );
+
+        let fee_history = match fee_history_blocks {
+            Some(_) => self
+                .sample_fee_history(&ranges)
+                .await
+                .inspect_err(|error| {
+                    warn!(
+                        ?chain,
+                        %date,
+                        %error,
+                        "eth_feeHistory failed, sampling base fees from block headers"
+                    );
+                })
+                .ok(),
+            None => None,
+        };
+
+        let (base_fees, effective_prices, source) = match fee_history {
+            Some((base_fees, effective_prices)) => (
+                base_fees,
+                Some(effective_prices),
+                GasPercentileSource::FeeHistory,
+            ),
+            None => {
+                let blocks = ranges.iter().map(|(first, _)| *first);
+                let base_fees =
+                    get_base_fees(&self.provider, blocks, None, self.header_cache.as_ref())
+                        .await
+                        .fees
+                        .into_values()
+                        .map(|base_fee| base_fee.as_u256().saturating_to::<u128>())
+                        .collect();
+                (base_fees, None, GasPercentileSource::BlockHeaders)
+            }
+        };
+
+        let sampled_blocks = base_fees.len();
+        let base_fee = GasPercentiles::from_samples(base_fees).ok_or_else(|| {
+            GasCalculationError::calculation_failed(format!(
+                "no gas price samples for {chain:?} on {date}"
+            ))
+        })?;
+        let percentiles = DailyGasPercentiles {
+            chain,
+            date,
+            window,
+            base_fee,
+            effective_gas_price: effective_prices.and_then(GasPercentiles::from_samples),
+            sampled_blocks,
+            source,
+        };
+        debug!(?percentiles, "Computed daily gas percentiles");
+
+        self.percentile_cache
+            .lock()
+            .await
+            .insert((chain, date), percentiles.clone());
+        Ok(percentiles)
+    }
+
+    /// Base fee and effective gas price samples from `eth_feeHistory`
+    async fn sample_fee_history(
+        &self,
+        ranges: &[(BlockNumber, u64)],
+    ) -> Result<(Vec<u128>, Vec<u128>), RpcError> {
+        let mut base_fees = Vec::new();
+        let mut effective_prices = Vec::new();
+
+        for &(first, count) in ranges {
+            let last = first + count - 1;
+            let history = self
+                .provider
+                .get_fee_history(count, BlockNumberOrTag::Number(last), &REWARD_PERCENTILES)
+                .await
+                .map_err(|e| RpcError::request_failed(format!("eth_feeHistory({last})"), e))?;
+
+            let rewards = history.reward.unwrap_or_default();
+            // `base_fee_per_gas` also holds the base fee of the block after `last`
+            for (block, base_fee) in history
+                .base_fee_per_gas
+                .iter()
+                .take(count as usize)
+                .enumerate()
+            {
+                base_fees.push(*base_fee);
+                if let Some(block_rewards) = rewards.get(block) {
+                    effective_prices.extend(
+                        block_rewards
+                            .iter()
+                            .map(|reward| base_fee.saturating_add(*reward)),
+                    );
+                }
+            }
+        }
+
+        Ok((base_fees, effective_prices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_ranges_cover_short_windows_and_spread_over_long_ones() {
+        assert_eq!(sample_ranges(100, 100, 1024), vec![(100, 1)]);
+        assert_eq!(sample_ranges(0, 1_499, 1024), vec![(0, 1024), (1024, 476)]);
+        assert_eq!(
+            sample_ranges(0, 299, 128),
+            vec![(0, 128), (128, 128), (256, 44)]
+        );
+
+        // A day of 2-second blocks
+        let ranges = sample_ranges(1_000_000, 1_043_199, 1024);
+        assert_eq!(ranges.len(), 24);
+        assert_eq!(ranges[0], (1_000_000, 1024));
+        assert_eq!(ranges[1], (1_001_800, 1024));
+        let (last_first, last_count) = ranges[23];
+        assert!(last_first + last_count - 1 <= 1_043_199);
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        assert_eq!(GasPercentiles::from_samples(Vec::new()), None);
+
+        let percentiles = GasPercentiles::from_samples((1..=100).rev().collect()).unwrap();
+        assert_eq!(percentiles.p10, GasPrice::new(10));
+        assert_eq!(percentiles.p50, GasPrice::new(50));
+        assert_eq!(percentiles.p90, GasPrice::new(90));
+
+        let single = GasPercentiles::from_samples(vec![7]).unwrap();
+        assert_eq!(single.p10, GasPrice::new(7));
+        assert_eq!(single.p90, GasPrice::new(7));
+    }
+}
@@ -0,0 +1,14 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions from semioscan results into formats used by other analytics tools.
+//!
+//! - `record_batch` (feature `arrow`) - Arrow `RecordBatch`
+//!   conversions for combined, gas, and price results
+
+#[cfg(feature = "arrow")]
+mod record_batch;
+
+#[cfg(feature = "arrow")]
+pub use record_batch::{gas_results_to_arrow, price_results_to_arrow, U256_DECIMAL_PRECISION};
@@ -0,0 +1,335 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Arrow `RecordBatch` conversions.
+//!
+//! Wei and raw token amounts become `Decimal256(76, 0)` columns, which
+//! DataFusion, Polars and pyarrow read natively. Arrow decimals top out at 76
+//! digits, slightly below `U256::MAX` (78 digits); a value that does not fit is
+//! reported as an [`ArrowError::InvalidArgumentError`] rather than truncated.
+//! Addresses and hashes are `FixedSizeBinary` columns.
+
+use std::sync::Arc;
+
+use ::arrow::array::{
+    ArrayRef, Decimal256Builder, FixedSizeBinaryBuilder, Float64Builder, UInt64Builder,
+};
+use ::arrow::datatypes::{i256, DataType, Field, Schema};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+use alloy_primitives::{Address, U256};
+
+use crate::gas::GasCostResult;
+use crate::price::TokenPriceResult;
+use crate::retrieval::CombinedDataResult;
+
+/// Precision of the `Decimal256` columns used for U256 values
+pub const U256_DECIMAL_PRECISION: u8 = 76;
+
+impl CombinedDataResult {
+    /// Convert `transactions_data` into an Arrow `RecordBatch`, one row per entry
+    ///
+    /// The chain and address columns repeat the result-level values on every
+    /// row so batches from different results can be concatenated directly.
+    /// Rows are identified by `(chain_id, tx_hash, log_index)`; `log_index` is
+    /// null when the provider omitted it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrowError::InvalidArgumentError`] if an amount exceeds the
+    /// 76-digit range of `Decimal256`.
+    pub fn to_arrow(&self) -> Result<RecordBatch, ArrowError> {
+        let rows = self.transactions_data.len();
+        let mut chain_id = UInt64Builder::with_capacity(rows);
+        let mut from_address = FixedSizeBinaryBuilder::with_capacity(rows, 20);
+        let mut to_address = FixedSizeBinaryBuilder::with_capacity(rows, 20);
+        let mut token_address = FixedSizeBinaryBuilder::with_capacity(rows, 20);
+        let mut tx_hash = FixedSizeBinaryBuilder::with_capacity(rows, 32);
+        let mut log_index = UInt64Builder::with_capacity(rows);
+        let mut block_number = UInt64Builder::with_capacity(rows);
+        let mut gas_used = decimal_builder(rows)?;
+        let mut effective_gas_price = decimal_builder(rows)?;
+        let mut l1_fee = decimal_builder(rows)?;
+        let mut blob_gas_cost = decimal_builder(rows)?;
+        let mut total_gas_cost = decimal_builder(rows)?;
+        let mut transferred_amount = decimal_builder(rows)?;
+
+        for tx in &self.transactions_data {
+            chain_id.append_value(self.chain as u64);
+            append_address(&mut from_address, self.from_address)?;
+            append_address(&mut to_address, self.to_address)?;
+            append_address(&mut token_address, self.token_address)?;
+            tx_hash.append_value(tx.tx_hash.as_slice())?;
+            log_index.append_option(tx.log_index);
+            block_number.append_value(tx.block_number);
+            gas_used.append_value(to_i256(tx.gas_used.as_u256())?);
+            effective_gas_price.append_value(to_i256(tx.effective_gas_price.as_u256())?);
+            match tx.l1_fee {
+                Some(fee) => l1_fee.append_value(to_i256(fee)?),
+                None => l1_fee.append_null(),
+            }
+            blob_gas_cost.append_value(to_i256(tx.blob_gas_cost)?);
+            total_gas_cost.append_value(to_i256(tx.total_gas_cost())?);
+            transferred_amount.append_value(to_i256(tx.transferred_amount)?);
+        }
+
+        let schema = Schema::new(vec![
+            Field::new("chain_id", DataType::UInt64, false),
+            Field::new("from_address", DataType::FixedSizeBinary(20), false),
+            Field::new("to_address", DataType::FixedSizeBinary(20), false),
+            Field::new("token_address", DataType::FixedSizeBinary(20), false),
+            Field::new("tx_hash", DataType::FixedSizeBinary(32), false),
+            Field::new("log_index", DataType::UInt64, true),
+            Field::new("block_number", DataType::UInt64, false),
+            decimal_field("gas_used", false),
+            decimal_field("effective_gas_price", false),
+            decimal_field("l1_fee", true),
+            decimal_field("blob_gas_cost", false),
+            decimal_field("total_gas_cost", false),
+            decimal_field("transferred_amount", false),
+        ]);
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(chain_id.finish()),
+            Arc::new(from_address.finish()),
+            Arc::new(to_address.finish()),
+            Arc::new(token_address.finish()),
+            Arc::new(tx_hash.finish()),
+            Arc::new(log_index.finish()),
+            Arc::new(block_number.finish()),
+            Arc::new(gas_used.finish()),
+            Arc::new(effective_gas_price.finish()),
+            Arc::new(l1_fee.finish()),
+            Arc::new(blob_gas_cost.finish()),
+            Arc::new(total_gas_cost.finish()),
+            Arc::new(transferred_amount.finish()),
+        ];
+
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
+}
+
+impl GasCostResult {
+    /// Convert this result into a single-row Arrow `RecordBatch`
+    ///
+    /// Use [`gas_results_to_arrow`] to convert many results into one batch.
+    pub fn to_arrow(&self) -> Result<RecordBatch, ArrowError> {
+        gas_results_to_arrow(std::slice::from_ref(self))
+    }
+}
+
+/// Convert gas cost results into an Arrow `RecordBatch`, one row per result
+///
+/// # Errors
+///
+/// Returns [`ArrowError::InvalidArgumentError`] if a cost exceeds the 76-digit
+/// range of `Decimal256`.
+pub fn gas_results_to_arrow(results: &[GasCostResult]) -> Result<RecordBatch, ArrowError> {
+    let rows = results.len();
+    let mut chain_id = UInt64Builder::with_capacity(rows);
+    let mut from = FixedSizeBinaryBuilder::with_capacity(rows, 20);
+    let mut to = FixedSizeBinaryBuilder::with_capacity(rows, 20);
+    let mut total_gas_cost = decimal_builder(rows)?;
+    let mut transaction_count = UInt64Builder::with_capacity(rows);
+    let mut execution_gas_cost = decimal_builder(rows)?;
+    let mut blob_gas_cost = decimal_builder(rows)?;
+    let mut l1_data_fee = decimal_builder(rows)?;
+    let mut blob_count = UInt64Builder::with_capacity(rows);
+
+    for result in results {
+        chain_id.append_value(result.chain as u64);
+        append_address(&mut from, result.from)?;
+        append_address(&mut to, result.to)?;
+        total_gas_cost.append_value(to_i256(result.total_gas_cost.as_u256())?);
+        transaction_count.append_value(result.transaction_count.as_usize() as u64);
+        execution_gas_cost.append_value(to_i256(result.breakdown.execution_gas_cost)?);
+        blob_gas_cost.append_value(to_i256(result.breakdown.blob_gas_cost)?);
+        l1_data_fee.append_value(to_i256(result.breakdown.l1_data_fee)?);
+        blob_count.append_value(result.breakdown.blob_count.as_usize() as u64);
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("chain_id", DataType::UInt64, false),
+        Field::new("from", DataType::FixedSizeBinary(20), false),
+        Field::new("to", DataType::FixedSizeBinary(20), false),
+        decimal_field("total_gas_cost", false),
+        Field::new("transaction_count", DataType::UInt64, false),
+        decimal_field("execution_gas_cost", false),
+        decimal_field("blob_gas_cost", false),
+        decimal_field("l1_data_fee", false),
+        Field::new("blob_count", DataType::UInt64, false),
+    ]);
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(chain_id.finish()),
+        Arc::new(from.finish()),
+        Arc::new(to.finish()),
+        Arc::new(total_gas_cost.finish()),
+        Arc::new(transaction_count.finish()),
+        Arc::new(execution_gas_cost.finish()),
+        Arc::new(blob_gas_cost.finish()),
+        Arc::new(l1_data_fee.finish()),
+        Arc::new(blob_count.finish()),
+    ];
+
+    RecordBatch::try_new(Arc::new(schema), columns)
+}
+
+/// Convert token price results into an Arrow `RecordBatch`, one row per result
+///
+/// Amounts are already normalized, so they are written as `Float64`.
+pub fn price_results_to_arrow(results: &[TokenPriceResult]) -> Result<RecordBatch, ArrowError> {
+    let rows = results.len();
+    let mut token_address = FixedSizeBinaryBuilder::with_capacity(rows, 20);
+    let mut total_token_amount = Float64Builder::with_capacity(rows);
+    let mut total_usdc_amount = Float64Builder::with_capacity(rows);
+    let mut average_price = Float64Builder::with_capacity(rows);
+    let mut transaction_count = UInt64Builder::with_capacity(rows);
+
+    for result in results {
+        append_address(&mut token_address, result.token_address)?;
+        total_token_amount.append_value(result.total_token_amount.as_f64());
+        total_usdc_amount.append_value(result.total_usdc_amount.as_f64());
+        average_price.append_value(result.get_average_price().as_f64());
+        transaction_count.append_value(result.transaction_count.as_usize() as u64);
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("token_address", DataType::FixedSizeBinary(20), false),
+        Field::new("total_token_amount", DataType::Float64, false),
+        Field::new("total_usdc_amount", DataType::Float64, false),
+        Field::new("average_price", DataType::Float64, false),
+        Field::new("transaction_count", DataType::UInt64, false),
+    ]);
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(token_address.finish()),
+        Arc::new(total_token_amount.finish()),
+        Arc::new(total_usdc_amount.finish()),
+        Arc::new(average_price.finish()),
+        Arc::new(transaction_count.finish()),
+    ];
+
+    RecordBatch::try_new(Arc::new(schema), columns)
+}
+
+fn decimal_field(name: &str, nullable: bool) -> Field {
+    Field::new(
+        name,
+        DataType::Decimal256(U256_DECIMAL_PRECISION, 0),
+        nullable,
+    )
+}
+
+fn decimal_builder(capacity: usize) -> Result<Decimal256Builder, ArrowError> {
+    Decimal256Builder::with_capacity(capacity).with_precision_and_scale(U256_DECIMAL_PRECISION, 0)
+}
+
+fn append_address(
+    builder: &mut FixedSizeBinaryBuilder,
+    address: Address,
+) -> Result<(), ArrowError> {
+    builder.append_value(address.as_slice())
+}
+
+/// Convert a U256 into an `i256`, rejecting values outside `Decimal256(76, 0)`
+fn to_i256(value: U256) -> Result<i256, ArrowError> {
+    let max = U256::from(10u64).pow(U256::from(U256_DECIMAL_PRECISION)) - U256::from(1u64);
+    if value > max {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "{value} exceeds Decimal256 precision {U256_DECIMAL_PRECISION}"
+        )));
+    }
+    Ok(i256::from_le_bytes(value.to_le_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retrieval::GasAndAmountForTx;
+    use crate::types::gas::{GasAmount, GasPrice};
+    use ::arrow::array::{Array, Decimal256Array, UInt64Array};
+    use alloy_chains::NamedChain;
+    use alloy_primitives::TxHash;
+
+    fn sample_tx(l1_fee: Option<u64>) -> GasAndAmountForTx {
+        GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(0xab),
+            block_number: 100,
+            block_timestamp: None,
+            tx_index: None,
+            log_index: None,
+            token_address: None,
+            gas_used: GasAmount::new(21_000),
+            effective_gas_price: GasPrice::new(10),
+            base_fee_per_gas: None,
+            l1_fee: l1_fee.map(U256::from),
+            blob_gas_cost: U256::ZERO,
+            transferred_amount: U256::from(1_000u64),
+        }
+    }
+
+    #[test]
+    fn test_combined_result_batch_has_one_row_per_transaction() {
+        let mut result = CombinedDataResult::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+        );
+        result.add_transaction_data(sample_tx(Some(5)));
+        result.add_transaction_data(GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(0xcd),
+            log_index: Some(3),
+            ..sample_tx(None)
+        });
+
+        let batch = result.to_arrow().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 13);
+
+        let log_index = batch
+            .column_by_name("log_index")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert!(log_index.is_null(0));
+        assert_eq!(log_index.value(1), 3);
+
+        let total = batch
+            .column_by_name("total_gas_cost")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Decimal256Array>()
+            .unwrap();
+        assert_eq!(total.value(0), i256::from(210_005i64));
+        assert_eq!(total.value(1), i256::from(210_000i64));
+
+        let l1_fee = batch.column_by_name("l1_fee").unwrap();
+        assert!(l1_fee.is_valid(0));
+        assert!(l1_fee.is_null(1));
+    }
+
+    #[test]
+    fn test_gas_results_batch_has_one_row_per_result() {
+        let mut result = GasCostResult::new(
+            NamedChain::Mainnet,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+        );
+        result.total_gas_cost = crate::WeiAmount::from(1_000u64);
+
+        let batch = gas_results_to_arrow(&[result.clone(), result.clone()]).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(result.to_arrow().unwrap().num_rows(), 1);
+    }
+
+    #[test]
+    fn test_values_beyond_decimal256_are_rejected() {
+        assert!(to_i256(U256::MAX).is_err());
+        let max = U256::from(10u64).pow(U256::from(76u64)) - U256::from(1u64);
+        assert!(to_i256(max).is_ok());
+    }
+}
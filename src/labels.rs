@@ -0,0 +1,332 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Human-readable labels and tags for addresses
+//!
+//! Reports full of raw hex addresses are hard to read. An [`AddressBook`] maps
+//! addresses to labels such as `"Binance Hot Wallet 14"` plus free-form tags,
+//! and [`AddressBook::display`] renders an address with its label when one is
+//! known.
+//!
+//! # File format
+//!
+//! Address books are keyed by address. Each value is either a plain label or a
+//! table with a label and tags:
+//!
+//! ```json
+//! {
+//!   "0x28C6c06298d514Db089934071355E5743bf21d60": "Binance Hot Wallet 14",
+//!   "0x111111125421cA6dc452d289314280a0f8842A65": {
+//!     "label": "1inch Router v6",
+//!     "tags": ["router", "aggregator"]
+//!   }
+//! }
+//! ```
+//!
+//! The same layout is accepted as TOML when the `toml` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use alloy_primitives::address;
+//! use semioscan::AddressBook;
+//!
+//! let book = AddressBook::from_json_str(
+//!     r#"{ "0x28C6c06298d514Db089934071355E5743bf21d60": "Binance Hot Wallet 14" }"#,
+//! )?;
+//!
+//! let hot_wallet = address!("28C6c06298d514Db089934071355E5743bf21d60");
+//! assert_eq!(book.label(&hot_wallet), Some("Binance Hot Wallet 14"));
+//! assert_eq!(
+//!     book.display(hot_wallet).to_string(),
+//!     "Binance Hot Wallet 14 (0x28C6c06298d514Db089934071355E5743bf21d60)"
+//! );
+//!
+//! // Unlabelled addresses format as the checksummed address
+//! let unknown = address!("0000000000000000000000000000000000000001");
+//! assert_eq!(book.display(unknown).to_string(), unknown.to_string());
+//! # Ok::<(), semioscan::AddressBookError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AddressBookError;
+
+/// Label and tags attached to an address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RawAddressLabel")]
+pub struct AddressLabel {
+    /// Human-readable name for the address
+    #[serde(rename = "label")]
+    pub label: String,
+    /// Free-form tags (e.g. `"cex"`, `"router"`)
+    #[serde(rename = "tags", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl AddressLabel {
+    /// Create a label with no tags
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Add a tag
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Check whether the label carries `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+/// Accepts either `"label"` or `{ label, tags }` when deserializing
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAddressLabel {
+    Plain(String),
+    Detailed {
+        label: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+}
+
+impl From<RawAddressLabel> for AddressLabel {
+    fn from(raw: RawAddressLabel) -> Self {
+        match raw {
+            RawAddressLabel::Plain(label) => Self::new(label),
+            RawAddressLabel::Detailed { label, tags } => Self { label, tags },
+        }
+    }
+}
+
+/// Dictionary of address labels
+///
+/// Serializes as a map from address to [`AddressLabel`], sorted by address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AddressBook {
+    entries: BTreeMap<Address, AddressLabel>,
+}
+
+impl AddressBook {
+    /// Create an empty address book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load an address book, choosing the format from the file extension
+    ///
+    /// `.json` is always supported; `.toml` requires the `toml` feature.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AddressBookError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| AddressBookError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json_str(&contents),
+            #[cfg(feature = "toml")]
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::from_toml_str(&contents),
+            _ => Err(AddressBookError::UnsupportedFormat {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+
+    /// Parse an address book from a JSON document
+    pub fn from_json_str(json: &str) -> Result<Self, AddressBookError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Parse an address book from a TOML document
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, AddressBookError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Serialize the address book to pretty-printed JSON
+    pub fn to_json_string(&self) -> Result<String, AddressBookError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Add or replace the label for `address`, returning the previous one
+    pub fn insert(&mut self, address: Address, label: AddressLabel) -> Option<AddressLabel> {
+        self.entries.insert(address, label)
+    }
+
+    /// Add or replace the label for `address`
+    pub fn with_label(mut self, address: Address, label: impl Into<String>) -> Self {
+        self.insert(address, AddressLabel::new(label));
+        self
+    }
+
+    /// Merge `other` into this book; labels from `other` win on conflict
+    pub fn extend(&mut self, other: AddressBook) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Label and tags for `address`, if known
+    pub fn get(&self, address: &Address) -> Option<&AddressLabel> {
+        self.entries.get(address)
+    }
+
+    /// Label for `address`, if known
+    pub fn label(&self, address: &Address) -> Option<&str> {
+        self.get(address).map(|entry| entry.label.as_str())
+    }
+
+    /// Addresses carrying `tag`
+    pub fn tagged<'a>(
+        &'a self,
+        tag: &'a str,
+    ) -> impl Iterator<Item = (&'a Address, &'a AddressLabel)> {
+        self.entries
+            .iter()
+            .filter(move |(_, entry)| entry.has_tag(tag))
+    }
+
+    /// All labelled addresses, sorted by address
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &AddressLabel)> {
+        self.entries.iter()
+    }
+
+    /// Number of labelled addresses
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the book has no labels
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Wrap `address` so it formats with its label when one is known
+    pub fn display(&self, address: Address) -> LabeledAddress<'_> {
+        LabeledAddress {
+            address,
+            label: self.label(&address),
+        }
+    }
+}
+
+/// An address paired with its optional label, for display
+///
+/// Formats as `Label (0x…)` when labelled and as the checksummed address otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabeledAddress<'a> {
+    /// The address being displayed
+    pub address: Address,
+    /// Its label, if the address book has one
+    pub label: Option<&'a str>,
+}
+
+impl fmt::Display for LabeledAddress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.label {
+            Some(label) => write!(f, "{label} ({})", self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const BINANCE: Address = address!("28C6c06298d514Db089934071355E5743bf21d60");
+    const ROUTER: Address = address!("111111125421cA6dc452d289314280a0f8842A65");
+
+    #[test]
+    fn json_accepts_plain_and_detailed_labels() {
+        let book = AddressBook::from_json_str(
+            r#"{
+                "0x28C6c06298d514Db089934071355E5743bf21d60": "Binance Hot Wallet 14",
+                "0x111111125421cA6dc452d289314280a0f8842A65": {
+                    "label": "1inch Router v6",
+                    "tags": ["router", "aggregator"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(book.len(), 2);
+        assert_eq!(book.label(&BINANCE), Some("Binance Hot Wallet 14"));
+        assert!(book.get(&ROUTER).unwrap().has_tag("router"));
+        assert_eq!(book.tagged("aggregator").count(), 1);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let mut book = AddressBook::new().with_label(BINANCE, "Binance Hot Wallet 14");
+        book.insert(
+            ROUTER,
+            AddressLabel::new("1inch Router v6").with_tag("router"),
+        );
+
+        let json = book.to_json_string().unwrap();
+        assert_eq!(AddressBook::from_json_str(&json).unwrap(), book);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_uses_the_same_layout() {
+        let book = AddressBook::from_toml_str(
+            r#"
+            "0x28C6c06298d514Db089934071355E5743bf21d60" = "Binance Hot Wallet 14"
+
+            ["0x111111125421cA6dc452d289314280a0f8842A65"]
+            label = "1inch Router v6"
+            tags = ["router"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(book.label(&BINANCE), Some("Binance Hot Wallet 14"));
+        assert!(book.get(&ROUTER).unwrap().has_tag("router"));
+    }
+
+    #[test]
+    fn load_dispatches_on_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("labels.json");
+        std::fs::write(
+            &json,
+            r#"{"0x28C6c06298d514Db089934071355E5743bf21d60": "Binance"}"#,
+        )
+        .unwrap();
+        assert_eq!(AddressBook::load(&json).unwrap().len(), 1);
+
+        let csv = dir.path().join("labels.csv");
+        std::fs::write(&csv, "").unwrap();
+        assert!(matches!(
+            AddressBook::load(&csv),
+            Err(AddressBookError::UnsupportedFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn display_annotates_known_addresses() {
+        let book = AddressBook::new().with_label(BINANCE, "Binance Hot Wallet 14");
+
+        assert_eq!(
+            book.display(BINANCE).to_string(),
+            format!("Binance Hot Wallet 14 ({BINANCE})")
+        );
+        assert_eq!(book.display(ROUTER).to_string(), ROUTER.to_string());
+    }
+}
@@ -15,65 +15,126 @@
 //! - `types` - Strong types for type safety
 //! - `config` - Configuration system
 //! - `gas` - Gas calculation domain
-//! - `price` - Price extraction domain
+//! - `price` - Price extraction domain and USD exchange rates
 //! - `blocks` - Block window calculations
 //! - `events` - Event processing
+//! - `labels` - Address labels and tags for human-readable output
 //! - `provider` - Dynamic provider utilities for runtime chain selection
 //! - `transport` - Transport layer utilities (rate limiting, etc.)
+//! - `interop` - Conversions into external analytics formats such as Arrow (internal)
+//! - `sink` - Persisting results to external storage (backends are feature-gated)
+//! - `client` - High-level client wiring the calculators together
+//! - `daily` - Nightly runs of the client's calculations over a set of targets
+//! - `report` - Markdown and HTML summaries of combined results
 //! - `cache` - Caching infrastructure (internal)
 //! - `retrieval` - Data orchestration (internal)
 //! - `tracing` - Observability (internal)
 
 // === Module Declarations ===
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod blocks;
 mod cache;
+mod client;
 pub mod config;
+mod daily;
+#[cfg(feature = "ens")]
+mod ens;
 pub mod errors;
 mod events;
+#[cfg(feature = "firehose")]
+pub mod firehose;
 mod gas;
+mod interop;
+mod labels;
 pub mod price;
 pub mod provider;
+#[cfg(feature = "pyo3")]
+pub mod python;
+mod report;
 mod retrieval;
+pub mod sink;
 mod tracing;
 pub mod transport;
 mod types;
 
 // === Core Types (from types/) ===
+pub use types::address::{RecipientAddress, SenderAddress, TokenAddress};
 pub use types::config::{BlockCount, MaxBlockRange, TransactionCount};
+pub use types::display::{DisplayOptions, DisplayValue};
 pub use types::fees::{L1DataFee, Percentage};
 pub use types::gas::{
     BlobCount, BlobGasAmount, BlobGasPrice, GasAmount, GasBreakdown, GasBreakdownBuilder, GasPrice,
 };
 pub use types::tokens::{
-    NormalizedAmount, TokenAmount, TokenDecimals, TokenPrice, TokenSet, UsdValue, UsdValueError,
+    Currency, FiatValue, NormalizedAmount, TokenAmount, TokenDecimals, TokenMetadata, TokenPrice,
+    TokenSet, UsdValue, UsdValueError,
 };
+pub use types::warnings::ScanWarning;
 pub use types::wei::WeiAmount;
 
 // === Configuration (from config/) ===
+pub use config::calibration::{
+    BlockRangeCalibrator, CalibrationOptions, CalibrationProbe, CalibrationResult,
+    CalibrationStore, ProbeOutcome,
+};
 pub use config::constants;
-pub use config::{ChainConfig, SemioscanConfig, SemioscanConfigBuilder};
+pub use config::{
+    AdaptiveChunking, ChainConfig, ScanPolicy, SemioscanConfig, SemioscanConfigBuilder,
+};
+pub use config::{SpanConfig, SpanSubsystem, SpanVerbosity};
 
 // === Error Types (from errors/) ===
 pub use errors::{
-    BlockWindowError, EventProcessingError, GasCalculationError, PriceCalculationError,
-    RetrievalError, RpcError, SemioscanError,
+    AddressBookError, BlockWindowError, EventProcessingError, FxError, GasCalculationError,
+    JobError, PriceCalculationError, RetrievalError, RpcError, SemioscanError, SinkError,
 };
 
 // === Gas Calculation (from gas/) ===
 pub use gas::adapter::{EthereumReceiptAdapter, OptimismReceiptAdapter, ReceiptAdapter};
 pub use gas::blob;
 pub use gas::cache::GasCache;
-pub use gas::{EventType, GasCostCalculator, GasCostResult, GasForTx};
+pub use gas::{
+    DailyGasPercentiles, EventType, GasCostCalculator, GasCostResult, GasForTx,
+    GasPercentileSource, GasPercentiles, NetworkCheck, TransactionGas,
+};
 
 // === Price Extraction (from price/) ===
+#[cfg(feature = "fx-http")]
+pub use price::HttpFxRates;
 pub use price::{
-    PriceCalculator, PriceSource, PriceSourceError, RawSwapResult, SwapData, TokenPriceResult,
+    Denominator, FxRate, LiquidityThresholds, PriceCalculator, PriceConfidence, PriceSource,
+    PriceSourceError, PriceWarning, QuoteSide, RawSwapResult, StablecoinRegistry, StaticFxRates,
+    SwapData, SwapDedup, TokenPriceResult,
 };
 
 // === Block Windows (from blocks/) ===
 pub use blocks::{
-    BlockWindowCache, BlockWindowCalculator, CacheKey, CacheStats, DailyBlockWindow, DiskCache,
-    MemoryCache, NoOpCache, UnixTimestamp,
+    BlockCheckpoint, BlockCheckpoints, BlockInterval, BlockWindowCache, BlockWindowCalculator,
+    CacheKey, CachePolicy, CacheStats, DailyBlockWindow, MemoryCache, NoOpCache, Provenance,
+    TimeInterval, UnixTimestamp, WindowProvenance,
+};
+
+#[cfg(feature = "disk-cache")]
+pub use blocks::{AuditAction, AuditRecord, AuditedCache, CacheAuditLog};
+#[cfg(feature = "disk-cache")]
+pub use blocks::{DiskCache, MaintenanceHandle, MaintenanceReport};
+
+#[cfg(feature = "compression")]
+pub use blocks::Codec;
+
+// === Contract Deployments (from cache/) ===
+pub use cache::deployment::{find_deployment_block, DeploymentCache};
+
+// === Block Headers (from cache/) ===
+pub use cache::header::{CachedHeader, HeaderCache, HeaderCacheStats};
+
+// === Transactions and Receipts (from cache/) ===
+pub use cache::transaction::{TransactionCache, TransactionCacheStats};
+
+// === Finality (from cache/) ===
+pub use cache::finality::{
+    Checkpoint, Finality, FinalitySource, FinalityTag, FinalityTracker, Reconciliation,
 };
 
 // === Cache Types (from blocks/cache/types, re-exported via types/cache) ===
@@ -81,23 +142,82 @@ pub use types::cache::{AccessSequence, TimestampMillis};
 
 // === Events (from events/) ===
 pub use events::fetch_logs_chunked;
-pub use events::EventScanner;
-pub use events::{extract_transferred_to_tokens, extract_transferred_to_tokens_with_config};
-pub use events::{AmountCalculator, AmountResult};
-pub use events::{Approval, Transfer};
+#[cfg(feature = "hypersync")]
+pub use events::HyperSyncLogSource;
+pub use events::{
+    arbitrum_retryable_leg, bridge_event_topics, extract_arbitrum_legs, extract_op_stack_legs,
+    BridgeDirection, BridgeFlow, BridgeFlowCalculator, BridgeFlows, BridgeLeg, BridgeLegKind,
+    BridgeLegScanner, BridgeProtocol,
+};
+pub use events::{
+    discover_recipients, discover_recipients_with_config, extract_transferred_to_tokens,
+    extract_transferred_to_tokens_with_config,
+};
+pub use events::{
+    extract_safe_executions, ExecutionFailure, ExecutionSuccess, SafeExecution,
+    SafeExecutionScanner, SafeTransfer,
+};
+pub use events::{
+    wrapped_native_as_transfer, AmountCalculator, AmountResult, TransferTax, TransferTaxMode,
+};
+pub use events::{AllowanceKey, AllowanceState, AllowanceTracker, ApprovalAnalyzer};
+pub use events::{Approval, Transfer, WethDeposit, WethWithdrawal};
+pub use events::{ApprovalFilterBuilder, EventQuery, TransferFilterBuilder};
+pub use events::{CachedLogSource, LogCacheStats, LogSource, RpcLogSource};
+pub use events::{DecodedEvent, DecodedScan, EventDecoderFn, EventRegistry, EventScanner};
+pub use events::{
+    Enricher, EventSink, Passthrough, Pipeline, PipelineHandle, PipelineInput, PipelineStats,
+};
+pub use events::{TransferEdge, TransferGraph, TransferGraphBuilder, TransferGraphExport};
+
+// === Address Labels (from labels/) ===
+#[cfg(feature = "ens")]
+pub use ens::{namehash, EnsResolver, ENS_REGISTRY, ENS_TAG};
+pub use labels::{AddressBook, AddressLabel, LabeledAddress};
 
 // === Retrieval (Data Orchestration) ===
 pub use retrieval::{
-    batch_fetch_balances, batch_fetch_eth_balances, get_token_decimal_precision,
-    u256_to_bigdecimal, BalanceError, BalanceQuery, BalanceResult, CombinedCalculator,
-    CombinedDataLookupAttempt, CombinedDataLookupFailure, CombinedDataLookupPass,
-    CombinedDataLookupStage, CombinedDataResult, CombinedDataRetrievalMetadata, DecimalPrecision,
-    GasAndAmountForTx,
+    batch_fetch_balances, batch_fetch_balances_at, batch_fetch_eth_balances, enrich_with_usd,
+    get_token_decimal_precision, u256_to_bigdecimal, AllowanceChange, AnomalyDetector,
+    AnomalyMethod, AnomalyMetric, BalanceChange, BalanceError, BalanceQuery, BalanceResult,
+    CallCache, CombinedCalculator, CombinedDataFiat, CombinedDataLookupAttempt,
+    CombinedDataLookupFailure, CombinedDataLookupPass, CombinedDataLookupStage, CombinedDataResult,
+    CombinedDataRetrievalMetadata, CombinedDataUsd, CombinedQuery, CombinedQueryBuilder,
+    DailyAnomaly, DailyCaller, DailyCombinedData, DecimalPrecision, GasAndAmountFiat,
+    GasAndAmountForTx, GasAndAmountUsd, GasAttribution, HolderSnapshot, SnapshotCollector,
+    SnapshotDiff, TransferCursor, UsdPriceTable, WindowBoundary,
+};
+#[cfg(feature = "disk-cache")]
+pub use retrieval::{
+    BackfillTask, JobManifest, JobReport, JobRunner, JobState, TaskFailure, TaskRecord, TaskStatus,
 };
 
+// === High-level Client (from client.rs) ===
+pub use client::{
+    CalculatorRequest, CalculatorResponse, CalculatorService, DailyGasReport, SemioscanClient,
+    SemioscanClientBuilder,
+};
+
+// === Daily Runs (from daily.rs) ===
+pub use daily::{
+    DailyReport, DailyRunReport, DailyRunner, DailyTarget, FailedTarget, RunSummary, TargetOutcome,
+};
+pub use sink::json::JsonLinesSink;
+#[cfg(feature = "webhook")]
+pub use sink::webhook::WebhookSink;
+pub use sink::{ResultSink, SinkRecord};
+
+// === Reports (from report.rs) ===
+pub use report::{Report, ReportFormat, ReportOptions};
+
+// === Interop (from interop/) ===
+#[cfg(feature = "arrow")]
+pub use interop::{gas_results_to_arrow, price_results_to_arrow, U256_DECIMAL_PRECISION};
+
 // === Transport Layers ===
 pub use transport::{
-    RateLimitLayer, RateLimitService, RetryConfig, RetryLayer, RetryLayerBuilder, RetryService,
+    EndpointBudget, KeyUsage, KeyUsageTracker, RateBudget, RateLimitLayer, RateLimitService,
+    RetryConfig, RetryLayer, RetryLayerBuilder, RetryService, RotatingTransport, RotationReason,
 };
 
 // === Provider Utilities ===
@@ -105,9 +225,11 @@ pub use transport::{
 pub use provider::create_ws_provider;
 pub use provider::{
     create_http_provider, create_typed_http_provider, network_type_for_chain,
-    rate_limited_http_provider, simple_http_provider, AnyHttpProvider, ChainAwareProvider,
-    ChainEndpoint, DynProviderBuilder, EthereumHttpProvider, NetworkType, OptimismHttpProvider,
-    PooledProvider, ProviderConfig, ProviderPool, ProviderPoolBuilder, SharedProvider,
+    rate_limited_http_provider, simple_http_provider, AnyHttpProvider, CapabilityCache,
+    ChainAwareProvider, ChainEndpoint, ChainHead, DynProviderBuilder, EthereumHttpProvider,
+    HeadTracker, HeadTrackerHandle, NetworkType, OptimismHttpProvider, PooledProvider,
+    ProviderCapabilities, ProviderConfig, ProviderPool, ProviderPoolBuilder, ReorgDetector,
+    ReorgEvent, ReorgHook, SharedProvider,
 };
 
 // Note: Cache internals (cache::BlockRangeCache) and tracing spans are NOT re-exported
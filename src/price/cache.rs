@@ -2,9 +2,14 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use alloy_primitives::{Address, BlockNumber};
+use std::collections::{BTreeSet, HashMap};
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockHash, BlockNumber};
+use chrono::NaiveDate;
 
 use crate::cache::block_range::{BlockRangeCache, Mergeable};
+use crate::cache::finality::{Checkpoint, Reconciliation};
 use crate::price::calculator::TokenPriceResult;
 
 /// A range of blocks with start and end inclusive
@@ -64,10 +69,19 @@ impl Mergeable for TokenPriceResult {
 /// Cache for token price calculation results
 ///
 /// This cache stores price data keyed by `(token_address, start_block, end_block)` and provides
-/// intelligent features like automatic range merging and gap detection.
+/// intelligent features like automatic range merging and gap detection. Final
+/// per-date results are also kept, keyed by `(chain, token_address, date)`.
 #[derive(Debug, Clone, Default)]
 pub struct PriceCache {
     inner: BlockRangeCache<Address, TokenPriceResult>,
+    dates: HashMap<(NamedChain, Address, NaiveDate), DatedPrice>,
+}
+
+/// A per-date result and the last block of the day it covers
+#[derive(Debug, Clone)]
+struct DatedPrice {
+    end_block: BlockNumber,
+    result: TokenPriceResult,
 }
 
 impl PriceCache {
@@ -100,6 +114,69 @@ impl PriceCache {
             .insert(token_address, start_block, end_block, result);
     }
 
+    /// Insert a result computed against unfinalized blocks
+    ///
+    /// Like [`insert`](Self::insert), but the entry is dropped by a later
+    /// [`settle`](Self::settle) if `checkpoint`'s block is reorged out.
+    pub fn insert_provisional(
+        &mut self,
+        token_address: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        result: TokenPriceResult,
+        checkpoint: Checkpoint,
+    ) {
+        self.inner
+            .insert_provisional(token_address, start_block, end_block, result, checkpoint);
+    }
+
+    /// Result cached for `token_address` on `date`, if any
+    pub fn get_for_date(
+        &self,
+        chain: NamedChain,
+        token_address: Address,
+        date: NaiveDate,
+    ) -> Option<TokenPriceResult> {
+        self.dates
+            .get(&(chain, token_address, date))
+            .map(|dated| dated.result.clone())
+    }
+
+    /// Cache the result for `token_address` on `date`, whose last block is
+    /// `end_block`
+    pub fn insert_for_date(
+        &mut self,
+        chain: NamedChain,
+        token_address: Address,
+        date: NaiveDate,
+        end_block: BlockNumber,
+        result: TokenPriceResult,
+    ) {
+        self.dates.insert(
+            (chain, token_address, date),
+            DatedPrice { end_block, result },
+        );
+    }
+
+    /// Clear all cached entries that reach `first_invalid_block` or later
+    pub fn clear_from_block(&mut self, first_invalid_block: BlockNumber) {
+        self.inner
+            .retain(|_, _, end_block| end_block < first_invalid_block);
+        self.dates
+            .retain(|_, dated| dated.end_block < first_invalid_block);
+    }
+
+    /// Checkpoint blocks of provisional entries that are now final
+    pub fn pending_checkpoints(&self, finalized_block: BlockNumber) -> BTreeSet<BlockNumber> {
+        self.inner.pending_checkpoints(finalized_block)
+    }
+
+    /// Confirm or drop provisional entries given the canonical hashes of
+    /// final blocks
+    pub fn settle(&mut self, canonical: &HashMap<BlockNumber, BlockHash>) -> Reconciliation {
+        self.inner.settle(canonical)
+    }
+
     /// Calculate which block ranges need to be processed by finding gaps in the cached data
     ///
     /// Returns a tuple of:
@@ -141,6 +218,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(token_amount),
             total_usdc_amount: UsdValue::new(usdc_amount),
             transaction_count: TransactionCount::new(1),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         }
     }
 
@@ -514,6 +595,55 @@ mod tests {
         assert!(result.is_some(), "Should handle large block numbers");
     }
 
+    #[test]
+    fn test_date_entries_are_keyed_by_chain_token_and_date() {
+        let mut cache = PriceCache::default();
+        let token = address!("1111111111111111111111111111111111111111");
+        let date = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let result = create_price_result(token, 10.0, 20.0);
+
+        cache.insert_for_date(NamedChain::Base, token, date, 2000, result.clone());
+
+        assert_eq!(
+            cache.get_for_date(NamedChain::Base, token, date),
+            Some(result)
+        );
+        assert_eq!(cache.get_for_date(NamedChain::Mainnet, token, date), None);
+        assert_eq!(
+            cache.get_for_date(NamedChain::Base, token, date.succ_opt().unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clear_from_block_drops_reorged_dates() {
+        let mut cache = PriceCache::default();
+        let token = address!("1111111111111111111111111111111111111111");
+        let day = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let next_day = day.succ_opt().unwrap();
+        cache.insert_for_date(
+            NamedChain::Base,
+            token,
+            day,
+            1999,
+            create_price_result(token, 1.0, 1.0),
+        );
+        cache.insert_for_date(
+            NamedChain::Base,
+            token,
+            next_day,
+            3999,
+            create_price_result(token, 1.0, 1.0),
+        );
+
+        cache.clear_from_block(3000);
+
+        assert!(cache.get_for_date(NamedChain::Base, token, day).is_some());
+        assert!(cache
+            .get_for_date(NamedChain::Base, token, next_day)
+            .is_none());
+    }
+
     #[test]
     fn test_multiple_tokens_isolated() {
         let mut cache = PriceCache::default();
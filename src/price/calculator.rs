@@ -7,18 +7,30 @@ use alloy_erc20::LazyToken;
 use alloy_primitives::{Address, BlockNumber, B256, U256};
 use alloy_provider::Provider;
 use alloy_rpc_types::Filter;
+use chrono::NaiveDate;
 use futures::future::join_all;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
+use crate::blocks::window::BlockWindowCalculator;
+use crate::cache::deployment::DeploymentCache;
+use crate::cache::finality::{log_reconciliation, FinalityTracker};
 use crate::config::SemioscanConfig;
-use crate::errors::PriceCalculationError;
+use crate::errors::{PriceCalculationError, RpcError};
 use crate::events::scanner::EventScanner;
+use crate::events::source::LogSource;
 use crate::price::cache::PriceCache;
-use crate::price::{PriceSource, PriceSourceError, SwapData};
-use crate::{NormalizedAmount, TokenAmount, TokenDecimals, TokenPrice, TransactionCount, UsdValue};
+use crate::price::dedup::LoggedSwap;
+use crate::price::{PriceSource, PriceSourceError, SwapData, SwapDedup};
+use crate::types::address::TokenAddress;
+use crate::types::warnings::{extend_warnings, ScanWarning};
+use crate::{
+    NormalizedAmount, Percentage, TokenAmount, TokenDecimals, TokenPrice, TransactionCount,
+    UsdValue,
+};
 
 // Internal type for swap data processing
 struct SwapAmounts {
@@ -26,13 +38,130 @@ struct SwapAmounts {
     usdc_amount: UsdValue,
 }
 
-// Price calculation result
-#[derive(Debug, Clone, Serialize)]
+/// Aggregated price data for a token over a block range
+///
+/// Serialized field names are fixed via `serde(rename)` so stored results remain
+/// readable across releases.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TokenPriceResult {
+    #[serde(rename = "token_address")]
     pub token_address: Address,
+    #[serde(rename = "total_token_amount")]
     pub total_token_amount: NormalizedAmount,
+    #[serde(rename = "total_usdc_amount")]
     pub total_usdc_amount: UsdValue,
+    #[serde(rename = "transaction_count")]
     pub transaction_count: TransactionCount,
+    /// Whether the calculation was cancelled before covering the whole range
+    ///
+    /// Omitted from the serialized form when `false`.
+    #[serde(
+        default,
+        rename = "cancelled",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub cancelled: bool,
+    /// Problems found with the result, such as disagreement with a
+    /// cross-check source (see [`PriceCalculator::with_cross_check`])
+    ///
+    /// Omitted from the serialized form when empty.
+    #[serde(default, rename = "warnings", skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PriceWarning>,
+    /// Swap logs and block ranges left out of the totals
+    ///
+    /// Omitted from the serialized form when empty.
+    #[serde(
+        default,
+        rename = "scan_warnings",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub scan_warnings: Vec<ScanWarning>,
+    /// How much trading activity backs the price, when the calculator has
+    /// [`LiquidityThresholds`] configured
+    ///
+    /// Omitted from the serialized form when not assessed.
+    #[serde(
+        default,
+        rename = "confidence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub confidence: Option<PriceConfidence>,
+}
+
+/// Whether a price is backed by enough trading to be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceConfidence {
+    /// Volume and swap count meet the configured thresholds
+    Sufficient,
+    /// Some swaps were found, but fewer or smaller than the thresholds require
+    Low,
+    /// No swaps were found, so there is no price
+    None,
+}
+
+/// Minimum trading activity behind a price the calculator trusts
+///
+/// A daily price from a single $5 swap is easy to move and shouldn't be
+/// trusted. Results below either threshold are marked
+/// [`PriceConfidence::Low`] (or [`PriceConfidence::None`] without swaps), or
+/// rejected with [`PriceCalculationError::InsufficientLiquidity`] when
+/// [`rejecting`](Self::rejecting) is set.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LiquidityThresholds {
+    /// Minimum total USDC volume across the counted swaps
+    pub min_volume: UsdValue,
+    /// Minimum number of counted swaps
+    pub min_swaps: TransactionCount,
+    /// Return an error instead of marking the result
+    pub reject: bool,
+}
+
+impl LiquidityThresholds {
+    /// Thresholds that mark results below `min_volume` or `min_swaps`
+    pub fn new(min_volume: UsdValue, min_swaps: TransactionCount) -> Self {
+        Self {
+            min_volume,
+            min_swaps,
+            reject: false,
+        }
+    }
+
+    /// Fail calculations below the thresholds instead of marking them
+    pub fn rejecting(mut self) -> Self {
+        self.reject = true;
+        self
+    }
+
+    /// Confidence in `result` under these thresholds
+    pub fn assess(&self, result: &TokenPriceResult) -> PriceConfidence {
+        if result.transaction_count == TransactionCount::ZERO {
+            PriceConfidence::None
+        } else if result.total_usdc_amount < self.min_volume
+            || result.transaction_count < self.min_swaps
+        {
+            PriceConfidence::Low
+        } else {
+            PriceConfidence::Sufficient
+        }
+    }
+}
+
+/// Something that makes a [`TokenPriceResult`] suspect without making it an error
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriceWarning {
+    /// The cross-check source's average price differs from this result's by
+    /// more than the configured threshold
+    SourceDivergence {
+        /// Average price from the cross-check source
+        reference_price: TokenPrice,
+        /// Relative difference from the reference price (`0.1` is 10%)
+        divergence: f64,
+    },
+    /// This result found swaps but the cross-check source found none, so
+    /// its price couldn't be checked
+    NoReferenceSwaps,
 }
 
 impl Default for TokenPriceResult {
@@ -42,6 +171,10 @@ impl Default for TokenPriceResult {
             total_token_amount: NormalizedAmount::ZERO,
             total_usdc_amount: UsdValue::ZERO,
             transaction_count: TransactionCount::ZERO,
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         }
     }
 }
@@ -53,6 +186,10 @@ impl TokenPriceResult {
             total_token_amount: NormalizedAmount::ZERO,
             total_usdc_amount: UsdValue::ZERO,
             transaction_count: TransactionCount::ZERO,
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         }
     }
 
@@ -75,6 +212,11 @@ impl TokenPriceResult {
         self.total_token_amount += other.total_token_amount;
         self.total_usdc_amount += other.total_usdc_amount;
         self.transaction_count += other.transaction_count;
+        self.cancelled |= other.cancelled;
+        self.warnings.extend(other.warnings.iter().cloned());
+        extend_warnings(&mut self.scan_warnings, &other.scan_warnings);
+        // The combined totals haven't been assessed
+        self.confidence = None;
     }
 
     /// Get the total token amount
@@ -97,17 +239,22 @@ impl TokenPriceResult {
 ///
 /// This struct provides per-transaction granularity for swap data,
 /// useful when you need individual swap details rather than aggregated totals.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RawSwapResult {
     /// The raw swap data from the DEX event
+    #[serde(rename = "swap")]
     pub swap: SwapData,
     /// Normalized amount of input token (accounting for decimals)
+    #[serde(rename = "normalized_token_in_amount")]
     pub normalized_token_in_amount: NormalizedAmount,
     /// Normalized amount of output token (accounting for decimals)
+    #[serde(rename = "normalized_token_out_amount")]
     pub normalized_token_out_amount: NormalizedAmount,
     /// Decimals for the input token
+    #[serde(rename = "token_in_decimals")]
     pub token_in_decimals: TokenDecimals,
     /// Decimals for the output token
+    #[serde(rename = "token_out_decimals")]
     pub token_out_decimals: TokenDecimals,
 }
 
@@ -175,6 +322,19 @@ pub struct PriceCalculator<P> {
     token_decimals_cache: HashMap<Address, TokenDecimals>,
     price_cache: Mutex<PriceCache>,
     config: SemioscanConfig,
+    cancellation: Option<CancellationToken>,
+    log_source: Option<Arc<dyn LogSource>>,
+    finality: Option<FinalityTracker>,
+    cross_check: Option<CrossCheck>,
+    swap_dedup: SwapDedup,
+    liquidity_thresholds: Option<LiquidityThresholds>,
+    deployments: Option<DeploymentCache>,
+}
+
+/// Second price source the calculator compares its results against
+struct CrossCheck {
+    source: Box<dyn PriceSource>,
+    max_divergence: Percentage,
 }
 
 impl<P: Provider + Clone> PriceCalculator<P> {
@@ -193,7 +353,7 @@ impl<P: Provider + Clone> PriceCalculator<P> {
     pub fn new(
         provider: P,
         chain: NamedChain,
-        usdc_address: Address,
+        usdc_address: impl Into<TokenAddress>,
         price_source: Box<dyn PriceSource>,
     ) -> Self {
         Self::with_config(
@@ -205,6 +365,20 @@ impl<P: Provider + Clone> PriceCalculator<P> {
         )
     }
 
+    /// Create a new PriceCalculator quoting in the chain's canonical USDC
+    ///
+    /// Returns `None` when [`canonical_usdc`](crate::constants::canonical_usdc)
+    /// has no USDC for `chain`; use [`PriceCalculator::new`] with an explicit
+    /// address there.
+    pub fn for_chain(
+        provider: P,
+        chain: NamedChain,
+        price_source: Box<dyn PriceSource>,
+    ) -> Option<Self> {
+        crate::config::constants::canonical_usdc(chain)
+            .map(|usdc| Self::new(provider, chain, usdc, price_source))
+    }
+
     /// Create a new PriceCalculator with custom configuration
     ///
     /// # Arguments
@@ -217,21 +391,167 @@ impl<P: Provider + Clone> PriceCalculator<P> {
     pub fn with_config(
         provider: P,
         chain: NamedChain,
-        usdc_address: Address,
+        usdc_address: impl Into<TokenAddress>,
         price_source: Box<dyn PriceSource>,
         config: crate::SemioscanConfig,
     ) -> Self {
         Self {
             provider,
             price_source,
-            usdc_address,
+            usdc_address: usdc_address.into().as_address(),
             chain,
             token_decimals_cache: HashMap::new(),
             price_cache: Default::default(),
             config,
+            cancellation: None,
+            log_source: None,
+            finality: None,
+            cross_check: None,
+            swap_dedup: SwapDedup::default(),
+            liquidity_thresholds: None,
+            deployments: None,
         }
     }
 
+    /// Stop calculations between chunks once `token` is cancelled
+    ///
+    /// A cancelled calculation returns the totals gathered so far with
+    /// [`TokenPriceResult::cancelled`] set, instead of an error.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Fetch swap logs from `source` instead of `eth_getLogs` on the provider
+    pub fn with_log_source(mut self, source: Arc<dyn LogSource>) -> Self {
+        self.log_source = Some(source);
+        self
+    }
+
+    /// Cache ranges reaching past the finalized block provisionally
+    ///
+    /// Each calculation first refreshes `tracker` and drops cached ranges whose
+    /// last block was reorged out before it became final.
+    pub fn with_finality_tracker(mut self, tracker: FinalityTracker) -> Self {
+        self.finality = Some(tracker);
+        self
+    }
+
+    /// Price every calculation again with `source` and warn on disagreement
+    ///
+    /// A silently wrong decoder in one [`PriceSource`] yields plausible but
+    /// wrong prices. With a cross-check, each
+    /// [`calculate_price_between_blocks`](Self::calculate_price_between_blocks)
+    /// also prices the token from `source` over the same range and adds a
+    /// [`PriceWarning`] to [`TokenPriceResult::warnings`] when the average
+    /// prices differ by more than `max_divergence` of the reference price.
+    ///
+    /// The check scans the range a second time on every call; its results are
+    /// not cached.
+    pub fn with_cross_check(
+        mut self,
+        source: Box<dyn PriceSource>,
+        max_divergence: Percentage,
+    ) -> Self {
+        self.cross_check = Some(CrossCheck {
+            source,
+            max_divergence,
+        });
+        self
+    }
+
+    /// How swaps describing the same trade are deduplicated before pricing
+    ///
+    /// Defaults to [`SwapDedup::ByLog`].
+    pub fn with_swap_dedup(mut self, policy: SwapDedup) -> Self {
+        self.swap_dedup = policy;
+        self
+    }
+
+    /// Start scans no earlier than the router's and the token's deployment
+    ///
+    /// Deployment blocks are found once per contract with a binary search
+    /// over `eth_getCode` and kept in `cache`, which may be shared with other
+    /// calculators. Ranges ending before either deployment price to an empty
+    /// result without scanning. Requires an archive node.
+    pub fn with_deployment_cache(mut self, cache: DeploymentCache) -> Self {
+        self.deployments = Some(cache);
+        self
+    }
+
+    /// Assess every result against `thresholds`
+    ///
+    /// Results get a [`TokenPriceResult::confidence`], or fail with
+    /// [`PriceCalculationError::InsufficientLiquidity`] if `thresholds` is
+    /// [`rejecting`](LiquidityThresholds::rejecting). Cancelled results are
+    /// marked but never rejected.
+    pub fn with_liquidity_thresholds(mut self, thresholds: LiquidityThresholds) -> Self {
+        self.liquidity_thresholds = Some(thresholds);
+        self
+    }
+
+    /// Use already known `decimals` for tokens instead of reading them
+    ///
+    /// [`SemioscanClient::price_calculator`](crate::SemioscanClient::price_calculator)
+    /// passes the decimals it prefetched here.
+    pub fn with_token_decimals(
+        mut self,
+        decimals: impl IntoIterator<Item = (Address, TokenDecimals)>,
+    ) -> Self {
+        self.token_decimals_cache.extend(decimals);
+        self
+    }
+
+    fn lock_price_cache(&self) -> std::sync::MutexGuard<'_, PriceCache> {
+        self.price_cache.lock().expect(
+            "Price cache mutex poisoned - indicates a panic occurred while holding the lock",
+        )
+    }
+
+    /// Refresh finality and settle the provisional cache entries it now covers
+    async fn settle_provisional(&self) -> Result<(), RpcError> {
+        let Some(finality) = &self.finality else {
+            return Ok(());
+        };
+        let finalized_block = finality.refresh(&self.provider).await?.finalized_block;
+        let pending = self.lock_price_cache().pending_checkpoints(finalized_block);
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let canonical = finality.canonical_hashes(&self.provider, pending).await?;
+        let reconciliation = self.lock_price_cache().settle(&canonical);
+        log_reconciliation("price", reconciliation);
+        Ok(())
+    }
+
+    /// Cache `result`, provisionally if `end_block` isn't final yet
+    async fn cache_result(
+        &self,
+        token_address: Address,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+        result: TokenPriceResult,
+    ) -> Result<(), RpcError> {
+        let checkpoint = match &self.finality {
+            Some(finality) => finality.checkpoint(&self.provider, end_block).await?,
+            None => None,
+        };
+        let mut cache = self.lock_price_cache();
+        match checkpoint {
+            Some(checkpoint) => {
+                cache.insert_provisional(token_address, start_block, end_block, result, checkpoint)
+            }
+            None => cache.insert(token_address, start_block, end_block, result),
+        }
+        Ok(())
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
     async fn get_token_decimals(
         &mut self,
         token_address: Address,
@@ -342,7 +662,13 @@ impl<P: Provider + Clone> PriceCalculator<P> {
         let event_topics = self.price_source.event_topics();
 
         // Create a scanner to handle chunking and rate limiting
-        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let mut scanner = EventScanner::new(&self.provider, self.config.clone());
+        if let Some(token) = &self.cancellation {
+            scanner = scanner.with_cancellation(token.clone());
+        }
+        if let Some(source) = &self.log_source {
+            scanner = scanner.with_log_source(source.clone());
+        }
 
         // Build a filter for swap events from the price source
         let filter = Filter::new()
@@ -350,8 +676,8 @@ impl<P: Provider + Clone> PriceCalculator<P> {
             .event_signature(event_topics.clone());
 
         // Scan for all swap events in this gap
-        let logs = scanner
-            .scan(self.chain, filter, gap_start, gap_end)
+        let (logs, gaps) = scanner
+            .scan_with_warnings(self.chain, filter, gap_start, gap_end)
             .await
             .map_err(|e| {
                 PriceCalculationError::processing_failed(format!(
@@ -366,9 +692,18 @@ impl<P: Provider + Clone> PriceCalculator<P> {
             "Fetched logs for gap"
         );
 
-        // First pass: Extract all swap data and collect unique token addresses
+        // The scanner stops between chunks once cancelled; the logs it did fetch
+        // are still priced below
+        gap_result.cancelled = self.is_cancelled();
+        for gap in gaps {
+            self.config
+                .scan_policy
+                .record(&mut gap_result.scan_warnings, gap)
+                .map_err(PriceCalculationError::skipped_data)?;
+        }
+
+        // First pass: Extract the relevant swaps along with their log identity
         let mut swaps = Vec::new();
-        let mut token_addresses = HashSet::new();
 
         for log in &logs {
             match self.price_source.extract_swap_from_log(log) {
@@ -384,10 +719,11 @@ impl<P: Provider + Clone> PriceCalculator<P> {
                             && swap_data.token_out == token_address);
 
                     if is_relevant {
-                        // Collect token addresses for batch fetching
-                        token_addresses.insert(swap_data.token_in);
-                        token_addresses.insert(swap_data.token_out);
-                        swaps.push(swap_data);
+                        swaps.push(LoggedSwap {
+                            key: log.transaction_hash.zip(log.log_index),
+                            emitter: log.address(),
+                            swap: swap_data,
+                        });
                     }
                 }
                 Ok(None) => {
@@ -395,6 +731,13 @@ impl<P: Provider + Clone> PriceCalculator<P> {
                 }
                 Err(e @ PriceSourceError::DecodeError(_)) => {
                     error!(error = ?e, "Failed to decode log");
+                    self.config
+                        .scan_policy
+                        .record(
+                            &mut gap_result.scan_warnings,
+                            ScanWarning::skipped_log(log, &e),
+                        )
+                        .map_err(PriceCalculationError::skipped_data)?;
                 }
                 Err(
                     e @ (PriceSourceError::EmptyTokenArrays
@@ -402,10 +745,24 @@ impl<P: Provider + Clone> PriceCalculator<P> {
                     | PriceSourceError::InvalidSwapData { .. }),
                 ) => {
                     error!(error = ?e, "Invalid swap data in log");
+                    self.config
+                        .scan_policy
+                        .record(
+                            &mut gap_result.scan_warnings,
+                            ScanWarning::skipped_log(log, &e),
+                        )
+                        .map_err(PriceCalculationError::skipped_data)?;
                 }
             }
         }
 
+        // Count each trade once, then collect unique token addresses
+        let swaps = self.swap_dedup.apply(swaps);
+        let token_addresses: HashSet<Address> = swaps
+            .iter()
+            .flat_map(|swap| [swap.token_in, swap.token_out])
+            .collect();
+
         // Batch fetch all token decimals in parallel
         // When CallBatchLayer is enabled, these parallel calls are automatically
         // batched into a single Multicall3 RPC request
@@ -424,6 +781,18 @@ impl<P: Provider + Clone> PriceCalculator<P> {
                 }
                 Err(e) => {
                     error!(error = ?e, "Error processing swap data");
+                    self.config
+                        .scan_policy
+                        .record(
+                            &mut gap_result.scan_warnings,
+                            ScanWarning::SkippedLog {
+                                block_number: swap_data.block_number,
+                                tx_hash: swap_data.tx_hash,
+                                log_index: None,
+                                reason: e.to_string(),
+                            },
+                        )
+                        .map_err(PriceCalculationError::skipped_data)?;
                 }
             }
         }
@@ -468,7 +837,94 @@ impl<P: Provider + Clone> PriceCalculator<P> {
         Ok(None)
     }
 
+    /// Price `result`'s token from the cross-check source over the same
+    /// range and record any disagreement in its warnings
+    async fn cross_check_price(
+        &mut self,
+        result: &mut TokenPriceResult,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<(), PriceCalculationError> {
+        let Some(mut cross_check) = self.cross_check.take() else {
+            return Ok(());
+        };
+
+        // Run the reference source through the same scanning path, then put
+        // both sources back before surfacing any error
+        std::mem::swap(&mut self.price_source, &mut cross_check.source);
+        let reference = self
+            .process_gap_for_price(result.token_address, start_block, end_block)
+            .await;
+        std::mem::swap(&mut self.price_source, &mut cross_check.source);
+        let max_divergence = cross_check.max_divergence;
+        self.cross_check = Some(cross_check);
+
+        let reference = reference?;
+        // A partial reference scan can't vouch for or against the price
+        if reference.cancelled {
+            return Ok(());
+        }
+        if let Some(warning) = compare_prices(result, &reference, max_divergence) {
+            warn!(
+                token_address = ?result.token_address,
+                start_block,
+                end_block,
+                warning = ?warning,
+                "Price disagrees with cross-check source"
+            );
+            result.warnings.push(warning);
+        }
+        Ok(())
+    }
+
+    /// Price `token_address` against USDC from swaps in `start_block..=end_block`
+    ///
+    /// Cached block ranges are reused and only the gaps are scanned. With
+    /// [`LiquidityThresholds`] configured, the result's confidence is assessed
+    /// last.
     pub async fn calculate_price_between_blocks(
+        &mut self,
+        token_address: impl Into<TokenAddress>,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> Result<TokenPriceResult, PriceCalculationError> {
+        let token_address = token_address.into().as_address();
+        let result = self
+            .price_between_blocks(token_address, start_block, end_block)
+            .await?;
+        self.assess_confidence(result)
+    }
+
+    /// Mark or reject `result` according to the liquidity thresholds
+    fn assess_confidence(
+        &self,
+        mut result: TokenPriceResult,
+    ) -> Result<TokenPriceResult, PriceCalculationError> {
+        let Some(thresholds) = self.liquidity_thresholds else {
+            return Ok(result);
+        };
+        let confidence = thresholds.assess(&result);
+        if thresholds.reject && confidence != PriceConfidence::Sufficient && !result.cancelled {
+            return Err(PriceCalculationError::InsufficientLiquidity {
+                token: result.token_address,
+                volume: result.total_usdc_amount,
+                swaps: result.transaction_count,
+            });
+        }
+        if confidence != PriceConfidence::Sufficient {
+            warn!(
+                token_address = ?result.token_address,
+                volume = result.total_usdc_amount.as_f64(),
+                swaps = result.transaction_count.as_usize(),
+                confidence = ?confidence,
+                "Price backed by less trading than the configured thresholds"
+            );
+        }
+        result.confidence = Some(confidence);
+        Ok(result)
+    }
+
+    async fn price_between_blocks(
         &mut self,
         token_address: Address,
         start_block: BlockNumber,
@@ -481,21 +937,42 @@ impl<P: Provider + Clone> PriceCalculator<P> {
             "Starting price calculation"
         );
 
-        // Check cache and calculate gaps that need to be filled
-        let (cached_result, gaps) = {
-            let cache = self.price_cache.lock().expect(
-                "Price cache mutex poisoned - indicates a panic occurred while holding the lock",
-            );
-            cache.calculate_gaps(token_address, start_block, end_block)
+        self.settle_provisional().await?;
+
+        let start_block = match &self.deployments {
+            Some(deployments) => {
+                let contracts = [self.price_source.router_address(), token_address];
+                let clamped = deployments
+                    .clamp_start(
+                        &self.provider,
+                        self.chain,
+                        &contracts,
+                        start_block,
+                        end_block,
+                    )
+                    .await?;
+                let Some(start_block) = clamped else {
+                    return Ok(TokenPriceResult::new(token_address));
+                };
+                start_block
+            }
+            None => start_block,
         };
 
+        // Check cache and calculate gaps that need to be filled
+        let (cached_result, gaps) =
+            self.lock_price_cache()
+                .calculate_gaps(token_address, start_block, end_block);
+
         // If there are no gaps, we can return the cached result
-        if let Some(result) = cached_result.clone() {
+        if let Some(mut result) = cached_result.clone() {
             if gaps.is_empty() {
                 info!(
                     token_address = ?token_address,
                     "Using complete cached result for block range"
                 );
+                self.cross_check_price(&mut result, start_block, end_block)
+                    .await?;
                 return Ok(result);
             }
         }
@@ -505,6 +982,16 @@ impl<P: Provider + Clone> PriceCalculator<P> {
 
         // Process each gap
         for gap in gaps {
+            if self.is_cancelled() {
+                warn!(
+                    token_address = ?token_address,
+                    gap_start = gap.start,
+                    "Price calculation cancelled, returning partial result"
+                );
+                price_data.cancelled = true;
+                break;
+            }
+
             info!(
                 token_address = ?token_address,
                 gap_start = gap.start,
@@ -517,23 +1004,29 @@ impl<P: Provider + Clone> PriceCalculator<P> {
                 .process_gap_for_price(token_address, gap.start, gap.end)
                 .await?;
 
-            // Cache the gap result
-            {
-                let mut cache = self.price_cache.lock()
-                    .expect("Price cache mutex poisoned - indicates a panic occurred while holding the lock");
-                cache.insert(token_address, gap.start, gap.end, gap_result.clone());
+            // A cancelled gap only covers part of its range, so it must not be cached
+            if gap_result.cancelled {
+                price_data.merge(&gap_result);
+                break;
             }
 
+            // Cache the gap result
+            self.cache_result(token_address, gap.start, gap.end, gap_result.clone())
+                .await?;
+
             // Merge the gap result with our main result
             price_data.merge(&gap_result);
         }
 
         // Cache the complete result
-        {
-            let mut cache = self.price_cache.lock().expect(
-                "Price cache mutex poisoned - indicates a panic occurred while holding the lock",
-            );
-            cache.insert(token_address, start_block, end_block, price_data.clone());
+        if !price_data.cancelled {
+            self.cache_result(token_address, start_block, end_block, price_data.clone())
+                .await?;
+        }
+
+        if !price_data.cancelled {
+            self.cross_check_price(&mut price_data, start_block, end_block)
+                .await?;
         }
 
         info!(
@@ -541,12 +1034,77 @@ impl<P: Provider + Clone> PriceCalculator<P> {
             total_token_amount = price_data.total_token_amount.as_f64(),
             total_usdc_amount = price_data.total_usdc_amount.as_f64(),
             transaction_count = price_data.transaction_count.as_usize(),
+            cancelled = price_data.cancelled,
             "Finished price calculation"
         );
 
         Ok(price_data)
     }
 
+    /// Price `token_address` over the blocks produced on `date` (UTC) on this
+    /// calculator's chain
+    ///
+    /// Resolves the day's block range with `window_calculator`, which must
+    /// query the same chain, then prices it
+    /// like [`calculate_price_between_blocks`](Self::calculate_price_between_blocks),
+    /// so cached block ranges are reused and only the gaps are scanned. The
+    /// finished result is also cached under `(chain, token_address, date)`,
+    /// making repeat calls for the same day free. Cancelled results, and days
+    /// whose last block isn't final yet when a
+    /// [`FinalityTracker`](crate::FinalityTracker) is configured, are not
+    /// cached by date.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let windows = BlockWindowCalculator::with_memory_cache(provider.clone());
+    /// let price = calculator
+    ///     .calculate_price_for_date(token, date, &windows)
+    ///     .await?;
+    /// println!("{}", price.get_average_price());
+    /// ```
+    pub async fn calculate_price_for_date<W: Provider>(
+        &mut self,
+        token_address: impl Into<TokenAddress>,
+        date: NaiveDate,
+        window_calculator: &BlockWindowCalculator<W>,
+    ) -> Result<TokenPriceResult, PriceCalculationError> {
+        let token_address = token_address.into().as_address();
+        let chain = self.chain;
+        if let Some(result) = self
+            .lock_price_cache()
+            .get_for_date(chain, token_address, date)
+        {
+            info!(
+                chain = %chain,
+                token_address = ?token_address,
+                date = %date,
+                "Using cached price for date"
+            );
+            return Ok(result);
+        }
+
+        let window = window_calculator.get_daily_window(chain, date).await?;
+        let result = self
+            .calculate_price_between_blocks(token_address, window.start_block, window.end_block)
+            .await?;
+
+        let is_final = self
+            .finality
+            .as_ref()
+            .is_none_or(|finality| finality.is_final(window.end_block));
+        if !result.cancelled && is_final {
+            self.lock_price_cache().insert_for_date(
+                chain,
+                token_address,
+                date,
+                window.end_block,
+                result.clone(),
+            );
+        }
+        Ok(result)
+    }
+
     /// Extract raw swap data per transaction from a block range.
     ///
     /// Unlike [`calculate_price_between_blocks`](Self::calculate_price_between_blocks) which
@@ -591,7 +1149,10 @@ impl<P: Provider + Clone> PriceCalculator<P> {
         let event_topics = self.price_source.event_topics();
 
         // Create a scanner to handle chunking and rate limiting
-        let scanner = EventScanner::new(&self.provider, self.config.clone());
+        let mut scanner = EventScanner::new(&self.provider, self.config.clone());
+        if let Some(source) = &self.log_source {
+            scanner = scanner.with_log_source(source.clone());
+        }
 
         // Build a filter for swap events from the price source
         let filter = Filter::new()
@@ -691,6 +1252,31 @@ impl<P: Provider + Clone> PriceCalculator<P> {
     }
 }
 
+/// Compare a result with the cross-check source's result for the same range
+fn compare_prices(
+    result: &TokenPriceResult,
+    reference: &TokenPriceResult,
+    max_divergence: Percentage,
+) -> Option<PriceWarning> {
+    if result.total_token_amount.is_zero() {
+        return None;
+    }
+    if reference.total_token_amount.is_zero() {
+        return Some(PriceWarning::NoReferenceSwaps);
+    }
+
+    let price = result.get_average_price().as_f64();
+    let reference_price = reference.get_average_price();
+    if reference_price.is_zero() {
+        return Some(PriceWarning::NoReferenceSwaps);
+    }
+    let divergence = (price - reference_price.as_f64()).abs() / reference_price.as_f64();
+    (divergence > max_divergence.as_f64()).then_some(PriceWarning::SourceDivergence {
+        reference_price,
+        divergence,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -876,6 +1462,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(100.0),
             total_usdc_amount: UsdValue::new(200.0),
             transaction_count: TransactionCount::new(5),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         };
 
         // Average price = 200.0 / 100.0 = 2.0 USDC per token
@@ -890,6 +1480,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(333.33),
             total_usdc_amount: UsdValue::new(999.99),
             transaction_count: TransactionCount::new(10),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         };
 
         // Average price ≈ 3.0
@@ -913,6 +1507,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(10.0),
             total_usdc_amount: UsdValue::new(20.0),
             transaction_count: TransactionCount::new(1),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         };
 
         let r2 = TokenPriceResult {
@@ -920,6 +1518,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(20.0),
             total_usdc_amount: UsdValue::new(40.0),
             transaction_count: TransactionCount::new(2),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         };
 
         let r3 = TokenPriceResult {
@@ -927,6 +1529,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(30.0),
             total_usdc_amount: UsdValue::new(60.0),
             transaction_count: TransactionCount::new(3),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         };
 
         total.merge(&r1);
@@ -948,6 +1554,10 @@ mod tests {
             total_token_amount: NormalizedAmount::new(0.000001), // Very small amount
             total_usdc_amount: UsdValue::new(0.00000123),        // Even smaller USDC amount
             transaction_count: TransactionCount::new(1),
+            cancelled: false,
+            warnings: Vec::new(),
+            scan_warnings: Vec::new(),
+            confidence: None,
         };
 
         let price = result.get_average_price();
@@ -958,4 +1568,185 @@ mod tests {
             price.as_f64()
         );
     }
+
+    #[test]
+    fn test_token_price_result_serde_round_trip() {
+        let token = address!("1111111111111111111111111111111111111111");
+        let mut result = TokenPriceResult::new(token);
+        result.add_swap(100.0, 250.5);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["total_token_amount"], 100.0);
+        assert_eq!(json["total_usdc_amount"], 250.5);
+        assert_eq!(json["transaction_count"], 1);
+        assert!(json.get("token_address").is_some());
+
+        let decoded: TokenPriceResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_raw_swap_result_serde_round_trip() {
+        let swap = RawSwapResult {
+            swap: SwapData {
+                token_in: address!("1111111111111111111111111111111111111111"),
+                token_in_amount: U256::from(1_000_000u64),
+                token_out: address!("2222222222222222222222222222222222222222"),
+                token_out_amount: U256::from(2_000_000u64),
+                sender: None,
+                tx_hash: Some(B256::repeat_byte(0xab)),
+                block_number: Some(42),
+            },
+            normalized_token_in_amount: NormalizedAmount::new(1.0),
+            normalized_token_out_amount: NormalizedAmount::new(2.0),
+            token_in_decimals: TokenDecimals::new(6),
+            token_out_decimals: TokenDecimals::new(6),
+        };
+
+        let json = serde_json::to_value(&swap).unwrap();
+        assert_eq!(json["swap"]["block_number"], 42);
+        assert_eq!(json["token_in_decimals"], 6);
+
+        let decoded: RawSwapResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, swap);
+    }
+
+    fn priced(token_amount: f64, usdc_amount: f64) -> TokenPriceResult {
+        let mut result = TokenPriceResult::new(Address::ZERO);
+        if token_amount > 0.0 {
+            result.add_swap(token_amount, usdc_amount);
+        }
+        result
+    }
+
+    #[test]
+    fn test_cross_check_accepts_prices_within_threshold() {
+        let warning = compare_prices(
+            &priced(10.0, 21.0),
+            &priced(10.0, 20.0),
+            Percentage::new(0.1),
+        );
+        assert_eq!(warning, None);
+    }
+
+    #[test]
+    fn test_cross_check_flags_divergent_prices() {
+        let warning = compare_prices(
+            &priced(10.0, 30.0),
+            &priced(10.0, 20.0),
+            Percentage::new(0.1),
+        );
+        assert_eq!(
+            warning,
+            Some(PriceWarning::SourceDivergence {
+                reference_price: TokenPrice::new(2.0),
+                divergence: 0.5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_cross_check_without_reference_swaps() {
+        let threshold = Percentage::new(0.1);
+        assert_eq!(
+            compare_prices(&priced(10.0, 20.0), &priced(0.0, 0.0), threshold),
+            Some(PriceWarning::NoReferenceSwaps)
+        );
+        // Nothing to check when the result itself has no swaps
+        assert_eq!(
+            compare_prices(&priced(0.0, 0.0), &priced(10.0, 20.0), threshold),
+            None
+        );
+    }
+
+    #[test]
+    fn test_warnings_serialize_only_when_present() {
+        let mut result = priced(10.0, 20.0);
+        assert!(serde_json::to_value(&result)
+            .unwrap()
+            .get("warnings")
+            .is_none());
+
+        result.warnings.push(PriceWarning::NoReferenceSwaps);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["warnings"][0]["kind"], "no_reference_swaps");
+        let decoded: TokenPriceResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_liquidity_thresholds_assess_confidence() {
+        let thresholds = LiquidityThresholds::new(UsdValue::new(1_000.0), TransactionCount::new(3));
+
+        assert_eq!(thresholds.assess(&priced(0.0, 0.0)), PriceConfidence::None);
+        // One large swap is still too few
+        assert_eq!(
+            thresholds.assess(&priced(1_000.0, 5_000.0)),
+            PriceConfidence::Low
+        );
+
+        let mut busy = TokenPriceResult::new(Address::ZERO);
+        for _ in 0..3 {
+            busy.add_swap(100.0, 400.0);
+        }
+        assert_eq!(thresholds.assess(&busy), PriceConfidence::Sufficient);
+    }
+
+    #[test]
+    fn test_confidence_serializes_only_when_assessed() {
+        let mut result = priced(1.0, 5.0);
+        assert!(serde_json::to_value(&result)
+            .unwrap()
+            .get("confidence")
+            .is_none());
+
+        result.confidence = Some(PriceConfidence::Low);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["confidence"], "low");
+    }
+
+    struct NoSwaps;
+
+    impl PriceSource for NoSwaps {
+        fn router_address(&self) -> Address {
+            Address::ZERO
+        }
+
+        fn event_topics(&self) -> Vec<B256> {
+            Vec::new()
+        }
+
+        fn extract_swap_from_log(
+            &self,
+            _log: &alloy_rpc_types::Log,
+        ) -> Result<Option<SwapData>, PriceSourceError> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_known_token_decimals_are_not_read_on_chain() {
+        use alloy_provider::ProviderBuilder;
+        use alloy_transport::mock::Asserter;
+
+        let token = address!("1111111111111111111111111111111111111111");
+        // No responses are queued, so any RPC call fails
+        let provider = ProviderBuilder::default().connect_mocked_client(Asserter::new());
+        let mut calculator = PriceCalculator::new(
+            provider,
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Box::new(NoSwaps),
+        )
+        .with_token_decimals([(token, TokenDecimals::WBTC)]);
+
+        assert_eq!(
+            calculator.get_token_decimals(token).await.unwrap(),
+            TokenDecimals::WBTC
+        );
+        assert!(calculator
+            .get_token_decimals(Address::repeat_byte(0x22))
+            .await
+            .is_err());
+    }
 }
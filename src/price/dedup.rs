@@ -0,0 +1,138 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deduplication of swaps before price aggregation
+//!
+//! The same economic swap can reach the calculator more than once: the same
+//! log returned twice by overlapping filters or log sources, or an aggregator
+//! swap alongside the pool swaps it routed through. [`SwapDedup`] decides
+//! which of those the calculator counts.
+
+use std::collections::HashSet;
+
+use alloy_primitives::{Address, B256};
+
+use crate::price::SwapData;
+
+/// How [`PriceCalculator`](crate::PriceCalculator) handles swaps that
+/// describe the same trade
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SwapDedup {
+    /// Count each swap log once, keyed by `(tx_hash, log_index)`
+    #[default]
+    ByLog,
+    /// Like [`ByLog`](Self::ByLog), and when a transaction has a swap emitted
+    /// by one of these aggregator contracts, drop its swaps emitted by other
+    /// contracts, which are the aggregator's routing legs
+    AggregatorWins(HashSet<Address>),
+}
+
+/// A swap with the identity of the log it was extracted from
+#[derive(Debug, Clone)]
+pub(crate) struct LoggedSwap {
+    /// `(tx_hash, log_index)`, when the log carries both
+    pub(crate) key: Option<(B256, u64)>,
+    /// Contract that emitted the log
+    pub(crate) emitter: Address,
+    pub(crate) swap: SwapData,
+}
+
+impl SwapDedup {
+    /// Drop repeated and superseded swaps, keeping the order of the rest
+    ///
+    /// Swaps from logs without a transaction hash or log index can't be
+    /// matched and are always kept.
+    pub(crate) fn apply(&self, swaps: Vec<LoggedSwap>) -> Vec<SwapData> {
+        let mut seen = HashSet::new();
+        let swaps: Vec<LoggedSwap> = swaps
+            .into_iter()
+            .filter(|swap| swap.key.is_none_or(|key| seen.insert(key)))
+            .collect();
+
+        let aggregator_txs: HashSet<B256> = match self {
+            SwapDedup::ByLog => HashSet::new(),
+            SwapDedup::AggregatorWins(aggregators) => swaps
+                .iter()
+                .filter(|swap| aggregators.contains(&swap.emitter))
+                .filter_map(|swap| swap.key.map(|(tx_hash, _)| tx_hash))
+                .collect(),
+        };
+
+        swaps
+            .into_iter()
+            .filter(|swap| match (self, swap.key) {
+                (SwapDedup::AggregatorWins(aggregators), Some((tx_hash, _))) => {
+                    aggregators.contains(&swap.emitter) || !aggregator_txs.contains(&tx_hash)
+                }
+                _ => true,
+            })
+            .map(|swap| swap.swap)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+
+    const AGGREGATOR: Address = address!("00000000000000000000000000000000000000a1");
+    const POOL: Address = address!("00000000000000000000000000000000000000b1");
+
+    fn logged(tx: u8, log_index: u64, emitter: Address, amount: u64) -> LoggedSwap {
+        LoggedSwap {
+            key: Some((B256::repeat_byte(tx), log_index)),
+            emitter,
+            swap: SwapData {
+                token_in: Address::ZERO,
+                token_in_amount: U256::from(amount),
+                token_out: Address::ZERO,
+                token_out_amount: U256::from(amount),
+                sender: None,
+                tx_hash: Some(B256::repeat_byte(tx)),
+                block_number: None,
+            },
+        }
+    }
+
+    fn amounts(swaps: &[SwapData]) -> Vec<u64> {
+        swaps
+            .iter()
+            .map(|swap| swap.token_in_amount.to::<u64>())
+            .collect()
+    }
+
+    #[test]
+    fn by_log_drops_repeated_logs() {
+        let swaps = vec![
+            logged(1, 0, POOL, 10),
+            logged(1, 0, POOL, 10),
+            logged(1, 1, POOL, 20),
+        ];
+
+        assert_eq!(amounts(&SwapDedup::ByLog.apply(swaps)), vec![10, 20]);
+    }
+
+    #[test]
+    fn swaps_without_log_identity_are_kept() {
+        let mut swap = logged(1, 0, POOL, 10);
+        swap.key = None;
+
+        let deduped = SwapDedup::ByLog.apply(vec![swap.clone(), swap]);
+        assert_eq!(deduped.len(), 2);
+    }
+
+    #[test]
+    fn aggregator_wins_drops_routing_legs() {
+        let swaps = vec![
+            logged(1, 0, POOL, 10),
+            logged(1, 1, AGGREGATOR, 11),
+            logged(2, 0, POOL, 20),
+        ];
+        let policy = SwapDedup::AggregatorWins(HashSet::from([AGGREGATOR]));
+
+        // Transaction 1's pool leg is dropped; transaction 2 had no aggregator
+        assert_eq!(amounts(&policy.apply(swaps)), vec![11, 20]);
+    }
+}
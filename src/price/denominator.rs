@@ -0,0 +1,246 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Choosing the quote token of extracted swaps
+//!
+//! [`PriceCalculator`](crate::PriceCalculator) prices tokens against a single
+//! stablecoin, which callers don't always know in advance: a pool or router
+//! may quote in USDC on one chain and USDT on another. [`Denominator`] either
+//! fixes the quote token or detects it from the swaps themselves, using a
+//! [`StablecoinRegistry`].
+
+use std::collections::{BTreeMap, HashSet};
+
+use alloy_chains::NamedChain;
+use alloy_primitives::Address;
+
+use crate::config::constants::{canonical_tokens, stablecoins};
+use crate::price::SwapData;
+
+/// Set of tokens treated as stablecoins when detecting the quote side
+///
+/// The default registry holds [`stablecoins::ALL`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StablecoinRegistry {
+    tokens: HashSet<Address>,
+}
+
+impl Default for StablecoinRegistry {
+    fn default() -> Self {
+        Self {
+            tokens: stablecoins::ALL.iter().copied().collect(),
+        }
+    }
+}
+
+impl StablecoinRegistry {
+    /// Create a registry with no tokens
+    ///
+    /// Use [`StablecoinRegistry::default`] for one holding [`stablecoins::ALL`].
+    pub fn empty() -> Self {
+        Self {
+            tokens: HashSet::new(),
+        }
+    }
+
+    /// Registry of the canonical USDC, USDT and DAI on `chain`
+    ///
+    /// See [`canonical_tokens`].
+    pub fn for_chain(chain: NamedChain) -> Self {
+        let canonical = canonical_tokens(chain);
+        Self {
+            tokens: [canonical.usdc, canonical.usdt, canonical.dai]
+                .into_iter()
+                .flatten()
+                .collect(),
+        }
+    }
+
+    /// Add `token` to the registry
+    pub fn with_token(mut self, token: Address) -> Self {
+        self.tokens.insert(token);
+        self
+    }
+
+    /// Remove `token` from the registry
+    pub fn without_token(mut self, token: Address) -> Self {
+        self.tokens.remove(&token);
+        self
+    }
+
+    /// Whether `token` is a registered stablecoin
+    pub fn contains(&self, token: &Address) -> bool {
+        self.tokens.contains(token)
+    }
+}
+
+/// Which side of a swap holds the quote token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteSide {
+    /// The quote token was sold (`token_in`)
+    In,
+    /// The quote token was bought (`token_out`)
+    Out,
+}
+
+/// How the quote token of a set of swaps is chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Denominator {
+    /// Use whichever side of each swap is a registered stablecoin
+    ///
+    /// Swaps with a stablecoin on both sides, or on neither, have no quote
+    /// side.
+    Auto(StablecoinRegistry),
+    /// Always quote in this token
+    Explicit(Address),
+}
+
+impl Default for Denominator {
+    fn default() -> Self {
+        Self::Auto(StablecoinRegistry::default())
+    }
+}
+
+impl Denominator {
+    /// The side of `swap` holding the quote token, if exactly one side does
+    pub fn quote_side(&self, swap: &SwapData) -> Option<QuoteSide> {
+        let is_quote = |token: &Address| match self {
+            Denominator::Auto(registry) => registry.contains(token),
+            Denominator::Explicit(quote) => token == quote,
+        };
+        match (is_quote(&swap.token_in), is_quote(&swap.token_out)) {
+            (true, false) => Some(QuoteSide::In),
+            (false, true) => Some(QuoteSide::Out),
+            _ => None,
+        }
+    }
+
+    /// The quote token of `swap`, if it has a quote side
+    pub fn quote_token(&self, swap: &SwapData) -> Option<Address> {
+        self.quote_side(swap).map(|side| match side {
+            QuoteSide::In => swap.token_in,
+            QuoteSide::Out => swap.token_out,
+        })
+    }
+
+    /// The token to price `swaps` against
+    ///
+    /// An explicit denominator is returned as is. Otherwise this is the
+    /// stablecoin that quotes the most swaps, with ties going to the lowest
+    /// address so the choice is deterministic. Returns `None` when no swap
+    /// has a quote side.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use semioscan::{Denominator, PriceCalculator};
+    ///
+    /// let swaps = calculator.extract_raw_swaps(start, end).await?;
+    /// let quote = Denominator::default()
+    ///     .detect(swaps.iter().map(|raw| &raw.swap))
+    ///     .ok_or("no stablecoin swaps in range")?;
+    /// let calculator = PriceCalculator::new(provider, chain, quote, price_source);
+    /// ```
+    pub fn detect<'a>(&self, swaps: impl IntoIterator<Item = &'a SwapData>) -> Option<Address> {
+        if let Denominator::Explicit(quote) = self {
+            return Some(*quote);
+        }
+        let mut counts = BTreeMap::<Address, usize>::new();
+        for token in swaps.into_iter().filter_map(|swap| self.quote_token(swap)) {
+            *counts.entry(token).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+            .map(|(token, _)| token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+
+    const TOKEN: Address = address!("00000000000000000000000000000000000000aa");
+
+    fn swap(token_in: Address, token_out: Address) -> SwapData {
+        SwapData {
+            token_in,
+            token_in_amount: U256::from(1),
+            token_out,
+            token_out_amount: U256::from(1),
+            sender: None,
+            tx_hash: None,
+            block_number: None,
+        }
+    }
+
+    #[test]
+    fn test_auto_finds_the_stablecoin_side() {
+        let denominator = Denominator::default();
+
+        assert_eq!(
+            denominator.quote_side(&swap(TOKEN, stablecoins::ETH_USDC)),
+            Some(QuoteSide::Out)
+        );
+        assert_eq!(
+            denominator.quote_side(&swap(stablecoins::ETH_USDT, TOKEN)),
+            Some(QuoteSide::In)
+        );
+        // Stablecoin-to-stablecoin and unrelated swaps are ambiguous
+        assert_eq!(
+            denominator.quote_side(&swap(stablecoins::ETH_USDC, stablecoins::ETH_USDT)),
+            None
+        );
+        assert_eq!(denominator.quote_side(&swap(TOKEN, Address::ZERO)), None);
+    }
+
+    #[test]
+    fn test_detect_picks_the_most_common_quote_token() {
+        let swaps = [
+            swap(TOKEN, stablecoins::ETH_USDT),
+            swap(stablecoins::ETH_USDC, TOKEN),
+            swap(TOKEN, stablecoins::ETH_USDT),
+        ];
+
+        assert_eq!(
+            Denominator::default().detect(&swaps),
+            Some(stablecoins::ETH_USDT)
+        );
+        assert_eq!(Denominator::default().detect(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_explicit_and_custom_registries_override_detection() {
+        let swaps = [swap(TOKEN, stablecoins::ETH_USDT)];
+
+        let explicit = Denominator::Explicit(stablecoins::ETH_USDC);
+        assert_eq!(explicit.detect(&swaps), Some(stablecoins::ETH_USDC));
+        assert_eq!(explicit.quote_side(&swaps[0]), None);
+
+        let registry = StablecoinRegistry::default()
+            .without_token(stablecoins::ETH_USDT)
+            .with_token(TOKEN);
+        assert_eq!(Denominator::Auto(registry).detect(&swaps), Some(TOKEN));
+    }
+
+    #[test]
+    fn test_chain_registry_holds_canonical_stablecoins() {
+        let registry = StablecoinRegistry::for_chain(NamedChain::Base);
+        assert!(registry.contains(&stablecoins::BASE_USDC));
+        assert!(!registry.contains(&stablecoins::ETH_USDC));
+    }
+
+    #[test]
+    fn test_empty_registry_holds_no_stablecoins() {
+        let swaps = [swap(TOKEN, stablecoins::ETH_USDT)];
+
+        assert!(!StablecoinRegistry::empty().contains(&stablecoins::ETH_USDT));
+        assert!(StablecoinRegistry::default().contains(&stablecoins::ETH_USDT));
+        assert_eq!(
+            Denominator::Auto(StablecoinRegistry::empty()).detect(&swaps),
+            None
+        );
+    }
+}
@@ -0,0 +1,252 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exchange rates from USD to other fiat currencies
+//!
+//! Values are computed in USD; [`FxRate`] sources supply the daily rates that
+//! convert them for reports in other currencies. [`StaticFxRates`] holds a
+//! fixed table, for tests and for rates pinned by finance. With the `fx-http`
+//! feature, [`HttpFxRates`] fetches ECB reference rates from a
+//! Frankfurter-compatible API and caches them per day.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::price::fx::StaticFxRates;
+//! use semioscan::Currency;
+//!
+//! let rates = StaticFxRates::new()
+//!     .with_rate(Currency::EUR, 0.92)
+//!     .with_rate_on(Currency::EUR, date, 0.91);
+//!
+//! let eur = usd_value.convert(Currency::EUR, date, &rates).await?;
+//! let report = combined_usd.to_currency(Currency::EUR, &rates).await?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::errors::FxError;
+use crate::types::tokens::Currency;
+
+/// A source of daily exchange rates from USD
+///
+/// A rate is the number of units of the currency one US dollar bought on the
+/// day.
+#[async_trait]
+pub trait FxRate: Send + Sync {
+    /// Units of `currency` per US dollar on `date`
+    async fn usd_rate(&self, currency: Currency, date: NaiveDate) -> Result<f64, FxError>;
+}
+
+/// Reject rates that can't convert a value
+fn checked_rate(currency: Currency, rate: f64) -> Result<f64, FxError> {
+    if rate.is_finite() && rate > 0.0 {
+        Ok(rate)
+    } else {
+        Err(FxError::InvalidRate { currency, rate })
+    }
+}
+
+/// A fixed table of exchange rates
+///
+/// A dated rate applies from its day until the next dated rate, so a table of
+/// business-day rates also covers weekends. Days before the first dated rate
+/// use the currency's undated rate, if any. USD is always 1.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFxRates {
+    undated: BTreeMap<Currency, f64>,
+    dated: BTreeMap<Currency, BTreeMap<NaiveDate, f64>>,
+}
+
+impl StaticFxRates {
+    /// An empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `rate` for `currency` on days without a dated rate
+    pub fn with_rate(mut self, currency: Currency, rate: f64) -> Self {
+        self.undated.insert(currency, rate);
+        self
+    }
+
+    /// Use `rate` for `currency` from `date` until its next dated rate
+    pub fn with_rate_on(mut self, currency: Currency, date: NaiveDate, rate: f64) -> Self {
+        self.dated.entry(currency).or_default().insert(date, rate);
+        self
+    }
+
+    fn rate(&self, currency: Currency, date: NaiveDate) -> Option<f64> {
+        if currency == Currency::USD {
+            return Some(1.0);
+        }
+        self.dated
+            .get(&currency)
+            .and_then(|rates| rates.range(..=date).next_back())
+            .map(|(_, rate)| *rate)
+            .or_else(|| self.undated.get(&currency).copied())
+    }
+}
+
+#[async_trait]
+impl FxRate for StaticFxRates {
+    async fn usd_rate(&self, currency: Currency, date: NaiveDate) -> Result<f64, FxError> {
+        let rate = self
+            .rate(currency, date)
+            .ok_or(FxError::RateUnavailable { currency, date })?;
+        checked_rate(currency, rate)
+    }
+}
+
+#[cfg(feature = "fx-http")]
+pub use http::HttpFxRates;
+
+#[cfg(feature = "fx-http")]
+mod http {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use serde::Deserialize;
+
+    use super::{checked_rate, FxRate};
+    use crate::errors::FxError;
+    use crate::types::tokens::Currency;
+
+    /// Body of a Frankfurter `/{date}?from=USD&to=XXX` response
+    #[derive(Deserialize)]
+    struct RatesResponse {
+        #[serde(rename = "rates")]
+        rates: HashMap<String, f64>,
+    }
+
+    /// Exchange rates fetched from a Frankfurter-compatible HTTP API
+    ///
+    /// Requests `{base_url}/{date}?from=USD&to={currency}`. The public
+    /// Frankfurter service publishes ECB reference rates, answering for
+    /// weekends and holidays with the previous business day's rate. Each
+    /// currency and day is fetched once; later lookups are served from
+    /// memory.
+    #[derive(Debug)]
+    pub struct HttpFxRates {
+        client: reqwest::Client,
+        base_url: String,
+        timeout: Duration,
+        rates: Mutex<HashMap<(Currency, NaiveDate), f64>>,
+    }
+
+    impl Default for HttpFxRates {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl HttpFxRates {
+        /// The public Frankfurter API
+        pub const DEFAULT_BASE_URL: &'static str = "https://api.frankfurter.app";
+
+        /// How long a request may take before it fails, unless set with
+        /// [`with_timeout`](Self::with_timeout)
+        pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+        /// A source using [`DEFAULT_BASE_URL`](Self::DEFAULT_BASE_URL)
+        pub fn new() -> Self {
+            Self::with_base_url(Self::DEFAULT_BASE_URL)
+        }
+
+        /// A source using a self-hosted or proxied API at `base_url`
+        pub fn with_base_url(base_url: impl Into<String>) -> Self {
+            Self {
+                client: reqwest::Client::new(),
+                base_url: base_url.into().trim_end_matches('/').to_string(),
+                timeout: Self::DEFAULT_TIMEOUT,
+                rates: Mutex::new(HashMap::new()),
+            }
+        }
+
+        /// Fail a request that hasn't completed within `timeout`
+        ///
+        /// Defaults to [`DEFAULT_TIMEOUT`](Self::DEFAULT_TIMEOUT), so a rate
+        /// service that never answers can't stall a report.
+        pub fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = timeout;
+            self
+        }
+
+        /// Currencies and days fetched so far
+        pub fn cached(&self) -> HashSet<(Currency, NaiveDate)> {
+            self.rates.lock().unwrap().keys().copied().collect()
+        }
+    }
+
+    #[async_trait]
+    impl FxRate for HttpFxRates {
+        async fn usd_rate(&self, currency: Currency, date: NaiveDate) -> Result<f64, FxError> {
+            if currency == Currency::USD {
+                return Ok(1.0);
+            }
+            if let Some(rate) = self.rates.lock().unwrap().get(&(currency, date)) {
+                return Ok(*rate);
+            }
+
+            let url = format!("{}/{}", self.base_url, date.format("%Y-%m-%d"));
+            let response: RatesResponse = self
+                .client
+                .get(url)
+                .timeout(self.timeout)
+                .query(&[("from", "USD"), ("to", currency.as_str())])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let rate = response
+                .rates
+                .get(currency.as_str())
+                .copied()
+                .ok_or(FxError::RateUnavailable { currency, date })?;
+            let rate = checked_rate(currency, rate)?;
+
+            self.rates.lock().unwrap().insert((currency, date), rate);
+            Ok(rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_static_rates_carry_forward_and_fall_back_to_undated() {
+        let rates = StaticFxRates::new()
+            .with_rate(Currency::EUR, 0.9)
+            .with_rate_on(Currency::EUR, day(3), 0.95)
+            .with_rate_on(Currency::EUR, day(7), 0.96)
+            .with_rate(Currency::GBP, -1.0);
+
+        assert_eq!(rates.usd_rate(Currency::EUR, day(1)).await.unwrap(), 0.9);
+        assert_eq!(rates.usd_rate(Currency::EUR, day(3)).await.unwrap(), 0.95);
+        // The weekend keeps Friday's rate
+        assert_eq!(rates.usd_rate(Currency::EUR, day(9)).await.unwrap(), 0.96);
+        assert_eq!(rates.usd_rate(Currency::USD, day(1)).await.unwrap(), 1.0);
+        assert!(matches!(
+            rates.usd_rate(Currency::JPY, day(1)).await,
+            Err(FxError::RateUnavailable { .. })
+        ));
+        assert!(matches!(
+            rates.usd_rate(Currency::GBP, day(1)).await,
+            Err(FxError::InvalidRate { .. })
+        ));
+    }
+}
@@ -76,39 +76,59 @@
 //! ```
 //!
 //! See [`examples/custom_dex_integration.rs`](https://github.com/semiotic-ai/semioscan/blob/main/examples/custom_dex_integration.rs)
-//! for a complete reference implementation.
+//! for a complete reference implementation, including the builder to copy for
+//! configuring a source: per-chain contract resolution with an explicit
+//! override, a sender allowlist and minimum swap amounts.
 
 use alloy_primitives::{Address, BlockNumber, B256, U256};
 use alloy_rpc_types::Log;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 pub use crate::types::price::PriceSourceError;
 
 pub mod cache;
 pub mod calculator;
+mod dedup;
+mod denominator;
+pub mod fx;
 
-pub use calculator::{PriceCalculator, RawSwapResult, TokenPriceResult};
+pub use calculator::{
+    LiquidityThresholds, PriceCalculator, PriceConfidence, PriceWarning, RawSwapResult,
+    TokenPriceResult,
+};
+pub use dedup::SwapDedup;
+pub use denominator::{Denominator, QuoteSide, StablecoinRegistry};
+#[cfg(feature = "fx-http")]
+pub use fx::HttpFxRates;
+pub use fx::{FxRate, StaticFxRates};
 
 /// Represents a single token swap extracted from on-chain events
 ///
 /// This is the core data structure that [`PriceSource`] implementations must produce.
 /// Token amounts are raw U256 values (not normalized) - the [`crate::PriceCalculator`]
 /// handles decimal normalization.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SwapData {
     /// Token that was sold (input token)
+    #[serde(rename = "token_in")]
     pub token_in: Address,
     /// Amount of input token sold (raw U256, not normalized for decimals)
+    #[serde(rename = "token_in_amount")]
     pub token_in_amount: U256,
     /// Token that was bought (output token)
+    #[serde(rename = "token_out")]
     pub token_out: Address,
     /// Amount of output token received (raw U256, not normalized for decimals)
+    #[serde(rename = "token_out_amount")]
     pub token_out_amount: U256,
     /// Optional: transaction initiator (useful for filtering specific addresses)
+    #[serde(rename = "sender")]
     pub sender: Option<Address>,
     /// Optional: transaction hash (populated when extracting from logs)
+    #[serde(rename = "tx_hash")]
     pub tx_hash: Option<B256>,
     /// Optional: block number (populated when extracting from logs)
+    #[serde(rename = "block_number")]
     pub block_number: Option<BlockNumber>,
 }
 
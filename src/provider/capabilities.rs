@@ -0,0 +1,291 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Discovery of optional RPC methods an endpoint supports
+//!
+//! Providers differ in what they serve beyond the core `eth_*` methods: some
+//! have `eth_getBlockReceipts` or the `trace_*` namespace, and `eth_feeHistory`
+//! depth limits range from a handful of blocks to 1024. [`ProviderCapabilities::probe`]
+//! finds out with a few cheap requests against the latest block, and
+//! [`CapabilityCache`] makes sure that happens once per endpoint.
+//!
+//! Calculators given capabilities with `with_capabilities` use them to pick the
+//! cheapest strategy available, such as reading a chunk's base fees from one
+//! `eth_feeHistory` request instead of one header per block. Calculators given
+//! a cache with `with_capability_probe` probe the endpoint on their first scan
+//! instead; [`SemioscanClient`](crate::SemioscanClient) wires this up for every
+//! chain. Without either, calculators assume only the core methods.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::provider::CapabilityCache;
+//! use semioscan::GasCostCalculator;
+//!
+//! // Probed once, on the first calculation
+//! let calculator = GasCostCalculator::new(provider)
+//!     .with_capability_probe(CapabilityCache::new(), "https://mainnet.example");
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+use tracing::{debug, info};
+
+/// `eth_feeHistory` block counts tried while probing, largest first
+const FEE_HISTORY_PROBES: [u64; 4] = [1024, 256, 64, 1];
+
+/// Optional RPC methods and limits supported by one endpoint
+///
+/// Any probe that fails, for whatever reason, records the feature as
+/// unsupported, so a transient error only costs speed, never correctness.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderCapabilities {
+    /// `eth_getBlockReceipts` is available
+    #[serde(rename = "block_receipts")]
+    pub block_receipts: bool,
+    /// The `trace_*` namespace (tested with `trace_block`) is available
+    #[serde(rename = "trace")]
+    pub trace: bool,
+    /// Most blocks one `eth_feeHistory` request may cover, or `None` if the
+    /// method is unavailable
+    #[serde(rename = "fee_history_max_blocks")]
+    pub fee_history_max_blocks: Option<u64>,
+}
+
+impl ProviderCapabilities {
+    /// Probe `provider` for optional methods
+    ///
+    /// Makes up to six requests, all against the latest block.
+    pub async fn probe<N: Network, P: Provider<N>>(provider: &P) -> Self {
+        let block_receipts = provider
+            .get_block_receipts(BlockId::latest())
+            .await
+            .inspect_err(|error| debug!(%error, "eth_getBlockReceipts unsupported"))
+            .is_ok();
+
+        let trace = provider
+            .raw_request::<_, serde_json::Value>(
+                Cow::Borrowed("trace_block"),
+                (BlockNumberOrTag::Latest,),
+            )
+            .await
+            .inspect_err(|error| debug!(%error, "trace_block unsupported"))
+            .is_ok();
+
+        let mut fee_history_max_blocks = None;
+        for block_count in FEE_HISTORY_PROBES {
+            match provider
+                .get_fee_history(block_count, BlockNumberOrTag::Latest, &[])
+                .await
+            {
+                // Providers may silently return fewer blocks than requested
+                Ok(history) => {
+                    let returned = history.base_fee_per_gas.len().saturating_sub(1) as u64;
+                    fee_history_max_blocks = Some(returned.clamp(1, block_count));
+                    break;
+                }
+                Err(error) => debug!(block_count, %error, "eth_feeHistory request rejected"),
+            }
+        }
+
+        let capabilities = Self {
+            block_receipts,
+            trace,
+            fee_history_max_blocks,
+        };
+        info!(?capabilities, "Probed provider capabilities");
+        capabilities
+    }
+
+    /// Whether `eth_feeHistory` can cover `block_count` blocks in one request
+    #[must_use]
+    pub fn fee_history_covers(&self, block_count: u64) -> bool {
+        self.fee_history_max_blocks
+            .is_some_and(|max_blocks| block_count <= max_blocks)
+    }
+}
+
+/// Probed capabilities per endpoint, shared between calculators
+///
+/// Cloning shares the underlying map.
+#[derive(Debug, Clone, Default)]
+pub struct CapabilityCache {
+    endpoints: Arc<Mutex<HashMap<String, Arc<OnceCell<ProviderCapabilities>>>>>,
+}
+
+impl CapabilityCache {
+    /// An empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capabilities of `endpoint`, probing `provider` the first time it's seen
+    ///
+    /// Concurrent callers for the same endpoint wait for one probe rather than
+    /// each running their own. The cache itself is only locked to look up the
+    /// endpoint, so probes of different endpoints run concurrently and reads
+    /// of other endpoints never wait for a probe.
+    pub async fn get_or_probe<N: Network, P: Provider<N>>(
+        &self,
+        endpoint: &str,
+        provider: &P,
+    ) -> ProviderCapabilities {
+        let cell = self.cell(endpoint);
+        *cell
+            .get_or_init(|| ProviderCapabilities::probe(provider))
+            .await
+    }
+
+    /// Previously probed capabilities of `endpoint`
+    pub async fn get(&self, endpoint: &str) -> Option<ProviderCapabilities> {
+        self.lock()
+            .get(endpoint)
+            .and_then(|cell| cell.get().copied())
+    }
+
+    /// Record capabilities for `endpoint` without probing, e.g. from configuration
+    pub async fn insert(&self, endpoint: impl Into<String>, capabilities: ProviderCapabilities) {
+        self.lock()
+            .insert(endpoint.into(), Arc::new(OnceCell::from(capabilities)));
+    }
+
+    /// The (possibly still empty) entry of `endpoint`
+    fn cell(&self, endpoint: &str) -> Arc<OnceCell<ProviderCapabilities>> {
+        self.lock().entry(endpoint.to_string()).or_default().clone()
+    }
+
+    fn lock(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OnceCell<ProviderCapabilities>>>> {
+        // The map is only ever touched by infallible operations, so a poisoned
+        // lock still holds consistent data
+        self.endpoints
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Where a calculator gets the capabilities of its provider from
+#[derive(Debug, Clone, Default)]
+pub(crate) enum CapabilitySource {
+    /// Only the core methods are assumed
+    #[default]
+    Unknown,
+    /// Capabilities given with `with_capabilities`
+    Known(ProviderCapabilities),
+    /// Probed through `cache` on first use, as configured with
+    /// `with_capability_probe`
+    Probe {
+        cache: CapabilityCache,
+        endpoint: String,
+    },
+}
+
+impl CapabilitySource {
+    /// The provider's capabilities, probing it if this is the first request
+    /// for a [`Probe`](Self::Probe) source
+    pub(crate) async fn resolve<N: Network, P: Provider<N>>(
+        &self,
+        provider: &P,
+    ) -> Option<ProviderCapabilities> {
+        match self {
+            Self::Unknown => None,
+            Self::Known(capabilities) => Some(*capabilities),
+            Self::Probe { cache, endpoint } => Some(cache.get_or_probe(endpoint, provider).await),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_network::Ethereum;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_transport::mock::Asserter;
+
+    #[test]
+    fn test_fee_history_covers_respects_probed_limit() {
+        let capabilities = ProviderCapabilities {
+            fee_history_max_blocks: Some(128),
+            ..ProviderCapabilities::default()
+        };
+        assert!(capabilities.fee_history_covers(128));
+        assert!(!capabilities.fee_history_covers(129));
+        assert!(!ProviderCapabilities::default().fee_history_covers(1));
+    }
+
+    #[tokio::test]
+    async fn test_probe_records_failures_as_unsupported_and_caches_per_endpoint() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+
+        asserter.push_failure_msg("method not found");
+        asserter.push_success(&serde_json::json!([]));
+        asserter.push_failure_msg("block count too large");
+        asserter.push_success(&serde_json::json!({
+            "oldestBlock": "0x64",
+            "baseFeePerGas": vec!["0x1"; 201],
+            "gasUsedRatio": vec![0.5; 200],
+        }));
+
+        let cache = CapabilityCache::new();
+        let capabilities = cache.get_or_probe("https://rpc.example", &provider).await;
+        assert_eq!(
+            capabilities,
+            ProviderCapabilities {
+                block_receipts: false,
+                trace: true,
+                fee_history_max_blocks: Some(200),
+            }
+        );
+
+        // Served from the cache: probing again would hit the empty mock queue
+        // and record everything as unsupported
+        assert_eq!(
+            cache.get_or_probe("https://rpc.example", &provider).await,
+            capabilities
+        );
+        assert_eq!(cache.get("https://rpc.example").await, Some(capabilities));
+    }
+
+    #[tokio::test]
+    async fn test_probe_source_probes_once_on_first_use() {
+        let asserter = Asserter::new();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&serde_json::json!([]));
+        asserter.push_failure_msg("method not found");
+        asserter.push_failure_msg("method not found");
+        asserter.push_failure_msg("method not found");
+        asserter.push_failure_msg("method not found");
+        asserter.push_failure_msg("method not found");
+
+        let cache = CapabilityCache::new();
+        let source = CapabilitySource::Probe {
+            cache: cache.clone(),
+            endpoint: "https://rpc.example".to_string(),
+        };
+        assert_eq!(cache.get("https://rpc.example").await, None);
+
+        let expected = ProviderCapabilities {
+            block_receipts: true,
+            trace: false,
+            fee_history_max_blocks: None,
+        };
+        assert_eq!(source.resolve(&provider).await, Some(expected));
+        // The mock queue is empty now, so a second probe would differ
+        assert_eq!(source.resolve(&provider).await, Some(expected));
+        assert_eq!(cache.get("https://rpc.example").await, Some(expected));
+        assert_eq!(CapabilitySource::Unknown.resolve(&provider).await, None);
+    }
+}
@@ -6,6 +6,8 @@
 
 use std::time::Duration;
 
+use crate::transport::KeyUsageTracker;
+
 /// Configuration for creating providers
 ///
 /// # Example
@@ -17,6 +19,25 @@ use std::time::Duration;
 ///     .with_rate_limit(10);
 /// ```
 ///
+/// # Multiple keys
+///
+/// Additional URLs (typically the same provider with different API keys) are
+/// tried in order when the current one returns HTTP 401/403/429, so a revoked
+/// or exhausted key does not abort a long backfill. Per-key usage is available
+/// from [`ProviderConfig::key_usage`], which shares its counters with every
+/// provider built from this configuration.
+///
+/// ```rust,ignore
+/// use semioscan::provider::{create_http_provider, ProviderConfig};
+///
+/// let config = ProviderConfig::alchemy_keys(&["key-a", "key-b"], "eth-mainnet");
+/// let provider = create_http_provider(config.clone())?;
+/// // ... later
+/// for usage in config.key_usage().snapshot() {
+///     println!("{}: {} requests, {} rate limited", usage.endpoint, usage.requests, usage.rate_limited);
+/// }
+/// ```
+///
 /// Note: RPC request/response logging is handled natively by alloy's transport
 /// layer at DEBUG/TRACE level.
 #[derive(Debug, Clone)]
@@ -29,6 +50,11 @@ pub struct ProviderConfig {
     pub timeout: Option<Duration>,
     /// Minimum delay between requests (alternative to rate limiting)
     pub min_delay: Option<Duration>,
+    /// Further endpoints (e.g. the same provider with other API keys) to rotate
+    /// to when `url` is rejected
+    pub additional_urls: Vec<String>,
+    /// Per-endpoint usage counters, shared with providers built from this config
+    pub key_usage: KeyUsageTracker,
 }
 
 impl ProviderConfig {
@@ -40,9 +66,50 @@ impl ProviderConfig {
             rate_limit_per_second: None,
             timeout: None,
             min_delay: None,
+            additional_urls: Vec::new(),
+            key_usage: KeyUsageTracker::new(),
         }
     }
 
+    /// Add an endpoint to rotate to when earlier ones are rejected
+    ///
+    /// Rotation happens on HTTP 401/403 (invalid or expired key) and 429
+    /// (quota exhausted). Rate limiting, if configured, applies to the
+    /// combined traffic across all endpoints.
+    #[must_use]
+    pub fn with_additional_url(mut self, url: impl Into<String>) -> Self {
+        self.additional_urls.push(url.into());
+        self
+    }
+
+    /// Add several endpoints to rotate to, in order
+    #[must_use]
+    pub fn with_additional_urls(
+        mut self,
+        urls: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.additional_urls
+            .extend(urls.into_iter().map(Into::into));
+        self
+    }
+
+    /// All configured endpoints, primary first
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.url.as_str()).chain(self.additional_urls.iter().map(String::as_str))
+    }
+
+    /// Check if this configuration rotates across more than one endpoint
+    #[must_use]
+    pub fn has_multiple_endpoints(&self) -> bool {
+        !self.additional_urls.is_empty()
+    }
+
+    /// Per-endpoint usage counters for providers built from this configuration
+    #[must_use]
+    pub fn key_usage(&self) -> &KeyUsageTracker {
+        &self.key_usage
+    }
+
     /// Set rate limiting (requests per second)
     ///
     /// When set, the provider will automatically throttle requests to stay
@@ -125,6 +192,22 @@ impl ProviderConfig {
     pub fn alchemy(api_key: &str, network: &str) -> Self {
         Self::private_endpoint(format!("https://{network}.g.alchemy.com/v2/{api_key}"))
     }
+
+    /// Configuration preset for Alchemy with several API keys, rotated on rejection
+    ///
+    /// # Panics
+    ///
+    /// Panics if `api_keys` is empty.
+    #[must_use]
+    pub fn alchemy_keys(api_keys: &[&str], network: &str) -> Self {
+        let (first, rest) = api_keys
+            .split_first()
+            .expect("alchemy_keys requires at least one API key");
+        Self::alchemy(first, network).with_additional_urls(
+            rest.iter()
+                .map(|key| format!("https://{network}.g.alchemy.com/v2/{key}")),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -179,4 +262,31 @@ mod tests {
         assert!(config.url.contains("alchemy.com"));
         assert!(config.url.contains("my-api-key"));
     }
+
+    #[test]
+    fn test_provider_config_additional_urls() {
+        let config = ProviderConfig::new("https://a.example.com")
+            .with_additional_url("https://b.example.com")
+            .with_additional_urls(["https://c.example.com"]);
+
+        assert!(config.has_multiple_endpoints());
+        assert_eq!(
+            config.urls().collect::<Vec<_>>(),
+            vec![
+                "https://a.example.com",
+                "https://b.example.com",
+                "https://c.example.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provider_config_alchemy_keys() {
+        let config = ProviderConfig::alchemy_keys(&["key-a", "key-b"], "eth-mainnet");
+        let urls: Vec<_> = config.urls().collect();
+        assert_eq!(urls.len(), 2);
+        assert!(urls[0].ends_with("/key-a"));
+        assert!(urls[1].ends_with("/key-b"));
+        assert_eq!(config.rate_limit_per_second, Some(50));
+    }
 }
@@ -6,10 +6,11 @@
 
 use alloy_network::AnyNetwork;
 use alloy_provider::RootProvider;
-use alloy_rpc_client::ClientBuilder;
+use alloy_rpc_client::{ClientBuilder, RpcClient};
+use alloy_transport_http::Http;
 
 use crate::errors::RpcError;
-use crate::transport::RateLimitLayer;
+use crate::transport::{RateLimitLayer, RotatingTransport};
 
 use super::config::ProviderConfig;
 use super::AnyHttpProvider;
@@ -23,6 +24,7 @@ use super::AnyHttpProvider;
 ///
 /// - Rate limiting: Automatically throttles requests
 /// - Timeout: Sets request timeout
+/// - Additional URLs: Rotates to the next endpoint on HTTP 401/403/429
 ///
 /// Note: RPC request/response logging is handled natively by alloy's transport
 /// layer at DEBUG/TRACE level.
@@ -54,6 +56,10 @@ use super::AnyHttpProvider;
 /// - The URL is malformed
 /// - The URL cannot be parsed
 pub fn create_http_provider(config: ProviderConfig) -> Result<AnyHttpProvider, RpcError> {
+    if config.has_multiple_endpoints() {
+        return Ok(RootProvider::<AnyNetwork>::new(rotating_client(&config)?));
+    }
+
     let url: url::Url = config
         .url
         .parse()
@@ -176,22 +182,57 @@ pub fn create_typed_http_provider<N>(
 where
     N: alloy_network::Network,
 {
+    Ok(RootProvider::<N>::new(http_client(&config)?))
+}
+
+/// Build the RPC client behind [`create_typed_http_provider`]
+///
+/// Split out so one client (and its rate limiter) can back providers for
+/// several network types.
+pub(crate) fn http_client(config: &ProviderConfig) -> Result<RpcClient, RpcError> {
+    if config.has_multiple_endpoints() {
+        return rotating_client(config);
+    }
+
     let url: url::Url = config
         .url
         .parse()
         .map_err(|e| RpcError::ProviderUrlInvalid(format!("{e}")))?;
 
     match config.rate_limit_per_second {
-        Some(rps) => {
-            let client = ClientBuilder::default()
-                .layer(RateLimitLayer::per_second(rps))
-                .http(url);
+        Some(rps) => Ok(ClientBuilder::default()
+            .layer(RateLimitLayer::per_second(rps))
+            .http(url)),
+        None => Ok(RpcClient::new_http(url)),
+    }
+}
 
-            Ok(RootProvider::<N>::new(client))
-        }
+/// Build an RPC client that rotates across all of `config`'s endpoints
+///
+/// Rate limiting wraps the rotating transport, so the configured limit applies
+/// to the combined traffic across endpoints.
+fn rotating_client(config: &ProviderConfig) -> Result<RpcClient, RpcError> {
+    let endpoints = config
+        .urls()
+        .map(|url| {
+            let url: url::Url = url
+                .parse()
+                .map_err(|e| RpcError::ProviderUrlInvalid(format!("{e}")))?;
+            Ok((url.clone(), Http::new(url)))
+        })
+        .collect::<Result<Vec<_>, RpcError>>()?;
+    let transport = RotatingTransport::new(endpoints, config.key_usage.clone());
 
-        None => Ok(RootProvider::<N>::new_http(url)),
-    }
+    let client = match (config.rate_limit_per_second, config.min_delay) {
+        (Some(rps), _) => ClientBuilder::default()
+            .layer(RateLimitLayer::per_second(rps))
+            .transport(transport, false),
+        (None, Some(delay)) => ClientBuilder::default()
+            .layer(RateLimitLayer::with_min_delay(delay))
+            .transport(transport, false),
+        (None, None) => ClientBuilder::default().transport(transport, false),
+    };
+    Ok(client)
 }
 
 /// Quick helper to create a simple HTTP provider without configuration
@@ -263,4 +304,23 @@ mod tests {
             create_typed_http_provider::<Ethereum>(ProviderConfig::new("http://localhost:8545"));
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_create_http_provider_with_additional_urls_registers_usage() {
+        let config = ProviderConfig::new("http://localhost:8545")
+            .with_additional_url("http://localhost:8546")
+            .with_rate_limit(10);
+
+        assert!(create_http_provider(config.clone()).is_ok());
+
+        let usage = config.key_usage().snapshot();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[1].endpoint, "http://localhost#1");
+    }
+
+    #[test]
+    fn test_create_http_provider_rejects_invalid_additional_url() {
+        let config = ProviderConfig::new("http://localhost:8545").with_additional_url("not-a-url");
+        assert!(create_http_provider(config).is_err());
+    }
 }
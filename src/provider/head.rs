@@ -0,0 +1,316 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shared view of a provider's chain head
+//!
+//! A [`HeadTracker`] fetches the latest block once per tick, or follows a
+//! header subscription, and publishes it as a [`ChainHead`] on a watch
+//! channel. Everything that needs the head subscribes to that channel
+//! instead of asking the provider itself: a
+//! [`ReorgDetector`](crate::ReorgDetector) follows it with
+//! [`follow_heads`](crate::ReorgDetector::follow_heads), and a
+//! [`FinalityTracker`](crate::FinalityTracker) given one through
+//! [`with_head`](crate::FinalityTracker::with_head) reads it for its
+//! confirmation-depth fallback.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use semioscan::{FinalityTracker, HeadTracker, ReorgDetector};
+//!
+//! let heads = HeadTracker::new(provider.clone())
+//!     .with_poll_interval(Duration::from_secs(2))
+//!     .spawn();
+//!
+//! let finality = FinalityTracker::new().with_head(heads.subscribe());
+//! let mut detector = ReorgDetector::new(provider);
+//! let head_updates = heads.subscribe();
+//! tokio::spawn(async move { detector.follow_heads(head_updates).await });
+//! ```
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy_consensus::BlockHeader;
+use alloy_network::{BlockResponse, Ethereum, HeaderResponse, Network};
+use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+use alloy_transport::TransportErrorKind;
+use futures::{Stream, StreamExt};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, warn};
+
+use crate::blocks::window::UnixTimestamp;
+use crate::errors::RpcError;
+
+/// The latest block of a chain, as last seen by a [`HeadTracker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Block number of the head
+    pub number: BlockNumber,
+    /// Hash of the head block
+    pub hash: BlockHash,
+    /// Hash of the block the head builds on, used to detect reorgs
+    pub parent_hash: BlockHash,
+    /// When the head block was produced
+    pub timestamp: UnixTimestamp,
+}
+
+impl ChainHead {
+    /// The head described by `header`
+    pub fn of<H: BlockHeader + HeaderResponse>(header: &H) -> Self {
+        Self {
+            number: header.number(),
+            hash: header.hash(),
+            parent_hash: header.parent_hash(),
+            timestamp: UnixTimestamp::from_u64(header.timestamp()),
+        }
+    }
+
+    /// Whether `self` should replace `current` as the published head
+    ///
+    /// A lower block, as a lagging load-balanced node may return, is ignored;
+    /// a different block at the same height is a reorg and replaces it.
+    fn supersedes(&self, current: Option<&ChainHead>) -> bool {
+        match current {
+            None => true,
+            Some(current) => {
+                self.number > current.number
+                    || (self.number == current.number && self.hash != current.hash)
+            }
+        }
+    }
+}
+
+/// Fetches a provider's chain head and fans it out to subscribers
+pub struct HeadTracker<P, N = Ethereum> {
+    provider: P,
+    sender: Arc<watch::Sender<Option<ChainHead>>>,
+    poll_interval: Duration,
+    _network: PhantomData<fn() -> N>,
+}
+
+impl<P, N> HeadTracker<P, N> {
+    /// Polling interval used by [`spawn`](Self::spawn) unless set with
+    /// [`with_poll_interval`](Self::with_poll_interval)
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(4);
+
+    /// Track the head of `provider`'s chain
+    pub fn new(provider: P) -> Self {
+        let (sender, _) = watch::channel(None);
+        Self {
+            provider,
+            sender: Arc::new(sender),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            _network: PhantomData,
+        }
+    }
+
+    /// Poll every `interval` once [`spawn`](Self::spawn)ed
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "head poll interval must be non-zero");
+        self.poll_interval = interval;
+        self
+    }
+
+    /// A receiver seeing every head published from now on
+    ///
+    /// It holds `None` until the first head is fetched.
+    pub fn subscribe(&self) -> watch::Receiver<Option<ChainHead>> {
+        self.sender.subscribe()
+    }
+
+    /// The most recently published head, if any
+    pub fn latest(&self) -> Option<ChainHead> {
+        *self.sender.borrow()
+    }
+
+    /// Publish `head` if it supersedes the current one, returning whether it
+    /// did
+    pub fn publish(&self, head: ChainHead) -> bool {
+        publish(&self.sender, head)
+    }
+}
+
+impl<P: Provider<N>, N: Network> HeadTracker<P, N> {
+    /// Fetch the latest block and publish it
+    ///
+    /// Returns the published head, which is the previous one if the fetched
+    /// block is behind it.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the latest block can't be fetched.
+    pub async fn poll(&self) -> Result<ChainHead, RpcError> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await
+            .map_err(RpcError::get_block_number_failed)?
+            .ok_or_else(|| {
+                RpcError::get_block_number_failed(TransportErrorKind::custom_str(
+                    "provider returned no latest block",
+                ))
+            })?;
+        let head = ChainHead::of(block.header());
+        self.publish(head);
+        Ok(self.latest().unwrap_or(head))
+    }
+
+    /// Publish every header of `headers` until it ends
+    ///
+    /// Use with a block subscription, such as `RealtimeEventScanner`'s
+    /// `subscribe_blocks` (`ws` feature), to track the head without polling.
+    pub async fn follow<S>(&self, headers: S)
+    where
+        S: Stream<Item = N::HeaderResponse>,
+    {
+        let mut headers = std::pin::pin!(headers);
+        while let Some(header) = headers.next().await {
+            self.publish(ChainHead::of(&header));
+        }
+    }
+}
+
+impl<P, N> HeadTracker<P, N>
+where
+    P: Provider<N> + Send + Sync + 'static,
+    N: Network,
+{
+    /// [`poll`](Self::poll) every poll interval in the background
+    ///
+    /// The first poll runs immediately. Failed polls are logged and retried
+    /// on the next tick.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a tokio runtime.
+    pub fn spawn(self) -> HeadTrackerHandle {
+        let sender = Arc::clone(&self.sender);
+        let mut ticker = tokio::time::interval(self.poll_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let task = tokio::spawn(async move {
+            loop {
+                ticker.tick().await;
+                match self.poll().await {
+                    Ok(head) => debug!(block_number = head.number, "Polled chain head"),
+                    Err(e) => warn!(error = %e, "Chain head poll failed"),
+                }
+            }
+        });
+
+        HeadTrackerHandle { sender, task }
+    }
+}
+
+/// Handle to a task started by [`HeadTracker::spawn`]
+///
+/// The task stops when the handle is dropped or [`stop`](Self::stop) is
+/// called. Receivers then keep the last head but see no further changes.
+#[derive(Debug)]
+pub struct HeadTrackerHandle {
+    sender: Arc<watch::Sender<Option<ChainHead>>>,
+    task: JoinHandle<()>,
+}
+
+impl HeadTrackerHandle {
+    /// A receiver seeing every head published from now on
+    pub fn subscribe(&self) -> watch::Receiver<Option<ChainHead>> {
+        self.sender.subscribe()
+    }
+
+    /// The most recently published head, if any
+    pub fn latest(&self) -> Option<ChainHead> {
+        *self.sender.borrow()
+    }
+
+    /// Stop polling
+    pub fn stop(self) {
+        // Dropping the handle aborts the task
+    }
+
+    /// Whether the task has stopped
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Drop for HeadTrackerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn publish(sender: &watch::Sender<Option<ChainHead>>, head: ChainHead) -> bool {
+    sender.send_if_modified(|current| {
+        let replace = head.supersedes(current.as_ref());
+        if replace {
+            *current = Some(head);
+        }
+        replace
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_rpc_types::{Block, Header};
+    use alloy_transport::mock::Asserter;
+
+    fn block(number: BlockNumber, fork: u8) -> Block {
+        let mut hash = [fork; 32];
+        hash[..8].copy_from_slice(&number.to_be_bytes());
+        Block {
+            header: Header {
+                hash: B256::from(hash),
+                inner: alloy_consensus::Header {
+                    number,
+                    timestamp: 1_000 + number,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn poll_publishes_new_heads_only() {
+        let asserter = Asserter::new();
+        let tracker: HeadTracker<RootProvider> =
+            HeadTracker::new(ProviderBuilder::default().connect_mocked_client(asserter.clone()));
+        let mut heads = tracker.subscribe();
+        assert_eq!(*heads.borrow(), None);
+
+        asserter.push_success(&Some(block(10, 0)));
+        let head = tracker.poll().await.unwrap();
+        assert_eq!(head.number, 10);
+        assert_eq!(head.timestamp, UnixTimestamp(1_010));
+        assert!(heads.has_changed().unwrap());
+        assert_eq!(*heads.borrow_and_update(), Some(head));
+
+        // A lagging node's older block leaves the head where it was
+        asserter.push_success(&Some(block(9, 0)));
+        assert_eq!(tracker.poll().await.unwrap(), head);
+        assert!(!heads.has_changed().unwrap());
+
+        // A competing block at the same height replaces it
+        asserter.push_success(&Some(block(10, 1)));
+        let reorged = tracker.poll().await.unwrap();
+        assert_ne!(reorged.hash, head.hash);
+        assert!(heads.has_changed().unwrap());
+    }
+}
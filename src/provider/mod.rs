@@ -16,6 +16,8 @@
 //! This module provides:
 //! - [`create_http_provider`] - Create an HTTP provider with optional rate limiting
 //! - [`create_ws_provider`] - Create a WebSocket provider for real-time subscriptions (requires `ws` feature)
+//! - [`HeadTracker`] - Fetch the chain head once and fan it out to every consumer
+//! - [`ReorgDetector`] - Detect chain reorganizations and notify caches and subscribers
 //!
 //! # When to Use Dynamic Providers
 //!
@@ -71,18 +73,25 @@
 //! For full network-specific support, use the generic calculators with explicit
 //! `Ethereum` or `Optimism` network types.
 
+pub mod capabilities;
 mod config;
 mod factory;
+mod head;
 mod pool;
+mod reorg;
 
+pub use capabilities::{CapabilityCache, ProviderCapabilities};
 pub use config::ProviderConfig;
 #[cfg(feature = "ws")]
 pub use factory::create_ws_provider;
+pub(crate) use factory::http_client;
 pub use factory::{
     create_http_provider, create_typed_http_provider, rate_limited_http_provider,
     simple_http_provider,
 };
+pub use head::{ChainHead, HeadTracker, HeadTrackerHandle};
 pub use pool::{ChainEndpoint, PooledProvider, ProviderPool, ProviderPoolBuilder};
+pub use reorg::{ReorgDetector, ReorgEvent, ReorgHook};
 
 use alloy_chains::NamedChain;
 use alloy_network::{AnyNetwork, Ethereum};
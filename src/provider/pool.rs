@@ -105,6 +105,8 @@ pub type PooledProvider = Arc<RootProvider<AnyNetwork>>;
 pub struct ProviderPool {
     /// Map of chain to provider
     providers: RwLock<HashMap<NamedChain, PooledProvider>>,
+    /// Map of chain to fallback providers, in the order they were added
+    fallbacks: RwLock<HashMap<NamedChain, Vec<PooledProvider>>>,
     /// Default rate limit for new providers (requests per second)
     default_rate_limit: Option<u32>,
 }
@@ -124,6 +126,7 @@ impl ProviderPool {
     pub fn with_defaults(rate_limit: Option<u32>) -> Self {
         Self {
             providers: RwLock::new(HashMap::new()),
+            fallbacks: RwLock::new(HashMap::new()),
             default_rate_limit: rate_limit,
         }
     }
@@ -194,6 +197,42 @@ impl ProviderPool {
             .and_then(|providers| providers.get(&chain).cloned())
     }
 
+    /// Add a fallback provider for a chain
+    ///
+    /// Fallbacks aren't returned by [`get`](Self::get). Callers that retry a
+    /// failed request elsewhere, such as
+    /// [`BlockWindowCalculator::with_pool_fallbacks`](crate::BlockWindowCalculator::with_pool_fallbacks),
+    /// read them with [`fallbacks`](Self::fallbacks).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid
+    pub fn add_fallback(
+        &self,
+        chain: NamedChain,
+        url: &str,
+        rate_limit: Option<u32>,
+    ) -> Result<(), RpcError> {
+        let provider = create_pooled_provider(url, rate_limit.or(self.default_rate_limit))?;
+
+        let mut fallbacks = self.fallbacks.write().map_err(|_| {
+            RpcError::ProviderConnectionFailed("Provider pool lock poisoned".to_string())
+        })?;
+        fallbacks.entry(chain).or_default().push(Arc::new(provider));
+        info!(chain = ?chain, url = url, "Added fallback provider to pool");
+        Ok(())
+    }
+
+    /// Get the fallback providers for a chain, in the order they were added
+    #[must_use]
+    pub fn fallbacks(&self, chain: NamedChain) -> Vec<PooledProvider> {
+        self.fallbacks
+            .read()
+            .ok()
+            .and_then(|fallbacks| fallbacks.get(&chain).cloned())
+            .unwrap_or_default()
+    }
+
     /// Get a provider for a chain, or add it if not present
     ///
     /// This is useful for lazy initialization of providers.
@@ -219,10 +258,13 @@ impl ProviderPool {
         })
     }
 
-    /// Remove a provider from the pool
+    /// Remove a provider, and its fallbacks, from the pool
     ///
     /// Returns the removed provider if it existed.
     pub fn remove(&self, chain: NamedChain) -> Option<PooledProvider> {
+        if let Ok(mut fallbacks) = self.fallbacks.write() {
+            fallbacks.remove(&chain);
+        }
         self.providers
             .write()
             .ok()
@@ -264,6 +306,9 @@ impl ProviderPool {
 
     /// Clear all providers from the pool
     pub fn clear(&self) {
+        if let Ok(mut fallbacks) = self.fallbacks.write() {
+            fallbacks.clear();
+        }
         if let Ok(mut providers) = self.providers.write() {
             providers.clear();
             info!("Cleared all providers from pool");
@@ -275,6 +320,7 @@ impl ProviderPool {
 #[derive(Default)]
 pub struct ProviderPoolBuilder {
     endpoints: Vec<ChainEndpoint>,
+    fallbacks: Vec<ChainEndpoint>,
     default_rate_limit: Option<u32>,
 }
 
@@ -312,6 +358,17 @@ impl ProviderPoolBuilder {
         self
     }
 
+    /// Add a fallback endpoint for a chain (see [`ProviderPool::add_fallback`])
+    #[must_use]
+    pub fn add_fallback(mut self, chain: NamedChain, url: &str) -> Self {
+        self.fallbacks.push(ChainEndpoint {
+            chain,
+            url: url.to_string(),
+            rate_limit: None,
+        });
+        self
+    }
+
     /// Set the default rate limit for all providers
     #[must_use]
     pub fn with_rate_limit(mut self, requests_per_second: u32) -> Self {
@@ -334,6 +391,13 @@ impl ProviderPoolBuilder {
                 endpoint.rate_limit.or(self.default_rate_limit),
             )?;
         }
+        for endpoint in self.fallbacks {
+            pool.add_fallback(
+                endpoint.chain,
+                &endpoint.url,
+                endpoint.rate_limit.or(self.default_rate_limit),
+            )?;
+        }
 
         Ok(pool)
     }
@@ -521,6 +585,23 @@ mod tests {
         assert!(pool.is_empty());
     }
 
+    #[test]
+    fn test_pool_fallbacks() {
+        let pool = ProviderPoolBuilder::new()
+            .add_chain(NamedChain::Mainnet, "https://eth.llamarpc.com")
+            .add_fallback(NamedChain::Mainnet, "https://ethereum-rpc.publicnode.com")
+            .build()
+            .unwrap();
+
+        // Fallbacks don't count as configured chains
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.fallbacks(NamedChain::Mainnet).len(), 1);
+        assert!(pool.fallbacks(NamedChain::Base).is_empty());
+
+        pool.remove(NamedChain::Mainnet);
+        assert!(pool.fallbacks(NamedChain::Mainnet).is_empty());
+    }
+
     #[test]
     fn test_invalid_url() {
         let pool = ProviderPool::new();
@@ -0,0 +1,582 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chain reorganization detection
+//!
+//! A [`ReorgDetector`] remembers the hashes of the most recent blocks it has
+//! seen. Each new header is linked to them through its parent hash; when the
+//! link breaks, the detector walks the new chain back to the last block both
+//! chains share and publishes a [`ReorgEvent`] naming the orphaned blocks.
+//!
+//! Events go to every [`subscribe`](ReorgDetector::subscribe)r on a
+//! broadcast channel, and to the [`ReorgHook`]s registered on the detector,
+//! which invalidate cached data built from orphaned blocks. [`HeaderCache`]
+//! and a shared [`GasCache`] are hooks out of the box.
+//!
+//! Headers come from a block subscription passed to
+//! [`follow`](ReorgDetector::follow), or from a [`HeadTracker`](crate::HeadTracker)
+//! shared with other consumers through
+//! [`follow_heads`](ReorgDetector::follow_heads).
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use futures::StreamExt;
+//! use semioscan::{GasCache, HeaderCache, RealtimeEventScanner, ReorgDetector};
+//!
+//! let gas_cache = Arc::new(tokio::sync::Mutex::new(GasCache::default()));
+//! let headers = HeaderCache::new();
+//!
+//! let mut detector = ReorgDetector::new(provider.clone())
+//!     .with_hook(gas_cache.clone())
+//!     .with_hook(Arc::new(headers.clone()));
+//! let mut reorgs = detector.subscribe();
+//!
+//! tokio::spawn(async move {
+//!     while let Ok(reorg) = reorgs.recv().await {
+//!         println!("Blocks from {} are no longer canonical", reorg.first_invalid_block());
+//!     }
+//! });
+//!
+//! let scanner = RealtimeEventScanner::new(ws_provider);
+//! detector.follow(scanner.subscribe_blocks().await?).await?;
+//! ```
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_network::{BlockResponse, Ethereum, HeaderResponse, Network};
+use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, warn};
+
+use crate::cache::header::HeaderCache;
+use crate::cache::transaction::TransactionCache;
+use crate::errors::RpcError;
+use crate::events::log_cache::CachedLogSource;
+use crate::gas::cache::GasCache;
+use crate::provider::head::ChainHead;
+
+/// Blocks no longer on the canonical chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgEvent {
+    /// The last block both the old and the new chain contain
+    ///
+    /// For a reorg deeper than the detector remembers, this is the block
+    /// before the oldest orphaned one, with a zero hash as it wasn't checked.
+    pub common_ancestor: BlockNumHash,
+    /// The replaced blocks, oldest first
+    pub orphaned_blocks: Vec<BlockNumHash>,
+}
+
+impl ReorgEvent {
+    /// The first block number whose contents changed
+    pub fn first_invalid_block(&self) -> BlockNumber {
+        self.common_ancestor.number + 1
+    }
+
+    /// Number of blocks replaced
+    pub fn depth(&self) -> usize {
+        self.orphaned_blocks.len()
+    }
+}
+
+/// Reacts to a reorg, typically by dropping data derived from orphaned blocks
+#[async_trait]
+pub trait ReorgHook: Send + Sync {
+    async fn on_reorg(&self, event: &ReorgEvent);
+}
+
+#[async_trait]
+impl ReorgHook for HeaderCache {
+    async fn on_reorg(&self, event: &ReorgEvent) {
+        self.clear_from_block(event.first_invalid_block());
+    }
+}
+
+#[async_trait]
+impl<N: Network> ReorgHook for TransactionCache<N> {
+    async fn on_reorg(&self, event: &ReorgEvent) {
+        self.clear_from_block(event.first_invalid_block());
+    }
+}
+
+#[async_trait]
+impl ReorgHook for CachedLogSource {
+    async fn on_reorg(&self, event: &ReorgEvent) {
+        self.clear_from_block(event.first_invalid_block());
+    }
+}
+
+#[async_trait]
+impl ReorgHook for tokio::sync::Mutex<GasCache> {
+    async fn on_reorg(&self, event: &ReorgEvent) {
+        self.lock()
+            .await
+            .clear_from_block(event.first_invalid_block());
+    }
+}
+
+/// The linking fields of a block header
+#[derive(Debug, Clone, Copy)]
+struct Link {
+    number: BlockNumber,
+    hash: BlockHash,
+    parent_hash: BlockHash,
+}
+
+impl From<&ChainHead> for Link {
+    fn from(head: &ChainHead) -> Self {
+        Self {
+            number: head.number,
+            hash: head.hash,
+            parent_hash: head.parent_hash,
+        }
+    }
+}
+
+impl Link {
+    fn of<H: BlockHeader + HeaderResponse>(header: &H) -> Self {
+        Self {
+            number: header.number(),
+            hash: header.hash(),
+            parent_hash: header.parent_hash(),
+        }
+    }
+}
+
+/// Detects reorgs from a sequence of block headers
+pub struct ReorgDetector<P, N = Ethereum> {
+    provider: P,
+    /// Canonical hashes of the most recent blocks seen
+    recent: BTreeMap<BlockNumber, BlockHash>,
+    depth: usize,
+    sender: broadcast::Sender<ReorgEvent>,
+    hooks: Vec<Arc<dyn ReorgHook>>,
+    _network: PhantomData<fn() -> N>,
+}
+
+impl<P, N> ReorgDetector<P, N> {
+    /// Blocks remembered by [`ReorgDetector::new`]
+    pub const DEFAULT_DEPTH: usize = 128;
+
+    /// Events buffered per subscriber before slow subscribers start lagging
+    const CHANNEL_CAPACITY: usize = 64;
+
+    /// Detect reorgs up to [`DEFAULT_DEPTH`](Self::DEFAULT_DEPTH) blocks deep,
+    /// fetching missing ancestors from `provider`
+    pub fn new(provider: P) -> Self {
+        let (sender, _) = broadcast::channel(Self::CHANNEL_CAPACITY);
+        Self {
+            provider,
+            recent: BTreeMap::new(),
+            depth: Self::DEFAULT_DEPTH,
+            sender,
+            hooks: Vec::new(),
+            _network: PhantomData,
+        }
+    }
+
+    /// Remember the last `depth` blocks (at least one)
+    ///
+    /// A reorg deeper than that is reported as orphaning every remembered
+    /// block.
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Run `hook` on every reorg, before subscribers are notified
+    pub fn with_hook(mut self, hook: Arc<dyn ReorgHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// A receiver for every reorg detected from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<ReorgEvent> {
+        self.sender.subscribe()
+    }
+
+    /// The most recent block seen on the canonical chain
+    pub fn tip(&self) -> Option<BlockNumHash> {
+        self.recent
+            .last_key_value()
+            .map(|(number, hash)| BlockNumHash::new(*number, *hash))
+    }
+}
+
+impl<P: Provider<N>, N: Network> ReorgDetector<P, N> {
+    /// Record `header` as the new chain tip, reporting a reorg if it doesn't
+    /// extend the chain seen so far
+    ///
+    /// Headers may skip blocks or repeat; missing ancestors are fetched from
+    /// the provider by hash.
+    ///
+    /// # Errors
+    ///
+    /// Fails if an ancestor of `header` can't be fetched. The detector's view
+    /// of the chain is left unchanged.
+    pub async fn observe(
+        &mut self,
+        header: &N::HeaderResponse,
+    ) -> Result<Option<ReorgEvent>, RpcError> {
+        self.observe_link(Link::of(header)).await
+    }
+
+    /// Fetch the latest block and [`observe`](Self::observe) it
+    ///
+    /// # Errors
+    ///
+    /// Fails if the latest block or one of its ancestors can't be fetched.
+    pub async fn poll(&mut self) -> Result<Option<ReorgEvent>, RpcError> {
+        let block_number = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(RpcError::get_block_number_failed)?;
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Number(block_number))
+            .await
+            .map_err(|e| RpcError::get_block_failed(block_number, e))?
+            .ok_or(RpcError::BlockNotFound { block_number })?;
+        self.observe(block.header()).await
+    }
+
+    /// [`observe`](Self::observe) every header of `headers` until it ends
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first header whose ancestors can't be fetched.
+    pub async fn follow<S>(&mut self, headers: S) -> Result<(), RpcError>
+    where
+        S: Stream<Item = N::HeaderResponse>,
+    {
+        let mut headers = std::pin::pin!(headers);
+        while let Some(header) = headers.next().await {
+            self.observe(&header).await?;
+        }
+        Ok(())
+    }
+
+    /// [`observe`](Self::observe) every head published on `heads`, as by a
+    /// [`HeadTracker`](crate::HeadTracker), until its sender is dropped
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first head whose ancestors can't be fetched.
+    pub async fn follow_heads(
+        &mut self,
+        mut heads: watch::Receiver<Option<ChainHead>>,
+    ) -> Result<(), RpcError> {
+        loop {
+            let head = *heads.borrow_and_update();
+            if let Some(head) = head {
+                self.observe_link(Link::from(&head)).await?;
+            }
+            if heads.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn observe_link(&mut self, link: Link) -> Result<Option<ReorgEvent>, RpcError> {
+        if self.recent.get(&link.number) == Some(&link.hash) {
+            return Ok(None);
+        }
+
+        // Walk the new chain back until it meets a remembered block
+        let oldest = self.recent.first_key_value().map(|(number, _)| *number);
+        let mut new_chain = vec![link];
+        let mut current = link;
+        let ancestor = loop {
+            let Some(parent_number) = current.number.checked_sub(1) else {
+                break None;
+            };
+            if self.recent.get(&parent_number) == Some(&current.parent_hash) {
+                break Some(BlockNumHash::new(parent_number, current.parent_hash));
+            }
+            if oldest.is_none_or(|oldest| parent_number < oldest) {
+                break None;
+            }
+            if new_chain.len() > self.depth {
+                // Too far ahead to link up; start over from the new chain
+                debug!(
+                    block_number = link.number,
+                    "Lost track of the chain, restarting reorg detection"
+                );
+                self.recent = new_chain
+                    .iter()
+                    .map(|block| (block.number, block.hash))
+                    .collect();
+                return Ok(None);
+            }
+            current = self.fetch_link(parent_number, current.parent_hash).await?;
+            new_chain.push(current);
+        };
+
+        let first_new = ancestor.map_or(0, |ancestor| ancestor.number + 1);
+        let orphaned_blocks: Vec<_> = self
+            .recent
+            .range(first_new..)
+            .map(|(number, hash)| BlockNumHash::new(*number, *hash))
+            .collect();
+
+        self.recent.retain(|number, _| *number < first_new);
+        for block in &new_chain {
+            self.recent.insert(block.number, block.hash);
+        }
+        while self.recent.len() > self.depth {
+            self.recent.pop_first();
+        }
+
+        if orphaned_blocks.is_empty() {
+            return Ok(None);
+        }
+
+        let event = ReorgEvent {
+            common_ancestor: ancestor.unwrap_or_else(|| {
+                warn!(
+                    depth = self.depth,
+                    "Reorg deeper than the remembered blocks, orphaning all of them"
+                );
+                let first = orphaned_blocks[0].number;
+                BlockNumHash::new(first.saturating_sub(1), BlockHash::ZERO)
+            }),
+            orphaned_blocks,
+        };
+        warn!(
+            common_ancestor = event.common_ancestor.number,
+            depth = event.depth(),
+            new_tip = link.number,
+            "Chain reorganization detected"
+        );
+
+        for hook in &self.hooks {
+            hook.on_reorg(&event).await;
+        }
+        if self.sender.send(event.clone()).is_err() {
+            debug!("No reorg subscribers");
+        }
+        Ok(Some(event))
+    }
+
+    async fn fetch_link(
+        &self,
+        block_number: BlockNumber,
+        hash: BlockHash,
+    ) -> Result<Link, RpcError> {
+        let block = self
+            .provider
+            .get_block_by_hash(hash)
+            .await
+            .map_err(|e| RpcError::get_block_failed(block_number, e))?
+            .ok_or(RpcError::BlockNotFound { block_number })?;
+        Ok(Link::of(block.header()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_rpc_types::{Block, Header};
+    use alloy_transport::mock::Asserter;
+
+    /// Hash of block `number` on fork `fork`
+    fn hash(number: BlockNumber, fork: u8) -> BlockHash {
+        let mut bytes = [fork; 32];
+        bytes[..8].copy_from_slice(&number.to_be_bytes());
+        B256::from(bytes)
+    }
+
+    fn header(number: BlockNumber, fork: u8, parent_fork: u8) -> Header {
+        Header {
+            hash: hash(number, fork),
+            inner: alloy_consensus::Header {
+                number,
+                parent_hash: hash(number - 1, parent_fork),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn detector(asserter: &Asserter) -> ReorgDetector<RootProvider> {
+        ReorgDetector::new(ProviderBuilder::default().connect_mocked_client(asserter.clone()))
+    }
+
+    struct RecordingHook(std::sync::Mutex<Vec<BlockNumber>>);
+
+    #[async_trait]
+    impl ReorgHook for RecordingHook {
+        async fn on_reorg(&self, event: &ReorgEvent) {
+            self.0.lock().unwrap().push(event.first_invalid_block());
+        }
+    }
+
+    #[tokio::test]
+    async fn extending_the_chain_is_not_a_reorg() {
+        let asserter = Asserter::new();
+        let mut detector = detector(&asserter);
+
+        for number in 1..=5 {
+            assert_eq!(detector.observe(&header(number, 0, 0)).await.unwrap(), None);
+        }
+        // Repeats are ignored too
+        assert_eq!(detector.observe(&header(5, 0, 0)).await.unwrap(), None);
+        assert_eq!(detector.tip(), Some(BlockNumHash::new(5, hash(5, 0))));
+    }
+
+    #[tokio::test]
+    async fn competing_tip_orphans_the_old_blocks() {
+        let asserter = Asserter::new();
+        let hook = Arc::new(RecordingHook(Default::default()));
+        let mut detector = detector(&asserter).with_hook(hook.clone());
+        let mut reorgs = detector.subscribe();
+        for number in 1..=5 {
+            detector.observe(&header(number, 0, 0)).await.unwrap();
+        }
+
+        // Block 5 of fork 1 builds on fork 1's block 4, which is fetched
+        asserter.push_success(&Some(Block {
+            header: header(4, 1, 0),
+            ..Default::default()
+        }));
+        let event = detector.observe(&header(5, 1, 1)).await.unwrap().unwrap();
+
+        assert_eq!(event.common_ancestor, BlockNumHash::new(3, hash(3, 0)));
+        assert_eq!(
+            event.orphaned_blocks,
+            vec![
+                BlockNumHash::new(4, hash(4, 0)),
+                BlockNumHash::new(5, hash(5, 0))
+            ]
+        );
+        assert_eq!(reorgs.recv().await.unwrap(), event);
+        assert_eq!(*hook.0.lock().unwrap(), vec![4]);
+        assert_eq!(detector.tip(), Some(BlockNumHash::new(5, hash(5, 1))));
+    }
+
+    #[tokio::test]
+    async fn skipped_blocks_are_filled_without_a_reorg() {
+        let asserter = Asserter::new();
+        let mut detector = detector(&asserter);
+        detector.observe(&header(1, 0, 0)).await.unwrap();
+
+        asserter.push_success(&Some(Block {
+            header: header(2, 0, 0),
+            ..Default::default()
+        }));
+        assert_eq!(detector.observe(&header(3, 0, 0)).await.unwrap(), None);
+        assert_eq!(detector.recent.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn header_cache_hook_drops_orphaned_headers() {
+        use crate::cache::header::CachedHeader;
+
+        let headers = HeaderCache::new();
+        for number in 1..=3 {
+            headers.insert(CachedHeader {
+                number,
+                hash: hash(number, 0),
+                timestamp: number,
+                base_fee_per_gas: None,
+            });
+        }
+        headers
+            .on_reorg(&ReorgEvent {
+                common_ancestor: BlockNumHash::new(1, hash(1, 0)),
+                orphaned_blocks: vec![BlockNumHash::new(2, hash(2, 0))],
+            })
+            .await;
+
+        assert!(headers.get(1).is_some());
+        assert!(headers.get(2).is_none());
+        assert!(headers.get(3).is_none());
+    }
+
+    #[tokio::test]
+    async fn log_cache_hook_drops_orphaned_pages() {
+        use crate::events::source::LogSource;
+        use alloy_rpc_types::{Filter, Log};
+        use alloy_transport::TransportError;
+
+        /// Returns one log at the end of every range
+        struct LastBlockSource;
+
+        #[async_trait]
+        impl LogSource for LastBlockSource {
+            async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, TransportError> {
+                Ok(vec![Log {
+                    block_number: filter.get_to_block(),
+                    ..Default::default()
+                }])
+            }
+        }
+
+        let logs = CachedLogSource::new(Arc::new(LastBlockSource));
+        for (from, to) in [(1, 10), (11, 20)] {
+            logs.get_logs(&Filter::new().from_block(from).to_block(to))
+                .await
+                .unwrap();
+        }
+        logs.on_reorg(&ReorgEvent {
+            common_ancestor: BlockNumHash::new(14, hash(14, 0)),
+            orphaned_blocks: vec![BlockNumHash::new(15, hash(15, 0))],
+        })
+        .await;
+
+        assert_eq!(logs.stats().entries, 1);
+    }
+
+    #[tokio::test]
+    async fn transaction_cache_hook_drops_orphaned_transactions() {
+        use alloy_rpc_types::TransactionReceipt;
+
+        let receipt = |number: BlockNumber| -> TransactionReceipt {
+            serde_json::from_value(serde_json::json!({
+                "transactionHash": B256::repeat_byte(number as u8),
+                "transactionIndex": "0x0",
+                "blockHash": hash(number, 0),
+                "blockNumber": format!("{number:#x}"),
+                "from": "0x0000000000000000000000000000000000000001",
+                "to": "0x0000000000000000000000000000000000000002",
+                "cumulativeGasUsed": "0x5208",
+                "gasUsed": "0x5208",
+                "contractAddress": null,
+                "logs": [],
+                "logsBloom": format!("0x{}", "0".repeat(512)),
+                "type": "0x2",
+                "effectiveGasPrice": "0x3b9aca00",
+                "status": "0x1"
+            }))
+            .unwrap()
+        };
+        let transactions = TransactionCache::<Ethereum>::new();
+        for number in 1..=3 {
+            transactions.insert_receipt(B256::repeat_byte(number as u8), receipt(number));
+        }
+        transactions
+            .on_reorg(&ReorgEvent {
+                common_ancestor: BlockNumHash::new(1, hash(1, 0)),
+                orphaned_blocks: vec![BlockNumHash::new(2, hash(2, 0))],
+            })
+            .await;
+
+        assert!(transactions.receipt(B256::repeat_byte(1)).is_some());
+        assert!(transactions.receipt(B256::repeat_byte(2)).is_none());
+        assert_eq!(transactions.stats().entries, 1);
+    }
+}
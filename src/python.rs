@@ -0,0 +1,363 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Python bindings
+//!
+//! Exposes a `semioscan.Client` class to Python, backed by a
+//! [`SemioscanClient`]. Its methods return awaitables (run on the Tokio
+//! runtime managed by `pyo3-async-runtimes`, the maintained successor of
+//! `pyo3-asyncio`) that resolve to plain dicts, with addresses, hashes and
+//! 256-bit amounts as strings and dates as `datetime.date`.
+//!
+//! Build the extension with [maturin](https://www.maturin.rs) from the
+//! repository root, which picks up the `pyo3` feature from `pyproject.toml`:
+//!
+//! ```bash
+//! maturin develop --release
+//! ```
+//!
+//! ```python
+//! import asyncio, datetime
+//! import semioscan
+//!
+//! async def main():
+//!     client = semioscan.Client({8453: "https://base.example.com"})
+//!     window = await client.get_daily_window(8453, datetime.date(2025, 10, 1))
+//!     print(window["start_block"], window["end_block"])
+//!
+//! asyncio.run(main())
+//! ```
+//!
+//! # Price sources
+//!
+//! `calculate_price` takes any Python object with the same methods as
+//! [`PriceSource`]:
+//!
+//! - `router_address()` returns the contract address to scan, as a string
+//! - `event_topics()` returns the event signature hashes, as strings
+//! - `extract_swap_from_log(log)` receives the log as a dict (in JSON-RPC
+//!   form) and returns `None` or a dict with `token_in`, `token_in_amount`,
+//!   `token_out` and `token_out_amount` keys, and optionally `sender`
+//! - `sender_address()` and `should_include_swap(swap)` are optional
+//!
+//! Amounts may be Python ints or decimal/hex strings; use strings for amounts
+//! above 2^64.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_rpc_types::Log;
+use chrono::NaiveDate;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pythonize::{depythonize, pythonize};
+use serde::Serialize;
+
+use crate::client::SemioscanClient;
+use crate::errors;
+use crate::price::{PriceSource, PriceSourceError, SwapData};
+
+create_exception!(
+    semioscan,
+    SemioscanError,
+    PyException,
+    "Raised when a semioscan query fails"
+);
+
+/// Python-facing client over one RPC endpoint per chain
+#[pyclass(name = "Client", module = "semioscan", frozen)]
+pub struct PyClient {
+    inner: Arc<SemioscanClient>,
+}
+
+#[pymethods]
+impl PyClient {
+    /// Connect to `endpoints`, a dict mapping chain IDs to RPC URLs
+    #[new]
+    fn new(endpoints: HashMap<u64, String>) -> PyResult<Self> {
+        let mut builder = SemioscanClient::builder();
+        for (chain_id, url) in endpoints {
+            builder = builder.add_chain(chain_from_id(chain_id)?, url);
+        }
+        // Transport layers may spawn onto the runtime they are built in
+        let _runtime = pyo3_async_runtimes::tokio::get_runtime().enter();
+        let inner = builder
+            .build()
+            .map_err(|e| SemioscanError::new_err(e.to_string()))?;
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Chain IDs with a registered endpoint
+    fn chains(&self) -> Vec<u64> {
+        self.inner.chains().into_iter().map(u64::from).collect()
+    }
+
+    /// Block range produced on `date` (UTC), as a dict
+    fn get_daily_window<'py>(
+        &self,
+        py: Python<'py>,
+        chain_id: u64,
+        date: NaiveDate,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let chain = chain_from_id(chain_id)?;
+        let client = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let window = client.daily_window(chain, date).await.map_err(scan_error)?;
+            to_python(&window)
+        })
+    }
+
+    /// Price of `token` against `usdc_address` from swaps found by
+    /// `price_source` in `start_block..=end_block`, as a dict
+    #[allow(clippy::too_many_arguments)]
+    fn calculate_price<'py>(
+        &self,
+        py: Python<'py>,
+        chain_id: u64,
+        usdc_address: &str,
+        price_source: Bound<'py, PyAny>,
+        token: &str,
+        start_block: BlockNumber,
+        end_block: BlockNumber,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let chain = chain_from_id(chain_id)?;
+        let source = PyPriceSource::new(price_source)?;
+        let mut calculator = self
+            .inner
+            .price_calculator(chain, parse_address(usdc_address)?, Box::new(source))
+            .map_err(scan_error)?;
+        let token = parse_address(token)?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let result = calculator
+                .calculate_price_between_blocks(token, start_block, end_block)
+                .await
+                .map_err(|e| SemioscanError::new_err(e.to_string()))?;
+            to_python(&result)
+        })
+    }
+
+    /// Transfer amounts and gas costs for `token` from `from_address` to
+    /// `to_address` on `date` (UTC), as a dict
+    fn calculate_combined_data<'py>(
+        &self,
+        py: Python<'py>,
+        chain_id: u64,
+        date: NaiveDate,
+        from_address: &str,
+        to_address: &str,
+        token: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let chain = chain_from_id(chain_id)?;
+        let (from, to, token) = (
+            parse_address(from_address)?,
+            parse_address(to_address)?,
+            parse_address(token)?,
+        );
+        let client = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let result = client
+                .daily_combined_data(chain, date, from, to, token)
+                .await
+                .map_err(scan_error)?;
+            to_python(&result)
+        })
+    }
+}
+
+/// A [`PriceSource`] implemented by a Python object
+///
+/// The address and topics are read once, when the source is wrapped; swap
+/// extraction calls back into Python for every log.
+struct PyPriceSource {
+    object: Py<PyAny>,
+    router_address: Address,
+    event_topics: Vec<B256>,
+    sender_address: Option<Address>,
+    filters_swaps: bool,
+}
+
+impl PyPriceSource {
+    fn new(object: Bound<'_, PyAny>) -> PyResult<Self> {
+        let router_address =
+            parse_address(&object.call_method0("router_address")?.extract::<String>()?)?;
+        let event_topics = object
+            .call_method0("event_topics")?
+            .extract::<Vec<String>>()?
+            .iter()
+            .map(|topic| {
+                topic.parse::<B256>().map_err(|e| {
+                    PyValueError::new_err(format!("invalid event topic '{topic}': {e}"))
+                })
+            })
+            .collect::<PyResult<_>>()?;
+        let sender_address = if object.hasattr("sender_address")? {
+            object
+                .call_method0("sender_address")?
+                .extract::<Option<String>>()?
+                .as_deref()
+                .map(parse_address)
+                .transpose()?
+        } else {
+            None
+        };
+        Ok(Self {
+            router_address,
+            event_topics,
+            sender_address,
+            filters_swaps: object.hasattr("should_include_swap")?,
+            object: object.unbind(),
+        })
+    }
+}
+
+impl PriceSource for PyPriceSource {
+    fn router_address(&self) -> Address {
+        self.router_address
+    }
+
+    fn event_topics(&self) -> Vec<B256> {
+        self.event_topics.clone()
+    }
+
+    fn extract_swap_from_log(&self, log: &Log) -> Result<Option<SwapData>, PriceSourceError> {
+        Python::with_gil(|py| {
+            let swap = self
+                .object
+                .bind(py)
+                .call_method1("extract_swap_from_log", (pythonize(py, log)?,))?;
+            if swap.is_none() {
+                return Ok(None);
+            }
+            let mut swap: SwapData = depythonize(&swap)?;
+            swap.tx_hash = swap.tx_hash.or(log.transaction_hash);
+            swap.block_number = swap.block_number.or(log.block_number);
+            Ok(Some(swap))
+        })
+        .map_err(|e: PyErr| PriceSourceError::invalid_swap_data(e.to_string()))
+    }
+
+    fn sender_address(&self) -> Option<Address> {
+        self.sender_address
+    }
+
+    fn should_include_swap(&self, swap: &SwapData) -> bool {
+        if !self.filters_swaps {
+            return true;
+        }
+        Python::with_gil(|py| -> PyResult<bool> {
+            let swap = pythonize(py, swap)?;
+            self.object
+                .bind(py)
+                .call_method1("should_include_swap", (swap,))?
+                .extract()
+        })
+        .unwrap_or(false)
+    }
+}
+
+fn chain_from_id(chain_id: u64) -> PyResult<NamedChain> {
+    NamedChain::try_from(chain_id)
+        .map_err(|_| PyValueError::new_err(format!("unsupported chain ID {chain_id}")))
+}
+
+fn parse_address(address: &str) -> PyResult<Address> {
+    address
+        .parse()
+        .map_err(|e| PyValueError::new_err(format!("invalid address '{address}': {e}")))
+}
+
+fn scan_error(error: errors::SemioscanError) -> PyErr {
+    SemioscanError::new_err(error.to_string())
+}
+
+fn to_python(value: &impl Serialize) -> PyResult<Py<PyAny>> {
+    Python::with_gil(|py| Ok(pythonize(py, value)?.unbind()))
+}
+
+/// The `semioscan` Python module
+#[pymodule]
+fn semioscan(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyClient>()?;
+    m.add("SemioscanError", m.py().get_type::<SemioscanError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+    use pyo3::ffi::c_str;
+    use pyo3::types::PyModule;
+
+    const SOURCE: &std::ffi::CStr = c_str!(
+        r#"
+class Source:
+    def router_address(self):
+        return "0x00000000000000000000000000000000000000aa"
+
+    def event_topics(self):
+        return ["0x" + "11" * 32]
+
+    def extract_swap_from_log(self, log):
+        if log["address"].lower() != self.router_address():
+            return None
+        return {
+            "token_in": "0x00000000000000000000000000000000000000b1",
+            "token_in_amount": 10 ** 6,
+            "token_out": "0x00000000000000000000000000000000000000b2",
+            "token_out_amount": "1000",
+        }
+
+    def should_include_swap(self, swap):
+        return swap["token_out_amount"] != "0x0"
+"#
+    );
+
+    fn python_source() -> PyPriceSource {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module =
+                PyModule::from_code(py, SOURCE, c_str!("source.py"), c_str!("source")).unwrap();
+            PyPriceSource::new(module.getattr("Source").unwrap().call0().unwrap()).unwrap()
+        })
+    }
+
+    #[test]
+    fn python_objects_act_as_price_sources() {
+        let source = python_source();
+        assert_eq!(
+            source.router_address(),
+            address!("00000000000000000000000000000000000000aa")
+        );
+        assert_eq!(source.event_topics(), vec![B256::repeat_byte(0x11)]);
+        assert_eq!(source.sender_address(), None);
+
+        let mut log = Log::default();
+        log.inner.address = source.router_address();
+        log.block_number = Some(42);
+        let swap = source.extract_swap_from_log(&log).unwrap().unwrap();
+
+        assert_eq!(swap.token_in_amount, U256::from(1_000_000));
+        assert_eq!(swap.token_out_amount, U256::from(1000));
+        assert_eq!(swap.block_number, Some(42));
+        assert!(source.should_include_swap(&swap));
+
+        log.inner.address = Address::ZERO;
+        assert_eq!(source.extract_swap_from_log(&log).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_unknown_chains() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let err = chain_from_id(u64::MAX).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
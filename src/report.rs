@@ -0,0 +1,649 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Human-readable summaries of combined results
+//!
+//! A [`Report`] lays out a [`CombinedDataResult`] or a
+//! [`DailyCombinedData`] series as a short document: the totals, the
+//! transactions that cost the most gas and, for a series, a row per day with
+//! a bar charting its gas cost, followed by any anomalous days. Render it to
+//! Markdown for tickets and chat, or to a standalone HTML page.
+//!
+//! Native amounts are formatted with [`DisplayValue::native`], so they carry
+//! the chain's currency symbol. Token amounts are raw integers unless
+//! [`ReportOptions::with_token_decimals`] says how to scale them.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{DisplayOptions, Report, ReportOptions, TokenDecimals};
+//!
+//! let by_day = calculator
+//!     .calculate_for_dates(&query, start_date..=end_date, &windows)
+//!     .await?;
+//! let options = ReportOptions::default()
+//!     .with_title("Treasury → router, March")
+//!     .with_token_decimals(TokenDecimals::USDC)
+//!     .with_token_symbol("USDC");
+//! std::fs::write("report.md", Report::for_daily(&by_day, &options).to_markdown())?;
+//! ```
+
+use std::fmt::Write as _;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::U256;
+
+use crate::retrieval::{AnomalyMetric, CombinedDataResult, DailyCombinedData};
+use crate::types::display::{DisplayOptions, DisplayValue};
+use crate::types::tokens::TokenDecimals;
+use crate::types::wei::WeiAmount;
+
+/// Output format of [`Report::render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportFormat {
+    /// GitHub-flavored Markdown, with pipe tables
+    Markdown,
+    /// A standalone HTML document
+    Html,
+}
+
+/// What a [`Report`] includes and how it formats numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportOptions {
+    title: Option<String>,
+    top_transactions: usize,
+    display: DisplayOptions,
+    token_decimals: Option<TokenDecimals>,
+    token_symbol: Option<String>,
+    chart_width: usize,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            top_transactions: 10,
+            display: DisplayOptions::fixed(6).with_thousands_separator(','),
+            token_decimals: None,
+            token_symbol: None,
+            chart_width: 20,
+        }
+    }
+}
+
+impl ReportOptions {
+    /// Title of the report, instead of one naming the sender and recipient
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// List the `count` transactions that cost the most gas (10 by default,
+    /// 0 to leave the table out)
+    pub fn with_top_transactions(mut self, count: usize) -> Self {
+        self.top_transactions = count;
+        self
+    }
+
+    /// Format amounts with `options`
+    ///
+    /// Defaults to six decimal places with `,` grouping thousands.
+    pub fn with_display_options(mut self, options: DisplayOptions) -> Self {
+        self.display = options;
+        self
+    }
+
+    /// Scale token amounts by the token's `decimals` instead of showing raw
+    /// integers
+    pub fn with_token_decimals(mut self, decimals: TokenDecimals) -> Self {
+        self.token_decimals = Some(decimals);
+        self
+    }
+
+    /// Unit shown after token amounts, such as `USDC`
+    pub fn with_token_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.token_symbol = Some(symbol.into());
+        self
+    }
+
+    /// Width in characters of the longest bar in daily charts (20 by
+    /// default, 0 to leave the chart column out)
+    pub fn with_chart_width(mut self, width: usize) -> Self {
+        self.chart_width = width;
+        self
+    }
+
+    fn native(&self, amount: U256, chain: NamedChain) -> String {
+        DisplayValue::native(WeiAmount::new(amount), chain, &self.display).to_string()
+    }
+
+    fn token(&self, amount: U256) -> String {
+        let amount = match self.token_decimals {
+            Some(decimals) => self.display.format_units(amount, decimals.as_u8()),
+            None => amount.to_string(),
+        };
+        match &self.token_symbol {
+            Some(symbol) => format!("{amount} {symbol}"),
+            None => amount,
+        }
+    }
+
+    /// A bar `chart_width` long for `max`, and proportionally shorter for `value`
+    fn bar(&self, value: U256, max: U256) -> String {
+        if max.is_zero() {
+            return String::new();
+        }
+        let width = U256::from(self.chart_width);
+        let length = (value.saturating_mul(width) + max / U256::from(2)) / max;
+        "█".repeat(length.saturating_to::<usize>().min(self.chart_width))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Table {
+    columns: Vec<(&'static str, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Table(Table),
+}
+
+/// A summary document of combined results
+///
+/// Build one with [`for_combined`](Self::for_combined) or
+/// [`for_daily`](Self::for_daily), then [`render`](Self::render) it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+impl Report {
+    /// A report of one result: its totals and most expensive transactions
+    pub fn for_combined(result: &CombinedDataResult, options: &ReportOptions) -> Self {
+        let mut report = Self::new(result, options);
+        report.add_summary(result, options);
+        report
+    }
+
+    /// A report of a daily series: totals over the period, a row per day,
+    /// the most expensive transactions and any anomalous days
+    ///
+    /// An empty series gives a report saying so.
+    pub fn for_daily(data: &DailyCombinedData, options: &ReportOptions) -> Self {
+        let Some(first) = data.days.values().next() else {
+            return Self {
+                title: options
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "Combined data".to_string()),
+                blocks: vec![Block::Paragraph("No days to report.".to_string())],
+            };
+        };
+        let mut total = CombinedDataResult::new(
+            first.chain,
+            first.from_address,
+            first.to_address,
+            first.token_address,
+        );
+        for result in data.days.values() {
+            total.merge(result);
+        }
+
+        let mut report = Self::new(&total, options);
+        if let (Some(start), Some(end)) = (data.days.keys().next(), data.days.keys().last()) {
+            report.blocks.push(Block::Paragraph(format!(
+                "{start} to {end}, {} days.",
+                data.days.len()
+            )));
+        }
+        report.add_summary(&total, options);
+
+        let max_gas = data
+            .days
+            .values()
+            .map(|result| result.overall_total_gas_cost)
+            .max()
+            .unwrap_or_default();
+        let mut columns = vec![
+            ("Date", Align::Left),
+            ("Transactions", Align::Right),
+            ("Amount", Align::Right),
+            ("Gas cost", Align::Right),
+        ];
+        if options.chart_width > 0 {
+            columns.push(("", Align::Left));
+        }
+        let rows = data
+            .days
+            .iter()
+            .map(|(date, result)| {
+                let mut row = vec![
+                    date.to_string(),
+                    result.transaction_count.as_usize().to_string(),
+                    options.token(result.total_amount_transferred),
+                    options.native(result.overall_total_gas_cost, result.chain),
+                ];
+                if options.chart_width > 0 {
+                    row.push(options.bar(result.overall_total_gas_cost, max_gas));
+                }
+                row
+            })
+            .collect();
+        report.blocks.push(Block::Heading("Daily".to_string()));
+        report.blocks.push(Block::Table(Table { columns, rows }));
+
+        if !data.anomalies.is_empty() {
+            let rows = data
+                .anomalies
+                .iter()
+                .map(|anomaly| {
+                    let (value, baseline) = match anomaly.metric {
+                        AnomalyMetric::OverallTotalGasCost => (
+                            options.native(f64_to_u256(anomaly.value), total.chain),
+                            options.native(f64_to_u256(anomaly.baseline), total.chain),
+                        ),
+                        AnomalyMetric::TransactionCount => (
+                            format!("{:.0}", anomaly.value),
+                            format!("{:.1}", anomaly.baseline),
+                        ),
+                    };
+                    vec![
+                        anomaly.date.to_string(),
+                        metric_name(anomaly.metric).to_string(),
+                        value,
+                        baseline,
+                        anomaly
+                            .score
+                            .map_or_else(|| "n/a".to_string(), |score| format!("{score:+.2}")),
+                    ]
+                })
+                .collect();
+            report.blocks.push(Block::Heading("Anomalies".to_string()));
+            report.blocks.push(Block::Table(Table {
+                columns: vec![
+                    ("Date", Align::Left),
+                    ("Metric", Align::Left),
+                    ("Value", Align::Right),
+                    ("Baseline", Align::Right),
+                    ("Score", Align::Right),
+                ],
+                rows,
+            }));
+        }
+        report
+    }
+
+    /// The report's title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Render the report in `format`
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    /// Render the report as GitHub-flavored Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", escape_markdown(&self.title));
+        for block in &self.blocks {
+            out.push('\n');
+            match block {
+                Block::Heading(text) => {
+                    let _ = writeln!(out, "## {}", escape_markdown(text));
+                }
+                Block::Paragraph(text) => {
+                    let _ = writeln!(out, "{}", escape_markdown(text));
+                }
+                Block::Table(table) => {
+                    let header: Vec<_> = table
+                        .columns
+                        .iter()
+                        .map(|(name, _)| escape_markdown(name))
+                        .collect();
+                    let rule: Vec<_> = table
+                        .columns
+                        .iter()
+                        .map(|(_, align)| match align {
+                            Align::Left => "---",
+                            Align::Right => "---:",
+                        })
+                        .collect();
+                    let _ = writeln!(out, "| {} |", header.join(" | "));
+                    let _ = writeln!(out, "| {} |", rule.join(" | "));
+                    for row in &table.rows {
+                        let cells: Vec<_> = row.iter().map(|cell| escape_markdown(cell)).collect();
+                        let _ = writeln!(out, "| {} |", cells.join(" | "));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Render the report as a standalone HTML document
+    pub fn to_html(&self) -> String {
+        let title = escape_html(&self.title);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        for block in &self.blocks {
+            match block {
+                Block::Heading(text) => {
+                    let _ = writeln!(out, "<h2>{}</h2>", escape_html(text));
+                }
+                Block::Paragraph(text) => {
+                    let _ = writeln!(out, "<p>{}</p>", escape_html(text));
+                }
+                Block::Table(table) => {
+                    out.push_str("<table>\n<thead>\n<tr>");
+                    for (name, align) in &table.columns {
+                        let _ = write!(out, "<th{}>{}</th>", html_align(*align), escape_html(name));
+                    }
+                    out.push_str("</tr>\n</thead>\n<tbody>\n");
+                    for row in &table.rows {
+                        out.push_str("<tr>");
+                        for ((_, align), cell) in table.columns.iter().zip(row) {
+                            let _ =
+                                write!(out, "<td{}>{}</td>", html_align(*align), escape_html(cell));
+                        }
+                        out.push_str("</tr>\n");
+                    }
+                    out.push_str("</tbody>\n</table>\n");
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// A report titled after `result`, with a line naming its chain and addresses
+    fn new(result: &CombinedDataResult, options: &ReportOptions) -> Self {
+        let title = options.title.clone().unwrap_or_else(|| {
+            format!(
+                "Combined data: {} → {}",
+                result.from_address, result.to_address
+            )
+        });
+        let blocks = vec![Block::Paragraph(format!(
+            "Chain {}, token {}, from {} to {}.",
+            result.chain, result.token_address, result.from_address, result.to_address
+        ))];
+        Self { title, blocks }
+    }
+
+    /// Notes on completeness, the totals and the top transactions of `result`
+    fn add_summary(&mut self, result: &CombinedDataResult, options: &ReportOptions) {
+        if result.cancelled {
+            self.blocks.push(Block::Paragraph(
+                "The calculation was cancelled before the end of the range, so these figures are partial."
+                    .to_string(),
+            ));
+        }
+        let failures = result.retrieval_metadata.partial_failures.len();
+        if failures > 0 {
+            self.blocks.push(Block::Paragraph(format!(
+                "{failures} transfers couldn't be looked up and are missing from these figures."
+            )));
+        }
+        if !result.warnings.is_empty() {
+            self.blocks.push(Block::Paragraph(format!(
+                "{} warnings were recorded during the scan.",
+                result.warnings.len()
+            )));
+        }
+
+        let chain = result.chain;
+        let mut rows = vec![
+            vec![
+                "Transactions".to_string(),
+                result.transaction_count.as_usize().to_string(),
+            ],
+            vec![
+                "Amount transferred".to_string(),
+                options.token(result.total_amount_transferred),
+            ],
+            vec![
+                "Execution gas".to_string(),
+                options.native(result.total_l2_execution_cost, chain),
+            ],
+        ];
+        if !result.total_blob_gas_cost.is_zero() {
+            rows.push(vec![
+                "Blob gas".to_string(),
+                options.native(result.total_blob_gas_cost, chain),
+            ]);
+        }
+        if !result.total_l1_fee.is_zero() {
+            rows.push(vec![
+                "L1 data fee".to_string(),
+                options.native(result.total_l1_fee, chain),
+            ]);
+        }
+        rows.push(vec![
+            "Total gas cost".to_string(),
+            options.native(result.overall_total_gas_cost, chain),
+        ]);
+        if !result.total_base_fee_burned.is_zero() || !result.total_priority_fee_paid.is_zero() {
+            rows.push(vec![
+                "Base fee burned".to_string(),
+                options.native(result.total_base_fee_burned, chain),
+            ]);
+            rows.push(vec![
+                "Priority fee paid".to_string(),
+                options.native(result.total_priority_fee_paid, chain),
+            ]);
+        }
+        self.blocks.push(Block::Heading("Totals".to_string()));
+        self.blocks.push(Block::Table(Table {
+            columns: vec![("Metric", Align::Left), ("Value", Align::Right)],
+            rows,
+        }));
+
+        if options.top_transactions == 0 || result.transactions_data.is_empty() {
+            return;
+        }
+        let mut transactions: Vec<_> = result.transactions_data.iter().collect();
+        // Most expensive first, then in chain order
+        transactions.sort_by(|a, b| {
+            b.total_gas_cost()
+                .cmp(&a.total_gas_cost())
+                .then(a.block_number.cmp(&b.block_number))
+                .then(a.log_index.cmp(&b.log_index))
+        });
+        let rows = transactions
+            .into_iter()
+            .take(options.top_transactions)
+            .enumerate()
+            .map(|(index, tx)| {
+                vec![
+                    (index + 1).to_string(),
+                    tx.tx_hash.to_string(),
+                    tx.block_number.to_string(),
+                    options.token(tx.transferred_amount),
+                    options.native(tx.total_gas_cost(), chain),
+                ]
+            })
+            .collect();
+        self.blocks
+            .push(Block::Heading("Top transactions by gas cost".to_string()));
+        self.blocks.push(Block::Table(Table {
+            columns: vec![
+                ("#", Align::Right),
+                ("Transaction", Align::Left),
+                ("Block", Align::Right),
+                ("Amount", Align::Right),
+                ("Gas cost", Align::Right),
+            ],
+            rows,
+        }));
+    }
+}
+
+fn metric_name(metric: AnomalyMetric) -> &'static str {
+    match metric {
+        AnomalyMetric::OverallTotalGasCost => "Total gas cost",
+        AnomalyMetric::TransactionCount => "Transactions",
+    }
+}
+
+/// A non-negative float of wei, rounded to a whole wei
+fn f64_to_u256(value: f64) -> U256 {
+    // `max` also maps NaN to zero, so only values past `U256::MAX` fail
+    U256::try_from(value.max(0.0).round()).unwrap_or(U256::MAX)
+}
+
+/// Escape the characters that would break a Markdown table or start markup
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '|' | '\\' | '*' | '_' | '`' | '<' | '>' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn html_align(align: Align) -> &'static str {
+    match align {
+        Align::Left => "",
+        Align::Right => " style=\"text-align: right\"",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retrieval::GasAndAmountForTx;
+    use crate::types::gas::{GasAmount, GasPrice};
+    use alloy_primitives::{Address, TxHash};
+    use chrono::NaiveDate;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_f64_to_u256_rounds_clamps_and_saturates() {
+        assert_eq!(f64_to_u256(1_499.5), U256::from(1_500u64));
+        assert_eq!(f64_to_u256(-3.0), U256::ZERO);
+        assert_eq!(f64_to_u256(f64::NAN), U256::ZERO);
+        assert_eq!(f64_to_u256(f64::INFINITY), U256::MAX);
+        assert_eq!(
+            f64_to_u256(1e20),
+            U256::from(100_000_000_000_000_000_000u128)
+        );
+    }
+
+    fn transfer(n: u8, gas_used: u64, amount: u64) -> GasAndAmountForTx {
+        GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(n),
+            block_number: 100 + n as u64,
+            block_timestamp: None,
+            tx_index: Some(0),
+            log_index: Some(0),
+            token_address: None,
+            gas_used: GasAmount::from(U256::from(gas_used)),
+            effective_gas_price: GasPrice::from(U256::from(1_000_000_000u64)),
+            base_fee_per_gas: None,
+            l1_fee: None,
+            blob_gas_cost: U256::ZERO,
+            transferred_amount: U256::from(amount),
+        }
+    }
+
+    fn result(transfers: &[GasAndAmountForTx]) -> CombinedDataResult {
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::repeat_byte(0xa1),
+            Address::repeat_byte(0xb2),
+            Address::repeat_byte(0xc3),
+        );
+        for transfer in transfers {
+            result.add_transaction_data(transfer.clone());
+        }
+        result
+    }
+
+    #[test]
+    fn test_combined_report_lists_totals_and_most_expensive_transactions_first() {
+        let result = result(&[
+            transfer(1, 21_000, 1_500_000),
+            transfer(2, 63_000, 2_500_000),
+        ]);
+        let options = ReportOptions::default()
+            .with_title("Treasury | router")
+            .with_token_decimals(TokenDecimals::USDC)
+            .with_token_symbol("USDC")
+            .with_top_transactions(1);
+        let markdown = Report::for_combined(&result, &options).to_markdown();
+
+        assert!(markdown.starts_with("# Treasury \\| router\n"));
+        assert!(markdown.contains("| Amount transferred | 4.000000 USDC |"));
+        assert!(markdown.contains("| Total gas cost | 0.000084 ETH |"));
+        assert!(markdown.contains("| --- | ---: |"));
+        // Only the top transaction, which is the second
+        assert!(markdown.contains(&TxHash::repeat_byte(2).to_string()));
+        assert!(!markdown.contains(&TxHash::repeat_byte(1).to_string()));
+    }
+
+    #[test]
+    fn test_daily_report_charts_days_and_escapes_html() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let data = DailyCombinedData {
+            days: BTreeMap::from([
+                (day(1), result(&[transfer(1, 20_000, 1)])),
+                (day(2), result(&[transfer(2, 40_000, 1)])),
+                (day(3), result(&[])),
+            ]),
+            anomalies: Vec::new(),
+        };
+        let options = ReportOptions::default()
+            .with_title("<Treasury & co>")
+            .with_chart_width(4);
+        let report = Report::for_daily(&data, &options);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("2025-03-01 to 2025-03-03, 3 days."));
+        assert!(markdown.contains("| 2025-03-01 | 1 | 1 | 0.000020 ETH | ██ |"));
+        assert!(markdown.contains("| 2025-03-02 | 1 | 1 | 0.000040 ETH | ████ |"));
+        assert!(markdown.contains("| 2025-03-03 | 0 | 0 | 0.000000 ETH |  |"));
+        assert!(markdown.contains("| Transactions | 2 |"));
+
+        let html = report.render(ReportFormat::Html);
+        assert!(html.contains("<title>&lt;Treasury &amp; co&gt;</title>"));
+        assert!(html.contains("<td style=\"text-align: right\">0.000040 ETH</td>"));
+        assert!(html.ends_with("</html>\n"));
+
+        let empty = Report::for_daily(&DailyCombinedData::default(), &ReportOptions::default());
+        assert!(empty.to_markdown().contains("No days to report."));
+    }
+}
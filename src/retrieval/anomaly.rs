@@ -0,0 +1,302 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flagging days whose gas or transaction counts break from recent history
+//!
+//! An [`AnomalyDetector`] compares each day of a daily series against the
+//! days before it, on both `overall_total_gas_cost` and `transaction_count`,
+//! with either a rolling z-score or an interquartile-range fence. Days it
+//! flags come back as [`DailyAnomaly`]s in [`DailyCombinedData::anomalies`],
+//! so a pipeline can alert on, say, a tenfold gas spike without computing
+//! statistics itself.
+//!
+//! Only days in the series count as history, so the first
+//! [`min_history`](AnomalyDetector::with_min_history) days of a series are
+//! never flagged. Cancelled days are neither flagged nor used as history,
+//! since their totals are incomplete.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{AnomalyDetector, AnomalyMethod};
+//!
+//! let calculator = CombinedCalculator::new(provider)
+//!     .with_anomaly_detector(AnomalyDetector::new(AnomalyMethod::Iqr { multiplier: 3.0 }));
+//! let daily = calculator
+//!     .calculate_for_dates(&query, start..=end, &windows)
+//!     .await?;
+//! for anomaly in &daily.anomalies {
+//!     alert(format!("{}: {:?} was {} (usually {})", anomaly.date, anomaly.metric, anomaly.value, anomaly.baseline));
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::types::CombinedDataResult;
+
+/// Combined data for each day of a date range, with the days that stand out
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyCombinedData {
+    /// Result for each UTC date, in date order
+    #[serde(rename = "days")]
+    pub days: BTreeMap<NaiveDate, CombinedDataResult>,
+    /// Days whose figures deviate from the days before them
+    #[serde(rename = "anomalies", default, skip_serializing_if = "Vec::is_empty")]
+    pub anomalies: Vec<DailyAnomaly>,
+}
+
+/// Daily figure an [`AnomalyDetector`] checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    /// [`CombinedDataResult::overall_total_gas_cost`], in wei
+    OverallTotalGasCost,
+    /// [`CombinedDataResult::transaction_count`]
+    TransactionCount,
+}
+
+impl AnomalyMetric {
+    const ALL: [Self; 2] = [Self::OverallTotalGasCost, Self::TransactionCount];
+
+    fn value(self, result: &CombinedDataResult) -> f64 {
+        match self {
+            Self::OverallTotalGasCost => f64::from(result.overall_total_gas_cost),
+            Self::TransactionCount => result.transaction_count.as_usize() as f64,
+        }
+    }
+}
+
+/// How far from its history a day must be to be flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnomalyMethod {
+    /// More than `threshold` standard deviations from the history's mean
+    ZScore {
+        /// Standard deviations from the mean beyond which a day is flagged
+        threshold: f64,
+    },
+    /// More than `multiplier` interquartile ranges below the history's first
+    /// quartile or above its third
+    ///
+    /// Less swayed than the z-score by earlier outliers in the history.
+    Iqr {
+        /// Interquartile ranges beyond the quartiles at which a day is
+        /// flagged; 1.5 is Tukey's fence for outliers, 3 for far outliers
+        multiplier: f64,
+    },
+}
+
+/// A day whose figure deviates from the days before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyAnomaly {
+    /// The flagged UTC day
+    #[serde(rename = "date")]
+    pub date: NaiveDate,
+    /// Which of the day's figures deviates
+    #[serde(rename = "metric")]
+    pub metric: AnomalyMetric,
+    /// The day's figure
+    #[serde(rename = "value")]
+    pub value: f64,
+    /// What the history suggested: its mean for a z-score, its median for an
+    /// interquartile range
+    #[serde(rename = "baseline")]
+    pub baseline: f64,
+    /// Signed distance from the history, in standard deviations for a
+    /// z-score and in interquartile ranges beyond the fence for an
+    /// interquartile range
+    ///
+    /// `None` when the history didn't vary at all, so any change is flagged
+    /// but can't be scaled.
+    #[serde(rename = "score", default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+/// Flags days of a daily series that deviate from the days before them
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnomalyDetector {
+    method: AnomalyMethod,
+    lookback: usize,
+    min_history: usize,
+}
+
+impl Default for AnomalyDetector {
+    /// A z-score threshold of 3 over the previous 14 days, once 7 are available
+    fn default() -> Self {
+        Self {
+            method: AnomalyMethod::ZScore { threshold: 3.0 },
+            lookback: 14,
+            min_history: 7,
+        }
+    }
+}
+
+impl AnomalyDetector {
+    /// A detector using `method`, with the default lookback and minimum history
+    pub fn new(method: AnomalyMethod) -> Self {
+        Self {
+            method,
+            ..Self::default()
+        }
+    }
+
+    /// Compare each day against at most the `days` before it
+    pub fn with_lookback(mut self, days: usize) -> Self {
+        self.lookback = days.max(1);
+        self
+    }
+
+    /// Only check days with at least `days` days of history
+    pub fn with_min_history(mut self, days: usize) -> Self {
+        self.min_history = days.max(1);
+        self
+    }
+
+    /// Days of `days` that deviate from their history, in date order
+    pub fn detect(&self, days: &BTreeMap<NaiveDate, CombinedDataResult>) -> Vec<DailyAnomaly> {
+        let series: Vec<(NaiveDate, &CombinedDataResult)> = days
+            .iter()
+            .filter(|(_, result)| !result.cancelled)
+            .map(|(date, result)| (*date, result))
+            .collect();
+
+        let mut anomalies = Vec::new();
+        for (index, (date, result)) in series.iter().enumerate() {
+            let history = &series[index.saturating_sub(self.lookback)..index];
+            if history.len() < self.min_history.min(self.lookback) {
+                continue;
+            }
+            for metric in AnomalyMetric::ALL {
+                let mut values: Vec<f64> = history
+                    .iter()
+                    .map(|(_, result)| metric.value(result))
+                    .collect();
+                let value = metric.value(result);
+                if let Some((baseline, score)) = self.method.deviation(value, &mut values) {
+                    anomalies.push(DailyAnomaly {
+                        date: *date,
+                        metric,
+                        value,
+                        baseline,
+                        score,
+                    });
+                }
+            }
+        }
+        anomalies
+    }
+}
+
+impl AnomalyMethod {
+    /// Baseline and score of `value` against `history`, if it is anomalous
+    fn deviation(self, value: f64, history: &mut [f64]) -> Option<(f64, Option<f64>)> {
+        match self {
+            AnomalyMethod::ZScore { threshold } => {
+                let count = history.len() as f64;
+                let mean = history.iter().sum::<f64>() / count;
+                let variance = history.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+                let std_dev = variance.sqrt();
+                if std_dev == 0.0 {
+                    return (value != mean).then_some((mean, None));
+                }
+                let score = (value - mean) / std_dev;
+                (score.abs() > threshold).then_some((mean, Some(score)))
+            }
+            AnomalyMethod::Iqr { multiplier } => {
+                history.sort_by(f64::total_cmp);
+                let q1 = quantile(history, 0.25);
+                let median = quantile(history, 0.5);
+                let q3 = quantile(history, 0.75);
+                let iqr = q3 - q1;
+                if iqr == 0.0 {
+                    return (value != median).then_some((median, None));
+                }
+                let score = if value > q3 {
+                    (value - q3) / iqr
+                } else if value < q1 {
+                    (value - q1) / iqr
+                } else {
+                    0.0
+                };
+                (score.abs() > multiplier).then_some((median, Some(score)))
+            }
+        }
+    }
+}
+
+/// Linearly interpolated quantile of sorted, non-empty `values`
+fn quantile(values: &[f64], q: f64) -> f64 {
+    let position = q * (values.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    values[lower] + (values[upper] - values[lower]) * (position - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_chains::NamedChain;
+    use alloy_primitives::{Address, U256};
+
+    use crate::types::config::TransactionCount;
+
+    fn series(gas: &[u64]) -> BTreeMap<NaiveDate, CombinedDataResult> {
+        let start = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        start
+            .iter_days()
+            .zip(gas)
+            .map(|(date, gas)| {
+                let mut result = CombinedDataResult::new(
+                    NamedChain::Mainnet,
+                    Address::ZERO,
+                    Address::ZERO,
+                    Address::ZERO,
+                );
+                result.overall_total_gas_cost = U256::from(*gas);
+                result.transaction_count = TransactionCount::new(10);
+                (date, result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tenfold_gas_spike_is_flagged() {
+        let days = series(&[100, 110, 95, 105, 98, 102, 100, 1_000, 101]);
+
+        for method in [
+            AnomalyMethod::ZScore { threshold: 3.0 },
+            AnomalyMethod::Iqr { multiplier: 3.0 },
+        ] {
+            let anomalies = AnomalyDetector::new(method).detect(&days);
+            assert_eq!(anomalies.len(), 1, "{method:?}");
+            let spike = &anomalies[0];
+            assert_eq!(spike.date, NaiveDate::from_ymd_opt(2025, 3, 8).unwrap());
+            assert_eq!(spike.metric, AnomalyMetric::OverallTotalGasCost);
+            assert_eq!(spike.value, 1_000.0);
+            assert!(spike.score.unwrap() > 3.0);
+        }
+    }
+
+    #[test]
+    fn test_short_or_cancelled_history_is_not_flagged() {
+        let mut days = series(&[100, 100, 100, 5_000]);
+        assert!(AnomalyDetector::default().detect(&days).is_empty());
+
+        // A flat history flags any change, without a score
+        let anomalies = AnomalyDetector::default().with_min_history(3).detect(&days);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].baseline, 100.0);
+        assert_eq!(anomalies[0].score, None);
+
+        days.values_mut().last().unwrap().cancelled = true;
+        assert!(AnomalyDetector::default()
+            .with_min_history(3)
+            .detect(&days)
+            .is_empty());
+    }
+}
@@ -53,14 +53,20 @@
 //! }
 //! ```
 
+use alloy_eips::BlockId;
 use alloy_erc20::LazyToken;
-use alloy_network::Network;
+use alloy_network::{Network, TransactionBuilder};
 use alloy_primitives::{Address, U256};
 use alloy_provider::Provider;
 use alloy_rpc_types::TransactionTrait;
+use alloy_sol_types::{sol, SolCall};
 use futures::future::join_all;
 use tracing::{info, warn};
 
+sol! {
+    function balanceOf(address owner) external view returns (uint256);
+}
+
 /// Query for a token balance: (token_address, holder_address)
 pub type BalanceQuery = (Address, Address);
 
@@ -149,6 +155,73 @@ where
     join_all(fetch_futures).await
 }
 
+/// Batch fetch token balances for multiple (token, holder) pairs as of `block`.
+///
+/// Like [`batch_fetch_balances`], but each `balanceOf` call reads the state at
+/// `block` instead of the latest block, so historical balances need an
+/// archive node. The calls run in parallel and are batched into one
+/// Multicall3 request under `CallBatchLayer`.
+///
+/// # Returns
+///
+/// A vector of results, one for each query, in query order. Each result is
+/// either:
+/// - `Ok((token, holder, balance))` on success
+/// - `Err((token, holder, error_message))` if the call failed or returned
+///   something other than a `uint256`
+pub async fn batch_fetch_balances_at<N, P>(
+    provider: &P,
+    queries: &[BalanceQuery],
+    block: BlockId,
+) -> Vec<Result<BalanceResult, BalanceError>>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    if queries.is_empty() {
+        return vec![];
+    }
+
+    info!(
+        count = queries.len(),
+        ?block,
+        "Batch fetching token balances at block"
+    );
+
+    let fetch_futures: Vec<_> = queries
+        .iter()
+        .map(|&(token_address, holder_address)| async move {
+            let request = N::TransactionRequest::default()
+                .with_to(token_address)
+                .with_input(
+                    balanceOfCall {
+                        owner: holder_address,
+                    }
+                    .abi_encode(),
+                );
+            let balance = match provider.call(request).block(block).await {
+                Ok(data) => balanceOfCall::abi_decode_returns(&data).map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match balance {
+                Ok(balance) => Ok((token_address, holder_address, balance)),
+                Err(e) => {
+                    warn!(
+                        ?token_address,
+                        ?holder_address,
+                        ?block,
+                        error = %e,
+                        "Failed to fetch token balance"
+                    );
+                    Err((token_address, holder_address, e))
+                }
+            }
+        })
+        .collect();
+
+    join_all(fetch_futures).await
+}
+
 /// Batch fetch ETH balances for multiple addresses.
 ///
 /// This function executes all ETH balance queries in parallel.
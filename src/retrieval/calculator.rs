@@ -20,32 +20,24 @@
 //! let calculator = CombinedCalculator::new(provider);
 //! ```
 //!
-//! For Ethereum-compatible chains (Ethereum, Arbitrum, Polygon, etc.), use
-//! [`calculate_combined_data_ethereum`](CombinedCalculator::calculate_combined_data_ethereum):
+//! Describe the transfers to look for with a [`CombinedQuery`]:
 //!
 //! ```ignore
-//! let result = calculator.calculate_combined_data_ethereum(
-//!     chain,
-//!     from_address,
-//!     to_address,
-//!     token_address,
-//!     from_block,
-//!     to_block,
-//! ).await?;
+//! let query = CombinedQuery::builder()
+//!     .chain(chain)
+//!     .from(from_address)
+//!     .to(to_address)
+//!     .token(token_address)
+//!     .blocks(from_block..=to_block)
+//!     .build()?;
 //! ```
 //!
-//! For Optimism-based chains with L1 data fees (Optimism, Base, etc.), use
-//! [`calculate_combined_data_optimism`](CombinedCalculator::calculate_combined_data_optimism):
+//! Then call `calculate`, which is available for both Ethereum-compatible
+//! chains (Ethereum, Arbitrum, Polygon, etc.) and Optimism-based chains with
+//! L1 data fees (Optimism, Base, etc.), depending on the provider's network:
 //!
 //! ```ignore
-//! let result = calculator.calculate_combined_data_optimism(
-//!     chain,
-//!     from_address,
-//!     to_address,
-//!     token_address,
-//!     from_block,
-//!     to_block,
-//! ).await?;
+//! let result = calculator.calculate(&query).await?;
 //! ```
 //!
 //! The calculator automatically handles:
@@ -64,24 +56,43 @@ use alloy_provider::Provider;
 use alloy_rpc_types::{Log as RpcLog, TransactionTrait};
 use alloy_sol_types::SolEvent;
 use alloy_transport::TransportError;
+use chrono::NaiveDate;
 use futures::future::join_all;
 use op_alloy_network::Optimism;
-use std::{borrow::Cow, error::Error as StdError, sync::Arc};
-use tokio::time::sleep;
+use std::{
+    borrow::Cow, collections::BTreeMap, error::Error as StdError, ops::RangeInclusive, sync::Arc,
+};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, trace, warn, Instrument};
 
-use crate::config::SemioscanConfig;
+use crate::blocks::window::BlockWindowCalculator;
+use crate::cache::deployment::DeploymentCache;
+use crate::cache::header::HeaderCache;
+use crate::cache::transaction::TransactionCache;
+use crate::config::{within_deadline, OperationDeadline, SemioscanConfig};
+use crate::events::chunked::{get_logs_bisecting, ChunkScheduler};
 use crate::events::definitions::Transfer;
+use crate::events::discovery::discover_recipients_from_source;
+use crate::events::source::{LogSource, RpcLogSource};
+use crate::events::transfer_tax::{estimate_transfer_tax, sample_blocks, TransferTaxMode};
 use crate::gas::adapter::{EthereumReceiptAdapter, OptimismReceiptAdapter, ReceiptAdapter};
+use crate::gas::base_fee::get_base_fees;
+use crate::provider::capabilities::CapabilitySource;
+use crate::provider::{CapabilityCache, ProviderCapabilities};
 use crate::tracing::spans;
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
 use crate::types::gas::{GasAmount, GasPrice};
+use crate::types::tokens::TokenAmount;
+use crate::types::warnings::ScanWarning;
 
+use super::anomaly::{AnomalyDetector, DailyCombinedData};
 use super::gas_calculation::GasCalculationCore;
+use super::query::CombinedQuery;
 use super::types::{
     CombinedDataLookupAttempt, CombinedDataLookupFailure, CombinedDataLookupPass,
-    CombinedDataLookupStage, CombinedDataResult, GasAndAmountForTx,
+    CombinedDataLookupStage, CombinedDataResult, GasAndAmountForTx, GasAttribution, TransferCursor,
 };
-use crate::errors::RetrievalError;
+use crate::errors::{RetrievalError, SemioscanError};
 
 /// Log metadata extracted from RpcLog for batch processing.
 ///
@@ -92,6 +103,11 @@ use crate::errors::RetrievalError;
 struct LogBatchEntry {
     tx_hash: TxHash,
     block_number: BlockNumber,
+    block_timestamp: Option<u64>,
+    tx_index: Option<u64>,
+    log_index: Option<u64>,
+    token_address: Address,
+    base_fee_per_gas: Option<GasPrice>,
     transfer_value: alloy_primitives::U256,
 }
 
@@ -170,6 +186,9 @@ fn build_lookup_failure(
     CombinedDataLookupFailure {
         tx_hash: entry.tx_hash,
         block_number: entry.block_number,
+        block_timestamp: entry.block_timestamp,
+        tx_index: entry.tx_index,
+        log_index: entry.log_index,
         transfer_value: entry.transfer_value,
         attempts: vec![build_lookup_attempt(pass, stage, &error)],
     }
@@ -223,6 +242,26 @@ fn lookup_request_failed(
     ))
 }
 
+/// Build the error returned when a combined calculation runs out of time
+fn deadline_exceeded(
+    deadline: Option<&OperationDeadline>,
+    next_block: BlockNumber,
+    partial: CombinedDataResult,
+) -> RetrievalError {
+    let deadline = deadline.map(OperationDeadline::budget).unwrap_or_default();
+    warn!(
+        ?deadline,
+        next_block,
+        transactions_found = partial.transaction_count.as_usize(),
+        "Combined data calculation exceeded its operation deadline"
+    );
+    RetrievalError::DeadlineExceeded {
+        deadline,
+        next_block,
+        partial: Box::new(partial),
+    }
+}
+
 fn should_attempt_permissive_tx_decode(chain: NamedChain, error: &TransportError) -> bool {
     // The observed zkSync incident shape is an Alloy deserialization error
     // (`missing field accessList`), so match the structured error variant
@@ -238,6 +277,18 @@ where
 {
     provider: Arc<P>,
     config: SemioscanConfig,
+    cancellation: Option<CancellationToken>,
+    gas_attribution: GasAttribution,
+    capabilities: CapabilitySource,
+    log_source: Option<Arc<dyn LogSource>>,
+    header_cache: Option<HeaderCache>,
+    transaction_cache: Option<TransactionCache<N>>,
+    deployments: Option<DeploymentCache>,
+    resume_after: Option<TransferCursor>,
+    page_limit: Option<usize>,
+    anomaly_detector: AnomalyDetector,
+    transfer_tax: TransferTaxMode,
+    transfer_tax_samples: usize,
     network_marker: std::marker::PhantomData<N>,
 }
 
@@ -257,10 +308,222 @@ where
         Self {
             provider: Arc::new(provider),
             config,
+            cancellation: None,
+            gas_attribution: GasAttribution::default(),
+            capabilities: CapabilitySource::Unknown,
+            log_source: None,
+            header_cache: None,
+            transaction_cache: None,
+            deployments: None,
+            resume_after: None,
+            page_limit: None,
+            anomaly_detector: AnomalyDetector::default(),
+            transfer_tax: TransferTaxMode::default(),
+            transfer_tax_samples: 3,
             network_marker: std::marker::PhantomData,
         }
     }
 
+    /// Use methods the provider is known to support
+    ///
+    /// With `eth_feeHistory` available, a chunk's base fees are read in one
+    /// request instead of one block header each. See
+    /// [`ProviderCapabilities::probe`].
+    pub fn with_capabilities(mut self, capabilities: ProviderCapabilities) -> Self {
+        self.capabilities = CapabilitySource::Known(capabilities);
+        self
+    }
+
+    /// Probe the provider for optional methods before the first chunk's base
+    /// fees are read
+    ///
+    /// Probes are shared through `cache`, keyed by `endpoint`. Replaces
+    /// capabilities given with [`with_capabilities`](Self::with_capabilities).
+    pub fn with_capability_probe(
+        mut self,
+        cache: CapabilityCache,
+        endpoint: impl Into<String>,
+    ) -> Self {
+        self.capabilities = CapabilitySource::Probe {
+            cache,
+            endpoint: endpoint.into(),
+        };
+        self
+    }
+
+    /// Fetch logs from `source` instead of `eth_getLogs` on the provider
+    ///
+    /// Receipts, transactions and blocks still come from the provider.
+    pub fn with_log_source(mut self, source: Arc<dyn LogSource>) -> Self {
+        self.log_source = Some(source);
+        self
+    }
+
+    /// Read and record block headers in `cache`, which may be shared with
+    /// other calculators on the same chain
+    ///
+    /// Base fee lookups check it before fetching a header.
+    pub fn with_header_cache(mut self, cache: HeaderCache) -> Self {
+        self.header_cache = Some(cache);
+        self
+    }
+
+    /// Read and record transactions and receipts in `cache`, which may be
+    /// shared with other calculators
+    ///
+    /// Lookups check it before fetching a transaction or receipt, so a
+    /// transaction already seen by a gas run isn't fetched again.
+    pub fn with_transaction_cache(mut self, cache: TransactionCache<N>) -> Self {
+        self.transaction_cache = Some(cache);
+        self
+    }
+
+    /// Start scans no earlier than the token's deployment block
+    ///
+    /// The deployment block is found once per token with a binary search
+    /// over `eth_getCode` and kept in `cache`, which may be shared with other
+    /// calculators. Ranges ending before the deployment return an empty
+    /// result without scanning. Requires an archive node.
+    pub fn with_deployment_cache(mut self, cache: DeploymentCache) -> Self {
+        self.deployments = Some(cache);
+        self
+    }
+
+    /// How to attribute gas when one transaction makes several matching transfers
+    ///
+    /// Defaults to [`GasAttribution::Full`], which reports the full gas cost
+    /// on every transfer.
+    pub fn with_gas_attribution(mut self, policy: GasAttribution) -> Self {
+        self.gas_attribution = policy;
+        self
+    }
+
+    /// Only return transfers after `cursor` in chain order
+    ///
+    /// Scans start at the cursor's block instead of the start of the range,
+    /// and transfers at or before the cursor are skipped, so paging with
+    /// [`CombinedDataResult::next_cursor`] neither rescans earlier blocks nor
+    /// repeats transfers. Ranges ending before the cursor return an empty
+    /// result without scanning.
+    pub fn resume_after(mut self, cursor: TransferCursor) -> Self {
+        self.resume_after = Some(cursor);
+        self
+    }
+
+    /// Stop after `limit` transfers, setting
+    /// [`CombinedDataResult::page_limit_reached`]
+    ///
+    /// The rest of the range is left for the next page: pass the result's
+    /// [`next_cursor`](CombinedDataResult::next_cursor) to
+    /// [`resume_after`](Self::resume_after) to continue. Transfers whose
+    /// lookups fail count towards the limit. Pages are cut at a transfer
+    /// rather than a transaction, and gas is still shared between all of a
+    /// transaction's transfers under [`GasAttribution::SplitEvenly`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero, since a page without transfers never advances.
+    pub fn with_page_limit(mut self, limit: usize) -> Self {
+        assert!(limit > 0, "page limit must be at least one transfer");
+        self.page_limit = Some(limit);
+        self
+    }
+
+    /// Flag days of date-range results with `detector`
+    ///
+    /// Defaults to [`AnomalyDetector::default`]. See
+    /// [`DailyCombinedData::anomalies`].
+    pub fn with_anomaly_detector(mut self, detector: AnomalyDetector) -> Self {
+        self.anomaly_detector = detector;
+        self
+    }
+
+    /// Stop calculations between chunks once `token` is cancelled
+    ///
+    /// A cancelled calculation returns the transfers gathered so far with
+    /// [`CombinedDataResult::cancelled`] set, instead of an error.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Check whether the recipient receives what `Transfer` events report
+    ///
+    /// Works like [`AmountCalculator::with_transfer_tax`](crate::AmountCalculator::with_transfer_tax):
+    /// a few blocks with matched transfers are sampled and the ratio is
+    /// reported as [`CombinedDataResult::transfer_tax`]. With
+    /// [`TransferTaxMode::Adjust`], every transaction's transferred amount and
+    /// the total are scaled by it; gas costs are unaffected.
+    pub fn with_transfer_tax(mut self, mode: TransferTaxMode) -> Self {
+        self.transfer_tax = mode;
+        self
+    }
+
+    /// Number of blocks sampled for transfer tax detection (default 3)
+    pub fn with_transfer_tax_samples(mut self, samples: usize) -> Self {
+        self.transfer_tax_samples = samples;
+        self
+    }
+
+    /// Detect the transfer tax of `result`'s token and, under
+    /// [`TransferTaxMode::Adjust`], scale its transferred amounts
+    async fn apply_transfer_tax(
+        &self,
+        result: &mut CombinedDataResult,
+    ) -> Result<(), RetrievalError> {
+        if self.transfer_tax == TransferTaxMode::Ignore || result.transactions_data.is_empty() {
+            return Ok(());
+        }
+        let mut blocks: Vec<BlockNumber> = result
+            .transactions_data
+            .iter()
+            .map(|tx| tx.block_number)
+            .collect();
+        blocks.sort_unstable();
+        let samples = sample_blocks(&blocks, self.transfer_tax_samples);
+
+        let rpc_logs = RpcLogSource::<_, N>::new(self.provider.as_ref());
+        let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
+        result.transfer_tax = estimate_transfer_tax(
+            self.provider.as_ref(),
+            log_source,
+            result.token_address,
+            result.to_address,
+            &samples,
+        )
+        .await?;
+
+        let Some(tax) = result.transfer_tax else {
+            return Ok(());
+        };
+        if !tax.is_exact() {
+            warn!(
+                chain = %result.chain,
+                to = ?result.to_address,
+                token = ?result.token_address,
+                received_bps = tax.received_bps,
+                "Recipient received a different amount than transfer events report"
+            );
+        }
+        if self.transfer_tax == TransferTaxMode::Adjust {
+            let mut total = alloy_primitives::U256::ZERO;
+            for tx in &mut result.transactions_data {
+                tx.transferred_amount = tax
+                    .apply(TokenAmount::from(tx.transferred_amount))
+                    .as_u256();
+                total = total.saturating_add(tx.transferred_amount);
+            }
+            result.total_amount_transferred = total;
+        }
+        Ok(())
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
     fn process_lookup_results<A: ReceiptAdapter<N> + Send + Sync>(
         entry: LogBatchEntry,
         tx_result: Result<Option<TransactionGasData>, CombinedDataLookupFailure>,
@@ -307,8 +570,13 @@ where
         Ok(GasAndAmountForTx {
             tx_hash,
             block_number: entry.block_number,
+            block_timestamp: entry.block_timestamp,
+            tx_index: entry.tx_index,
+            log_index: entry.log_index,
+            token_address: Some(entry.token_address),
             gas_used: GasAmount::from(gas_used),
             effective_gas_price: GasPrice::from(effective_gas_price),
+            base_fee_per_gas: entry.base_fee_per_gas,
             l1_fee,
             transferred_amount: entry.transfer_value,
             blob_gas_cost,
@@ -322,11 +590,21 @@ where
         pass: CombinedDataLookupPass,
     ) -> Result<Option<TransactionGasData>, CombinedDataLookupFailure> {
         let tx_hash = entry.tx_hash;
+        let cache = self.transaction_cache.as_ref();
+        if let Some(transaction) = cache.and_then(|cache| cache.transaction(tx_hash)) {
+            return Ok(Some(TransactionGasData::from_transaction(&transaction)));
+        }
 
         match self.provider.get_transaction_by_hash(tx_hash).await {
-            Ok(transaction) => Ok(transaction
-                .as_ref()
-                .map(TransactionGasData::from_transaction)),
+            Ok(transaction) => {
+                let gas_data = transaction
+                    .as_ref()
+                    .map(TransactionGasData::from_transaction);
+                if let (Some(cache), Some(transaction)) = (cache, transaction) {
+                    cache.insert_transaction(tx_hash, transaction);
+                }
+                Ok(gas_data)
+            }
             Err(error) if should_attempt_permissive_tx_decode(chain, &error) => {
                 warn!(
                     ?chain,
@@ -410,7 +688,19 @@ where
     ) -> Result<GasAndAmountForTx, CombinedDataLookupFailure> {
         let provider = self.provider.clone();
         let tx_hash = entry.tx_hash;
-        let span = spans::process_log_for_combined_data(tx_hash);
+        let span = spans::process_log_for_combined_data(&self.config.spans, tx_hash);
+
+        let cache = self.transaction_cache.as_ref();
+        let fetch_receipt = async move {
+            if let Some(receipt) = cache.and_then(|cache| cache.receipt(tx_hash)) {
+                return Ok(Some(receipt));
+            }
+            let receipt = provider.get_transaction_receipt(tx_hash).await?;
+            if let (Some(cache), Some(receipt)) = (cache, &receipt) {
+                cache.insert_receipt(tx_hash, receipt.clone());
+            }
+            Ok::<_, TransportError>(receipt)
+        };
 
         // The serial fallback intentionally re-fetches both tx and receipt even if
         // only one side failed in the batch pass. That keeps the retry path simple
@@ -418,7 +708,7 @@ where
         let (tx_result, receipt_result) = async move {
             tokio::join!(
                 self.fetch_transaction_gas_data(chain, entry, pass),
-                provider.get_transaction_receipt(tx_hash)
+                fetch_receipt
             )
         }
         .instrument(span)
@@ -478,13 +768,21 @@ where
     async fn retry_failed_tx_data<A: ReceiptAdapter<N> + Send + Sync>(
         &self,
         chain: NamedChain,
+        token_address: Address,
+        base_fee_per_gas: Option<GasPrice>,
         mut failure: CombinedDataLookupFailure,
         max_attempts: usize,
         adapter: &A,
     ) -> (Result<GasAndAmountForTx, CombinedDataLookupFailure>, usize) {
+        // Logs are filtered by token, so the failed log came from `token_address`
         let entry = LogBatchEntry {
             tx_hash: failure.tx_hash,
             block_number: failure.block_number,
+            block_timestamp: failure.block_timestamp,
+            tx_index: failure.tx_index,
+            log_index: failure.log_index,
+            token_address,
+            base_fee_per_gas,
             transfer_value: failure.transfer_value,
         };
 
@@ -527,8 +825,10 @@ where
         from_block: BlockNumber,
         to_block: BlockNumber,
         adapter: &A,
+        deadline: Option<&OperationDeadline>,
     ) -> Result<CombinedDataResult, RetrievalError> {
         let span = spans::process_block_range_for_combined_data(
+            &self.config.spans,
             chain,
             from_address,
             to_address,
@@ -537,165 +837,446 @@ where
             to_block,
         );
         async {
-            let mut result =
-                CombinedDataResult::new(chain, from_address, to_address, token_address);
-            let mut current_block = from_block;
+            let mut results = self
+                .process_block_range_for_recipients(
+                    chain,
+                    from_address,
+                    &[to_address],
+                    token_address,
+                    from_block,
+                    to_block,
+                    adapter,
+                    deadline,
+                )
+                .await?;
+            Ok(results.remove(&to_address).unwrap_or_else(|| {
+                CombinedDataResult::new(chain, from_address, to_address, token_address)
+            }))
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Process a block range for `from_address`'s transfers to each of
+    /// `recipients`, fetching each chunk with one filter listing them all
+    ///
+    /// Each recipient's result is the one a scan of its transfers alone would
+    /// give: gas is shared only between a transaction's transfers to the same
+    /// recipient, and the page limit applies to each recipient separately. If
+    /// the deadline passes, the error carries the partial result of the
+    /// lowest-addressed recipient.
+    #[allow(clippy::too_many_arguments)]
+    async fn process_block_range_for_recipients<A: ReceiptAdapter<N> + Send + Sync>(
+        &self,
+        chain: NamedChain,
+        from_address: Address,
+        recipients: &[Address],
+        token_address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        adapter: &A,
+        deadline: Option<&OperationDeadline>,
+    ) -> Result<BTreeMap<Address, CombinedDataResult>, RetrievalError> {
+        let mut results: BTreeMap<Address, CombinedDataResult> = recipients
+            .iter()
+            .map(|&to_address| {
+                (
+                    to_address,
+                    CombinedDataResult::new(chain, from_address, to_address, token_address),
+                )
+            })
+            .collect();
+        if results.is_empty() {
+            return Ok(results);
+        }
+        let partial = |results: BTreeMap<Address, CombinedDataResult>| {
+            results
+                .into_values()
+                .next()
+                .expect("there is at least one recipient")
+        };
 
-            // Get config values for this chain
-            let max_block_range = self.config.get_max_block_range(chain);
-            let rate_limit = self.config.get_rate_limit_delay(chain);
-            let serial_lookup_fallback_attempts =
-                self.config.get_serial_lookup_fallback_attempts(chain);
+        // Get config values for this chain
+        let mut chunks = ChunkScheduler::new(&self.config, chain, from_block, to_block);
+        let rpc_logs = RpcLogSource::<_, N>::new(self.provider.as_ref());
+        let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
+        let mut pacer = self.config.pacer(chain);
+        let serial_lookup_fallback_attempts =
+            self.config.get_serial_lookup_fallback_attempts(chain);
+        let mut page_remaining: BTreeMap<Address, usize> = self
+            .page_limit
+            .map(|limit| {
+                results
+                    .keys()
+                    .map(|&to_address| (to_address, limit))
+                    .collect()
+            })
+            .unwrap_or_default();
 
-            while current_block <= to_block {
-                let chunk_end =
-                    std::cmp::min(current_block + max_block_range.as_u64() - 1, to_block);
+        while let Some((current_block, chunk_end)) = chunks.next_chunk() {
+            // Wait for a rate limit permit before the next chunk
+            pacer.wait().await;
 
-                let filter = GasCalculationCore::create_transfer_filter(
+            if self.is_cancelled() {
+                warn!(
+                    ?chain,
                     current_block,
-                    chunk_end,
-                    token_address,
-                    from_address,
-                    to_address,
+                    to_block,
+                    "Combined data calculation cancelled, returning partial result"
                 );
+                for result in results.values_mut() {
+                    result.cancelled = true;
+                }
+                break;
+            }
 
-                trace!(?filter, current_block, chunk_end, "Fetching logs");
-                let logs: Vec<RpcLog> = self.provider.get_logs(&filter).await.map_err(|e| {
-                    RetrievalError::Rpc(crate::errors::RpcError::get_logs_failed(
-                        format!(
-                            "get_logs for blocks {current_block}-{chunk_end} on {chain:?}"
-                        ),
-                        e,
-                    ))
-                })?;
-                trace!(
-                    logs_count = logs.len(),
-                    current_block,
-                    chunk_end,
-                    "Fetched logs"
-                );
+            if deadline.is_some_and(OperationDeadline::is_expired) {
+                return Err(deadline_exceeded(deadline, current_block, partial(results)));
+            }
 
-                // First pass: Decode all logs and collect entries for batch fetching
-                let mut log_entries = Vec::with_capacity(logs.len());
-                for rpc_log_entry in &logs {
-                    match Transfer::decode_log(&rpc_log_entry.inner) {
-                        Ok(transfer_event_data) => {
-                            let tx_hash = match rpc_log_entry.transaction_hash {
-                                Some(hash) => hash,
-                                None => {
-                                    error!("Missing transaction hash in log entry");
-                                    continue;
-                                }
-                            };
-                            let block_number = match rpc_log_entry.block_number {
-                                Some(num) => num,
-                                None => {
-                                    error!("Missing block number in log entry");
-                                    continue;
-                                }
-                            };
+            let filter = GasCalculationCore::create_transfers_to_filter(
+                current_block,
+                chunk_end,
+                token_address,
+                from_address,
+                recipients,
+            );
 
-                            info!(
-                                ?chain, ?from_address, ?to_address, ?token_address,
-                                amount = ?transfer_event_data.value,
-                                block = block_number,
+            trace!(?filter, current_block, chunk_end, "Fetching logs");
+            let Some(logs) = within_deadline(
+                deadline,
+                get_logs_bisecting(log_source, &filter, current_block, chunk_end),
+            )
+            .await
+            else {
+                return Err(deadline_exceeded(deadline, current_block, partial(results)));
+            };
+            let logs: Vec<RpcLog> = match logs {
+                Ok(logs) => logs,
+                Err(e) if chunks.chunk_failed() => {
+                    warn!(
+                        ?e,
+                        current_block,
+                        chunk_end,
+                        "Error fetching logs, retrying with a smaller chunk"
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    return Err(RetrievalError::Rpc(
+                        crate::errors::RpcError::get_logs_failed(
+                            format!("get_logs for blocks {current_block}-{chunk_end} on {chain:?}"),
+                            e,
+                        ),
+                    ));
+                }
+            };
+            let log_count = logs.len();
+            trace!(
+                logs_count = logs.len(),
+                current_block,
+                chunk_end,
+                "Fetched logs"
+            );
+
+            // First pass: Decode all logs and collect each recipient's entries for batch fetching
+            let mut log_entries: BTreeMap<Address, Vec<LogBatchEntry>> = BTreeMap::new();
+            // Transfers already returned before the resume cursor, which
+            // still count when sharing gas between a transaction's transfers
+            let mut resumed: BTreeMap<Address, Vec<(TxHash, Option<u64>)>> = BTreeMap::new();
+            for rpc_log_entry in &logs {
+                let recipient = rpc_log_entry
+                    .topics()
+                    .get(2)
+                    .map(|topic| Address::from_word(*topic));
+                let Some(result) = recipient.and_then(|to_address| results.get_mut(&to_address))
+                else {
+                    trace!(?rpc_log_entry.transaction_hash, "Skipping log outside the requested recipients");
+                    continue;
+                };
+                // A recipient whose page is full is done with this range
+                if result.page_limit_reached {
+                    continue;
+                }
+                let to_address = result.to_address;
+                match Transfer::decode_log(&rpc_log_entry.inner) {
+                    Ok(transfer_event_data) => {
+                        let tx_hash = match rpc_log_entry.transaction_hash {
+                            Some(hash) => hash,
+                            None => {
+                                error!("Missing transaction hash in log entry");
+                                self.config
+                                    .scan_policy
+                                    .record(
+                                        &mut result.warnings,
+                                        ScanWarning::skipped_log(
+                                            rpc_log_entry,
+                                            "missing transaction hash",
+                                        ),
+                                    )
+                                    .map_err(RetrievalError::skipped_data)?;
+                                continue;
+                            }
+                        };
+                        let block_number = match rpc_log_entry.block_number {
+                            Some(num) => num,
+                            None => {
+                                error!("Missing block number in log entry");
+                                self.config
+                                    .scan_policy
+                                    .record(
+                                        &mut result.warnings,
+                                        ScanWarning::skipped_log(
+                                            rpc_log_entry,
+                                            "missing block number",
+                                        ),
+                                    )
+                                    .map_err(RetrievalError::skipped_data)?;
+                                continue;
+                            }
+                        };
+
+                        if self.resume_after.is_some_and(|cursor| {
+                            cursor.covers(
+                                block_number,
+                                rpc_log_entry.transaction_index,
+                                rpc_log_entry.log_index,
+                            )
+                        }) {
+                            trace!(
                                 ?tx_hash,
-                                "Decoded Transfer event for batch processing"
+                                block_number,
+                                "Skipping transfer at or before resume cursor"
                             );
+                            resumed
+                                .entry(to_address)
+                                .or_default()
+                                .push((tx_hash, rpc_log_entry.log_index));
+                            continue;
+                        }
+
+                        info!(
+                            ?chain, ?from_address, ?to_address, ?token_address,
+                            amount = ?transfer_event_data.value,
+                            block = block_number,
+                            ?tx_hash,
+                            "Decoded Transfer event for batch processing"
+                        );
 
-                            log_entries.push(LogBatchEntry {
+                        log_entries
+                            .entry(to_address)
+                            .or_default()
+                            .push(LogBatchEntry {
                                 tx_hash,
                                 block_number,
+                                block_timestamp: rpc_log_entry.block_timestamp,
+                                tx_index: rpc_log_entry.transaction_index,
+                                log_index: rpc_log_entry.log_index,
+                                token_address: rpc_log_entry.address(),
+                                base_fee_per_gas: None,
                                 transfer_value: transfer_event_data.value,
                             });
-                        }
-                        Err(e) => {
-                            error!(error = %e, log_data = ?rpc_log_entry.data(), log_topics = ?rpc_log_entry.topics(), "Failed to decode Transfer log. Skipping log.");
-                            // Continue with other logs
-                        }
+                    }
+                    Err(e) => {
+                        error!(error = %e, log_data = ?rpc_log_entry.data(), log_topics = ?rpc_log_entry.topics(), "Failed to decode Transfer log. Skipping log.");
+                        self.config
+                            .scan_policy
+                            .record(
+                                &mut result.warnings,
+                                ScanWarning::skipped_log(rpc_log_entry, &e),
+                            )
+                            .map_err(RetrievalError::skipped_data)?;
+                        // Continue with other logs
                     }
                 }
+            }
 
-                // Second pass: Batch fetch all transaction and receipt data
-                let batch_results = self.batch_fetch_tx_data(chain, &log_entries, adapter).await;
-
-                // Process batch results
-                let mut batch_failures = Vec::new();
-                for batch_result in batch_results {
-                    match batch_result {
-                        Ok(data) => {
-                            result.add_transaction_data(data);
-                        }
-                        Err(failure) => {
-                            batch_failures.push(failure);
-                        }
-                    }
+            // Leave each recipient's transfers past the page limit for the next page
+            let mut next_page: BTreeMap<Address, Vec<LogBatchEntry>> = BTreeMap::new();
+            for (to_address, remaining) in &mut page_remaining {
+                let result = results
+                    .get_mut(to_address)
+                    .expect("page limits are kept for recipients");
+                if result.page_limit_reached {
+                    continue;
                 }
+                let entries = log_entries.entry(*to_address).or_default();
+                let mut rest = Vec::new();
+                if entries.len() > *remaining {
+                    rest = entries.split_off(*remaining);
+                }
+                *remaining -= entries.len();
+                result.page_limit_reached =
+                    *remaining == 0 && (!rest.is_empty() || chunk_end < to_block);
+                next_page.insert(*to_address, rest);
+            }
 
-                if !batch_failures.is_empty() {
-                    if serial_lookup_fallback_attempts == 0 {
-                        warn!(
-                            failed_lookups = batch_failures.len(),
-                            "Batch combined lookups failed and serial fallback is disabled for this chain"
-                        );
-                    } else {
-                        warn!(
-                            failed_lookups = batch_failures.len(),
-                            max_attempts_per_lookup = serial_lookup_fallback_attempts,
-                            "Retrying failed combined lookups serially after batch pass"
-                        );
-                    }
+            let capabilities = self.capabilities.resolve(&*self.provider).await;
+            let Some(base_fees) = within_deadline(
+                deadline,
+                get_base_fees(
+                    &self.provider,
+                    log_entries
+                        .values()
+                        .flatten()
+                        .map(|entry| entry.block_number),
+                    capabilities.as_ref(),
+                    self.header_cache.as_ref(),
+                ),
+            )
+            .await
+            else {
+                return Err(deadline_exceeded(deadline, current_block, partial(results)));
+            };
+            for (to_address, entries) in &mut log_entries {
+                let result = results
+                    .get_mut(to_address)
+                    .expect("entries are only collected for recipients");
+                for entry in entries {
+                    entry.base_fee_per_gas =
+                        base_fees.for_block(entry.block_number, &mut result.warnings);
                 }
+            }
 
-                // The fallback pass is intentionally sequential across failures to avoid
-                // reproducing the original burst pattern against the provider.
-                for batch_failure in batch_failures {
-                    let (retry_result, fallback_attempts) = self
-                        .retry_failed_tx_data(
-                            chain,
-                            batch_failure,
-                            serial_lookup_fallback_attempts,
-                            adapter,
-                        )
-                        .await;
-                    result
-                        .retrieval_metadata
-                        .record_fallback_attempts(fallback_attempts);
-
-                    match retry_result {
-                        Ok(data) => {
-                            result.retrieval_metadata.record_fallback_recovery();
-                            result.add_transaction_data(data);
-                        }
-                        Err(failure) => {
-                            log_combined_data_skip(
-                                &failure,
-                                chain,
-                                from_address,
-                                to_address,
-                                token_address,
-                                from_block,
-                                to_block,
-                            );
-                            result.retrieval_metadata.record_partial_failure(failure);
-                        }
+            // Second pass: Batch fetch all recipients' transaction and receipt data together
+            let (owners, batch): (Vec<Address>, Vec<LogBatchEntry>) = log_entries
+                .iter()
+                .flat_map(|(&to_address, entries)| {
+                    entries.iter().map(move |&entry| (to_address, entry))
+                })
+                .unzip();
+            let Some(batch_results) =
+                within_deadline(deadline, self.batch_fetch_tx_data(chain, &batch, adapter)).await
+            else {
+                return Err(deadline_exceeded(deadline, current_block, partial(results)));
+            };
+
+            // Share each transaction's gas between its transfers to the same
+            // recipient, counting transfers whose lookups fail below as well
+            let positions: BTreeMap<Address, _> = log_entries
+                .iter()
+                .map(|(&to_address, entries)| {
+                    let positions = GasAttribution::positions(
+                        resumed
+                            .remove(&to_address)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .chain(
+                                entries
+                                    .iter()
+                                    .chain(next_page.get(&to_address).into_iter().flatten())
+                                    .map(|entry| (entry.tx_hash, entry.log_index)),
+                            ),
+                    );
+                    (to_address, positions)
+                })
+                .collect();
+            let attribute = |to_address: Address, mut data: GasAndAmountForTx| {
+                if let Some(&(position, count)) = positions
+                    .get(&to_address)
+                    .and_then(|positions| positions.get(&data.dedup_key()))
+                {
+                    data.attribute_gas(self.gas_attribution, position, count);
+                }
+                data
+            };
+
+            // Process batch results
+            let mut batch_failures = Vec::new();
+            for (to_address, batch_result) in owners.into_iter().zip(batch_results) {
+                let result = results
+                    .get_mut(&to_address)
+                    .expect("entries are only collected for recipients");
+                match batch_result {
+                    Ok(data) => {
+                        result.add_transaction_data(attribute(to_address, data));
+                    }
+                    Err(failure) => {
+                        batch_failures.push((to_address, failure));
                     }
                 }
+            }
 
-                current_block = chunk_end + 1;
+            if !batch_failures.is_empty() {
+                if serial_lookup_fallback_attempts == 0 {
+                    warn!(
+                        failed_lookups = batch_failures.len(),
+                        "Batch combined lookups failed and serial fallback is disabled for this chain"
+                    );
+                } else {
+                    warn!(
+                        failed_lookups = batch_failures.len(),
+                        max_attempts_per_lookup = serial_lookup_fallback_attempts,
+                        "Retrying failed combined lookups serially after batch pass"
+                    );
+                }
+            }
 
-                // Apply rate limiting if configured for this chain
-                if let Some(delay) = rate_limit {
-                    if current_block <= to_block {
-                        trace!(?chain, ?delay, "Applying rate limit delay");
-                        sleep(delay).await;
+            // The fallback pass is intentionally sequential across failures to avoid
+            // reproducing the original burst pattern against the provider.
+            for (to_address, batch_failure) in batch_failures {
+                let (retry_result, fallback_attempts) = self
+                    .retry_failed_tx_data(
+                        chain,
+                        token_address,
+                        base_fees.fees.get(&batch_failure.block_number).copied(),
+                        batch_failure,
+                        serial_lookup_fallback_attempts,
+                        adapter,
+                    )
+                    .await;
+                let result = results
+                    .get_mut(&to_address)
+                    .expect("entries are only collected for recipients");
+                result
+                    .retrieval_metadata
+                    .record_fallback_attempts(fallback_attempts);
+
+                match retry_result {
+                    Ok(data) => {
+                        result.retrieval_metadata.record_fallback_recovery();
+                        result.add_transaction_data(attribute(to_address, data));
+                    }
+                    Err(failure) => {
+                        log_combined_data_skip(
+                            &failure,
+                            chain,
+                            from_address,
+                            to_address,
+                            token_address,
+                            from_block,
+                            to_block,
+                        );
+                        let reason = failure
+                            .final_attempt()
+                            .map(|attempt| attempt.error.clone())
+                            .unwrap_or_default();
+                        self.config
+                            .scan_policy
+                            .record(
+                                &mut result.warnings,
+                                ScanWarning::failed_receipt(
+                                    failure.tx_hash,
+                                    Some(failure.block_number),
+                                    reason,
+                                ),
+                            )
+                            .map_err(RetrievalError::skipped_data)?;
+                        result.retrieval_metadata.record_partial_failure(failure);
                     }
                 }
             }
+
+            chunks.chunk_succeeded(log_count);
+            if results.values().all(|result| result.page_limit_reached) {
+                break;
+            }
+        }
+        for result in results.values() {
             info!(
                 ?chain,
                 %from_address,
-                %to_address,
+                to_address = %result.to_address,
                 %token_address,
                 from_block,
                 to_block,
@@ -703,28 +1284,75 @@ where
                 skipped_logs = result.retrieval_metadata.skipped_logs,
                 fallback_attempts = result.retrieval_metadata.fallback_attempts,
                 fallback_recovered = result.retrieval_metadata.fallback_recovered,
+                cancelled = result.cancelled,
+                page_limit_reached = result.page_limit_reached,
                 "Finished processing block range"
             );
-            Ok(result)
         }
-        .instrument(span)
-        .await
+        Ok(results)
     }
 
-    /// Calculates combined transfer amount and gas cost data.
-    /// Caching is not implemented in this version but can be added by adapting GasCostCache logic.
-    #[allow(clippy::too_many_arguments)]
-    pub async fn calculate_combined_data_with_adapter<A: ReceiptAdapter<N> + Send + Sync>(
+    /// The first block to scan for `token_address` transfers in the range
+    ///
+    /// Skips blocks before the token's deployment and at or before the
+    /// resume cursor's block; `None` if that leaves nothing to scan.
+    async fn scan_start(
         &self,
         chain: NamedChain,
-        from_address: Address,
-        to_address: Address,
         token_address: Address,
         from_block: BlockNumber,
         to_block: BlockNumber,
+    ) -> Result<Option<BlockNumber>, RetrievalError> {
+        let from_block = match &self.deployments {
+            Some(deployments) => {
+                let clamped = deployments
+                    .clamp_start(
+                        self.provider.as_ref(),
+                        chain,
+                        &[token_address],
+                        from_block,
+                        to_block,
+                    )
+                    .await?;
+                let Some(from_block) = clamped else {
+                    return Ok(None);
+                };
+                from_block
+            }
+            None => from_block,
+        };
+        let from_block = self
+            .resume_after
+            .map_or(from_block, |cursor| from_block.max(cursor.block_number));
+        Ok((from_block <= to_block).then_some(from_block))
+    }
+
+    /// Calculates combined transfer amount and gas cost data for `query`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RetrievalError::DeadlineExceeded`] with the result gathered so far
+    /// if the chain's [`operation_deadline`](SemioscanConfig::operation_deadline) passes.
+    pub async fn calculate_with_adapter<A: ReceiptAdapter<N> + Send + Sync>(
+        &self,
+        query: &CombinedQuery,
         adapter: &A,
     ) -> Result<CombinedDataResult, RetrievalError> {
+        let CombinedQuery {
+            chain,
+            from_address,
+            to_address,
+            token_address,
+            from_block,
+            to_block,
+        } = *query;
+        let (from_address, to_address, token_address) = (
+            from_address.as_address(),
+            to_address.as_address(),
+            token_address.as_address(),
+        );
         let span = spans::calculate_combined_data_with_adapter(
+            &self.config.spans,
             chain,
             from_address,
             to_address,
@@ -733,7 +1361,20 @@ where
             to_block,
         );
         async {
-            let result = self
+            let Some(from_block) = self
+                .scan_start(chain, token_address, from_block, to_block)
+                .await?
+            else {
+                return Ok(CombinedDataResult::new(
+                    chain,
+                    from_address,
+                    to_address,
+                    token_address,
+                ));
+            };
+
+            let deadline = OperationDeadline::start(self.config.get_operation_deadline(chain));
+            let mut result = self
                 .process_block_range_for_combined_data(
                     chain,
                     from_address,
@@ -742,26 +1383,31 @@ where
                     from_block,
                     to_block,
                     adapter,
+                    deadline.as_ref(),
                 )
                 .await?;
+            if !result.cancelled {
+                self.apply_transfer_tax(&mut result).await?;
+            }
 
             Ok(result)
         }
         .instrument(span)
         .await
     }
-}
-
-// Network-specific public methods
 
-impl<P: Provider<Ethereum> + Send + Sync + Clone + 'static> CombinedCalculator<Ethereum, P>
-where
-    <Ethereum as Network>::TransactionResponse:
-        TransactionTrait + alloy_provider::network::eip2718::Typed2718 + Send + Sync + Clone,
-    <Ethereum as Network>::ReceiptResponse: Send + Sync + std::fmt::Debug + Clone,
-{
+    /// Calculates combined transfer amount and gas cost data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RetrievalError::DeadlineExceeded`] with the result gathered so far
+    /// if the chain's [`operation_deadline`](SemioscanConfig::operation_deadline) passes.
+    #[deprecated(
+        since = "0.13.0",
+        note = "Use calculate_with_adapter with a CombinedQuery, which names each argument"
+    )]
     #[allow(clippy::too_many_arguments)]
-    pub async fn calculate_combined_data_ethereum(
+    pub async fn calculate_combined_data_with_adapter<A: ReceiptAdapter<N> + Send + Sync>(
         &self,
         chain: NamedChain,
         from_address: Address,
@@ -769,54 +1415,471 @@ where
         token_address: Address,
         from_block: BlockNumber,
         to_block: BlockNumber,
+        adapter: &A,
     ) -> Result<CombinedDataResult, RetrievalError> {
-        let adapter = EthereumReceiptAdapter;
-        self.calculate_combined_data_with_adapter(
+        let query = CombinedQuery {
             chain,
-            from_address,
-            to_address,
-            token_address,
+            from_address: from_address.into(),
+            to_address: to_address.into(),
+            token_address: token_address.into(),
             from_block,
             to_block,
-            &adapter,
-        )
-        .await
+        };
+        self.calculate_with_adapter(&query, adapter).await
     }
-}
 
-impl<P: Provider<Optimism> + Send + Sync + Clone + 'static> CombinedCalculator<Optimism, P>
-where
-    <Optimism as Network>::TransactionResponse:
-        TransactionTrait + alloy_provider::network::eip2718::Typed2718 + Send + Sync + Clone,
-    <Optimism as Network>::ReceiptResponse: Send + Sync + std::fmt::Debug + Clone,
-{
-    #[allow(clippy::too_many_arguments)]
-    pub async fn calculate_combined_data_optimism(
+    /// Discovers every recipient of `from_address`'s `token_address`
+    /// transfers in the range, then calculates combined data for each
+    ///
+    /// The first pass lists the distinct recipients with
+    /// [`discover_recipients`](crate::discover_recipients), reading logs from
+    /// the calculator's [log source](Self::with_log_source); the second scans
+    /// the range once with a filter listing them all, and attributes each
+    /// transfer to its recipient. Each result matches what
+    /// [`calculate_with_adapter`](Self::calculate_with_adapter) returns for
+    /// that recipient alone. Results are keyed by recipient. If the
+    /// calculator is cancelled, every recipient's result is included with
+    /// [`CombinedDataResult::cancelled`] set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first discovery or retrieval error. If the chain's
+    /// [`operation_deadline`](SemioscanConfig::operation_deadline) passes,
+    /// [`RetrievalError::DeadlineExceeded`] carries the partial result of the
+    /// lowest-addressed recipient.
+    pub async fn calculate_for_counterparties_with_adapter<A: ReceiptAdapter<N> + Send + Sync>(
         &self,
         chain: NamedChain,
-        from_address: Address,
-        to_address: Address,
-        token_address: Address,
+        from_address: impl Into<SenderAddress>,
+        token_address: impl Into<TokenAddress>,
         from_block: BlockNumber,
         to_block: BlockNumber,
-    ) -> Result<CombinedDataResult, RetrievalError> {
-        let adapter = OptimismReceiptAdapter;
-        self.calculate_combined_data_with_adapter(
+        adapter: &A,
+    ) -> Result<BTreeMap<Address, CombinedDataResult>, RetrievalError> {
+        let from_address = from_address.into();
+        let token_address = token_address.into();
+
+        let rpc_logs = RpcLogSource::<_, N>::new(self.provider.as_ref());
+        let log_source: &dyn LogSource = self.log_source.as_deref().unwrap_or(&rpc_logs);
+        let recipients: Vec<Address> = discover_recipients_from_source(
+            log_source,
+            &self.config,
             chain,
-            from_address,
-            to_address,
             token_address,
+            from_address,
             from_block,
             to_block,
-            &adapter,
         )
-        .await
+        .await?
+        .into_iter()
+        .map(Address::from)
+        .collect();
+
+        let (from_address, token_address) = (from_address.as_address(), token_address.as_address());
+        let Some(scan_from) = self
+            .scan_start(chain, token_address, from_block, to_block)
+            .await?
+        else {
+            return Ok(recipients
+                .into_iter()
+                .map(|to_address| {
+                    (
+                        to_address,
+                        CombinedDataResult::new(chain, from_address, to_address, token_address),
+                    )
+                })
+                .collect());
+        };
+
+        let deadline = OperationDeadline::start(self.config.get_operation_deadline(chain));
+        let mut results = self
+            .process_block_range_for_recipients(
+                chain,
+                from_address,
+                &recipients,
+                token_address,
+                scan_from,
+                to_block,
+                adapter,
+                deadline.as_ref(),
+            )
+            .await?;
+        for result in results.values_mut() {
+            if !result.cancelled {
+                self.apply_transfer_tax(result).await?;
+            }
+        }
+        Ok(results)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Calculates combined data for `query`'s transfers on each UTC day in
+    /// `dates`
+    ///
+    /// The query's block range is replaced by each day's window, which
+    /// `windows` maps from the date, so repeated runs over overlapping dates
+    /// reuse the cached daily windows instead of repeating the binary
+    /// searches. Days are processed in order; if the calculator is cancelled,
+    /// the day in progress is included (with [`CombinedDataResult::cancelled`]
+    /// set) and later days are skipped. No days are returned when `dates` is
+    /// empty.
+    ///
+    /// Days deviating from the days before them are listed in
+    /// [`DailyCombinedData::anomalies`], as judged by the calculator's
+    /// [anomaly detector](Self::with_anomaly_detector).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first block window or retrieval error; results for earlier
+    /// days are discarded.
+    pub async fn calculate_for_dates_with_adapter<W, A>(
+        &self,
+        query: &CombinedQuery,
+        dates: RangeInclusive<NaiveDate>,
+        windows: &BlockWindowCalculator<W>,
+        adapter: &A,
+    ) -> Result<DailyCombinedData, SemioscanError>
+    where
+        W: Provider,
+        A: ReceiptAdapter<N> + Send + Sync,
+    {
+        let chain = query.chain;
+        let (start_date, end_date) = dates.into_inner();
+        let mut results = BTreeMap::new();
+
+        for date in start_date.iter_days().take_while(|date| *date <= end_date) {
+            let window = windows.get_daily_window(chain, date).await?;
+            let day_query = query.with_blocks(window.start_block..=window.end_block);
+            let result = self.calculate_with_adapter(&day_query, adapter).await?;
+
+            let cancelled = result.cancelled;
+            results.insert(date, result);
+            if cancelled {
+                info!(chain = %chain, %date, "Cancelled, skipping remaining dates");
+                break;
+            }
+        }
+
+        let anomalies = self.anomaly_detector.detect(&results);
+        if !anomalies.is_empty() {
+            warn!(chain = %chain, count = anomalies.len(), "Anomalous days detected");
+        }
+        Ok(DailyCombinedData {
+            days: results,
+            anomalies,
+        })
+    }
+
+    /// Calculates combined data for each UTC day from `start_date` to `end_date` inclusive
+    ///
+    /// See [`calculate_for_dates_with_adapter`](Self::calculate_for_dates_with_adapter).
+    #[deprecated(
+        since = "0.13.0",
+        note = "Use calculate_for_dates_with_adapter with a CombinedQuery, which names each argument"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_combined_data_for_dates_with_adapter<W, A>(
+        &self,
+        chain: NamedChain,
+        from_address: impl Into<SenderAddress>,
+        to_address: impl Into<RecipientAddress>,
+        token_address: impl Into<TokenAddress>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        windows: &BlockWindowCalculator<W>,
+        adapter: &A,
+    ) -> Result<DailyCombinedData, SemioscanError>
+    where
+        W: Provider,
+        A: ReceiptAdapter<N> + Send + Sync,
+    {
+        let query = dateless_query(chain, from_address, to_address, token_address);
+        self.calculate_for_dates_with_adapter(&query, start_date..=end_date, windows, adapter)
+            .await
+    }
+}
+
+/// A query for the positional daily methods, whose blocks are replaced per day
+fn dateless_query(
+    chain: NamedChain,
+    from_address: impl Into<SenderAddress>,
+    to_address: impl Into<RecipientAddress>,
+    token_address: impl Into<TokenAddress>,
+) -> CombinedQuery {
+    CombinedQuery {
+        chain,
+        from_address: from_address.into(),
+        to_address: to_address.into(),
+        token_address: token_address.into(),
+        from_block: 0,
+        to_block: 0,
+    }
+}
+
+// Network-specific public methods
+
+impl<P: Provider<Ethereum> + Send + Sync + Clone + 'static> CombinedCalculator<Ethereum, P>
+where
+    <Ethereum as Network>::TransactionResponse:
+        TransactionTrait + alloy_provider::network::eip2718::Typed2718 + Send + Sync + Clone,
+    <Ethereum as Network>::ReceiptResponse: Send + Sync + std::fmt::Debug + Clone,
+{
+    /// Calculates combined transfer amount and gas cost data for `query`
+    ///
+    /// See [`calculate_with_adapter`](Self::calculate_with_adapter).
+    pub async fn calculate(
+        &self,
+        query: &CombinedQuery,
+    ) -> Result<CombinedDataResult, RetrievalError> {
+        self.calculate_with_adapter(query, &EthereumReceiptAdapter)
+            .await
+    }
+
+    /// Discovers the recipients of `from_address`'s transfers, then
+    /// calculates combined data for each
+    ///
+    /// See [`calculate_for_counterparties_with_adapter`](Self::calculate_for_counterparties_with_adapter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let by_counterparty = calculator
+    ///     .calculate_for_counterparties(chain, hot_wallet, usdc, from_block, to_block)
+    ///     .await?;
+    /// for (recipient, result) in &by_counterparty {
+    ///     println!(
+    ///         "{recipient}: {} transferred, {} wei of gas",
+    ///         result.total_amount_transferred, result.overall_total_gas_cost
+    ///     );
+    /// }
+    /// ```
+    pub async fn calculate_for_counterparties(
+        &self,
+        chain: NamedChain,
+        from_address: impl Into<SenderAddress>,
+        token_address: impl Into<TokenAddress>,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<BTreeMap<Address, CombinedDataResult>, RetrievalError> {
+        self.calculate_for_counterparties_with_adapter(
+            chain,
+            from_address,
+            token_address,
+            from_block,
+            to_block,
+            &EthereumReceiptAdapter,
+        )
+        .await
+    }
+
+    #[deprecated(
+        since = "0.13.0",
+        note = "Use calculate with a CombinedQuery, which names each argument"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_combined_data_ethereum(
+        &self,
+        chain: NamedChain,
+        from_address: Address,
+        to_address: Address,
+        token_address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<CombinedDataResult, RetrievalError> {
+        let query = CombinedQuery {
+            chain,
+            from_address: from_address.into(),
+            to_address: to_address.into(),
+            token_address: token_address.into(),
+            from_block,
+            to_block,
+        };
+        self.calculate(&query).await
+    }
+
+    /// Calculates combined data for `query`'s transfers on each UTC day in
+    /// `dates`
+    ///
+    /// See [`calculate_for_dates_with_adapter`](Self::calculate_for_dates_with_adapter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let windows = BlockWindowCalculator::with_disk_cache(provider.clone(), "windows.json")?;
+    /// let query = CombinedQuery { chain, from_address, to_address, token_address, from_block: 0, to_block: 0 };
+    /// let by_day = calculator
+    ///     .calculate_for_dates(&query, start_date..=end_date, &windows)
+    ///     .await?;
+    /// for (date, result) in &by_day.days {
+    ///     println!("{date}: {} transfers", result.transaction_count);
+    /// }
+    /// ```
+    pub async fn calculate_for_dates<W: Provider>(
+        &self,
+        query: &CombinedQuery,
+        dates: RangeInclusive<NaiveDate>,
+        windows: &BlockWindowCalculator<W>,
+    ) -> Result<DailyCombinedData, SemioscanError> {
+        self.calculate_for_dates_with_adapter(query, dates, windows, &EthereumReceiptAdapter)
+            .await
+    }
+
+    /// Calculates combined data for each UTC day from `start_date` to `end_date` inclusive
+    ///
+    /// See [`calculate_for_dates`](Self::calculate_for_dates).
+    #[deprecated(
+        since = "0.13.0",
+        note = "Use calculate_for_dates with a CombinedQuery, which names each argument"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_combined_data_for_dates<W: Provider>(
+        &self,
+        chain: NamedChain,
+        from_address: impl Into<SenderAddress>,
+        to_address: impl Into<RecipientAddress>,
+        token_address: impl Into<TokenAddress>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        windows: &BlockWindowCalculator<W>,
+    ) -> Result<DailyCombinedData, SemioscanError> {
+        let query = dateless_query(chain, from_address, to_address, token_address);
+        self.calculate_for_dates(&query, start_date..=end_date, windows)
+            .await
+    }
+}
+
+impl<P: Provider<Optimism> + Send + Sync + Clone + 'static> CombinedCalculator<Optimism, P>
+where
+    <Optimism as Network>::TransactionResponse:
+        TransactionTrait + alloy_provider::network::eip2718::Typed2718 + Send + Sync + Clone,
+    <Optimism as Network>::ReceiptResponse: Send + Sync + std::fmt::Debug + Clone,
+{
+    /// Calculates combined transfer amount and gas cost data for `query`
+    ///
+    /// See [`calculate_with_adapter`](Self::calculate_with_adapter).
+    pub async fn calculate(
+        &self,
+        query: &CombinedQuery,
+    ) -> Result<CombinedDataResult, RetrievalError> {
+        self.calculate_with_adapter(query, &OptimismReceiptAdapter)
+            .await
+    }
+
+    /// Discovers the recipients of `from_address`'s transfers, then
+    /// calculates combined data for each
+    ///
+    /// See [`calculate_for_counterparties_with_adapter`](Self::calculate_for_counterparties_with_adapter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let by_counterparty = calculator
+    ///     .calculate_for_counterparties(chain, hot_wallet, usdc, from_block, to_block)
+    ///     .await?;
+    /// for (recipient, result) in &by_counterparty {
+    ///     println!(
+    ///         "{recipient}: {} transferred, {} wei of gas",
+    ///         result.total_amount_transferred, result.overall_total_gas_cost
+    ///     );
+    /// }
+    /// ```
+    pub async fn calculate_for_counterparties(
+        &self,
+        chain: NamedChain,
+        from_address: impl Into<SenderAddress>,
+        token_address: impl Into<TokenAddress>,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<BTreeMap<Address, CombinedDataResult>, RetrievalError> {
+        self.calculate_for_counterparties_with_adapter(
+            chain,
+            from_address,
+            token_address,
+            from_block,
+            to_block,
+            &OptimismReceiptAdapter,
+        )
+        .await
+    }
+
+    #[deprecated(
+        since = "0.13.0",
+        note = "Use calculate with a CombinedQuery, which names each argument"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_combined_data_optimism(
+        &self,
+        chain: NamedChain,
+        from_address: Address,
+        to_address: Address,
+        token_address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<CombinedDataResult, RetrievalError> {
+        let query = CombinedQuery {
+            chain,
+            from_address: from_address.into(),
+            to_address: to_address.into(),
+            token_address: token_address.into(),
+            from_block,
+            to_block,
+        };
+        self.calculate(&query).await
+    }
+
+    /// Calculates combined data for `query`'s transfers on each UTC day in
+    /// `dates`
+    ///
+    /// See [`calculate_for_dates_with_adapter`](Self::calculate_for_dates_with_adapter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let windows = BlockWindowCalculator::with_disk_cache(provider.clone(), "windows.json")?;
+    /// let query = CombinedQuery { chain, from_address, to_address, token_address, from_block: 0, to_block: 0 };
+    /// let by_day = calculator
+    ///     .calculate_for_dates(&query, start_date..=end_date, &windows)
+    ///     .await?;
+    /// for (date, result) in &by_day.days {
+    ///     println!("{date}: {} transfers", result.transaction_count);
+    /// }
+    /// ```
+    pub async fn calculate_for_dates<W: Provider>(
+        &self,
+        query: &CombinedQuery,
+        dates: RangeInclusive<NaiveDate>,
+        windows: &BlockWindowCalculator<W>,
+    ) -> Result<DailyCombinedData, SemioscanError> {
+        self.calculate_for_dates_with_adapter(query, dates, windows, &OptimismReceiptAdapter)
+            .await
+    }
+
+    /// Calculates combined data for each UTC day from `start_date` to `end_date` inclusive
+    ///
+    /// See [`calculate_for_dates`](Self::calculate_for_dates).
+    #[deprecated(
+        since = "0.13.0",
+        note = "Use calculate_for_dates with a CombinedQuery, which names each argument"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn calculate_combined_data_for_dates<W: Provider>(
+        &self,
+        chain: NamedChain,
+        from_address: impl Into<SenderAddress>,
+        to_address: impl Into<RecipientAddress>,
+        token_address: impl Into<TokenAddress>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        windows: &BlockWindowCalculator<W>,
+    ) -> Result<DailyCombinedData, SemioscanError> {
+        let query = dateless_query(chain, from_address, to_address, token_address);
+        self.calculate_for_dates(&query, start_date..=end_date, windows)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
     use alloy_json_rpc as j;
     use alloy_network::Network;
     use alloy_primitives::{address, Address, LogData, B256, U256};
@@ -830,9 +1893,10 @@ mod tests {
         collections::{HashMap, VecDeque},
         sync::{Arc, Mutex},
         task::{Context, Poll},
+        time::Duration,
     };
 
-    use crate::SemioscanConfigBuilder;
+    use crate::{ScanPolicy, SemioscanConfigBuilder};
 
     #[derive(Clone, Debug, Default)]
     struct MethodResponseTransport {
@@ -1108,14 +2172,14 @@ mod tests {
 
         let calculator = create_calculator(transport.clone());
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                68_854_738,
-                68_854_738,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 68_854_738,
+                to_block: 68_854_738,
+            })
             .await
             .expect("combined calculation should return partial metadata instead of erroring");
 
@@ -1222,14 +2286,14 @@ mod tests {
 
         let calculator = create_calculator(transport.clone());
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                42,
-                42,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 42,
+                to_block: 42,
+            })
             .await
             .expect("combined calculation should succeed");
 
@@ -1245,6 +2309,279 @@ mod tests {
         assert_eq!(transport.request_count("eth_getTransactionReceipt"), 1);
     }
 
+    #[tokio::test]
+    async fn counterparties_are_discovered_then_attributed_from_one_scan() {
+        let transport = MethodResponseTransport::default();
+        let from_address = address!("0xa111111111111111111111111111111111111111");
+        let token_address = address!("0xc333333333333333333333333333333333333333");
+        let recipients = [
+            address!("0xb222222222222222222222222222222222222222"),
+            address!("0xb444444444444444444444444444444444444444"),
+        ];
+        let transfers: Vec<_> = recipients
+            .iter()
+            .enumerate()
+            .map(|(i, &recipient)| {
+                let tx_hash = TxHash::from(B256::repeat_byte(0x10 + i as u8));
+                let log = create_transfer_log(
+                    tx_hash,
+                    42,
+                    token_address,
+                    from_address,
+                    recipient,
+                    U256::from(100 * (i as u64 + 1)),
+                );
+                (tx_hash, recipient, log)
+            })
+            .collect();
+
+        // Discovery sees both transfers, then one scan filtered to both
+        // recipients attributes each transfer to its own
+        let logs: Vec<_> = transfers.iter().map(|(_, _, log)| log.clone()).collect();
+        transport.push_success("eth_getLogs", &logs);
+        transport.push_success("eth_getLogs", &logs);
+        for (tx_hash, recipient, _) in &transfers {
+            transport.push_success(
+                "eth_getTransactionByHash",
+                &Some(create_test_transaction(*tx_hash, from_address, *recipient)),
+            );
+            transport.push_success(
+                "eth_getTransactionReceipt",
+                &Some(create_test_receipt(
+                    *tx_hash,
+                    from_address,
+                    *recipient,
+                    21_000,
+                    100,
+                )),
+            );
+        }
+
+        let results = create_calculator(transport.clone())
+            .calculate_for_counterparties(NamedChain::Mainnet, from_address, token_address, 42, 42)
+            .await
+            .expect("counterparty calculation should succeed");
+
+        assert_eq!(results.keys().copied().collect::<Vec<_>>(), recipients);
+        assert_eq!(
+            results[&recipients[1]].total_amount_transferred,
+            U256::from(200)
+        );
+        assert_eq!(
+            results[&recipients[0]].total_amount_transferred,
+            U256::from(100)
+        );
+        assert_eq!(results[&recipients[0]].transaction_count.as_usize(), 1);
+        assert_eq!(results[&recipients[1]].transaction_count.as_usize(), 1);
+        assert_eq!(transport.request_count("eth_getLogs"), 2);
+    }
+
+    #[tokio::test]
+    async fn shared_transaction_cache_skips_repeat_lookups() {
+        let transport = MethodResponseTransport::default();
+        let from_address = address!("0xa111111111111111111111111111111111111111");
+        let to_address = address!("0xb222222222222222222222222222222222222222");
+        let token_address = address!("0xc333333333333333333333333333333333333333");
+        let tx_hash = TxHash::from(B256::repeat_byte(0x10));
+        let log = create_transfer_log(
+            tx_hash,
+            42,
+            token_address,
+            from_address,
+            to_address,
+            U256::from(1_234_u64),
+        );
+
+        transport.push_success("eth_getLogs", &vec![log.clone()]);
+        transport.push_success("eth_getLogs", &vec![log]);
+        transport.push_success(
+            "eth_getTransactionByHash",
+            &Some(create_test_transaction(tx_hash, from_address, to_address)),
+        );
+        transport.push_success(
+            "eth_getTransactionReceipt",
+            &Some(create_test_receipt(
+                tx_hash,
+                from_address,
+                to_address,
+                21_000,
+                100,
+            )),
+        );
+
+        let cache = TransactionCache::new();
+        let query = CombinedQuery {
+            chain: NamedChain::Mainnet,
+            from_address: from_address.into(),
+            to_address: to_address.into(),
+            token_address: token_address.into(),
+            from_block: 42,
+            to_block: 42,
+        };
+        for _ in 0..2 {
+            let result = create_calculator(transport.clone())
+                .with_transaction_cache(cache.clone())
+                .calculate(&query)
+                .await
+                .expect("combined calculation should succeed");
+            assert_eq!(result.transactions_data.len(), 1);
+        }
+
+        assert_eq!(transport.request_count("eth_getTransactionByHash"), 1);
+        assert_eq!(transport.request_count("eth_getTransactionReceipt"), 1);
+        assert_eq!(cache.stats().hits, 2);
+    }
+
+    #[tokio::test]
+    async fn page_limit_stops_after_limit_transfers_and_resumes() {
+        let transport = MethodResponseTransport::default();
+        let chain = NamedChain::Mainnet;
+        let from_address = address!("0xa111111111111111111111111111111111111111");
+        let to_address = address!("0xb222222222222222222222222222222222222222");
+        let token_address = address!("0xc333333333333333333333333333333333333333");
+        let first = TxHash::from(B256::repeat_byte(0x10));
+        let second = TxHash::from(B256::repeat_byte(0x20));
+        let transfer = |tx_hash, tx_index| RpcLog {
+            transaction_index: Some(tx_index),
+            log_index: Some(tx_index),
+            ..create_transfer_log(
+                tx_hash,
+                42,
+                token_address,
+                from_address,
+                to_address,
+                U256::from(1_u64),
+            )
+        };
+        let query = CombinedQuery {
+            chain,
+            from_address: from_address.into(),
+            to_address: to_address.into(),
+            token_address: token_address.into(),
+            from_block: 10,
+            to_block: 42,
+        };
+
+        for tx_hash in [first, second] {
+            transport.push_success(
+                "eth_getLogs",
+                &vec![transfer(first, 0), transfer(second, 1)],
+            );
+            transport.push_success(
+                "eth_getTransactionByHash",
+                &Some(create_test_transaction(tx_hash, from_address, to_address)),
+            );
+            transport.push_success(
+                "eth_getTransactionReceipt",
+                &Some(create_test_receipt(
+                    tx_hash,
+                    from_address,
+                    to_address,
+                    21_000,
+                    100,
+                )),
+            );
+        }
+
+        let page = create_calculator(transport.clone())
+            .with_page_limit(1)
+            .calculate(&query)
+            .await
+            .expect("combined calculation should succeed");
+        assert_eq!(page.transactions_data.len(), 1);
+        assert_eq!(page.transactions_data[0].tx_hash, first);
+        assert!(page.page_limit_reached);
+        assert_eq!(transport.request_count("eth_getTransactionByHash"), 1);
+
+        let cursor = page.next_cursor().expect("page has a cursor");
+        let page = create_calculator(transport.clone())
+            .with_page_limit(1)
+            .resume_after(cursor)
+            .calculate(&query)
+            .await
+            .expect("combined calculation should succeed");
+        assert_eq!(page.transactions_data.len(), 1);
+        assert_eq!(page.transactions_data[0].tx_hash, second);
+        assert!(!page.page_limit_reached);
+        assert_eq!(transport.request_count("eth_getTransactionByHash"), 2);
+    }
+
+    #[tokio::test]
+    async fn resume_after_skips_transfers_up_to_the_cursor() {
+        let transport = MethodResponseTransport::default();
+        let chain = NamedChain::Mainnet;
+        let from_address = address!("0xa111111111111111111111111111111111111111");
+        let to_address = address!("0xb222222222222222222222222222222222222222");
+        let token_address = address!("0xc333333333333333333333333333333333333333");
+        let returned = TxHash::from(B256::repeat_byte(0x10));
+        let next = TxHash::from(B256::repeat_byte(0x20));
+        let transfer = |tx_hash, tx_index, log_index| RpcLog {
+            transaction_index: Some(tx_index),
+            log_index: Some(log_index),
+            ..create_transfer_log(
+                tx_hash,
+                42,
+                token_address,
+                from_address,
+                to_address,
+                U256::from(1_u64),
+            )
+        };
+
+        transport.push_success(
+            "eth_getLogs",
+            &vec![transfer(returned, 0, 0), transfer(next, 1, 3)],
+        );
+        transport.push_success(
+            "eth_getTransactionByHash",
+            &Some(create_test_transaction(next, from_address, to_address)),
+        );
+        transport.push_success(
+            "eth_getTransactionReceipt",
+            &Some(create_test_receipt(
+                next,
+                from_address,
+                to_address,
+                21_000,
+                100,
+            )),
+        );
+
+        let cursor = TransferCursor::new(42, 0, 0);
+        let calculator = create_calculator(transport.clone()).resume_after(cursor);
+        let result = calculator
+            .calculate(&CombinedQuery {
+                chain,
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 10,
+                to_block: 42,
+            })
+            .await
+            .expect("combined calculation should succeed");
+
+        assert_eq!(result.transactions_data.len(), 1);
+        assert_eq!(result.transactions_data[0].tx_hash, next);
+        assert_eq!(result.next_cursor(), Some(TransferCursor::new(42, 1, 3)));
+        assert_eq!(transport.request_count("eth_getTransactionByHash"), 1);
+
+        // A range ending before the cursor isn't scanned
+        let result = calculator
+            .calculate(&CombinedQuery {
+                chain,
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 10,
+                to_block: 41,
+            })
+            .await
+            .expect("combined calculation should succeed");
+        assert_eq!(result.transaction_count.as_usize(), 0);
+        assert_eq!(transport.request_count("eth_getLogs"), 1);
+    }
+
     #[tokio::test]
     async fn tx_lookup_failure_marks_result_partial_and_surfaces_metadata() {
         let transport = MethodResponseTransport::default();
@@ -1291,14 +2628,14 @@ mod tests {
 
         let calculator = create_calculator(transport.clone());
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                100,
-                100,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 100,
+                to_block: 100,
+            })
             .await
             .expect("combined calculation should return partial result");
 
@@ -1310,6 +2647,10 @@ mod tests {
         assert_eq!(result.retrieval_metadata.fallback_attempts, 1);
         assert_eq!(result.retrieval_metadata.fallback_recovered, 0);
         assert_eq!(result.retrieval_metadata.skipped_tx_hashes(), vec![tx_hash]);
+        assert!(matches!(
+            result.warnings.as_slice(),
+            [ScanWarning::FailedReceipt { .. }]
+        ));
 
         let failure = &result.retrieval_metadata.partial_failures[0];
         assert_eq!(failure.tx_hash, tx_hash);
@@ -1343,6 +2684,69 @@ mod tests {
         assert_eq!(transport.request_count("eth_getTransactionReceipt"), 2);
     }
 
+    #[tokio::test]
+    async fn strict_policy_fails_on_lookup_failure() {
+        let transport = MethodResponseTransport::default();
+        let from_address = address!("0x1111111111111111111111111111111111111111");
+        let to_address = address!("0x2222222222222222222222222222222222222222");
+        let token_address = address!("0x3333333333333333333333333333333333333333");
+        let tx_hash = TxHash::from(B256::repeat_byte(0xAB));
+
+        transport.push_success(
+            "eth_getLogs",
+            &vec![create_transfer_log(
+                tx_hash,
+                100,
+                token_address,
+                from_address,
+                to_address,
+                U256::from(777_u64),
+            )],
+        );
+        transport.push_failure_msg("eth_getTransactionByHash", "batch tx lookup failed");
+        transport.push_failure_msg("eth_getTransactionByHash", "fallback tx lookup failed");
+        for _ in 0..2 {
+            transport.push_success(
+                "eth_getTransactionReceipt",
+                &Some(create_test_receipt(
+                    tx_hash,
+                    from_address,
+                    to_address,
+                    21_000,
+                    100,
+                )),
+            );
+        }
+
+        let config = SemioscanConfigBuilder::new()
+            .scan_policy(ScanPolicy::Strict)
+            .build();
+        let calculator = create_calculator_with_config(transport, config);
+        let error = calculator
+            .calculate(&CombinedQuery {
+                chain: NamedChain::Mainnet,
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 100,
+                to_block: 100,
+            })
+            .await
+            .expect_err("strict policy should reject the skipped transfer");
+
+        match error {
+            RetrievalError::SkippedData { warning } => assert!(matches!(
+                *warning,
+                ScanWarning::FailedReceipt {
+                    tx_hash: skipped,
+                    block_number: Some(100),
+                    ..
+                } if skipped == tx_hash
+            )),
+            other => panic!("expected SkippedData, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn receipt_lookup_failure_marks_result_partial_and_surfaces_metadata() {
         let transport = MethodResponseTransport::default();
@@ -1380,14 +2784,14 @@ mod tests {
 
         let calculator = create_calculator(transport.clone());
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                200,
-                200,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 200,
+                to_block: 200,
+            })
             .await
             .expect("combined calculation should return partial result");
 
@@ -1469,14 +2873,14 @@ mod tests {
 
         let calculator = create_calculator(transport.clone());
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                300,
-                300,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 300,
+                to_block: 300,
+            })
             .await
             .expect("combined calculation should succeed after fallback");
 
@@ -1530,14 +2934,14 @@ mod tests {
             .build();
         let calculator = create_calculator_with_config(transport.clone(), config);
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                301,
-                301,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 301,
+                to_block: 301,
+            })
             .await
             .expect("combined calculation should return partial result");
 
@@ -1591,14 +2995,14 @@ mod tests {
 
         let calculator = create_calculator(transport.clone());
         let result = calculator
-            .calculate_combined_data_ethereum(
+            .calculate(&CombinedQuery {
                 chain,
-                from_address,
-                to_address,
-                token_address,
-                68_854_738,
-                68_854_738,
-            )
+                from_address: from_address.into(),
+                to_address: to_address.into(),
+                token_address: token_address.into(),
+                from_block: 68_854_738,
+                to_block: 68_854_738,
+            })
             .await
             .expect("combined calculation should recover from zkSync tx shape mismatch");
 
@@ -1613,4 +3017,131 @@ mod tests {
         assert_eq!(transport.request_count("eth_getTransactionByHash"), 2);
         assert_eq!(transport.request_count("eth_getTransactionReceipt"), 1);
     }
+
+    #[tokio::test]
+    async fn cancelled_token_stops_before_fetching_logs() {
+        let transport = MethodResponseTransport::default();
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let calculator = create_calculator(transport.clone()).with_cancellation(token);
+        let result = calculator
+            .calculate(&CombinedQuery {
+                chain: NamedChain::Mainnet,
+                from_address: address!("0x1111111111111111111111111111111111111111").into(),
+                to_address: address!("0x2222222222222222222222222222222222222222").into(),
+                token_address: address!("0x3333333333333333333333333333333333333333").into(),
+                from_block: 100,
+                to_block: 10_000,
+            })
+            .await
+            .expect("cancelled calculation should return a partial result");
+
+        assert!(result.cancelled);
+        assert!(result.is_partial());
+        assert_eq!(result.transaction_count.as_usize(), 0);
+        assert_eq!(transport.request_count("eth_getLogs"), 0);
+    }
+
+    #[tokio::test]
+    async fn expired_deadline_returns_partial_progress_error() {
+        let transport = MethodResponseTransport::default();
+        let config = SemioscanConfigBuilder::new()
+            .operation_deadline(Duration::ZERO)
+            .build();
+
+        let calculator = create_calculator_with_config(transport.clone(), config);
+        let error = calculator
+            .calculate(&CombinedQuery {
+                chain: NamedChain::Mainnet,
+                from_address: address!("0x1111111111111111111111111111111111111111").into(),
+                to_address: address!("0x2222222222222222222222222222222222222222").into(),
+                token_address: address!("0x3333333333333333333333333333333333333333").into(),
+                from_block: 100,
+                to_block: 10_000,
+            })
+            .await
+            .expect_err("an expired deadline should fail the calculation");
+
+        match error {
+            RetrievalError::DeadlineExceeded {
+                deadline,
+                next_block,
+                partial,
+            } => {
+                assert_eq!(deadline, Duration::ZERO);
+                assert_eq!(next_block, 100);
+                assert_eq!(partial.transaction_count.as_usize(), 0);
+            }
+            other => panic!("expected DeadlineExceeded, got {other:?}"),
+        }
+        assert_eq!(transport.request_count("eth_getLogs"), 0);
+    }
+
+    #[tokio::test]
+    async fn date_range_uses_cached_windows_and_stops_when_cancelled() {
+        use crate::blocks::cache::{BlockWindowCache, CacheKey, MemoryCache};
+        use crate::blocks::window::{DailyBlockWindow, UnixTimestamp};
+        use chrono::NaiveDate;
+
+        let chain = NamedChain::Mainnet;
+        let start_date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let end_date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+
+        let cache = MemoryCache::new();
+        for (day, date) in start_date.iter_days().take(3).enumerate() {
+            let start_block = 1_000 * (day as u64 + 1);
+            let start_ts = 1_740_787_200 + 86_400 * day as i64;
+            let window = DailyBlockWindow::new(
+                start_block,
+                start_block + 999,
+                UnixTimestamp(start_ts),
+                UnixTimestamp(start_ts + 86_400),
+            )
+            .unwrap();
+            cache
+                .insert(CacheKey::new(chain, date), window)
+                .await
+                .unwrap();
+        }
+
+        let transport = MethodResponseTransport::default();
+        let provider: RootProvider<Ethereum> =
+            ProviderBuilder::default().connect_client(RpcClient::new(transport.clone(), true));
+        let windows = BlockWindowCalculator::new(provider, Box::new(cache));
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let calculator = create_calculator(transport.clone()).with_cancellation(token);
+
+        let query = CombinedQuery {
+            chain,
+            from_address: address!("0x1111111111111111111111111111111111111111").into(),
+            to_address: address!("0x2222222222222222222222222222222222222222").into(),
+            token_address: address!("0x3333333333333333333333333333333333333333").into(),
+            from_block: 0,
+            to_block: 0,
+        };
+        let results = calculator
+            .calculate_for_dates(&query, start_date..=end_date, &windows)
+            .await
+            .expect("cancelled calculation should return partial results");
+
+        // Only the first day is attempted, and no RPC calls were needed for its window
+        assert_eq!(
+            results.days.keys().copied().collect::<Vec<_>>(),
+            vec![start_date]
+        );
+        assert!(results.days[&start_date].cancelled);
+        assert!(results.anomalies.is_empty());
+        assert_eq!(transport.request_count("eth_getBlockByNumber"), 0);
+
+        // An inverted range is empty
+        assert!(calculator
+            .calculate_for_dates(&query, end_date..=start_date, &windows)
+            .await
+            .unwrap()
+            .days
+            .is_empty());
+    }
 }
@@ -0,0 +1,357 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contract calls pinned to daily block windows
+//!
+//! Questions like "what was `totalSupply` at the end of day D" need an
+//! `eth_call` against the state at a day's boundary block. [`DailyCaller`]
+//! runs typed calls (any [`SolCall`]) at the first or last block of a
+//! [`DailyBlockWindow`] and, with a [`CallCache`], reuses the returned data per
+//! `(chain, address, selector, arguments, date, boundary)`.
+//!
+//! Calls read historical state, so the provider must be an archive node for
+//! dates outside its pruning window.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use alloy_sol_types::sol;
+//! use semioscan::{CallCache, DailyCaller, WindowBoundary};
+//!
+//! sol! {
+//!     function totalSupply() external view returns (uint256);
+//! }
+//!
+//! let caller = DailyCaller::new(provider, NamedChain::Base).with_cache(CallCache::new());
+//! let supply = caller
+//!     .call_on_date(&windows, date, WindowBoundary::End, token, &totalSupplyCall {})
+//!     .await?;
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use alloy_chains::NamedChain;
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, BlockNumber, Bytes};
+use alloy_provider::Provider;
+use alloy_rpc_types::{TransactionInput, TransactionRequest};
+use alloy_sol_types::SolCall;
+use chrono::{DateTime, NaiveDate};
+use tracing::debug;
+
+use crate::blocks::{BlockWindowCalculator, DailyBlockWindow};
+use crate::errors::{RetrievalError, RpcError};
+
+/// Which end of a daily window a call reads state at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WindowBoundary {
+    /// After the window's first block
+    Start,
+    /// After the window's last block, i.e. the state at the end of the day
+    #[default]
+    End,
+}
+
+impl WindowBoundary {
+    /// The block of `window` at this boundary
+    pub fn block(&self, window: &DailyBlockWindow) -> BlockNumber {
+        match self {
+            WindowBoundary::Start => window.start_block,
+            WindowBoundary::End => window.end_block,
+        }
+    }
+}
+
+/// Cache key of a pinned call
+///
+/// The selector is kept apart from the arguments so entries can be told apart
+/// in logs; both are part of the key, so `balanceOf(alice)` and
+/// `balanceOf(bob)` are cached separately.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CallKey {
+    chain: NamedChain,
+    address: Address,
+    selector: [u8; 4],
+    arguments: Bytes,
+    date: NaiveDate,
+    boundary: WindowBoundary,
+}
+
+/// Shared cache of raw `eth_call` results for past daily windows
+///
+/// Cheap to clone; clones share entries. Results are stored as returned data,
+/// so one cache serves calls of any type.
+#[derive(Debug, Clone, Default)]
+pub struct CallCache {
+    results: Arc<Mutex<HashMap<CallKey, Bytes>>>,
+}
+
+impl CallCache {
+    /// An empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.results.lock().unwrap().len()
+    }
+
+    /// Whether the cache holds no results
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached results
+    pub fn clear(&self) {
+        self.results.lock().unwrap().clear();
+    }
+
+    fn get(&self, key: &CallKey) -> Option<Bytes> {
+        self.results.lock().unwrap().get(key).cloned()
+    }
+
+    fn insert(&self, key: CallKey, data: Bytes) {
+        self.results.lock().unwrap().insert(key, data);
+    }
+}
+
+/// Runs typed `eth_call`s at daily window boundaries on one chain
+pub struct DailyCaller<P> {
+    provider: P,
+    chain: NamedChain,
+    cache: Option<CallCache>,
+}
+
+impl<P: Provider> DailyCaller<P> {
+    /// Create a caller for `chain` without a result cache
+    pub fn new(provider: P, chain: NamedChain) -> Self {
+        Self {
+            provider,
+            chain,
+            cache: None,
+        }
+    }
+
+    /// Reuse results from `cache`, which may be shared with other callers
+    pub fn with_cache(mut self, cache: CallCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Run `call` against `address` at `boundary` of `date`'s window
+    ///
+    /// The window comes from `windows`, which caches it in turn.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the window can't be computed, the call reverts or fails, or
+    /// the returned data doesn't decode as `C`'s return type.
+    pub async fn call_on_date<C: SolCall, W: Provider>(
+        &self,
+        windows: &BlockWindowCalculator<W>,
+        date: NaiveDate,
+        boundary: WindowBoundary,
+        address: Address,
+        call: &C,
+    ) -> Result<C::Return, RetrievalError> {
+        let window = windows.get_daily_window(self.chain, date).await?;
+        self.call_in_window(&window, boundary, address, call).await
+    }
+
+    /// Run `call` against `address` at `boundary` of `window`
+    ///
+    /// The cache date is the UTC day `window` starts on.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the call reverts or fails, or the returned data doesn't
+    /// decode as `C`'s return type.
+    pub async fn call_in_window<C: SolCall>(
+        &self,
+        window: &DailyBlockWindow,
+        boundary: WindowBoundary,
+        address: Address,
+        call: &C,
+    ) -> Result<C::Return, RetrievalError> {
+        let key = self.key(window, boundary, address, call)?;
+
+        let data = match self.cache.as_ref().and_then(|cache| cache.get(&key)) {
+            Some(data) => {
+                debug!(chain = %self.chain, ?address, signature = C::SIGNATURE, date = %key.date, "Using cached call result");
+                data
+            }
+            None => {
+                let data = self
+                    .call_at_block(address, call, boundary.block(window))
+                    .await?;
+                if let Some(cache) = &self.cache {
+                    cache.insert(key, data.clone());
+                }
+                data
+            }
+        };
+
+        decode::<C>(&data)
+    }
+
+    /// The cached result of `call` against `address` at `boundary` of
+    /// `window`, without calling the provider on a miss
+    pub(crate) fn cached_in_window<C: SolCall>(
+        &self,
+        window: &DailyBlockWindow,
+        boundary: WindowBoundary,
+        address: Address,
+        call: &C,
+    ) -> Result<Option<C::Return>, RetrievalError> {
+        let Some(cache) = &self.cache else {
+            return Ok(None);
+        };
+        let key = self.key(window, boundary, address, call)?;
+        cache.get(&key).map(|data| decode::<C>(&data)).transpose()
+    }
+
+    /// Cache `data` as the result of `call` against `address` at `boundary`
+    /// of `window`, for results fetched outside this caller
+    pub(crate) fn cache_in_window<C: SolCall>(
+        &self,
+        window: &DailyBlockWindow,
+        boundary: WindowBoundary,
+        address: Address,
+        call: &C,
+        data: Bytes,
+    ) -> Result<(), RetrievalError> {
+        if let Some(cache) = &self.cache {
+            cache.insert(self.key(window, boundary, address, call)?, data);
+        }
+        Ok(())
+    }
+
+    /// Cache key of `call` at `boundary` of `window`, dated by the UTC day
+    /// `window` starts on
+    fn key<C: SolCall>(
+        &self,
+        window: &DailyBlockWindow,
+        boundary: WindowBoundary,
+        address: Address,
+        call: &C,
+    ) -> Result<CallKey, RetrievalError> {
+        let date = DateTime::from_timestamp(window.start_ts.0, 0)
+            .ok_or_else(|| {
+                RetrievalError::conversion_failed(format!(
+                    "window start {} is not a valid date",
+                    window.start_ts
+                ))
+            })?
+            .date_naive();
+        Ok(CallKey {
+            chain: self.chain,
+            address,
+            selector: C::SELECTOR,
+            arguments: call.abi_encode()[4..].to_vec().into(),
+            date,
+            boundary,
+        })
+    }
+
+    async fn call_at_block<C: SolCall>(
+        &self,
+        address: Address,
+        call: &C,
+        block: BlockNumber,
+    ) -> Result<Bytes, RpcError> {
+        let request = TransactionRequest::default()
+            .to(address)
+            .input(TransactionInput::new(call.abi_encode().into()));
+        self.provider
+            .call(request)
+            .block(BlockId::number(block))
+            .await
+            .map_err(|e| RpcError::request_failed(format!("{} at block {block}", C::SIGNATURE), e))
+    }
+}
+
+/// Decode `data` as `C`'s return type
+fn decode<C: SolCall>(data: &[u8]) -> Result<C::Return, RetrievalError> {
+    C::abi_decode_returns(data).map_err(|e| {
+        RetrievalError::conversion_failed(format!("{} returned invalid data: {e}", C::SIGNATURE))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::UnixTimestamp;
+    use alloy_primitives::{address, U256};
+    use alloy_provider::{ProviderBuilder, RootProvider};
+    use alloy_sol_types::{sol, SolValue};
+    use alloy_transport::mock::Asserter;
+
+    sol! {
+        function totalSupply() external view returns (uint256);
+        function balanceOf(address owner) external view returns (uint256);
+    }
+
+    const TOKEN: Address = address!("00000000000000000000000000000000000000aa");
+
+    fn window() -> DailyBlockWindow {
+        // 2025-10-10
+        DailyBlockWindow::new(
+            1_000,
+            2_000,
+            UnixTimestamp(1_760_054_400),
+            UnixTimestamp(1_760_140_800),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_boundaries_pick_window_ends() {
+        assert_eq!(WindowBoundary::Start.block(&window()), 1_000);
+        assert_eq!(WindowBoundary::End.block(&window()), 2_000);
+    }
+
+    #[tokio::test]
+    async fn test_cached_calls_skip_the_provider() {
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        asserter.push_success(&Bytes::from(U256::from(42).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(7).abi_encode()));
+
+        let cache = CallCache::new();
+        let caller = DailyCaller::new(provider, NamedChain::Mainnet).with_cache(cache.clone());
+        let window = window();
+
+        let supply = caller
+            .call_in_window(&window, WindowBoundary::End, TOKEN, &totalSupplyCall {})
+            .await
+            .unwrap();
+        assert_eq!(supply, U256::from(42));
+
+        // Served from the cache, leaving the second response queued
+        let cached = caller
+            .call_in_window(&window, WindowBoundary::End, TOKEN, &totalSupplyCall {})
+            .await
+            .unwrap();
+        assert_eq!(cached, U256::from(42));
+        assert_eq!(cache.len(), 1);
+
+        // Different arguments are a different entry
+        let balance = caller
+            .call_in_window(
+                &window,
+                WindowBoundary::End,
+                TOKEN,
+                &balanceOfCall {
+                    owner: Address::ZERO,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, U256::from(7));
+        assert_eq!(cache.len(), 2);
+    }
+}
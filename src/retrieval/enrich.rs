@@ -0,0 +1,469 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! USD valuation of combined gas and transfer data
+//!
+//! [`enrich_with_usd`] joins daily prices onto a [`CombinedDataResult`]: gas is
+//! valued at the native token's price and transfers at the transferred token's
+//! price, both for the UTC day the transaction landed in. The output,
+//! [`CombinedDataUsd`], has one row per transaction with its raw and USD
+//! values side by side, plus USD totals.
+//!
+//! [`CombinedDataUsd::to_currency`] converts those values into another fiat
+//! currency, such as EUR, at an [`FxRate`] source's rate for each
+//! transaction's day.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{
+//!     enrich_with_usd, Currency, StaticFxRates, TokenDecimals, TokenPrice, UsdPriceTable,
+//! };
+//!
+//! let prices = UsdPriceTable::new(TokenDecimals::USDC)
+//!     .with_native_price(date, TokenPrice::new(3_200.0))
+//!     .with_token_price_result(date, &usdc_price_result);
+//!
+//! let report = enrich_with_usd(&combined, &[window], &prices);
+//! println!("gas ${}, transferred ${}", report.total_gas_cost_usd, report.total_amount_transferred_usd);
+//!
+//! let rates = StaticFxRates::new().with_rate_on(Currency::EUR, date, 0.92);
+//! let eur = report.to_currency(Currency::EUR, &rates).await?;
+//! println!("gas {}, transferred {}", eur.total_gas_cost, eur.total_amount_transferred);
+//! ```
+
+use std::collections::BTreeMap;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::Address;
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::blocks::window::DailyBlockWindow;
+use crate::errors::FxError;
+use crate::price::fx::FxRate;
+use crate::price::TokenPriceResult;
+use crate::types::config::TransactionCount;
+use crate::types::tokens::{Currency, FiatValue, TokenAmount, TokenDecimals, TokenPrice, UsdValue};
+use crate::types::wei::WeiAmount;
+
+use super::types::{window_index, CombinedDataResult, GasAndAmountForTx};
+
+/// Daily USD prices for the native token and one transferred token
+#[derive(Debug, Clone)]
+pub struct UsdPriceTable {
+    token_decimals: TokenDecimals,
+    native: BTreeMap<NaiveDate, TokenPrice>,
+    token: BTreeMap<NaiveDate, TokenPrice>,
+}
+
+impl UsdPriceTable {
+    /// An empty table for a token with `token_decimals` decimals
+    pub fn new(token_decimals: TokenDecimals) -> Self {
+        Self {
+            token_decimals,
+            native: BTreeMap::new(),
+            token: BTreeMap::new(),
+        }
+    }
+
+    /// Price of the chain's native token (used for gas) on `date`
+    pub fn with_native_price(mut self, date: NaiveDate, price: TokenPrice) -> Self {
+        self.native.insert(date, price);
+        self
+    }
+
+    /// Price of the transferred token on `date`
+    pub fn with_token_price(mut self, date: NaiveDate, price: TokenPrice) -> Self {
+        self.token.insert(date, price);
+        self
+    }
+
+    /// Price of the transferred token on `date`, from a day of DEX swaps
+    ///
+    /// Uses the volume-weighted [`TokenPriceResult::get_average_price`]. A
+    /// result without swaps has no price and is ignored.
+    pub fn with_token_price_result(self, date: NaiveDate, result: &TokenPriceResult) -> Self {
+        let price = result.get_average_price();
+        if price.is_zero() {
+            return self;
+        }
+        self.with_token_price(date, price)
+    }
+
+    fn native_price(&self, date: NaiveDate) -> Option<TokenPrice> {
+        self.native.get(&date).copied()
+    }
+
+    fn token_price(&self, date: NaiveDate) -> Option<TokenPrice> {
+        self.token.get(&date).copied()
+    }
+}
+
+/// One transaction with its gas cost and transferred amount in USD
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GasAndAmountUsd {
+    /// Raw gas and transfer data
+    #[serde(flatten)]
+    pub transaction: GasAndAmountForTx,
+    /// UTC day the transaction was priced for, if it could be dated
+    #[serde(rename = "date")]
+    pub date: Option<NaiveDate>,
+    /// Total gas cost valued at the native token price on `date`
+    #[serde(rename = "gas_cost_usd")]
+    pub gas_cost_usd: Option<UsdValue>,
+    /// Transferred amount valued at the token price on `date`
+    #[serde(rename = "transferred_amount_usd")]
+    pub transferred_amount_usd: Option<UsdValue>,
+}
+
+impl GasAndAmountUsd {
+    /// Whether both USD values are known
+    #[must_use]
+    pub fn is_priced(&self) -> bool {
+        self.gas_cost_usd.is_some() && self.transferred_amount_usd.is_some()
+    }
+}
+
+/// A [`CombinedDataResult`] with USD values per transaction and in total
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CombinedDataUsd {
+    #[serde(rename = "chain")]
+    pub chain: NamedChain,
+    #[serde(rename = "from_address")]
+    pub from_address: Address,
+    #[serde(rename = "to_address")]
+    pub to_address: Address,
+    #[serde(rename = "token_address")]
+    pub token_address: Address,
+    #[serde(rename = "transaction_count")]
+    pub transaction_count: TransactionCount,
+    /// Sum of the known per-transaction gas costs
+    #[serde(rename = "total_gas_cost_usd")]
+    pub total_gas_cost_usd: UsdValue,
+    /// Sum of the known per-transaction transferred amounts
+    #[serde(rename = "total_amount_transferred_usd")]
+    pub total_amount_transferred_usd: UsdValue,
+    /// Transactions missing a native or token price; the totals exclude
+    /// their unpriced values
+    #[serde(rename = "unpriced_transactions")]
+    pub unpriced_transactions: usize,
+    /// Whether the underlying result was partial (see [`CombinedDataResult::is_partial`])
+    #[serde(rename = "partial")]
+    pub partial: bool,
+    #[serde(rename = "transactions")]
+    pub transactions: Vec<GasAndAmountUsd>,
+}
+
+impl CombinedDataUsd {
+    /// Converts every USD value into `currency` at `fx`'s rate for its
+    /// transaction's day
+    ///
+    /// Each day's rate is looked up once. Fails if `fx` has no rate for a
+    /// day with priced transactions; unpriced values stay `None`.
+    pub async fn to_currency<F: FxRate + ?Sized>(
+        &self,
+        currency: Currency,
+        fx: &F,
+    ) -> Result<CombinedDataFiat, FxError> {
+        let mut rates = BTreeMap::new();
+        for date in self.transactions.iter().filter_map(|row| row.date) {
+            if !rates.contains_key(&date) {
+                let rate = if currency == Currency::USD {
+                    1.0
+                } else {
+                    fx.usd_rate(currency, date).await?
+                };
+                rates.insert(date, rate);
+            }
+        }
+
+        let mut total_gas_cost = FiatValue::new(currency, 0.0);
+        let mut total_amount_transferred = FiatValue::new(currency, 0.0);
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|row| {
+                let convert = |value: Option<UsdValue>| {
+                    let rate = rates.get(&row.date?)?;
+                    Some(value?.to_fiat(currency, *rate))
+                };
+                let converted = GasAndAmountFiat {
+                    transaction: row.transaction.clone(),
+                    date: row.date,
+                    gas_cost: convert(row.gas_cost_usd),
+                    transferred_amount: convert(row.transferred_amount_usd),
+                };
+                total_gas_cost.amount += converted.gas_cost.map_or(0.0, |value| value.amount);
+                total_amount_transferred.amount += converted
+                    .transferred_amount
+                    .map_or(0.0, |value| value.amount);
+                converted
+            })
+            .collect();
+
+        Ok(CombinedDataFiat {
+            chain: self.chain,
+            from_address: self.from_address,
+            to_address: self.to_address,
+            token_address: self.token_address,
+            transaction_count: self.transaction_count,
+            currency,
+            total_gas_cost,
+            total_amount_transferred,
+            unpriced_transactions: self.unpriced_transactions,
+            partial: self.partial,
+            transactions,
+        })
+    }
+}
+
+/// One transaction with its gas cost and transferred amount in a fiat
+/// currency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GasAndAmountFiat {
+    /// Raw gas and transfer data
+    #[serde(flatten)]
+    pub transaction: GasAndAmountForTx,
+    /// UTC day the transaction was priced and converted for
+    #[serde(rename = "date")]
+    pub date: Option<NaiveDate>,
+    #[serde(rename = "gas_cost")]
+    pub gas_cost: Option<FiatValue>,
+    #[serde(rename = "transferred_amount")]
+    pub transferred_amount: Option<FiatValue>,
+}
+
+/// A [`CombinedDataUsd`] converted into another fiat currency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CombinedDataFiat {
+    #[serde(rename = "chain")]
+    pub chain: NamedChain,
+    #[serde(rename = "from_address")]
+    pub from_address: Address,
+    #[serde(rename = "to_address")]
+    pub to_address: Address,
+    #[serde(rename = "token_address")]
+    pub token_address: Address,
+    #[serde(rename = "transaction_count")]
+    pub transaction_count: TransactionCount,
+    /// Currency of every value below
+    #[serde(rename = "currency")]
+    pub currency: Currency,
+    /// Sum of the known per-transaction gas costs
+    #[serde(rename = "total_gas_cost")]
+    pub total_gas_cost: FiatValue,
+    /// Sum of the known per-transaction transferred amounts
+    #[serde(rename = "total_amount_transferred")]
+    pub total_amount_transferred: FiatValue,
+    /// Transactions missing a native or token price
+    #[serde(rename = "unpriced_transactions")]
+    pub unpriced_transactions: usize,
+    #[serde(rename = "partial")]
+    pub partial: bool,
+    #[serde(rename = "transactions")]
+    pub transactions: Vec<GasAndAmountFiat>,
+}
+
+/// Values `result` in USD using the prices for each transaction's UTC day
+///
+/// A transaction is dated by its block timestamp when known, otherwise by the
+/// window in `windows` containing its block. Pass the windows the scan
+/// covered; for a single-day scan that is just that day's window.
+/// Transactions that can't be dated, or whose day has no price, are kept
+/// with `None` USD values and counted in
+/// [`unpriced_transactions`](CombinedDataUsd::unpriced_transactions).
+#[must_use]
+pub fn enrich_with_usd(
+    result: &CombinedDataResult,
+    windows: &[DailyBlockWindow],
+    prices: &UsdPriceTable,
+) -> CombinedDataUsd {
+    let mut total_gas_cost_usd = UsdValue::ZERO;
+    let mut total_amount_transferred_usd = UsdValue::ZERO;
+    let mut unpriced_transactions = 0;
+
+    let transactions = result
+        .transactions_data
+        .iter()
+        .map(|tx| {
+            let date = transaction_date(tx, windows);
+            let gas_cost_usd = date.and_then(|date| {
+                let price = prices.native_price(date)?;
+                let cost = WeiAmount::new(tx.total_gas_cost()).to_ether();
+                Some(UsdValue::new(cost * price.as_f64()))
+            });
+            let transferred_amount_usd = date.and_then(|date| {
+                let price = prices.token_price(date)?;
+                let amount =
+                    TokenAmount::new(tx.transferred_amount).normalize(prices.token_decimals);
+                Some(price.value_of(amount))
+            });
+
+            let row = GasAndAmountUsd {
+                transaction: tx.clone(),
+                date,
+                gas_cost_usd,
+                transferred_amount_usd,
+            };
+            total_gas_cost_usd += row.gas_cost_usd.unwrap_or_default();
+            total_amount_transferred_usd += row.transferred_amount_usd.unwrap_or_default();
+            if !row.is_priced() {
+                unpriced_transactions += 1;
+            }
+            row
+        })
+        .collect();
+
+    CombinedDataUsd {
+        chain: result.chain,
+        from_address: result.from_address,
+        to_address: result.to_address,
+        token_address: result.token_address,
+        transaction_count: result.transaction_count,
+        total_gas_cost_usd,
+        total_amount_transferred_usd,
+        unpriced_transactions,
+        partial: result.is_partial(),
+        transactions,
+    }
+}
+
+/// UTC day of a transaction, by block timestamp or by containing window
+fn transaction_date(tx: &GasAndAmountForTx, windows: &[DailyBlockWindow]) -> Option<NaiveDate> {
+    let timestamp = match tx.block_timestamp {
+        Some(timestamp) => i64::try_from(timestamp).ok()?,
+        None => {
+            windows[window_index(windows, tx.block_number, None)?]
+                .start_ts
+                .0
+        }
+    };
+    DateTime::from_timestamp(timestamp, 0).map(|datetime| datetime.date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::window::UnixTimestamp;
+    use crate::price::fx::StaticFxRates;
+    use crate::types::gas::{GasAmount, GasPrice};
+    use alloy_primitives::{TxHash, U256};
+
+    fn transfer(block_number: u64, block_timestamp: Option<u64>) -> GasAndAmountForTx {
+        GasAndAmountForTx {
+            tx_hash: TxHash::left_padding_from(&block_number.to_be_bytes()),
+            block_number,
+            block_timestamp,
+            tx_index: None,
+            log_index: None,
+            token_address: None,
+            // 0.001 ETH of gas
+            gas_used: GasAmount::new(100_000),
+            effective_gas_price: GasPrice::from_gwei(10),
+            base_fee_per_gas: None,
+            l1_fee: None,
+            blob_gas_cost: U256::ZERO,
+            // 250 USDC
+            transferred_amount: U256::from(250_000_000u64),
+        }
+    }
+
+    #[test]
+    fn test_enrich_values_each_transaction_at_its_days_prices() {
+        // 2025-03-01
+        let window = DailyBlockWindow::new(
+            100,
+            199,
+            UnixTimestamp(1_740_787_200),
+            UnixTimestamp(1_740_873_600),
+        )
+        .unwrap();
+        let march_1 = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let march_2 = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        // Dated through the window
+        result.add_transaction_data(transfer(150, None));
+        // Dated by timestamp, on a day without a token price
+        result.add_transaction_data(transfer(250, Some(1_740_880_000)));
+        // Can't be dated
+        result.add_transaction_data(transfer(900, None));
+
+        let prices = UsdPriceTable::new(TokenDecimals::USDC)
+            .with_native_price(march_1, TokenPrice::new(2_000.0))
+            .with_native_price(march_2, TokenPrice::new(3_000.0))
+            .with_token_price(march_1, TokenPrice::new(1.0));
+
+        let report = enrich_with_usd(&result, &[window], &prices);
+
+        let rows = &report.transactions;
+        assert_eq!(rows[0].date, Some(march_1));
+        assert_eq!(rows[0].gas_cost_usd, Some(UsdValue::new(2.0)));
+        assert_eq!(rows[0].transferred_amount_usd, Some(UsdValue::new(250.0)));
+        assert_eq!(rows[1].date, Some(march_2));
+        assert_eq!(rows[1].gas_cost_usd, Some(UsdValue::new(3.0)));
+        assert_eq!(rows[1].transferred_amount_usd, None);
+        assert_eq!(rows[2].date, None);
+
+        assert_eq!(report.total_gas_cost_usd, UsdValue::new(5.0));
+        assert_eq!(report.total_amount_transferred_usd, UsdValue::new(250.0));
+        assert_eq!(report.unpriced_transactions, 2);
+    }
+
+    #[tokio::test]
+    async fn test_to_currency_converts_each_row_at_its_days_rate() {
+        let march_1 = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let march_2 = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        // 2025-03-01 and 2025-03-02, by timestamp
+        result.add_transaction_data(transfer(150, Some(1_740_800_000)));
+        result.add_transaction_data(transfer(250, Some(1_740_880_000)));
+        // Can't be dated
+        result.add_transaction_data(transfer(900, None));
+
+        let prices = UsdPriceTable::new(TokenDecimals::USDC)
+            .with_native_price(march_1, TokenPrice::new(2_000.0))
+            .with_native_price(march_2, TokenPrice::new(3_000.0))
+            .with_token_price(march_1, TokenPrice::new(1.0));
+        let report = enrich_with_usd(&result, &[], &prices);
+
+        let rates = StaticFxRates::new()
+            .with_rate_on(Currency::EUR, march_1, 0.75)
+            .with_rate_on(Currency::EUR, march_2, 0.5);
+        let eur = report.to_currency(Currency::EUR, &rates).await.unwrap();
+
+        let rows = &eur.transactions;
+        assert_eq!(rows[0].gas_cost, Some(FiatValue::new(Currency::EUR, 1.5)));
+        assert_eq!(
+            rows[0].transferred_amount,
+            Some(FiatValue::new(Currency::EUR, 187.5))
+        );
+        assert_eq!(rows[1].gas_cost, Some(FiatValue::new(Currency::EUR, 1.5)));
+        assert_eq!(rows[1].transferred_amount, None);
+        assert_eq!(rows[2].gas_cost, None);
+        assert_eq!(eur.total_gas_cost, FiatValue::new(Currency::EUR, 3.0));
+        assert_eq!(
+            eur.total_amount_transferred,
+            FiatValue::new(Currency::EUR, 187.5)
+        );
+
+        let missing_day = StaticFxRates::new().with_rate_on(Currency::EUR, march_2, 0.5);
+        assert!(matches!(
+            report.to_currency(Currency::EUR, &missing_day).await,
+            Err(FxError::RateUnavailable { date, .. }) if date == march_1
+        ));
+    }
+}
@@ -5,7 +5,7 @@
 //! Core gas calculation logic
 
 use alloy_eips::Typed2718;
-use alloy_primitives::{Address, BlockNumber, U256};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use alloy_rpc_types::{Filter, TransactionTrait};
 use alloy_sol_types::SolEvent;
 
@@ -46,6 +46,28 @@ impl GasCalculationCore {
             .topic1(from_address)
             .topic2(to_address)
     }
+
+    /// A Transfer filter for `from_address`'s transfers to any of `to_addresses`
+    pub(crate) fn create_transfers_to_filter(
+        current_block: BlockNumber,
+        to_block: BlockNumber,
+        token_address: Address,
+        from_address: Address,
+        to_addresses: &[Address],
+    ) -> Filter {
+        Filter::new()
+            .from_block(current_block)
+            .to_block(to_block)
+            .address(token_address)
+            .event_signature(Transfer::SIGNATURE_HASH)
+            .topic1(from_address)
+            .topic2(
+                to_addresses
+                    .iter()
+                    .map(|address| address.into_word())
+                    .collect::<Vec<B256>>(),
+            )
+    }
 }
 
 #[cfg(test)]
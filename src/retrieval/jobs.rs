@@ -0,0 +1,680 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resumable backfills over many chains, tokens and dates
+//!
+//! A [`JobManifest`] lists the [`BackfillTask`]s of a backfill, one per
+//! (chain, token, sender, recipient, date). [`JobRunner::run`] runs every task
+//! not yet completed with a caller-supplied function, typically one that calls
+//! [`CombinedCalculator`](super::CombinedCalculator) for the task's day and
+//! stores the result. The runner:
+//!
+//! - records each task's outcome in a JSON state file, written atomically
+//!   after every change,
+//! - skips tasks the state file lists as completed, so rerunning the same
+//!   manifest after a crash or a partial failure picks up where it stopped,
+//! - runs tasks concurrently, bounded both overall and per chain. Each
+//!   chain's tasks are scheduled separately, so tasks waiting on a busy chain
+//!   never hold a slot another chain could use,
+//! - retries a failed task a bounded number of times before moving on.
+//!
+//! A task interrupted by a crash is run again from the start, so the task
+//! function should be idempotent, e.g. by upserting its results.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{BackfillTask, JobManifest, JobRunner};
+//!
+//! let mut manifest = JobManifest::new();
+//! for chain in [NamedChain::Mainnet, NamedChain::Base] {
+//!     for date in start.iter_days().take_while(|date| *date <= end) {
+//!         manifest.push(BackfillTask::new(chain, token, from, to, date));
+//!     }
+//! }
+//!
+//! let runner = JobRunner::new("backfill-state.json").with_per_chain_concurrency(2);
+//! let report = runner
+//!     .run(&manifest, |task| async move {
+//!         let result = client.combined_data_for_date(&task).await?;
+//!         insert_combined_result(&pool, &result).await
+//!     })
+//!     .await?;
+//! for failure in &report.failed {
+//!     eprintln!("{}: {}", failure.task.id(), failure.error);
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::Address;
+use chrono::NaiveDate;
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, Semaphore};
+use tracing::{info, warn};
+
+use crate::errors::JobError;
+use crate::types::cache::TimestampMillis;
+
+/// One unit of a backfill: transfers of a token between two addresses on one
+/// chain during one UTC day
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BackfillTask {
+    /// Chain to scan
+    #[serde(rename = "chain")]
+    pub chain: NamedChain,
+    /// Token whose transfers are scanned
+    #[serde(rename = "token_address")]
+    pub token_address: Address,
+    /// Sender of the transfers
+    #[serde(rename = "from_address")]
+    pub from_address: Address,
+    /// Recipient of the transfers
+    #[serde(rename = "to_address")]
+    pub to_address: Address,
+    /// UTC day to scan
+    #[serde(rename = "date")]
+    pub date: NaiveDate,
+}
+
+impl BackfillTask {
+    /// Task for transfers of `token_address` from `from_address` to
+    /// `to_address` on `chain` during `date`
+    #[must_use]
+    pub fn new(
+        chain: NamedChain,
+        token_address: Address,
+        from_address: Address,
+        to_address: Address,
+        date: NaiveDate,
+    ) -> Self {
+        Self {
+            chain,
+            token_address,
+            from_address,
+            to_address,
+            date,
+        }
+    }
+
+    /// Key of this task in the job's state file
+    #[must_use]
+    pub fn id(&self) -> String {
+        format!(
+            "{}/{}/{}/{}/{}",
+            self.chain, self.token_address, self.from_address, self.to_address, self.date
+        )
+    }
+}
+
+/// Tasks of a backfill, in the order they were added, without duplicates
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "ManifestTasks")]
+pub struct JobManifest {
+    #[serde(rename = "tasks")]
+    tasks: Vec<BackfillTask>,
+    /// The same tasks, to find duplicates without scanning `tasks`
+    #[serde(skip)]
+    added: HashSet<BackfillTask>,
+}
+
+/// Serialized form of a [`JobManifest`], deduplicated when read back
+#[derive(Deserialize)]
+struct ManifestTasks {
+    #[serde(rename = "tasks")]
+    tasks: Vec<BackfillTask>,
+}
+
+impl From<ManifestTasks> for JobManifest {
+    fn from(manifest: ManifestTasks) -> Self {
+        manifest.tasks.into_iter().collect()
+    }
+}
+
+impl JobManifest {
+    /// Create an empty manifest
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `task`, returning `false` if the manifest already has it
+    pub fn push(&mut self, task: BackfillTask) -> bool {
+        if !self.added.insert(task.clone()) {
+            return false;
+        }
+        self.tasks.push(task);
+        true
+    }
+
+    /// Tasks in the order they were added
+    #[must_use]
+    pub fn tasks(&self) -> &[BackfillTask] {
+        &self.tasks
+    }
+
+    /// Number of tasks
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Whether the manifest has no tasks
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+impl FromIterator<BackfillTask> for JobManifest {
+    fn from_iter<I: IntoIterator<Item = BackfillTask>>(iter: I) -> Self {
+        let mut manifest = Self::new();
+        for task in iter {
+            manifest.push(task);
+        }
+        manifest
+    }
+}
+
+/// Outcome of a task's latest run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// The task succeeded and is skipped by later runs
+    Completed,
+    /// Every attempt of the task's latest run failed; later runs retry it
+    Failed,
+}
+
+/// What the state file records about one task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskRecord {
+    /// Outcome of the task's latest attempt
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+    /// Attempts across every run, including the successful one
+    #[serde(rename = "attempts")]
+    pub attempts: u32,
+    /// Error of the latest failed attempt
+    #[serde(
+        rename = "last_error",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_error: Option<String>,
+    /// When the latest attempt finished
+    #[serde(rename = "updated_at")]
+    pub updated_at: TimestampMillis,
+}
+
+/// Progress of a job as stored in its state file, keyed by [`BackfillTask::id`]
+///
+/// Tasks without a record haven't been run, or were interrupted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobState {
+    /// Record of each task that has been run
+    #[serde(rename = "tasks")]
+    pub tasks: BTreeMap<String, TaskRecord>,
+}
+
+impl JobState {
+    /// Record of `task`, or `None` if it hasn't been run
+    #[must_use]
+    pub fn record(&self, task: &BackfillTask) -> Option<&TaskRecord> {
+        self.tasks.get(&task.id())
+    }
+
+    /// Whether `task` succeeded in an earlier run
+    #[must_use]
+    pub fn is_completed(&self, task: &BackfillTask) -> bool {
+        self.record(task)
+            .is_some_and(|record| record.status == TaskStatus::Completed)
+    }
+
+    fn update(&mut self, task: &BackfillTask, status: TaskStatus, error: Option<String>) {
+        let record = self.tasks.entry(task.id()).or_insert(TaskRecord {
+            status,
+            attempts: 0,
+            last_error: None,
+            updated_at: TimestampMillis::now(),
+        });
+        record.status = status;
+        record.attempts = record.attempts.saturating_add(1);
+        if error.is_some() {
+            record.last_error = error;
+        }
+        record.updated_at = TimestampMillis::now();
+    }
+}
+
+/// A task whose every attempt failed during a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskFailure {
+    /// The failed task
+    pub task: BackfillTask,
+    /// Attempts made during the run
+    pub attempts: u32,
+    /// Error of the last attempt
+    pub error: String,
+}
+
+/// Summary of one [`JobRunner::run`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobReport {
+    /// Tasks that succeeded during the run
+    pub completed: usize,
+    /// Tasks skipped because an earlier run completed them
+    pub already_completed: usize,
+    /// Tasks that still failed after their last attempt
+    pub failed: Vec<TaskFailure>,
+}
+
+impl JobReport {
+    /// Whether every task in the manifest is now completed
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Runs a [`JobManifest`] and tracks its progress in a state file
+///
+/// See the [module documentation](self) for how tasks are scheduled and
+/// resumed.
+#[derive(Debug, Clone)]
+pub struct JobRunner {
+    state_path: PathBuf,
+    max_concurrency: usize,
+    per_chain_concurrency: usize,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl JobRunner {
+    /// Create a runner that keeps its state in `state_path`
+    ///
+    /// Defaults to four tasks at a time, one per chain, and three attempts
+    /// per task one second apart.
+    pub fn new(state_path: impl Into<PathBuf>) -> Self {
+        Self {
+            state_path: state_path.into(),
+            max_concurrency: 4,
+            per_chain_concurrency: 1,
+            max_attempts: 3,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Run at most `limit` tasks at once across all chains
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = limit.max(1);
+        self
+    }
+
+    /// Run at most `limit` tasks at once on any one chain
+    ///
+    /// Keeps a backfill within each provider's rate limits while other chains
+    /// proceed.
+    pub fn with_per_chain_concurrency(mut self, limit: usize) -> Self {
+        self.per_chain_concurrency = limit.max(1);
+        self
+    }
+
+    /// Try each task up to `attempts` times per run
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Wait `delay` after a failed attempt before retrying the task
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Path of the state file
+    #[must_use]
+    pub fn state_path(&self) -> &Path {
+        &self.state_path
+    }
+
+    /// Read the state file, or an empty state if it doesn't exist yet
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JobError`] if the file can't be read or parsed.
+    pub async fn load_state(&self) -> Result<JobState, JobError> {
+        match tokio::fs::read(&self.state_path).await {
+            Ok(bytes) => {
+                serde_json::from_slice(&bytes).map_err(|e| JobError::json(&self.state_path, e))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(JobState::default()),
+            Err(e) => Err(JobError::io(&self.state_path, e)),
+        }
+    }
+
+    /// Run every task of `manifest` not yet completed with `run_task`
+    ///
+    /// A task's failures don't stop the run: after its last attempt it is
+    /// recorded as failed and listed in the report, and the next run of the
+    /// same manifest retries it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JobError`] if the state file can't be read or written. Tasks
+    /// finished before the error are already recorded.
+    pub async fn run<F, Fut, E>(
+        &self,
+        manifest: &JobManifest,
+        run_task: F,
+    ) -> Result<JobReport, JobError>
+    where
+        F: Fn(BackfillTask) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: Display,
+    {
+        let state = self.load_state().await?;
+        let mut report = JobReport::default();
+
+        let pending: Vec<&BackfillTask> = manifest
+            .tasks
+            .iter()
+            .filter(|task| !state.is_completed(task))
+            .collect();
+        report.already_completed = manifest.len() - pending.len();
+        info!(
+            path = %self.state_path.display(),
+            tasks = manifest.len(),
+            pending = pending.len(),
+            "Running backfill job"
+        );
+
+        // Each chain runs its own queue, and a task only takes one of the
+        // overall slots once its chain has room for it
+        let slots = Semaphore::new(self.max_concurrency);
+        let state = Mutex::new(state);
+        let run_task = &run_task;
+
+        let chains = group_by_chain(pending).into_iter().map(|tasks| {
+            let (slots, state) = (&slots, &state);
+            stream::iter(tasks)
+                .map(move |task| async move {
+                    let _slot = slots.acquire().await.expect("semaphore is never closed");
+                    self.run_with_retries(task, run_task, state).await
+                })
+                .buffer_unordered(self.per_chain_concurrency)
+                .collect::<Vec<_>>()
+        });
+        let outcomes: Vec<Result<Option<TaskFailure>, JobError>> =
+            join_all(chains).await.into_iter().flatten().collect();
+
+        for outcome in outcomes {
+            match outcome? {
+                Some(failure) => report.failed.push(failure),
+                None => report.completed += 1,
+            }
+        }
+        info!(
+            completed = report.completed,
+            already_completed = report.already_completed,
+            failed = report.failed.len(),
+            "Finished backfill job"
+        );
+        Ok(report)
+    }
+
+    async fn run_with_retries<F, Fut, E>(
+        &self,
+        task: &BackfillTask,
+        run_task: &F,
+        state: &Mutex<JobState>,
+    ) -> Result<Option<TaskFailure>, JobError>
+    where
+        F: Fn(BackfillTask) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: Display,
+    {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match run_task(task.clone()).await {
+                Ok(()) => {
+                    self.save(state, task, TaskStatus::Completed, None).await?;
+                    return Ok(None);
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    warn!(
+                        task = %task.id(),
+                        attempt = attempts,
+                        max_attempts = self.max_attempts,
+                        %error,
+                        "Backfill task failed"
+                    );
+                    self.save(state, task, TaskStatus::Failed, Some(error.clone()))
+                        .await?;
+                    if attempts >= self.max_attempts {
+                        return Ok(Some(TaskFailure {
+                            task: task.clone(),
+                            attempts,
+                            error,
+                        }));
+                    }
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+            }
+        }
+    }
+
+    /// Record an attempt and write the whole state, holding the lock so
+    /// writes land in order
+    async fn save(
+        &self,
+        state: &Mutex<JobState>,
+        task: &BackfillTask,
+        status: TaskStatus,
+        error: Option<String>,
+    ) -> Result<(), JobError> {
+        let mut state = state.lock().await;
+        state.update(task, status, error);
+        let json =
+            serde_json::to_vec_pretty(&*state).map_err(|e| JobError::json(&self.state_path, e))?;
+
+        if let Some(parent) = self.state_path.parent() {
+            if !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| JobError::io(parent, e))?;
+            }
+        }
+        // Write atomically so a crash never leaves a truncated state file
+        let temp_path = self.state_path.with_extension("tmp");
+        tokio::fs::write(&temp_path, &json)
+            .await
+            .map_err(|e| JobError::io(&temp_path, e))?;
+        tokio::fs::rename(&temp_path, &self.state_path)
+            .await
+            .map_err(|e| JobError::io(&self.state_path, e))
+    }
+}
+
+/// Split `tasks` into one queue per chain, in order of each chain's first
+/// task, keeping each chain's tasks in manifest order
+fn group_by_chain(tasks: Vec<&BackfillTask>) -> Vec<Vec<&BackfillTask>> {
+    let mut queues: Vec<Vec<&BackfillTask>> = Vec::new();
+    let mut positions: HashMap<NamedChain, usize> = HashMap::new();
+    for task in tasks {
+        let position = *positions.entry(task.chain).or_insert_with(|| {
+            queues.push(Vec::new());
+            queues.len() - 1
+        });
+        queues[position].push(task);
+    }
+    queues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn task(chain: NamedChain, day: u32) -> BackfillTask {
+        BackfillTask::new(
+            chain,
+            Address::repeat_byte(0x01),
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_manifest_drops_duplicates_and_tasks_queue_by_chain() {
+        let manifest: JobManifest = [
+            task(NamedChain::Mainnet, 1),
+            task(NamedChain::Mainnet, 2),
+            task(NamedChain::Mainnet, 1),
+            task(NamedChain::Base, 1),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(manifest.len(), 3);
+
+        let queues: Vec<Vec<_>> = group_by_chain(manifest.tasks().iter().collect())
+            .into_iter()
+            .map(|queue| {
+                queue
+                    .into_iter()
+                    .map(|task| (task.chain, task.date.day0()))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            queues,
+            vec![
+                vec![(NamedChain::Mainnet, 0), (NamedChain::Mainnet, 1)],
+                vec![(NamedChain::Base, 0)]
+            ]
+        );
+
+        // Duplicates are also dropped from a manifest read back
+        let json = serde_json::json!({
+            "tasks": [task(NamedChain::Base, 1), task(NamedChain::Base, 1)]
+        });
+        let mut read: JobManifest = serde_json::from_value(json).unwrap();
+        assert_eq!(read.len(), 1);
+        assert!(!read.push(task(NamedChain::Base, 1)));
+    }
+
+    #[tokio::test]
+    async fn test_a_busy_chain_does_not_hold_slots_other_chains_could_use() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = JobRunner::new(dir.path().join("state.json")).with_max_concurrency(3);
+        let manifest: JobManifest = [1, 2, 3]
+            .into_iter()
+            .map(|day| task(NamedChain::Mainnet, day))
+            .chain([1, 2, 3].into_iter().map(|day| task(NamedChain::Base, day)))
+            .chain([task(NamedChain::Optimism, 1)])
+            .collect();
+
+        // Mainnet's first task can't finish until every Base task has
+        let (base_done, base_progress) = tokio::sync::watch::channel(0_usize);
+        let base_done = &base_done;
+        let run = runner.run(&manifest, move |task| {
+            let mut base_progress = base_progress.clone();
+            async move {
+                match task.chain {
+                    NamedChain::Base => base_done.send_modify(|done| *done += 1),
+                    NamedChain::Mainnet => {
+                        base_progress
+                            .wait_for(|done| *done == 3)
+                            .await
+                            .map_err(|e| e.to_string())?;
+                    }
+                    _ => {}
+                }
+                Ok::<_, String>(())
+            }
+        });
+        let report = tokio::time::timeout(Duration::from_secs(5), run)
+            .await
+            .expect("other chains kept running while Mainnet waited")
+            .unwrap();
+        assert_eq!(report.completed, 7);
+    }
+
+    #[tokio::test]
+    async fn test_failed_tasks_are_retried_and_completed_tasks_skipped_on_rerun() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = JobRunner::new(dir.path().join("state.json"))
+            .with_max_attempts(2)
+            .with_retry_delay(Duration::ZERO);
+        let manifest: JobManifest = [
+            task(NamedChain::Mainnet, 1),
+            task(NamedChain::Mainnet, 2),
+            task(NamedChain::Base, 1),
+        ]
+        .into_iter()
+        .collect();
+
+        // Base fails every attempt of the first run
+        let calls = AtomicUsize::new(0);
+        let report = runner
+            .run(&manifest, |task| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match task.chain {
+                        NamedChain::Base => Err(format!("rpc down on day {}", task.date.day())),
+                        _ => Ok(()),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(report.completed, 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].attempts, 2);
+        assert_eq!(report.failed[0].error, "rpc down on day 1");
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let state = runner.load_state().await.unwrap();
+        let failed = state.record(&task(NamedChain::Base, 1)).unwrap();
+        assert_eq!(failed.status, TaskStatus::Failed);
+        assert_eq!(failed.attempts, 2);
+        assert!(state.is_completed(&task(NamedChain::Mainnet, 2)));
+
+        // A rerun only runs the failed task
+        let calls = AtomicUsize::new(0);
+        let report = runner
+            .run(&manifest, |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok::<_, String>(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(report.already_completed, 2);
+        assert_eq!(report.completed, 1);
+        assert!(report.is_complete());
+
+        let record = runner
+            .load_state()
+            .await
+            .unwrap()
+            .record(&task(NamedChain::Base, 1))
+            .cloned()
+            .unwrap();
+        assert_eq!(record.status, TaskStatus::Completed);
+        assert_eq!(record.attempts, 3);
+        assert_eq!(record.last_error.as_deref(), Some("rpc down on day 1"));
+    }
+}
@@ -6,26 +6,54 @@
 //!
 //! This module handles coordinated retrieval of blockchain data including:
 //! - Combined gas and price data extraction
+//! - Anomaly flags on daily gas totals and transaction counts
 //! - Transfer amount calculations
 //! - Decimal precision handling
+//! - USD valuation of combined results
 //! - Batch balance fetching
+//! - Contract calls pinned to daily window boundaries
+//! - Day-end holder snapshots and their differences
+//! - Resumable multi-chain, multi-date backfill jobs
 
 // Combined retrieval sub-modules
+pub mod anomaly;
 pub mod balance;
 mod calculator;
+pub mod calls;
 mod decimal_precision;
+pub mod enrich;
 mod gas_calculation;
+#[cfg(feature = "disk-cache")]
+pub mod jobs;
+mod query;
+pub mod snapshot;
 mod types;
 mod utils;
 
 // Re-export public API
+pub use anomaly::{AnomalyDetector, AnomalyMethod, AnomalyMetric, DailyAnomaly, DailyCombinedData};
 pub use balance::{
-    batch_fetch_balances, batch_fetch_eth_balances, BalanceError, BalanceQuery, BalanceResult,
+    batch_fetch_balances, batch_fetch_balances_at, batch_fetch_eth_balances, BalanceError,
+    BalanceQuery, BalanceResult,
 };
 pub use calculator::CombinedCalculator;
+pub use calls::{CallCache, DailyCaller, WindowBoundary};
 pub use decimal_precision::DecimalPrecision;
+pub use enrich::{
+    enrich_with_usd, CombinedDataFiat, CombinedDataUsd, GasAndAmountFiat, GasAndAmountUsd,
+    UsdPriceTable,
+};
+#[cfg(feature = "disk-cache")]
+pub use jobs::{
+    BackfillTask, JobManifest, JobReport, JobRunner, JobState, TaskFailure, TaskRecord, TaskStatus,
+};
+pub use query::{CombinedQuery, CombinedQueryBuilder};
+pub use snapshot::{
+    AllowanceChange, BalanceChange, HolderSnapshot, SnapshotCollector, SnapshotDiff,
+};
 pub use types::{
     CombinedDataLookupAttempt, CombinedDataLookupFailure, CombinedDataLookupPass,
     CombinedDataLookupStage, CombinedDataResult, CombinedDataRetrievalMetadata, GasAndAmountForTx,
+    GasAttribution, TransferCursor,
 };
 pub use utils::{get_token_decimal_precision, u256_to_bigdecimal};
@@ -0,0 +1,174 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Named parameters for combined data calculations
+//!
+//! A [`CombinedQuery`] names the chain, the sender, recipient and token of
+//! the transfers, and the block range to scan, so the three addresses and
+//! the two block bounds can't be swapped by position.
+
+use std::ops::RangeInclusive;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::BlockNumber;
+
+use crate::errors::RetrievalError;
+use crate::types::address::{RecipientAddress, SenderAddress, TokenAddress};
+
+/// Transfers of `token_address` from `from_address` to `to_address` on
+/// `chain`, between `from_block` and `to_block` inclusive
+///
+/// # Examples
+///
+/// ```
+/// use alloy_chains::NamedChain;
+/// use alloy_primitives::address;
+/// use semioscan::CombinedQuery;
+///
+/// let query = CombinedQuery::builder()
+///     .chain(NamedChain::Base)
+///     .from(address!("0x1111111111111111111111111111111111111111"))
+///     .to(address!("0x2222222222222222222222222222222222222222"))
+///     .token(address!("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"))
+///     .blocks(20_000_000..=20_010_000)
+///     .build()
+///     .unwrap();
+/// assert_eq!(query.from_block, 20_000_000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CombinedQuery {
+    /// Chain to scan
+    pub chain: NamedChain,
+    /// Sender of the transfers
+    pub from_address: SenderAddress,
+    /// Recipient of the transfers
+    pub to_address: RecipientAddress,
+    /// Token transferred
+    pub token_address: TokenAddress,
+    /// First block to scan
+    pub from_block: BlockNumber,
+    /// Last block to scan
+    pub to_block: BlockNumber,
+}
+
+impl CombinedQuery {
+    /// Create a builder for constructing a query
+    pub fn builder() -> CombinedQueryBuilder {
+        CombinedQueryBuilder::new()
+    }
+
+    /// The same transfers over the blocks in `blocks`
+    ///
+    /// Accepts an inclusive range or a [`BlockInterval`](crate::BlockInterval), such as a daily
+    /// window's [`blocks`](crate::DailyBlockWindow::blocks).
+    pub fn with_blocks(mut self, blocks: impl Into<RangeInclusive<BlockNumber>>) -> Self {
+        let blocks = blocks.into();
+        self.from_block = *blocks.start();
+        self.to_block = *blocks.end();
+        self
+    }
+}
+
+/// Builder for constructing [`CombinedQuery`] instances
+///
+/// Every field must be set before [`build`](Self::build).
+#[derive(Debug, Clone, Default)]
+pub struct CombinedQueryBuilder {
+    chain: Option<NamedChain>,
+    from_address: Option<SenderAddress>,
+    to_address: Option<RecipientAddress>,
+    token_address: Option<TokenAddress>,
+    blocks: Option<RangeInclusive<BlockNumber>>,
+}
+
+impl CombinedQueryBuilder {
+    /// Create a new builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the chain to scan
+    pub fn chain(mut self, chain: NamedChain) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Set the sender of the transfers
+    pub fn from(mut self, address: impl Into<SenderAddress>) -> Self {
+        self.from_address = Some(address.into());
+        self
+    }
+
+    /// Set the recipient of the transfers
+    pub fn to(mut self, address: impl Into<RecipientAddress>) -> Self {
+        self.to_address = Some(address.into());
+        self
+    }
+
+    /// Set the token transferred
+    pub fn token(mut self, address: impl Into<TokenAddress>) -> Self {
+        self.token_address = Some(address.into());
+        self
+    }
+
+    /// Set the blocks to scan, both ends inclusive
+    pub fn blocks(mut self, blocks: impl Into<RangeInclusive<BlockNumber>>) -> Self {
+        self.blocks = Some(blocks.into());
+        self
+    }
+
+    /// Build the query
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RetrievalError::IncompleteQuery`] naming the first field
+    /// that wasn't set.
+    pub fn build(self) -> Result<CombinedQuery, RetrievalError> {
+        let missing = RetrievalError::incomplete_query;
+        let chain = self.chain.ok_or_else(|| missing("chain"))?;
+        let from_address = self.from_address.ok_or_else(|| missing("from"))?;
+        let to_address = self.to_address.ok_or_else(|| missing("to"))?;
+        let token_address = self.token_address.ok_or_else(|| missing("token"))?;
+        let blocks = self.blocks.ok_or_else(|| missing("blocks"))?;
+        Ok(CombinedQuery {
+            chain,
+            from_address,
+            to_address,
+            token_address,
+            from_block: *blocks.start(),
+            to_block: *blocks.end(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::interval::BlockInterval;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn build_requires_every_field() {
+        let builder = CombinedQuery::builder()
+            .chain(NamedChain::Mainnet)
+            .from(Address::repeat_byte(1))
+            .to(Address::repeat_byte(2))
+            .token(Address::repeat_byte(3));
+
+        let err = builder.clone().build().unwrap_err();
+        assert!(matches!(
+            err,
+            RetrievalError::IncompleteQuery { field: "blocks" }
+        ));
+
+        let query = builder.blocks(10..=20).build().unwrap();
+        assert_eq!(query.from_address.as_address(), Address::repeat_byte(1));
+        assert_eq!(query.to_address.as_address(), Address::repeat_byte(2));
+        assert_eq!(query.token_address.as_address(), Address::repeat_byte(3));
+        assert_eq!((query.from_block, query.to_block), (10, 20));
+        assert_eq!(query.with_blocks(30..=40).to_block, 40);
+        let interval = BlockInterval::new(50, 60).unwrap();
+        assert_eq!(query.with_blocks(interval).from_block, 50);
+    }
+}
@@ -0,0 +1,600 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Holder snapshots at day ends and the differences between them
+//!
+//! A [`HolderSnapshot`] records, as of the last block of a UTC day, which
+//! tokens have been transferred to an address (see
+//! [`extract_transferred_to_tokens`](crate::extract_transferred_to_tokens)),
+//! its balance of each, and the allowances it has granted (replayed by
+//! [`ApprovalAnalyzer`]). [`SnapshotDiff`] compares two snapshots and keeps
+//! only what changed.
+//!
+//! Balances are read in one batch per snapshot with
+//! [`batch_fetch_balances_at`]. A token whose `balanceOf` fails is recorded in
+//! [`HolderSnapshot::balance_failures`] instead of failing the snapshot.
+//!
+//! [`SnapshotCollector::diff`] builds the second snapshot incrementally from
+//! the first, scanning only the blocks between the two day ends.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::SnapshotCollector;
+//!
+//! let collector = SnapshotCollector::new(provider, NamedChain::Base, router)
+//!     .with_discovery_start(deployment_block);
+//! let diff = collector.diff(&windows, monday, friday).await?;
+//!
+//! for change in &diff.balances {
+//!     println!("{}: {} -> {}", change.token, change.before, change.after);
+//! }
+//! println!("new tokens: {}", diff.tokens_added);
+//! ```
+
+use std::collections::BTreeMap;
+
+use alloy_chains::NamedChain;
+use alloy_eips::BlockId;
+use alloy_network::Ethereum;
+use alloy_primitives::{Address, BlockNumber, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::{sol, SolValue};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::blocks::{BlockWindowCalculator, DailyBlockWindow};
+use crate::config::SemioscanConfig;
+use crate::errors::RetrievalError;
+use crate::events::approvals::{AllowanceKey, AllowanceTracker, ApprovalAnalyzer};
+use crate::events::discovery::extract_transferred_to_tokens_with_config;
+use crate::retrieval::balance::batch_fetch_balances_at;
+use crate::retrieval::calls::{CallCache, DailyCaller, WindowBoundary};
+use crate::types::tokens::TokenSet;
+
+sol! {
+    function balanceOf(address owner) external view returns (uint256);
+}
+
+/// State of one holder at the end of a UTC day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HolderSnapshot {
+    /// Address the snapshot describes
+    pub holder: Address,
+    /// Day the snapshot was taken at the end of
+    pub date: NaiveDate,
+    /// Last block of `date`
+    pub block: BlockNumber,
+    /// Tokens transferred to the holder up to `block`, plus any tracked
+    /// explicitly
+    pub tokens: TokenSet,
+    /// Raw balance of each token in `tokens` at `block`, except those in
+    /// `balance_failures`
+    pub balances: BTreeMap<Address, U256>,
+    /// Error of each token in `tokens` whose balance couldn't be read
+    pub balance_failures: BTreeMap<Address, String>,
+    /// Latest approved value of each allowance the holder granted up to `block`
+    pub allowances: BTreeMap<AllowanceKey, U256>,
+}
+
+/// Change in the holder's balance of one token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    /// Token contract
+    #[serde(rename = "token")]
+    pub token: Address,
+    /// Raw balance in the earlier snapshot, zero if it wasn't tracked yet
+    #[serde(rename = "before")]
+    pub before: U256,
+    /// Raw balance in the later snapshot, zero if it is no longer tracked
+    #[serde(rename = "after")]
+    pub after: U256,
+}
+
+/// Change in one allowance the holder granted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowanceChange {
+    /// The allowance
+    #[serde(rename = "key")]
+    pub key: AllowanceKey,
+    /// Approved value in the earlier snapshot, zero if never approved
+    #[serde(rename = "before")]
+    pub before: U256,
+    /// Approved value in the later snapshot
+    #[serde(rename = "after")]
+    pub after: U256,
+}
+
+/// What changed for a holder between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Address both snapshots describe
+    #[serde(rename = "holder")]
+    pub holder: Address,
+    /// Date of the earlier snapshot
+    #[serde(rename = "from_date")]
+    pub from_date: NaiveDate,
+    /// Date of the later snapshot
+    #[serde(rename = "to_date")]
+    pub to_date: NaiveDate,
+    /// Block of the earlier snapshot
+    #[serde(rename = "from_block")]
+    pub from_block: BlockNumber,
+    /// Block of the later snapshot
+    #[serde(rename = "to_block")]
+    pub to_block: BlockNumber,
+    /// Tokens in the later snapshot only
+    #[serde(rename = "tokens_added")]
+    pub tokens_added: TokenSet,
+    /// Tokens in the earlier snapshot only
+    #[serde(rename = "tokens_removed")]
+    pub tokens_removed: TokenSet,
+    /// Balances that differ, ordered by token
+    #[serde(rename = "balances")]
+    pub balances: Vec<BalanceChange>,
+    /// Tokens whose balance couldn't be read in either snapshot, left out of
+    /// `balances`
+    #[serde(
+        rename = "unreadable_balances",
+        default,
+        skip_serializing_if = "TokenSet::is_empty"
+    )]
+    pub unreadable_balances: TokenSet,
+    /// Allowances that differ, ordered by key
+    #[serde(rename = "allowances")]
+    pub allowances: Vec<AllowanceChange>,
+}
+
+impl SnapshotDiff {
+    /// Differences from `from` to `to`
+    ///
+    /// Tokens and allowances missing from one side count as zero there,
+    /// except balances that couldn't be read, which are listed in
+    /// [`unreadable_balances`](Self::unreadable_balances) instead.
+    pub fn between(from: &HolderSnapshot, to: &HolderSnapshot) -> Self {
+        let unreadable_balances: TokenSet = from
+            .balance_failures
+            .keys()
+            .chain(to.balance_failures.keys())
+            .copied()
+            .collect();
+        let balances = changes(&from.balances, &to.balances)
+            .filter(|(token, _, _)| !unreadable_balances.contains(token))
+            .map(|(token, before, after)| BalanceChange {
+                token,
+                before,
+                after,
+            })
+            .collect();
+        let allowances = changes(&from.allowances, &to.allowances)
+            .map(|(key, before, after)| AllowanceChange { key, before, after })
+            .collect();
+
+        Self {
+            holder: to.holder,
+            from_date: from.date,
+            to_date: to.date,
+            from_block: from.block,
+            to_block: to.block,
+            tokens_added: to
+                .tokens
+                .iter()
+                .filter(|token| !from.tokens.contains(token))
+                .copied()
+                .collect(),
+            tokens_removed: from
+                .tokens
+                .iter()
+                .filter(|token| !to.tokens.contains(token))
+                .copied()
+                .collect(),
+            balances,
+            unreadable_balances,
+            allowances,
+        }
+    }
+
+    /// Whether nothing changed between the snapshots
+    ///
+    /// Unreadable balances aren't counted as changes.
+    pub fn is_empty(&self) -> bool {
+        self.tokens_added.is_empty()
+            && self.tokens_removed.is_empty()
+            && self.balances.is_empty()
+            && self.allowances.is_empty()
+    }
+}
+
+/// `(key, before, after)` for keys whose values differ, missing values as zero
+fn changes<'a, K: Ord + Copy>(
+    before: &'a BTreeMap<K, U256>,
+    after: &'a BTreeMap<K, U256>,
+) -> impl Iterator<Item = (K, U256, U256)> + 'a {
+    let mut keys: Vec<K> = before.keys().chain(after.keys()).copied().collect();
+    keys.sort_unstable();
+    keys.dedup();
+    keys.into_iter().filter_map(move |key| {
+        let old = before.get(&key).copied().unwrap_or_default();
+        let new = after.get(&key).copied().unwrap_or_default();
+        (old != new).then_some((key, old, new))
+    })
+}
+
+/// Takes [`HolderSnapshot`]s of one address on one chain
+pub struct SnapshotCollector<P> {
+    provider: P,
+    chain: NamedChain,
+    holder: Address,
+    config: SemioscanConfig,
+    discovery_start: BlockNumber,
+    tokens: TokenSet,
+    calls: CallCache,
+}
+
+impl<P: Provider + Clone> SnapshotCollector<P> {
+    /// Create a collector for `holder` on `chain`
+    ///
+    /// Discovery and approval replay start at genesis; see
+    /// [`with_discovery_start`](Self::with_discovery_start).
+    pub fn new(provider: P, chain: NamedChain, holder: Address) -> Self {
+        Self {
+            provider,
+            chain,
+            holder,
+            config: SemioscanConfig::default(),
+            discovery_start: 0,
+            tokens: TokenSet::new(),
+            calls: CallCache::new(),
+        }
+    }
+
+    /// Use `config` for chunking and rate limiting log scans
+    pub fn with_config(mut self, config: SemioscanConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// First block scanned for transfers and approvals
+    ///
+    /// Set this to the holder's deployment block (see
+    /// [`DeploymentCache`](crate::DeploymentCache)) to skip history before it
+    /// existed.
+    pub fn with_discovery_start(mut self, block: BlockNumber) -> Self {
+        self.discovery_start = block;
+        self
+    }
+
+    /// Always report the balance of `token`, whether or not it was discovered
+    pub fn with_token(mut self, token: Address) -> Self {
+        self.tokens.insert(token);
+        self
+    }
+
+    /// Cache balance calls in `cache`, which may be shared
+    pub fn with_call_cache(mut self, cache: CallCache) -> Self {
+        self.calls = cache;
+        self
+    }
+
+    /// Snapshot the holder at the end of `date`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the day's window can't be computed or a log scan fails. A
+    /// failed `balanceOf` call is recorded in
+    /// [`HolderSnapshot::balance_failures`].
+    pub async fn snapshot<W: Provider>(
+        &self,
+        windows: &BlockWindowCalculator<W>,
+        date: NaiveDate,
+    ) -> Result<HolderSnapshot, RetrievalError> {
+        let window = windows.get_daily_window(self.chain, date).await?;
+        let (snapshot, _) = self
+            .extend(
+                date,
+                &window,
+                self.tokens.clone(),
+                AllowanceTracker::new(),
+                self.discovery_start,
+            )
+            .await?;
+        Ok(snapshot)
+    }
+
+    /// Snapshot the holder at the end of `from` and `to` and compare them
+    ///
+    /// The `to` snapshot reuses the `from` snapshot's discovered tokens and
+    /// allowances and only scans the blocks after it.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`snapshot`](Self::snapshot), or with
+    /// [`RetrievalError::ConversionFailed`] if `to` is before `from`.
+    pub async fn diff<W: Provider>(
+        &self,
+        windows: &BlockWindowCalculator<W>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<SnapshotDiff, RetrievalError> {
+        if to < from {
+            return Err(RetrievalError::conversion_failed(format!(
+                "snapshot diff from {from} to earlier date {to}"
+            )));
+        }
+        let from_window = windows.get_daily_window(self.chain, from).await?;
+        let to_window = windows.get_daily_window(self.chain, to).await?;
+
+        let (before, tracker) = self
+            .extend(
+                from,
+                &from_window,
+                self.tokens.clone(),
+                AllowanceTracker::new(),
+                self.discovery_start,
+            )
+            .await?;
+        let (after, _) = self
+            .extend(
+                to,
+                &to_window,
+                before.tokens.clone(),
+                tracker,
+                (from_window.end_block + 1).max(self.discovery_start),
+            )
+            .await?;
+
+        let diff = SnapshotDiff::between(&before, &after);
+        info!(
+            chain = %self.chain,
+            holder = ?self.holder,
+            from = %from,
+            to = %to,
+            tokens_added = diff.tokens_added.len(),
+            balance_changes = diff.balances.len(),
+            allowance_changes = diff.allowances.len(),
+            "Computed snapshot diff"
+        );
+        Ok(diff)
+    }
+
+    /// Scan `[from_block, window.end_block]` on top of `tokens` and `tracker`,
+    /// then read balances at the end of `window`
+    ///
+    /// Balances missing from the call cache are fetched in one batch and
+    /// cached.
+    async fn extend(
+        &self,
+        date: NaiveDate,
+        window: &DailyBlockWindow,
+        mut tokens: TokenSet,
+        mut tracker: AllowanceTracker,
+        from_block: BlockNumber,
+    ) -> Result<(HolderSnapshot, AllowanceTracker), RetrievalError> {
+        if from_block <= window.end_block {
+            let discovered = extract_transferred_to_tokens_with_config(
+                &self.provider,
+                self.chain,
+                self.holder,
+                from_block,
+                window.end_block,
+                &self.config,
+            )
+            .await?;
+            for token in discovered {
+                tokens.insert(token);
+            }
+            ApprovalAnalyzer::new(self.provider.clone(), self.config.clone())
+                .replay_into(
+                    &mut tracker,
+                    self.chain,
+                    Some(self.holder),
+                    &[],
+                    from_block,
+                    window.end_block,
+                )
+                .await?;
+        }
+
+        let caller =
+            DailyCaller::new(self.provider.clone(), self.chain).with_cache(self.calls.clone());
+        let call = balanceOfCall { owner: self.holder };
+        let mut balances = BTreeMap::new();
+        let mut queries = Vec::new();
+        for &token in &tokens {
+            match caller.cached_in_window(window, WindowBoundary::End, token, &call)? {
+                Some(balance) => {
+                    balances.insert(token, balance);
+                }
+                None => queries.push((token, self.holder)),
+            }
+        }
+
+        let mut balance_failures = BTreeMap::new();
+        let block = BlockId::number(window.end_block);
+        for result in batch_fetch_balances_at::<Ethereum, _>(&self.provider, &queries, block).await
+        {
+            match result {
+                Ok((token, _, balance)) => {
+                    caller.cache_in_window(
+                        window,
+                        WindowBoundary::End,
+                        token,
+                        &call,
+                        balance.abi_encode().into(),
+                    )?;
+                    balances.insert(token, balance);
+                }
+                Err((token, _, error)) => {
+                    balance_failures.insert(token, error);
+                }
+            }
+        }
+
+        let allowances = tracker
+            .for_owner(self.holder)
+            .map(|(key, state)| (*key, state.value))
+            .collect();
+
+        let snapshot = HolderSnapshot {
+            holder: self.holder,
+            date,
+            block: window.end_block,
+            tokens,
+            balances,
+            balance_failures,
+            allowances,
+        };
+        Ok((snapshot, tracker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const HOLDER: Address = address!("00000000000000000000000000000000000000aa");
+    const SPENDER: Address = address!("00000000000000000000000000000000000000bb");
+    const USDC: Address = address!("00000000000000000000000000000000000000c1");
+    const WETH: Address = address!("00000000000000000000000000000000000000c2");
+
+    fn snapshot(day: u32, balances: &[(Address, u64)], allowance: Option<u64>) -> HolderSnapshot {
+        HolderSnapshot {
+            holder: HOLDER,
+            date: NaiveDate::from_ymd_opt(2025, 10, day).unwrap(),
+            block: u64::from(day) * 1_000,
+            tokens: balances.iter().map(|(token, _)| *token).collect(),
+            balances: balances
+                .iter()
+                .map(|(token, balance)| (*token, U256::from(*balance)))
+                .collect(),
+            balance_failures: BTreeMap::new(),
+            allowances: allowance
+                .map(|value| {
+                    let key = AllowanceKey {
+                        owner: HOLDER,
+                        spender: SPENDER,
+                        token: USDC,
+                    };
+                    (key, U256::from(value))
+                })
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_keeps_only_changes() {
+        let before = snapshot(1, &[(USDC, 100)], Some(50));
+        let after = snapshot(2, &[(USDC, 100), (WETH, 7)], Some(0));
+
+        let diff = SnapshotDiff::between(&before, &after);
+
+        assert_eq!(diff.tokens_added, TokenSet::from_iter([WETH]));
+        assert!(diff.tokens_removed.is_empty());
+        assert_eq!(
+            diff.balances,
+            vec![BalanceChange {
+                token: WETH,
+                before: U256::ZERO,
+                after: U256::from(7),
+            }]
+        );
+        assert_eq!(diff.allowances.len(), 1);
+        assert_eq!(diff.allowances[0].before, U256::from(50));
+        assert_eq!(diff.allowances[0].after, U256::ZERO);
+        assert_eq!((diff.from_block, diff.to_block), (1_000, 2_000));
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_an_empty_diff() {
+        let snapshot = snapshot(1, &[(USDC, 100)], Some(50));
+        assert!(SnapshotDiff::between(&snapshot, &snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_unreadable_balances_are_not_reported_as_changes() {
+        let before = snapshot(1, &[(USDC, 100), (WETH, 7)], None);
+        let mut after = snapshot(2, &[(USDC, 100)], None);
+        after.tokens.insert(WETH);
+        after
+            .balance_failures
+            .insert(WETH, "execution reverted".to_string());
+
+        let diff = SnapshotDiff::between(&before, &after);
+
+        assert!(diff.balances.is_empty());
+        assert_eq!(diff.unreadable_balances, TokenSet::from_iter([WETH]));
+        assert!(diff.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_balances_are_batched_cached_and_failures_recorded() {
+        use crate::blocks::UnixTimestamp;
+        use alloy_primitives::Bytes;
+        use alloy_provider::{ProviderBuilder, RootProvider};
+        use alloy_transport::mock::Asserter;
+
+        let asserter = Asserter::new();
+        let provider: RootProvider =
+            ProviderBuilder::default().connect_mocked_client(asserter.clone());
+        let calls = CallCache::new();
+        let collector = SnapshotCollector::new(provider.clone(), NamedChain::Mainnet, HOLDER)
+            .with_call_cache(calls.clone());
+        // 2025-10-10
+        let window = DailyBlockWindow::new(
+            1_000,
+            2_000,
+            UnixTimestamp(1_760_054_400),
+            UnixTimestamp(1_760_140_800),
+        )
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+        let tokens = TokenSet::from_iter([USDC, WETH]);
+
+        // USDC is cached, so only WETH's balance is fetched; starting after
+        // the window skips the log scans
+        DailyCaller::new(provider, NamedChain::Mainnet)
+            .with_cache(calls.clone())
+            .cache_in_window(
+                &window,
+                WindowBoundary::End,
+                USDC,
+                &balanceOfCall { owner: HOLDER },
+                U256::from(5).abi_encode().into(),
+            )
+            .unwrap();
+        asserter.push_failure_msg("execution reverted");
+        let (snapshot, _) = collector
+            .extend(
+                date,
+                &window,
+                tokens.clone(),
+                AllowanceTracker::new(),
+                2_001,
+            )
+            .await
+            .unwrap();
+        assert_eq!(snapshot.balances, BTreeMap::from([(USDC, U256::from(5))]));
+        assert_eq!(
+            snapshot
+                .balance_failures
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![WETH]
+        );
+        assert_eq!(calls.len(), 1);
+
+        asserter.push_success(&Bytes::from(U256::from(9).abi_encode()));
+        let (snapshot, _) = collector
+            .extend(date, &window, tokens, AllowanceTracker::new(), 2_001)
+            .await
+            .unwrap();
+        assert_eq!(snapshot.balances[&WETH], U256::from(9));
+        assert!(snapshot.balance_failures.is_empty());
+        assert_eq!(calls.len(), 2);
+    }
+}
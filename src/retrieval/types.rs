@@ -6,27 +6,68 @@
 
 use alloy_chains::NamedChain;
 use alloy_primitives::{Address, BlockNumber, TxHash, U256};
+use chrono::{DateTime, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use crate::blocks::window::DailyBlockWindow;
+use crate::events::transfer_tax::TransferTax;
 use crate::types::config::TransactionCount;
 use crate::types::gas::{GasAmount, GasPrice};
+use crate::types::warnings::{extend_warnings, push_warning, ScanWarning};
 
 /// Data for a single transaction including gas and transferred amount.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GasAndAmountForTx {
     /// Transaction hash for the enriched transfer.
+    #[serde(rename = "tx_hash")]
     pub tx_hash: TxHash,
     /// Block number containing the transaction.
+    #[serde(rename = "block_number")]
     pub block_number: BlockNumber,
-    /// L2 gas used by the transaction.
+    /// Timestamp of that block, when the provider includes it in logs.
+    #[serde(
+        default,
+        rename = "block_timestamp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub block_timestamp: Option<u64>,
+    /// Position of the transaction within its block, when the provider includes it.
+    #[serde(default, rename = "tx_index", skip_serializing_if = "Option::is_none")]
+    pub tx_index: Option<u64>,
+    /// Position of the matched log within its block, when the provider includes it.
+    #[serde(default, rename = "log_index", skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
+    /// Contract that emitted the matched log.
+    #[serde(
+        default,
+        rename = "token_address",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub token_address: Option<Address>,
+    /// L2 gas used by the transaction, or the share attributed to this
+    /// transfer under [`GasAttribution`].
+    #[serde(rename = "gas_used")]
     pub gas_used: GasAmount,
     /// Effective L2 gas price charged for the transaction.
+    #[serde(rename = "effective_gas_price")]
     pub effective_gas_price: GasPrice,
+    /// EIP-1559 base fee of the transaction's block, if it was looked up.
+    #[serde(
+        default,
+        rename = "base_fee_per_gas",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base_fee_per_gas: Option<GasPrice>,
     /// Optional L1 data fee charged by L2 chains that expose it in the receipt.
+    #[serde(rename = "l1_fee")]
     pub l1_fee: Option<U256>,
     /// Additional blob gas cost for EIP-4844 transactions.
+    #[serde(rename = "blob_gas_cost")]
     pub blob_gas_cost: U256,
     /// ERC-20 amount transferred by the decoded log this transaction matched.
+    #[serde(rename = "transferred_amount")]
     pub transferred_amount: U256,
 }
 
@@ -34,10 +75,181 @@ impl GasAndAmountForTx {
     /// Calculates the total gas cost for this transaction, including L2 gas, L1 fee, and blob gas.
     #[must_use]
     pub fn total_gas_cost(&self) -> U256 {
-        let l2_execution_cost = self.gas_used * self.effective_gas_price;
+        let l2_execution_cost = (self.gas_used * self.effective_gas_price).as_u256();
         let total_cost = l2_execution_cost.saturating_add(self.blob_gas_cost);
         total_cost.saturating_add(self.l1_fee.unwrap_or_default())
     }
+
+    /// Portion of the L2 execution cost paid as base fee, if the base fee is known
+    #[must_use]
+    pub fn base_fee_burned(&self) -> Option<U256> {
+        self.fee_split().map(|(base_fee, _)| base_fee)
+    }
+
+    /// Portion of the L2 execution cost paid as priority fee, if the base fee is known
+    #[must_use]
+    pub fn priority_fee_paid(&self) -> Option<U256> {
+        self.fee_split().map(|(_, priority_fee)| priority_fee)
+    }
+
+    fn fee_split(&self) -> Option<(U256, U256)> {
+        let base_fee = self.base_fee_per_gas?;
+        Some(self.gas_used.fee_split(self.effective_gas_price, base_fee))
+    }
+
+    /// Identifies the transfer this row was built from
+    ///
+    /// Two rows with the same key describe the same log, so a
+    /// [`CombinedDataResult`] keeps only the first of them. Rows without a
+    /// `log_index` are keyed by transaction hash alone.
+    #[must_use]
+    pub fn dedup_key(&self) -> (TxHash, Option<u64>) {
+        (self.tx_hash, self.log_index)
+    }
+
+    /// Position of this transfer on chain, for resuming a scan after it
+    ///
+    /// `None` if the provider didn't return the transaction or log index.
+    #[must_use]
+    pub fn cursor(&self) -> Option<TransferCursor> {
+        Some(TransferCursor::new(
+            self.block_number,
+            self.tx_index?,
+            self.log_index?,
+        ))
+    }
+
+    /// Reduces the gas fields to this transfer's share of the transaction
+    ///
+    /// `position` is the transfer's place, by log index, among the `count`
+    /// matching transfers in its transaction. Splitting rounds down and gives
+    /// the remainder to the first transfer, so the shares always add up to
+    /// the transaction's gas.
+    pub fn attribute_gas(&mut self, policy: GasAttribution, position: usize, count: usize) {
+        if count <= 1 {
+            return;
+        }
+        match policy {
+            GasAttribution::Full => {}
+            GasAttribution::FirstLogOnly => {
+                if position > 0 {
+                    self.gas_used = GasAmount::new(0);
+                    self.l1_fee = self.l1_fee.map(|_| U256::ZERO);
+                    self.blob_gas_cost = U256::ZERO;
+                }
+            }
+            GasAttribution::SplitEvenly => {
+                let share = |total: U256| {
+                    let count = U256::from(count);
+                    let base = total / count;
+                    if position == 0 {
+                        base + total % count
+                    } else {
+                        base
+                    }
+                };
+                self.gas_used = GasAmount::from(share(self.gas_used.as_u256()));
+                self.l1_fee = self.l1_fee.map(share);
+                self.blob_gas_cost = share(self.blob_gas_cost);
+            }
+        }
+    }
+}
+
+/// Position of a transfer on chain: its block, transaction index and log index
+///
+/// Cursors order transfers the way the chain does, so they give stable
+/// pagination over transfer-level results. Take one from the last transfer
+/// of a page with [`CombinedDataResult::next_cursor`] and pass it to
+/// [`CombinedCalculator::resume_after`](super::CombinedCalculator::resume_after)
+/// to fetch the next page without rescanning from the start of the range.
+/// [`CombinedCalculator::with_page_limit`](super::CombinedCalculator::with_page_limit)
+/// bounds the number of transfers in each page.
+///
+/// Fields compare in declaration order, so the derived `Ord` is chain order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TransferCursor {
+    /// Block containing the transfer
+    #[serde(rename = "block_number")]
+    pub block_number: BlockNumber,
+    /// Position of the transfer's transaction within its block
+    #[serde(rename = "tx_index")]
+    pub tx_index: u64,
+    /// Position of the transfer's log within its block
+    #[serde(rename = "log_index")]
+    pub log_index: u64,
+}
+
+impl TransferCursor {
+    /// Cursor at the log `log_index` of transaction `tx_index` in `block_number`
+    #[must_use]
+    pub const fn new(block_number: BlockNumber, tx_index: u64, log_index: u64) -> Self {
+        Self {
+            block_number,
+            tx_index,
+            log_index,
+        }
+    }
+
+    /// Whether a log at this position was already returned, so a resumed
+    /// scan skips it
+    ///
+    /// Logs in the cursor's block without a transaction or log index can't be
+    /// placed and are kept.
+    pub(crate) fn covers(
+        &self,
+        block_number: BlockNumber,
+        tx_index: Option<u64>,
+        log_index: Option<u64>,
+    ) -> bool {
+        match (tx_index, log_index) {
+            _ if block_number != self.block_number => block_number < self.block_number,
+            (Some(tx_index), Some(log_index)) => {
+                Self::new(block_number, tx_index, log_index) <= *self
+            }
+            _ => false,
+        }
+    }
+}
+
+/// How a transaction's gas is attributed when several of its logs match
+///
+/// A transaction that makes two matching transfers is otherwise reported as
+/// two rows each carrying the full gas cost, which double-counts the gas in
+/// [`CombinedDataResult`] totals.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GasAttribution {
+    /// Every transfer carries the transaction's full gas cost
+    #[default]
+    Full,
+    /// The gas cost is divided evenly between the transaction's transfers
+    SplitEvenly,
+    /// The first transfer (by log index) carries the gas cost, the rest none
+    FirstLogOnly,
+}
+
+impl GasAttribution {
+    /// Position and count of each transfer among its transaction's transfers,
+    /// keyed by [`GasAndAmountForTx::dedup_key`]
+    pub(crate) fn positions(
+        keys: impl IntoIterator<Item = (TxHash, Option<u64>)>,
+    ) -> HashMap<(TxHash, Option<u64>), (usize, usize)> {
+        let mut by_tx: HashMap<TxHash, Vec<Option<u64>>> = HashMap::new();
+        for (tx_hash, log_index) in keys {
+            by_tx.entry(tx_hash).or_default().push(log_index);
+        }
+        let mut positions = HashMap::new();
+        for (tx_hash, mut log_indexes) in by_tx {
+            log_indexes.sort_unstable();
+            log_indexes.dedup();
+            let count = log_indexes.len();
+            for (position, log_index) in log_indexes.into_iter().enumerate() {
+                positions.insert((tx_hash, log_index), (position, count));
+            }
+        }
+        positions
+    }
 }
 
 /// Which follow-up RPC lookup failed while enriching a decoded transfer log.
@@ -79,6 +291,12 @@ pub struct CombinedDataLookupAttempt {
 pub struct CombinedDataLookupFailure {
     pub tx_hash: TxHash,
     pub block_number: BlockNumber,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_index: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
     pub transfer_value: U256,
     pub attempts: Vec<CombinedDataLookupAttempt>,
 }
@@ -139,23 +357,143 @@ impl CombinedDataRetrievalMetadata {
 }
 
 /// Aggregated result for combined data retrieval over a block range.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+///
+/// Field names are pinned with explicit `serde(rename)` attributes and form a
+/// stable storage format; renaming a Rust field does not change the JSON key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CombinedDataResult {
+    #[serde(rename = "chain")]
     pub chain: NamedChain,
+    #[serde(rename = "from_address")]
     pub from_address: Address,
+    #[serde(rename = "to_address")]
     pub to_address: Address,
+    #[serde(rename = "token_address")]
     pub token_address: Address,
+    #[serde(rename = "total_l2_execution_cost")]
     pub total_l2_execution_cost: U256,
+    #[serde(rename = "total_blob_gas_cost")]
     pub total_blob_gas_cost: U256,
+    #[serde(rename = "total_l1_fee")]
     pub total_l1_fee: U256,
+    #[serde(rename = "overall_total_gas_cost")]
     pub overall_total_gas_cost: U256,
+    #[serde(rename = "total_amount_transferred")]
     pub total_amount_transferred: U256,
+    /// Sum of [`GasAndAmountForTx::base_fee_burned`] over transactions with a known base fee
+    #[serde(default, rename = "total_base_fee_burned")]
+    pub total_base_fee_burned: U256,
+    /// Sum of [`GasAndAmountForTx::priority_fee_paid`] over transactions with a known base fee
+    #[serde(default, rename = "total_priority_fee_paid")]
+    pub total_priority_fee_paid: U256,
+    #[serde(rename = "transaction_count")]
     pub transaction_count: TransactionCount,
+    #[serde(rename = "transactions_data")]
     pub transactions_data: Vec<GasAndAmountForTx>,
-    #[serde(default)]
+    #[serde(default, rename = "retrieval_metadata")]
     pub retrieval_metadata: CombinedDataRetrievalMetadata,
+    /// Whether the calculation was cancelled before covering the whole range
+    ///
+    /// Omitted from the serialized form when `false`.
+    #[serde(
+        default,
+        rename = "cancelled",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub cancelled: bool,
+    /// Logs and transactions left out of the totals, including the lookups
+    /// in [`retrieval_metadata`](Self::retrieval_metadata)'s partial failures
+    ///
+    /// Omitted from the serialized form when empty.
+    #[serde(default, rename = "warnings", skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ScanWarning>,
+    /// Share of the emitted amounts the recipient actually received, when
+    /// [`CombinedCalculator::with_transfer_tax`](super::CombinedCalculator::with_transfer_tax)
+    /// detected it
+    ///
+    /// With [`TransferTaxMode::Adjust`](crate::TransferTaxMode::Adjust) the
+    /// transferred amounts have already been scaled by it. Omitted from the
+    /// serialized form when `None`.
+    #[serde(
+        default,
+        rename = "transfer_tax",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transfer_tax: Option<TransferTax>,
+    /// Whether the scan stopped after the page limit set with
+    /// [`CombinedCalculator::with_page_limit`](super::CombinedCalculator::with_page_limit)
+    ///
+    /// More transfers may follow [`next_cursor`](Self::next_cursor) in the
+    /// range. Omitted from the serialized form when `false`.
+    #[serde(
+        default,
+        rename = "page_limit_reached",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub page_limit_reached: bool,
+    #[serde(skip)]
+    seen: TransactionKeys,
+}
+
+/// Dedup keys of `transactions_data`
+///
+/// `indexed` counts the leading rows whose keys are in `keys`. Rows pushed
+/// onto the public `transactions_data` directly are indexed on the next
+/// insert, and the set is only rebuilt from scratch if rows were removed.
+#[derive(Debug, Clone, Default)]
+struct TransactionKeys {
+    keys: HashSet<(TxHash, Option<u64>)>,
+    indexed: usize,
 }
 
+impl PartialEq for CombinedDataResult {
+    fn eq(&self, other: &Self) -> bool {
+        // The dedup index is derived from `transactions_data`, so comparing the
+        // rows compares their keys; a deserialized result has not built it yet
+        let Self {
+            chain,
+            from_address,
+            to_address,
+            token_address,
+            total_l2_execution_cost,
+            total_blob_gas_cost,
+            total_l1_fee,
+            overall_total_gas_cost,
+            total_amount_transferred,
+            total_base_fee_burned,
+            total_priority_fee_paid,
+            transaction_count,
+            transactions_data,
+            retrieval_metadata,
+            cancelled,
+            warnings,
+            transfer_tax,
+            page_limit_reached,
+            seen: _,
+        } = self;
+        *chain == other.chain
+            && *from_address == other.from_address
+            && *to_address == other.to_address
+            && *token_address == other.token_address
+            && *total_l2_execution_cost == other.total_l2_execution_cost
+            && *total_blob_gas_cost == other.total_blob_gas_cost
+            && *total_l1_fee == other.total_l1_fee
+            && *overall_total_gas_cost == other.overall_total_gas_cost
+            && *total_amount_transferred == other.total_amount_transferred
+            && *total_base_fee_burned == other.total_base_fee_burned
+            && *total_priority_fee_paid == other.total_priority_fee_paid
+            && *transaction_count == other.transaction_count
+            && *transactions_data == other.transactions_data
+            && *retrieval_metadata == other.retrieval_metadata
+            && *cancelled == other.cancelled
+            && *warnings == other.warnings
+            && *transfer_tax == other.transfer_tax
+            && *page_limit_reached == other.page_limit_reached
+    }
+}
+
+impl Eq for CombinedDataResult {}
+
 impl CombinedDataResult {
     #[must_use]
     pub fn new(
@@ -174,14 +512,52 @@ impl CombinedDataResult {
             total_l1_fee: U256::ZERO,
             overall_total_gas_cost: U256::ZERO,
             total_amount_transferred: U256::ZERO,
+            total_base_fee_burned: U256::ZERO,
+            total_priority_fee_paid: U256::ZERO,
             transaction_count: TransactionCount::new(0),
             transactions_data: Vec::new(),
             retrieval_metadata: CombinedDataRetrievalMetadata::default(),
+            cancelled: false,
+            warnings: Vec::new(),
+            transfer_tax: None,
+            page_limit_reached: false,
+            seen: TransactionKeys::default(),
+        }
+    }
+
+    /// Adds a transaction's data to the totals
+    ///
+    /// Returns `false`, leaving the result unchanged, if a row with the same
+    /// [`dedup_key`](GasAndAmountForTx::dedup_key) is already present.
+    pub fn add_transaction_data(&mut self, data: GasAndAmountForTx) -> bool {
+        self.index_transactions();
+        if !self.seen.keys.insert(data.dedup_key()) {
+            return false;
+        }
+
+        self.add_totals(&data);
+        self.transactions_data.push(data);
+        self.seen.indexed += 1;
+        true
+    }
+
+    /// Bring the dedup index up to date with `transactions_data`
+    ///
+    /// The index is not serialized and `transactions_data` is public, so rows
+    /// may have been added or removed without it.
+    fn index_transactions(&mut self) {
+        if self.seen.indexed > self.transactions_data.len() {
+            self.seen = TransactionKeys::default();
         }
+        for data in &self.transactions_data[self.seen.indexed..] {
+            self.seen.keys.insert(data.dedup_key());
+        }
+        self.seen.indexed = self.transactions_data.len();
     }
 
-    pub fn add_transaction_data(&mut self, data: GasAndAmountForTx) {
-        let l2_execution_cost = data.gas_used * data.effective_gas_price;
+    /// Add one transaction's gas and amount to the totals
+    fn add_totals(&mut self, data: &GasAndAmountForTx) {
+        let l2_execution_cost = (data.gas_used * data.effective_gas_price).as_u256();
         self.total_l2_execution_cost = self
             .total_l2_execution_cost
             .saturating_add(l2_execution_cost);
@@ -195,37 +571,220 @@ impl CombinedDataResult {
         self.total_amount_transferred = self
             .total_amount_transferred
             .saturating_add(data.transferred_amount);
+        if let Some((base_fee, priority_fee)) = data.fee_split() {
+            self.total_base_fee_burned = self.total_base_fee_burned.saturating_add(base_fee);
+            self.total_priority_fee_paid =
+                self.total_priority_fee_paid.saturating_add(priority_fee);
+        }
         self.transaction_count += TransactionCount::new(1);
-        self.transactions_data.push(data);
     }
 
     /// Merge another result into this one (for combining results from multiple block ranges)
+    ///
+    /// `other`'s totals are added, less those of its transactions already
+    /// present here (see [`add_transaction_data`](Self::add_transaction_data)).
+    /// Merging overlapping ranges therefore doesn't double-count the overlap,
+    /// merging the same result twice is a no-op for the totals, and totals
+    /// of transfers `other` doesn't list in `transactions_data` are kept.
     pub fn merge(&mut self, other: &CombinedDataResult) {
-        self.total_l2_execution_cost = self
-            .total_l2_execution_cost
-            .saturating_add(other.total_l2_execution_cost);
-        self.total_blob_gas_cost = self
-            .total_blob_gas_cost
-            .saturating_add(other.total_blob_gas_cost);
-        self.total_l1_fee = self.total_l1_fee.saturating_add(other.total_l1_fee);
-        self.overall_total_gas_cost = self
-            .overall_total_gas_cost
-            .saturating_add(other.overall_total_gas_cost);
-        self.total_amount_transferred = self
-            .total_amount_transferred
-            .saturating_add(other.total_amount_transferred);
-        self.transaction_count += other.transaction_count;
-        self.transactions_data
-            .extend(other.transactions_data.iter().cloned());
+        self.index_transactions();
+        let mut overlap = CombinedDataResult::new(
+            self.chain,
+            self.from_address,
+            self.to_address,
+            self.token_address,
+        );
+        for tx in &other.transactions_data {
+            if self.seen.keys.insert(tx.dedup_key()) {
+                self.transactions_data.push(tx.clone());
+                self.seen.indexed += 1;
+            } else {
+                overlap.add_totals(tx);
+            }
+        }
+
+        let remaining = |total: U256, overlap: U256| total.saturating_sub(overlap);
+        self.total_l2_execution_cost = self.total_l2_execution_cost.saturating_add(remaining(
+            other.total_l2_execution_cost,
+            overlap.total_l2_execution_cost,
+        ));
+        self.total_blob_gas_cost = self.total_blob_gas_cost.saturating_add(remaining(
+            other.total_blob_gas_cost,
+            overlap.total_blob_gas_cost,
+        ));
+        self.total_l1_fee = self
+            .total_l1_fee
+            .saturating_add(remaining(other.total_l1_fee, overlap.total_l1_fee));
+        self.overall_total_gas_cost = self.overall_total_gas_cost.saturating_add(remaining(
+            other.overall_total_gas_cost,
+            overlap.overall_total_gas_cost,
+        ));
+        self.total_amount_transferred = self.total_amount_transferred.saturating_add(remaining(
+            other.total_amount_transferred,
+            overlap.total_amount_transferred,
+        ));
+        self.total_base_fee_burned = self.total_base_fee_burned.saturating_add(remaining(
+            other.total_base_fee_burned,
+            overlap.total_base_fee_burned,
+        ));
+        self.total_priority_fee_paid = self.total_priority_fee_paid.saturating_add(remaining(
+            other.total_priority_fee_paid,
+            overlap.total_priority_fee_paid,
+        ));
+        self.transaction_count += TransactionCount::new(
+            other
+                .transaction_count
+                .as_usize()
+                .saturating_sub(overlap.transaction_count.as_usize()),
+        );
         self.retrieval_metadata.merge(&other.retrieval_metadata);
+        self.cancelled |= other.cancelled;
+        self.page_limit_reached |= other.page_limit_reached;
+        self.transfer_tax = self.transfer_tax.or(other.transfer_tax);
+        extend_warnings(&mut self.warnings, &other.warnings);
+    }
+
+    /// Cursor of the last transfer in chain order, to resume after this
+    /// result with [`CombinedCalculator::resume_after`](super::CombinedCalculator::resume_after)
+    ///
+    /// `None` if there are no transfers or none carries a transaction and log
+    /// index.
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<TransferCursor> {
+        self.transactions_data
+            .iter()
+            .filter_map(GasAndAmountForTx::cursor)
+            .max()
     }
 
+    /// Whether any transfers are missing, either skipped after failed lookups
+    /// or never reached because the calculation was cancelled
     #[must_use]
     pub fn is_partial(&self) -> bool {
-        self.retrieval_metadata.has_partial_failures()
+        self.cancelled || self.retrieval_metadata.has_partial_failures()
+    }
+
+    /// Splits a multi-day result into one result per daily window, keyed by UTC date
+    ///
+    /// Lets one scan over a long block range produce daily figures without
+    /// rescanning. A transfer is attributed by its block timestamp when the
+    /// provider returned one, and by its block number otherwise. Transfers
+    /// outside every window are dropped, and each window gets an entry even
+    /// when it has no transfers.
+    ///
+    /// Skipped transfers and warnings are split the same way, with a gap
+    /// clipped to each window it overlaps. Warnings without a block go to
+    /// every day. Fallback counters can't be attributed to a day and are left
+    /// at zero, and every day is marked `cancelled` if this result was.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let query = query.with_blocks(first.start_block..=last.end_block);
+    /// let result = calculator.calculate(&query).await?;
+    /// for (date, day) in result.split_by_day(&[first, second, last]) {
+    ///     println!("{date}: {} transfers", day.transaction_count);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn split_by_day(&self, windows: &[DailyBlockWindow]) -> BTreeMap<NaiveDate, Self> {
+        let mut days: Vec<Self> = windows
+            .iter()
+            .map(|_| {
+                let mut day = Self::new(
+                    self.chain,
+                    self.from_address,
+                    self.to_address,
+                    self.token_address,
+                );
+                day.cancelled = self.cancelled;
+                day.transfer_tax = self.transfer_tax;
+                day
+            })
+            .collect();
+
+        for tx in &self.transactions_data {
+            if let Some(index) = window_index(windows, tx.block_number, tx.block_timestamp) {
+                days[index].add_transaction_data(tx.clone());
+            }
+        }
+        for failure in &self.retrieval_metadata.partial_failures {
+            if let Some(index) =
+                window_index(windows, failure.block_number, failure.block_timestamp)
+            {
+                days[index]
+                    .retrieval_metadata
+                    .record_partial_failure(failure.clone());
+            }
+        }
+        for warning in &self.warnings {
+            for (window, day) in windows.iter().zip(&mut days) {
+                let clipped = match warning {
+                    ScanWarning::SkippedLog {
+                        block_number: Some(block),
+                        ..
+                    }
+                    | ScanWarning::FailedReceipt {
+                        block_number: Some(block),
+                        ..
+                    }
+                    | ScanWarning::MissingBaseFee {
+                        block_number: block,
+                        ..
+                    } => (window.start_block..=window.end_block)
+                        .contains(block)
+                        .then(|| warning.clone()),
+                    ScanWarning::Gap {
+                        from_block,
+                        to_block,
+                        reason,
+                    } => (*from_block <= window.end_block && *to_block >= window.start_block).then(
+                        || {
+                            ScanWarning::gap(
+                                (*from_block).max(window.start_block),
+                                (*to_block).min(window.end_block),
+                                reason,
+                            )
+                        },
+                    ),
+                    _ => Some(warning.clone()),
+                };
+                if let Some(warning) = clipped {
+                    push_warning(&mut day.warnings, warning);
+                }
+            }
+        }
+
+        let mut by_date: BTreeMap<NaiveDate, Self> = BTreeMap::new();
+        for (window, day) in windows.iter().zip(days) {
+            let Some(date) = DateTime::from_timestamp(window.start_ts.0, 0) else {
+                continue;
+            };
+            match by_date.entry(date.date_naive()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(day);
+                }
+                Entry::Occupied(mut entry) => entry.get_mut().merge(&day),
+            }
+        }
+        by_date
     }
 }
 
+/// Index of the window containing a block, by timestamp when known
+pub(crate) fn window_index(
+    windows: &[DailyBlockWindow],
+    block_number: BlockNumber,
+    block_timestamp: Option<u64>,
+) -> Option<usize> {
+    windows.iter().position(|window| match block_timestamp {
+        Some(timestamp) => i64::try_from(timestamp).is_ok_and(|timestamp| {
+            (window.start_ts.0..window.end_ts_exclusive.0).contains(&timestamp)
+        }),
+        None => (window.start_block..=window.end_block).contains(&block_number),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,8 +800,13 @@ mod tests {
         GasAndAmountForTx {
             tx_hash: TxHash::ZERO,
             block_number: 1000,
+            block_timestamp: None,
+            tx_index: None,
+            log_index: None,
+            token_address: None,
             gas_used: GasAmount::from(gas_used),
             effective_gas_price: GasPrice::from(gas_price),
+            base_fee_per_gas: None,
             l1_fee: l1_fee.map(U256::from),
             blob_gas_cost: U256::from(blob_gas_cost),
             transferred_amount: U256::from(transferred_amount),
@@ -406,6 +970,9 @@ mod tests {
             .record_partial_failure(CombinedDataLookupFailure {
                 tx_hash: TxHash::repeat_byte(0x11),
                 block_number: 123,
+                block_timestamp: None,
+                tx_index: None,
+                log_index: None,
                 transfer_value: U256::from(42_u64),
                 attempts: vec![CombinedDataLookupAttempt {
                     pass: CombinedDataLookupPass::Batch,
@@ -433,6 +1000,9 @@ mod tests {
             partial_failures: vec![CombinedDataLookupFailure {
                 tx_hash: TxHash::repeat_byte(0x22),
                 block_number: 456,
+                block_timestamp: None,
+                tx_index: None,
+                log_index: None,
                 transfer_value: U256::from(7_u64),
                 attempts: vec![CombinedDataLookupAttempt {
                     pass: CombinedDataLookupPass::Batch,
@@ -469,6 +1039,9 @@ mod tests {
             .record_partial_failure(CombinedDataLookupFailure {
                 tx_hash: TxHash::repeat_byte(0x22),
                 block_number: 456,
+                block_timestamp: None,
+                tx_index: None,
+                log_index: None,
                 transfer_value: U256::from(99_u64),
                 attempts: vec![CombinedDataLookupAttempt {
                     pass: CombinedDataLookupPass::SerialFallback,
@@ -490,4 +1063,367 @@ mod tests {
             vec![TxHash::repeat_byte(0x22)]
         );
     }
+
+    #[test]
+    fn test_combined_data_result_serde_round_trip() {
+        let mut result = CombinedDataResult::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+        );
+        result.add_transaction_data(create_test_tx(21000, 50, Some(100), 0, 1_000));
+
+        let json = serde_json::to_value(&result).unwrap();
+        for key in [
+            "chain",
+            "from_address",
+            "to_address",
+            "token_address",
+            "total_l2_execution_cost",
+            "total_blob_gas_cost",
+            "total_l1_fee",
+            "overall_total_gas_cost",
+            "total_amount_transferred",
+            "transaction_count",
+            "transactions_data",
+            "retrieval_metadata",
+        ] {
+            assert!(json.get(key).is_some(), "missing key {key}");
+        }
+        assert!(json["transactions_data"][0].get("tx_hash").is_some());
+        assert!(json["transactions_data"][0]
+            .get("transferred_amount")
+            .is_some());
+
+        let decoded: CombinedDataResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_cancelled_result_is_partial_and_merges() {
+        let mut result = CombinedDataResult::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+        );
+        let json = serde_json::to_value(&result).unwrap();
+        assert!(json.get("cancelled").is_none());
+
+        let mut cancelled = result.clone();
+        cancelled.cancelled = true;
+        result.merge(&cancelled);
+        assert!(result.cancelled);
+        assert!(result.is_partial());
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["cancelled"], true);
+        let decoded: CombinedDataResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_split_by_day_attributes_transfers_to_windows() {
+        use crate::blocks::window::UnixTimestamp;
+
+        // 2025-03-01 and 2025-03-02
+        let day_one = DailyBlockWindow::new(
+            100,
+            199,
+            UnixTimestamp(1_740_787_200),
+            UnixTimestamp(1_740_873_600),
+        )
+        .unwrap();
+        let day_two = DailyBlockWindow::new(
+            200,
+            299,
+            UnixTimestamp(1_740_873_600),
+            UnixTimestamp(1_740_960_000),
+        )
+        .unwrap();
+
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        // Attributed by block number
+        let mut first = create_test_tx(21000, 10, None, 0, 5);
+        first.block_number = 150;
+        // The timestamp wins over the block number
+        let mut second = create_test_tx(21000, 10, None, 0, 7);
+        second.tx_hash = TxHash::repeat_byte(0x02);
+        second.block_number = 199;
+        second.block_timestamp = Some(1_740_873_605);
+        // Outside both windows
+        let mut third = create_test_tx(21000, 10, None, 0, 11);
+        third.tx_hash = TxHash::repeat_byte(0x03);
+        third.block_number = 500;
+        for tx in [first, second, third] {
+            result.add_transaction_data(tx);
+        }
+
+        let days = result.split_by_day(&[day_one, day_two]);
+        let dates: Vec<_> = days.keys().map(ToString::to_string).collect();
+        assert_eq!(dates, vec!["2025-03-01", "2025-03-02"]);
+
+        let amounts: Vec<_> = days
+            .values()
+            .map(|day| day.total_amount_transferred)
+            .collect();
+        assert_eq!(amounts, vec![U256::from(5u64), U256::from(7u64)]);
+        assert!(days
+            .values()
+            .all(|day| day.transaction_count == TransactionCount::new(1)));
+    }
+
+    #[test]
+    fn test_split_by_day_clips_warnings_to_windows() {
+        use crate::blocks::window::UnixTimestamp;
+
+        let day_one = DailyBlockWindow::new(
+            100,
+            199,
+            UnixTimestamp(1_740_787_200),
+            UnixTimestamp(1_740_873_600),
+        )
+        .unwrap();
+        let day_two = DailyBlockWindow::new(
+            200,
+            299,
+            UnixTimestamp(1_740_873_600),
+            UnixTimestamp(1_740_960_000),
+        )
+        .unwrap();
+
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        let failed = ScanWarning::failed_receipt(TxHash::ZERO, Some(250), "not found");
+        result.warnings = vec![ScanWarning::gap(150, 249, "timeout"), failed.clone()];
+
+        let days: Vec<_> = result
+            .split_by_day(&[day_one, day_two])
+            .into_values()
+            .collect();
+        assert_eq!(
+            days[0].warnings,
+            vec![ScanWarning::gap(150, 199, "timeout")]
+        );
+        assert_eq!(
+            days[1].warnings,
+            vec![ScanWarning::gap(200, 249, "timeout"), failed]
+        );
+    }
+
+    #[test]
+    fn test_transfer_cursor_follows_chain_order() {
+        let cursor = TransferCursor::new(100, 2, 7);
+        assert!(cursor.covers(99, None, None));
+        assert!(cursor.covers(100, Some(2), Some(7)));
+        assert!(cursor.covers(100, Some(1), Some(9)));
+        assert!(!cursor.covers(100, Some(2), Some(8)));
+        assert!(!cursor.covers(100, Some(3), Some(0)));
+        assert!(!cursor.covers(101, Some(0), Some(0)));
+        // Unplaceable logs in the cursor's block are kept
+        assert!(!cursor.covers(100, None, Some(1)));
+
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        assert_eq!(result.next_cursor(), None);
+        for (hash, block_number, tx_index, log_index) in
+            [(1, 100, 3, 9), (2, 101, 0, 1), (3, 100, 4, 12)]
+        {
+            result.add_transaction_data(GasAndAmountForTx {
+                tx_hash: TxHash::repeat_byte(hash),
+                block_number,
+                tx_index: Some(tx_index),
+                log_index: Some(log_index),
+                ..create_test_tx(21000, 10, None, 0, 1)
+            });
+        }
+        assert_eq!(result.next_cursor(), Some(TransferCursor::new(101, 0, 1)));
+
+        let json = serde_json::to_value(TransferCursor::new(101, 0, 1)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"block_number": 101, "tx_index": 0, "log_index": 1})
+        );
+    }
+
+    #[test]
+    fn test_merging_overlapping_results_is_idempotent() {
+        let transfer = |hash: u8, log_index: u64, amount: u64| GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(hash),
+            log_index: Some(log_index),
+            ..create_test_tx(21000, 10, None, 0, amount)
+        };
+        let new_result = || {
+            CombinedDataResult::new(
+                NamedChain::Mainnet,
+                Address::ZERO,
+                Address::ZERO,
+                Address::ZERO,
+            )
+        };
+
+        // Blocks 100..=200 and 150..=250 share the second transaction
+        let mut left = new_result();
+        left.add_transaction_data(transfer(0x01, 0, 5));
+        left.add_transaction_data(transfer(0x02, 3, 7));
+        let mut right = new_result();
+        right.add_transaction_data(transfer(0x02, 3, 7));
+        // Same transaction, different log
+        right.add_transaction_data(transfer(0x02, 4, 11));
+
+        left.merge(&right);
+        let once = left.clone();
+        left.merge(&right);
+        left.merge(&once);
+
+        assert_eq!(left, once);
+        assert_eq!(left.transaction_count, TransactionCount::new(3));
+        assert_eq!(left.transactions_data.len(), 3);
+        assert_eq!(left.total_amount_transferred, U256::from(23u64));
+        assert_eq!(left.overall_total_gas_cost, U256::from(3 * 21000 * 10));
+        assert!(!left.add_transaction_data(transfer(0x01, 0, 5)));
+
+        // The dedup index is rebuilt after a round trip
+        let mut decoded: CombinedDataResult =
+            serde_json::from_value(serde_json::to_value(&left).unwrap()).unwrap();
+        decoded.merge(&right);
+        assert_eq!(decoded.transaction_count, TransactionCount::new(3));
+    }
+
+    #[test]
+    fn test_merge_keeps_totals_of_unlisted_transfers() {
+        let mut left = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        left.add_transaction_data(create_test_tx(21000, 10, None, 0, 5));
+
+        // Totals only, as when per-transaction rows were dropped to save space
+        let mut totals_only = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        totals_only.add_transaction_data(GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(0x02),
+            ..create_test_tx(21000, 10, None, 0, 7)
+        });
+        totals_only.transactions_data.clear();
+
+        left.merge(&totals_only);
+        assert_eq!(left.transaction_count, TransactionCount::new(2));
+        assert_eq!(left.total_amount_transferred, U256::from(12u64));
+        assert_eq!(left.transactions_data.len(), 1);
+    }
+
+    #[test]
+    fn test_equality_ignores_dedup_index_state() {
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        result.add_transaction_data(create_test_tx(21000, 10, None, 0, 5));
+
+        let mut other = result.clone();
+        other.seen = TransactionKeys::default();
+        assert_eq!(result, other);
+
+        other.add_transaction_data(GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(0x02),
+            ..create_test_tx(21000, 10, None, 0, 7)
+        });
+        assert_ne!(result, other);
+    }
+
+    #[test]
+    fn test_gas_attribution_shares_sum_to_transaction_gas() {
+        let hash = TxHash::repeat_byte(0x0a);
+        let positions = GasAttribution::positions([
+            (hash, Some(9)),
+            (hash, Some(2)),
+            (hash, Some(5)),
+            (TxHash::repeat_byte(0x0b), Some(0)),
+        ]);
+        assert_eq!(positions[&(hash, Some(2))], (0, 3));
+        assert_eq!(positions[&(hash, Some(9))], (2, 3));
+        assert_eq!(positions[&(TxHash::repeat_byte(0x0b), Some(0))], (0, 1));
+
+        let tx = create_test_tx(21_001, 10, Some(100), 50, 1_000);
+        let shares = |policy| {
+            (0..3)
+                .map(|position| {
+                    let mut share = tx.clone();
+                    share.attribute_gas(policy, position, 3);
+                    share.total_gas_cost()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let full = shares(GasAttribution::Full);
+        assert!(full.iter().all(|cost| *cost == tx.total_gas_cost()));
+
+        let split = shares(GasAttribution::SplitEvenly);
+        assert_eq!(split.iter().copied().sum::<U256>(), tx.total_gas_cost());
+        // 7_001 gas, 34 of L1 fee and 18 of blob gas go to the first transfer
+        assert_eq!(split[0], U256::from(7_001 * 10 + 34 + 18));
+        assert_eq!(split[1], U256::from(7_000 * 10 + 33 + 16));
+
+        let first = shares(GasAttribution::FirstLogOnly);
+        assert_eq!(first, vec![tx.total_gas_cost(), U256::ZERO, U256::ZERO]);
+
+        // A lone transfer always keeps the full cost
+        let mut lone = tx.clone();
+        lone.attribute_gas(GasAttribution::FirstLogOnly, 0, 1);
+        assert_eq!(lone, tx);
+    }
+
+    #[test]
+    fn test_combined_result_totals_base_and_priority_fees() {
+        let mut result = CombinedDataResult::new(
+            NamedChain::Mainnet,
+            Address::ZERO,
+            Address::ZERO,
+            Address::ZERO,
+        );
+        result.add_transaction_data(GasAndAmountForTx {
+            base_fee_per_gas: Some(GasPrice::from(8u64)),
+            ..create_test_tx(21000, 10, None, 0, 1)
+        });
+        // Without a base fee the transaction is left out of the split
+        result.add_transaction_data(GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(0x01),
+            ..create_test_tx(50000, 10, None, 0, 1)
+        });
+
+        let tx = &result.transactions_data[0];
+        assert_eq!(tx.base_fee_burned(), Some(U256::from(21000 * 8)));
+        assert_eq!(tx.priority_fee_paid(), Some(U256::from(21000 * 2)));
+        assert_eq!(result.transactions_data[1].base_fee_burned(), None);
+
+        assert_eq!(result.total_base_fee_burned, U256::from(21000 * 8));
+        assert_eq!(result.total_priority_fee_paid, U256::from(21000 * 2));
+        assert_eq!(
+            result.total_l2_execution_cost,
+            U256::from(21000 * 10 + 50000 * 10)
+        );
+    }
 }
@@ -9,14 +9,16 @@ use alloy_primitives::{Address, U256};
 use bigdecimal::BigDecimal;
 use std::str::FromStr;
 
-use crate::config::constants::stablecoins::BSC_BINANCE_PEG_USDC;
+use crate::config::constants::{canonical_tokens, canonical_usdc};
 use crate::errors::RetrievalError;
 
 use super::decimal_precision::DecimalPrecision;
 
 /// Get the decimal precision for a specific token on a specific chain.
 /// Native tokens (Address::ZERO) use 18 decimals.
-/// Most USDC tokens use 6 decimals, but BSC Binance-Peg USDC uses 18 decimals.
+/// Canonical tokens from [`canonical_tokens`] use their known decimals: most
+/// USDC and USDT use 6, but BSC Binance-Peg USDC and BSC USDT use 18, and DAI
+/// and wrapped native tokens use 18. Other tokens are assumed to be USDC-like.
 ///
 /// # Arguments
 /// * `chain` - The named chain
@@ -31,10 +33,15 @@ pub fn get_token_decimal_precision(chain: NamedChain, token_address: Address) ->
     }
 
     // BSC Binance-Peg USDC has 18 decimals instead of 6
-    if matches!(chain, NamedChain::BinanceSmartChain) && token_address == BSC_BINANCE_PEG_USDC {
-        DecimalPrecision::BinancePegUsdc // 18 decimals
-    } else {
-        DecimalPrecision::Usdc // 6 decimals
+    if matches!(chain, NamedChain::BinanceSmartChain)
+        && canonical_usdc(chain) == Some(token_address)
+    {
+        return DecimalPrecision::BinancePegUsdc; // 18 decimals
+    }
+
+    match canonical_tokens(chain).decimals(chain, token_address) {
+        Some(6) | None => DecimalPrecision::Usdc, // 6 decimals
+        Some(decimals) => DecimalPrecision::Custom(decimals),
     }
 }
 
@@ -136,6 +143,17 @@ mod tests {
         assert_eq!(precision, DecimalPrecision::Usdc); // Defaults to USDC precision
     }
 
+    #[test]
+    fn get_token_decimal_precision_for_canonical_18_decimal_tokens() {
+        let bsc_usdt = address!("55d398326f99059fF775485246999027B3197955");
+        let precision = get_token_decimal_precision(NamedChain::BinanceSmartChain, bsc_usdt);
+        assert_eq!(precision, DecimalPrecision::Custom(18));
+
+        let mainnet_dai = address!("6B175474E89094C44Da98b954EedeAC495271d0F");
+        let precision = get_token_decimal_precision(NamedChain::Mainnet, mainnet_dai);
+        assert_eq!(precision, DecimalPrecision::Custom(18));
+    }
+
     // ========== u256_to_bigdecimal tests ==========
 
     #[test]
@@ -0,0 +1,420 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! ClickHouse sink for high-volume transaction and swap data.
+//!
+//! Rows are written with ClickHouse's native `RowBinary` insert format in
+//! batches of [`ClickHouseSinkConfig::batch_size`]. A failed batch is retried
+//! with exponential backoff before the error is surfaced. Batches that were
+//! already acknowledged stay written, so pair this sink with a
+//! `ReplacingMergeTree` table (as the bundled DDL does) to make retries of a
+//! whole result idempotent.
+//!
+//! U256 values are stored as `UInt256` and hashes/addresses as `FixedString`,
+//! so nothing is lost to decimal formatting. Transaction rows are keyed by
+//! `(chain_id, block_number, tx_hash, log_index)`, so each transfer in a
+//! transaction keeps its own row; a missing log index is stored as `-1`.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::sink::clickhouse::{ClickHouseSink, ClickHouseSinkConfig};
+//! use std::time::Duration;
+//!
+//! let client = clickhouse::Client::default().with_url("http://localhost:8123");
+//! let config = ClickHouseSinkConfig::default()
+//!     .with_batch_size(50_000)
+//!     .with_max_retries(5)
+//!     .with_retry_backoff(Duration::from_millis(500));
+//! let sink = ClickHouseSink::new(client, config);
+//!
+//! sink.create_schema().await?;
+//! sink.write_combined_result(&result).await?;
+//! ```
+
+use std::time::Duration;
+
+use alloy_chains::NamedChain;
+use alloy_primitives::{Address, B256, U256};
+use clickhouse::{Client, Row};
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::errors::SinkError;
+use crate::price::RawSwapResult;
+use crate::retrieval::{CombinedDataResult, GasAndAmountForTx};
+
+/// DDL for the transactions table written by [`ClickHouseSink::write_transactions`]
+pub const TRANSACTIONS_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS semioscan_transactions (
+    chain_id            UInt64,
+    tx_hash             FixedString(32),
+    log_index           Int64,
+    block_number        UInt64,
+    from_address        FixedString(20),
+    to_address          FixedString(20),
+    token_address       FixedString(20),
+    gas_used            UInt256,
+    effective_gas_price UInt256,
+    l1_fee              Nullable(UInt256),
+    blob_gas_cost       UInt256,
+    total_gas_cost      UInt256,
+    transferred_amount  UInt256
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (chain_id, block_number, tx_hash, log_index)
+"#;
+
+/// DDL for the swaps table written by [`ClickHouseSink::write_swaps`]
+pub const SWAPS_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS semioscan_swaps (
+    chain_id                    UInt64,
+    tx_hash                     Nullable(FixedString(32)),
+    block_number                Nullable(UInt64),
+    sender                      Nullable(FixedString(20)),
+    token_in                    FixedString(20),
+    token_in_amount             UInt256,
+    token_out                   FixedString(20),
+    token_out_amount            UInt256,
+    normalized_token_in_amount  Float64,
+    normalized_token_out_amount Float64,
+    token_in_decimals           UInt8,
+    token_out_decimals          UInt8
+)
+ENGINE = MergeTree
+ORDER BY (chain_id, token_in, token_out)
+"#;
+
+/// Configuration for [`ClickHouseSink`]
+#[derive(Debug, Clone)]
+pub struct ClickHouseSinkConfig {
+    /// Table receiving [`GasAndAmountForTx`] rows
+    pub transactions_table: String,
+    /// Table receiving [`RawSwapResult`] rows
+    pub swaps_table: String,
+    /// Maximum rows per insert; zero is treated as one
+    pub batch_size: usize,
+    /// Retries per batch after the first attempt fails
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on each subsequent retry
+    pub retry_backoff: Duration,
+}
+
+impl Default for ClickHouseSinkConfig {
+    fn default() -> Self {
+        Self {
+            transactions_table: "semioscan_transactions".to_string(),
+            swaps_table: "semioscan_swaps".to_string(),
+            batch_size: 10_000,
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(250),
+        }
+    }
+}
+
+impl ClickHouseSinkConfig {
+    /// Set the transactions table name
+    pub fn with_transactions_table(mut self, table: impl Into<String>) -> Self {
+        self.transactions_table = table.into();
+        self
+    }
+
+    /// Set the swaps table name
+    pub fn with_swaps_table(mut self, table: impl Into<String>) -> Self {
+        self.swaps_table = table.into();
+        self
+    }
+
+    /// Set the maximum number of rows per insert (clamped to at least 1)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the number of retries per batch
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the initial retry backoff
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Backoff before retry number `attempt` (0-based)
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.min(16)))
+    }
+}
+
+/// Row layout of the transactions table
+#[derive(Debug, Clone, PartialEq, Eq, Row, Serialize)]
+pub struct TransactionRow {
+    pub chain_id: u64,
+    pub tx_hash: [u8; 32],
+    /// Log index of the transfer, or `-1` when the provider omitted it
+    pub log_index: i64,
+    pub block_number: u64,
+    pub from_address: [u8; 20],
+    pub to_address: [u8; 20],
+    pub token_address: [u8; 20],
+    pub gas_used: [u8; 32],
+    pub effective_gas_price: [u8; 32],
+    pub l1_fee: Option<[u8; 32]>,
+    pub blob_gas_cost: [u8; 32],
+    pub total_gas_cost: [u8; 32],
+    pub transferred_amount: [u8; 32],
+}
+
+impl TransactionRow {
+    /// Build a row for one transaction of a combined result
+    pub fn new(
+        chain: NamedChain,
+        from_address: Address,
+        to_address: Address,
+        token_address: Address,
+        tx: &GasAndAmountForTx,
+    ) -> Self {
+        Self {
+            chain_id: chain as u64,
+            tx_hash: tx.tx_hash.0,
+            log_index: tx
+                .log_index
+                .and_then(|index| i64::try_from(index).ok())
+                .unwrap_or(-1),
+            block_number: tx.block_number,
+            from_address: from_address.0 .0,
+            to_address: to_address.0 .0,
+            token_address: token_address.0 .0,
+            gas_used: uint256(tx.gas_used.as_u256()),
+            effective_gas_price: uint256(tx.effective_gas_price.as_u256()),
+            l1_fee: tx.l1_fee.map(uint256),
+            blob_gas_cost: uint256(tx.blob_gas_cost),
+            total_gas_cost: uint256(tx.total_gas_cost()),
+            transferred_amount: uint256(tx.transferred_amount),
+        }
+    }
+}
+
+/// Row layout of the swaps table
+#[derive(Debug, Clone, PartialEq, Row, Serialize)]
+pub struct SwapRow {
+    pub chain_id: u64,
+    pub tx_hash: Option<[u8; 32]>,
+    pub block_number: Option<u64>,
+    pub sender: Option<[u8; 20]>,
+    pub token_in: [u8; 20],
+    pub token_in_amount: [u8; 32],
+    pub token_out: [u8; 20],
+    pub token_out_amount: [u8; 32],
+    pub normalized_token_in_amount: f64,
+    pub normalized_token_out_amount: f64,
+    pub token_in_decimals: u8,
+    pub token_out_decimals: u8,
+}
+
+impl SwapRow {
+    /// Build a row for one extracted swap
+    pub fn new(chain: NamedChain, swap: &RawSwapResult) -> Self {
+        Self {
+            chain_id: chain as u64,
+            tx_hash: swap.swap.tx_hash.map(|hash: B256| hash.0),
+            block_number: swap.swap.block_number,
+            sender: swap.swap.sender.map(|sender| sender.0 .0),
+            token_in: swap.swap.token_in.0 .0,
+            token_in_amount: uint256(swap.swap.token_in_amount),
+            token_out: swap.swap.token_out.0 .0,
+            token_out_amount: uint256(swap.swap.token_out_amount),
+            normalized_token_in_amount: swap.normalized_token_in_amount.as_f64(),
+            normalized_token_out_amount: swap.normalized_token_out_amount.as_f64(),
+            token_in_decimals: swap.token_in_decimals.as_u8(),
+            token_out_decimals: swap.token_out_decimals.as_u8(),
+        }
+    }
+}
+
+/// Batched, retrying writer for ClickHouse
+#[derive(Clone)]
+pub struct ClickHouseSink {
+    client: Client,
+    config: ClickHouseSinkConfig,
+}
+
+impl ClickHouseSink {
+    /// Create a sink from a configured ClickHouse client
+    pub fn new(client: Client, config: ClickHouseSinkConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// Get the sink configuration
+    pub fn config(&self) -> &ClickHouseSinkConfig {
+        &self.config
+    }
+
+    /// Create the default tables if they do not already exist
+    ///
+    /// The bundled DDL uses the default table names; create custom-named tables
+    /// yourself with the same column layout.
+    pub async fn create_schema(&self) -> Result<(), SinkError> {
+        self.client.query(TRANSACTIONS_SCHEMA).execute().await?;
+        self.client.query(SWAPS_SCHEMA).execute().await?;
+        Ok(())
+    }
+
+    /// Write every transaction of a combined result
+    ///
+    /// Returns the number of rows written.
+    pub async fn write_combined_result(
+        &self,
+        result: &CombinedDataResult,
+    ) -> Result<usize, SinkError> {
+        let rows: Vec<TransactionRow> = result
+            .transactions_data
+            .iter()
+            .map(|tx| {
+                TransactionRow::new(
+                    result.chain,
+                    result.from_address,
+                    result.to_address,
+                    result.token_address,
+                    tx,
+                )
+            })
+            .collect();
+        self.write_rows(&self.config.transactions_table, &rows)
+            .await
+    }
+
+    /// Write pre-built transaction rows
+    ///
+    /// Returns the number of rows written.
+    pub async fn write_transactions(&self, rows: &[TransactionRow]) -> Result<usize, SinkError> {
+        self.write_rows(&self.config.transactions_table, rows).await
+    }
+
+    /// Write extracted swaps for a chain
+    ///
+    /// Returns the number of rows written.
+    pub async fn write_swaps(
+        &self,
+        chain: NamedChain,
+        swaps: &[RawSwapResult],
+    ) -> Result<usize, SinkError> {
+        let rows: Vec<SwapRow> = swaps.iter().map(|swap| SwapRow::new(chain, swap)).collect();
+        self.write_rows(&self.config.swaps_table, &rows).await
+    }
+
+    async fn write_rows<T>(&self, table: &str, rows: &[T]) -> Result<usize, SinkError>
+    where
+        T: Row + Serialize,
+    {
+        // The field is public, so it may be zero without `with_batch_size`
+        for batch in rows.chunks(self.config.batch_size.max(1)) {
+            let mut attempt = 0;
+            loop {
+                match self.insert_batch(table, batch).await {
+                    Ok(()) => {
+                        debug!(table, rows = batch.len(), "Inserted ClickHouse batch");
+                        break;
+                    }
+                    Err(e) if attempt < self.config.max_retries => {
+                        let delay = self.config.backoff_for(attempt);
+                        warn!(
+                            table,
+                            attempt = attempt + 1,
+                            max_retries = self.config.max_retries,
+                            delay_ms = delay.as_millis() as u64,
+                            error = %e,
+                            "ClickHouse batch insert failed, retrying"
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        Ok(rows.len())
+    }
+
+    async fn insert_batch<T>(&self, table: &str, batch: &[T]) -> Result<(), SinkError>
+    where
+        T: Row + Serialize,
+    {
+        let mut insert = self.client.insert(table)?;
+        for row in batch {
+            insert.write(row).await?;
+        }
+        insert.end().await?;
+        Ok(())
+    }
+}
+
+/// ClickHouse expects `UInt256` as 32 little-endian bytes
+fn uint256(value: U256) -> [u8; 32] {
+    value.to_le_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::gas::{GasAmount, GasPrice};
+    use alloy_primitives::TxHash;
+
+    #[test]
+    fn test_uint256_is_little_endian() {
+        let bytes = uint256(U256::from(0x0102u64));
+        assert_eq!(bytes[0], 0x02);
+        assert_eq!(bytes[1], 0x01);
+        assert!(bytes[2..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_transaction_row_carries_total_gas_cost() {
+        let tx = GasAndAmountForTx {
+            tx_hash: TxHash::repeat_byte(0xab),
+            block_number: 42,
+            block_timestamp: None,
+            tx_index: None,
+            log_index: None,
+            token_address: None,
+            gas_used: GasAmount::new(21_000),
+            effective_gas_price: GasPrice::new(10),
+            base_fee_per_gas: None,
+            l1_fee: Some(U256::from(5u64)),
+            blob_gas_cost: U256::ZERO,
+            transferred_amount: U256::from(1_000u64),
+        };
+        let row = TransactionRow::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            Address::repeat_byte(0x33),
+            &tx,
+        );
+
+        assert_eq!(row.chain_id, 8453);
+        assert_eq!(row.tx_hash, [0xab; 32]);
+        assert_eq!(row.log_index, -1);
+        assert_eq!(row.total_gas_cost, uint256(U256::from(210_005u64)));
+        assert_eq!(row.l1_fee, Some(uint256(U256::from(5u64))));
+    }
+
+    #[test]
+    fn test_backoff_doubles_per_attempt() {
+        let config = ClickHouseSinkConfig::default().with_retry_backoff(Duration::from_millis(100));
+        assert_eq!(config.backoff_for(0), Duration::from_millis(100));
+        assert_eq!(config.backoff_for(1), Duration::from_millis(200));
+        assert_eq!(config.backoff_for(3), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn test_batch_size_is_never_zero() {
+        let config = ClickHouseSinkConfig::default().with_batch_size(0);
+        assert_eq!(config.batch_size, 1);
+    }
+}
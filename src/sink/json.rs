@@ -0,0 +1,148 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON Lines output for computed results
+//!
+//! [`JsonLinesSink`] writes one JSON object per line for every
+//! [`SinkRecord`], which makes a run's output easy to pipe into `jq` or a log
+//! shipper. [`JsonLinesSink::stdout`] writes to standard output.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{DailyRunner, JsonLinesSink};
+//!
+//! let runner = DailyRunner::new(client)
+//!     .with_target(target)
+//!     .with_sink(JsonLinesSink::stdout());
+//! runner.run_yesterday().await;
+//! ```
+
+use std::io::{Stdout, Write};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::{ResultSink, SinkRecord};
+use crate::daily::{DailyReport, RunSummary};
+use crate::errors::SinkError;
+
+/// Writes each record as one line of JSON
+#[derive(Debug)]
+pub struct JsonLinesSink<W> {
+    writer: Mutex<W>,
+}
+
+impl JsonLinesSink<Stdout> {
+    /// A sink writing to standard output
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout())
+    }
+}
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    /// A sink writing to `writer`
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// The writer, with everything written so far
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_record(&self, record: SinkRecord<'_>) -> Result<(), SinkError> {
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        writer.write_all(&line)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<W: Write + Send> ResultSink for JsonLinesSink<W> {
+    async fn write_daily(&self, report: &DailyReport) -> Result<(), SinkError> {
+        self.write_record(SinkRecord::DailyReport(report))
+    }
+
+    async fn write_summary(&self, summary: &RunSummary) -> Result<(), SinkError> {
+        self.write_record(SinkRecord::RunSummary(summary))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_chains::NamedChain;
+    use alloy_primitives::Address;
+    use chrono::{NaiveDate, Utc};
+
+    use crate::blocks::window::DailyBlockWindow;
+    use crate::daily::DailyTarget;
+    use crate::gas::calculator::GasCostResult;
+    use crate::retrieval::CombinedDataResult;
+    use crate::UnixTimestamp;
+
+    #[tokio::test]
+    async fn records_are_written_as_tagged_lines() {
+        let target = DailyTarget::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x01),
+        );
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let report = DailyReport {
+            target,
+            date,
+            window: DailyBlockWindow::new(
+                100,
+                199,
+                UnixTimestamp(1_735_689_600),
+                UnixTimestamp(1_735_776_000),
+            )
+            .unwrap(),
+            gas: GasCostResult::new(
+                target.chain,
+                target.from_address.into(),
+                target.to_address.into(),
+            ),
+            combined: CombinedDataResult::new(
+                target.chain,
+                target.from_address.into(),
+                target.to_address.into(),
+                target.token_address.into(),
+            ),
+            price: None,
+        };
+        let summary = RunSummary {
+            date,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            completed: 1,
+            awaiting_finality: 0,
+            failed: Vec::new(),
+        };
+
+        let sink = JsonLinesSink::new(Vec::new());
+        sink.write_daily(&report).await.unwrap();
+        sink.write_summary(&summary).await.unwrap();
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "daily_report");
+        assert_eq!(lines[0]["window"]["end_block"], 199);
+        assert!(lines[0].get("price").is_none());
+        assert_eq!(lines[1]["type"], "run_summary");
+        assert_eq!(lines[1]["completed"], 1);
+    }
+}
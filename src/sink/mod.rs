@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persisting semioscan results to external storage.
+//!
+//! Each backend lives in its own submodule behind a feature flag, so the core
+//! crate stays free of database drivers:
+//!
+//! - `postgres` (feature `postgres`) - upserts [`CombinedDataResult`](crate::CombinedDataResult)
+//!   transactions via `sqlx`
+//! - `clickhouse` (feature `clickhouse`) - batched, retrying columnar inserts of
+//!   transaction and swap rows
+//!
+//! [`ResultSink`] is the hook for storage this crate doesn't know about: a
+//! [`DailyRunner`](crate::DailyRunner) hands each day's results, then a
+//! summary of the run, to every sink it was given. Two sinks are built in:
+//!
+//! - `json` - [`JsonLinesSink`](json::JsonLinesSink) writes JSON Lines to
+//!   stdout or any writer
+//! - `webhook` (feature `webhook`) - [`WebhookSink`](webhook::WebhookSink)
+//!   POSTs raw JSON records, or Slack-compatible summaries, to an HTTP
+//!   endpoint
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::daily::{DailyReport, RunSummary};
+use crate::errors::SinkError;
+
+#[cfg(feature = "clickhouse")]
+pub mod clickhouse;
+pub mod json;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+/// Receives results as they are computed
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use semioscan::{DailyReport, ResultSink, SinkError};
+///
+/// struct Postgres(sqlx::PgPool);
+///
+/// #[async_trait::async_trait]
+/// impl ResultSink for Postgres {
+///     async fn write_daily(&self, report: &DailyReport) -> Result<(), SinkError> {
+///         insert_combined_result(&self.0, &report.combined).await.map(|_| ())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait ResultSink: Send + Sync {
+    /// Store the results for one target and day
+    async fn write_daily(&self, report: &DailyReport) -> Result<(), SinkError>;
+
+    /// Record the outcome of a run, after its results were written
+    ///
+    /// Does nothing by default.
+    async fn write_summary(&self, _summary: &RunSummary) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+/// One record written by the built-in sinks
+///
+/// Serialized as the wrapped value with a `type` field added, e.g.
+/// `{"type":"run_summary","date":"2025-01-01",...}`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkRecord<'a> {
+    /// Results for one target and day, tagged `"daily_report"`
+    DailyReport(&'a DailyReport),
+    /// Outcome of a run, tagged `"run_summary"`
+    RunSummary(&'a RunSummary),
+}
@@ -0,0 +1,191 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Postgres sink for combined gas and transfer data.
+//!
+//! Every U256 value is stored as `NUMERIC(78, 0)` in its raw base unit (wei for
+//! gas, token base units for transfers), converted through
+//! [`u256_to_bigdecimal`](crate::u256_to_bigdecimal) so no precision is lost.
+//! Rows are keyed by `(chain_id, tx_hash, log_index)`, so every transfer in a
+//! transaction gets its own row; re-inserting a result overwrites the stored
+//! rows. Entries whose provider omitted the log index are stored with a
+//! `log_index` of `-1`.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::sink::postgres::{create_schema, insert_combined_result};
+//! use sqlx::PgPool;
+//!
+//! let pool = PgPool::connect(&database_url).await?;
+//! create_schema(&pool).await?;
+//!
+//! let result = calculator.calculate(&query).await?;
+//! let rows = insert_combined_result(&pool, &result).await?;
+//! ```
+
+use alloy_primitives::U256;
+use bigdecimal::BigDecimal;
+use sqlx::PgPool;
+
+use crate::errors::{RetrievalError, SinkError};
+use crate::retrieval::{u256_to_bigdecimal, CombinedDataResult, DecimalPrecision};
+
+/// Name of the table written by [`insert_combined_result`]
+pub const COMBINED_TRANSACTIONS_TABLE: &str = "semioscan_combined_transactions";
+
+/// DDL for the combined transactions table
+///
+/// Safe to run repeatedly; see [`create_schema`].
+pub const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS semioscan_combined_transactions (
+    chain_id            BIGINT         NOT NULL,
+    tx_hash             BYTEA          NOT NULL,
+    log_index           BIGINT         NOT NULL,
+    block_number        BIGINT         NOT NULL,
+    from_address        BYTEA          NOT NULL,
+    to_address          BYTEA          NOT NULL,
+    token_address       BYTEA          NOT NULL,
+    gas_used            NUMERIC(78, 0) NOT NULL,
+    effective_gas_price NUMERIC(78, 0) NOT NULL,
+    l1_fee              NUMERIC(78, 0),
+    blob_gas_cost       NUMERIC(78, 0) NOT NULL,
+    total_gas_cost      NUMERIC(78, 0) NOT NULL,
+    transferred_amount  NUMERIC(78, 0) NOT NULL,
+    inserted_at         TIMESTAMPTZ    NOT NULL DEFAULT now(),
+    PRIMARY KEY (chain_id, tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS semioscan_combined_transactions_block_idx
+    ON semioscan_combined_transactions (chain_id, block_number);
+"#;
+
+const UPSERT: &str = r#"
+INSERT INTO semioscan_combined_transactions (
+    chain_id, tx_hash, log_index, block_number, from_address, to_address, token_address,
+    gas_used, effective_gas_price, l1_fee, blob_gas_cost, total_gas_cost, transferred_amount
+)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+ON CONFLICT (chain_id, tx_hash, log_index) DO UPDATE SET
+    block_number = EXCLUDED.block_number,
+    from_address = EXCLUDED.from_address,
+    to_address = EXCLUDED.to_address,
+    token_address = EXCLUDED.token_address,
+    gas_used = EXCLUDED.gas_used,
+    effective_gas_price = EXCLUDED.effective_gas_price,
+    l1_fee = EXCLUDED.l1_fee,
+    blob_gas_cost = EXCLUDED.blob_gas_cost,
+    total_gas_cost = EXCLUDED.total_gas_cost,
+    transferred_amount = EXCLUDED.transferred_amount,
+    inserted_at = now()
+"#;
+
+/// Create the sink's tables and indexes if they do not already exist
+pub async fn create_schema(pool: &PgPool) -> Result<(), SinkError> {
+    sqlx::raw_sql(SCHEMA).execute(pool).await?;
+    Ok(())
+}
+
+/// Upsert every transaction in `result`, keyed by `(chain_id, tx_hash, log_index)`
+///
+/// All rows are written in a single database transaction, so either the whole
+/// result is stored or none of it is. Transfers that share a transaction hash
+/// are stored as separate rows as long as their log indexes differ; entries
+/// without a log index share the `-1` slot of their transaction.
+///
+/// Returns the number of rows inserted or updated.
+///
+/// # Errors
+///
+/// Returns [`SinkError::Conversion`] if a value cannot be represented as a
+/// `NUMERIC`, or [`SinkError::Postgres`] if any statement fails. Nothing is
+/// committed in either case.
+pub async fn insert_combined_result(
+    pool: &PgPool,
+    result: &CombinedDataResult,
+) -> Result<u64, SinkError> {
+    let chain_id = chain_id_param(result);
+    let mut db_tx = pool.begin().await?;
+    let mut rows_affected = 0;
+
+    for tx in &result.transactions_data {
+        let l1_fee = tx.l1_fee.map(numeric).transpose()?;
+        let block_number = i64::try_from(tx.block_number).map_err(|_| {
+            RetrievalError::conversion_failed(format!(
+                "block number {block} does not fit in BIGINT",
+                block = tx.block_number
+            ))
+        })?;
+        let log_index = log_index_param(tx.log_index)?;
+
+        let done = sqlx::query(UPSERT)
+            .bind(chain_id)
+            .bind(tx.tx_hash.as_slice())
+            .bind(log_index)
+            .bind(block_number)
+            .bind(result.from_address.as_slice())
+            .bind(result.to_address.as_slice())
+            .bind(result.token_address.as_slice())
+            .bind(numeric(tx.gas_used.as_u256())?)
+            .bind(numeric(tx.effective_gas_price.as_u256())?)
+            .bind(l1_fee)
+            .bind(numeric(tx.blob_gas_cost)?)
+            .bind(numeric(tx.total_gas_cost())?)
+            .bind(numeric(tx.transferred_amount)?)
+            .execute(&mut *db_tx)
+            .await?;
+        rows_affected += done.rows_affected();
+    }
+
+    db_tx.commit().await?;
+    Ok(rows_affected)
+}
+
+/// Convert a raw U256 into an unscaled `NUMERIC` value
+fn numeric(value: U256) -> Result<BigDecimal, SinkError> {
+    Ok(u256_to_bigdecimal(value, DecimalPrecision::Custom(0))?)
+}
+
+/// Log index column value; `-1` stands in for a log index the provider omitted
+fn log_index_param(log_index: Option<u64>) -> Result<i64, SinkError> {
+    match log_index {
+        None => Ok(-1),
+        Some(index) => i64::try_from(index).map_err(|_| {
+            RetrievalError::conversion_failed(format!("log index {index} does not fit in BIGINT"))
+                .into()
+        }),
+    }
+}
+
+/// Postgres has no unsigned integers; every known chain ID fits in `BIGINT`
+fn chain_id_param(result: &CombinedDataResult) -> i64 {
+    result.chain as u64 as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_numeric_preserves_full_u256_range() {
+        let value = numeric(U256::MAX).unwrap();
+        assert_eq!(value, BigDecimal::from_str(&U256::MAX.to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_schema_targets_the_documented_table() {
+        assert!(SCHEMA.contains(COMBINED_TRANSACTIONS_TABLE));
+        assert!(UPSERT.contains(COMBINED_TRANSACTIONS_TABLE));
+        assert!(SCHEMA.contains("PRIMARY KEY (chain_id, tx_hash, log_index)"));
+        assert!(UPSERT.contains("ON CONFLICT (chain_id, tx_hash, log_index)"));
+    }
+
+    #[test]
+    fn test_log_index_param_uses_sentinel_for_missing_index() {
+        assert_eq!(log_index_param(None).unwrap(), -1);
+        assert_eq!(log_index_param(Some(7)).unwrap(), 7);
+        assert!(log_index_param(Some(u64::MAX)).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! HTTP webhook delivery of computed results
+//!
+//! [`WebhookSink`] POSTs every [`SinkRecord`] as a JSON body to a URL, for
+//! services that want each day's numbers pushed to them. The body is the raw
+//! record, which chat services don't accept: [`WebhookSink::slack`] posts
+//! each run's summary as a Slack-compatible `{"text": ...}` message instead,
+//! and [`WebhookSink::summaries_only`] skips the per-target reports while
+//! keeping the raw format.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use semioscan::{DailyRunner, WebhookSink};
+//!
+//! let runner = DailyRunner::new(client)
+//!     .with_target(target)
+//!     .with_sink(
+//!         WebhookSink::new("https://metrics.internal/semioscan")
+//!             .with_header("Authorization", format!("Bearer {token}")),
+//!     )
+//!     .with_sink(WebhookSink::new(slack_webhook_url).slack());
+//! ```
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{ResultSink, SinkRecord};
+use crate::daily::{DailyReport, RunSummary};
+use crate::errors::SinkError;
+
+/// POSTs each record as JSON to a URL
+#[derive(Clone)]
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+    reports: bool,
+    slack: bool,
+}
+
+impl std::fmt::Debug for WebhookSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header_names: Vec<&str> = self.headers.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("WebhookSink")
+            .field("url", &self.url)
+            .field("headers", &header_names)
+            .field("timeout", &self.timeout)
+            .field("reports", &self.reports)
+            .field("slack", &self.slack)
+            .finish()
+    }
+}
+
+impl WebhookSink {
+    /// How long a request may take before it fails, unless set with
+    /// [`with_timeout`](Self::with_timeout)
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// A sink posting reports and summaries to `url` as raw JSON records
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+            headers: Vec::new(),
+            timeout: Self::DEFAULT_TIMEOUT,
+            reports: true,
+            slack: false,
+        }
+    }
+
+    /// Fail a request that hasn't completed within `timeout`
+    ///
+    /// Defaults to [`DEFAULT_TIMEOUT`](Self::DEFAULT_TIMEOUT), so an endpoint
+    /// that never answers can't stall a run.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send `name: value` with every request, e.g. for authentication
+    ///
+    /// Header values are left out of the sink's `Debug` output.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Post run summaries only, not the results for each target
+    pub fn summaries_only(mut self) -> Self {
+        self.reports = false;
+        self
+    }
+
+    /// Post each run summary as a Slack incoming-webhook message
+    ///
+    /// The body is `{"text": ...}` with the run's counts and failed targets,
+    /// which Slack and compatible chat services accept. Implies
+    /// [`summaries_only`](Self::summaries_only).
+    pub fn slack(mut self) -> Self {
+        self.reports = false;
+        self.slack = true;
+        self
+    }
+
+    async fn post(&self, record: SinkRecord<'_>) -> Result<(), SinkError> {
+        let request = self.client.post(&self.url).timeout(self.timeout);
+        let mut request = match record {
+            SinkRecord::RunSummary(summary) if self.slack => {
+                request.json(&json!({ "text": slack_text(summary) }))
+            }
+            record => request.json(&record),
+        };
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResultSink for WebhookSink {
+    async fn write_daily(&self, report: &DailyReport) -> Result<(), SinkError> {
+        if !self.reports {
+            return Ok(());
+        }
+        self.post(SinkRecord::DailyReport(report)).await
+    }
+
+    async fn write_summary(&self, summary: &RunSummary) -> Result<(), SinkError> {
+        self.post(SinkRecord::RunSummary(summary)).await
+    }
+}
+
+/// One line of counts, then a line per failed target
+fn slack_text(summary: &RunSummary) -> String {
+    let mut text = format!(
+        "semioscan run for {}: {} completed, {} awaiting finality, {} failed",
+        summary.date,
+        summary.completed,
+        summary.awaiting_finality,
+        summary.failed.len()
+    );
+    for failure in &summary.failed {
+        let target = &failure.target;
+        let _ = write!(
+            text,
+            "\n• {} {} from {} to {}: {}",
+            target.chain,
+            target.token_address,
+            target.from_address,
+            target.to_address,
+            failure.error
+        );
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_chains::NamedChain;
+    use alloy_primitives::Address;
+    use chrono::{NaiveDate, Utc};
+
+    use crate::daily::{DailyTarget, FailedTarget};
+
+    #[test]
+    fn test_slack_text_counts_outcomes_and_lists_failures() {
+        let target = DailyTarget::new(
+            NamedChain::Base,
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x01),
+        );
+        let summary = RunSummary {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            completed: 2,
+            awaiting_finality: 1,
+            failed: vec![FailedTarget {
+                target,
+                error: "rpc down".to_string(),
+            }],
+        };
+
+        let text = slack_text(&summary);
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("semioscan run for 2025-01-01: 2 completed, 1 awaiting finality, 1 failed")
+        );
+        let failure = lines.next().unwrap();
+        assert!(failure.starts_with("• base "));
+        assert!(failure.ends_with(": rpc down"));
+        assert_eq!(lines.next(), None);
+    }
+}
@@ -9,26 +9,57 @@
 //! `#[instrument]` attributes directly on functions, each instrumented operation has
 //! a corresponding span helper function in this module.
 //!
+//! Each helper takes the caller's [`SpanConfig`] and returns [`Span::none`]
+//! when the span's subsystem is configured below the span's level, or when an
+//! item span is not in the sample.
+//!
 //! Usage pattern:
 //! ```rust,ignore
 //! pub async fn my_operation(&self, param: Type) -> Result<T> {
-//!     let span = spans::my_operation(param_value);
-//!     let _guard = span.enter();
-//!     // Business logic here
+//!     let span = spans::my_operation(&self.config.spans, param_value);
+//!     spans::in_span(span, async {
+//!         // Business logic here
+//!     })
+//!     .await
 //! }
 //! ```
+//!
+//! Async code must not hold a `span.enter()` guard across an `.await`: the span
+//! stays entered while the task is suspended, so whatever else the thread polls
+//! in the meantime is recorded inside it and the span's timing covers time it
+//! spent idle. [`in_span`] (or [`Instrument::instrument`]) enters the span only
+//! while the future is polled. `clippy.toml` disallows `Span::enter` so the
+//! mistake fails `cargo clippy`.
 
 use alloy_chains::NamedChain;
 use alloy_primitives::{Address, BlockNumber, TxHash};
 use chrono::NaiveDate;
-use tracing::{Level, Span};
+use std::future::Future;
+
+use tracing::{Instrument, Level, Span};
+
+use crate::config::{SpanConfig, SpanSubsystem, SpanVerbosity};
+
+/// Run `future` inside `span`, entering it only while the future is polled
+#[inline]
+pub(crate) async fn in_span<F: Future>(span: Span, future: F) -> F::Output {
+    future.instrument(span).await
+}
+
+/// Sampling key of a transaction's item spans
+fn tx_sample_key(tx_hash: TxHash) -> u64 {
+    u64::from_be_bytes(tx_hash.0[24..].try_into().expect("8 bytes"))
+}
 
 /// Create span for processing a single log entry for combined data extraction.
 ///
 /// Parent: process_block_range_for_combined_data span
 /// Children: RPC calls for transaction and receipt retrieval
 #[inline]
-pub(crate) fn process_log_for_combined_data(tx_hash: TxHash) -> Span {
+pub(crate) fn process_log_for_combined_data(config: &SpanConfig, tx_hash: TxHash) -> Span {
+    if !config.item_enabled(SpanSubsystem::Retrieval, tx_sample_key(tx_hash)) {
+        return Span::none();
+    }
     tracing::trace_span!("semioscan.process_log_for_combined_data", tx_hash = %tx_hash,)
 }
 
@@ -38,6 +69,7 @@ pub(crate) fn process_log_for_combined_data(tx_hash: TxHash) -> Span {
 /// Children: process_log_for_combined_data spans (one per log)
 #[inline]
 pub(crate) fn process_block_range_for_combined_data(
+    config: &SpanConfig,
     chain: NamedChain,
     from_address: Address,
     to_address: Address,
@@ -45,6 +77,9 @@ pub(crate) fn process_block_range_for_combined_data(
     from_block: BlockNumber,
     to_block: BlockNumber,
 ) -> Span {
+    if !config.enabled(SpanSubsystem::Retrieval, SpanVerbosity::Chunk) {
+        return Span::none();
+    }
     tracing::debug_span!(
         "semioscan.process_block_range_for_combined_data",
         chain_id = %chain,
@@ -64,6 +99,7 @@ pub(crate) fn process_block_range_for_combined_data(
 /// Children: process_block_range_for_combined_data span
 #[inline]
 pub(crate) fn calculate_combined_data_with_adapter(
+    config: &SpanConfig,
     chain: NamedChain,
     from_address: Address,
     to_address: Address,
@@ -71,6 +107,9 @@ pub(crate) fn calculate_combined_data_with_adapter(
     from_block: BlockNumber,
     to_block: BlockNumber,
 ) -> Span {
+    if !config.enabled(SpanSubsystem::Retrieval, SpanVerbosity::Operation) {
+        return Span::none();
+    }
     tracing::span!(
         Level::INFO,
         "semioscan.calculate_combined_data_with_adapter",
@@ -88,7 +127,10 @@ pub(crate) fn calculate_combined_data_with_adapter(
 /// Parent: Gas calculator operation span
 /// Children: RPC calls for transaction and receipt retrieval
 #[inline]
-pub(crate) fn process_event_log(tx_hash: TxHash) -> Span {
+pub(crate) fn process_event_log(config: &SpanConfig, tx_hash: TxHash) -> Span {
+    if !config.item_enabled(SpanSubsystem::Gas, tx_sample_key(tx_hash)) {
+        return Span::none();
+    }
     tracing::span!(
         Level::INFO,
         "semioscan.process_event_log",
@@ -101,7 +143,10 @@ pub(crate) fn process_event_log(tx_hash: TxHash) -> Span {
 /// Parent: Block window calculation span
 /// Children: RPC call to get block
 #[inline]
-pub(crate) fn get_block_timestamp(block_number: BlockNumber) -> Span {
+pub(crate) fn get_block_timestamp(config: &SpanConfig, block_number: BlockNumber) -> Span {
+    if !config.item_enabled(SpanSubsystem::BlockWindow, block_number) {
+        return Span::none();
+    }
     tracing::debug_span!("semioscan.get_block_timestamp", block_number = block_number,)
 }
 
@@ -110,7 +155,14 @@ pub(crate) fn get_block_timestamp(block_number: BlockNumber) -> Span {
 /// Parent: get_daily_window span
 /// Children: get_block_timestamp spans (during binary search)
 #[inline]
-pub(crate) fn find_first_block_at_or_after(target_ts: u64, latest_block: BlockNumber) -> Span {
+pub(crate) fn find_first_block_at_or_after(
+    config: &SpanConfig,
+    target_ts: u64,
+    latest_block: BlockNumber,
+) -> Span {
+    if !config.enabled(SpanSubsystem::BlockWindow, SpanVerbosity::Chunk) {
+        return Span::none();
+    }
     tracing::debug_span!(
         "semioscan.find_first_block_at_or_after",
         target_ts = target_ts,
@@ -123,7 +175,14 @@ pub(crate) fn find_first_block_at_or_after(target_ts: u64, latest_block: BlockNu
 /// Parent: get_daily_window span
 /// Children: get_block_timestamp spans (during binary search)
 #[inline]
-pub(crate) fn find_last_block_at_or_before(target_ts: u64, latest_block: BlockNumber) -> Span {
+pub(crate) fn find_last_block_at_or_before(
+    config: &SpanConfig,
+    target_ts: u64,
+    latest_block: BlockNumber,
+) -> Span {
+    if !config.enabled(SpanSubsystem::BlockWindow, SpanVerbosity::Chunk) {
+        return Span::none();
+    }
     tracing::debug_span!(
         "semioscan.find_last_block_at_or_before",
         target_ts = target_ts,
@@ -138,7 +197,10 @@ pub(crate) fn find_last_block_at_or_before(target_ts: u64, latest_block: BlockNu
 /// Parent: None (root span for this operation)
 /// Children: find_first_block_at_or_after, find_last_block_at_or_before spans
 #[inline]
-pub(crate) fn get_daily_window(chain: NamedChain, date: NaiveDate) -> Span {
+pub(crate) fn get_daily_window(config: &SpanConfig, chain: NamedChain, date: NaiveDate) -> Span {
+    if !config.enabled(SpanSubsystem::BlockWindow, SpanVerbosity::Operation) {
+        return Span::none();
+    }
     tracing::info_span!(
         "semioscan.get_daily_window",
         chain_id = %chain,
@@ -152,6 +214,7 @@ pub(crate) fn get_daily_window(chain: NamedChain, date: NaiveDate) -> Span {
 /// Children: RPC calls for fetching logs and processing individual log entries
 #[inline]
 pub(crate) fn process_logs_in_range(
+    config: &SpanConfig,
     event_type: crate::EventType,
     chain: NamedChain,
     topic1: Address,
@@ -160,6 +223,9 @@ pub(crate) fn process_logs_in_range(
     from_block: BlockNumber,
     to_block: BlockNumber,
 ) -> Span {
+    if !config.enabled(SpanSubsystem::Gas, SpanVerbosity::Chunk) {
+        return Span::none();
+    }
     tracing::debug_span!(
         "semioscan.process_logs_in_range",
         event_type = event_type.name(),
@@ -181,6 +247,7 @@ pub(crate) fn process_logs_in_range(
 /// Children: process_logs_in_range spans (one per gap)
 #[inline]
 pub(crate) fn calculate_gas_cost_with_adapter(
+    config: &SpanConfig,
     event_type: crate::EventType,
     chain: NamedChain,
     topic1: Address,
@@ -188,6 +255,9 @@ pub(crate) fn calculate_gas_cost_with_adapter(
     start_block: BlockNumber,
     end_block: BlockNumber,
 ) -> Span {
+    if !config.enabled(SpanSubsystem::Gas, SpanVerbosity::Operation) {
+        return Span::none();
+    }
     tracing::info_span!(
         "semioscan.calculate_gas_cost_with_adapter",
         event_type = event_type.name(),
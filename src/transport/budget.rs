@@ -0,0 +1,269 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rate budget shared between scanning loops.
+//!
+//! Each calculator used to pace itself by sleeping `rate_limit_delay` between
+//! chunks. Two calculators on the same provider therefore sent twice the
+//! intended request rate. A [`RateBudget`] holds one token bucket per endpoint;
+//! every scanning loop whose [`SemioscanConfig`](crate::SemioscanConfig) points
+//! at the same endpoint draws permits from the same bucket.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::{sleep, Instant};
+
+/// Token buckets keyed by endpoint, shared by every clone.
+///
+/// Buckets are created on first use. An endpoint configured with
+/// [`with_limit`](Self::with_limit) uses that limit; otherwise its bucket
+/// allows one request per `rate_limit_delay` of the chain being scanned.
+///
+/// # Example
+///
+/// ```rust
+/// use semioscan::{RateBudget, SemioscanConfigBuilder};
+/// use std::time::Duration;
+///
+/// let budget = RateBudget::new().with_limit("alchemy-base", 10, Duration::from_secs(1));
+///
+/// // Both calculators built from these configs share the 10 req/s budget
+/// let gas_config = SemioscanConfigBuilder::new()
+///     .rate_budget(budget.endpoint("alchemy-base"))
+///     .build();
+/// let price_config = gas_config.clone();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RateBudget {
+    state: Arc<Mutex<BudgetState>>,
+}
+
+#[derive(Debug, Default)]
+struct BudgetState {
+    limits: HashMap<String, (u32, Duration)>,
+    buckets: HashMap<String, Bucket>,
+}
+
+impl RateBudget {
+    /// Create an empty budget
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow `requests` per `period` for `endpoint`, regardless of chain delays
+    pub fn with_limit(self, endpoint: impl Into<String>, requests: u32, period: Duration) -> Self {
+        {
+            let mut state = self.lock();
+            let endpoint = endpoint.into();
+            state.buckets.remove(&endpoint);
+            state.limits.insert(endpoint, (requests.max(1), period));
+        }
+        self
+    }
+
+    /// Handle that draws from this budget on behalf of `endpoint`
+    pub fn endpoint(&self, endpoint: impl Into<String>) -> EndpointBudget {
+        EndpointBudget {
+            budget: self.clone(),
+            endpoint: endpoint.into(),
+        }
+    }
+
+    /// Wait for a permit to send one request to `endpoint`
+    ///
+    /// `min_interval` sizes the bucket when `endpoint` has no explicit limit;
+    /// `None` means such endpoints are not throttled.
+    pub async fn acquire(&self, endpoint: &str, min_interval: Option<Duration>) {
+        let wait = {
+            let mut state = self.lock();
+            let limit = state
+                .limits
+                .get(endpoint)
+                .copied()
+                .or(min_interval.map(|interval| (1, interval)));
+            let Some((requests, period)) = limit else {
+                return;
+            };
+            state
+                .buckets
+                .entry(endpoint.to_string())
+                .or_insert_with(|| Bucket::new(requests, period))
+                .reserve(Instant::now())
+        };
+
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BudgetState> {
+        // The critical sections never panic, so a poisoned lock still holds valid state
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A [`RateBudget`] bound to one endpoint, stored in
+/// [`SemioscanConfig::rate_budget`](crate::SemioscanConfig::rate_budget)
+#[derive(Clone, Debug)]
+pub struct EndpointBudget {
+    budget: RateBudget,
+    endpoint: String,
+}
+
+impl EndpointBudget {
+    /// The endpoint key permits are drawn for
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Wait for a permit to send one request
+    pub async fn acquire(&self, min_interval: Option<Duration>) {
+        self.budget.acquire(&self.endpoint, min_interval).await;
+    }
+}
+
+/// Token bucket that hands out reservations, so waiters queue in order
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    interval: Duration,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(requests: u32, period: Duration) -> Self {
+        Self {
+            capacity: f64::from(requests),
+            tokens: f64::from(requests),
+            interval: period / requests,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take one token, returning how long the caller must wait before using it
+    ///
+    /// Tokens may go negative; each waiter is pushed one interval further back.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        if !self.interval.is_zero() {
+            let elapsed = now.saturating_duration_since(self.last_refill);
+            self.tokens = (self.tokens + elapsed.as_secs_f64() / self.interval.as_secs_f64())
+                .min(self.capacity);
+        }
+        self.last_refill = now;
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            self.interval.mul_f64(-self.tokens)
+        }
+    }
+}
+
+/// Paces one scanning loop, through a shared budget when configured
+///
+/// Without a budget this reproduces the old behaviour of sleeping
+/// `min_interval` between consecutive requests of the same loop.
+#[derive(Debug)]
+pub(crate) struct Pacer<'a> {
+    budget: Option<&'a EndpointBudget>,
+    min_interval: Option<Duration>,
+    started: bool,
+}
+
+impl<'a> Pacer<'a> {
+    pub(crate) fn new(budget: Option<&'a EndpointBudget>, min_interval: Option<Duration>) -> Self {
+        Self {
+            budget,
+            min_interval,
+            started: false,
+        }
+    }
+
+    /// Wait until the next request may be sent
+    pub(crate) async fn wait(&mut self) {
+        let first = !std::mem::replace(&mut self.started, true);
+        match (self.budget, self.min_interval) {
+            (Some(budget), interval) => budget.acquire(interval).await,
+            (None, Some(delay)) if !first => sleep(delay).await,
+            (None, _) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_spaces_requests_by_interval() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(1, Duration::from_millis(100));
+        bucket.last_refill = start;
+
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(start), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_bucket_refills_and_allows_bursts_up_to_capacity() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(2, Duration::from_secs(1));
+        bucket.last_refill = start;
+
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::from_millis(500));
+
+        // Two seconds later the bucket is full again, not over capacity
+        let later = start + Duration::from_secs(2);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.reserve(later), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_clones_share_one_bucket_per_endpoint() {
+        let budget = RateBudget::new();
+        let gas = budget.endpoint("shared");
+        let price = budget.endpoint("shared");
+        let other = budget.endpoint("other");
+        let interval = Some(Duration::from_millis(40));
+
+        let started = Instant::now();
+        gas.acquire(interval).await;
+        other.acquire(interval).await;
+        price.acquire(interval).await;
+        gas.acquire(interval).await;
+
+        // Three permits on "shared" need two intervals
+        assert_eq!(started.elapsed(), Duration::from_millis(80));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_endpoints_without_limit_or_interval_are_not_throttled() {
+        let budget = RateBudget::new();
+        let started = Instant::now();
+        for _ in 0..100 {
+            budget.acquire("free", None).await;
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pacer_without_budget_sleeps_between_requests() {
+        let mut pacer = Pacer::new(None, Some(Duration::from_millis(30)));
+        let started = Instant::now();
+        pacer.wait().await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        pacer.wait().await;
+        assert_eq!(started.elapsed(), Duration::from_millis(30));
+    }
+}
@@ -10,6 +10,21 @@
 //! Note: RPC request/response logging is handled natively by alloy's transport
 //! layer at DEBUG/TRACE level.
 //!
+//! # Endpoint Rotation
+//!
+//! [`RotatingTransport`] spreads requests across several endpoints or API keys,
+//! moving on when one is rejected (HTTP 401/403/429) and recording per-endpoint
+//! usage in a [`KeyUsageTracker`]. It is wired up automatically by
+//! [`crate::provider::create_http_provider`] when a
+//! [`crate::provider::ProviderConfig`] has additional URLs.
+//!
+//! # Shared Rate Budgets
+//!
+//! [`RateBudget`] coordinates the scanning loops of several calculators that use
+//! the same endpoint. Set it on each calculator's
+//! [`crate::SemioscanConfig::rate_budget`] and they draw from one token bucket
+//! instead of each sleeping `rate_limit_delay` on its own.
+//!
 //! # Rate Limiting
 //!
 //! The [`RateLimitLayer`] provides configurable rate limiting for RPC requests,
@@ -47,8 +62,13 @@
 //! };
 //! ```
 
+mod budget;
 mod rate_limit;
 mod retry;
+mod rotation;
 
+pub(crate) use budget::Pacer;
+pub use budget::{EndpointBudget, RateBudget};
 pub use rate_limit::{RateLimitLayer, RateLimitService};
 pub use retry::{RetryConfig, RetryLayer, RetryLayerBuilder, RetryService};
+pub use rotation::{KeyUsage, KeyUsageTracker, RotatingTransport, RotationReason};
@@ -0,0 +1,419 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Endpoint rotation across multiple API keys or RPC URLs.
+//!
+//! Long backfills against keyed RPC providers fail when a key is revoked,
+//! rotated, or exhausts its quota. [`RotatingTransport`] holds one transport per
+//! endpoint and moves on to the next endpoint whenever the current one answers
+//! with HTTP 401/403 (bad or expired key) or 429 (quota exhausted). Every
+//! request, success, and rotation is counted per endpoint in a
+//! [`KeyUsageTracker`].
+//!
+//! Endpoints are reported by a redacted label (`scheme://host#index`) so API
+//! keys embedded in URLs never reach logs or usage snapshots.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket, RpcError};
+use alloy_transport::{TransportError, TransportErrorKind};
+use tracing::{debug, warn};
+
+/// Why a request moved on to the next endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationReason {
+    /// The endpoint rejected the key (HTTP 401 or 403)
+    Unauthorized,
+    /// The endpoint rate limited or exhausted the key's quota (HTTP 429)
+    RateLimited,
+}
+
+/// Usage counters for a single endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyUsage {
+    /// Redacted endpoint label (`scheme://host#index`)
+    pub endpoint: String,
+    /// Requests sent to this endpoint
+    pub requests: u64,
+    /// Requests that succeeded
+    pub successes: u64,
+    /// Requests rejected with HTTP 401/403
+    pub unauthorized: u64,
+    /// Requests rejected with HTTP 429
+    pub rate_limited: u64,
+    /// Requests that failed for any other reason
+    pub other_errors: u64,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    endpoints: Vec<KeyUsage>,
+    current: usize,
+}
+
+/// Shared per-endpoint usage accounting for a [`RotatingTransport`]
+///
+/// Cloning the tracker shares the underlying counters, so a clone kept by the
+/// caller observes the usage of the transport it was handed to.
+#[derive(Debug, Clone, Default)]
+pub struct KeyUsageTracker {
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl KeyUsageTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the counters for every endpoint, in rotation order
+    pub fn snapshot(&self) -> Vec<KeyUsage> {
+        self.lock().endpoints.clone()
+    }
+
+    /// Index of the endpoint new requests start from
+    pub fn current_index(&self) -> usize {
+        self.lock().current
+    }
+
+    /// Track `labels`, keeping the counters of endpoints already tracked
+    fn register(&self, labels: Vec<String>) {
+        let mut state = self.lock();
+        let previous = std::mem::take(&mut state.endpoints);
+        state.endpoints = labels
+            .into_iter()
+            .map(|endpoint| {
+                previous
+                    .iter()
+                    .find(|usage| usage.endpoint == endpoint)
+                    .cloned()
+                    .unwrap_or_else(|| KeyUsage {
+                        endpoint,
+                        ..Default::default()
+                    })
+            })
+            .collect();
+        if state.current >= state.endpoints.len() {
+            state.current = 0;
+        }
+    }
+
+    fn record(&self, index: usize, update: impl FnOnce(&mut KeyUsage)) {
+        if let Some(usage) = self.lock().endpoints.get_mut(index) {
+            update(usage);
+        }
+    }
+
+    /// Advance past `index` unless another request already rotated away from it
+    fn rotate_from(&self, index: usize) {
+        let mut state = self.lock();
+        let len = state.endpoints.len().max(1);
+        if state.current == index {
+            state.current = (index + 1) % len;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TrackerState> {
+        // Counters stay meaningful even if a holder panicked mid-update
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A transport that rotates across endpoints on authorization and quota errors
+///
+/// Each request starts at the tracker's current endpoint. If it fails with a
+/// [`RotationReason`], the tracker advances and the request is retried on the
+/// next endpoint, trying each endpoint at most once. Any other error is
+/// returned immediately so retry layers above can handle it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use semioscan::transport::{KeyUsageTracker, RotatingTransport};
+/// use alloy_rpc_client::ClientBuilder;
+/// use alloy_transport_http::Http;
+///
+/// let tracker = KeyUsageTracker::new();
+/// let transport = RotatingTransport::new(
+///     vec![(primary_url.clone(), Http::new(primary_url)), (backup_url.clone(), Http::new(backup_url))],
+///     tracker.clone(),
+/// );
+/// let client = ClientBuilder::default().transport(transport, false);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RotatingTransport<S> {
+    endpoints: Arc<[S]>,
+    tracker: KeyUsageTracker,
+}
+
+impl<S> RotatingTransport<S> {
+    /// Create a rotating transport over `(url, transport)` pairs
+    ///
+    /// URLs are only used to derive redacted labels for the tracker. Counters
+    /// the tracker already holds for an endpoint with the same label are kept,
+    /// so transports rebuilt from one [`ProviderConfig`](crate::ProviderConfig)
+    /// keep accumulating into the same per-endpoint counts.
+    pub fn new(endpoints: Vec<(url::Url, S)>, tracker: KeyUsageTracker) -> Self {
+        let (urls, transports): (Vec<_>, Vec<_>) = endpoints.into_iter().unzip();
+        tracker.register(
+            urls.iter()
+                .enumerate()
+                .map(|(index, url)| redacted_label(url, index))
+                .collect(),
+        );
+        Self {
+            endpoints: transports.into(),
+            tracker,
+        }
+    }
+
+    /// The tracker recording this transport's usage
+    pub fn tracker(&self) -> &KeyUsageTracker {
+        &self.tracker
+    }
+}
+
+impl<S> tower::Service<RequestPacket> for RotatingTransport<S>
+where
+    S: tower::Service<RequestPacket, Response = ResponsePacket, Error = TransportError>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    S::Future: Send,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness is checked per endpoint when the request is dispatched
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let endpoints = self.endpoints.clone();
+        let tracker = self.tracker.clone();
+
+        Box::pin(async move {
+            let count = endpoints.len();
+            if count == 0 {
+                return Err(TransportErrorKind::custom_str(
+                    "no RPC endpoints configured",
+                ));
+            }
+
+            let start = tracker.current_index() % count;
+            let mut last_error = None;
+
+            for offset in 0..count {
+                let index = (start + offset) % count;
+                let mut service = endpoints[index].clone();
+                tracker.record(index, |usage| usage.requests += 1);
+
+                let error = match service.call(request.clone()).await {
+                    Ok(response) => {
+                        tracker.record(index, |usage| usage.successes += 1);
+                        return Ok(response);
+                    }
+                    Err(error) => error,
+                };
+
+                let Some(reason) = rotation_reason(&error) else {
+                    tracker.record(index, |usage| usage.other_errors += 1);
+                    return Err(error);
+                };
+
+                tracker.record(index, |usage| match reason {
+                    RotationReason::Unauthorized => usage.unauthorized += 1,
+                    RotationReason::RateLimited => usage.rate_limited += 1,
+                });
+                tracker.rotate_from(index);
+                warn!(
+                    endpoint = index,
+                    ?reason,
+                    error = %error,
+                    "RPC endpoint rejected request, rotating to next endpoint"
+                );
+                last_error = Some(error);
+            }
+
+            debug!(endpoints = count, "All RPC endpoints rejected the request");
+            Err(last_error.expect("at least one endpoint was tried"))
+        })
+    }
+}
+
+/// Classifies errors that should move a request on to the next endpoint
+fn rotation_reason(error: &TransportError) -> Option<RotationReason> {
+    match error {
+        RpcError::Transport(TransportErrorKind::HttpError(http)) => match http.status {
+            401 | 403 => Some(RotationReason::Unauthorized),
+            429 => Some(RotationReason::RateLimited),
+            _ => None,
+        },
+        // Some providers report quota exhaustion as a JSON-RPC error with code 429
+        RpcError::ErrorResp(payload) if payload.code == 429 => Some(RotationReason::RateLimited),
+        _ => None,
+    }
+}
+
+/// Label an endpoint without exposing keys in its path or query string
+fn redacted_label(url: &url::Url, index: usize) -> String {
+    let host = url.host_str().unwrap_or("unknown");
+    format!("{}://{host}#{index}", url.scheme())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, Response, ResponsePayload};
+    use serde_json::value::RawValue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::Service;
+
+    /// Endpoint that always fails with `status`, or succeeds when `status` is `None`
+    #[derive(Clone)]
+    struct MockEndpoint {
+        status: Option<u16>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockEndpoint {
+        fn new(status: Option<u16>) -> Self {
+            Self {
+                status,
+                calls: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    impl tower::Service<RequestPacket> for MockEndpoint {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = Pin<Box<dyn Future<Output = Result<ResponsePacket, TransportError>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: RequestPacket) -> Self::Future {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let status = self.status;
+            Box::pin(async move {
+                match status {
+                    Some(status) => Err(TransportErrorKind::http_error(status, String::new())),
+                    None => Ok(ResponsePacket::Single(Response {
+                        id: Id::Number(1),
+                        payload: ResponsePayload::Success(
+                            RawValue::from_string("\"0x1\"".to_string()).unwrap(),
+                        ),
+                    })),
+                }
+            })
+        }
+    }
+
+    fn request() -> RequestPacket {
+        RequestPacket::Single(
+            Request::new("eth_blockNumber", Id::Number(1), ())
+                .serialize()
+                .unwrap(),
+        )
+    }
+
+    fn transport(endpoints: &[MockEndpoint]) -> RotatingTransport<MockEndpoint> {
+        transport_with_tracker(endpoints, KeyUsageTracker::new())
+    }
+
+    fn transport_with_tracker(
+        endpoints: &[MockEndpoint],
+        tracker: KeyUsageTracker,
+    ) -> RotatingTransport<MockEndpoint> {
+        RotatingTransport::new(
+            endpoints
+                .iter()
+                .enumerate()
+                .map(|(i, endpoint)| {
+                    let url = format!("https://eth-mainnet.example.com/v2/secret-key-{i}");
+                    (url.parse().unwrap(), endpoint.clone())
+                })
+                .collect(),
+            tracker,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rotates_past_exhausted_keys() {
+        let endpoints = [MockEndpoint::new(Some(429)), MockEndpoint::new(None)];
+        let mut transport = transport(&endpoints);
+
+        assert!(transport.call(request()).await.is_ok());
+        assert_eq!(transport.tracker().current_index(), 1);
+
+        // Subsequent requests start from the healthy endpoint
+        assert!(transport.call(request()).await.is_ok());
+        assert_eq!(endpoints[0].calls.load(Ordering::SeqCst), 1);
+        assert_eq!(endpoints[1].calls.load(Ordering::SeqCst), 2);
+
+        let usage = transport.tracker().snapshot();
+        assert_eq!(usage[0].rate_limited, 1);
+        assert_eq!(usage[1].successes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fails_after_every_key_is_rejected() {
+        let endpoints = [MockEndpoint::new(Some(401)), MockEndpoint::new(Some(429))];
+        let mut transport = transport(&endpoints);
+
+        assert!(transport.call(request()).await.is_err());
+
+        let usage = transport.tracker().snapshot();
+        assert_eq!(usage[0].unauthorized, 1);
+        assert_eq!(usage[1].rate_limited, 1);
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_do_not_rotate() {
+        let endpoints = [MockEndpoint::new(Some(500)), MockEndpoint::new(None)];
+        let mut transport = transport(&endpoints);
+
+        assert!(transport.call(request()).await.is_err());
+        assert_eq!(transport.tracker().current_index(), 0);
+        assert_eq!(endpoints[1].calls.load(Ordering::SeqCst), 0);
+        assert_eq!(transport.tracker().snapshot()[0].other_errors, 1);
+    }
+
+    #[test]
+    fn test_labels_do_not_leak_keys() {
+        let transport = transport(&[MockEndpoint::new(None)]);
+        let usage = transport.tracker().snapshot();
+        assert_eq!(usage[0].endpoint, "https://eth-mainnet.example.com#0");
+    }
+
+    #[tokio::test]
+    async fn test_rebuilt_transport_keeps_per_endpoint_counts() {
+        let endpoints = [MockEndpoint::new(Some(429)), MockEndpoint::new(None)];
+        let tracker = KeyUsageTracker::new();
+
+        let mut first = transport_with_tracker(&endpoints, tracker.clone());
+        assert!(first.call(request()).await.is_ok());
+
+        // A transport rebuilt from the same config shares the tracker
+        let mut second = transport_with_tracker(&endpoints, tracker.clone());
+        assert_eq!(tracker.current_index(), 1);
+        assert!(second.call(request()).await.is_ok());
+
+        let usage = tracker.snapshot();
+        assert_eq!(usage[0].requests, 1);
+        assert_eq!(usage[0].rate_limited, 1);
+        assert_eq!(usage[1].requests, 2);
+        assert_eq!(usage[1].successes, 2);
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Addresses tagged with the role they play in a transfer
+//!
+//! Calculators that take a sender, a recipient and a token accept these
+//! types, so passing a token where a sender belongs fails to compile once
+//! the values are typed. Each converts from and into a plain [`Address`],
+//! so untyped call sites keep working.
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// Define an [`Address`] newtype for one role, convertible from and into a
+/// plain address
+macro_rules! role_address {
+    ($(#[$meta:meta])* $name:ident, $role:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(Address);
+
+        impl $name {
+            #[doc = concat!("Create a ", $role, " address")]
+            pub const fn new(address: Address) -> Self {
+                Self(address)
+            }
+
+            /// Get the inner address
+            pub const fn as_address(&self) -> Address {
+                self.0
+            }
+        }
+
+        impl From<Address> for $name {
+            fn from(address: Address) -> Self {
+                Self(address)
+            }
+        }
+
+        impl From<$name> for Address {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+role_address!(
+    /// Address sending the transfers being analyzed
+    ///
+    /// For Approval events, the owner granting the allowance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_primitives::Address;
+    /// use semioscan::SenderAddress;
+    ///
+    /// let sender = SenderAddress::from(Address::repeat_byte(1));
+    /// assert_eq!(sender.as_address(), Address::repeat_byte(1));
+    /// ```
+    ///
+    /// A typed address in another role isn't accepted where a sender belongs:
+    ///
+    /// ```compile_fail
+    /// use alloy_primitives::Address;
+    /// use semioscan::{CombinedQuery, TokenAddress};
+    ///
+    /// let token = TokenAddress::new(Address::repeat_byte(1));
+    /// let builder = CombinedQuery::builder().from(token);
+    /// ```
+    SenderAddress,
+    "sender"
+);
+
+role_address!(
+    /// Address receiving the transfers being analyzed
+    ///
+    /// For Approval events, the spender being granted the allowance.
+    RecipientAddress,
+    "recipient"
+);
+
+role_address!(
+    /// Contract address of the token being transferred
+    TokenAddress,
+    "token"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_serialize_as_plain_addresses() {
+        let address = Address::repeat_byte(0xab);
+        let token = TokenAddress::from(address);
+
+        assert_eq!(
+            serde_json::to_string(&token).unwrap(),
+            serde_json::to_string(&address).unwrap()
+        );
+        assert_eq!(token.to_string(), address.to_string());
+        let sender: SenderAddress =
+            serde_json::from_str(&serde_json::to_string(&address).unwrap()).unwrap();
+        assert_eq!(Address::from(sender), address);
+    }
+}
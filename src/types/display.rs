@@ -0,0 +1,380 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Number formatting policy for display strings
+//!
+//! [`DisplayOptions`] controls how the `to_display` methods on
+//! [`UsdValue`](crate::UsdValue), [`TokenPrice`](crate::TokenPrice),
+//! [`NormalizedAmount`](crate::NormalizedAmount),
+//! [`WeiAmount`](crate::WeiAmount), [`GasPrice`](crate::GasPrice),
+//! [`L1DataFee`](crate::L1DataFee), [`Percentage`](crate::Percentage) and
+//! [`GasCostResult`](crate::GasCostResult) render their values: fixed or exact precision, digit grouping, and scientific
+//! notation. Fixed precision rounds half away from zero, so every value in a
+//! column has the same number of decimal places.
+//!
+//! [`DisplayValue`] pairs a formatted amount with its unit, so consumers
+//! don't have to assume one. Amounts of a chain's native currency carry that
+//! chain's symbol rather than `ETH`, and fiat amounts carry their currency
+//! code.
+
+use alloy_chains::NamedChain;
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::types::gas::GasPrice;
+use crate::types::tokens::{FiatValue, UsdValue};
+use crate::types::wei::WeiAmount;
+
+/// How `to_display` methods format numbers
+///
+/// The default keeps every significant digit, trimming trailing fractional
+/// zeros, with no digit grouping.
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::{DisplayOptions, UsdValue};
+///
+/// let value = UsdValue::new(1234567.5);
+/// assert_eq!(value.to_display(&DisplayOptions::default()), "$1234567.5");
+/// assert_eq!(value.to_display(&DisplayOptions::fixed(2)), "$1234567.50");
+/// assert_eq!(
+///     value.to_display(&DisplayOptions::fixed(2).with_thousands_separator(',')),
+///     "$1,234,567.50"
+/// );
+/// assert_eq!(
+///     value.to_display(&DisplayOptions::fixed(3).with_scientific(true)),
+///     "$1.235e6"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DisplayOptions {
+    /// Digits after the decimal point, or `None` to keep every significant
+    /// digit
+    ///
+    /// In scientific notation, this counts the digits of the mantissa.
+    #[serde(rename = "decimal_places", default)]
+    pub decimal_places: Option<usize>,
+    /// Character grouping the integer digits in threes, if any
+    ///
+    /// Ignored in scientific notation.
+    #[serde(rename = "thousands_separator", default)]
+    pub thousands_separator: Option<char>,
+    /// Format as a mantissa and power of ten, like `1.5e-7`
+    #[serde(rename = "scientific", default)]
+    pub scientific: bool,
+}
+
+impl DisplayOptions {
+    /// Round to exactly `decimal_places` digits after the decimal point
+    pub fn fixed(decimal_places: usize) -> Self {
+        Self {
+            decimal_places: Some(decimal_places),
+            ..Self::default()
+        }
+    }
+
+    /// Group integer digits in threes with `separator`
+    pub fn with_thousands_separator(mut self, separator: char) -> Self {
+        self.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Enable or disable scientific notation
+    pub fn with_scientific(mut self, scientific: bool) -> Self {
+        self.scientific = scientific;
+        self
+    }
+
+    /// Format a float
+    ///
+    /// NaN and infinite values are formatted as Rust would.
+    pub(crate) fn format_f64(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        // `Display` for floats never uses an exponent and gives the shortest
+        // digits that round-trip
+        let digits = value.abs().to_string();
+        let (whole, fractional) = digits.split_once('.').unwrap_or((&digits, ""));
+        let formatted = self.format_digits(whole, fractional);
+        if value.is_sign_negative() && formatted.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+            format!("-{formatted}")
+        } else {
+            formatted
+        }
+    }
+
+    /// Format a fixed-point integer with `decimals` fractional digits, exactly
+    pub(crate) fn format_units(&self, value: U256, decimals: u8) -> String {
+        let digits = format!(
+            "{:0>width$}",
+            value.to_string(),
+            width = decimals as usize + 1
+        );
+        let (whole, fractional) = digits.split_at(digits.len() - decimals as usize);
+        self.format_digits(whole, fractional)
+    }
+
+    /// Format a non-negative decimal given as its integer and fractional digits
+    fn format_digits(&self, whole: &str, fractional: &str) -> String {
+        let whole = match whole.trim_start_matches('0') {
+            "" => "0",
+            trimmed => trimmed,
+        };
+        if self.scientific {
+            self.format_scientific(whole, fractional)
+        } else {
+            self.format_positional(whole, fractional)
+        }
+    }
+
+    fn format_positional(&self, whole: &str, fractional: &str) -> String {
+        let (whole, fractional) = match self.decimal_places {
+            Some(places) => {
+                let (mut digits, carried) =
+                    round_digits(&format!("{whole}{fractional}"), whole.len() + places);
+                if carried {
+                    digits.insert(0, '1');
+                }
+                let fractional = digits.split_off(digits.len() - places);
+                (digits, fractional)
+            }
+            None => (
+                whole.to_string(),
+                fractional.trim_end_matches('0').to_string(),
+            ),
+        };
+        let whole = match self.thousands_separator {
+            Some(separator) => group_thousands(&whole, separator),
+            None => whole,
+        };
+        if fractional.is_empty() {
+            whole
+        } else {
+            format!("{whole}.{fractional}")
+        }
+    }
+
+    fn format_scientific(&self, whole: &str, fractional: &str) -> String {
+        let digits = format!("{whole}{fractional}");
+        let leading_zeros = digits.len() - digits.trim_start_matches('0').len();
+        let significant = &digits[leading_zeros..];
+        if significant.is_empty() {
+            let mantissa = match self.decimal_places {
+                Some(places) if places > 0 => format!("0.{}", "0".repeat(places)),
+                _ => "0".to_string(),
+            };
+            return format!("{mantissa}e0");
+        }
+
+        let mut exponent = whole.len() as i64 - 1 - leading_zeros as i64;
+        let mantissa = match self.decimal_places {
+            Some(places) => {
+                let (mut rounded, carried) = round_digits(significant, places + 1);
+                if carried {
+                    rounded.insert(0, '1');
+                    rounded.pop();
+                    exponent += 1;
+                }
+                rounded
+            }
+            None => significant.trim_end_matches('0').to_string(),
+        };
+        let (first, rest) = mantissa.split_at(1);
+        if rest.is_empty() {
+            format!("{first}e{exponent}")
+        } else {
+            format!("{first}.{rest}e{exponent}")
+        }
+    }
+}
+
+/// A formatted amount and the unit it is in
+///
+/// Amounts always use `.` as the decimal point, whatever the locale. They
+/// are only grouped when the [`DisplayOptions`] used ask for a thousands
+/// separator, so leave it unset for output meant to be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_chains::NamedChain;
+/// use alloy_primitives::U256;
+/// use semioscan::{DisplayOptions, DisplayValue, WeiAmount};
+///
+/// let fee = WeiAmount::new(U256::from(2_500_000_000_000_000u64));
+/// let value = DisplayValue::native(fee, NamedChain::BinanceSmartChain, &DisplayOptions::fixed(4));
+/// assert_eq!(value.amount, "0.0025");
+/// assert_eq!(value.unit, "BNB");
+/// assert_eq!(value.to_string(), "0.0025 BNB");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DisplayValue {
+    /// The formatted number, without any currency sign
+    #[serde(rename = "amount")]
+    pub amount: String,
+    /// Unit of `amount`, such as `ETH`, `gwei` or `USD`
+    #[serde(rename = "unit")]
+    pub unit: String,
+}
+
+/// Unit used for native amounts of chains without a known currency symbol
+const UNKNOWN_NATIVE_UNIT: &str = "native";
+
+impl DisplayValue {
+    /// Pair an already formatted `amount` with its `unit`
+    pub fn new(amount: impl Into<String>, unit: impl Into<String>) -> Self {
+        Self {
+            amount: amount.into(),
+            unit: unit.into(),
+        }
+    }
+
+    /// An amount of `chain`'s native currency, in whole units
+    ///
+    /// The unit is the chain's currency symbol, or `native` when it has none
+    /// on record.
+    pub fn native(amount: WeiAmount, chain: NamedChain, options: &DisplayOptions) -> Self {
+        Self::new(
+            amount.to_display(options),
+            chain
+                .native_currency_symbol()
+                .unwrap_or(UNKNOWN_NATIVE_UNIT),
+        )
+    }
+
+    /// A gas price in gwei
+    pub fn gwei(price: GasPrice, options: &DisplayOptions) -> Self {
+        Self::new(price.to_display(options), "gwei")
+    }
+
+    /// A USD value, with the unit `USD` in place of the `$` sign
+    pub fn usd(value: UsdValue, options: &DisplayOptions) -> Self {
+        Self::new(options.format_f64(value.as_f64()), "USD")
+    }
+
+    /// A fiat value, with its currency code in place of a sign
+    pub fn fiat(value: FiatValue, options: &DisplayOptions) -> Self {
+        Self::new(options.format_f64(value.amount), value.currency.as_str())
+    }
+}
+
+impl std::fmt::Display for DisplayValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.unit)
+    }
+}
+
+/// Round a digit string to its first `keep` digits, half away from zero
+///
+/// Pads with zeros when shorter. Returns whether rounding carried out of the
+/// first digit, in which case the caller must prepend a `1`.
+fn round_digits(digits: &str, keep: usize) -> (String, bool) {
+    if digits.len() <= keep {
+        return (format!("{digits:0<keep$}"), false);
+    }
+    let mut kept: Vec<u8> = digits.as_bytes()[..keep].to_vec();
+    if digits.as_bytes()[keep] < b'5' {
+        return (String::from_utf8(kept).unwrap_or_default(), false);
+    }
+    for digit in kept.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return (String::from_utf8(kept).unwrap_or_default(), false);
+        }
+    }
+    (String::from_utf8(kept).unwrap_or_default(), true)
+}
+
+/// Insert `separator` between groups of three integer digits
+fn group_thousands(whole: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(whole.len() + whole.len() / 3);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::tokens::Currency;
+
+    #[test]
+    fn test_fixed_precision_pads_and_rounds() {
+        let options = DisplayOptions::fixed(2);
+        assert_eq!(options.format_f64(1.5), "1.50");
+        assert_eq!(options.format_f64(0.005), "0.01");
+        assert_eq!(options.format_f64(999.999), "1000.00");
+        assert_eq!(options.format_f64(-0.001), "0.00");
+        assert_eq!(DisplayOptions::fixed(0).format_f64(2.5), "3");
+
+        // Exact integers round without going through a float
+        let wei = U256::from(1_234_567_890_123_456_789u128);
+        assert_eq!(DisplayOptions::fixed(4).format_units(wei, 18), "1.2346");
+        assert_eq!(
+            DisplayOptions::default().format_units(wei, 18),
+            "1.234567890123456789"
+        );
+        assert_eq!(
+            DisplayOptions::default().format_units(U256::from(5u64), 9),
+            "0.000000005"
+        );
+    }
+
+    #[test]
+    fn test_thousands_separator_groups_integer_digits() {
+        let options = DisplayOptions::fixed(1).with_thousands_separator(',');
+        assert_eq!(options.format_f64(1_234_567.25), "1,234,567.3");
+        assert_eq!(options.format_f64(123.0), "123.0");
+        assert_eq!(options.format_f64(-1_000.0), "-1,000.0");
+    }
+
+    #[test]
+    fn test_scientific_notation_normalizes_mantissa() {
+        let options = DisplayOptions::default().with_scientific(true);
+        assert_eq!(options.format_f64(0.000_000_15), "1.5e-7");
+        assert_eq!(options.format_f64(1_500.0), "1.5e3");
+        assert_eq!(options.format_f64(0.0), "0e0");
+        assert_eq!(
+            DisplayOptions::fixed(2)
+                .with_scientific(true)
+                .format_f64(9.996),
+            "1.00e1"
+        );
+        assert_eq!(
+            DisplayOptions::fixed(1)
+                .with_scientific(true)
+                .format_units(U256::from(1_500_000_000u64), 18),
+            "1.5e-9"
+        );
+    }
+
+    #[test]
+    fn test_display_value_uses_the_chains_native_unit() {
+        let options = DisplayOptions::fixed(2);
+        let fee = WeiAmount::new(U256::from(1_000_000_000_000_000_000u128));
+
+        let mainnet = DisplayValue::native(fee, NamedChain::Mainnet, &options);
+        assert_eq!(mainnet, DisplayValue::new("1.00", "ETH"));
+        let bsc = DisplayValue::native(fee, NamedChain::BinanceSmartChain, &options);
+        assert_eq!(bsc, DisplayValue::new("1.00", "BNB"));
+
+        let usd = DisplayValue::usd(UsdValue::new(12.5), &options);
+        assert_eq!(usd.to_string(), "12.50 USD");
+        assert_eq!(
+            serde_json::to_value(&usd).unwrap(),
+            serde_json::json!({"amount": "12.50", "unit": "USD"})
+        );
+
+        let eur = DisplayValue::fiat(UsdValue::new(12.5).to_fiat(Currency::EUR, 0.5), &options);
+        assert_eq!(eur.to_string(), "6.25 EUR");
+    }
+}
@@ -6,10 +6,14 @@
 //!
 //! Separates different kinds of fees for type safety and clarity.
 
+use alloy_chains::NamedChain;
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 use std::ops::Add;
 
+use crate::types::display::{DisplayOptions, DisplayValue};
+use crate::types::wei::WeiAmount;
+
 /// L1 data fee for L2 transactions
 ///
 /// L2 chains (Arbitrum, Optimism, Base, etc.) post transaction data to L1
@@ -129,6 +133,29 @@ impl L1DataFee {
         let wei_f64 = wei_str.parse::<f64>().unwrap_or(0.0);
         wei_f64 / wei_per_eth as f64
     }
+
+    /// Format the fee in whole units of the native currency, without a unit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_primitives::U256;
+    /// use semioscan::{DisplayOptions, L1DataFee};
+    ///
+    /// let fee = L1DataFee::new(U256::from(1_250_000_000_000_000u64));
+    /// assert_eq!(fee.to_display(&DisplayOptions::fixed(4)), "0.0013");
+    /// ```
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        WeiAmount::new(self.0).to_display(options)
+    }
+
+    /// Format in `chain`'s native currency, tagged with its symbol
+    ///
+    /// L1 data fees are charged in the L2's native currency, which isn't
+    /// always ETH.
+    pub fn to_display_value(&self, chain: NamedChain, options: &DisplayOptions) -> DisplayValue {
+        DisplayValue::native(WeiAmount::new(self.0), chain, options)
+    }
 }
 
 impl From<u64> for L1DataFee {
@@ -151,14 +178,13 @@ impl Add for L1DataFee {
     }
 }
 
+/// Formats like [`WeiAmount`], without a currency symbol
+///
+/// Use [`to_display_value`](L1DataFee::to_display_value) for a string tagged
+/// with the chain's native currency.
 impl std::fmt::Display for L1DataFee {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let eth = self.as_eth_f64();
-        if eth < 0.000001 {
-            write!(f, "{} wei (L1 data fee)", self.0)
-        } else {
-            write!(f, "{:.6} ETH (L1 data fee)", eth)
-        }
+        write!(f, "{} (L1 data fee)", WeiAmount::new(self.0))
     }
 }
 
@@ -254,6 +280,21 @@ impl Percentage {
         format!("{:.2}%", self.0 * 100.0)
     }
 
+    /// Format as a percentage string under `options`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semioscan::{DisplayOptions, Percentage};
+    ///
+    /// let percent = Percentage::new(0.7534);
+    /// assert_eq!(percent.to_display(&DisplayOptions::fixed(1)), "75.3%");
+    /// assert_eq!(percent.to_display(&DisplayOptions::default()), "75.34%");
+    /// ```
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        format!("{}%", options.format_f64(self.0 * 100.0))
+    }
+
     /// Check if percentage is zero
     pub fn is_zero(&self) -> bool {
         self.0.abs() < f64::EPSILON
@@ -364,10 +405,19 @@ mod tests {
     fn test_display_large_fee() {
         // 0.01 ETH in wei
         let fee = L1DataFee::new(U256::from(10_000_000_000_000_000u64));
-        let display = format!("{}", fee);
-        assert!(display.contains("0.01"));
-        assert!(display.contains("ETH"));
-        assert!(display.contains("L1 data fee"));
+        assert_eq!(format!("{}", fee), "0.010000 (L1 data fee)");
+    }
+
+    #[test]
+    fn test_to_display_follows_options() {
+        let fee = L1DataFee::new(U256::from(1_234_500_000_000_000_000u128));
+        let options = DisplayOptions::fixed(2).with_thousands_separator(',');
+        assert_eq!(fee.to_display(&options), "1.23");
+        assert_eq!(
+            Percentage::new(0.123456).to_display(&DisplayOptions::fixed(3)),
+            "12.346%"
+        );
+        assert_eq!(Percentage::ONE_HUNDRED.to_display(&options), "100.00%");
     }
 
     #[test]
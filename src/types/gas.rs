@@ -14,6 +14,9 @@ use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, Mul};
 
+use crate::types::display::DisplayOptions;
+use crate::types::wei::{format_units, WeiAmount, GWEI_DECIMALS};
+
 /// Amount of gas consumed by a transaction
 ///
 /// This represents the total gas units consumed, not the cost.
@@ -53,6 +56,43 @@ impl GasAmount {
     pub fn cost(&self, price: GasPrice) -> U256 {
         self.0.saturating_mul(price.0)
     }
+
+    /// Split the cost at `effective_price` into base fee and priority fee
+    ///
+    /// Returns `(base_fee_cost, priority_fee_cost)`. The base fee is capped at
+    /// the effective price, so the two always add up to
+    /// [`cost`](Self::cost)`(effective_price)`.
+    ///
+    /// # Example
+    /// ```
+    /// use alloy_primitives::U256;
+    /// use semioscan::{GasAmount, GasPrice};
+    ///
+    /// let gas = GasAmount::new(21_000);
+    /// let (burned, tip) = gas.fee_split(GasPrice::from_gwei(12), GasPrice::from_gwei(10));
+    /// assert_eq!(burned, gas.cost(GasPrice::from_gwei(10)));
+    /// assert_eq!(tip, gas.cost(GasPrice::from_gwei(2)));
+    /// ```
+    pub fn fee_split(&self, effective_price: GasPrice, base_fee: GasPrice) -> (U256, U256) {
+        let base_fee_cost = self.cost(base_fee.min(effective_price));
+        let total = self.cost(effective_price);
+        (base_fee_cost, total.saturating_sub(base_fee_cost))
+    }
+
+    /// Multiply gas amount by gas price, returning `None` on overflow
+    pub fn checked_cost(&self, price: GasPrice) -> Option<WeiAmount> {
+        self.0.checked_mul(price.0).map(WeiAmount::new)
+    }
+
+    /// Checked addition. Returns `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Saturating subtraction, clamping at zero
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
 }
 
 impl From<u64> for GasAmount {
@@ -128,6 +168,25 @@ impl GasPrice {
     pub fn total_cost(&self, amount: GasAmount) -> U256 {
         self.0.saturating_mul(amount.0)
     }
+
+    /// Multiply by gas amount, returning `None` on overflow
+    pub fn checked_total_cost(&self, amount: GasAmount) -> Option<WeiAmount> {
+        amount.checked_cost(*self)
+    }
+
+    /// Format as an exact decimal gwei string with trailing zeros removed
+    ///
+    /// Unlike [`as_gwei_f64`](Self::as_gwei_f64), this keeps sub-gwei precision.
+    pub fn to_gwei_string(&self) -> String {
+        format_units(self.0, GWEI_DECIMALS)
+    }
+
+    /// Format as a decimal gwei string following `options`
+    ///
+    /// Rounding is exact, without converting through a float.
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        options.format_units(self.0, GWEI_DECIMALS)
+    }
 }
 
 impl From<u64> for GasPrice {
@@ -154,20 +213,24 @@ impl std::fmt::Display for GasPrice {
 }
 
 /// Type-safe multiplication: GasAmount × GasPrice = Wei cost
+///
+/// Saturates at `U256::MAX`; use [`GasAmount::checked_cost`] to detect overflow.
 impl Mul<GasPrice> for GasAmount {
-    type Output = U256;
+    type Output = WeiAmount;
 
     fn mul(self, rhs: GasPrice) -> Self::Output {
-        self.cost(rhs)
+        WeiAmount::new(self.cost(rhs))
     }
 }
 
 /// Type-safe multiplication: GasPrice × GasAmount = Wei cost
+///
+/// Saturates at `U256::MAX`; use [`GasPrice::checked_total_cost`] to detect overflow.
 impl Mul<GasAmount> for GasPrice {
-    type Output = U256;
+    type Output = WeiAmount;
 
     fn mul(self, rhs: GasAmount) -> Self::Output {
-        self.total_cost(rhs)
+        WeiAmount::new(self.total_cost(rhs))
     }
 }
 
@@ -213,7 +276,39 @@ mod tests {
         let cost1 = gas * price;
         let cost2 = price * gas;
         assert_eq!(cost1, cost2);
-        assert_eq!(cost1, U256::from(1_000_000_000_000_000u64));
+        assert_eq!(cost1, WeiAmount::from(1_000_000_000_000_000u64));
+    }
+
+    #[test]
+    fn test_checked_cost() {
+        let gas = GasAmount::new(21000);
+        let price = GasPrice::from_gwei(50);
+        assert_eq!(
+            gas.checked_cost(price),
+            Some(WeiAmount::from(1_050_000_000_000_000u64))
+        );
+        assert_eq!(price.checked_total_cost(gas), gas.checked_cost(price));
+
+        let max_gas = GasAmount::from(U256::MAX);
+        assert_eq!(max_gas.checked_cost(price), None);
+        assert_eq!(max_gas * price, WeiAmount::new(U256::MAX));
+    }
+
+    #[test]
+    fn test_gas_amount_checked_and_saturating() {
+        let a = GasAmount::new(50000);
+        let b = GasAmount::new(21000);
+        assert_eq!(a.checked_add(b), Some(GasAmount::new(71000)));
+        assert_eq!(GasAmount::from(U256::MAX).checked_add(b), None);
+        assert_eq!(a.saturating_sub(b), GasAmount::new(29000));
+        assert_eq!(b.saturating_sub(a), GasAmount::new(0));
+    }
+
+    #[test]
+    fn test_gas_price_to_gwei_string() {
+        assert_eq!(GasPrice::from_gwei(50).to_gwei_string(), "50");
+        assert_eq!(GasPrice::new(1_500_000_000).to_gwei_string(), "1.5");
+        assert_eq!(GasPrice::new(100).to_gwei_string(), "0.0000001");
     }
 
     #[test]
@@ -535,15 +630,30 @@ impl std::fmt::Display for BlobGasPrice {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct GasBreakdown {
     /// Cost for regular execution gas (gas_used * effective_gas_price)
+    #[serde(rename = "execution_gas_cost")]
     pub execution_gas_cost: U256,
     /// Cost for blob gas (blob_gas_used * blob_gas_price) - EIP-4844 only
+    #[serde(rename = "blob_gas_cost")]
     pub blob_gas_cost: U256,
     /// L1 data fee for OP-stack chains (Optimism, Base, etc.)
+    #[serde(rename = "l1_data_fee")]
     pub l1_data_fee: U256,
     /// Number of blobs in the transaction (0 for non-EIP-4844)
+    #[serde(rename = "blob_count")]
     pub blob_count: BlobCount,
     /// Blob gas price used for this transaction
+    #[serde(rename = "blob_gas_price")]
     pub blob_gas_price: BlobGasPrice,
+    /// Portion of the execution cost paid as EIP-1559 base fee
+    ///
+    /// Zero when the block's base fee wasn't looked up.
+    #[serde(default, rename = "base_fee_burned")]
+    pub base_fee_burned: U256,
+    /// Portion of the execution cost paid as priority fee to the block producer
+    ///
+    /// Zero when the block's base fee wasn't looked up.
+    #[serde(default, rename = "priority_fee_paid")]
+    pub priority_fee_paid: U256,
 }
 
 impl GasBreakdown {
@@ -555,6 +665,8 @@ impl GasBreakdown {
             l1_data_fee: U256::ZERO,
             blob_count: BlobCount::ZERO,
             blob_gas_price: BlobGasPrice::ZERO,
+            base_fee_burned: U256::ZERO,
+            priority_fee_paid: U256::ZERO,
         }
     }
 
@@ -587,6 +699,10 @@ impl GasBreakdown {
             .saturating_add(other.execution_gas_cost);
         self.blob_gas_cost = self.blob_gas_cost.saturating_add(other.blob_gas_cost);
         self.l1_data_fee = self.l1_data_fee.saturating_add(other.l1_data_fee);
+        self.base_fee_burned = self.base_fee_burned.saturating_add(other.base_fee_burned);
+        self.priority_fee_paid = self
+            .priority_fee_paid
+            .saturating_add(other.priority_fee_paid);
         // For merged results, blob_count represents total blobs across all txs
         self.blob_count = BlobCount::new(
             self.blob_count
@@ -623,6 +739,13 @@ impl std::fmt::Display for GasBreakdown {
         if self.has_l1_data_fee() {
             write!(f, ", L1 data: {} wei", self.l1_data_fee)?;
         }
+        if self.base_fee_burned > U256::ZERO || self.priority_fee_paid > U256::ZERO {
+            write!(
+                f,
+                " (base fee: {} wei, priority fee: {} wei)",
+                self.base_fee_burned, self.priority_fee_paid
+            )?;
+        }
         write!(f, ", total: {} wei", self.total_cost())
     }
 }
@@ -635,6 +758,8 @@ pub struct GasBreakdownBuilder {
     l1_data_fee: U256,
     blob_count: BlobCount,
     blob_gas_price: BlobGasPrice,
+    base_fee_burned: U256,
+    priority_fee_paid: U256,
 }
 
 impl GasBreakdownBuilder {
@@ -673,6 +798,18 @@ impl GasBreakdownBuilder {
         self
     }
 
+    /// Set the base fee portion of the execution cost
+    pub fn base_fee_burned(mut self, cost: U256) -> Self {
+        self.base_fee_burned = cost;
+        self
+    }
+
+    /// Set the priority fee portion of the execution cost
+    pub fn priority_fee_paid(mut self, cost: U256) -> Self {
+        self.priority_fee_paid = cost;
+        self
+    }
+
     /// Build the GasBreakdown
     pub fn build(self) -> GasBreakdown {
         GasBreakdown {
@@ -681,6 +818,8 @@ impl GasBreakdownBuilder {
             l1_data_fee: self.l1_data_fee,
             blob_count: self.blob_count,
             blob_gas_price: self.blob_gas_price,
+            base_fee_burned: self.base_fee_burned,
+            priority_fee_paid: self.priority_fee_paid,
         }
     }
 }
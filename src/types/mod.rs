@@ -5,19 +5,25 @@
 //! Strong types for type safety across semioscan.
 //!
 //! This module provides newtype wrappers for various domain concepts:
+//! - Addresses tagged with their role in a transfer
 //! - Wei amounts and gas calculations
 //! - Token amounts and decimals
 //! - Configuration values (block ranges, rate limits)
 //! - Fee calculations
+//! - Number formatting options for display strings
 //! - Cache metadata (timestamps, access sequences)
 //! - Price source errors (type-safe error handling without type erasure)
+//! - Data-quality warnings attached to scan results
 
+pub mod address;
 pub mod cache;
 pub mod config;
+pub mod display;
 pub mod fees;
 pub mod gas;
 pub mod price;
 pub mod tokens;
+pub mod warnings;
 pub mod wei;
 
 // Note: Public types are re-exported from lib.rs, not here
@@ -0,0 +1,193 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fiat currency values converted from USD
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::FxError;
+use crate::types::display::DisplayOptions;
+
+/// An ISO 4217 currency code, such as `USD` or `EUR`
+///
+/// Codes are three ASCII letters and are stored upper case.
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::Currency;
+///
+/// let eur: Currency = "eur".parse().unwrap();
+/// assert_eq!(eur, Currency::EUR);
+/// assert_eq!(eur.as_str(), "EUR");
+/// assert!("euro".parse::<Currency>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    /// US dollar
+    pub const USD: Self = Self(*b"USD");
+    /// Euro
+    pub const EUR: Self = Self(*b"EUR");
+    /// Pound sterling
+    pub const GBP: Self = Self(*b"GBP");
+    /// Japanese yen
+    pub const JPY: Self = Self(*b"JPY");
+    /// Swiss franc
+    pub const CHF: Self = Self(*b"CHF");
+
+    /// Parse a three-letter currency code, in either case
+    pub fn new(code: &str) -> Result<Self, FxError> {
+        match code.as_bytes() {
+            [a, b, c] if code.bytes().all(|byte| byte.is_ascii_alphabetic()) => Ok(Self([
+                a.to_ascii_uppercase(),
+                b.to_ascii_uppercase(),
+                c.to_ascii_uppercase(),
+            ])),
+            _ => Err(FxError::InvalidCurrency {
+                code: code.to_string(),
+            }),
+        }
+    }
+
+    /// The upper case code
+    pub fn as_str(&self) -> &str {
+        // Only ever built from ASCII letters
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+
+    /// The currency's sign, for the currencies that have a common one
+    pub fn symbol(&self) -> Option<&'static str> {
+        match &self.0 {
+            b"USD" => Some("$"),
+            b"EUR" => Some("€"),
+            b"GBP" => Some("£"),
+            b"JPY" => Some("¥"),
+            _ => None,
+        }
+    }
+
+    /// Digits after the decimal point in the currency's minor unit, per ISO 4217
+    ///
+    /// Two for most currencies; none for currencies such as the yen and won,
+    /// and three for the Gulf dinars and rials.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semioscan::Currency;
+    ///
+    /// assert_eq!(Currency::EUR.minor_units(), 2);
+    /// assert_eq!(Currency::JPY.minor_units(), 0);
+    /// ```
+    pub fn minor_units(&self) -> usize {
+        match &self.0 {
+            b"BIF" | b"CLP" | b"DJF" | b"GNF" | b"ISK" | b"JPY" | b"KMF" | b"KRW" | b"PYG"
+            | b"RWF" | b"UGX" | b"UYI" | b"VND" | b"VUV" | b"XAF" | b"XOF" | b"XPF" => 0,
+            b"BHD" | b"IQD" | b"JOD" | b"KWD" | b"LYD" | b"OMR" | b"TND" => 3,
+            _ => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for Currency {
+    type Err = FxError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::new(code)
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = FxError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Self::new(&code)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An amount of a fiat currency
+///
+/// Produced from a [`UsdValue`](crate::UsdValue) with
+/// [`to_fiat`](crate::UsdValue::to_fiat) or
+/// [`convert`](crate::UsdValue::convert).
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::{Currency, DisplayOptions, UsdValue};
+///
+/// let value = UsdValue::new(1200.0).to_fiat(Currency::EUR, 0.75);
+/// assert_eq!(value.amount, 900.0);
+/// assert_eq!(value.to_display(&DisplayOptions::fixed(2)), "€900.00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct FiatValue {
+    #[serde(rename = "currency")]
+    pub currency: Currency,
+    #[serde(rename = "amount")]
+    pub amount: f64,
+}
+
+impl FiatValue {
+    /// `amount` of `currency`
+    pub const fn new(currency: Currency, amount: f64) -> Self {
+        Self { currency, amount }
+    }
+
+    /// Format with the currency's sign, or its code after the amount when it
+    /// has no common sign
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        let amount = options.format_f64(self.amount);
+        match self.currency.symbol() {
+            Some(symbol) => format!("{symbol}{amount}"),
+            None => format!("{amount} {}", self.currency),
+        }
+    }
+}
+
+/// Shows the amount to the currency's [minor unit](Currency::minor_units),
+/// so yen have no decimals
+impl std::fmt::Display for FiatValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let decimals = self.currency.minor_units();
+        match self.currency.symbol() {
+            Some(symbol) => write!(f, "{symbol}{:.decimals$}", self.amount),
+            None => write!(f, "{:.decimals$} {}", self.amount, self.currency),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_rounds_to_the_currencys_minor_unit() {
+        assert_eq!(FiatValue::new(Currency::EUR, 75.0).to_string(), "€75.00");
+        assert_eq!(
+            FiatValue::new(Currency::JPY, 11_234.4).to_string(),
+            "¥11234"
+        );
+        assert_eq!(
+            FiatValue::new(Currency::new("kwd").unwrap(), 1.5).to_string(),
+            "1.500 KWD"
+        );
+        assert_eq!(FiatValue::new(Currency::CHF, 0.5).to_string(), "0.50 CHF");
+    }
+}
@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! ERC-20 token metadata
+
+use serde::{Deserialize, Serialize};
+
+use super::TokenDecimals;
+
+/// Decimals and symbol of an ERC-20 token, as read from its contract
+///
+/// # Examples
+///
+/// ```
+/// use semioscan::{TokenDecimals, TokenMetadata};
+///
+/// let usdc = TokenMetadata::new(TokenDecimals::USDC).with_symbol("USDC");
+/// assert_eq!(usdc.label(), "USDC");
+/// assert_eq!(TokenMetadata::new(TokenDecimals::STANDARD).label(), "tokens");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    /// Decimals reported by `decimals()`
+    #[serde(rename = "decimals")]
+    pub decimals: TokenDecimals,
+    /// Symbol reported by `symbol()`, if the token returns a string
+    #[serde(rename = "symbol")]
+    pub symbol: Option<String>,
+}
+
+impl TokenMetadata {
+    /// Label used by [`label`](Self::label) for tokens without a symbol
+    const UNKNOWN_SYMBOL: &'static str = "tokens";
+
+    /// Metadata of a token without a symbol
+    pub fn new(decimals: TokenDecimals) -> Self {
+        Self {
+            decimals,
+            symbol: None,
+        }
+    }
+
+    /// Set the token's symbol
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// The symbol, or `"tokens"` if the token has none
+    pub fn label(&self) -> &str {
+        self.symbol.as_deref().unwrap_or(Self::UNKNOWN_SYMBOL)
+    }
+}
@@ -19,10 +19,16 @@
 //!     | × TokenPrice
 //!     ↓
 //! UsdValue (f64, USD-denominated)
+//!     |
+//!     | × FX rate
+//!     ↓
+//! FiatValue (f64, any currency)
 //! ```
 
 mod amount;
 mod decimals;
+mod fiat;
+mod metadata;
 mod normalized;
 mod price;
 mod set;
@@ -30,6 +36,8 @@ mod usd;
 
 pub use amount::TokenAmount;
 pub use decimals::TokenDecimals;
+pub use fiat::{Currency, FiatValue};
+pub use metadata::TokenMetadata;
 pub use normalized::NormalizedAmount;
 pub use price::TokenPrice;
 pub use set::TokenSet;
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::ops::Add;
 
 use super::usd::UsdValue;
+use crate::types::display::DisplayOptions;
 
 /// Token amount normalized by decimals (human-readable)
 ///
@@ -81,6 +82,11 @@ impl NormalizedAmount {
     pub fn is_zero(&self) -> bool {
         self.0.abs() < f64::EPSILON
     }
+
+    /// Format following `options`
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        options.format_f64(self.0)
+    }
 }
 
 impl From<f64> for NormalizedAmount {
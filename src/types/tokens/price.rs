@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 
 use super::normalized::NormalizedAmount;
 use super::usd::UsdValue;
+use crate::types::display::DisplayOptions;
 
 /// Price of one token in USDC (or other stablecoin)
 ///
@@ -111,6 +112,21 @@ impl TokenPrice {
     pub fn format(&self, precision: usize) -> String {
         format!("${:.precision$}", self.0, precision = precision)
     }
+
+    /// Format as price string following `options`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semioscan::{DisplayOptions, TokenPrice};
+    ///
+    /// let price = TokenPrice::new(0.00001234);
+    /// assert_eq!(price.to_display(&DisplayOptions::fixed(6)), "$0.000012");
+    /// assert_eq!(price.to_display(&DisplayOptions::default().with_scientific(true)), "$1.234e-5");
+    /// ```
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        format!("${}", options.format_f64(self.0))
+    }
 }
 
 impl From<f64> for TokenPrice {
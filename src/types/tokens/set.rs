@@ -91,6 +91,12 @@ impl FromIterator<Address> for TokenSet {
     }
 }
 
+impl Extend<Address> for TokenSet {
+    fn extend<T: IntoIterator<Item = Address>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
 impl IntoIterator for TokenSet {
     type Item = Address;
     type IntoIter = std::collections::btree_set::IntoIter<Address>;
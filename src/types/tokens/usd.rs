@@ -4,10 +4,16 @@
 
 //! USD value type for financial calculations
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::ops::Add;
 use thiserror::Error;
 
+use super::fiat::{Currency, FiatValue};
+use crate::errors::FxError;
+use crate::price::fx::FxRate;
+use crate::types::display::DisplayOptions;
+
 /// Errors that can occur when creating a USD value
 #[derive(Debug, Error, Clone, Copy)]
 pub enum UsdValueError {
@@ -182,10 +188,59 @@ impl UsdValue {
         format!("${:.precision$}", self.0, precision = precision)
     }
 
+    /// Format as USD string following `options`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semioscan::{DisplayOptions, UsdValue};
+    ///
+    /// let value = UsdValue::new(1234.5);
+    /// assert_eq!(value.to_display(&DisplayOptions::fixed(2)), "$1234.50");
+    /// assert_eq!(
+    ///     value.to_display(&DisplayOptions::fixed(0).with_thousands_separator(',')),
+    ///     "$1,235"
+    /// );
+    /// ```
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        format!("${}", options.format_f64(self.0))
+    }
+
     /// Get absolute value
     pub fn abs(&self) -> Self {
         Self(self.0.abs())
     }
+
+    /// Convert to `currency` at `rate` units of it per US dollar
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semioscan::{Currency, UsdValue};
+    ///
+    /// let value = UsdValue::new(100.0).to_fiat(Currency::GBP, 0.75);
+    /// assert_eq!(value.amount, 75.0);
+    /// assert_eq!(value.to_string(), "£75.00");
+    /// ```
+    pub fn to_fiat(&self, currency: Currency, rate: f64) -> FiatValue {
+        FiatValue::new(currency, self.0 * rate)
+    }
+
+    /// Convert to `currency` at `fx`'s rate for `date`
+    ///
+    /// Converting to [`Currency::USD`] doesn't consult `fx`.
+    pub async fn convert<F: FxRate + ?Sized>(
+        &self,
+        currency: Currency,
+        date: NaiveDate,
+        fx: &F,
+    ) -> Result<FiatValue, FxError> {
+        if currency == Currency::USD {
+            return Ok(self.to_fiat(currency, 1.0));
+        }
+        let rate = fx.usd_rate(currency, date).await?;
+        Ok(self.to_fiat(currency, rate))
+    }
 }
 
 // Note: We intentionally do NOT implement From<f64> because it would bypass validation.
@@ -0,0 +1,238 @@
+// SPDX-FileCopyrightText: 2025 Semiotic AI, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Data-quality warnings attached to scan results
+//!
+//! Scans keep going when a single log, transaction or chunk of blocks can't be
+//! processed, so a result can be missing data without the calculation failing.
+//! Everything left out is recorded as a [`ScanWarning`] on the result, letting
+//! callers decide whether it is complete enough to use.
+
+use std::fmt::{self, Display};
+
+use alloy_primitives::{BlockNumber, TxHash};
+use alloy_rpc_types::Log;
+use serde::{Deserialize, Serialize};
+
+/// Data a scan left out of its result
+///
+/// Serialized with a `kind` tag, e.g.
+/// `{"kind":"gap","from_block":100,"to_block":199,"reason":"..."}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScanWarning {
+    /// A log was skipped because it couldn't be decoded or lacked required
+    /// fields
+    SkippedLog {
+        /// Block the log was emitted in, if the provider returned it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_number: Option<BlockNumber>,
+        /// Transaction that emitted the log, if the provider returned it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tx_hash: Option<TxHash>,
+        /// Position of the log in its block, if the provider returned it
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        log_index: Option<u64>,
+        /// Why the log was skipped
+        reason: String,
+    },
+    /// A transaction was skipped because it or its receipt couldn't be
+    /// fetched
+    FailedReceipt {
+        /// The skipped transaction
+        tx_hash: TxHash,
+        /// Block of the transaction, when known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_number: Option<BlockNumber>,
+        /// Why the lookup failed
+        reason: String,
+    },
+    /// Blocks whose logs couldn't be fetched, so they were never scanned
+    ///
+    /// Consecutive failed chunks are coalesced into one gap.
+    Gap {
+        /// First unscanned block
+        from_block: BlockNumber,
+        /// Last unscanned block (inclusive)
+        to_block: BlockNumber,
+        /// Why the first chunk of the gap failed
+        reason: String,
+    },
+    /// A block's base fee couldn't be fetched, so its transactions are
+    /// reported without a base/priority fee split
+    MissingBaseFee {
+        /// Block whose base fee is missing
+        block_number: BlockNumber,
+        /// Why the lookup failed
+        reason: String,
+    },
+}
+
+impl ScanWarning {
+    /// A [`SkippedLog`](Self::SkippedLog) warning for `log`
+    pub fn skipped_log(log: &Log, reason: impl Display) -> Self {
+        Self::SkippedLog {
+            block_number: log.block_number,
+            tx_hash: log.transaction_hash,
+            log_index: log.log_index,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// A [`FailedReceipt`](Self::FailedReceipt) warning for `tx_hash`
+    pub fn failed_receipt(
+        tx_hash: TxHash,
+        block_number: Option<BlockNumber>,
+        reason: impl Display,
+    ) -> Self {
+        Self::FailedReceipt {
+            tx_hash,
+            block_number,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// A [`Gap`](Self::Gap) warning for blocks `from_block..=to_block`
+    pub fn gap(from_block: BlockNumber, to_block: BlockNumber, reason: impl Display) -> Self {
+        Self::Gap {
+            from_block,
+            to_block,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// A [`MissingBaseFee`](Self::MissingBaseFee) warning for `block_number`
+    pub fn missing_base_fee(block_number: BlockNumber, reason: impl Display) -> Self {
+        Self::MissingBaseFee {
+            block_number,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ScanWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanWarning::SkippedLog {
+                block_number,
+                tx_hash,
+                log_index,
+                reason,
+            } => {
+                write!(f, "skipped log")?;
+                if let Some(log_index) = log_index {
+                    write!(f, " {log_index}")?;
+                }
+                if let Some(tx_hash) = tx_hash {
+                    write!(f, " of transaction {tx_hash}")?;
+                }
+                if let Some(block_number) = block_number {
+                    write!(f, " in block {block_number}")?;
+                }
+                write!(f, ": {reason}")
+            }
+            ScanWarning::FailedReceipt {
+                tx_hash,
+                block_number,
+                reason,
+            } => {
+                write!(f, "failed lookup of transaction {tx_hash}")?;
+                if let Some(block_number) = block_number {
+                    write!(f, " in block {block_number}")?;
+                }
+                write!(f, ": {reason}")
+            }
+            ScanWarning::Gap {
+                from_block,
+                to_block,
+                reason,
+            } => write!(f, "unscanned blocks {from_block}-{to_block}: {reason}"),
+            ScanWarning::MissingBaseFee {
+                block_number,
+                reason,
+            } => write!(f, "missing base fee of block {block_number}: {reason}"),
+        }
+    }
+}
+
+/// Append `warning` to `warnings`
+///
+/// A gap that starts right after the last warning's gap extends it instead,
+/// and a warning already present is dropped, so merging overlapping results
+/// doesn't repeat warnings.
+pub(crate) fn push_warning(warnings: &mut Vec<ScanWarning>, warning: ScanWarning) {
+    if let (
+        Some(ScanWarning::Gap {
+            from_block: last_from,
+            to_block: last_to,
+            ..
+        }),
+        ScanWarning::Gap {
+            from_block,
+            to_block,
+            ..
+        },
+    ) = (warnings.last_mut(), &warning)
+    {
+        if *from_block >= *last_from && *from_block <= last_to.saturating_add(1) {
+            *last_to = (*last_to).max(*to_block);
+            return;
+        }
+    }
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
+
+/// Append each of `other` to `warnings` with [`push_warning`]
+pub(crate) fn extend_warnings<'a>(
+    warnings: &mut Vec<ScanWarning>,
+    other: impl IntoIterator<Item = &'a ScanWarning>,
+) {
+    for warning in other {
+        push_warning(warnings, warning.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consecutive_gaps_are_coalesced() {
+        let mut warnings = Vec::new();
+        push_warning(&mut warnings, ScanWarning::gap(100, 199, "timeout"));
+        push_warning(&mut warnings, ScanWarning::gap(200, 299, "timeout"));
+        push_warning(&mut warnings, ScanWarning::gap(150, 250, "timeout"));
+        assert_eq!(warnings, vec![ScanWarning::gap(100, 299, "timeout")]);
+
+        // A scanned block in between starts a new gap
+        push_warning(&mut warnings, ScanWarning::gap(301, 400, "timeout"));
+        assert_eq!(warnings.len(), 2);
+
+        // Repeats are dropped
+        let skipped = ScanWarning::failed_receipt(TxHash::ZERO, Some(5), "not found");
+        extend_warnings(&mut warnings, &[skipped.clone(), skipped]);
+        assert_eq!(warnings.len(), 3);
+    }
+
+    #[test]
+    fn warnings_serialize_with_a_kind_tag() {
+        let json = serde_json::to_value(ScanWarning::gap(1, 2, "limit")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"kind": "gap", "from_block": 1, "to_block": 2, "reason": "limit"})
+        );
+
+        let skipped = ScanWarning::SkippedLog {
+            block_number: Some(7),
+            tx_hash: None,
+            log_index: None,
+            reason: "bad data".into(),
+        };
+        let json = serde_json::to_string(&skipped).unwrap();
+        assert!(!json.contains("tx_hash"));
+        assert_eq!(serde_json::from_str::<ScanWarning>(&json).unwrap(), skipped);
+    }
+}
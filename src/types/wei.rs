@@ -9,7 +9,16 @@
 
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
-use std::ops::Add;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub};
+
+use crate::types::display::DisplayOptions;
+
+/// Number of wei in one gwei
+pub(crate) const GWEI_DECIMALS: u8 = 9;
+
+/// Number of wei in one ether
+const ETHER_DECIMALS: u8 = 18;
 
 /// Represents an amount of native currency (ETH, MATIC, etc.) in wei
 ///
@@ -103,6 +112,101 @@ impl WeiAmount {
         self.0.to_string().parse::<f64>().unwrap_or(0.0)
             / eth_divisor.to_string().parse::<f64>().unwrap_or(1.0)
     }
+
+    /// Checked addition. Returns `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Checked subtraction. Returns `None` if `rhs` is larger than `self`.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Checked multiplication by a scalar. Returns `None` on overflow.
+    pub fn checked_mul(self, rhs: u64) -> Option<Self> {
+        self.0.checked_mul(U256::from(rhs)).map(Self)
+    }
+
+    /// Checked division by a scalar. Returns `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: u64) -> Option<Self> {
+        self.0.checked_div(U256::from(rhs)).map(Self)
+    }
+
+    /// Saturating addition, clamping at `U256::MAX`
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating subtraction, clamping at zero
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Saturating multiplication by a scalar, clamping at `U256::MAX`
+    pub fn saturating_mul(self, rhs: u64) -> Self {
+        Self(self.0.saturating_mul(U256::from(rhs)))
+    }
+
+    /// Format as an exact decimal gwei string with trailing zeros removed
+    ///
+    /// Unlike [`to_gwei`](Self::to_gwei), this is lossless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_primitives::U256;
+    /// use semioscan::WeiAmount;
+    ///
+    /// assert_eq!(WeiAmount::new(U256::from(1_500_000_000u64)).to_gwei_string(), "1.5");
+    /// assert_eq!(WeiAmount::new(U256::from(1u64)).to_gwei_string(), "0.000000001");
+    /// ```
+    pub fn to_gwei_string(&self) -> String {
+        format_units(self.0, GWEI_DECIMALS)
+    }
+
+    /// Format as an exact decimal ether string with trailing zeros removed
+    ///
+    /// Unlike [`to_ether`](Self::to_ether), this is lossless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_primitives::U256;
+    /// use semioscan::WeiAmount;
+    ///
+    /// let amount = WeiAmount::new(U256::from(1_500_000_000_000_000_000u128));
+    /// assert_eq!(amount.to_eth_string(), "1.5");
+    /// assert_eq!(WeiAmount::ZERO.to_eth_string(), "0");
+    /// ```
+    pub fn to_eth_string(&self) -> String {
+        format_units(self.0, ETHER_DECIMALS)
+    }
+
+    /// Format as a decimal ether string following `options`
+    ///
+    /// Rounding is exact, without converting through a float.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use alloy_primitives::U256;
+    /// use semioscan::{DisplayOptions, WeiAmount};
+    ///
+    /// let amount = WeiAmount::new(U256::from(1_500_000_000_000_000_000u128));
+    /// assert_eq!(amount.to_display(&DisplayOptions::fixed(4)), "1.5000");
+    /// ```
+    pub fn to_display(&self, options: &DisplayOptions) -> String {
+        options.format_units(self.0, ETHER_DECIMALS)
+    }
+}
+
+/// Format a fixed-point integer as an exact decimal string
+///
+/// Trailing fractional zeros are trimmed, and the decimal point is omitted
+/// entirely for whole values.
+pub(crate) fn format_units(value: U256, decimals: u8) -> String {
+    DisplayOptions::default().format_units(value, decimals)
 }
 
 impl From<u64> for WeiAmount {
@@ -125,13 +229,52 @@ impl Add for WeiAmount {
     }
 }
 
+impl AddAssign for WeiAmount {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for WeiAmount {
+    type Output = Self;
+
+    /// Saturating subtraction, clamping at zero
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl Sum for WeiAmount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a WeiAmount> for WeiAmount {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl From<WeiAmount> for U256 {
+    fn from(value: WeiAmount) -> Self {
+        value.0
+    }
+}
+
+/// Formats dust in wei and anything larger in whole units of the native
+/// currency, without a currency symbol
+///
+/// The symbol depends on the chain, which a bare amount doesn't know; use
+/// [`DisplayValue::native`](crate::DisplayValue::native) for a string tagged
+/// with it.
 impl std::fmt::Display for WeiAmount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let eth = self.to_ether();
         if eth < 0.000001 {
             write!(f, "{} wei", self.0)
         } else {
-            write!(f, "{:.6} ETH", eth)
+            write!(f, "{:.6}", eth)
         }
     }
 }
@@ -202,9 +345,7 @@ mod tests {
     fn test_display_large_amount() {
         // 0.01 ETH in wei
         let amount = WeiAmount::new(U256::from(10_000_000_000_000_000u64));
-        let display = format!("{}", amount);
-        assert!(display.contains("0.01"));
-        assert!(display.contains("ETH"));
+        assert_eq!(format!("{}", amount), "0.010000");
     }
 
     #[test]
@@ -238,6 +379,88 @@ mod tests {
         assert!(small < large);
     }
 
+    #[test]
+    fn test_checked_arithmetic() {
+        let a = WeiAmount::new(U256::from(500));
+        let b = WeiAmount::new(U256::from(300));
+
+        assert_eq!(a.checked_add(b), Some(WeiAmount::new(U256::from(800))));
+        assert_eq!(a.checked_sub(b), Some(WeiAmount::new(U256::from(200))));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(a.checked_mul(3), Some(WeiAmount::new(U256::from(1500))));
+        assert_eq!(a.checked_div(2), Some(WeiAmount::new(U256::from(250))));
+        assert_eq!(a.checked_div(0), None);
+        assert_eq!(WeiAmount::new(U256::MAX).checked_add(a), None);
+        assert_eq!(WeiAmount::new(U256::MAX).checked_mul(2), None);
+    }
+
+    #[test]
+    fn test_saturating_subtraction() {
+        let a = WeiAmount::new(U256::from(500));
+        let b = WeiAmount::new(U256::from(300));
+
+        assert_eq!(a - b, WeiAmount::new(U256::from(200)));
+        assert_eq!(b - a, WeiAmount::ZERO);
+        assert_eq!(
+            WeiAmount::new(U256::MAX).saturating_mul(2),
+            WeiAmount::new(U256::MAX)
+        );
+    }
+
+    #[test]
+    fn test_add_assign_and_sum() {
+        let mut total = WeiAmount::ZERO;
+        total += WeiAmount::from(100u64);
+        total += WeiAmount::from(50u64);
+        assert_eq!(total, WeiAmount::from(150u64));
+
+        let amounts = [
+            WeiAmount::from(1u64),
+            WeiAmount::from(2u64),
+            WeiAmount::from(3u64),
+        ];
+        assert_eq!(amounts.iter().sum::<WeiAmount>(), WeiAmount::from(6u64));
+        assert_eq!(
+            amounts.into_iter().sum::<WeiAmount>(),
+            WeiAmount::from(6u64)
+        );
+    }
+
+    #[test]
+    fn test_to_eth_string() {
+        assert_eq!(WeiAmount::ZERO.to_eth_string(), "0");
+        assert_eq!(
+            WeiAmount::from(1_000_000_000_000_000_000u64).to_eth_string(),
+            "1"
+        );
+        assert_eq!(
+            WeiAmount::from(1_500_000_000_000_000_000u64).to_eth_string(),
+            "1.5"
+        );
+        assert_eq!(
+            WeiAmount::from(1u64).to_eth_string(),
+            "0.000000000000000001"
+        );
+    }
+
+    #[test]
+    fn test_to_eth_string_is_exact_for_large_values() {
+        // Beyond f64 precision: 123456789.123456789123456789 ETH
+        let wei = U256::from_str_radix("123456789123456789123456789", 10).unwrap();
+        assert_eq!(
+            WeiAmount::new(wei).to_eth_string(),
+            "123456789.123456789123456789"
+        );
+    }
+
+    #[test]
+    fn test_to_gwei_string() {
+        assert_eq!(WeiAmount::ZERO.to_gwei_string(), "0");
+        assert_eq!(WeiAmount::from(5_000_000_000u64).to_gwei_string(), "5");
+        assert_eq!(WeiAmount::from(1_250_000_000u64).to_gwei_string(), "1.25");
+        assert_eq!(WeiAmount::from(42u64).to_gwei_string(), "0.000000042");
+    }
+
     #[test]
     fn test_gas_cost_scenario() {
         // 100,000 gas at 50 gwei = 0.005 ETH
@@ -322,6 +322,7 @@ fn test_chain_config_with_only_rate_limit() {
         rate_limit_delay: Some(Duration::from_millis(250)),
         rpc_timeout: None,
         serial_lookup_fallback_attempts: None,
+        operation_deadline: None,
     };
 
     assert!(config.rate_limit_delay.is_some());
@@ -336,6 +337,7 @@ fn test_chain_config_with_only_max_blocks() {
         rate_limit_delay: None,
         rpc_timeout: None,
         serial_lookup_fallback_attempts: None,
+        operation_deadline: None,
     };
 
     assert!(config.max_block_range.is_some());
@@ -350,6 +352,7 @@ fn test_chain_config_with_both_settings() {
         rate_limit_delay: Some(Duration::from_millis(250)),
         rpc_timeout: None,
         serial_lookup_fallback_attempts: None,
+        operation_deadline: None,
     };
 
     assert_eq!(config.max_block_range, Some(MaxBlockRange::new(1000)));